@@ -0,0 +1,55 @@
+use std::ffi::CString;
+
+use objc::{class, msg_send, rc::autoreleasepool, runtime::Object, sel, sel_impl};
+use tauri::AppHandle;
+use tracing::warn;
+
+const NS_ACCESSIBILITY_PRIORITY_HIGH: isize = 90;
+
+#[link(name = "AppKit", kind = "framework")]
+unsafe extern "C" {
+    static NSAccessibilityAnnouncementRequestedNotification: *mut Object;
+    static NSAccessibilityAnnouncementKey: *mut Object;
+    static NSAccessibilityPriorityKey: *mut Object;
+
+    fn NSAccessibilityPostNotificationWithUserInfo(
+        element: *mut Object,
+        notification: *mut Object,
+        user_info: *mut Object,
+    );
+}
+
+pub(super) fn post_announcement(app: &AppHandle, message: &str) {
+    let Ok(message) = CString::new(message) else {
+        return;
+    };
+    if let Err(error) = app.run_on_main_thread(move || unsafe { announce(&message) }) {
+        warn!(%error, "failed to post VoiceOver announcement on the main thread");
+    }
+}
+
+#[allow(unexpected_cfgs)]
+unsafe fn announce(message: &CString) {
+    autoreleasepool(|| {
+        let application: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+        let text: *mut Object = msg_send![class!(NSString), stringWithUTF8String: message.as_ptr()];
+        if application.is_null() || text.is_null() {
+            return;
+        }
+        let priority: *mut Object =
+            msg_send![class!(NSNumber), numberWithInteger: NS_ACCESSIBILITY_PRIORITY_HIGH];
+        let keys = [NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey];
+        let values = [text, priority];
+        let user_info: *mut Object = msg_send![
+            class!(NSDictionary),
+            dictionaryWithObjects: values.as_ptr()
+            forKeys: keys.as_ptr()
+            count: keys.len()
+        ];
+        NSAccessibilityPostNotificationWithUserInfo(
+            application,
+            NSAccessibilityAnnouncementRequestedNotification,
+            user_info,
+        );
+    });
+}
@@ -0,0 +1,70 @@
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{debug, warn};
+
+use crate::{status_notifier::AppStatus, AppState};
+
+pub const EVENT_ACCESSIBILITY_ANNOUNCEMENT: &str = "voice://accessibility-announcement";
+
+pub fn status_announcement(status: AppStatus) -> Option<&'static str> {
+    match status {
+        AppStatus::Listening => Some("Listening"),
+        AppStatus::Transcribing => Some("Transcribing"),
+        AppStatus::NothingHeard => Some("Nothing heard"),
+        AppStatus::Idle | AppStatus::Error => None,
+    }
+}
+
+pub fn announce(app: &AppHandle, message: &str) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if !state
+        .services
+        .settings_store
+        .current()
+        .screen_reader_announcements
+    {
+        return;
+    }
+
+    debug!(message, "posting screen reader announcement");
+    post_announcement(app, message);
+    if let Err(error) = app.emit(EVENT_ACCESSIBILITY_ANNOUNCEMENT, message) {
+        warn!(%error, "failed to emit accessibility announcement event");
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn post_announcement(app: &AppHandle, message: &str) {
+    macos::post_announcement(app, message);
+}
+
+#[cfg(target_os = "windows")]
+fn post_announcement(app: &AppHandle, message: &str) {
+    windows::post_announcement(app, message);
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn post_announcement(_app: &AppHandle, _message: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::status_announcement;
+    use crate::status_notifier::AppStatus;
+
+    #[test]
+    fn announces_active_statuses_but_not_idle() {
+        assert_eq!(status_announcement(AppStatus::Listening), Some("Listening"));
+        assert_eq!(
+            status_announcement(AppStatus::NothingHeard),
+            Some("Nothing heard")
+        );
+        assert_eq!(status_announcement(AppStatus::Idle), None);
+        assert_eq!(status_announcement(AppStatus::Error), None);
+    }
+}
@@ -0,0 +1,95 @@
+use std::ffi::c_void;
+
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+type Hwnd = *mut c_void;
+type Bstr = *mut u16;
+
+const NOTIFICATION_KIND_OTHER: i32 = 4;
+const NOTIFICATION_PROCESSING_IMPORTANT_MOST_RECENT: i32 = 1;
+const ACTIVITY_ID: &str = "buzz-status";
+
+#[repr(C)]
+struct UnknownVtbl {
+    query_interface: usize,
+    add_ref: usize,
+    release: unsafe extern "system" fn(*mut c_void) -> u32,
+}
+
+#[link(name = "uiautomationcore")]
+unsafe extern "system" {
+    fn UiaHostProviderFromHwnd(hwnd: Hwnd, provider: *mut *mut c_void) -> i32;
+    fn UiaRaiseNotificationEvent(
+        provider: *mut c_void,
+        kind: i32,
+        processing: i32,
+        display_string: Bstr,
+        activity_id: Bstr,
+    ) -> i32;
+}
+
+#[link(name = "oleaut32")]
+unsafe extern "system" {
+    fn SysAllocString(value: *const u16) -> Bstr;
+    fn SysFreeString(value: Bstr);
+}
+
+pub(super) fn post_announcement(app: &AppHandle, message: &str) {
+    let Some(window) = app
+        .get_webview_window(crate::window_manager::MAIN_WINDOW_LABEL)
+        .or_else(|| app.webview_windows().into_values().next())
+    else {
+        return;
+    };
+    let hwnd = match window.hwnd() {
+        Ok(hwnd) => hwnd.0 as usize,
+        Err(error) => {
+            warn!(%error, "failed to access window handle for announcement");
+            return;
+        }
+    };
+    let message = message.to_string();
+    if let Err(error) = app.run_on_main_thread(move || unsafe {
+        raise_notification(hwnd as Hwnd, &message);
+    }) {
+        warn!(%error, "failed to raise UI Automation notification on the main thread");
+    }
+}
+
+unsafe fn raise_notification(hwnd: Hwnd, message: &str) {
+    let mut provider: *mut c_void = std::ptr::null_mut();
+    let result = UiaHostProviderFromHwnd(hwnd, &mut provider);
+    if result < 0 || provider.is_null() {
+        warn!(
+            hresult = result,
+            "failed to get UI Automation provider for announcement"
+        );
+        return;
+    }
+
+    let display = SysAllocString(wide(message).as_ptr());
+    let activity = SysAllocString(wide(ACTIVITY_ID).as_ptr());
+    let result = UiaRaiseNotificationEvent(
+        provider,
+        NOTIFICATION_KIND_OTHER,
+        NOTIFICATION_PROCESSING_IMPORTANT_MOST_RECENT,
+        display,
+        activity,
+    );
+    if result < 0 {
+        warn!(
+            hresult = result,
+            "failed to raise UI Automation notification"
+        );
+    }
+    SysFreeString(display);
+    SysFreeString(activity);
+
+    let vtbl = *(provider as *mut *const UnknownVtbl);
+    ((*vtbl).release)(provider);
+}
+
+fn wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
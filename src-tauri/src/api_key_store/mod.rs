@@ -1,17 +1,15 @@
 use std::{
     collections::HashMap,
-    fs,
-    io::Write,
-    path::{Path, PathBuf},
+    path::PathBuf,
     sync::{Arc, Mutex},
-    time::{SystemTime, UNIX_EPOCH},
 };
 use tracing::{debug, info};
 
-use crate::settings_store::DEFAULT_TRANSCRIPTION_PROVIDER;
+use crate::{persistence::JsonDocumentStore, settings_store::DEFAULT_TRANSCRIPTION_PROVIDER};
 
 const API_KEY_STORE_NAMESPACE: &str = "voice.transcription.api-keys";
 const API_KEYS_FILE_NAME: &str = "api_keys.json";
+const PROXY_PASSWORD_ACCOUNT: &str = "proxy";
 
 #[derive(Debug, Clone)]
 pub struct ApiKeyStore {
@@ -39,17 +37,7 @@ impl ApiKeyStore {
 
     pub fn get_api_key(&self, provider: &str) -> Result<Option<String>, String> {
         let account = normalize_provider(provider)?;
-        if let Some(cached) = self.get_cached_api_key(account.as_str())? {
-            debug!(provider = %account, "api key served from in-memory cache");
-            return Ok(cached);
-        }
-
-        debug!(provider = %account, "reading api key from store");
-        let key = self
-            .backend
-            .get(API_KEY_STORE_NAMESPACE, account.as_str())?;
-        self.set_cached_api_key(account.as_str(), key.clone())?;
-        Ok(key)
+        self.get_secret(account.as_str())
     }
 
     pub fn has_api_key(&self, provider: &str) -> Result<bool, String> {
@@ -59,21 +47,56 @@ impl ApiKeyStore {
     pub fn set_api_key(&self, provider: &str, key: &str) -> Result<(), String> {
         let account = normalize_provider(provider)?;
         let normalized_key = normalize_api_key(key)?;
-        info!(provider = %account, "writing api key to store");
-        self.backend.set(
-            API_KEY_STORE_NAMESPACE,
-            account.as_str(),
-            normalized_key.as_str(),
-        )?;
-        self.set_cached_api_key(account.as_str(), Some(normalized_key))
+        self.set_secret(account.as_str(), normalized_key)
     }
 
     pub fn delete_api_key(&self, provider: &str) -> Result<(), String> {
         let account = normalize_provider(provider)?;
-        info!(provider = %account, "deleting api key from store");
+        self.delete_secret(account.as_str())
+    }
+
+    pub fn get_proxy_password(&self) -> Result<Option<String>, String> {
+        self.get_secret(PROXY_PASSWORD_ACCOUNT)
+    }
+
+    pub fn has_proxy_password(&self) -> Result<bool, String> {
+        Ok(self.get_proxy_password()?.is_some())
+    }
+
+    pub fn set_proxy_password(&self, password: &str) -> Result<(), String> {
+        if password.trim().is_empty() {
+            return Err("`password` cannot be empty".to_string());
+        }
+        self.set_secret(PROXY_PASSWORD_ACCOUNT, password.to_string())
+    }
+
+    pub fn delete_proxy_password(&self) -> Result<(), String> {
+        self.delete_secret(PROXY_PASSWORD_ACCOUNT)
+    }
+
+    fn get_secret(&self, account: &str) -> Result<Option<String>, String> {
+        if let Some(cached) = self.get_cached_api_key(account)? {
+            debug!(provider = %account, "api key served from in-memory cache");
+            return Ok(cached);
+        }
+
+        debug!(provider = %account, "reading api key from store");
+        let key = self.backend.get(API_KEY_STORE_NAMESPACE, account)?;
+        self.set_cached_api_key(account, key.clone())?;
+        Ok(key)
+    }
+
+    fn set_secret(&self, account: &str, secret: String) -> Result<(), String> {
+        info!(provider = %account, "writing api key to store");
         self.backend
-            .delete(API_KEY_STORE_NAMESPACE, account.as_str())?;
-        self.clear_cached_api_key(account.as_str())
+            .set(API_KEY_STORE_NAMESPACE, account, secret.as_str())?;
+        self.set_cached_api_key(account, Some(secret))
+    }
+
+    fn delete_secret(&self, account: &str) -> Result<(), String> {
+        info!(provider = %account, "deleting api key from store");
+        self.backend.delete(API_KEY_STORE_NAMESPACE, account)?;
+        self.clear_cached_api_key(account)
     }
 
     fn get_cached_api_key(&self, provider: &str) -> Result<Option<Option<String>>, String> {
@@ -111,151 +134,38 @@ trait ApiKeyBackend: Send + Sync + std::fmt::Debug {
 
 #[derive(Debug)]
 struct FileBackend {
-    file_path: PathBuf,
-    io_lock: Mutex<()>,
+    document: JsonDocumentStore<HashMap<String, String>>,
 }
 
 impl FileBackend {
     fn new(file_path: PathBuf) -> Self {
         Self {
-            file_path,
-            io_lock: Mutex::new(()),
-        }
-    }
-
-    fn ensure_file_exists(&self) -> Result<(), String> {
-        if let Some(parent_dir) = self.file_path.parent() {
-            fs::create_dir_all(parent_dir).map_err(|error| {
-                format!(
-                    "Failed to create API key directory `{}`: {error}",
-                    parent_dir.display()
-                )
-            })?;
-        }
-
-        if self.file_path.exists() {
-            return Ok(());
+            document: JsonDocumentStore::new(file_path, "API key").without_recovery(),
         }
-
-        write_atomic_file(&self.file_path, br#"{}"#)
-    }
-
-    fn read_keys(&self) -> Result<HashMap<String, String>, String> {
-        self.ensure_file_exists()?;
-        let raw_contents = fs::read_to_string(&self.file_path).map_err(|error| {
-            format!(
-                "Failed to read API key file `{}`: {error}",
-                self.file_path.display()
-            )
-        })?;
-
-        if raw_contents.trim().is_empty() {
-            return Ok(HashMap::new());
-        }
-
-        serde_json::from_str(&raw_contents).map_err(|error| {
-            format!(
-                "Failed to parse API key file `{}`: {error}",
-                self.file_path.display()
-            )
-        })
-    }
-
-    fn write_keys(&self, keys: &HashMap<String, String>) -> Result<(), String> {
-        let serialized = serde_json::to_vec_pretty(keys)
-            .map_err(|error| format!("Failed to serialize API keys: {error}"))?;
-        write_atomic_file(&self.file_path, &serialized)
     }
 }
 
 impl ApiKeyBackend for FileBackend {
     fn get(&self, _service: &str, account: &str) -> Result<Option<String>, String> {
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "api key backend lock poisoned".to_string())?;
-        let keys = self.read_keys()?;
+        let _guard = self.document.lock()?;
+        self.document.ensure_exists()?;
+        let keys = self.document.read()?;
         Ok(normalize_optional_string(keys.get(account).cloned()))
     }
 
     fn set(&self, _service: &str, account: &str, key: &str) -> Result<(), String> {
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "api key backend lock poisoned".to_string())?;
-        let mut keys = self.read_keys()?;
-        keys.insert(account.to_string(), key.to_string());
-        self.write_keys(&keys)
+        self.document.update(|keys| {
+            keys.insert(account.to_string(), key.to_string());
+            Ok(())
+        })
     }
 
     fn delete(&self, _service: &str, account: &str) -> Result<(), String> {
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "api key backend lock poisoned".to_string())?;
-        let mut keys = self.read_keys()?;
-        if keys.remove(account).is_some() {
-            return self.write_keys(&keys);
-        }
-
-        Ok(())
-    }
-}
-
-fn write_atomic_file(file_path: &Path, contents: &[u8]) -> Result<(), String> {
-    let temp_path = temp_file_path_for(file_path);
-    let mut temp_file = fs::OpenOptions::new()
-        .create_new(true)
-        .write(true)
-        .open(&temp_path)
-        .map_err(|error| {
-            format!(
-                "Failed to create temp API key file `{}`: {error}",
-                temp_path.display()
-            )
-        })?;
-
-    if let Err(error) = temp_file.write_all(contents) {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Failed to write temp API key file `{}`: {error}",
-            temp_path.display()
-        ));
-    }
-
-    if let Err(error) = temp_file.sync_all() {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Failed to flush temp API key file `{}`: {error}",
-            temp_path.display()
-        ));
+        self.document.update(|keys| {
+            keys.remove(account);
+            Ok(())
+        })
     }
-
-    drop(temp_file);
-
-    fs::rename(&temp_path, file_path).map_err(|error| {
-        let _ = fs::remove_file(&temp_path);
-        format!(
-            "Failed to finalize API key file `{}`: {error}",
-            file_path.display()
-        )
-    })?;
-
-    Ok(())
-}
-
-fn temp_file_path_for(file_path: &Path) -> PathBuf {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or(API_KEYS_FILE_NAME);
-    let pid = std::process::id();
-
-    file_path.with_file_name(format!(".{file_name}.{pid}.{timestamp}.tmp"))
 }
 
 fn normalize_provider(provider: &str) -> Result<String, String> {
@@ -449,6 +359,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn proxy_password_is_stored_apart_from_provider_keys() {
+        let store = ApiKeyStore::with_backend(Arc::new(InMemoryBackend::default()));
+
+        assert!(store.set_proxy_password("  ").is_err());
+        store
+            .set_proxy_password("hunter2")
+            .expect("set should succeed");
+        assert!(store.has_proxy_password().expect("has should succeed"));
+        assert_eq!(
+            store
+                .get_proxy_password()
+                .expect("get should succeed")
+                .as_deref(),
+            Some("hunter2")
+        );
+        assert!(store.get_api_key("proxy").is_err());
+        assert_eq!(
+            store.get_api_key("openai").expect("get should succeed"),
+            None
+        );
+
+        store
+            .delete_proxy_password()
+            .expect("delete should succeed");
+        assert!(!store.has_proxy_password().expect("has should succeed"));
+    }
+
     #[test]
     fn rejects_blank_provider_or_key_values() {
         let store = ApiKeyStore::with_backend(Arc::new(InMemoryBackend::default()));
@@ -0,0 +1,120 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    accessibility_announcer,
+    hotkey_service::{frontmost_application, FrontmostApplication},
+    settings_store::VoiceSettings,
+};
+
+pub const EVENT_DICTATION_BLOCKED: &str = "voice://dictation-blocked";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockedAction {
+    Recording,
+    Insertion,
+}
+
+impl BlockedAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Recording => "recording",
+            Self::Insertion => "insertion",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationBlockedEvent {
+    pub action: &'static str,
+    pub app: String,
+    pub message: String,
+}
+
+pub fn blocked_app_name(
+    frontmost: &FrontmostApplication,
+    blocked_apps: &[String],
+) -> Option<String> {
+    let entry = blocked_apps.iter().find(|entry| frontmost.matches(entry))?;
+    Some(
+        frontmost
+            .name
+            .clone()
+            .or_else(|| frontmost.bundle_id.clone())
+            .unwrap_or_else(|| entry.clone()),
+    )
+}
+
+pub fn blocked_message(action: BlockedAction, app_name: &str) -> String {
+    match action {
+        BlockedAction::Recording => {
+            format!("Dictation is turned off in {app_name}, so recording did not start")
+        }
+        BlockedAction::Insertion => {
+            format!("Dictation is turned off in {app_name}, so the text was not inserted")
+        }
+    }
+}
+
+pub fn ensure_allowed(
+    app: &AppHandle,
+    settings: &VoiceSettings,
+    action: BlockedAction,
+) -> Result<(), String> {
+    if settings.blocked_apps.is_empty() {
+        return Ok(());
+    }
+    let Some(app_name) = frontmost_application()
+        .and_then(|frontmost| blocked_app_name(&frontmost, &settings.blocked_apps))
+    else {
+        return Ok(());
+    };
+
+    let message = blocked_message(action, &app_name);
+    info!(action = action.as_str(), app = %app_name, "dictation blocked in frontmost app");
+    accessibility_announcer::announce(app, &message);
+    let payload = DictationBlockedEvent {
+        action: action.as_str(),
+        app: app_name,
+        message: message.clone(),
+    };
+    if let Err(error) = app.emit(EVENT_DICTATION_BLOCKED, payload) {
+        warn!(%error, "failed to emit dictation blocked event");
+    }
+    Err(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::blocked_app_name;
+    use crate::hotkey_service::FrontmostApplication;
+
+    #[test]
+    fn names_the_blocked_app_the_way_the_user_knows_it() {
+        let blocked = vec!["com.agilebits.onepassword7".to_string()];
+        let password_manager = FrontmostApplication {
+            bundle_id: Some("com.agilebits.onepassword7".to_string()),
+            name: Some("1Password 7".to_string()),
+        };
+        let unnamed = FrontmostApplication {
+            bundle_id: Some("com.agilebits.onepassword7".to_string()),
+            name: None,
+        };
+        let editor = FrontmostApplication {
+            bundle_id: Some("com.microsoft.VSCode".to_string()),
+            name: Some("Code".to_string()),
+        };
+
+        assert_eq!(
+            blocked_app_name(&password_manager, &blocked).as_deref(),
+            Some("1Password 7")
+        );
+        assert_eq!(
+            blocked_app_name(&unnamed, &blocked).as_deref(),
+            Some("com.agilebits.onepassword7")
+        );
+        assert_eq!(blocked_app_name(&editor, &blocked), None);
+    }
+}
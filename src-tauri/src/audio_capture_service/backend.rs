@@ -0,0 +1,877 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    StreamConfig,
+};
+use tracing::{debug, info, warn};
+
+use super::{
+    build_input_stream, enumerate_input_devices,
+    input_profile::{InputProcessor, InputProfiles},
+    prefer_default_device_handle, process_input_frames, select_input_device,
+    standby::{pre_roll_sample_count, retain_latest_samples},
+    AudioInputChunk, AudioInputChunkCallback, AudioLevel, AudioLevelMeter, EnumeratedInputDevice,
+    MicrophoneInfo, MicrophoneTransport, RecordingRuntime, StreamController,
+};
+
+const PLAYBACK_CHUNK_INTERVAL: Duration = Duration::from_millis(20);
+
+// The stream never leaves the capture worker thread, so backends may return handles that
+// are not `Send`.
+pub trait AudioBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn list_input_devices(&self) -> Result<Vec<MicrophoneInfo>, String>;
+    fn open_input_stream(
+        &self,
+        preferred_device_id: Option<&str>,
+        sink: CaptureSink,
+    ) -> Result<OpenedInputStream, String>;
+}
+
+pub struct OpenedInputStream {
+    pub stream: Box<dyn StreamController>,
+    pub runtime: RecordingRuntime,
+    pub stream_error_rx: Receiver<String>,
+}
+
+#[derive(Clone)]
+pub struct CaptureSink {
+    samples: Arc<Mutex<Vec<i16>>>,
+    level_meter: Arc<AudioLevelMeter>,
+    on_input_chunk: Arc<Mutex<Option<AudioInputChunkCallback>>>,
+    standby: Arc<AtomicBool>,
+    pre_roll_ms: Arc<AtomicU64>,
+    input_profiles: Arc<Mutex<InputProfiles>>,
+    processor: Arc<Mutex<InputProcessor>>,
+    prefer_built_in_microphone: Arc<AtomicBool>,
+}
+
+impl CaptureSink {
+    pub fn new(
+        samples: Arc<Mutex<Vec<i16>>>,
+        level_meter: Arc<AudioLevelMeter>,
+        on_input_chunk: Option<AudioInputChunkCallback>,
+    ) -> Self {
+        Self {
+            samples,
+            level_meter,
+            on_input_chunk: Arc::new(Mutex::new(on_input_chunk)),
+            standby: Arc::new(AtomicBool::new(false)),
+            pre_roll_ms: Arc::new(AtomicU64::new(0)),
+            input_profiles: Arc::new(Mutex::new(InputProfiles::new())),
+            processor: Arc::new(Mutex::new(InputProcessor::default())),
+            prefer_built_in_microphone: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_input_profiles(mut self, input_profiles: Arc<Mutex<InputProfiles>>) -> Self {
+        self.input_profiles = input_profiles;
+        self
+    }
+
+    pub fn with_built_in_preference(mut self, prefer_built_in_microphone: Arc<AtomicBool>) -> Self {
+        self.prefer_built_in_microphone = prefer_built_in_microphone;
+        self
+    }
+
+    pub fn prefers_built_in_microphone(&self) -> bool {
+        self.prefer_built_in_microphone.load(Ordering::Relaxed)
+    }
+
+    pub fn standby(
+        samples: Arc<Mutex<Vec<i16>>>,
+        level_meter: Arc<AudioLevelMeter>,
+        pre_roll: Duration,
+        standby_listener: Option<AudioInputChunkCallback>,
+    ) -> Self {
+        let sink = Self::new(samples, level_meter, standby_listener);
+        sink.standby.store(true, Ordering::Relaxed);
+        sink.pre_roll_ms
+            .store(pre_roll.as_millis() as u64, Ordering::Relaxed);
+        sink
+    }
+
+    pub fn is_standby(&self) -> bool {
+        self.standby.load(Ordering::Relaxed)
+    }
+
+    pub fn promote(
+        &self,
+        on_input_chunk: Option<AudioInputChunkCallback>,
+        sample_rate_hz: u32,
+    ) -> usize {
+        // Holding the callback lock blocks `push_frames`, so no frames slip in between the
+        // trim and the callback swap.
+        let Ok(mut callback_guard) = self.on_input_chunk.lock() else {
+            return 0;
+        };
+        self.standby.store(false, Ordering::Relaxed);
+        let pre_roll = Duration::from_millis(self.pre_roll_ms.swap(0, Ordering::Relaxed));
+        let retained = match self.samples.lock() {
+            Ok(mut sample_buffer) => {
+                retain_latest_samples(
+                    &mut sample_buffer,
+                    pre_roll_sample_count(pre_roll, sample_rate_hz),
+                );
+                sample_buffer.clone()
+            }
+            Err(_) => Vec::new(),
+        };
+        let retained_count = retained.len();
+
+        if let Some(callback) = on_input_chunk.as_ref() {
+            if !retained.is_empty() {
+                callback(AudioInputChunk {
+                    pcm16_mono_samples: retained,
+                    sample_rate_hz,
+                });
+            }
+        }
+        *callback_guard = on_input_chunk;
+        retained_count
+    }
+
+    pub fn prepare(&self, sample_rate_hz: u32, device_id: &str) {
+        if let Ok(mut sample_buffer) = self.samples.lock() {
+            sample_buffer.clear();
+            sample_buffer.reserve(usize::try_from(sample_rate_hz).unwrap_or(48_000) * 10);
+        }
+
+        let profile = self
+            .input_profiles
+            .lock()
+            .ok()
+            .and_then(|profiles| profiles.get(device_id).copied());
+        if let Some(profile) = profile.as_ref() {
+            debug!(device_id, ?profile, "applying microphone input profile");
+        }
+        if let Ok(mut processor) = self.processor.lock() {
+            *processor = InputProcessor::new(&profile.unwrap_or_default(), sample_rate_hz);
+        }
+    }
+
+    pub fn push_frames<T, F>(&self, data: &[T], channels: usize, sample_rate_hz: u32, to_f32: F)
+    where
+        T: Copy,
+        F: Fn(T) -> f32,
+    {
+        let callback_guard = self.on_input_chunk.lock().ok();
+        let Ok(mut processor) = self.processor.lock() else {
+            return;
+        };
+        let mut to_mono = |frame: &[T]| {
+            let mixed = processor.mix_frame(frame, &to_f32);
+            processor.process(mixed)
+        };
+        if !self.is_standby() {
+            process_input_frames(
+                data,
+                channels,
+                &mut to_mono,
+                &self.samples,
+                &self.level_meter,
+                sample_rate_hz,
+                callback_guard
+                    .as_ref()
+                    .and_then(|callback| callback.as_ref()),
+            );
+            return;
+        }
+
+        process_input_frames(
+            data,
+            channels,
+            &mut to_mono,
+            &self.samples,
+            &AudioLevelMeter::default(),
+            sample_rate_hz,
+            callback_guard
+                .as_ref()
+                .and_then(|callback| callback.as_ref()),
+        );
+        // Trimming only once the buffer doubles keeps the front drain amortized.
+        let pre_roll = Duration::from_millis(self.pre_roll_ms.load(Ordering::Relaxed));
+        let keep = pre_roll_sample_count(pre_roll, sample_rate_hz);
+        if let Ok(mut sample_buffer) = self.samples.lock() {
+            if sample_buffer.len() > keep.saturating_mul(2) {
+                retain_latest_samples(&mut sample_buffer, keep);
+            }
+        }
+    }
+
+    pub fn take_level(&self) -> AudioLevel {
+        self.level_meter.take()
+    }
+
+    pub fn reset_level(&self) {
+        self.level_meter.reset();
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CpalAudioBackend;
+
+impl AudioBackend for CpalAudioBackend {
+    fn name(&self) -> &'static str {
+        "cpal"
+    }
+
+    fn list_input_devices(&self) -> Result<Vec<MicrophoneInfo>, String> {
+        let host = cpal::default_host();
+        Ok(enumerate_input_devices(&host)?
+            .into_iter()
+            .map(|device| MicrophoneInfo {
+                id: device.id,
+                name: device.name,
+                is_default: device.is_default,
+                sample_rate_hz: device.sample_rate_hz,
+                channels: device.channels,
+                transport: device.transport,
+            })
+            .collect())
+    }
+
+    fn open_input_stream(
+        &self,
+        preferred_device_id: Option<&str>,
+        sink: CaptureSink,
+    ) -> Result<OpenedInputStream, String> {
+        let host = cpal::default_host();
+        let default_input_device = host.default_input_device();
+        let default_input_device_name = default_input_device
+            .as_ref()
+            .and_then(|device| device.name().ok());
+        let devices = enumerate_input_devices(&host)?;
+        if devices.is_empty() {
+            return Err("No microphone input devices are available".to_string());
+        }
+
+        let (selected_device, bluetooth_notice) = select_input_device(
+            devices,
+            preferred_device_id,
+            default_input_device_name.as_deref(),
+            sink.prefers_built_in_microphone(),
+        )?;
+        let EnumeratedInputDevice {
+            id: selected_device_id,
+            name: selected_device_name,
+            is_default: selected_is_default,
+            sample_rate_hz: _,
+            channels: _,
+            transport: _,
+            device: enumerated_device,
+        } = selected_device;
+        let (input_device, using_host_default_handle) = prefer_default_device_handle(
+            enumerated_device,
+            selected_is_default,
+            default_input_device,
+        );
+        if using_host_default_handle {
+            debug!(
+                device_id = %selected_device_id,
+                device_name = %selected_device_name,
+                "using host default microphone handle for recording"
+            );
+        } else if selected_is_default {
+            warn!(
+                device_id = %selected_device_id,
+                device_name = %selected_device_name,
+                "selected default microphone but host default handle was unavailable; using enumerated device handle"
+            );
+        }
+        info!(
+            device_id = %selected_device_id,
+            device_name = %selected_device_name,
+            "starting recording worker for selected device"
+        );
+
+        let supported_config = input_device.default_input_config().map_err(|err| {
+            format!(
+                "Failed to read default input config for '{}': {err}",
+                selected_device_name
+            )
+        })?;
+
+        let stream_config: StreamConfig = supported_config.clone().into();
+        let sample_format = supported_config.sample_format();
+        let input_channels = usize::from(stream_config.channels);
+        let sample_rate_hz = stream_config.sample_rate.0;
+
+        sink.prepare(sample_rate_hz, &selected_device_id);
+        let (stream_error_tx, stream_error_rx) = mpsc::channel::<String>();
+
+        let stream = build_input_stream(
+            &input_device,
+            &stream_config,
+            sample_format,
+            input_channels,
+            sample_rate_hz,
+            sink,
+            stream_error_tx,
+        )?;
+
+        stream
+            .play()
+            .map_err(|err| format!("Failed to start microphone stream: {err}"))?;
+        info!(
+            sample_rate_hz,
+            channels = stream_config.channels,
+            "microphone stream playback started"
+        );
+
+        Ok(OpenedInputStream {
+            stream: Box::new(stream),
+            runtime: RecordingRuntime {
+                sample_rate_hz,
+                channels: 1,
+                device_id: selected_device_id,
+                device_name: selected_device_name,
+                bluetooth_notice,
+            },
+            stream_error_rx,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct WavFileBackend {
+    path: PathBuf,
+}
+
+impl WavFileBackend {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn device_id(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+
+    fn device_name(&self) -> String {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string());
+        format!("File playback ({file_name})")
+    }
+
+    fn load(&self) -> Result<DecodedWav, String> {
+        let bytes = fs::read(&self.path).map_err(|error| {
+            format!(
+                "Failed to read audio playback file '{}': {error}",
+                self.path.display()
+            )
+        })?;
+        decode_pcm16_wav(&bytes)
+    }
+}
+
+impl AudioBackend for WavFileBackend {
+    fn name(&self) -> &'static str {
+        "wav_file"
+    }
+
+    fn list_input_devices(&self) -> Result<Vec<MicrophoneInfo>, String> {
+        let wav = self.load()?;
+        Ok(vec![MicrophoneInfo {
+            id: self.device_id(),
+            name: self.device_name(),
+            is_default: true,
+            sample_rate_hz: Some(wav.sample_rate_hz),
+            channels: Some(wav.channels),
+            transport: MicrophoneTransport::Other,
+        }])
+    }
+
+    fn open_input_stream(
+        &self,
+        _preferred_device_id: Option<&str>,
+        sink: CaptureSink,
+    ) -> Result<OpenedInputStream, String> {
+        let DecodedWav {
+            samples,
+            sample_rate_hz,
+            channels,
+        } = self.load()?;
+        info!(
+            path = %self.path.display(),
+            sample_rate_hz,
+            channels,
+            sample_count = samples.len(),
+            "starting audio file playback"
+        );
+        sink.prepare(sample_rate_hz, &self.device_id());
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let paused = Arc::new(AtomicBool::new(false));
+        let worker_stopped = Arc::clone(&stopped);
+        let worker_paused = Arc::clone(&paused);
+        let (_stream_error_tx, stream_error_rx) = mpsc::channel::<String>();
+        let frames_per_chunk =
+            (sample_rate_hz as usize * PLAYBACK_CHUNK_INTERVAL.as_millis() as usize / 1000).max(1);
+        let chunk_len = frames_per_chunk * usize::from(channels);
+        let join_handle = thread::spawn(move || {
+            for chunk in samples.chunks(chunk_len) {
+                while worker_paused.load(Ordering::Relaxed) {
+                    if worker_stopped.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    thread::sleep(PLAYBACK_CHUNK_INTERVAL);
+                }
+                if worker_stopped.load(Ordering::Relaxed) {
+                    return;
+                }
+                sink.push_frames(chunk, usize::from(channels), sample_rate_hz, |sample| {
+                    sample as f32 / i16::MAX as f32
+                });
+                thread::sleep(PLAYBACK_CHUNK_INTERVAL);
+            }
+            sink.reset_level();
+            debug!("audio file playback reached end of file");
+        });
+
+        Ok(OpenedInputStream {
+            stream: Box::new(PlaybackStream {
+                stopped,
+                paused,
+                join_handle: Some(join_handle),
+            }),
+            runtime: RecordingRuntime {
+                sample_rate_hz,
+                channels: 1,
+                device_id: self.device_id(),
+                device_name: self.device_name(),
+                bluetooth_notice: None,
+            },
+            stream_error_rx,
+        })
+    }
+}
+
+struct PlaybackStream {
+    stopped: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl StreamController for PlaybackStream {
+    fn pause_stream(&self) -> Result<(), String> {
+        self.paused.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn resume_stream(&self) -> Result<(), String> {
+        self.paused.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl Drop for PlaybackStream {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        if let Some(join_handle) = self.join_handle.take() {
+            if join_handle.join().is_err() {
+                warn!("audio file playback thread panicked");
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct DecodedWav {
+    samples: Vec<i16>,
+    sample_rate_hz: u32,
+    channels: u16,
+}
+
+fn decode_pcm16_wav(bytes: &[u8]) -> Result<DecodedWav, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Audio playback file is not a WAV file".to_string());
+    }
+
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    let mut offset = 12usize;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_size = u32::from_le_bytes([
+            bytes[offset + 4],
+            bytes[offset + 5],
+            bytes[offset + 6],
+            bytes[offset + 7],
+        ]) as usize;
+        let body_start = offset + 8;
+        let body_end = body_start
+            .checked_add(chunk_size)
+            .filter(|end| *end <= bytes.len())
+            .ok_or_else(|| "Audio playback file is truncated".to_string())?;
+        let body = &bytes[body_start..body_end];
+
+        if chunk_id == b"fmt " {
+            if body.len() < 16 {
+                return Err("Audio playback file has a malformed format chunk".to_string());
+            }
+            format = Some((
+                u16::from_le_bytes([body[0], body[1]]),
+                u16::from_le_bytes([body[2], body[3]]),
+                u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+                u16::from_le_bytes([body[14], body[15]]),
+            ));
+        } else if chunk_id == b"data" {
+            let Some((audio_format, channels, sample_rate_hz, bits_per_sample)) = format else {
+                return Err("Audio playback file is missing its format chunk".to_string());
+            };
+            if audio_format != 1 || bits_per_sample != 16 || channels == 0 {
+                return Err(format!(
+                    "Audio playback file must be 16-bit PCM (format {audio_format}, {bits_per_sample}-bit, {channels} channels)"
+                ));
+            }
+            return Ok(DecodedWav {
+                samples: body
+                    .chunks_exact(2)
+                    .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                    .collect(),
+                sample_rate_hz,
+                channels,
+            });
+        }
+
+        // Chunks are word-aligned; odd-sized chunks carry one pad byte.
+        offset = body_end + (chunk_size % 2);
+    }
+
+    Err("Audio playback file has no data chunk".to_string())
+}
+
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct FakeAudioBackend {
+    devices: Vec<MicrophoneInfo>,
+    frames: Vec<f32>,
+    sample_rate_hz: u32,
+    channels: u16,
+    open_failure: Option<String>,
+    stream_error: Option<String>,
+}
+
+#[cfg(test)]
+impl FakeAudioBackend {
+    pub fn new(sample_rate_hz: u32, channels: u16) -> Self {
+        Self {
+            sample_rate_hz,
+            channels,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_device(mut self, id: &str, is_default: bool) -> Self {
+        self.devices.push(MicrophoneInfo {
+            id: id.to_string(),
+            name: format!("Fake {id}"),
+            is_default,
+            sample_rate_hz: Some(self.sample_rate_hz),
+            channels: Some(self.channels),
+            transport: MicrophoneTransport::Other,
+        });
+        self
+    }
+
+    pub fn with_frames(mut self, frames: Vec<f32>) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    pub fn failing_to_open(mut self, message: &str) -> Self {
+        self.open_failure = Some(message.to_string());
+        self
+    }
+
+    pub fn with_stream_error(mut self, message: &str) -> Self {
+        self.stream_error = Some(message.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+struct FakeStream;
+
+#[cfg(test)]
+impl StreamController for FakeStream {
+    fn pause_stream(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn resume_stream(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl AudioBackend for FakeAudioBackend {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn list_input_devices(&self) -> Result<Vec<MicrophoneInfo>, String> {
+        Ok(self.devices.clone())
+    }
+
+    fn open_input_stream(
+        &self,
+        preferred_device_id: Option<&str>,
+        sink: CaptureSink,
+    ) -> Result<OpenedInputStream, String> {
+        if let Some(message) = &self.open_failure {
+            return Err(message.clone());
+        }
+        let device = self
+            .devices
+            .iter()
+            .find(|device| Some(device.id.as_str()) == preferred_device_id)
+            .or_else(|| self.devices.iter().find(|device| device.is_default))
+            .or_else(|| self.devices.first())
+            .ok_or_else(|| "No microphone input devices are available".to_string())?;
+
+        sink.prepare(self.sample_rate_hz, &device.id);
+        sink.push_frames(
+            &self.frames,
+            usize::from(self.channels),
+            self.sample_rate_hz,
+            |sample| sample,
+        );
+        let (stream_error_tx, stream_error_rx) = mpsc::channel::<String>();
+        if let Some(message) = &self.stream_error {
+            let _ = stream_error_tx.send(message.clone());
+        }
+
+        Ok(OpenedInputStream {
+            stream: Box::new(FakeStream),
+            runtime: RecordingRuntime {
+                sample_rate_hz: self.sample_rate_hz,
+                channels: 1,
+                device_id: device.id.clone(),
+                device_name: device.name.clone(),
+                bluetooth_notice: None,
+            },
+            stream_error_rx,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{mpsc, Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use uuid::Uuid;
+
+    use super::{
+        decode_pcm16_wav, AudioBackend, CaptureSink, DecodedWav, FakeAudioBackend, WavFileBackend,
+    };
+    use crate::audio_capture_service::{
+        pcm16_to_wav_bytes, run_recording_loop, AudioInputChunk, AudioInputChunkCallback,
+        AudioLevel, AudioLevelMeter, RecordedAudio, RecordingLoopExit,
+    };
+
+    fn test_sink() -> (CaptureSink, Arc<Mutex<Vec<i16>>>) {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let sink = CaptureSink::new(
+            Arc::clone(&samples),
+            Arc::new(AudioLevelMeter::default()),
+            None,
+        );
+        (sink, samples)
+    }
+
+    #[test]
+    fn fake_backend_downmixes_meters_and_encodes_captured_audio() {
+        let backend = FakeAudioBackend::new(16_000, 2)
+            .with_device("built-in", true)
+            .with_device("usb", false)
+            .with_frames(vec![0.5, 0.5, -0.25, -0.75, 1.0, 0.0]);
+        let (sink, samples) = test_sink();
+
+        let opened = backend
+            .open_input_stream(Some("usb"), sink.clone())
+            .expect("fake stream should open");
+        let captured = samples.lock().unwrap().clone();
+
+        assert_eq!(opened.runtime.device_id, "usb");
+        assert_eq!(opened.runtime.channels, 1);
+        assert_eq!(captured, vec![16_384, -16_384, 16_384]);
+        assert_eq!(sink.take_level().peak, 0.5);
+
+        let recording = RecordedAudio::from_pcm16_mono_samples(captured.clone(), 16_000, 0);
+        let wav = recording.into_wav_bytes().expect("expected wav bytes");
+        assert_eq!(
+            decode_pcm16_wav(&wav),
+            Ok(DecodedWav {
+                samples: captured,
+                sample_rate_hz: 16_000,
+                channels: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn fake_backend_surfaces_open_failures_and_stream_errors() {
+        let (sink, _) = test_sink();
+        let open_error = FakeAudioBackend::new(48_000, 1)
+            .with_device("built-in", true)
+            .failing_to_open("Device is busy")
+            .open_input_stream(None, sink.clone())
+            .err();
+        assert_eq!(open_error.as_deref(), Some("Device is busy"));
+
+        let no_devices = FakeAudioBackend::new(48_000, 1)
+            .open_input_stream(None, sink.clone())
+            .err();
+        assert_eq!(
+            no_devices.as_deref(),
+            Some("No microphone input devices are available")
+        );
+
+        let opened = FakeAudioBackend::new(48_000, 1)
+            .with_device("built-in", true)
+            .with_stream_error("device unplugged")
+            .open_input_stream(None, sink)
+            .expect("fake stream should open");
+        let (_stop_tx, stop_rx) = mpsc::channel::<()>();
+        assert_eq!(
+            run_recording_loop(&stop_rx, &opened.stream_error_rx, || {}),
+            RecordingLoopExit::StreamError("device unplugged".to_string())
+        );
+    }
+
+    #[test]
+    fn wav_file_backend_replays_file_into_sink_and_forwards_chunks() {
+        let source = (0..1_600)
+            .map(|index| (index % 64) as i16 * 256)
+            .collect::<Vec<_>>();
+        let path = std::env::temp_dir().join(format!("buzz-playback-{}.wav", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            pcm16_to_wav_bytes(&source, 16_000, 1).expect("expected wav bytes"),
+        )
+        .expect("wav file should write");
+        let forwarded = Arc::new(Mutex::new(0usize));
+        let forwarded_counter = Arc::clone(&forwarded);
+        let on_input_chunk: AudioInputChunkCallback = Arc::new(move |chunk: AudioInputChunk| {
+            *forwarded_counter.lock().unwrap() += chunk.pcm16_mono_samples.len();
+        });
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let sink = CaptureSink::new(
+            Arc::clone(&samples),
+            Arc::new(AudioLevelMeter::default()),
+            Some(on_input_chunk),
+        );
+
+        let backend = WavFileBackend::new(path.clone());
+        let devices = backend.list_input_devices().expect("file should list");
+        let opened = backend
+            .open_input_stream(None, sink)
+            .expect("playback should open");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while samples.lock().unwrap().len() < source.len() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        drop(opened);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].sample_rate_hz, Some(16_000));
+        assert_eq!(*samples.lock().unwrap(), source);
+        assert_eq!(*forwarded.lock().unwrap(), source.len());
+        assert!(decode_pcm16_wav(b"RIFF\0\0\0\0WAVE").is_err());
+    }
+
+    #[test]
+    fn standby_sink_keeps_only_pre_roll_and_replays_it_when_promoted() {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let level_meter = Arc::new(AudioLevelMeter::default());
+        let sink = CaptureSink::standby(
+            Arc::clone(&samples),
+            Arc::clone(&level_meter),
+            Duration::from_millis(100),
+            None,
+        );
+        let frames = vec![0.5_f32; 1_000];
+
+        for _ in 0..5 {
+            sink.push_frames(&frames, 1, 1_000, |sample| sample);
+        }
+        assert!(sink.is_standby());
+        assert!(samples.lock().unwrap().len() <= 1_000);
+        assert_eq!(level_meter.take(), AudioLevel::default());
+
+        let forwarded = Arc::new(Mutex::new(Vec::new()));
+        let forwarded_chunks = Arc::clone(&forwarded);
+        let on_input_chunk: AudioInputChunkCallback = Arc::new(move |chunk: AudioInputChunk| {
+            forwarded_chunks
+                .lock()
+                .unwrap()
+                .push(chunk.pcm16_mono_samples.len());
+        });
+        assert_eq!(sink.promote(Some(on_input_chunk), 1_000), 100);
+        sink.push_frames(&frames[..50], 1, 1_000, |sample| sample);
+
+        assert!(!sink.is_standby());
+        assert_eq!(samples.lock().unwrap().len(), 150);
+        assert_eq!(*forwarded.lock().unwrap(), vec![100, 50]);
+        assert_eq!(sink.take_level().peak, 0.5);
+    }
+
+    #[test]
+    fn paused_standby_sink_discards_audio_until_promoted() {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let sink = CaptureSink::standby(
+            Arc::clone(&samples),
+            Arc::new(AudioLevelMeter::default()),
+            Duration::ZERO,
+            None,
+        );
+
+        sink.push_frames(&[0.25_f32; 64], 1, 1_000, |sample| sample);
+        assert!(sink.is_standby());
+        assert!(samples.lock().unwrap().is_empty());
+
+        assert_eq!(sink.promote(None, 1_000), 0);
+        sink.push_frames(&[0.25_f32; 64], 1, 1_000, |sample| sample);
+        assert_eq!(samples.lock().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn standby_listener_hears_standby_audio_until_promoted() {
+        let heard = Arc::new(Mutex::new(0));
+        let heard_samples = Arc::clone(&heard);
+        let listener: AudioInputChunkCallback = Arc::new(move |chunk: AudioInputChunk| {
+            *heard_samples.lock().unwrap() += chunk.pcm16_mono_samples.len();
+        });
+        let sink = CaptureSink::standby(
+            Arc::new(Mutex::new(Vec::new())),
+            Arc::new(AudioLevelMeter::default()),
+            Duration::ZERO,
+            Some(listener),
+        );
+
+        sink.push_frames(&[0.25_f32; 64], 1, 1_000, |sample| sample);
+        assert_eq!(*heard.lock().unwrap(), 64);
+
+        sink.promote(None, 1_000);
+        sink.push_frames(&[0.25_f32; 64], 1, 1_000, |sample| sample);
+        assert_eq!(*heard.lock().unwrap(), 64);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+const ENVELOPE_ATTACK_SECS: f32 = 0.005;
+const ENVELOPE_RELEASE_SECS: f32 = 0.05;
+const NOISE_FLOOR_RISE_DB_PER_SEC: f32 = 3.0;
+const MIN_NOISE_FLOOR: f32 = 1e-5;
+// Seeding the noise floor from the silent start would leave it far too low for many seconds.
+const NOISE_FLOOR_WARMUP_SECS: f32 = 0.05;
+const NOISE_THRESHOLD_FACTOR: f32 = 3.0;
+const MAX_EXPANSION: f32 = 2.0;
+const MIN_SUPPRESSION_GAIN: f32 = 0.05;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputProfile {
+    pub gain_db: f32,
+    pub noise_suppression: f32,
+    pub input_channel: Option<usize>,
+}
+
+pub type InputProfiles = HashMap<String, InputProfile>;
+
+#[derive(Debug, Clone)]
+pub(super) struct InputProcessor {
+    gain: f32,
+    strength: f32,
+    input_channel: Option<usize>,
+    attack: f32,
+    release: f32,
+    floor_rise: f32,
+    warmup_samples: u32,
+    envelope: f32,
+    noise_floor: f32,
+}
+
+impl Default for InputProcessor {
+    fn default() -> Self {
+        Self::new(&InputProfile::default(), 48_000)
+    }
+}
+
+impl InputProcessor {
+    pub(super) fn new(profile: &InputProfile, sample_rate_hz: u32) -> Self {
+        let sample_rate = sample_rate_hz.max(1) as f32;
+        let smoothing = |secs: f32| (-1.0 / (secs * sample_rate)).exp();
+        Self {
+            gain: 10.0_f32.powf(profile.gain_db / 20.0),
+            strength: profile.noise_suppression.clamp(0.0, 1.0),
+            input_channel: profile.input_channel,
+            attack: smoothing(ENVELOPE_ATTACK_SECS),
+            release: smoothing(ENVELOPE_RELEASE_SECS),
+            floor_rise: 10.0_f32.powf(NOISE_FLOOR_RISE_DB_PER_SEC / 20.0 / sample_rate),
+            warmup_samples: (NOISE_FLOOR_WARMUP_SECS * sample_rate) as u32,
+            envelope: 0.0,
+            noise_floor: MIN_NOISE_FLOOR,
+        }
+    }
+
+    pub(super) fn mix_frame<T: Copy>(&self, frame: &[T], to_f32: impl Fn(T) -> f32) -> f32 {
+        match self.input_channel.and_then(|channel| frame.get(channel)) {
+            Some(&sample) => to_f32(sample),
+            None => frame.iter().map(|&sample| to_f32(sample)).sum::<f32>() / frame.len() as f32,
+        }
+    }
+
+    pub(super) fn process(&mut self, sample: f32) -> f32 {
+        let amplified = sample * self.gain;
+        if self.strength <= 0.0 {
+            return amplified;
+        }
+
+        let level = amplified.abs();
+        let smoothing = if level > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = level + (self.envelope - level) * smoothing;
+        if self.warmup_samples > 0 {
+            self.warmup_samples -= 1;
+            if self.warmup_samples == 0 {
+                self.noise_floor = self.envelope.max(MIN_NOISE_FLOOR);
+            }
+            return amplified;
+        }
+        self.noise_floor = if self.envelope < self.noise_floor {
+            self.envelope.max(MIN_NOISE_FLOOR)
+        } else {
+            self.noise_floor * self.floor_rise
+        };
+
+        let threshold = self.noise_floor * NOISE_THRESHOLD_FACTOR;
+        if self.envelope >= threshold {
+            return amplified;
+        }
+        let reduction = (self.envelope / threshold)
+            .powf(self.strength * MAX_EXPANSION)
+            .max(MIN_SUPPRESSION_GAIN);
+        amplified * reduction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputProcessor, InputProfile};
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn selects_a_channel_and_applies_gain() {
+        let profile = InputProfile {
+            gain_db: 6.0,
+            input_channel: Some(1),
+            ..InputProfile::default()
+        };
+        let mut processor = InputProcessor::new(&profile, 16_000);
+
+        let mixed = processor.mix_frame(&[0.9_f32, 0.25], |sample| sample);
+        assert_eq!(mixed, 0.25);
+        assert!((processor.process(mixed) - 0.499).abs() < 0.01);
+
+        let missing_channel = InputProcessor::new(
+            &InputProfile {
+                input_channel: Some(4),
+                ..InputProfile::default()
+            },
+            16_000,
+        );
+        assert_eq!(
+            missing_channel.mix_frame(&[0.5_f32, 0.25], |sample| sample),
+            0.375
+        );
+    }
+
+    #[test]
+    fn suppression_quiets_steady_noise_but_keeps_speech() {
+        let hiss = |index: usize| if index.is_multiple_of(2) { 0.01 } else { -0.01 };
+        let profile = InputProfile {
+            noise_suppression: 1.0,
+            ..InputProfile::default()
+        };
+        let mut processor = InputProcessor::new(&profile, 16_000);
+        let mut passthrough = InputProcessor::new(&InputProfile::default(), 16_000);
+
+        let noise = (0..16_000).map(hiss).collect::<Vec<_>>();
+        let processed_noise = noise
+            .iter()
+            .map(|&sample| processor.process(sample))
+            .collect::<Vec<_>>();
+        assert!(rms(&processed_noise[8_000..]) < rms(&noise) * 0.5);
+        assert_eq!(passthrough.process(0.01), 0.01);
+
+        let speech = (0..1_600)
+            .map(|index| 0.3 * (index as f32 * 0.2).sin() + hiss(index))
+            .collect::<Vec<_>>();
+        let processed_speech = speech
+            .iter()
+            .map(|&sample| processor.process(sample))
+            .collect::<Vec<_>>();
+        assert!(rms(&processed_speech[800..]) > rms(&speech[800..]) * 0.9);
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use super::quantize_audio_level_for_emit;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioLevel {
+    pub peak: f32,
+    pub rms: f32,
+}
+
+impl AudioLevel {
+    pub(super) fn quantized(self) -> Self {
+        Self {
+            peak: quantize_audio_level_for_emit(self.peak),
+            rms: quantize_audio_level_for_emit(self.rms),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AudioLevelMeter {
+    peak_bits: AtomicU32,
+    rms_bits: AtomicU32,
+}
+
+impl AudioLevelMeter {
+    // Levels are never negative, so their bit patterns order like the floats.
+    pub fn record(&self, peak: f32, rms: f32) {
+        self.peak_bits
+            .fetch_max(peak.max(0.0).to_bits(), Ordering::Relaxed);
+        self.rms_bits
+            .store(rms.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn take(&self) -> AudioLevel {
+        AudioLevel {
+            peak: f32::from_bits(self.peak_bits.swap(0, Ordering::Relaxed)),
+            rms: f32::from_bits(self.rms_bits.load(Ordering::Relaxed)),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.peak_bits.store(0, Ordering::Relaxed);
+        self.rms_bits.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioLevel, AudioLevelMeter};
+
+    #[test]
+    fn holds_the_highest_peak_until_taken() {
+        let meter = AudioLevelMeter::default();
+        meter.record(0.8, 0.3);
+        meter.record(0.2, 0.1);
+
+        assert_eq!(
+            meter.take(),
+            AudioLevel {
+                peak: 0.8,
+                rms: 0.1
+            }
+        );
+        assert_eq!(
+            meter.take(),
+            AudioLevel {
+                peak: 0.0,
+                rms: 0.1
+            }
+        );
+
+        meter.reset();
+        assert_eq!(meter.take(), AudioLevel::default());
+    }
+}
@@ -1,8 +1,13 @@
+mod backend;
+mod input_profile;
+mod level_meter;
+mod standby;
+
 use std::{
     collections::{HashMap, VecDeque},
     fmt,
     sync::{
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicBool, Ordering},
         mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
         Arc, Mutex,
     },
@@ -14,14 +19,23 @@ use cpal::{
     traits::{DeviceTrait, HostTrait, StreamTrait},
     Device, SampleFormat, Stream, StreamConfig, StreamError,
 };
+
+pub use backend::{AudioBackend, CaptureSink, CpalAudioBackend, OpenedInputStream, WavFileBackend};
+pub use input_profile::{InputProfile, InputProfiles};
+pub use level_meter::{AudioLevel, AudioLevelMeter};
 use serde::{Deserialize, Serialize};
+pub use standby::StandbyMode;
+use standby::{samples_duration, StandbyCapture, StandbyConfig};
 use tauri::{AppHandle, Emitter};
 use tracing::{debug, error, info, warn};
 
-pub const AUDIO_LEVEL_EVENT: &str = "audio-level";
+pub const AUDIO_LEVEL_EVENT: &str = "voice://audio-level";
 pub const AUDIO_INPUT_STREAM_ERROR_EVENT: &str = "voice://audio-input-stream-error";
-const LEVEL_EVENT_INTERVAL: Duration = Duration::from_millis(50);
+pub const BLUETOOTH_MICROPHONE_EVENT: &str = "voice://bluetooth-microphone";
+const LEVEL_EVENT_INTERVAL: Duration = Duration::from_millis(33);
 const WORKER_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+const AUDIO_PLAYBACK_FILE_ENV: &str = "BUZZ_AUDIO_PLAYBACK_FILE";
+const WAVEFORM_BUCKETS: usize = 64;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +45,43 @@ pub struct MicrophoneInfo {
     pub is_default: bool,
     pub sample_rate_hz: Option<u32>,
     pub channels: Option<u16>,
+    pub transport: MicrophoneTransport,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MicrophoneTransport {
+    BuiltIn,
+    Bluetooth,
+    #[default]
+    Other,
+}
+
+impl MicrophoneTransport {
+    fn from_device_name(name: &str) -> Self {
+        let name = name.to_ascii_lowercase();
+        if ["bluetooth", "airpods", "hands-free", "handsfree"]
+            .iter()
+            .any(|marker| name.contains(marker))
+        {
+            Self::Bluetooth
+        } else if ["built-in", "macbook"]
+            .iter()
+            .any(|marker| name.contains(marker))
+        {
+            Self::BuiltIn
+        } else {
+            Self::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BluetoothMicrophoneEvent {
+    pub device_id: String,
+    pub device_name: String,
+    pub fallback_device_name: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -69,8 +120,40 @@ pub struct AudioInputChunk {
     pub sample_rate_hz: u32,
 }
 
+impl AudioInputChunk {
+    pub fn duration_secs(&self) -> f64 {
+        if self.sample_rate_hz == 0 {
+            return 0.0;
+        }
+
+        self.pcm16_mono_samples.len() as f64 / f64::from(self.sample_rate_hz)
+    }
+
+    pub fn to_wav_bytes(&self) -> Result<Vec<u8>, String> {
+        pcm16_to_wav_bytes(&self.pcm16_mono_samples, self.sample_rate_hz, 1)
+    }
+}
+
 pub type AudioInputChunkCallback = Arc<dyn Fn(AudioInputChunk) + Send + Sync + 'static>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureStart {
+    Cold,
+    PreRoll,
+    Prewarmed,
+}
+
+impl CaptureStart {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cold => "cold",
+            Self::PreRoll => "pre_roll",
+            Self::Prewarmed => "prewarmed",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioInputStreamErrorEvent {
@@ -89,11 +172,12 @@ struct RecordingControl {
 }
 
 #[derive(Debug)]
-struct RecordingRuntime {
-    sample_rate_hz: u32,
-    channels: u16,
-    device_id: String,
-    device_name: String,
+pub struct RecordingRuntime {
+    pub sample_rate_hz: u32,
+    pub channels: u16,
+    pub device_id: String,
+    pub device_name: String,
+    pub bluetooth_notice: Option<BluetoothMicrophoneEvent>,
 }
 
 struct EnumeratedInputDevice {
@@ -102,6 +186,7 @@ struct EnumeratedInputDevice {
     is_default: bool,
     sample_rate_hz: Option<u32>,
     channels: Option<u16>,
+    transport: MicrophoneTransport,
     device: Device,
 }
 
@@ -110,6 +195,7 @@ struct MacosCoreAudioDeviceIdentity {
     device_id: u32,
     uid: Option<String>,
     name: Option<String>,
+    transport: Option<MicrophoneTransport>,
 }
 
 #[derive(Debug, Clone)]
@@ -120,14 +206,21 @@ struct InputDeviceSelectionCandidate {
 }
 
 pub struct AudioCaptureService {
+    backend: Arc<dyn AudioBackend>,
     recording: Mutex<Option<RecordingControl>>,
-    audio_level_bits: Arc<AtomicU32>,
+    standby_config: Mutex<Option<StandbyConfig>>,
+    standby: Mutex<Option<StandbyCapture>>,
+    standby_listener: Mutex<Option<AudioInputChunkCallback>>,
+    input_profiles: Arc<Mutex<InputProfiles>>,
+    prefer_built_in_microphone: Arc<AtomicBool>,
+    level_meter: Arc<AudioLevelMeter>,
     sample_buffer_pool: Arc<Mutex<Vec<Vec<i16>>>>,
 }
 
 impl fmt::Debug for AudioCaptureService {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("AudioCaptureService")
+            .field("backend", &self.backend.name())
             .finish_non_exhaustive()
     }
 }
@@ -140,29 +233,40 @@ impl Default for AudioCaptureService {
 
 impl AudioCaptureService {
     pub fn new() -> Self {
-        debug!("audio capture service initialized");
+        let backend: Arc<dyn AudioBackend> =
+            match std::env::var_os(AUDIO_PLAYBACK_FILE_ENV).filter(|value| !value.is_empty()) {
+                Some(path) => Arc::new(WavFileBackend::new(path.into())),
+                None => Arc::new(CpalAudioBackend),
+            };
+        Self::with_backend(backend)
+    }
+
+    pub fn with_backend(backend: Arc<dyn AudioBackend>) -> Self {
+        debug!(
+            backend = backend.name(),
+            "audio capture service initialized"
+        );
         Self {
+            backend,
             recording: Mutex::new(None),
-            audio_level_bits: Arc::new(AtomicU32::new(0.0_f32.to_bits())),
+            standby_config: Mutex::new(None),
+            standby: Mutex::new(None),
+            standby_listener: Mutex::new(None),
+            input_profiles: Arc::new(Mutex::new(InputProfiles::new())),
+            prefer_built_in_microphone: Arc::new(AtomicBool::new(false)),
+            level_meter: Arc::new(AudioLevelMeter::default()),
             sample_buffer_pool: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
     pub fn list_microphones(&self) -> Result<Vec<MicrophoneInfo>, String> {
-        let host = cpal::default_host();
-        let devices = enumerate_input_devices(&host)?;
-        debug!(count = devices.len(), "enumerated input microphones");
-
-        Ok(devices
-            .into_iter()
-            .map(|device| MicrophoneInfo {
-                id: device.id,
-                name: device.name,
-                is_default: device.is_default,
-                sample_rate_hz: device.sample_rate_hz,
-                channels: device.channels,
-            })
-            .collect())
+        let devices = self.backend.list_input_devices()?;
+        debug!(
+            backend = self.backend.name(),
+            count = devices.len(),
+            "enumerated input microphones"
+        );
+        Ok(devices)
     }
 
     pub fn start_recording(
@@ -170,7 +274,7 @@ impl AudioCaptureService {
         app_handle: AppHandle,
         preferred_device_id: Option<&str>,
         on_input_chunk: Option<AudioInputChunkCallback>,
-    ) -> Result<(), String> {
+    ) -> Result<CaptureStart, String> {
         info!(
             preferred_device_id = ?preferred_device_id,
             "audio capture start requested"
@@ -185,28 +289,43 @@ impl AudioCaptureService {
             return Err("Recording is already in progress".to_string());
         }
 
-        self.audio_level_bits
-            .store(0.0_f32.to_bits(), Ordering::Relaxed);
+        self.level_meter.reset();
+
+        if let Some((control, start)) =
+            self.promote_standby(preferred_device_id, on_input_chunk.clone())
+        {
+            if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, AudioLevel::default()) {
+                warn!(%error, "failed to emit initial audio level event");
+            }
+            *recording_guard = Some(control);
+            info!(start = start.as_str(), "audio capture started from standby");
+            return Ok(start);
+        }
 
         let samples = Arc::new(Mutex::new(self.take_reusable_sample_buffer()));
-        let worker_samples = Arc::clone(&samples);
-        let worker_level_bits = Arc::clone(&self.audio_level_bits);
+        let worker_backend = Arc::clone(&self.backend);
+        let worker_sink = CaptureSink::new(
+            Arc::clone(&samples),
+            Arc::clone(&self.level_meter),
+            on_input_chunk,
+        )
+        .with_input_profiles(Arc::clone(&self.input_profiles))
+        .with_built_in_preference(Arc::clone(&self.prefer_built_in_microphone));
         let worker_app_handle = app_handle.clone();
         let worker_preferred_device_id = preferred_device_id.map(str::to_string);
-        let worker_chunk_callback = on_input_chunk;
 
         let (ready_tx, ready_rx) = mpsc::channel::<Result<RecordingRuntime, String>>();
         let (stop_tx, stop_rx) = mpsc::channel::<()>();
 
         let mut join_handle = Some(thread::spawn(move || {
             recording_thread_main(
+                worker_backend,
                 worker_preferred_device_id,
-                worker_samples,
-                worker_level_bits,
+                worker_sink,
                 worker_app_handle,
-                worker_chunk_callback,
                 ready_tx,
                 stop_rx,
+                None,
             );
         }));
 
@@ -220,7 +339,7 @@ impl AudioCaptureService {
         let join_handle =
             join_handle.ok_or_else(|| "Microphone worker was unavailable".to_string())?;
 
-        if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, 0.0_f32) {
+        if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, AudioLevel::default()) {
             warn!(%error, "failed to emit initial audio level event");
         }
 
@@ -235,8 +354,15 @@ impl AudioCaptureService {
             device_name: runtime.device_name,
         });
 
-        info!("audio capture started");
-        Ok(())
+        info!(start = CaptureStart::Cold.as_str(), "audio capture started");
+        Ok(CaptureStart::Cold)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+            .lock()
+            .map(|recording| recording.is_some())
+            .unwrap_or(false)
     }
 
     pub fn stop_recording(&self, app_handle: AppHandle) -> Result<RecordedAudio, String> {
@@ -275,9 +401,8 @@ impl AudioCaptureService {
             std::mem::take(&mut *sample_guard)
         };
 
-        self.audio_level_bits
-            .store(0.0_f32.to_bits(), Ordering::Relaxed);
-        if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, 0.0_f32) {
+        self.level_meter.reset();
+        if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, AudioLevel::default()) {
             warn!(%error, "failed to emit audio level reset event after stop");
         }
 
@@ -304,6 +429,7 @@ impl AudioCaptureService {
             device_name = %device_name,
             "audio capture stopped"
         );
+        self.arm_standby(&app_handle);
 
         Ok(RecordedAudio {
             wav_bytes: None,
@@ -346,18 +472,292 @@ impl AudioCaptureService {
             return Err("Microphone capture thread panicked while aborting".to_string());
         }
 
-        self.audio_level_bits
-            .store(0.0_f32.to_bits(), Ordering::Relaxed);
-        if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, 0.0_f32) {
+        self.level_meter.reset();
+        if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, AudioLevel::default()) {
             warn!(%error, "failed to emit audio level reset event after abort");
         }
 
         info!("audio capture aborted");
+        self.arm_standby(&app_handle);
         Ok(true)
     }
 
-    pub fn get_audio_level(&self) -> f32 {
-        f32::from_bits(self.audio_level_bits.load(Ordering::Relaxed))
+    pub fn configure_standby(
+        &self,
+        app_handle: &AppHandle,
+        preferred_device_id: Option<&str>,
+        mode: Option<StandbyMode>,
+        listener: Option<AudioInputChunkCallback>,
+    ) -> Result<(), String> {
+        let config = mode.map(|mode| StandbyConfig {
+            preferred_device_id: preferred_device_id.map(str::to_string),
+            mode,
+        });
+        {
+            let mut config_guard = self
+                .standby_config
+                .lock()
+                .map_err(|_| "Standby settings lock is poisoned".to_string())?;
+            let mut listener_guard = self
+                .standby_listener
+                .lock()
+                .map_err(|_| "Standby listener lock is poisoned".to_string())?;
+            let listener_changed = match (listener_guard.as_ref(), listener.as_ref()) {
+                (Some(current), Some(next)) => !Arc::ptr_eq(current, next),
+                (current, next) => current.is_some() != next.is_some(),
+            };
+            if *config_guard != config || listener_changed {
+                info!(
+                    mode = ?mode,
+                    listener = listener.is_some(),
+                    "microphone standby configuration changed"
+                );
+                *config_guard = config;
+                *listener_guard = listener;
+                self.stop_standby();
+            }
+        }
+
+        self.arm_standby(app_handle);
+        Ok(())
+    }
+
+    fn arm_standby(&self, app_handle: &AppHandle) {
+        let Some(config) = self
+            .standby_config
+            .lock()
+            .ok()
+            .and_then(|config| config.clone())
+        else {
+            return;
+        };
+        let Ok(recording_guard) = self.recording.lock() else {
+            return;
+        };
+        if recording_guard.is_some() {
+            return;
+        }
+        let Ok(mut standby_guard) = self.standby.lock() else {
+            return;
+        };
+        if let Some(standby) = standby_guard.take() {
+            if standby.is_usable_for(config.preferred_device_id.as_deref()) {
+                *standby_guard = Some(standby);
+                return;
+            }
+            standby.stop();
+        }
+
+        let samples = Arc::new(Mutex::new(self.take_reusable_sample_buffer()));
+        let sink = CaptureSink::standby(
+            Arc::clone(&samples),
+            Arc::clone(&self.level_meter),
+            config.mode.pre_roll(),
+            self.standby_listener
+                .lock()
+                .ok()
+                .and_then(|listener| listener.clone()),
+        )
+        .with_input_profiles(Arc::clone(&self.input_profiles))
+        .with_built_in_preference(Arc::clone(&self.prefer_built_in_microphone));
+        let worker_backend = Arc::clone(&self.backend);
+        let worker_sink = sink.clone();
+        let worker_app_handle = app_handle.clone();
+        let worker_preferred_device_id = config.preferred_device_id.clone();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<RecordingRuntime, String>>();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (resume_tx, resume_rx) = match config.mode {
+            StandbyMode::Paused => {
+                let (resume_tx, resume_rx) = mpsc::channel::<()>();
+                (Some(resume_tx), Some(resume_rx))
+            }
+            StandbyMode::PreRoll(_) => (None, None),
+        };
+
+        let join_handle = thread::spawn(move || {
+            recording_thread_main(
+                worker_backend,
+                worker_preferred_device_id,
+                worker_sink,
+                worker_app_handle,
+                ready_tx,
+                stop_rx,
+                resume_rx,
+            );
+        });
+
+        debug!(mode = ?config.mode, "microphone standby armed");
+        *standby_guard = Some(StandbyCapture {
+            stop_tx,
+            resume_tx,
+            join_handle,
+            ready_rx,
+            samples,
+            sink,
+            config,
+        });
+    }
+
+    pub fn configure_input_profiles(
+        &self,
+        app_handle: &AppHandle,
+        profiles: InputProfiles,
+    ) -> Result<(), String> {
+        {
+            let mut current = self
+                .input_profiles
+                .lock()
+                .map_err(|_| "Input profiles lock is poisoned".to_string())?;
+            if *current == profiles {
+                return Ok(());
+            }
+            info!(
+                profiles = profiles.len(),
+                "microphone input profiles changed"
+            );
+            *current = profiles;
+        }
+
+        self.stop_standby();
+        self.arm_standby(app_handle);
+        Ok(())
+    }
+
+    pub fn set_prefer_built_in_microphone(&self, app_handle: &AppHandle, enabled: bool) {
+        if self
+            .prefer_built_in_microphone
+            .swap(enabled, Ordering::Relaxed)
+            == enabled
+        {
+            return;
+        }
+        info!(enabled, "built-in microphone preference changed");
+        self.stop_standby();
+        self.arm_standby(app_handle);
+    }
+
+    pub fn discard_standby_audio(&self) {
+        let Ok(standby_guard) = self.standby.lock() else {
+            return;
+        };
+        let Some(standby) = standby_guard.as_ref() else {
+            return;
+        };
+        match standby.samples.lock() {
+            Ok(mut samples) => samples.clear(),
+            Err(_) => warn!("standby sample buffer lock is poisoned; keeping buffered audio"),
+        };
+    }
+
+    fn stop_standby(&self) {
+        let standby = self
+            .standby
+            .lock()
+            .ok()
+            .and_then(|mut standby| standby.take());
+        if let Some(standby) = standby {
+            standby.stop();
+        }
+    }
+
+    fn promote_standby(
+        &self,
+        preferred_device_id: Option<&str>,
+        on_input_chunk: Option<AudioInputChunkCallback>,
+    ) -> Option<(RecordingControl, CaptureStart)> {
+        let standby = self.standby.lock().ok()?.take()?;
+        if !standby.is_usable_for(preferred_device_id) {
+            debug!("discarding microphone standby that does not match the requested microphone");
+            standby.stop();
+            return None;
+        }
+
+        let StandbyCapture {
+            stop_tx,
+            resume_tx,
+            join_handle,
+            ready_rx,
+            samples,
+            sink,
+            ..
+        } = standby;
+        let mut join_handle = Some(join_handle);
+        let runtime = match await_worker_startup(
+            &ready_rx,
+            &stop_tx,
+            &mut join_handle,
+            WORKER_STARTUP_TIMEOUT,
+        ) {
+            Ok(runtime) => runtime,
+            Err(error) => {
+                warn!(%error, "microphone standby failed; opening a fresh microphone stream");
+                return None;
+            }
+        };
+        let join_handle = join_handle?;
+
+        let retained_samples = sink.promote(on_input_chunk, runtime.sample_rate_hz);
+        let start = match resume_tx {
+            Some(resume_tx) => {
+                if resume_tx.send(()).is_err() {
+                    warn!("paused microphone worker exited before resume; opening a fresh stream");
+                    let _ = join_handle.join();
+                    return None;
+                }
+                CaptureStart::Prewarmed
+            }
+            None => CaptureStart::PreRoll,
+        };
+        let pre_roll = samples_duration(retained_samples, runtime.sample_rate_hz);
+        debug!(
+            retained_samples,
+            pre_roll_ms = pre_roll.as_millis(),
+            start = start.as_str(),
+            "promoted microphone standby to recording"
+        );
+
+        Some((
+            RecordingControl {
+                stop_tx,
+                join_handle,
+                samples,
+                sample_rate_hz: runtime.sample_rate_hz,
+                channels: runtime.channels,
+                started_at: Instant::now()
+                    .checked_sub(pre_roll)
+                    .unwrap_or_else(Instant::now),
+                device_id: runtime.device_id,
+                device_name: runtime.device_name,
+            },
+            start,
+        ))
+    }
+
+    pub fn drain_buffered_samples(&self) -> Result<Option<AudioInputChunk>, String> {
+        let recording_guard = self
+            .recording
+            .lock()
+            .map_err(|_| "Audio capture state lock is poisoned".to_string())?;
+        let Some(control) = recording_guard.as_ref() else {
+            return Ok(None);
+        };
+
+        let pcm16_mono_samples = {
+            let mut sample_guard = control
+                .samples
+                .lock()
+                .map_err(|_| "Audio sample buffer lock is poisoned".to_string())?;
+            std::mem::take(&mut *sample_guard)
+        };
+        debug!(
+            sample_count = pcm16_mono_samples.len(),
+            "drained buffered audio samples"
+        );
+
+        Ok(Some(AudioInputChunk {
+            pcm16_mono_samples,
+            sample_rate_hz: control.sample_rate_hz,
+        }))
     }
 
     pub fn debug_snapshot(&self) -> Result<Option<AudioCaptureDebugSnapshot>, String> {
@@ -401,7 +801,6 @@ impl AudioCaptureService {
 }
 
 impl RecordedAudio {
-    #[cfg(test)]
     pub fn from_wav_bytes(
         wav_bytes: Vec<u8>,
         sample_rate_hz: u32,
@@ -422,21 +821,20 @@ impl RecordedAudio {
         }
     }
 
-    pub fn empty(
+    #[cfg(test)]
+    pub fn from_pcm16_mono_samples(
+        pcm16_mono_samples: Vec<i16>,
         sample_rate_hz: u32,
-        channels: u16,
         duration_ms: u64,
-        device_id: String,
-        device_name: String,
     ) -> Self {
         Self {
-            wav_bytes: Some(Vec::new()),
+            wav_bytes: None,
             sample_rate_hz,
-            channels,
+            channels: 1,
             duration_ms,
-            device_id,
-            device_name,
-            pcm16_mono_samples: Vec::new(),
+            device_id: "test-device".to_string(),
+            device_name: "Test Device".to_string(),
+            pcm16_mono_samples,
             sample_buffer_pool: None,
         }
     }
@@ -453,6 +851,26 @@ impl RecordedAudio {
         self.pcm16_mono_samples.len()
     }
 
+    pub fn rms_level(&self) -> Option<f32> {
+        if self.pcm16_mono_samples.is_empty() {
+            return None;
+        }
+
+        let sum_of_squares = self
+            .pcm16_mono_samples
+            .iter()
+            .map(|sample| {
+                let normalized = f64::from(*sample) / f64::from(i16::MAX);
+                normalized * normalized
+            })
+            .sum::<f64>();
+        Some((sum_of_squares / self.pcm16_mono_samples.len() as f64).sqrt() as f32)
+    }
+
+    pub fn waveform(&self) -> Vec<u8> {
+        waveform_peaks(&self.pcm16_mono_samples, WAVEFORM_BUCKETS)
+    }
+
     pub fn ensure_wav_bytes(&mut self) -> Result<(), String> {
         if self.wav_bytes.is_none() {
             self.wav_bytes = Some(pcm16_to_wav_bytes(
@@ -465,6 +883,13 @@ impl RecordedAudio {
         Ok(())
     }
 
+    pub fn into_input_chunk(mut self) -> AudioInputChunk {
+        AudioInputChunk {
+            pcm16_mono_samples: std::mem::take(&mut self.pcm16_mono_samples),
+            sample_rate_hz: self.sample_rate_hz,
+        }
+    }
+
     pub fn into_wav_bytes(mut self) -> Result<Vec<u8>, String> {
         self.ensure_wav_bytes()?;
         self.pcm16_mono_samples.clear();
@@ -472,8 +897,9 @@ impl RecordedAudio {
     }
 }
 
-trait StreamController {
+pub trait StreamController {
     fn pause_stream(&self) -> Result<(), String>;
+    fn resume_stream(&self) -> Result<(), String>;
 }
 
 impl StreamController for Stream {
@@ -481,6 +907,11 @@ impl StreamController for Stream {
         self.pause()
             .map_err(|err| format!("Failed to pause microphone stream: {err}"))
     }
+
+    fn resume_stream(&self) -> Result<(), String> {
+        self.play()
+            .map_err(|err| format!("Failed to resume microphone stream: {err}"))
+    }
 }
 
 impl Drop for RecordedAudio {
@@ -561,27 +992,27 @@ fn spawn_worker_reaper_after_startup_timeout(handle: JoinHandle<()>, timeout: Du
 }
 
 fn recording_thread_main(
+    backend: Arc<dyn AudioBackend>,
     preferred_device_id: Option<String>,
-    samples: Arc<Mutex<Vec<i16>>>,
-    audio_level_bits: Arc<AtomicU32>,
+    sink: CaptureSink,
     app_handle: AppHandle,
-    on_input_chunk: Option<AudioInputChunkCallback>,
     ready_tx: Sender<Result<RecordingRuntime, String>>,
     stop_rx: Receiver<()>,
+    resume_rx: Option<Receiver<()>>,
 ) {
     debug!(
+        backend = backend.name(),
         preferred_device_id = ?preferred_device_id.as_deref(),
         "microphone worker thread started"
     );
-    let startup_result = start_recording_worker(
-        preferred_device_id.as_deref(),
-        Arc::clone(&samples),
-        Arc::clone(&audio_level_bits),
-        on_input_chunk,
-    );
+    let startup_result = backend.open_input_stream(preferred_device_id.as_deref(), sink.clone());
 
-    let (stream, runtime, stream_error_rx) = match startup_result {
-        Ok(started) => started,
+    let OpenedInputStream {
+        stream,
+        mut runtime,
+        stream_error_rx,
+    } = match startup_result {
+        Ok(opened) => opened,
         Err(err) => {
             error!(
                 preferred_device_id = ?preferred_device_id.as_deref(),
@@ -593,22 +1024,66 @@ fn recording_thread_main(
         }
     };
 
-    let _ = ready_tx.send(Ok(runtime));
-    let mut last_emitted_level: Option<f32> = None;
+    if let Some(notice) = runtime.bluetooth_notice.take() {
+        if let Err(error) = app_handle.emit(BLUETOOTH_MICROPHONE_EVENT, notice) {
+            warn!(%error, "failed to emit bluetooth microphone event");
+        }
+    }
+
+    if let Some(resume_rx) = resume_rx {
+        if let Err(error) = stream.pause_stream() {
+            error!(%error, "failed to pause prewarmed microphone stream");
+            let _ = ready_tx.send(Err(error));
+            return;
+        }
+        let _ = ready_tx.send(Ok(runtime));
+        // A dropped sender means the standby was discarded before any recording claimed it.
+        if resume_rx.recv().is_err() {
+            drop(stream);
+            debug!("prewarmed microphone worker exited without resuming");
+            return;
+        }
+        if let Err(error) = stream.resume_stream() {
+            drop(stream);
+            error!(%error, "failed to resume prewarmed microphone stream");
+            sink.reset_level();
+            let payload = AudioInputStreamErrorEvent { message: error };
+            if let Err(error) = app_handle.emit(AUDIO_INPUT_STREAM_ERROR_EVENT, payload) {
+                warn!(%error, "failed to emit audio stream error event");
+            }
+            return;
+        }
+    } else {
+        let _ = ready_tx.send(Ok(runtime));
+    }
+
+    let mut last_emitted_level: Option<AudioLevel> = None;
     let loop_exit = run_recording_loop(&stop_rx, &stream_error_rx, || {
-        let level =
-            quantize_audio_level_for_emit(f32::from_bits(audio_level_bits.load(Ordering::Relaxed)));
-        if last_emitted_level.is_some_and(|last| (last - level).abs() < f32::EPSILON) {
+        if sink.is_standby() {
+            return;
+        }
+        let level = sink.take_level().quantized();
+        if last_emitted_level == Some(level) {
             return;
         }
         last_emitted_level = Some(level);
         let _ = app_handle.emit(AUDIO_LEVEL_EVENT, level);
     });
 
-    pause_stream_before_release(&stream);
+    pause_stream_before_release(stream.as_ref());
     drop(stream);
-    audio_level_bits.store(0.0_f32.to_bits(), Ordering::Relaxed);
-    if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, 0.0_f32) {
+    if sink.is_standby() {
+        match loop_exit {
+            RecordingLoopExit::StreamError(message) => {
+                warn!(message = %message, "microphone standby exited due to stream error");
+            }
+            RecordingLoopExit::StopRequested => debug!("microphone standby worker exited"),
+        }
+        return;
+    }
+
+    sink.reset_level();
+    if let Err(error) = app_handle.emit(AUDIO_LEVEL_EVENT, AudioLevel::default()) {
         warn!(%error, "failed to emit audio level reset from worker thread");
     }
 
@@ -623,108 +1098,6 @@ fn recording_thread_main(
     }
 }
 
-fn start_recording_worker(
-    preferred_device_id: Option<&str>,
-    samples: Arc<Mutex<Vec<i16>>>,
-    audio_level_bits: Arc<AtomicU32>,
-    on_input_chunk: Option<AudioInputChunkCallback>,
-) -> Result<(Stream, RecordingRuntime, Receiver<String>), String> {
-    let host = cpal::default_host();
-    let default_input_device = host.default_input_device();
-    let default_input_device_name = default_input_device
-        .as_ref()
-        .and_then(|device| device.name().ok());
-    let devices = enumerate_input_devices(&host)?;
-    if devices.is_empty() {
-        return Err("No microphone input devices are available".to_string());
-    }
-
-    let selected_device = select_input_device(
-        devices,
-        preferred_device_id,
-        default_input_device_name.as_deref(),
-    )?;
-    let EnumeratedInputDevice {
-        id: selected_device_id,
-        name: selected_device_name,
-        is_default: selected_is_default,
-        sample_rate_hz: _,
-        channels: _,
-        device: enumerated_device,
-    } = selected_device;
-    let (input_device, using_host_default_handle) =
-        prefer_default_device_handle(enumerated_device, selected_is_default, default_input_device);
-    if using_host_default_handle {
-        debug!(
-            device_id = %selected_device_id,
-            device_name = %selected_device_name,
-            "using host default microphone handle for recording"
-        );
-    } else if selected_is_default {
-        warn!(
-            device_id = %selected_device_id,
-            device_name = %selected_device_name,
-            "selected default microphone but host default handle was unavailable; using enumerated device handle"
-        );
-    }
-    info!(
-        device_id = %selected_device_id,
-        device_name = %selected_device_name,
-        "starting recording worker for selected device"
-    );
-
-    let supported_config = input_device.default_input_config().map_err(|err| {
-        format!(
-            "Failed to read default input config for '{}': {err}",
-            selected_device_name
-        )
-    })?;
-
-    let stream_config: StreamConfig = supported_config.clone().into();
-    let sample_format = supported_config.sample_format();
-    let input_channels = usize::from(stream_config.channels);
-    let sample_rate_hz = stream_config.sample_rate.0;
-
-    if let Ok(mut sample_buffer) = samples.lock() {
-        sample_buffer.clear();
-        sample_buffer.reserve(usize::try_from(sample_rate_hz).unwrap_or(48_000) * 10);
-    }
-
-    let (stream_error_tx, stream_error_rx) = mpsc::channel::<String>();
-
-    let stream = build_input_stream(
-        &input_device,
-        &stream_config,
-        sample_format,
-        input_channels,
-        sample_rate_hz,
-        samples,
-        audio_level_bits,
-        on_input_chunk,
-        stream_error_tx,
-    )?;
-
-    stream
-        .play()
-        .map_err(|err| format!("Failed to start microphone stream: {err}"))?;
-    info!(
-        sample_rate_hz,
-        channels = stream_config.channels,
-        "microphone stream playback started"
-    );
-
-    Ok((
-        stream,
-        RecordingRuntime {
-            sample_rate_hz,
-            channels: 1,
-            device_id: selected_device_id,
-            device_name: selected_device_name,
-        },
-        stream_error_rx,
-    ))
-}
-
 #[derive(Debug, PartialEq, Eq)]
 enum RecordingLoopExit {
     StopRequested,
@@ -761,7 +1134,7 @@ fn report_stream_error(format_label: &str, stream_error_tx: &Sender<String>, err
     error!(%message, "microphone stream callback error");
 }
 
-fn pause_stream_before_release<S: StreamController>(stream: &S) {
+fn pause_stream_before_release<S: StreamController + ?Sized>(stream: &S) {
     if let Err(error) = stream.pause_stream() {
         warn!(%error, "failed to pause microphone stream before release");
     }
@@ -824,6 +1197,10 @@ fn enumerate_input_devices(host: &cpal::Host) -> Result<Vec<EnumeratedInputDevic
             (Some(default_id), Some(device_id)) => default_id == device_id,
             _ => default_name.as_ref() == Some(&name),
         };
+        let transport = coreaudio_identity
+            .as_ref()
+            .and_then(|identity| identity.transport)
+            .unwrap_or_else(|| MicrophoneTransport::from_device_name(&name));
         let config = device.default_input_config().ok();
 
         devices.push(EnumeratedInputDevice {
@@ -832,6 +1209,7 @@ fn enumerate_input_devices(host: &cpal::Host) -> Result<Vec<EnumeratedInputDevic
             is_default,
             sample_rate_hz: config.as_ref().map(|c| c.sample_rate().0),
             channels: config.as_ref().map(|c| c.channels()),
+            transport,
             device,
         });
     }
@@ -844,7 +1222,8 @@ fn select_input_device(
     mut devices: Vec<EnumeratedInputDevice>,
     preferred_device_id: Option<&str>,
     default_device_name: Option<&str>,
-) -> Result<EnumeratedInputDevice, String> {
+    prefer_built_in: bool,
+) -> Result<(EnumeratedInputDevice, Option<BluetoothMicrophoneEvent>), String> {
     let candidates = devices
         .iter()
         .map(|device| InputDeviceSelectionCandidate {
@@ -853,8 +1232,33 @@ fn select_input_device(
             is_default: device.is_default,
         })
         .collect::<Vec<_>>();
-    let selected_index =
+    let mut selected_index =
         select_input_device_index(&candidates, preferred_device_id, default_device_name)?;
+
+    let mut bluetooth_notice = None;
+    if devices[selected_index].transport == MicrophoneTransport::Bluetooth {
+        let transports = devices
+            .iter()
+            .map(|device| device.transport)
+            .collect::<Vec<_>>();
+        let fallback_index = built_in_fallback_index(&transports).filter(|_| prefer_built_in);
+        let headset = &devices[selected_index];
+        warn!(
+            device_id = %headset.id,
+            device_name = %headset.name,
+            falls_back_to_built_in = fallback_index.is_some(),
+            "selected microphone is a bluetooth headset"
+        );
+        bluetooth_notice = Some(BluetoothMicrophoneEvent {
+            device_id: headset.id.clone(),
+            device_name: headset.name.clone(),
+            fallback_device_name: fallback_index.map(|index| devices[index].name.clone()),
+        });
+        if let Some(fallback_index) = fallback_index {
+            selected_index = fallback_index;
+        }
+    }
+
     let selected = devices.swap_remove(selected_index);
     debug!(
         device_id = %selected.id,
@@ -862,7 +1266,13 @@ fn select_input_device(
         is_default = selected.is_default,
         "selected microphone device"
     );
-    Ok(selected)
+    Ok((selected, bluetooth_notice))
+}
+
+fn built_in_fallback_index(transports: &[MicrophoneTransport]) -> Option<usize> {
+    transports
+        .iter()
+        .position(|transport| *transport == MicrophoneTransport::BuiltIn)
 }
 
 fn select_input_device_index(
@@ -1059,11 +1469,13 @@ fn macos_collect_coreaudio_device_identities_in_global_order(
         kCFStringEncodingUTF8, CFStringGetCString, CFStringGetCStringPtr, CFStringRef,
     };
     use coreaudio::sys::{
-        kAudioDevicePropertyDeviceUID, kAudioDevicePropertyStreams, kAudioHardwareNoError,
-        kAudioHardwarePropertyDevices, kAudioObjectPropertyElementMaster, kAudioObjectPropertyName,
-        kAudioObjectPropertyScopeGlobal, kAudioObjectPropertyScopeInput, kAudioObjectSystemObject,
-        AudioDeviceID, AudioObjectGetPropertyData, AudioObjectGetPropertyDataSize,
-        AudioObjectPropertyAddress,
+        kAudioDevicePropertyDeviceUID, kAudioDevicePropertyStreams,
+        kAudioDevicePropertyTransportType, kAudioDeviceTransportTypeBluetooth,
+        kAudioDeviceTransportTypeBluetoothLE, kAudioDeviceTransportTypeBuiltIn,
+        kAudioHardwareNoError, kAudioHardwarePropertyDevices, kAudioObjectPropertyElementMaster,
+        kAudioObjectPropertyName, kAudioObjectPropertyScopeGlobal, kAudioObjectPropertyScopeInput,
+        kAudioObjectSystemObject, AudioDeviceID, AudioObjectGetPropertyData,
+        AudioObjectGetPropertyDataSize, AudioObjectPropertyAddress,
     };
     use std::{ffi::CStr, mem, os::raw::c_char, ptr::null};
 
@@ -1170,6 +1582,40 @@ fn macos_collect_coreaudio_device_identities_in_global_order(
         value
     }
 
+    fn read_device_transport(device_id: AudioDeviceID) -> Option<MicrophoneTransport> {
+        let property_address = AudioObjectPropertyAddress {
+            mSelector: kAudioDevicePropertyTransportType,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMaster,
+        };
+        let mut transport_type = 0u32;
+        let mut data_size = mem::size_of::<u32>() as u32;
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                device_id,
+                &property_address as *const _,
+                0,
+                null(),
+                &mut data_size as *mut _,
+                &mut transport_type as *mut _ as *mut _,
+            )
+        };
+        if status != kAudioHardwareNoError as i32 {
+            return None;
+        }
+
+        let transport = if transport_type == kAudioDeviceTransportTypeBluetooth as u32
+            || transport_type == kAudioDeviceTransportTypeBluetoothLE as u32
+        {
+            MicrophoneTransport::Bluetooth
+        } else if transport_type == kAudioDeviceTransportTypeBuiltIn as u32 {
+            MicrophoneTransport::BuiltIn
+        } else {
+            MicrophoneTransport::Other
+        };
+        Some(transport)
+    }
+
     fn device_supports_input(device_id: AudioDeviceID) -> bool {
         let property_address = AudioObjectPropertyAddress {
             mSelector: kAudioDevicePropertyStreams,
@@ -1215,6 +1661,7 @@ fn macos_collect_coreaudio_device_identities_in_global_order(
             device_id: device_id as u32,
             uid,
             name,
+            transport: read_device_transport(device_id),
         });
     }
 
@@ -1289,30 +1736,18 @@ fn build_input_stream(
     sample_format: SampleFormat,
     input_channels: usize,
     sample_rate_hz: u32,
-    samples: Arc<Mutex<Vec<i16>>>,
-    audio_level_bits: Arc<AtomicU32>,
-    on_input_chunk: Option<AudioInputChunkCallback>,
+    sink: CaptureSink,
     stream_error_tx: Sender<String>,
 ) -> Result<Stream, String> {
     match sample_format {
         SampleFormat::F32 => {
-            let samples = Arc::clone(&samples);
-            let level_bits = Arc::clone(&audio_level_bits);
-            let on_input_chunk = on_input_chunk.clone();
+            let sink = sink.clone();
             let stream_error_tx = stream_error_tx.clone();
             device
                 .build_input_stream(
                     stream_config,
                     move |data: &[f32], _| {
-                        process_input_frames(
-                            data,
-                            input_channels,
-                            |sample| sample,
-                            &samples,
-                            &level_bits,
-                            sample_rate_hz,
-                            on_input_chunk.as_ref(),
-                        );
+                        sink.push_frames(data, input_channels, sample_rate_hz, |sample| sample);
                     },
                     move |err| {
                         report_stream_error("f32", &stream_error_tx, err);
@@ -1322,23 +1757,15 @@ fn build_input_stream(
                 .map_err(|err| format!("Failed to build f32 input stream: {err}"))
         }
         SampleFormat::I16 => {
-            let samples = Arc::clone(&samples);
-            let level_bits = Arc::clone(&audio_level_bits);
-            let on_input_chunk = on_input_chunk.clone();
+            let sink = sink.clone();
             let stream_error_tx = stream_error_tx.clone();
             device
                 .build_input_stream(
                     stream_config,
                     move |data: &[i16], _| {
-                        process_input_frames(
-                            data,
-                            input_channels,
-                            |sample| sample as f32 / i16::MAX as f32,
-                            &samples,
-                            &level_bits,
-                            sample_rate_hz,
-                            on_input_chunk.as_ref(),
-                        );
+                        sink.push_frames(data, input_channels, sample_rate_hz, |sample| {
+                            sample as f32 / i16::MAX as f32
+                        });
                     },
                     move |err| {
                         report_stream_error("i16", &stream_error_tx, err);
@@ -1348,23 +1775,15 @@ fn build_input_stream(
                 .map_err(|err| format!("Failed to build i16 input stream: {err}"))
         }
         SampleFormat::U16 => {
-            let samples = Arc::clone(&samples);
-            let level_bits = Arc::clone(&audio_level_bits);
-            let on_input_chunk = on_input_chunk.clone();
+            let sink = sink.clone();
             let stream_error_tx = stream_error_tx.clone();
             device
                 .build_input_stream(
                     stream_config,
                     move |data: &[u16], _| {
-                        process_input_frames(
-                            data,
-                            input_channels,
-                            |sample| (sample as f32 / u16::MAX as f32) * 2.0 - 1.0,
-                            &samples,
-                            &level_bits,
-                            sample_rate_hz,
-                            on_input_chunk.as_ref(),
-                        );
+                        sink.push_frames(data, input_channels, sample_rate_hz, |sample| {
+                            (sample as f32 / u16::MAX as f32) * 2.0 - 1.0
+                        });
                     },
                     move |err| {
                         report_stream_error("u16", &stream_error_tx, err);
@@ -1382,14 +1801,13 @@ fn build_input_stream(
 fn process_input_frames<T, F>(
     data: &[T],
     channels: usize,
-    to_f32: F,
+    mut to_mono: F,
     samples: &Arc<Mutex<Vec<i16>>>,
-    audio_level_bits: &Arc<AtomicU32>,
+    level_meter: &AudioLevelMeter,
     sample_rate_hz: u32,
     on_input_chunk: Option<&AudioInputChunkCallback>,
 ) where
-    T: Copy,
-    F: Fn(T) -> f32,
+    F: FnMut(&[T]) -> f32,
 {
     if channels == 0 {
         return;
@@ -1409,12 +1827,7 @@ fn process_input_frames<T, F>(
         sample_buffer.reserve(data.len() / channels);
 
         for frame in data.chunks_exact(channels) {
-            let mut mixed = 0.0_f32;
-            for &sample in frame {
-                mixed += to_f32(sample);
-            }
-
-            let normalized = (mixed / channels as f32).clamp(-1.0, 1.0);
+            let normalized = to_mono(frame).clamp(-1.0, 1.0);
             let mono_pcm16 = float_to_pcm16(normalized);
             sample_buffer.push(mono_pcm16);
             if let Some(chunk) = mono_chunk.as_mut() {
@@ -1437,8 +1850,7 @@ fn process_input_frames<T, F>(
     } else {
         (sum_squares / frame_count as f64).sqrt() as f32
     };
-    let level = peak.max(rms);
-    audio_level_bits.store(level.to_bits(), Ordering::Relaxed);
+    level_meter.record(peak, rms);
 
     if let (Some(callback), Some(pcm16_mono_samples)) = (on_input_chunk, mono_chunk) {
         if !pcm16_mono_samples.is_empty() {
@@ -1450,12 +1862,28 @@ fn process_input_frames<T, F>(
     }
 }
 
+fn waveform_peaks(samples: &[i16], buckets: usize) -> Vec<u8> {
+    let buckets = buckets.min(samples.len());
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * samples.len() / buckets;
+            let end = (bucket + 1) * samples.len() / buckets;
+            let peak = samples[start..end]
+                .iter()
+                .map(|sample| u32::from(sample.unsigned_abs()))
+                .max()
+                .unwrap_or(0);
+            ((peak * 255 + 16_384) / 32_768) as u8
+        })
+        .collect()
+}
+
 fn quantize_audio_level_for_emit(level: f32) -> f32 {
     let clamped = level.clamp(0.0, 1.0);
     (clamped * 100.0).round() / 100.0
 }
 
-fn float_to_pcm16(sample: f32) -> i16 {
+pub fn float_to_pcm16(sample: f32) -> i16 {
     let clamped = sample.clamp(-1.0, 1.0);
     if clamped <= -1.0 {
         i16::MIN
@@ -1466,7 +1894,7 @@ fn float_to_pcm16(sample: f32) -> i16 {
     }
 }
 
-fn pcm16_to_wav_bytes(
+pub fn pcm16_to_wav_bytes(
     samples: &[i16],
     sample_rate_hz: u32,
     channels: u16,
@@ -1540,11 +1968,12 @@ mod tests {
 
     use super::{
         await_worker_startup, build_macos_identity_lookup_by_name, build_microphone_device_id,
-        ensure_unique_device_id, float_to_pcm16, legacy_device_slug, pause_stream_before_release,
-        pcm16_to_wav_bytes, prefer_default_device_handle, quantize_audio_level_for_emit,
-        run_recording_loop, select_input_device_index, slugify_device_name,
-        take_macos_identity_by_device_name, InputDeviceSelectionCandidate,
-        MacosCoreAudioDeviceIdentity, RecordingLoopExit, RecordingRuntime, StreamController,
+        built_in_fallback_index, ensure_unique_device_id, float_to_pcm16, legacy_device_slug,
+        pause_stream_before_release, pcm16_to_wav_bytes, prefer_default_device_handle,
+        quantize_audio_level_for_emit, run_recording_loop, select_input_device_index,
+        slugify_device_name, take_macos_identity_by_device_name, waveform_peaks, AudioInputChunk,
+        InputDeviceSelectionCandidate, MacosCoreAudioDeviceIdentity, MicrophoneTransport,
+        RecordingLoopExit, RecordingRuntime, StreamController,
     };
 
     struct MockStreamController {
@@ -1557,6 +1986,11 @@ mod tests {
             self.paused.store(true, Ordering::Relaxed);
             self.pause_result.clone()
         }
+
+        fn resume_stream(&self) -> Result<(), String> {
+            self.paused.store(false, Ordering::Relaxed);
+            Ok(())
+        }
     }
 
     #[test]
@@ -1631,11 +2065,13 @@ mod tests {
                 device_id: 101,
                 uid: Some("uid-built-in".to_string()),
                 name: Some("Built-in Mic".to_string()),
+                transport: None,
             },
             MacosCoreAudioDeviceIdentity {
                 device_id: 102,
                 uid: Some("uid-usb".to_string()),
                 name: Some("USB Mic".to_string()),
+                transport: None,
             },
         ];
 
@@ -1656,11 +2092,13 @@ mod tests {
                 device_id: 201,
                 uid: Some("uid-a".to_string()),
                 name: Some("USB Mic".to_string()),
+                transport: None,
             },
             MacosCoreAudioDeviceIdentity {
                 device_id: 202,
                 uid: Some("uid-b".to_string()),
                 name: Some("USB Mic".to_string()),
+                transport: None,
             },
         ];
 
@@ -1750,6 +2188,47 @@ mod tests {
         assert_eq!(selected, 1);
     }
 
+    #[test]
+    fn bluetooth_fallback_picks_the_first_built_in_microphone() {
+        assert_eq!(
+            built_in_fallback_index(&[
+                MicrophoneTransport::Bluetooth,
+                MicrophoneTransport::Other,
+                MicrophoneTransport::BuiltIn,
+            ]),
+            Some(2)
+        );
+        assert_eq!(
+            built_in_fallback_index(&[MicrophoneTransport::Bluetooth]),
+            None
+        );
+        assert_eq!(
+            MicrophoneTransport::from_device_name("Sam's AirPods Pro"),
+            MicrophoneTransport::Bluetooth
+        );
+        assert_eq!(
+            MicrophoneTransport::from_device_name("Headset (WH-1000XM4 Hands-Free AG Audio)"),
+            MicrophoneTransport::Bluetooth
+        );
+        assert_eq!(
+            MicrophoneTransport::from_device_name("MacBook Pro Microphone"),
+            MicrophoneTransport::BuiltIn
+        );
+        assert_eq!(
+            MicrophoneTransport::from_device_name("USB Mic"),
+            MicrophoneTransport::Other
+        );
+    }
+
+    #[test]
+    fn waveform_keeps_the_loudest_sample_of_each_bucket() {
+        let samples = [0, 100, -16_384, 0, i16::MIN, 5, 0, 0];
+
+        assert_eq!(waveform_peaks(&samples, 4), vec![1, 128, 255, 0]);
+        assert_eq!(waveform_peaks(&samples[..2], 64), vec![0, 1]);
+        assert!(waveform_peaks(&[], 64).is_empty());
+    }
+
     #[test]
     fn float_to_pcm16_clamps_and_scales() {
         assert_eq!(float_to_pcm16(-1.5), i16::MIN);
@@ -1794,6 +2273,19 @@ mod tests {
         assert_eq!(encoded_third, samples[2]);
     }
 
+    #[test]
+    fn input_chunk_reports_duration_and_encodes_mono_wav() {
+        let chunk = AudioInputChunk {
+            pcm16_mono_samples: vec![0; 8_000],
+            sample_rate_hz: 16_000,
+        };
+
+        assert_eq!(chunk.duration_secs(), 0.5);
+        let wav = chunk.to_wav_bytes().expect("expected wav bytes");
+        assert_eq!(u16::from_le_bytes([wav[22], wav[23]]), 1);
+        assert_eq!(wav.len(), 44 + 16_000);
+    }
+
     #[test]
     fn recording_loop_returns_stream_error_when_callback_reports_error() {
         let (_stop_tx, stop_rx) = mpsc::channel::<()>();
@@ -0,0 +1,116 @@
+use std::{
+    sync::{
+        mpsc::{Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use tracing::{debug, error};
+
+use super::{CaptureSink, RecordingRuntime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandbyMode {
+    PreRoll(Duration),
+    Paused,
+}
+
+impl StandbyMode {
+    pub(super) fn pre_roll(self) -> Duration {
+        match self {
+            Self::PreRoll(duration) => duration,
+            Self::Paused => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct StandbyConfig {
+    pub(super) preferred_device_id: Option<String>,
+    pub(super) mode: StandbyMode,
+}
+
+pub(super) struct StandbyCapture {
+    pub(super) stop_tx: Sender<()>,
+    pub(super) resume_tx: Option<Sender<()>>,
+    pub(super) join_handle: JoinHandle<()>,
+    pub(super) ready_rx: Receiver<Result<RecordingRuntime, String>>,
+    pub(super) samples: Arc<Mutex<Vec<i16>>>,
+    pub(super) sink: CaptureSink,
+    pub(super) config: StandbyConfig,
+}
+
+impl StandbyCapture {
+    pub(super) fn is_usable_for(&self, preferred_device_id: Option<&str>) -> bool {
+        self.config.preferred_device_id.as_deref() == preferred_device_id
+            && !self.join_handle.is_finished()
+    }
+
+    pub(super) fn stop(self) {
+        let Self {
+            stop_tx,
+            resume_tx,
+            join_handle,
+            ..
+        } = self;
+        let _ = stop_tx.send(());
+        drop(resume_tx);
+        if join_handle.join().is_err() {
+            error!("standby microphone worker panicked while stopping");
+        } else {
+            debug!("standby microphone worker stopped");
+        }
+    }
+}
+
+pub(super) fn pre_roll_sample_count(pre_roll: Duration, sample_rate_hz: u32) -> usize {
+    (pre_roll.as_millis() * u128::from(sample_rate_hz) / 1000) as usize
+}
+
+pub(super) fn samples_duration(sample_count: usize, sample_rate_hz: u32) -> Duration {
+    if sample_rate_hz == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_secs_f64(sample_count as f64 / f64::from(sample_rate_hz))
+}
+
+pub(super) fn retain_latest_samples(samples: &mut Vec<i16>, keep: usize) {
+    if samples.len() > keep {
+        let excess = samples.len() - keep;
+        samples.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{pre_roll_sample_count, retain_latest_samples, samples_duration};
+
+    #[test]
+    fn retains_only_the_latest_pre_roll_window() {
+        let mut samples: Vec<i16> = (0..10).collect();
+
+        retain_latest_samples(&mut samples, 4);
+        assert_eq!(samples, vec![6, 7, 8, 9]);
+
+        retain_latest_samples(&mut samples, 8);
+        assert_eq!(samples, vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn converts_between_pre_roll_duration_and_sample_count() {
+        assert_eq!(
+            pre_roll_sample_count(Duration::from_millis(1_500), 16_000),
+            24_000
+        );
+        assert_eq!(
+            samples_duration(24_000, 16_000),
+            Duration::from_millis(1_500)
+        );
+        assert_eq!(samples_duration(100, 0), Duration::ZERO);
+    }
+}
@@ -1,17 +1,17 @@
 use serde::{Deserialize, Serialize};
 use std::{
-    fs,
-    io::Write,
-    path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    path::PathBuf,
+    sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 use tracing::debug;
 
-use crate::api_key_store::ApiKeyStore;
+use crate::{api_key_store::ApiKeyStore, persistence::JsonDocumentStore};
 
 const AUTH_CREDENTIALS_FILE_NAME: &str = "auth_credentials.json";
 const OPENAI_PROVIDER: &str = "openai";
+// Order used both to fall back when the selected method has no credentials and to cycle methods.
+pub const PROVIDER_ORDER: [AuthMethod; 2] = [AuthMethod::ApiKey, AuthMethod::ChatgptOauth];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -64,26 +64,24 @@ pub struct ChatGptStoredCredentials {
 
 #[derive(Debug, Clone)]
 pub struct AuthStore {
-    file_path: PathBuf,
-    io_lock: Arc<Mutex<()>>,
+    document: Arc<JsonDocumentStore<AuthCredentials>>,
 }
 
 impl AuthStore {
     pub fn new(app_data_dir: PathBuf) -> Self {
         let file_path = app_data_dir.join(AUTH_CREDENTIALS_FILE_NAME);
         debug!(path = %file_path.display(), "auth store initialized");
+        // Credentials are never reset automatically; a malformed file needs a fresh sign-in.
+        let document = JsonDocumentStore::new(file_path, "auth credentials").without_recovery();
         Self {
-            file_path,
-            io_lock: Arc::new(Mutex::new(())),
+            document: Arc::new(document),
         }
     }
 
     pub fn current(&self) -> Result<AuthCredentials, String> {
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "auth store lock poisoned".to_string())?;
-        self.read_credentials()
+        let _guard = self.document.lock()?;
+        self.document.ensure_exists()?;
+        self.document.read()
     }
 
     pub fn current_auth_method(&self) -> Result<AuthMethod, String> {
@@ -92,15 +90,39 @@ impl AuthStore {
 
     pub fn effective_auth_method(&self, api_key_store: &ApiKeyStore) -> Result<AuthMethod, String> {
         let mut credentials = self.current()?;
-        if credentials.auth_method == AuthMethod::None
-            && api_key_store.has_api_key(OPENAI_PROVIDER)?
-        {
-            credentials.auth_method = AuthMethod::ApiKey;
-            self.write_credentials(&credentials)?;
+        let configured = self.configured_methods(api_key_store)?;
+        if !configured.contains(&credentials.auth_method) {
+            if let Some(fallback) = configured.first() {
+                debug!(
+                    from = credentials.auth_method.as_str(),
+                    to = fallback.as_str(),
+                    "falling back to a configured auth method"
+                );
+                credentials.auth_method = *fallback;
+                self.document.write(&credentials)?;
+            }
         }
         Ok(credentials.auth_method)
     }
 
+    pub fn configured_methods(
+        &self,
+        api_key_store: &ApiKeyStore,
+    ) -> Result<Vec<AuthMethod>, String> {
+        let credentials = self.current()?;
+        let has_api_key =
+            credentials.api_key.is_some() || api_key_store.has_api_key(OPENAI_PROVIDER)?;
+        let has_chatgpt_login = resolve_chatgpt_credentials(&credentials).is_some();
+        Ok(PROVIDER_ORDER
+            .into_iter()
+            .filter(|method| match method {
+                AuthMethod::ApiKey => has_api_key,
+                AuthMethod::ChatgptOauth => has_chatgpt_login,
+                AuthMethod::None => false,
+            })
+            .collect())
+    }
+
     pub fn set_auth_method(&self, method: AuthMethod) -> Result<AuthCredentials, String> {
         self.with_update(|credentials| {
             credentials.auth_method = method;
@@ -197,57 +219,11 @@ impl AuthStore {
     where
         F: FnMut(&mut AuthCredentials) -> Result<(), String>,
     {
-        let mut credentials = self.current()?;
-        update(&mut credentials)?;
-        self.write_credentials(&credentials)?;
-        Ok(credentials)
-    }
-
-    fn ensure_file_exists(&self) -> Result<(), String> {
-        if let Some(parent_dir) = self.file_path.parent() {
-            fs::create_dir_all(parent_dir).map_err(|error| {
-                format!(
-                    "Failed to create auth credentials directory `{}`: {error}",
-                    parent_dir.display()
-                )
-            })?;
-        }
-
-        if self.file_path.exists() {
-            return Ok(());
-        }
-
-        let serialized = serde_json::to_vec_pretty(&AuthCredentials::default())
-            .map_err(|error| format!("Failed to serialize default auth credentials: {error}"))?;
-        write_atomic_file(&self.file_path, &serialized)
-    }
-
-    fn read_credentials(&self) -> Result<AuthCredentials, String> {
-        self.ensure_file_exists()?;
-        let raw_contents = fs::read_to_string(&self.file_path).map_err(|error| {
-            format!(
-                "Failed to read auth credentials file `{}`: {error}",
-                self.file_path.display()
-            )
-        })?;
-
-        if raw_contents.trim().is_empty() {
-            return Ok(AuthCredentials::default());
-        }
-
-        serde_json::from_str::<AuthCredentials>(&raw_contents).map_err(|error| {
-            format!(
-                "Failed to parse auth credentials file `{}`: {error}",
-                self.file_path.display()
-            )
+        self.document.update(|credentials| {
+            update(credentials)?;
+            Ok(credentials.clone())
         })
     }
-
-    fn write_credentials(&self, credentials: &AuthCredentials) -> Result<(), String> {
-        let serialized = serde_json::to_vec_pretty(credentials)
-            .map_err(|error| format!("Failed to serialize auth credentials: {error}"))?;
-        write_atomic_file(&self.file_path, &serialized)
-    }
 }
 
 pub fn now_epoch_seconds() -> u64 {
@@ -284,64 +260,10 @@ fn normalize_required_string(value: Option<String>, field_name: &str) -> Result<
     Ok(trimmed.to_string())
 }
 
-fn write_atomic_file(file_path: &Path, contents: &[u8]) -> Result<(), String> {
-    let temp_path = temp_file_path_for(file_path);
-    let mut temp_file = fs::OpenOptions::new()
-        .create_new(true)
-        .write(true)
-        .open(&temp_path)
-        .map_err(|error| {
-            format!(
-                "Failed to create temp auth credentials file `{}`: {error}",
-                temp_path.display()
-            )
-        })?;
-
-    if let Err(error) = temp_file.write_all(contents) {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Failed to write temp auth credentials file `{}`: {error}",
-            temp_path.display()
-        ));
-    }
-
-    if let Err(error) = temp_file.sync_all() {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Failed to flush temp auth credentials file `{}`: {error}",
-            temp_path.display()
-        ));
-    }
-
-    drop(temp_file);
-
-    fs::rename(&temp_path, file_path).map_err(|error| {
-        let _ = fs::remove_file(&temp_path);
-        format!(
-            "Failed to finalize auth credentials file `{}`: {error}",
-            file_path.display()
-        )
-    })?;
-
-    Ok(())
-}
-
-fn temp_file_path_for(file_path: &Path) -> PathBuf {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or(AUTH_CREDENTIALS_FILE_NAME);
-    let pid = std::process::id();
-
-    file_path.with_file_name(format!(".{file_name}.{pid}.{timestamp}.tmp"))
-}
-
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use super::*;
     use crate::api_key_store::ApiKeyStore;
 
@@ -406,4 +328,29 @@ mod tests {
             AuthMethod::ApiKey
         );
     }
+
+    #[test]
+    fn effective_auth_method_falls_back_to_any_configured_method() {
+        let app_data_dir = temp_app_data_dir("fallback");
+        let api_key_store = ApiKeyStore::new(app_data_dir.clone());
+        let store = AuthStore::new(app_data_dir);
+        store
+            .save_chatgpt_login("access", "refresh", 1234, "acct_1")
+            .expect("oauth login should persist");
+        store
+            .set_auth_method(AuthMethod::ApiKey)
+            .expect("auth method should persist");
+
+        let method = store
+            .effective_auth_method(&api_key_store)
+            .expect("effective method should resolve");
+
+        assert_eq!(method, AuthMethod::ChatgptOauth);
+        assert_eq!(
+            store
+                .configured_methods(&api_key_store)
+                .expect("configured methods should resolve"),
+            [AuthMethod::ChatgptOauth]
+        );
+    }
 }
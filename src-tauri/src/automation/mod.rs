@@ -0,0 +1,106 @@
+mod script_commands;
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tauri::{AppHandle, Listener, Manager};
+use tokio::sync::oneshot;
+use tracing::debug;
+
+use crate::{
+    cli,
+    history_store::HistoryStore,
+    hotkey_service::{HotkeyService, RecordingTransition},
+    EVENT_PIPELINE_ERROR, EVENT_TRANSCRIPT_READY,
+};
+
+pub use script_commands::register_script_commands;
+
+const STOP_TRANSCRIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub fn start_dictation(app: &AppHandle) -> Result<(), String> {
+    let hotkey_service = app.state::<HotkeyService>();
+    if !hotkey_service.request_recording_transition(app, RecordingTransition::Started) {
+        return Err("Buzz is already recording".to_string());
+    }
+    Ok(())
+}
+
+pub async fn stop_dictation(app: &AppHandle) -> Result<String, String> {
+    let (sender, receiver) = oneshot::channel();
+    let sender = Arc::new(Mutex::new(Some(sender)));
+    let listeners = [EVENT_TRANSCRIPT_READY, EVENT_PIPELINE_ERROR].map(|event_name| {
+        let sender = Arc::clone(&sender);
+        app.listen(event_name, move |event| {
+            let result = pipeline_event_result(event_name, event.payload());
+            if let Some(sender) = sender.lock().ok().and_then(|mut sender| sender.take()) {
+                let _ = sender.send(result);
+            }
+        })
+    });
+
+    let hotkey_service = app.state::<HotkeyService>();
+    let result = if hotkey_service.request_recording_transition(app, RecordingTransition::Stopped) {
+        match tokio::time::timeout(STOP_TRANSCRIPT_TIMEOUT, receiver).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err("Buzz stopped listening for the transcript".to_string()),
+            Err(_) => Err("Timed out waiting for Buzz to finish transcribing".to_string()),
+        }
+    } else {
+        Err("Buzz is not currently recording".to_string())
+    };
+    for listener in listeners {
+        app.unlisten(listener);
+    }
+    result
+}
+
+pub async fn transcribe_file(path: &Path) -> Result<String, String> {
+    debug!(file = %path.display(), "automation file transcription requested");
+    cli::transcribe_file_text(path).await
+}
+
+pub fn last_transcript(app: &AppHandle) -> Result<String, String> {
+    app.state::<HistoryStore>()
+        .list_entries(1, 0)?
+        .into_iter()
+        .next()
+        .map(|entry| entry.text)
+        .ok_or_else(|| "No transcripts yet".to_string())
+}
+
+fn pipeline_event_result(event_name: &str, payload: &str) -> Result<String, String> {
+    let payload = serde_json::from_str::<serde_json::Value>(payload).unwrap_or_default();
+    if event_name == EVENT_PIPELINE_ERROR {
+        return Err(payload["message"]
+            .as_str()
+            .unwrap_or("Buzz failed to transcribe the recording")
+            .to_string());
+    }
+    Ok(payload["text"].as_str().unwrap_or_default().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pipeline_event_result;
+    use crate::{EVENT_PIPELINE_ERROR, EVENT_TRANSCRIPT_READY};
+
+    #[test]
+    fn pipeline_events_resolve_to_transcripts_and_errors() {
+        assert_eq!(
+            pipeline_event_result(EVENT_TRANSCRIPT_READY, r#"{"text":"hello"}"#),
+            Ok("hello".to_string())
+        );
+        assert_eq!(
+            pipeline_event_result(EVENT_PIPELINE_ERROR, r#"{"message":"No speech"}"#),
+            Err("No speech".to_string())
+        );
+        assert_eq!(
+            pipeline_event_result(EVENT_PIPELINE_ERROR, "not json"),
+            Err("Buzz failed to transcribe the recording".to_string())
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    ffi::{c_char, CStr},
+    future::Future,
+    path::PathBuf,
+    ptr,
+    sync::OnceLock,
+};
+
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    rc::autoreleasepool,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+const NS_UTF8_STRING_ENCODING: usize = 4;
+const ERR_AE_EVENT_FAILED: isize = -10_000;
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+type PerformCommand = extern "C" fn(&Object, Sel) -> *mut Object;
+
+// Buzz.sdef names these classes, so they have to exist before the first script command arrives.
+#[allow(unexpected_cfgs)]
+pub fn register_script_commands(app: &AppHandle) {
+    if APP.set(app.clone()).is_err() {
+        return;
+    }
+
+    let commands: [(&str, PerformCommand); 4] = [
+        ("BuzzStartDictationCommand", perform_start_dictation),
+        ("BuzzStopDictationCommand", perform_stop_dictation),
+        ("BuzzTranscribeFileCommand", perform_transcribe_file),
+        ("BuzzLastTranscriptCommand", perform_last_transcript),
+    ];
+    for (name, perform) in commands {
+        let Some(mut decl) = ClassDecl::new(name, class!(NSScriptCommand)) else {
+            warn!(
+                class = name,
+                "applescript command class is already registered"
+            );
+            continue;
+        };
+        unsafe {
+            decl.add_method(sel!(performDefaultImplementation), perform);
+        }
+        decl.register();
+    }
+    info!("applescript commands registered");
+}
+
+extern "C" fn perform_start_dictation(command: &Object, _cmd: Sel) -> *mut Object {
+    let Some(app) = APP.get() else {
+        return ptr::null_mut();
+    };
+    if let Err(error) = super::start_dictation(app) {
+        unsafe { fail_command(command, &error) };
+    }
+    ptr::null_mut()
+}
+
+extern "C" fn perform_stop_dictation(command: &Object, _cmd: Sel) -> *mut Object {
+    let Some(app) = APP.get() else {
+        return ptr::null_mut();
+    };
+    let task_app = app.clone();
+    resume_when_done(app, command, async move {
+        super::stop_dictation(&task_app).await
+    });
+    ptr::null_mut()
+}
+
+#[allow(unexpected_cfgs)]
+extern "C" fn perform_transcribe_file(command: &Object, _cmd: Sel) -> *mut Object {
+    let Some(app) = APP.get() else {
+        return ptr::null_mut();
+    };
+    // A `file` direct parameter arrives as an NSURL.
+    let path = unsafe {
+        let url: *mut Object = msg_send![command, directParameter];
+        if url.is_null() {
+            None
+        } else {
+            let path: *mut Object = msg_send![url, path];
+            rust_string(path).map(PathBuf::from)
+        }
+    };
+    let Some(path) = path else {
+        unsafe { fail_command(command, "Choose an audio file to transcribe") };
+        return ptr::null_mut();
+    };
+
+    resume_when_done(
+        app,
+        command,
+        async move { super::transcribe_file(&path).await },
+    );
+    ptr::null_mut()
+}
+
+extern "C" fn perform_last_transcript(command: &Object, _cmd: Sel) -> *mut Object {
+    let Some(app) = APP.get() else {
+        return ptr::null_mut();
+    };
+    match super::last_transcript(app) {
+        Ok(text) => unsafe { ns_string(&text) },
+        Err(error) => {
+            unsafe { fail_command(command, &error) };
+            ptr::null_mut()
+        }
+    }
+}
+
+struct SuspendedCommand(*mut Object);
+
+// The command is retained until it is resumed, and it is only touched again on the main thread.
+unsafe impl Send for SuspendedCommand {}
+
+impl SuspendedCommand {
+    #[allow(unexpected_cfgs)]
+    unsafe fn resume(self, result: Result<String, String>) {
+        autoreleasepool(|| {
+            let value = match result {
+                Ok(text) => ns_string(&text),
+                Err(error) => {
+                    fail_command(&*self.0, &error);
+                    ptr::null_mut()
+                }
+            };
+            let _: () = msg_send![self.0, resumeExecutionWithResult: value];
+            let _: () = msg_send![self.0, release];
+        });
+    }
+}
+
+// Suspends the script command so the main thread stays free while `task` runs, then hands the
+// result back to AppleScript.
+#[allow(unexpected_cfgs)]
+fn resume_when_done(
+    app: &AppHandle,
+    command: &Object,
+    task: impl Future<Output = Result<String, String>> + Send + 'static,
+) {
+    let suspended = unsafe {
+        let command: *mut Object = msg_send![command, retain];
+        let _: () = msg_send![command, suspendExecution];
+        SuspendedCommand(command)
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = task.await;
+        if let Err(error) = app.run_on_main_thread(move || unsafe { suspended.resume(result) }) {
+            warn!(%error, "failed to resume applescript command on the main thread");
+        }
+    });
+}
+
+#[allow(unexpected_cfgs)]
+unsafe fn fail_command(command: &Object, message: &str) {
+    let message = ns_string(message);
+    let _: () = msg_send![command, setScriptErrorNumber: ERR_AE_EVENT_FAILED];
+    let _: () = msg_send![command, setScriptErrorString: message];
+}
+
+#[allow(unexpected_cfgs)]
+unsafe fn ns_string(value: &str) -> *mut Object {
+    let string: *mut Object = msg_send![class!(NSString), alloc];
+    let string: *mut Object = msg_send![
+        string,
+        initWithBytes: value.as_ptr()
+        length: value.len()
+        encoding: NS_UTF8_STRING_ENCODING
+    ];
+    msg_send![string, autorelease]
+}
+
+#[allow(unexpected_cfgs)]
+unsafe fn rust_string(value: *mut Object) -> Option<String> {
+    if value.is_null() {
+        return None;
+    }
+    let utf8: *const c_char = msg_send![value, UTF8String];
+    (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().into_owned())
+}
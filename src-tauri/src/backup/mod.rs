@@ -0,0 +1,207 @@
+use std::{fs, path::PathBuf};
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::{
+    history_store::{HistoryEntry, HistoryStore},
+    offline_queue::{self, OfflineQueue, PendingRecording},
+    persistence::JsonDocumentStore,
+    settings_store::VoiceSettings,
+    stats_store::{StatsStore, UsageStats},
+    AppState,
+};
+
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    pub history_entries: usize,
+    pub audio_recordings: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct BackupRecording {
+    recording: PendingRecording,
+    wav_base64: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct BackupArchive {
+    manifest: BackupManifest,
+    settings: VoiceSettings,
+    history: Vec<HistoryEntry>,
+    stats: UsageStats,
+    #[serde(default)]
+    audio: Vec<BackupRecording>,
+}
+
+impl BackupArchive {
+    fn parse(contents: &str) -> Result<Self, String> {
+        let value = serde_json::from_str::<serde_json::Value>(contents)
+            .map_err(|error| format!("Failed to parse backup file: {error}"))?;
+        let format_version = value
+            .pointer("/manifest/formatVersion")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| "Backup file has no manifest".to_string())?;
+        if format_version > u64::from(BACKUP_FORMAT_VERSION) {
+            return Err(format!(
+                "Backup format version {format_version} is newer than this version of Buzz supports ({BACKUP_FORMAT_VERSION})"
+            ));
+        }
+
+        serde_json::from_value(value)
+            .map_err(|error| format!("Failed to read backup file: {error}"))
+    }
+}
+
+#[tauri::command]
+pub fn create_backup(
+    app: AppHandle,
+    path: String,
+    include_audio: Option<bool>,
+) -> Result<BackupManifest, String> {
+    let include_audio = include_audio.unwrap_or(false);
+    info!(path = %path, include_audio, "backup requested");
+
+    let history = app.state::<HistoryStore>().all_entries()?;
+    let stats = app.state::<StatsStore>().snapshot()?;
+    let settings = app.state::<AppState>().services.settings_store.current();
+    let audio = if include_audio {
+        let queue = app.state::<OfflineQueue>();
+        queue
+            .list()?
+            .into_iter()
+            .map(|recording| {
+                let wav_base64 = BASE64_STANDARD.encode(queue.read_audio(&recording.id)?);
+                Ok(BackupRecording {
+                    recording,
+                    wav_base64,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    } else {
+        Vec::new()
+    };
+
+    let archive = BackupArchive {
+        manifest: BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            app_version: app.package_info().version.to_string(),
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            history_entries: history.len(),
+            audio_recordings: audio.len(),
+        },
+        settings,
+        history,
+        stats,
+        audio,
+    };
+    JsonDocumentStore::<BackupArchive>::new(PathBuf::from(&path), "backup")
+        .without_recovery()
+        .write(&archive)?;
+    info!(
+        path = %path,
+        history_entries = archive.manifest.history_entries,
+        audio_recordings = archive.manifest.audio_recordings,
+        "backup created"
+    );
+    Ok(archive.manifest)
+}
+
+#[tauri::command]
+pub fn restore_backup(app: AppHandle, path: String) -> Result<BackupManifest, String> {
+    info!(path = %path, "backup restore requested");
+    let contents = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read backup file `{path}`: {error}"))?;
+    let archive = BackupArchive::parse(&contents)?;
+
+    let recordings = archive
+        .audio
+        .into_iter()
+        .map(|audio| {
+            BASE64_STANDARD
+                .decode(audio.wav_base64)
+                .map(|wav_bytes| (audio.recording, wav_bytes))
+                .map_err(|error| format!("Failed to decode backed up recording audio: {error}"))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let settings = app
+        .state::<AppState>()
+        .services
+        .settings_store
+        .restore(&app, archive.settings)?;
+    app.state::<HistoryStore>()
+        .replace_entries(archive.history)?;
+    app.state::<StatsStore>().restore(&archive.stats)?;
+
+    let queue = app.state::<OfflineQueue>();
+    for (recording, wav_bytes) in recordings {
+        let id = recording.id.clone();
+        match queue.restore(recording, &wav_bytes) {
+            Ok(true) => {}
+            Ok(false) => info!(id = %id, "backed up recording is already queued"),
+            Err(error) => warn!(id = %id, %error, "failed to restore backed up recording"),
+        }
+    }
+    offline_queue::flush_in_background(&app);
+
+    crate::apply_restored_settings(&app, &settings);
+    info!(
+        path = %path,
+        app_version = %archive.manifest.app_version,
+        history_entries = archive.manifest.history_entries,
+        "backup restored"
+    );
+    Ok(archive.manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackupArchive, BackupManifest, BACKUP_FORMAT_VERSION};
+    use crate::{settings_store::VoiceSettings, stats_store::UsageStats};
+
+    fn archive(format_version: u32) -> BackupArchive {
+        BackupArchive {
+            manifest: BackupManifest {
+                format_version,
+                app_version: "0.1.0".to_string(),
+                created_at: "2026-01-01T00:00:00.000Z".to_string(),
+                history_entries: 0,
+                audio_recordings: 0,
+            },
+            settings: VoiceSettings::default(),
+            history: Vec::new(),
+            stats: UsageStats::default(),
+            audio: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse_round_trips_and_rejects_newer_formats() {
+        let current = archive(BACKUP_FORMAT_VERSION);
+        let serialized = serde_json::to_string(&current).expect("archive should serialize");
+        assert_eq!(
+            BackupArchive::parse(&serialized).expect("archive should parse"),
+            current
+        );
+
+        let newer = serde_json::to_string(&archive(BACKUP_FORMAT_VERSION + 1))
+            .expect("archive should serialize");
+        let error = BackupArchive::parse(&newer).expect_err("newer format should be rejected");
+        assert!(error.contains("newer"));
+
+        let error = BackupArchive::parse("{}").expect_err("manifest is required");
+        assert!(error.contains("manifest"));
+    }
+}
@@ -0,0 +1,890 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::{
+    api_key_store::ApiKeyStore,
+    auth_store::{AuthMethod, AuthStore},
+    history_store::{HistoryEntry, HistoryStore, TranscriptSummary},
+    http_api::HttpApiService,
+    resolve_transcription_prompt,
+    settings_store::{SettingsStore, VoiceSettings},
+    subtitles::{self, SubtitleOptions},
+    transcription::{
+        chatgpt::{ChatGptTranscriptionConfig, ChatGptTranscriptionProvider},
+        openai::{OpenAiTranscriptionConfig, OpenAiTranscriptionProvider},
+        TranscriptionOptions, TranscriptionOrchestrator, TranscriptionProvider,
+        TranscriptionSegment,
+    },
+    trigger_service::URL_SCHEME,
+};
+
+const APP_IDENTIFIER: &str = "com.sawyerhood.buzz";
+const TRANSCRIBE_COMMAND: &str = "transcribe";
+const START_COMMAND: &str = "start";
+const STOP_COMMAND: &str = "stop";
+const LAST_TRANSCRIPT_COMMAND: &str = "last-transcript";
+const EXIT_CODE_FAILURE: i32 = 1;
+const EXIT_CODE_USAGE: i32 = 2;
+const STOP_TRANSCRIPT_TIMEOUT: Duration = Duration::from_secs(120);
+const HISTORY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const SSE_EVENT_TRANSCRIPT_READY: &str = "transcript-ready";
+const SSE_EVENT_PIPELINE_ERROR: &str = "pipeline-error";
+const USAGE: &str = "Usage:
+  buzz transcribe <file> [--provider openai|chatgpt-oauth] [--format txt|srt|vtt|json]
+                  [--max-line-length <chars>] [--max-caption-secs <secs>]
+  buzz start
+  buzz stop [--format txt|srt|vtt|json]
+  buzz last-transcript [--format txt|srt|vtt|json]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CliProvider {
+    OpenAi,
+    ChatgptOauth,
+}
+
+impl CliProvider {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAi),
+            "chatgpt-oauth" | "chatgpt" => Ok(Self::ChatgptOauth),
+            other => Err(format!("Unsupported provider `{other}`")),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::ChatgptOauth => "chatgpt-oauth",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CliOutputFormat {
+    #[default]
+    Txt,
+    Srt,
+    Vtt,
+    Json,
+}
+
+impl CliOutputFormat {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "txt" | "text" => Ok(Self::Txt),
+            "srt" => Ok(Self::Srt),
+            "vtt" => Ok(Self::Vtt),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Unsupported output format `{other}`")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Transcribe {
+        file: PathBuf,
+        provider: Option<CliProvider>,
+        format: CliOutputFormat,
+        subtitles: SubtitleOptions,
+    },
+    StartDictation,
+    StopDictation {
+        format: CliOutputFormat,
+    },
+    LastTranscript {
+        format: CliOutputFormat,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CliTranscript {
+    text: String,
+    duration_secs: Option<f64>,
+    language: Option<String>,
+    provider: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    segments: Vec<TranscriptionSegment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<TranscriptSummary>,
+}
+
+impl From<HistoryEntry> for CliTranscript {
+    fn from(entry: HistoryEntry) -> Self {
+        Self {
+            text: entry.text,
+            duration_secs: entry.duration_secs,
+            language: entry.language,
+            provider: entry.provider,
+            segments: entry
+                .segments
+                .into_iter()
+                .map(|segment| TranscriptionSegment {
+                    start_secs: segment.start_secs,
+                    end_secs: segment.end_secs,
+                    text: segment.text,
+                    speaker: segment.speaker,
+                })
+                .collect(),
+            summary: entry.summary,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DictationResponse {
+    accepted: bool,
+}
+
+pub fn run_from_args(args: &[String]) -> Option<i32> {
+    let command = match parse_cli_args(args) {
+        Ok(Some(command)) => command,
+        Ok(None) => return None,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            return Some(EXIT_CODE_USAGE);
+        }
+    };
+
+    match tauri::async_runtime::block_on(execute(command)) {
+        Ok(output) => {
+            print!("{output}");
+            Some(0)
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            Some(EXIT_CODE_FAILURE)
+        }
+    }
+}
+
+fn parse_cli_args(args: &[String]) -> Result<Option<CliCommand>, String> {
+    let mut args = args.iter().skip(1);
+    let command = match args.next().map(String::as_str) {
+        Some(TRANSCRIBE_COMMAND) => parse_transcribe_args(args)?,
+        Some(START_COMMAND) => {
+            if let Some(extra) = args.next() {
+                return Err(format!("Unexpected argument `{extra}`"));
+            }
+            CliCommand::StartDictation
+        }
+        Some(STOP_COMMAND) => CliCommand::StopDictation {
+            format: parse_format_args(args)?,
+        },
+        Some(LAST_TRANSCRIPT_COMMAND) => CliCommand::LastTranscript {
+            format: parse_format_args(args)?,
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(command))
+}
+
+fn parse_transcribe_args<'a>(
+    mut args: impl Iterator<Item = &'a String>,
+) -> Result<CliCommand, String> {
+    let mut file: Option<PathBuf> = None;
+    let mut provider: Option<CliProvider> = None;
+    let mut format = CliOutputFormat::default();
+    let mut subtitles = SubtitleOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--provider" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --provider".to_string())?;
+                provider = Some(CliProvider::parse(value)?);
+            }
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --format".to_string())?;
+                format = CliOutputFormat::parse(value)?;
+            }
+            "--max-line-length" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --max-line-length".to_string())?;
+                subtitles.max_line_chars =
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|chars| *chars > 0)
+                        .ok_or_else(|| format!("Invalid --max-line-length `{value}`"))?;
+            }
+            "--max-caption-secs" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --max-caption-secs".to_string())?;
+                subtitles.max_caption_duration = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| *secs > 0.0)
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .ok_or_else(|| format!("Invalid --max-caption-secs `{value}`"))?;
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown option `{flag}`"));
+            }
+            path if file.is_none() => file = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument `{extra}`")),
+        }
+    }
+
+    let file = file.ok_or_else(|| "Missing audio file path".to_string())?;
+    Ok(CliCommand::Transcribe {
+        file,
+        provider,
+        format,
+        subtitles,
+    })
+}
+
+fn parse_format_args<'a>(
+    mut args: impl Iterator<Item = &'a String>,
+) -> Result<CliOutputFormat, String> {
+    let mut format = CliOutputFormat::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --format".to_string())?;
+                format = CliOutputFormat::parse(value)?;
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown option `{flag}`"));
+            }
+            extra => return Err(format!("Unexpected argument `{extra}`")),
+        }
+    }
+
+    Ok(format)
+}
+
+async fn execute(command: CliCommand) -> Result<String, String> {
+    match command {
+        CliCommand::Transcribe {
+            file,
+            provider,
+            format,
+            subtitles,
+        } => {
+            let timestamps = matches!(format, CliOutputFormat::Srt | CliOutputFormat::Vtt);
+            let transcript = transcribe_file(&file, provider, timestamps).await?;
+            render_transcript(&transcript, format, subtitles)
+        }
+        CliCommand::StartDictation => {
+            let Some(client) = AutomationClient::connect()? else {
+                open_trigger_url(START_COMMAND)?;
+                return Ok(String::new());
+            };
+            if !client.request_dictation(START_COMMAND).await? {
+                return Err("Buzz is already recording".to_string());
+            }
+            Ok(String::new())
+        }
+        CliCommand::StopDictation { format } => {
+            let transcript = match AutomationClient::connect()? {
+                Some(client) => client.stop_and_wait_for_transcript().await?,
+                None => stop_and_wait_for_history_entry().await?,
+            };
+            render_transcript(&transcript, format, SubtitleOptions::default())
+        }
+        CliCommand::LastTranscript { format } => {
+            let entry = latest_history_entry()?.ok_or_else(|| "No transcripts yet".to_string())?;
+            render_transcript(&entry.into(), format, SubtitleOptions::default())
+        }
+    }
+}
+
+// Without the HTTP API there is no event stream to wait on, so the recording is stopped through
+// the URL scheme and the transcript is picked up once it lands in history.
+async fn stop_and_wait_for_history_entry() -> Result<CliTranscript, String> {
+    let previous_id = latest_history_entry()?.map(|entry| entry.id);
+    open_trigger_url(STOP_COMMAND)?;
+
+    let wait_for_entry = async {
+        loop {
+            tokio::time::sleep(HISTORY_POLL_INTERVAL).await;
+            if let Some(entry) =
+                latest_history_entry()?.filter(|entry| Some(&entry.id) != previous_id.as_ref())
+            {
+                return Ok::<HistoryEntry, String>(entry);
+            }
+        }
+    };
+
+    let entry = tokio::time::timeout(STOP_TRANSCRIPT_TIMEOUT, wait_for_entry)
+        .await
+        .map_err(|_| {
+            "Timed out waiting for a new transcript in Buzz history. Enable the Buzz HTTP API to receive transcripts that are not saved to history."
+                .to_string()
+        })??;
+    Ok(entry.into())
+}
+
+fn latest_history_entry() -> Result<Option<HistoryEntry>, String> {
+    let history_store = HistoryStore::from_app_data_dir(&resolve_app_data_dir()?)?;
+    Ok(history_store.list_entries(1, 0)?.into_iter().next())
+}
+
+fn open_trigger_url(action: &str) -> Result<(), String> {
+    let url = format!("{URL_SCHEME}://{action}");
+    debug!(%url, "cli dictation request through url scheme");
+    tauri_plugin_opener::open_url(&url, None::<&str>)
+        .map_err(|error| format!("Failed to reach Buzz through `{url}`: {error}"))
+}
+
+struct AutomationClient {
+    client: reqwest::Client,
+    base_url: String,
+    token: String,
+    settings: VoiceSettings,
+}
+
+impl AutomationClient {
+    fn connect() -> Result<Option<Self>, String> {
+        let app_data_dir = resolve_app_data_dir()?;
+        let settings = SettingsStore::new()
+            .load_from_app_data_dir(&app_data_dir)
+            .unwrap_or_default();
+        if !settings.http_api_enabled {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            client: reqwest::Client::new(),
+            base_url: format!("http://127.0.0.1:{}", settings.http_api_port),
+            token: HttpApiService::new(app_data_dir).token()?,
+            settings,
+        }))
+    }
+
+    async fn request_dictation(&self, action: &str) -> Result<bool, String> {
+        debug!(action, "cli dictation request");
+        let response = self
+            .client
+            .post(format!("{}/v1/dictation/{action}", self.base_url))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|error| format!("Failed to reach Buzz: {error}"))?
+            .error_for_status()
+            .map_err(|error| format!("Buzz rejected the dictation request: {error}"))?;
+
+        response
+            .json::<DictationResponse>()
+            .await
+            .map(|response| response.accepted)
+            .map_err(|error| format!("Failed to parse Buzz response: {error}"))
+    }
+
+    async fn stop_and_wait_for_transcript(&self) -> Result<CliTranscript, String> {
+        let mut events = self
+            .client
+            .get(format!("{}/v1/events", self.base_url))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|error| format!("Failed to reach Buzz: {error}"))?
+            .error_for_status()
+            .map_err(|error| format!("Buzz rejected the event subscription: {error}"))?;
+
+        if !self.request_dictation(STOP_COMMAND).await? {
+            return Err("Buzz is not currently recording".to_string());
+        }
+
+        let wait_for_transcript = async {
+            let mut buffer = String::new();
+            loop {
+                let chunk = events
+                    .chunk()
+                    .await
+                    .map_err(|error| format!("Failed to read Buzz events: {error}"))?
+                    .ok_or_else(|| "Buzz closed the event stream".to_string())?;
+                buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                while let Some(frame_end) = buffer.find("\n\n") {
+                    let frame = buffer[..frame_end].to_string();
+                    buffer.drain(..frame_end + 2);
+                    if let Some(text) = transcript_from_sse_frame(&frame)? {
+                        return Ok::<String, String>(text);
+                    }
+                }
+            }
+        };
+
+        let text = tokio::time::timeout(STOP_TRANSCRIPT_TIMEOUT, wait_for_transcript)
+            .await
+            .map_err(|_| "Timed out waiting for Buzz to finish transcribing".to_string())??;
+
+        Ok(CliTranscript {
+            text,
+            duration_secs: None,
+            language: self.settings.language.clone(),
+            provider: self.settings.transcription_provider.clone(),
+            segments: Vec::new(),
+            summary: None,
+        })
+    }
+}
+
+fn transcript_from_sse_frame(frame: &str) -> Result<Option<String>, String> {
+    let mut event_name = None;
+    let mut data = Vec::new();
+    for line in frame.lines() {
+        if let Some(name) = line.strip_prefix("event: ") {
+            event_name = Some(name);
+        } else if let Some(line) = line.strip_prefix("data: ") {
+            data.push(line);
+        }
+    }
+
+    let payload = || serde_json::from_str::<serde_json::Value>(&data.join("\n")).ok();
+    match event_name {
+        Some(SSE_EVENT_TRANSCRIPT_READY) => Ok(Some(
+            payload()
+                .and_then(|value| value["text"].as_str().map(str::to_string))
+                .unwrap_or_default(),
+        )),
+        Some(SSE_EVENT_PIPELINE_ERROR) => Err(payload()
+            .and_then(|value| value["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| "Buzz failed to transcribe the recording".to_string())),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) async fn transcribe_file_text(file: &Path) -> Result<String, String> {
+    transcribe_file(file, None, false)
+        .await
+        .map(|transcript| transcript.text)
+}
+
+async fn transcribe_file(
+    file: &Path,
+    provider: Option<CliProvider>,
+    timestamps: bool,
+) -> Result<CliTranscript, String> {
+    let audio_bytes = std::fs::read(file)
+        .map_err(|error| format!("Failed to read audio file `{}`: {error}", file.display()))?;
+    let app_data_dir = resolve_app_data_dir()?;
+    let api_key_store = ApiKeyStore::new(app_data_dir.clone());
+    let auth_store = AuthStore::new(app_data_dir.clone());
+    let provider = match provider {
+        Some(provider) => provider,
+        None => resolve_default_provider(&auth_store, &api_key_store)?,
+    };
+
+    let settings = SettingsStore::new()
+        .load_from_app_data_dir(&app_data_dir)
+        .unwrap_or_default();
+    let options = TranscriptionOptions {
+        language: settings.language.clone(),
+        prompt: resolve_transcription_prompt(
+            &settings.transcription_style,
+            &settings.custom_transcription_prompt,
+        ),
+        timestamps,
+        ..TranscriptionOptions::default()
+    };
+
+    info!(
+        file = %file.display(),
+        provider = provider.as_str(),
+        audio_bytes = audio_bytes.len(),
+        "cli transcription requested"
+    );
+    let wav_duration_secs = wav_duration_secs(&audio_bytes);
+    let result = match provider {
+        CliProvider::OpenAi => {
+            let mut config = OpenAiTranscriptionConfig::from_env();
+            config.api_key_store_app_data_dir = Some(app_data_dir);
+            let orchestrator =
+                TranscriptionOrchestrator::new(Arc::new(OpenAiTranscriptionProvider::new(config)));
+            orchestrator.configure_model(settings.openai_transcription_model.as_deref());
+            orchestrator.transcribe(audio_bytes.into(), options).await
+        }
+        CliProvider::ChatgptOauth => {
+            ChatGptTranscriptionProvider::new(ChatGptTranscriptionConfig::from_env(), auth_store)
+                .transcribe(audio_bytes.into(), options)
+                .await
+        }
+    }
+    .map_err(|error| error.to_string())?;
+
+    Ok(CliTranscript {
+        text: result.text,
+        duration_secs: result.duration_secs.or(wav_duration_secs),
+        language: result.language,
+        provider: provider.as_str().to_string(),
+        segments: result.segments,
+        summary: None,
+    })
+}
+
+fn resolve_default_provider(
+    auth_store: &AuthStore,
+    api_key_store: &ApiKeyStore,
+) -> Result<CliProvider, String> {
+    let auth_method = auth_store
+        .effective_auth_method(api_key_store)
+        .map_err(|error| format!("Failed to resolve active auth method: {error}"))?;
+    debug!(
+        auth_method = auth_method.as_str(),
+        "resolved cli provider from auth method"
+    );
+    match auth_method {
+        AuthMethod::ApiKey => Ok(CliProvider::OpenAi),
+        AuthMethod::ChatgptOauth => Ok(CliProvider::ChatgptOauth),
+        AuthMethod::None => Err(
+            "No authentication configured. Add an OpenAI API key or login with ChatGPT in Buzz."
+                .to_string(),
+        ),
+    }
+}
+
+fn resolve_app_data_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var_os("HOME")
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| "Failed to resolve home directory".to_string())?;
+
+    Ok(PathBuf::from(home_dir)
+        .join("Library")
+        .join("Application Support")
+        .join(APP_IDENTIFIER))
+}
+
+fn wav_duration_secs(bytes: &[u8]) -> Option<f64> {
+    if bytes.len() < 44 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let byte_rate = u32::from_le_bytes(bytes[28..32].try_into().ok()?);
+    if byte_rate == 0 {
+        return None;
+    }
+
+    let mut offset = 12usize;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_size = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().ok()?);
+        if chunk_id == b"data" {
+            return Some(f64::from(chunk_size) / f64::from(byte_rate));
+        }
+        offset = offset.checked_add(8 + usize::try_from(chunk_size).ok()?)?;
+    }
+
+    None
+}
+
+fn render_transcript(
+    transcript: &CliTranscript,
+    format: CliOutputFormat,
+    subtitles: SubtitleOptions,
+) -> Result<String, String> {
+    match format {
+        CliOutputFormat::Txt => Ok(match &transcript.summary {
+            Some(summary) => format!("{}\n\n{}", transcript.text, summary.to_markdown()),
+            None => format!("{}\n", transcript.text),
+        }),
+        CliOutputFormat::Srt => Ok(subtitles::render_srt(&subtitle_cues(transcript, subtitles))),
+        CliOutputFormat::Vtt => Ok(subtitles::render_vtt(&subtitle_cues(transcript, subtitles))),
+        CliOutputFormat::Json => serde_json::to_string_pretty(transcript)
+            .map(|json| format!("{json}\n"))
+            .map_err(|error| format!("Failed to serialize transcript: {error}")),
+    }
+}
+
+fn subtitle_cues(
+    transcript: &CliTranscript,
+    subtitles: SubtitleOptions,
+) -> Vec<subtitles::SubtitleCue> {
+    if !transcript.segments.is_empty() {
+        return subtitles::build_cues(&transcript.segments, subtitles);
+    }
+
+    let whole_transcript = TranscriptionSegment {
+        start_secs: 0.0,
+        end_secs: transcript.duration_secs.unwrap_or(0.0).max(0.0),
+        text: transcript.text.clone(),
+        speaker: None,
+    };
+    subtitles::build_cues(&[whole_transcript], subtitles)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use crate::{
+        history_store::TranscriptSummary, subtitles::SubtitleOptions,
+        transcription::TranscriptionSegment,
+    };
+
+    use super::{
+        parse_cli_args, render_transcript, transcript_from_sse_frame, wav_duration_secs,
+        CliCommand, CliOutputFormat, CliProvider, CliTranscript,
+    };
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn transcript() -> CliTranscript {
+        CliTranscript {
+            text: "hello world".to_string(),
+            duration_secs: Some(2.5),
+            language: Some("en".to_string()),
+            provider: "openai".to_string(),
+            segments: Vec::new(),
+            summary: None,
+        }
+    }
+
+    #[test]
+    fn non_cli_arguments_fall_through_to_gui() {
+        assert_eq!(parse_cli_args(&args(&["buzz"])), Ok(None));
+        assert_eq!(
+            parse_cli_args(&args(&["buzz", "--start-dictation"])),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn transcribe_command_parses_provider_and_format() {
+        let command = parse_cli_args(&args(&[
+            "buzz",
+            "transcribe",
+            "clip.wav",
+            "--provider",
+            "chatgpt-oauth",
+            "--format",
+            "srt",
+        ]))
+        .expect("arguments should parse");
+
+        assert_eq!(
+            command,
+            Some(CliCommand::Transcribe {
+                file: PathBuf::from("clip.wav"),
+                provider: Some(CliProvider::ChatgptOauth),
+                format: CliOutputFormat::Srt,
+                subtitles: SubtitleOptions::default(),
+            })
+        );
+    }
+
+    #[test]
+    fn transcribe_command_parses_subtitle_limits() {
+        let command = parse_cli_args(&args(&[
+            "buzz",
+            "transcribe",
+            "clip.wav",
+            "--max-line-length",
+            "32",
+            "--max-caption-secs",
+            "4.5",
+        ]))
+        .expect("arguments should parse");
+
+        assert_eq!(
+            command,
+            Some(CliCommand::Transcribe {
+                file: PathBuf::from("clip.wav"),
+                provider: None,
+                format: CliOutputFormat::Txt,
+                subtitles: SubtitleOptions {
+                    max_line_chars: 32,
+                    max_caption_duration: Duration::from_millis(4_500),
+                },
+            })
+        );
+        assert!(parse_cli_args(&args(&[
+            "buzz",
+            "transcribe",
+            "a.wav",
+            "--max-line-length",
+            "0"
+        ]))
+        .is_err());
+        assert!(parse_cli_args(&args(&[
+            "buzz",
+            "transcribe",
+            "a.wav",
+            "--max-caption-secs",
+            "soon"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn transcribe_command_rejects_missing_file_and_unknown_options() {
+        assert!(parse_cli_args(&args(&["buzz", "transcribe"])).is_err());
+        assert!(parse_cli_args(&args(&["buzz", "transcribe", "a.wav", "--fast"])).is_err());
+        assert!(
+            parse_cli_args(&args(&["buzz", "transcribe", "a.wav", "--format", "doc"])).is_err()
+        );
+    }
+
+    #[test]
+    fn automation_verbs_parse_with_optional_format() {
+        assert_eq!(
+            parse_cli_args(&args(&["buzz", "start"])),
+            Ok(Some(CliCommand::StartDictation))
+        );
+        assert_eq!(
+            parse_cli_args(&args(&["buzz", "stop", "--format", "json"])),
+            Ok(Some(CliCommand::StopDictation {
+                format: CliOutputFormat::Json
+            }))
+        );
+        assert_eq!(
+            parse_cli_args(&args(&["buzz", "last-transcript"])),
+            Ok(Some(CliCommand::LastTranscript {
+                format: CliOutputFormat::Txt
+            }))
+        );
+        assert!(parse_cli_args(&args(&["buzz", "start", "now"])).is_err());
+        assert!(parse_cli_args(&args(&["buzz", "stop", "--wait"])).is_err());
+    }
+
+    #[test]
+    fn sse_frames_resolve_transcripts_and_errors() {
+        assert_eq!(
+            transcript_from_sse_frame("event: transcript-ready\ndata: {\"text\":\"hi\"}"),
+            Ok(Some("hi".to_string()))
+        );
+        assert_eq!(
+            transcript_from_sse_frame("event: pipeline-error\ndata: {\"message\":\"boom\"}"),
+            Err("boom".to_string())
+        );
+        assert_eq!(transcript_from_sse_frame(": keepalive"), Ok(None));
+        assert_eq!(
+            transcript_from_sse_frame("event: status-changed\ndata: \"idle\""),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn wav_duration_is_read_from_header() {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&36u32.to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&16_000u32.to_le_bytes());
+        wav.extend_from_slice(&32_000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&64_000u32.to_le_bytes());
+
+        assert_eq!(wav_duration_secs(&wav), Some(2.0));
+        assert_eq!(wav_duration_secs(b"not a wav file"), None);
+    }
+
+    #[test]
+    fn subtitle_formats_wrap_transcript_in_single_cue() {
+        assert_eq!(
+            render_transcript(
+                &transcript(),
+                CliOutputFormat::Srt,
+                SubtitleOptions::default()
+            )
+            .expect("srt should render"),
+            "1\n00:00:00,000 --> 00:00:02,500\nhello world\n"
+        );
+        assert_eq!(
+            render_transcript(
+                &transcript(),
+                CliOutputFormat::Vtt,
+                SubtitleOptions::default()
+            )
+            .expect("vtt should render"),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:02.500\nhello world\n"
+        );
+    }
+
+    #[test]
+    fn json_format_uses_camel_case_fields() {
+        let json = render_transcript(
+            &transcript(),
+            CliOutputFormat::Json,
+            SubtitleOptions::default(),
+        )
+        .expect("json should render");
+        let value: serde_json::Value = serde_json::from_str(&json).expect("json should parse");
+
+        assert_eq!(value["text"], "hello world");
+        assert_eq!(value["durationSecs"], 2.5);
+        assert_eq!(value["provider"], "openai");
+    }
+
+    #[test]
+    fn text_format_appends_summary_when_present() {
+        let mut summarized = transcript();
+        summarized.summary = Some(TranscriptSummary {
+            bullets: vec!["Greeting".to_string()],
+            action_items: Vec::new(),
+            model: "gpt-4o-mini".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        });
+
+        assert_eq!(
+            render_transcript(
+                &summarized,
+                CliOutputFormat::Txt,
+                SubtitleOptions::default()
+            )
+            .expect("txt should render"),
+            "hello world\n\n## Summary\n- Greeting\n"
+        );
+        assert_eq!(
+            render_transcript(
+                &transcript(),
+                CliOutputFormat::Json,
+                SubtitleOptions::default()
+            )
+            .map(|json| json.contains("summary")),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn subtitle_formats_use_timestamped_segments_when_available() {
+        let mut timed = transcript();
+        timed.segments = vec![
+            TranscriptionSegment {
+                start_secs: 0.0,
+                end_secs: 1.0,
+                text: "hello".to_string(),
+                speaker: None,
+            },
+            TranscriptionSegment {
+                start_secs: 1.5,
+                end_secs: 2.5,
+                text: "world".to_string(),
+                speaker: None,
+            },
+        ];
+
+        assert_eq!(
+            render_transcript(&timed, CliOutputFormat::Srt, SubtitleOptions::default())
+                .expect("srt should render"),
+            "1\n00:00:00,000 --> 00:00:01,000\nhello\n\n2\n00:00:01,500 --> 00:00:02,500\nworld\n"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tracing::{debug, info, warn};
+
+use crate::command_registry::{self, CommandAction, PaletteCommand};
+
+pub const PALETTE_WINDOW_LABEL: &str = "command-palette";
+
+const PALETTE_WINDOW_WIDTH: f64 = 520.0;
+const PALETTE_WINDOW_HEIGHT: f64 = 360.0;
+
+pub fn toggle_palette(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(PALETTE_WINDOW_LABEL) {
+        if window.is_visible().unwrap_or(false) {
+            hide_palette(app);
+            return;
+        }
+    }
+    show_palette(app);
+}
+
+pub fn show_palette(app: &AppHandle) {
+    let window = match app.get_webview_window(PALETTE_WINDOW_LABEL) {
+        Some(window) => window,
+        None => match WebviewWindowBuilder::new(
+            app,
+            PALETTE_WINDOW_LABEL,
+            WebviewUrl::App("palette.html".into()),
+        )
+        .title("Command Palette")
+        .inner_size(PALETTE_WINDOW_WIDTH, PALETTE_WINDOW_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible_on_all_workspaces(true)
+        .center()
+        .visible(false)
+        .build()
+        {
+            Ok(window) => {
+                info!("command palette window initialized");
+                window
+            }
+            Err(error) => {
+                warn!(%error, "command palette window initialization failed");
+                return;
+            }
+        },
+    };
+
+    if let Err(error) = window.show() {
+        warn!(%error, "failed to show command palette");
+    }
+    if let Err(error) = window.set_focus() {
+        warn!(%error, "failed to focus command palette");
+    }
+}
+
+fn hide_palette(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(PALETTE_WINDOW_LABEL) else {
+        return;
+    };
+    if let Err(error) = window.hide() {
+        warn!(%error, "failed to hide command palette");
+    }
+}
+
+#[tauri::command]
+pub fn search_palette_commands(app: AppHandle, query: String) -> Vec<PaletteCommand> {
+    command_registry::search(command_registry::commands(&app), &query)
+}
+
+#[tauri::command]
+pub async fn run_palette_command(
+    app: AppHandle,
+    action: CommandAction,
+    audio_bytes: Option<Vec<u8>>,
+) -> Result<(), String> {
+    hide_palette(&app);
+    command_registry::run(&app, action, audio_bytes).await
+}
+
+#[tauri::command]
+pub fn close_command_palette(app: AppHandle) {
+    hide_palette(&app);
+}
+
+#[derive(Debug, Default)]
+pub struct CommandPaletteShortcut {
+    registered: Mutex<Option<String>>,
+}
+
+impl CommandPaletteShortcut {
+    pub fn sync(&self, app: &AppHandle, shortcut: Option<&str>) {
+        let Ok(mut registered) = self.registered.lock() else {
+            warn!("command palette shortcut lock poisoned");
+            return;
+        };
+        if registered.as_deref() == shortcut {
+            return;
+        }
+
+        if let Some(previous) = registered.take() {
+            if let Err(error) = app.global_shortcut().unregister(previous.as_str()) {
+                warn!(%error, shortcut = %previous, "failed to unregister command palette shortcut");
+            }
+        }
+        let Some(shortcut) = shortcut else {
+            debug!("command palette shortcut cleared");
+            return;
+        };
+
+        let result = app
+            .global_shortcut()
+            .on_shortcut(shortcut, |app, _shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    toggle_palette(app);
+                }
+            });
+        match result {
+            Ok(()) => {
+                info!(shortcut, "command palette shortcut registered");
+                *registered = Some(shortcut.to_string());
+            }
+            Err(error) => {
+                warn!(%error, shortcut, "failed to register command palette shortcut");
+            }
+        }
+    }
+}
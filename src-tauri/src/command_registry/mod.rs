@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+use crate::{
+    accessibility_announcer, offline_queue,
+    privacy_mode::{self, PrivacyMode},
+    prompt_templates, provider_switch,
+    transcription::TranscriptionOptions,
+    window_manager::{self, WindowParams, WindowTarget},
+    AppState,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandAction {
+    StartDictation { template: Option<String> },
+    TranscribeFile,
+    OpenHistory,
+    OpenSettings,
+    SwitchProvider,
+    ToggleWakeWord,
+    TogglePrivacyMode,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteCommand {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub keywords: Vec<&'static str>,
+    pub needs_audio_file: bool,
+    pub action: CommandAction,
+}
+
+impl PaletteCommand {
+    fn new(title: impl Into<String>, action: CommandAction) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            keywords: Vec::new(),
+            needs_audio_file: false,
+            action,
+        }
+    }
+
+    fn subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    fn keywords(mut self, keywords: &[&'static str]) -> Self {
+        self.keywords = keywords.to_vec();
+        self
+    }
+
+    fn score(&self, query: &str) -> Option<u32> {
+        std::iter::once(self.title.as_str())
+            .chain(self.keywords.iter().copied())
+            .filter_map(|candidate| fuzzy_score(query, candidate))
+            .max()
+    }
+}
+
+pub fn commands(app: &AppHandle) -> Vec<PaletteCommand> {
+    let state = app.state::<AppState>();
+    let settings = state.services.settings_store.current();
+
+    let mut commands = vec![PaletteCommand::new(
+        "Start dictation",
+        CommandAction::StartDictation { template: None },
+    )
+    .subtitle("Default style")
+    .keywords(&["record", "talk"])];
+    commands.extend(settings.prompt_templates.iter().map(|template| {
+        PaletteCommand::new(
+            format!("Start dictation with {}", template.name),
+            CommandAction::StartDictation {
+                template: Some(template.name.clone()),
+            },
+        )
+        .subtitle("Prompt template")
+        .keywords(&["record", "profile"])
+    }));
+
+    let mut transcribe_file =
+        PaletteCommand::new("Transcribe audio file", CommandAction::TranscribeFile)
+            .subtitle("Copies the transcript to the clipboard")
+            .keywords(&["import", "wav"]);
+    transcribe_file.needs_audio_file = true;
+    commands.push(transcribe_file);
+    commands.push(PaletteCommand::new(
+        "Open history",
+        CommandAction::OpenHistory,
+    ));
+    commands.push(
+        PaletteCommand::new("Open settings", CommandAction::OpenSettings)
+            .keywords(&["preferences"]),
+    );
+
+    let next_provider = state
+        .services
+        .current_auth_method()
+        .and_then(|current| {
+            state
+                .services
+                .auth_store
+                .configured_methods(&state.services.api_key_store)
+                .map(|available| provider_switch::next_provider(current, &available))
+        })
+        .ok()
+        .flatten();
+    if let Some(next) = next_provider {
+        commands.push(
+            PaletteCommand::new(
+                format!("Transcribe with {}", provider_switch::provider_label(next)),
+                CommandAction::SwitchProvider,
+            )
+            .keywords(&["switch provider", "account"]),
+        );
+    }
+
+    let wake_word_title = if settings.wake_word_enabled {
+        "Pause hands-free listening"
+    } else {
+        "Resume hands-free listening"
+    };
+    commands.push(
+        PaletteCommand::new(wake_word_title, CommandAction::ToggleWakeWord)
+            .keywords(&["wake word", "pause"]),
+    );
+
+    let privacy_title = if app.state::<PrivacyMode>().is_active() {
+        "Turn off privacy mode"
+    } else {
+        "Turn on privacy mode"
+    };
+    commands.push(
+        PaletteCommand::new(privacy_title, CommandAction::TogglePrivacyMode)
+            .subtitle("Transcripts are inserted but not saved")
+            .keywords(&["incognito", "private"]),
+    );
+    commands
+}
+
+pub fn search(commands: Vec<PaletteCommand>, query: &str) -> Vec<PaletteCommand> {
+    let mut scored = commands
+        .into_iter()
+        .filter_map(|command| command.score(query).map(|score| (score, command)))
+        .collect::<Vec<_>>();
+    scored.sort_by(|(left, _), (right, _)| right.cmp(left));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let candidate = candidate.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut next_index = 0;
+    let mut previous_match = None;
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let index = next_index
+            + candidate
+                .get(next_index..)?
+                .iter()
+                .position(|c| *c == query_char)?;
+        score += 1;
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        if index > 0 && previous_match == Some(index - 1) {
+            score += 4;
+        }
+        previous_match = Some(index);
+        next_index = index + 1;
+    }
+    Some(score)
+}
+
+pub async fn run(
+    app: &AppHandle,
+    action: CommandAction,
+    audio_bytes: Option<Vec<u8>>,
+) -> Result<(), String> {
+    info!(?action, "palette command requested");
+    match action {
+        CommandAction::StartDictation { template } => {
+            prompt_templates::set_active_template(app, template)?;
+            prompt_templates::start_dictation_after_selection(app);
+        }
+        CommandAction::TranscribeFile => {
+            let audio_bytes =
+                audio_bytes.ok_or_else(|| "Choose an audio file to transcribe".to_string())?;
+            transcribe_file(app, audio_bytes).await?;
+        }
+        CommandAction::OpenHistory => {
+            window_manager::open(app, WindowTarget::History, WindowParams::default())
+        }
+        CommandAction::OpenSettings => {
+            window_manager::open(app, WindowTarget::Settings, WindowParams::default())
+        }
+        CommandAction::SwitchProvider => {
+            provider_switch::cycle_provider(app)?;
+        }
+        CommandAction::ToggleWakeWord => crate::toggle_wake_word(app),
+        CommandAction::TogglePrivacyMode => {
+            privacy_mode::toggle(app);
+        }
+    }
+    Ok(())
+}
+
+async fn transcribe_file(app: &AppHandle, audio_bytes: Vec<u8>) -> Result<(), String> {
+    let settings = app.state::<AppState>().services.settings_store.current();
+    let options = TranscriptionOptions {
+        language: settings.language.clone(),
+        prompt: crate::resolve_transcription_prompt_for_settings(&settings),
+        ..TranscriptionOptions::default()
+    };
+    let (text, _, provider) =
+        offline_queue::transcribe_with_active_provider(app, audio_bytes, options)
+            .await
+            .map_err(|error| error.to_string())?;
+    info!(
+        provider,
+        transcript_chars = text.chars().count(),
+        "audio file transcribed from palette"
+    );
+    app.state::<AppState>()
+        .services
+        .text_insertion_service
+        .copy_to_clipboard(&text)?;
+    accessibility_announcer::announce(app, "Copied to clipboard");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_score, search, CommandAction, PaletteCommand};
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_runs() {
+        assert_eq!(fuzzy_score("xyz", "Open history"), None);
+        assert_eq!(fuzzy_score("", "Open history"), Some(0));
+        assert!(fuzzy_score("oh", "Open history").unwrap() > fuzzy_score("oh", "Cohort").unwrap());
+        assert!(
+            fuzzy_score("his", "Open history").unwrap()
+                > fuzzy_score("hs", "Open history").unwrap()
+        );
+    }
+
+    #[test]
+    fn search_ranks_best_match_first_and_matches_keywords() {
+        let commands = vec![
+            PaletteCommand::new("Open settings", CommandAction::OpenSettings)
+                .keywords(&["preferences"]),
+            PaletteCommand::new("Open history", CommandAction::OpenHistory),
+            PaletteCommand::new("Transcribe audio file", CommandAction::TranscribeFile),
+        ];
+
+        let titles = |query: &str| {
+            search(commands.clone(), query)
+                .into_iter()
+                .map(|command| command.title)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(titles("hist"), ["Open history"]);
+        assert_eq!(titles("prefs"), ["Open settings"]);
+        assert_eq!(titles("").len(), 3);
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tracing::debug;
+
+use crate::{hotkey_service::FrontmostApplication, settings_store::VoiceSettings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CarryoverConfig {
+    pub enabled: bool,
+    pub window: Duration,
+    pub max_chars: usize,
+}
+
+impl CarryoverConfig {
+    pub fn from_settings(settings: &VoiceSettings) -> Self {
+        Self {
+            enabled: settings.context_carryover_enabled,
+            window: Duration::from_secs(u64::from(settings.context_carryover_window_secs)),
+            max_chars: settings.context_carryover_max_chars as usize,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CarriedTranscript {
+    tail: String,
+    app: Option<String>,
+    recorded_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct ContextCarryover {
+    last: Mutex<Option<CarriedTranscript>>,
+}
+
+impl ContextCarryover {
+    fn last(&self) -> MutexGuard<'_, Option<CarriedTranscript>> {
+        self.last.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn record(&self, config: &CarryoverConfig, app: Option<&FrontmostApplication>, text: &str) {
+        self.record_at(config, app, text, Instant::now());
+    }
+
+    fn record_at(
+        &self,
+        config: &CarryoverConfig,
+        app: Option<&FrontmostApplication>,
+        text: &str,
+        now: Instant,
+    ) {
+        let mut last = self.last();
+        if !config.enabled {
+            *last = None;
+            return;
+        }
+        let tail = transcript_tail(text, config.max_chars);
+        if tail.is_empty() {
+            return;
+        }
+        *last = Some(CarriedTranscript {
+            tail: tail.to_string(),
+            app: app_key(app),
+            recorded_at: now,
+        });
+    }
+
+    pub fn context_for(
+        &self,
+        config: &CarryoverConfig,
+        app: Option<&FrontmostApplication>,
+    ) -> Option<String> {
+        self.context_at(config, app, Instant::now())
+    }
+
+    fn context_at(
+        &self,
+        config: &CarryoverConfig,
+        app: Option<&FrontmostApplication>,
+        now: Instant,
+    ) -> Option<String> {
+        let mut last = self.last();
+        if !config.enabled {
+            *last = None;
+            return None;
+        }
+        let carried = last.as_ref()?;
+        if now.duration_since(carried.recorded_at) > config.window {
+            *last = None;
+            return None;
+        }
+        if carried.app != app_key(app) {
+            debug!("previous dictation went to another app; not carrying context");
+            return None;
+        }
+        // The window or length may have shrunk since this was recorded.
+        Some(transcript_tail(&carried.tail, config.max_chars).to_string())
+    }
+
+    pub fn clear(&self) {
+        *self.last() = None;
+    }
+}
+
+fn app_key(app: Option<&FrontmostApplication>) -> Option<String> {
+    let app = app?;
+    app.bundle_id
+        .as_deref()
+        .or(app.name.as_deref())
+        .map(str::to_lowercase)
+}
+
+fn transcript_tail(text: &str, max_chars: usize) -> &str {
+    let text = text.trim();
+    let char_count = text.chars().count();
+    if char_count <= max_chars {
+        return text;
+    }
+
+    let start = text
+        .char_indices()
+        .nth(char_count - max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    let tail = &text[start..];
+    let splits_word =
+        !text[..start].ends_with(char::is_whitespace) && !tail.starts_with(char::is_whitespace);
+    if splits_word {
+        if let Some(boundary) = tail.find(char::is_whitespace) {
+            return tail[boundary..].trim_start();
+        }
+    }
+    tail.trim_start()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{transcript_tail, CarryoverConfig, ContextCarryover};
+    use crate::hotkey_service::FrontmostApplication;
+
+    fn app(bundle_id: &str) -> FrontmostApplication {
+        FrontmostApplication {
+            bundle_id: Some(bundle_id.to_string()),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn tail_keeps_whole_words_within_the_limit() {
+        assert_eq!(transcript_tail("  short note  ", 50), "short note");
+        assert_eq!(
+            transcript_tail("Meeting with Anneliese about Kubernetes", 20),
+            "about Kubernetes"
+        );
+        assert_eq!(transcript_tail("the café menu", 7), "menu");
+        assert_eq!(transcript_tail("Supercalifragilistic", 5), "istic");
+    }
+
+    #[test]
+    fn carries_context_only_into_the_same_app_within_the_window() {
+        let config = CarryoverConfig {
+            enabled: true,
+            window: Duration::from_secs(60),
+            max_chars: 100,
+        };
+        let carryover = ContextCarryover::default();
+        let start = Instant::now();
+        let notes = app("com.apple.Notes");
+
+        carryover.record_at(
+            &config,
+            Some(&notes),
+            "Ask Siobhan about the Q3 rollout.",
+            start,
+        );
+
+        assert_eq!(
+            carryover
+                .context_at(&config, Some(&notes), start + Duration::from_secs(30))
+                .as_deref(),
+            Some("Ask Siobhan about the Q3 rollout.")
+        );
+        assert_eq!(
+            carryover.context_at(
+                &config,
+                Some(&app("com.tinyspeck.slackmacgap")),
+                start + Duration::from_secs(30)
+            ),
+            None
+        );
+        assert_eq!(
+            carryover.context_at(&config, Some(&notes), start + Duration::from_secs(61)),
+            None
+        );
+        assert_eq!(
+            carryover.context_at(&config, Some(&notes), start + Duration::from_secs(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn disabling_carryover_forgets_the_previous_transcript() {
+        let enabled = CarryoverConfig {
+            enabled: true,
+            window: Duration::from_secs(60),
+            max_chars: 100,
+        };
+        let disabled = CarryoverConfig {
+            enabled: false,
+            ..enabled
+        };
+        let carryover = ContextCarryover::default();
+        let now = Instant::now();
+
+        carryover.record_at(&enabled, None, "private notes", now);
+        assert_eq!(carryover.context_at(&disabled, None, now), None);
+        assert_eq!(carryover.context_at(&enabled, None, now), None);
+    }
+}
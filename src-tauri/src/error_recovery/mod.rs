@@ -0,0 +1,330 @@
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{debug, info, warn};
+
+use crate::{
+    accessibility_announcer,
+    audio_capture_service::{float_to_pcm16, pcm16_to_wav_bytes, RecordedAudio},
+    offline_queue,
+    settings_store::VoiceSettings,
+    status_notifier::{AppStatus, StatusSource},
+    transcription::TranscriptionOptions,
+    wake_word::WakeWordService,
+    AppState,
+};
+
+pub const EVENT_ERROR_RECOVERY: &str = "voice://error-recovery";
+pub const COMMAND_WINDOW: Duration = Duration::from_secs(8);
+const COMMAND_PROMPT: &str = "Retry. Copy instead. Cancel.";
+const STATUS_SOURCE: StatusSource = StatusSource::Command("error_recovery");
+
+const RETRY_PHRASES: [&str; 4] = ["retry", "try again", "again", "redo"];
+const COPY_PHRASES: [&str; 5] = [
+    "copy instead",
+    "copy",
+    "copy it",
+    "copy to clipboard",
+    "clipboard",
+];
+const CANCEL_PHRASES: [&str; 5] = ["cancel", "never mind", "nevermind", "dismiss", "stop"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryCommand {
+    Retry,
+    CopyInstead,
+    Cancel,
+}
+
+impl RecoveryCommand {
+    pub fn parse(text: &str) -> Option<Self> {
+        let normalized = text
+            .split_whitespace()
+            .map(|word| word.trim_matches(|character: char| !character.is_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let normalized = normalized.strip_prefix("please ").unwrap_or(&normalized);
+        if RETRY_PHRASES.contains(&normalized) {
+            Some(Self::Retry)
+        } else if COPY_PHRASES.contains(&normalized) {
+            Some(Self::CopyInstead)
+        } else if CANCEL_PHRASES.contains(&normalized) {
+            Some(Self::Cancel)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Retry => "retry",
+            Self::CopyInstead => "copy_instead",
+            Self::Cancel => "cancel",
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Recoverable {
+    Recording(RecordedAudio),
+    Transcript(String),
+}
+
+impl Recoverable {
+    fn commands(&self) -> &'static [RecoveryCommand] {
+        match self {
+            Self::Recording(_) => &[RecoveryCommand::Retry, RecoveryCommand::Cancel],
+            Self::Transcript(_) => &[
+                RecoveryCommand::Retry,
+                RecoveryCommand::CopyInstead,
+                RecoveryCommand::Cancel,
+            ],
+        }
+    }
+
+    fn prompt(&self) -> &'static str {
+        match self {
+            Self::Recording(_) => "Say retry or cancel",
+            Self::Transcript(_) => "Say retry, copy instead, or cancel",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorRecoveryEvent {
+    pub listening: bool,
+    pub commands: Vec<&'static str>,
+    pub command: Option<&'static str>,
+}
+
+#[derive(Debug, Default)]
+pub struct ErrorRecovery {
+    pending: Mutex<Option<Recoverable>>,
+}
+
+impl ErrorRecovery {
+    fn pending(&self) -> MutexGuard<'_, Option<Recoverable>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn keep_recording(&self, recorded_audio: RecordedAudio) {
+        *self.pending() = Some(Recoverable::Recording(recorded_audio));
+    }
+
+    pub fn keep_transcript(&self, transcript: &str) {
+        *self.pending() = Some(Recoverable::Transcript(transcript.to_string()));
+    }
+
+    pub fn clear(&self) {
+        *self.pending() = None;
+    }
+
+    fn take(&self) -> Option<Recoverable> {
+        self.pending().take()
+    }
+}
+
+pub fn offer(app: &AppHandle, settings: &VoiceSettings) {
+    let recovery = app.state::<ErrorRecovery>();
+    if !settings.voice_error_recovery {
+        recovery.clear();
+        return;
+    }
+    let Some((prompt, commands)) = recovery
+        .pending()
+        .as_ref()
+        .map(|pending| (pending.prompt(), pending.commands()))
+    else {
+        debug!("pipeline error left nothing to recover by voice");
+        return;
+    };
+
+    if let Err(error) = app
+        .state::<WakeWordService>()
+        .listen_for_command(COMMAND_WINDOW)
+    {
+        warn!(%error, "failed to open the spoken recovery command window");
+        return;
+    }
+    crate::sync_microphone_standby_with_settings(app, settings);
+    accessibility_announcer::announce(app, prompt);
+    emit_event(
+        app,
+        ErrorRecoveryEvent {
+            listening: true,
+            commands: commands.iter().map(RecoveryCommand::as_str).collect(),
+            command: None,
+        },
+    );
+}
+
+pub fn dismiss(app: &AppHandle) {
+    app.state::<ErrorRecovery>().clear();
+    if app.state::<WakeWordService>().cancel_command() {
+        debug!("spoken recovery command window dismissed");
+        crate::sync_microphone_standby_with_settings(
+            app,
+            &app.state::<AppState>().services.settings_store.current(),
+        );
+        emit_closed(app, None);
+    }
+}
+
+pub fn command_window_expired(app: &AppHandle) {
+    app.state::<ErrorRecovery>().clear();
+    emit_closed(app, None);
+}
+
+pub fn handle_command_utterance(app: &AppHandle, samples: &[f32], sample_rate_hz: u32) {
+    let pcm16: Vec<i16> = samples.iter().copied().map(float_to_pcm16).collect();
+    let wav_bytes = match pcm16_to_wav_bytes(&pcm16, sample_rate_hz, 1) {
+        Ok(wav_bytes) => wav_bytes,
+        Err(error) => {
+            warn!(%error, "failed to encode spoken recovery command");
+            command_window_expired(app);
+            return;
+        }
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let settings = app.state::<AppState>().services.settings_store.current();
+        let options = TranscriptionOptions {
+            language: settings.language,
+            prompt: Some(COMMAND_PROMPT.to_string()),
+            ..TranscriptionOptions::default()
+        };
+        let command =
+            match offline_queue::transcribe_with_active_provider(&app, wav_bytes, options).await {
+                Ok((text, _, _)) => RecoveryCommand::parse(&text),
+                Err(error) => {
+                    warn!(%error, "failed to transcribe spoken recovery command");
+                    None
+                }
+            };
+        match command {
+            Some(command) => run_command(&app, command),
+            None => {
+                info!("spoken recovery command not recognized");
+                accessibility_announcer::announce(&app, "Command not recognized");
+                command_window_expired(&app);
+            }
+        }
+    });
+}
+
+fn run_command(app: &AppHandle, command: RecoveryCommand) {
+    info!(
+        command = command.as_str(),
+        "running spoken recovery command"
+    );
+    let state = app.state::<AppState>();
+    if !matches!(
+        crate::get_status_from_state(&state),
+        AppStatus::Error | AppStatus::Idle
+    ) {
+        debug!("a new dictation started; ignoring spoken recovery command");
+        command_window_expired(app);
+        return;
+    }
+
+    let pending = app.state::<ErrorRecovery>().take();
+    match (command, pending) {
+        (RecoveryCommand::Cancel, _) => {
+            crate::set_status_for_state(app, &state, AppStatus::Idle, STATUS_SOURCE);
+            accessibility_announcer::announce(app, "Cancelled");
+        }
+        (RecoveryCommand::Retry, Some(Recoverable::Recording(recorded_audio))) => {
+            crate::retry_recording(app, recorded_audio);
+        }
+        (RecoveryCommand::Retry, Some(Recoverable::Transcript(transcript))) => {
+            let result =
+                crate::ensure_accessibility_permission_for_insertion(&state).and_then(|()| {
+                    accessibility_announcer::announce(app, "Inserting text");
+                    state
+                        .services
+                        .text_insertion_service
+                        .insert_text(&transcript)
+                });
+            finish_output(app, result);
+        }
+        (RecoveryCommand::CopyInstead, Some(Recoverable::Transcript(transcript))) => {
+            let result = state
+                .services
+                .text_insertion_service
+                .copy_to_clipboard(&transcript)
+                .inspect(|()| accessibility_announcer::announce(app, "Copied to clipboard"));
+            finish_output(app, result);
+        }
+        (RecoveryCommand::CopyInstead, _) => {
+            info!("no transcript to copy after the error");
+            accessibility_announcer::announce(app, "There is no text to copy");
+        }
+        (RecoveryCommand::Retry, None) => {
+            accessibility_announcer::announce(app, "Nothing to retry");
+        }
+    }
+    emit_closed(app, Some(command));
+}
+
+fn finish_output(app: &AppHandle, result: Result<(), String>) {
+    let state = app.state::<AppState>();
+    match result {
+        Ok(()) => crate::set_status_for_state(app, &state, AppStatus::Idle, STATUS_SOURCE),
+        Err(error) => {
+            warn!(%error, "spoken recovery command failed to output the transcript");
+            accessibility_announcer::announce(app, &format!("Error: {error}"));
+        }
+    }
+}
+
+fn emit_closed(app: &AppHandle, command: Option<RecoveryCommand>) {
+    emit_event(
+        app,
+        ErrorRecoveryEvent {
+            listening: false,
+            commands: Vec::new(),
+            command: command.as_ref().map(RecoveryCommand::as_str),
+        },
+    );
+}
+
+fn emit_event(app: &AppHandle, payload: ErrorRecoveryEvent) {
+    if let Err(error) = app.emit(EVENT_ERROR_RECOVERY, payload) {
+        warn!(%error, "failed to emit error recovery event");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RecoveryCommand;
+
+    #[test]
+    fn parses_only_whole_recovery_phrases() {
+        assert_eq!(
+            RecoveryCommand::parse("Retry."),
+            Some(RecoveryCommand::Retry)
+        );
+        assert_eq!(
+            RecoveryCommand::parse("Please try again!"),
+            Some(RecoveryCommand::Retry)
+        );
+        assert_eq!(
+            RecoveryCommand::parse("Copy instead."),
+            Some(RecoveryCommand::CopyInstead)
+        );
+        assert_eq!(
+            RecoveryCommand::parse("Never mind"),
+            Some(RecoveryCommand::Cancel)
+        );
+        assert_eq!(RecoveryCommand::parse("Retry the upload later"), None);
+        assert_eq!(RecoveryCommand::parse(""), None);
+    }
+}
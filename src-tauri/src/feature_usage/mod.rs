@@ -0,0 +1,191 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tracing::{debug, info, warn};
+
+use crate::{persistence::JsonDocumentStore, AppState};
+
+const FEATURE_USAGE_FILE_NAME: &str = "feature_usage.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureCategory {
+    Action,
+    Provider,
+    Error,
+}
+
+impl FeatureCategory {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Action => "action",
+            Self::Provider => "provider",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsage {
+    #[serde(default)]
+    pub actions: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub providers: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub errors: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub since: Option<String>,
+}
+
+impl FeatureUsage {
+    fn counters_mut(&mut self, category: FeatureCategory) -> &mut BTreeMap<String, u64> {
+        match category {
+            FeatureCategory::Action => &mut self.actions,
+            FeatureCategory::Provider => &mut self.providers,
+            FeatureCategory::Error => &mut self.errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsageReport {
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub usage: FeatureUsage,
+}
+
+#[derive(Debug)]
+pub struct FeatureUsageStore {
+    document: JsonDocumentStore<FeatureUsage>,
+}
+
+impl FeatureUsageStore {
+    pub fn new(app: &AppHandle) -> Result<Self, String> {
+        let app_data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|error| format!("Failed to resolve app data directory: {error}"))?;
+
+        Self::new_with_file_path(app_data_dir.join(FEATURE_USAGE_FILE_NAME))
+    }
+
+    pub fn new_with_file_path(file_path: PathBuf) -> Result<Self, String> {
+        debug!(path = %file_path.display(), "feature usage store initialized");
+        Ok(Self {
+            document: JsonDocumentStore::new(file_path, "feature usage"),
+        })
+    }
+
+    pub fn record(&self, category: FeatureCategory, name: &str) -> Result<(), String> {
+        self.document.update(|usage| {
+            let counter = usage
+                .counters_mut(category)
+                .entry(name.to_string())
+                .or_default();
+            *counter = counter.saturating_add(1);
+            usage
+                .since
+                .get_or_insert_with(|| Local::now().to_rfc3339_opts(SecondsFormat::Secs, false));
+            Ok(())
+        })
+    }
+
+    pub fn usage(&self) -> Result<FeatureUsage, String> {
+        let _guard = self.document.lock()?;
+        self.document.read()
+    }
+
+    pub fn reset(&self) -> Result<(), String> {
+        info!("resetting feature usage");
+        let _guard = self.document.lock()?;
+        self.document.write(&FeatureUsage::default())
+    }
+}
+
+pub fn record_if_enabled(app: &AppHandle, category: FeatureCategory, name: &str) {
+    let enabled = app
+        .state::<AppState>()
+        .services
+        .settings_store
+        .current()
+        .local_analytics_enabled;
+    if !enabled {
+        return;
+    }
+
+    if let Err(error) = app.state::<FeatureUsageStore>().record(category, name) {
+        warn!(category = category.as_str(), name, %error, "failed to record feature usage");
+    }
+}
+
+#[tauri::command]
+pub fn get_feature_usage(
+    state: State<'_, AppState>,
+    store: State<'_, FeatureUsageStore>,
+) -> Result<FeatureUsageReport, String> {
+    debug!("feature usage requested");
+    Ok(FeatureUsageReport {
+        enabled: state
+            .services
+            .settings_store
+            .current()
+            .local_analytics_enabled,
+        usage: store.usage()?,
+    })
+}
+
+#[tauri::command]
+pub fn reset_feature_usage(store: State<'_, FeatureUsageStore>) -> Result<(), String> {
+    store.reset()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use uuid::Uuid;
+
+    use super::{FeatureCategory, FeatureUsageStore};
+
+    fn test_store_path() -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("buzz-feature-usage-{}", Uuid::new_v4()))
+            .join("feature_usage.json")
+    }
+
+    #[test]
+    fn record_counts_each_category_separately_and_reset_clears() {
+        let path = test_store_path();
+        let store =
+            FeatureUsageStore::new_with_file_path(path.clone()).expect("store should initialize");
+
+        store
+            .record(FeatureCategory::Action, "start_recording")
+            .expect("action should record");
+        store
+            .record(FeatureCategory::Action, "start_recording")
+            .expect("action should record");
+        store
+            .record(FeatureCategory::Provider, "openai")
+            .expect("provider should record");
+        store
+            .record(FeatureCategory::Error, "NETWORK_UNAVAILABLE")
+            .expect("error should record");
+
+        let usage = store.usage().expect("usage should load");
+        assert_eq!(usage.actions.get("start_recording"), Some(&2));
+        assert_eq!(usage.providers.get("openai"), Some(&1));
+        assert_eq!(usage.errors.get("NETWORK_UNAVAILABLE"), Some(&1));
+        assert!(usage.since.is_some());
+
+        store.reset().expect("reset should succeed");
+        assert_eq!(
+            store.usage().expect("usage should load"),
+            Default::default()
+        );
+        let _ = std::fs::remove_dir_all(path.parent().expect("path should have a parent"));
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use serde::Serialize;
+
+use super::HistoryEntry;
+
+pub const EVENT_HISTORY_CHANGED: &str = "voice://history-changed";
+
+pub type HistoryChangeListener = Arc<dyn Fn(&HistoryChange) + Send + Sync + 'static>;
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryChange {
+    pub revision: u64,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+impl HistoryChange {
+    pub(super) fn added(id: &str) -> Self {
+        Self {
+            added: vec![id.to_string()],
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn updated(id: &str) -> Self {
+        Self {
+            updated: vec![id.to_string()],
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn deleted(ids: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            deleted: ids.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn between(before: &[HistoryEntry], after: &[HistoryEntry]) -> Self {
+        let before_by_id = before
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry))
+            .collect::<HashMap<_, _>>();
+        let mut change = Self::default();
+        for entry in after {
+            match before_by_id.get(entry.id.as_str()) {
+                None => change.added.push(entry.id.clone()),
+                Some(previous) if *previous != entry => change.updated.push(entry.id.clone()),
+                Some(_) => {}
+            }
+        }
+
+        let after_ids = after
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<HashSet<_>>();
+        change.deleted = before
+            .iter()
+            .filter(|entry| !after_ids.contains(entry.id.as_str()))
+            .map(|entry| entry.id.clone())
+            .collect();
+        change
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
@@ -1,20 +1,43 @@
 use std::{
-    fs,
-    io::Write,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fmt, fs,
     path::{Path, PathBuf},
-    sync::Mutex,
-    time::{SystemTime, UNIX_EPOCH},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
 };
 
 use chrono::{SecondsFormat, Utc};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::{
+    persistence::JsonDocumentStore,
+    write_behind::{WriteBehind, WriteBehindConfig},
+};
+
+mod changes;
+mod preview;
+mod query;
+mod sync;
+
+pub use changes::{HistoryChange, HistoryChangeListener, EVENT_HISTORY_CHANGED};
+pub use preview::{HistoryEntryPreview, DEFAULT_PREVIEW_CHARS};
+use query::HistoryCursor;
+pub use query::HistoryFilter;
+pub use sync::HistorySyncStatus;
+use sync::{merge, HistorySync, MergeOutcome, SyncRecord};
+
 const HISTORY_FILE_NAME: &str = "transcript_history.json";
 pub const MAX_HISTORY_PAGE_SIZE: usize = 200;
 pub const MAX_HISTORY_ENTRIES: usize = 500;
+const READING_WORDS_PER_MINUTE: u64 = 238;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -27,6 +50,153 @@ pub struct HistoryEntry {
     #[serde(default)]
     pub language: Option<String>,
     pub provider: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<HistorySegment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<TranscriptSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waveform: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, flatten)]
+    pub metrics: TranscriptMetrics,
+    #[serde(default, flatten)]
+    pub details: TranscriptionDetails,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<HistorySource>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistorySource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl HistorySource {
+    fn normalized(self) -> Option<Self> {
+        let source = Self {
+            app_name: normalize_optional(self.app_name),
+            bundle_id: normalize_optional(self.bundle_id),
+            window_title: normalize_optional(self.window_title),
+            url: normalize_optional(self.url).and_then(|url| page_url(&url)),
+        };
+        (source != Self::default()).then_some(source)
+    }
+
+    pub fn contains(&self, needle: &str) -> bool {
+        [&self.app_name, &self.window_title, &self.url]
+            .into_iter()
+            .flatten()
+            .any(|value| value.to_lowercase().contains(needle))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptMetrics {
+    #[serde(default)]
+    pub word_count: u64,
+    #[serde(default)]
+    pub char_count: u64,
+    #[serde(default)]
+    pub reading_time_secs: u64,
+}
+
+impl TranscriptMetrics {
+    pub fn for_transcript(text: &str, segments: &[HistorySegment]) -> Self {
+        let (word_count, char_count) = if segments.is_empty() {
+            (count_words(text), text.chars().count() as u64)
+        } else {
+            segments.iter().fold((0, 0), |(words, chars), segment| {
+                (
+                    words + count_words(&segment.text),
+                    chars + segment.text.chars().count() as u64,
+                )
+            })
+        };
+        Self {
+            word_count,
+            char_count,
+            reading_time_secs: (word_count * 60).div_ceil(READING_WORDS_PER_MINUTE),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySort {
+    #[default]
+    Newest,
+    Oldest,
+    Longest,
+    Shortest,
+}
+
+pub fn count_words(text: &str) -> u64 {
+    text.split_whitespace().count() as u64
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySegment {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    #[serde(default)]
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSummary {
+    pub bullets: Vec<String>,
+    #[serde(default)]
+    pub action_items: Vec<String>,
+    pub model: String,
+    pub created_at: String,
+}
+
+impl TranscriptSummary {
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("## Summary\n");
+        for bullet in &self.bullets {
+            markdown.push_str(&format!("- {bullet}\n"));
+        }
+        if !self.action_items.is_empty() {
+            markdown.push_str("\n## Action items\n");
+            for item in &self.action_items {
+                markdown.push_str(&format!("- [ ] {item}\n"));
+            }
+        }
+        markdown
+    }
 }
 
 impl HistoryEntry {
@@ -43,14 +213,95 @@ impl HistoryEntry {
             duration_secs,
             language: normalize_optional(language),
             provider: provider.trim().to_string(),
+            segments: Vec::new(),
+            summary: None,
+            raw_text: None,
+            updated_at: None,
+            waveform: Vec::new(),
+            metrics: TranscriptMetrics::default(),
+            details: TranscriptionDetails::default(),
+            source: None,
         }
+        .with_metrics()
+    }
+
+    pub fn modified_at(&self) -> &str {
+        self.updated_at.as_deref().unwrap_or(&self.timestamp)
+    }
+
+    pub fn with_raw_text(mut self, raw_text: Option<String>) -> Self {
+        self.raw_text = raw_text.filter(|raw_text| *raw_text != self.text);
+        self
+    }
+
+    pub fn with_details(mut self, details: TranscriptionDetails) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn with_source(mut self, source: Option<HistorySource>) -> Self {
+        self.source = source.and_then(HistorySource::normalized);
+        self
+    }
+
+    pub fn with_waveform(mut self, waveform: Vec<u8>) -> Self {
+        self.waveform = waveform;
+        self
+    }
+
+    pub fn with_segments(mut self, segments: Vec<HistorySegment>) -> Self {
+        self.text = render_segment_transcript(&segments);
+        self.segments = segments;
+        self.with_metrics()
+    }
+
+    fn with_metrics(mut self) -> Self {
+        self.metrics = TranscriptMetrics::for_transcript(&self.text, &self.segments);
+        self
     }
 }
 
-#[derive(Debug)]
+pub fn render_segment_transcript(segments: &[HistorySegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let timestamp = format_offset(segment.start_secs);
+            match segment.speaker.as_deref() {
+                Some(speaker) => format!("[{timestamp}] {speaker}: {}", segment.text),
+                None => format!("[{timestamp}] {}", segment.text),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn format_offset(secs: f64) -> String {
+    let total_secs = secs.max(0.0).floor() as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total_secs / 3600,
+        (total_secs / 60) % 60,
+        total_secs % 60
+    )
+}
+
 pub struct HistoryStore {
-    file_path: PathBuf,
-    io_lock: Mutex<()>,
+    document: JsonDocumentStore<Vec<HistoryEntry>>,
+    // Only locked while holding the document lock, so merges never race local edits.
+    sync: Mutex<Option<HistorySync>>,
+    revision: AtomicU64,
+    change_listener: Mutex<Option<HistoryChangeListener>>,
+    write_behind: Option<WriteBehind<Vec<HistoryEntry>>>,
+}
+
+impl fmt::Debug for HistoryStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoryStore")
+            .field("file_path", &self.document.file_path())
+            .field("revision", &self.revision())
+            .field("write_behind", &self.write_behind.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl HistoryStore {
@@ -60,19 +311,104 @@ impl HistoryStore {
             .app_data_dir()
             .map_err(|error| format!("Failed to resolve app data directory: {error}"))?;
 
+        let store =
+            Self::from_app_data_dir(&app_data_dir)?.with_write_behind(WriteBehindConfig::default());
+        let app = app.clone();
+        store.set_change_listener(Arc::new(move |change| {
+            if let Err(error) = app.emit(EVENT_HISTORY_CHANGED, change) {
+                warn!(%error, revision = change.revision, "failed to emit history changed event");
+            }
+        }));
+        Ok(store)
+    }
+
+    pub fn from_app_data_dir(app_data_dir: &Path) -> Result<Self, String> {
         let file_path = app_data_dir.join(HISTORY_FILE_NAME);
         debug!(path = %file_path.display(), "initializing history store");
         Self::new_with_file_path(file_path)
     }
 
     pub fn new_with_file_path(file_path: PathBuf) -> Result<Self, String> {
-        ensure_history_file(&file_path)?;
+        let document = JsonDocumentStore::new(file_path, "transcript history")
+            .with_normalize(normalize_entries);
+        document.ensure_exists()?;
         Ok(Self {
-            file_path,
-            io_lock: Mutex::new(()),
+            document,
+            sync: Mutex::new(None),
+            revision: AtomicU64::new(0),
+            change_listener: Mutex::new(None),
+            write_behind: None,
         })
     }
 
+    pub fn with_write_behind(mut self, config: WriteBehindConfig) -> Self {
+        self.write_behind = Some(WriteBehind::new(config));
+        self
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        self.flush_pending(true)
+    }
+
+    pub fn flush_if_due(&self) -> Result<(), String> {
+        self.flush_pending(false)
+    }
+
+    pub fn flush_on_crash(&self) {
+        let Some(_guard) = self.document.try_lock() else {
+            warn!("history store is busy; skipping crash flush");
+            return;
+        };
+        if let Err(error) = self.flush_pending_locked(true) {
+            warn!(%error, "failed to flush history on crash");
+        }
+    }
+
+    fn flush_pending(&self, force: bool) -> Result<(), String> {
+        let _guard = self.document.lock()?;
+        self.flush_pending_locked(force)
+    }
+
+    fn flush_pending_locked(&self, force: bool) -> Result<(), String> {
+        let Some(cache) = &self.write_behind else {
+            return Ok(());
+        };
+        let mut external_change = None;
+        let flushed = cache.flush_merging(Instant::now(), force, |entries, last_read| {
+            // Another process (the CLI, a second instance) may have written since we last
+            // read the file; replay our edits onto its current contents instead of dropping theirs.
+            let on_disk = self.document.read()?;
+            let merged = match last_read {
+                Some(last_read) if *last_read != on_disk => {
+                    let merged = merge_local_edits(last_read, entries, on_disk);
+                    external_change = Some(HistoryChange::between(entries, &merged));
+                    merged
+                }
+                _ => entries.clone(),
+            };
+            self.write_entries_to_disk(&merged)?;
+            Ok(merged)
+        })?;
+        if let Some(change) = external_change {
+            self.publish_change(change);
+        }
+        if flushed {
+            debug!(path = %self.document.file_path().display(), "flushed history entries");
+        }
+        Ok(())
+    }
+
+    pub fn set_change_listener(&self, listener: HistoryChangeListener) {
+        match self.change_listener.lock() {
+            Ok(mut current) => *current = Some(listener),
+            Err(_) => warn!("history change listener lock is poisoned"),
+        }
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Acquire)
+    }
+
     pub fn add_entry(&self, entry: HistoryEntry) -> Result<(), String> {
         validate_entry(&entry)?;
         debug!(
@@ -82,38 +418,72 @@ impl HistoryStore {
             "adding history entry"
         );
 
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "History store lock is poisoned".to_string())?;
+        let _guard = self.document.lock()?;
         let mut entries = self.read_entries()?;
 
+        let record = SyncRecord::entry(&entry);
+        let mut change = HistoryChange::added(&entry.id);
         let insert_at = entries.partition_point(|existing| existing.timestamp >= entry.timestamp);
         entries.insert(insert_at, entry);
         if entries.len() > MAX_HISTORY_ENTRIES {
-            let pruned_entries = entries.len() - MAX_HISTORY_ENTRIES;
-            entries.truncate(MAX_HISTORY_ENTRIES);
+            let pruned = entries.split_off(MAX_HISTORY_ENTRIES);
             info!(
-                pruned_entries,
+                pruned_entries = pruned.len(),
                 max_entries = MAX_HISTORY_ENTRIES,
                 "pruned oldest history entries"
             );
+            change.deleted = pruned.into_iter().map(|entry| entry.id).collect();
         }
 
-        self.write_entries(&entries)
+        self.write_entries(&entries)?;
+        self.mirror_to_sync_folder(&[record]);
+        self.publish_change(change);
+        // Dictations go to disk right away so the CLI and other readers see them.
+        if let Err(error) = self.flush_pending_locked(true) {
+            warn!(%error, "failed to write history entry to disk; will retry on next flush");
+        }
+        Ok(())
     }
 
     pub fn list_entries(&self, limit: usize, offset: usize) -> Result<Vec<HistoryEntry>, String> {
+        self.list_entries_sorted(limit, offset, HistorySort::Newest)
+    }
+
+    pub fn list_entries_sorted(
+        &self,
+        limit: usize,
+        offset: usize,
+        sort: HistorySort,
+    ) -> Result<Vec<HistoryEntry>, String> {
+        let filter = HistoryFilter {
+            sort,
+            ..HistoryFilter::default()
+        };
+        self.query_entries(&filter, limit, offset)
+    }
+
+    pub fn query_entries(
+        &self,
+        filter: &HistoryFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<HistoryEntry>, String> {
+        let compiled = filter.compile()?;
         if limit == 0 {
             return Ok(Vec::new());
         }
-        debug!(limit, offset, "listing history entries");
+        debug!(limit, offset, sort = ?filter.sort, "querying history entries");
 
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "History store lock is poisoned".to_string())?;
-        let entries = self.read_entries()?;
+        let _guard = self.document.lock()?;
+        let mut entries = self.read_entries()?;
+        entries.retain(|entry| compiled.matches(entry));
+        // Entries are stored newest first and the sorts are stable, so ties stay newest first.
+        match filter.sort {
+            HistorySort::Newest => {}
+            HistorySort::Oldest => entries.reverse(),
+            HistorySort::Longest => entries.sort_by_key(|entry| Reverse(entry.metrics.word_count)),
+            HistorySort::Shortest => entries.sort_by_key(|entry| entry.metrics.word_count),
+        }
 
         Ok(entries
             .into_iter()
@@ -122,12 +492,56 @@ impl HistoryStore {
             .collect())
     }
 
+    pub fn list_entry_previews(
+        &self,
+        filter: &HistoryFilter,
+        limit: usize,
+        offset: usize,
+        max_chars: usize,
+    ) -> Result<Vec<HistoryEntryPreview>, String> {
+        Ok(self
+            .query_entries(filter, limit, offset)?
+            .iter()
+            .map(|entry| HistoryEntryPreview::new(entry, max_chars))
+            .collect())
+    }
+
+    pub fn list_entries_after(
+        &self,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<HistoryPage, String> {
+        let cursor = cursor
+            .filter(|cursor| !cursor.trim().is_empty())
+            .map(HistoryCursor::decode)
+            .transpose()?;
+        let limit = limit.min(MAX_HISTORY_PAGE_SIZE);
+        debug!(limit, has_cursor = cursor.is_some(), "listing history page");
+
+        let _guard = self.document.lock()?;
+        let mut entries = self.read_entries()?;
+        entries.sort_by(|left, right| {
+            (right.timestamp.as_str(), right.id.as_str())
+                .cmp(&(left.timestamp.as_str(), left.id.as_str()))
+        });
+        let mut remaining = entries
+            .into_iter()
+            .filter(|entry| cursor.as_ref().is_none_or(|cursor| cursor.precedes(entry)));
+
+        let page = remaining.by_ref().take(limit).collect::<Vec<_>>();
+        let next_cursor = match (page.last(), remaining.next()) {
+            (Some(last), Some(_)) => Some(HistoryCursor::after(last).encode()),
+            _ => None,
+        };
+        Ok(HistoryPage {
+            entries: page,
+            next_cursor,
+        })
+    }
+
     pub fn get_entry(&self, id: &str) -> Result<Option<HistoryEntry>, String> {
         debug!(id, "fetching history entry");
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "History store lock is poisoned".to_string())?;
+        let _guard = self.document.lock()?;
         let entries = self.read_entries()?;
 
         Ok(entries.into_iter().find(|entry| entry.id == id))
@@ -135,10 +549,7 @@ impl HistoryStore {
 
     pub fn delete_entry(&self, id: &str) -> Result<bool, String> {
         info!(id, "deleting history entry");
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "History store lock is poisoned".to_string())?;
+        let _guard = self.document.lock()?;
         let mut entries = self.read_entries()?;
         let original_len = entries.len();
 
@@ -147,127 +558,289 @@ impl HistoryStore {
 
         if deleted {
             self.write_entries(&entries)?;
+            self.mirror_to_sync_folder(&[SyncRecord::tombstone(id)]);
+            self.publish_change(HistoryChange::deleted([id.to_string()]));
         }
 
         Ok(deleted)
     }
 
+    pub fn rename_speaker(
+        &self,
+        id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<HistoryEntry>, String> {
+        let from = from.trim();
+        let to = to.trim();
+        if from.is_empty() || to.is_empty() {
+            return Err("Speaker names cannot be empty".to_string());
+        }
+
+        info!(id, from, to, "renaming history speaker");
+        let _guard = self.document.lock()?;
+        let mut entries = self.read_entries()?;
+        let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) else {
+            return Ok(None);
+        };
+
+        let mut renamed = false;
+        for segment in entry.segments.iter_mut() {
+            if segment.speaker.as_deref() == Some(from) {
+                segment.speaker = Some(to.to_string());
+                renamed = true;
+            }
+        }
+        if !renamed {
+            return Err(format!("Speaker `{from}` does not appear in this entry"));
+        }
+
+        entry.text = render_segment_transcript(&entry.segments);
+        entry.metrics = TranscriptMetrics::for_transcript(&entry.text, &entry.segments);
+        entry.updated_at = Some(sync::now_timestamp());
+        let updated = entry.clone();
+        self.write_entries(&entries)?;
+        self.mirror_to_sync_folder(&[SyncRecord::entry(&updated)]);
+        self.publish_change(HistoryChange::updated(id));
+        Ok(Some(updated))
+    }
+
+    pub fn set_summary(
+        &self,
+        id: &str,
+        summary: TranscriptSummary,
+    ) -> Result<Option<HistoryEntry>, String> {
+        info!(id, "saving history summary");
+        let _guard = self.document.lock()?;
+        let mut entries = self.read_entries()?;
+        let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) else {
+            return Ok(None);
+        };
+
+        entry.summary = Some(summary);
+        entry.updated_at = Some(sync::now_timestamp());
+        let updated = entry.clone();
+        self.write_entries(&entries)?;
+        self.mirror_to_sync_folder(&[SyncRecord::entry(&updated)]);
+        self.publish_change(HistoryChange::updated(id));
+        Ok(Some(updated))
+    }
+
+    pub fn all_entries(&self) -> Result<Vec<HistoryEntry>, String> {
+        let _guard = self.document.lock()?;
+        self.read_entries()
+    }
+
+    pub fn replace_entries(&self, mut entries: Vec<HistoryEntry>) -> Result<(), String> {
+        entries.iter().try_for_each(validate_entry)?;
+        entries.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+        entries.truncate(MAX_HISTORY_ENTRIES);
+        info!(entries = entries.len(), "replacing history entries");
+
+        let _guard = self.document.lock()?;
+        let previous = self.read_entries()?;
+        self.write_entries(&entries)?;
+        self.mirror_to_sync_folder(&entries.iter().map(SyncRecord::entry).collect::<Vec<_>>());
+        self.publish_change(HistoryChange::between(&previous, &entries));
+        Ok(())
+    }
+
+    pub fn disk_usage_bytes(&self) -> u64 {
+        fs::metadata(self.document.file_path())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default()
+    }
+
     pub fn clear_history(&self) -> Result<(), String> {
         info!("clearing history entries");
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "History store lock is poisoned".to_string())?;
-        self.write_entries(&[])
+        let _guard = self.document.lock()?;
+        let cleared = self.read_entries()?;
+        let tombstones = cleared
+            .iter()
+            .map(|entry| SyncRecord::tombstone(&entry.id))
+            .collect::<Vec<_>>();
+        self.write_entries(&[])?;
+        self.mirror_to_sync_folder(&tombstones);
+        self.publish_change(HistoryChange::deleted(
+            cleared.into_iter().map(|entry| entry.id),
+        ));
+        Ok(())
     }
 
-    fn read_entries(&self) -> Result<Vec<HistoryEntry>, String> {
-        if !self.file_path.exists() {
-            return Ok(Vec::new());
+    pub fn configure_sync(&self, folder: Option<&Path>) -> Result<(), String> {
+        let _guard = self.document.lock()?;
+        let mut sync = self
+            .sync
+            .lock()
+            .map_err(|_| "History sync lock is poisoned".to_string())?;
+        if sync.as_ref().map(HistorySync::dir) == folder {
+            return Ok(());
         }
 
-        let raw_contents = fs::read_to_string(&self.file_path)
-            .map_err(|error| format!("Failed to read transcript history file: {error}"))?;
+        let Some(folder) = folder else {
+            *sync = None;
+            info!("history sync disabled");
+            return Ok(());
+        };
+        info!(folder = %folder.display(), "history sync enabled");
+        let state = sync.insert(HistorySync::new(folder.to_path_buf())?);
+        self.merge_with_sync_folder(state)
+    }
 
-        if raw_contents.trim().is_empty() {
-            return Ok(Vec::new());
+    pub fn sync_status(&self) -> HistorySyncStatus {
+        self.sync
+            .lock()
+            .ok()
+            .and_then(|sync| sync.as_ref().map(HistorySync::status))
+            .unwrap_or_default()
+    }
+
+    pub fn sync_if_changed(&self) -> Result<(), String> {
+        let _guard = self.document.lock()?;
+        let mut sync = self
+            .sync
+            .lock()
+            .map_err(|_| "History sync lock is poisoned".to_string())?;
+        match sync.as_mut() {
+            Some(state) if state.has_remote_changes() => self.merge_with_sync_folder(state),
+            _ => Ok(()),
         }
+    }
 
-        let mut entries = match serde_json::from_str::<Vec<HistoryEntry>>(&raw_contents) {
-            Ok(parsed) => parsed,
+    fn merge_with_sync_folder(&self, state: &mut HistorySync) -> Result<(), String> {
+        match self.apply_sync_merge(state) {
+            Ok((outcome, exported)) => {
+                info!(
+                    imported = outcome.imported,
+                    exported,
+                    removed = outcome.removed,
+                    "history synced with folder"
+                );
+                state.record_success(&outcome, exported);
+                Ok(())
+            }
             Err(error) => {
-                self.recover_malformed_history_file(format!(
-                    "Failed to parse transcript history file: {error}"
-                ))?;
-                return Ok(Vec::new());
+                state.record_error(&error);
+                Err(error)
             }
-        };
-
-        if let Err(error) = entries.iter().try_for_each(validate_entry) {
-            self.recover_malformed_history_file(format!(
-                "Failed to validate transcript history file: {error}"
-            ))?;
-            return Ok(Vec::new());
         }
+    }
 
-        if !entries
-            .windows(2)
-            .all(|window| window[0].timestamp >= window[1].timestamp)
-        {
-            entries.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    fn apply_sync_merge(&self, state: &mut HistorySync) -> Result<(MergeOutcome, usize), String> {
+        let local = self.read_entries()?;
+        let outcome = merge(local.clone(), state.read_records()?);
+        if outcome.entries != local {
+            self.write_entries(&outcome.entries)?;
+            self.publish_change(HistoryChange::between(&local, &outcome.entries));
         }
-
-        Ok(entries)
+        let exported = state.write_records(&outcome.to_export)?;
+        Ok((outcome, exported))
     }
 
-    fn write_entries(&self, entries: &[HistoryEntry]) -> Result<(), String> {
-        let serialized = serde_json::to_vec_pretty(entries)
-            .map_err(|error| format!("Failed to serialize transcript history entries: {error}"))?;
-        let temp_path = temp_file_path_for(&self.file_path);
-
-        let mut temp_file = fs::OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .open(&temp_path)
-            .map_err(|error| {
-                format!(
-                    "Failed to create transcript history temp file `{}`: {error}",
-                    temp_path.display()
-                )
-            })?;
-
-        if let Err(error) = temp_file.write_all(&serialized) {
-            let _ = fs::remove_file(&temp_path);
-            return Err(format!(
-                "Failed to write transcript history temp file `{}`: {error}",
-                temp_path.display()
-            ));
+    fn publish_change(&self, mut change: HistoryChange) {
+        if change.is_empty() {
+            return;
         }
-
-        if let Err(error) = temp_file.sync_all() {
-            let _ = fs::remove_file(&temp_path);
-            return Err(format!(
-                "Failed to flush transcript history temp file `{}`: {error}",
-                temp_path.display()
-            ));
+        change.revision = self.revision.fetch_add(1, Ordering::AcqRel) + 1;
+        debug!(
+            revision = change.revision,
+            added = change.added.len(),
+            updated = change.updated.len(),
+            deleted = change.deleted.len(),
+            "history changed"
+        );
+        let listener = self
+            .change_listener
+            .lock()
+            .ok()
+            .and_then(|listener| listener.clone());
+        if let Some(listener) = listener {
+            listener(&change);
         }
+    }
 
-        drop(temp_file);
+    fn mirror_to_sync_folder(&self, records: &[SyncRecord]) {
+        let Ok(mut sync) = self.sync.lock() else {
+            warn!("history sync lock is poisoned; skipping sync folder update");
+            return;
+        };
+        let Some(state) = sync.as_mut() else {
+            return;
+        };
+        if let Err(error) = state.write_records(records) {
+            warn!(%error, "failed to mirror history change to sync folder");
+            state.record_error(&error);
+        }
+    }
 
-        fs::rename(&temp_path, &self.file_path).map_err(|error| {
-            let _ = fs::remove_file(&temp_path);
-            format!("Failed to finalize transcript history file: {error}")
-        })?;
+    fn read_entries(&self) -> Result<Vec<HistoryEntry>, String> {
+        let Some(cache) = &self.write_behind else {
+            return self.document.read();
+        };
+        if let Some(entries) = cache.cached() {
+            return Ok(entries);
+        }
+        let entries = self.document.read()?;
+        cache.prime(entries.clone());
+        Ok(entries)
+    }
 
-        Ok(())
+    fn write_entries(&self, entries: &[HistoryEntry]) -> Result<(), String> {
+        match &self.write_behind {
+            Some(cache) => cache.store(entries.to_vec(), Instant::now()),
+            None => self.write_entries_to_disk(entries),
+        }
     }
 
-    fn recover_malformed_history_file(&self, reason: String) -> Result<(), String> {
-        let backup_path = backup_corrupt_history_file(&self.file_path)?;
-        self.write_entries(&[])?;
-        warn!(
-            path = %self.file_path.display(),
-            backup = %backup_path.display(),
-            reason = %reason,
-            "recovered malformed history file"
-        );
-        Ok(())
+    fn write_entries_to_disk(&self, entries: &[HistoryEntry]) -> Result<(), String> {
+        self.document.write(entries)
     }
 }
 
-fn ensure_history_file(file_path: &Path) -> Result<(), String> {
-    if let Some(parent_dir) = file_path.parent() {
-        fs::create_dir_all(parent_dir)
-            .map_err(|error| format!("Failed to create history directory: {error}"))?;
-    }
+fn merge_local_edits(
+    last_read: &[HistoryEntry],
+    local: &[HistoryEntry],
+    on_disk: Vec<HistoryEntry>,
+) -> Vec<HistoryEntry> {
+    let last_read_by_id = last_read
+        .iter()
+        .map(|entry| (entry.id.as_str(), entry))
+        .collect::<HashMap<_, _>>();
+    let local_ids = local
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect::<HashSet<_>>();
+    let edited = local
+        .iter()
+        .filter(|entry| last_read_by_id.get(entry.id.as_str()).copied() != Some(*entry))
+        .collect::<Vec<_>>();
+    let edited_ids = edited
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect::<HashSet<_>>();
+
+    let mut merged = on_disk
+        .into_iter()
+        .filter(|entry| {
+            let removed_locally = last_read_by_id.contains_key(entry.id.as_str())
+                && !local_ids.contains(entry.id.as_str());
+            !removed_locally && !edited_ids.contains(entry.id.as_str())
+        })
+        .collect::<Vec<_>>();
+    merged.extend(edited.into_iter().cloned());
+    merged.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    merged.truncate(MAX_HISTORY_ENTRIES);
+    merged
+}
 
-    if !file_path.exists() {
-        fs::write(file_path, "[]")
-            .map_err(|error| format!("Failed to initialize history file: {error}"))?;
-        info!(path = %file_path.display(), "created history file");
+fn page_url(value: &str) -> Option<String> {
+    let mut url = Url::parse(value).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
     }
-
-    Ok(())
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url.to_string())
 }
 
 fn normalize_optional(value: Option<String>) -> Option<String> {
@@ -281,45 +854,24 @@ fn normalize_optional(value: Option<String>) -> Option<String> {
     })
 }
 
-fn temp_file_path_for(file_path: &Path) -> PathBuf {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("transcript_history.json");
-
-    file_path.with_file_name(format!(
-        ".{file_name}.{}.{timestamp}.tmp",
-        std::process::id()
-    ))
-}
+fn normalize_entries(mut entries: Vec<HistoryEntry>) -> Result<Vec<HistoryEntry>, String> {
+    entries.iter().try_for_each(validate_entry)?;
 
-fn backup_corrupt_history_file(file_path: &Path) -> Result<PathBuf, String> {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("transcript_history.json");
-    let backup_path = file_path.with_file_name(format!(
-        "{file_name}.corrupt-{}-{timestamp}.bak",
-        std::process::id()
-    ));
-
-    fs::rename(file_path, &backup_path).map_err(|error| {
-        format!(
-            "Failed to backup malformed history file `{}` to `{}`: {error}",
-            file_path.display(),
-            backup_path.display()
-        )
-    })?;
+    if !entries
+        .windows(2)
+        .all(|window| window[0].timestamp >= window[1].timestamp)
+    {
+        entries.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    }
 
-    Ok(backup_path)
+    for entry in entries
+        .iter_mut()
+        .filter(|entry| entry.metrics.char_count == 0)
+    {
+        entry.metrics = TranscriptMetrics::for_transcript(&entry.text, &entry.segments);
+    }
+
+    Ok(entries)
 }
 
 fn validate_entry(entry: &HistoryEntry) -> Result<(), String> {
@@ -344,6 +896,8 @@ fn validate_entry(entry: &HistoryEntry) -> Result<(), String> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     fn create_test_store() -> (HistoryStore, PathBuf, PathBuf) {
@@ -391,6 +945,14 @@ mod tests {
             duration_secs: Some(2.5),
             language: Some("en".to_string()),
             provider: "openai".to_string(),
+            segments: Vec::new(),
+            summary: None,
+            raw_text: None,
+            updated_at: None,
+            waveform: Vec::new(),
+            metrics: TranscriptMetrics::default(),
+            details: TranscriptionDetails::default(),
+            source: None,
         }
     }
 
@@ -527,6 +1089,14 @@ mod tests {
             duration_secs: None,
             language: None,
             provider: "openai".to_string(),
+            segments: Vec::new(),
+            summary: None,
+            raw_text: None,
+            updated_at: None,
+            waveform: Vec::new(),
+            metrics: TranscriptMetrics::default(),
+            details: TranscriptionDetails::default(),
+            source: None,
         };
 
         let error = store
@@ -568,6 +1138,14 @@ mod tests {
                 duration_secs: None,
                 language: None,
                 provider: "openai".to_string(),
+                segments: Vec::new(),
+                summary: None,
+                raw_text: None,
+                updated_at: None,
+                waveform: Vec::new(),
+                metrics: TranscriptMetrics::default(),
+                details: TranscriptionDetails::default(),
+                source: None,
             })
             .collect();
         fs::write(
@@ -598,6 +1176,14 @@ mod tests {
                     duration_secs: None,
                     language: None,
                     provider: "openai".to_string(),
+                    segments: Vec::new(),
+                    summary: None,
+                    raw_text: None,
+                    updated_at: None,
+                    waveform: Vec::new(),
+                    metrics: TranscriptMetrics::default(),
+                    details: TranscriptionDetails::default(),
+                    source: None,
                 })
                 .expect("entry should be added");
         }
@@ -620,4 +1206,421 @@ mod tests {
 
         cleanup_test_dir(&test_dir);
     }
+
+    #[test]
+    fn offsets_render_as_hours_minutes_seconds() {
+        assert_eq!(format_offset(0.0), "00:00:00");
+        assert_eq!(format_offset(3_725.9), "01:02:05");
+    }
+
+    #[test]
+    fn rename_speaker_updates_segments_and_rendered_text() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let segment = |start_secs: f64, speaker: &str, text: &str| HistorySegment {
+            start_secs,
+            end_secs: start_secs + 5.0,
+            speaker: Some(speaker.to_string()),
+            text: text.to_string(),
+        };
+        let entry = HistoryEntry::new(String::new(), Some(10.0), None, "meeting".to_string())
+            .with_segments(vec![
+                segment(0.0, "Speaker 1", "hello"),
+                segment(5.0, "Speaker 2", "hi"),
+            ]);
+        assert_eq!(
+            entry.text,
+            "[00:00:00] Speaker 1: hello\n[00:00:05] Speaker 2: hi"
+        );
+        store
+            .add_entry(entry.clone())
+            .expect("entry should be added");
+
+        let renamed = store
+            .rename_speaker(&entry.id, "Speaker 1", " Ada ")
+            .expect("rename should succeed")
+            .expect("entry should exist");
+
+        assert_eq!(
+            renamed.text,
+            "[00:00:00] Ada: hello\n[00:00:05] Speaker 2: hi"
+        );
+        assert_eq!(
+            store.get_entry(&entry.id).expect("entry should load"),
+            Some(renamed)
+        );
+        assert!(store.rename_speaker(&entry.id, "Speaker 9", "Bob").is_err());
+        assert!(store.rename_speaker(&entry.id, "Speaker 2", " ").is_err());
+        assert_eq!(
+            store
+                .rename_speaker("missing", "Speaker 1", "Bob")
+                .expect("missing entry should not error"),
+            None
+        );
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn set_summary_persists_and_renders_markdown() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let entry = test_entry("we should ship friday", "2026-01-01T00:00:00Z");
+        store
+            .add_entry(entry.clone())
+            .expect("entry should be added");
+        let summary = TranscriptSummary {
+            bullets: vec!["Ship on Friday".to_string()],
+            action_items: vec!["Tag the release".to_string()],
+            model: "gpt-4o-mini".to_string(),
+            created_at: "2026-01-01T00:01:00Z".to_string(),
+        };
+
+        let updated = store
+            .set_summary(&entry.id, summary.clone())
+            .expect("summary should save")
+            .expect("entry should exist");
+
+        assert_eq!(updated.summary.as_ref(), Some(&summary));
+        assert_eq!(
+            store.get_entry(&entry.id).expect("entry should load"),
+            Some(updated)
+        );
+        assert_eq!(
+            summary.to_markdown(),
+            "## Summary\n- Ship on Friday\n\n## Action items\n- [ ] Tag the release\n"
+        );
+        assert_eq!(
+            store
+                .set_summary("missing", summary)
+                .expect("missing entry should not error"),
+            None
+        );
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn raw_text_is_kept_only_when_cleanup_changed_the_transcript() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let cleaned = HistoryEntry::new("Ship it.".to_string(), None, None, "openai".to_string())
+            .with_raw_text(Some("um ship it".to_string()));
+        let unchanged = HistoryEntry::new("same".to_string(), None, None, "openai".to_string())
+            .with_raw_text(Some("same".to_string()));
+        store
+            .add_entry(cleaned.clone())
+            .expect("entry should be added");
+
+        assert_eq!(unchanged.raw_text, None);
+        assert_eq!(
+            store
+                .get_entry(&cleaned.id)
+                .expect("entry should load")
+                .and_then(|entry| entry.raw_text),
+            Some("um ship it".to_string())
+        );
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn entries_carry_metrics_and_list_sorts_by_length() {
+        let (store, file_path, test_dir) = create_test_store();
+
+        let plain = HistoryEntry::new(
+            "one two three four".to_string(),
+            None,
+            None,
+            "openai".to_string(),
+        );
+        assert_eq!(
+            plain.metrics,
+            TranscriptMetrics {
+                word_count: 4,
+                char_count: 18,
+                reading_time_secs: 2,
+            }
+        );
+        let segmented = HistoryEntry::new(String::new(), None, None, "openai".to_string())
+            .with_segments(vec![HistorySegment {
+                start_secs: 0.0,
+                end_secs: 1.0,
+                speaker: Some("Speaker 1".to_string()),
+                text: "hi".to_string(),
+            }]);
+        assert_eq!(segmented.metrics.word_count, 1);
+        assert_eq!(segmented.metrics.char_count, 2);
+
+        store.add_entry(plain).expect("entry should be added");
+        store
+            .add_entry(test_entry("legacy words", "2000-01-01T00:00:00Z"))
+            .expect("entry should be added");
+        store.add_entry(segmented).expect("entry should be added");
+
+        let longest = store
+            .list_entries_sorted(10, 0, HistorySort::Longest)
+            .expect("list should succeed");
+        let word_counts = longest
+            .iter()
+            .map(|entry| entry.metrics.word_count)
+            .collect::<Vec<_>>();
+        assert_eq!(word_counts, vec![4, 2, 1]);
+        let oldest = store
+            .list_entries_sorted(1, 0, HistorySort::Oldest)
+            .expect("list should succeed");
+        assert_eq!(oldest[0].text, "legacy words");
+        assert!(fs::read_to_string(&file_path)
+            .expect("history file should be readable")
+            .contains("\"wordCount\": 2"));
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn query_entries_applies_every_filter_in_the_store() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let mut short_local = test_entry("Quick NOTE", "2026-01-01T09:00:00.000Z");
+        short_local.provider = "local".to_string();
+        short_local.language = Some("en-US".to_string());
+        short_local.duration_secs = Some(1.0);
+        let long_openai = test_entry("a longer note about lunch", "2026-01-02T09:00:00.000Z");
+        let mut german = test_entry("eine Notiz", "2026-01-03T09:00:00.000Z");
+        german.language = Some("de".to_string());
+        for entry in [short_local, long_openai, german] {
+            store.add_entry(entry).expect("entry should be added");
+        }
+
+        let query = |filter: HistoryFilter| {
+            store
+                .query_entries(&filter, 10, 0)
+                .expect("query should succeed")
+                .into_iter()
+                .map(|entry| entry.text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            query(HistoryFilter {
+                from: Some("2026-01-02T00:00:00Z".to_string()),
+                to: Some("2026-01-03T00:00:00Z".to_string()),
+                ..HistoryFilter::default()
+            }),
+            vec!["a longer note about lunch"]
+        );
+        assert_eq!(
+            query(HistoryFilter {
+                provider: Some("LOCAL".to_string()),
+                ..HistoryFilter::default()
+            }),
+            vec!["Quick NOTE"]
+        );
+        assert_eq!(
+            query(HistoryFilter {
+                language: Some("en".to_string()),
+                sort: HistorySort::Oldest,
+                ..HistoryFilter::default()
+            }),
+            vec!["Quick NOTE", "a longer note about lunch"]
+        );
+        assert_eq!(
+            query(HistoryFilter {
+                min_duration_secs: Some(2.0),
+                max_duration_secs: Some(3.0),
+                text_contains: Some(" note ".to_string()),
+                ..HistoryFilter::default()
+            }),
+            vec!["a longer note about lunch"]
+        );
+
+        let error = store
+            .query_entries(
+                &HistoryFilter {
+                    from: Some("yesterday".to_string()),
+                    ..HistoryFilter::default()
+                },
+                10,
+                0,
+            )
+            .expect_err("invalid bounds should be rejected");
+        assert!(error.contains("RFC 3339"));
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn cursor_pages_survive_entries_added_between_pages() {
+        let (store, _file_path, test_dir) = create_test_store();
+        for index in 0..5 {
+            store
+                .add_entry(test_entry(
+                    &format!("entry-{index}"),
+                    &format!("2026-01-01T00:00:0{index}.000Z"),
+                ))
+                .expect("entry should be added");
+        }
+
+        let first = store
+            .list_entries_after(None, 2)
+            .expect("first page should load");
+        store
+            .add_entry(test_entry("newest", "2026-01-02T00:00:00.000Z"))
+            .expect("entry should be added");
+        let second = store
+            .list_entries_after(first.next_cursor.as_deref(), 2)
+            .expect("second page should load");
+        let third = store
+            .list_entries_after(second.next_cursor.as_deref(), 2)
+            .expect("third page should load");
+
+        let texts = [first.entries, second.entries, third.entries]
+            .concat()
+            .into_iter()
+            .map(|entry| entry.text)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec!["entry-4", "entry-3", "entry-2", "entry-1", "entry-0"]
+        );
+        assert_eq!(third.next_cursor, None);
+        assert!(store.list_entries_after(Some("not a cursor!"), 2).is_err());
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn mutations_publish_changes_with_increasing_revisions() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&changes);
+        store.set_change_listener(Arc::new(move |change: &HistoryChange| {
+            recorded.lock().expect("changes lock").push(change.clone());
+        }));
+
+        let entry = test_entry("hello", "2026-01-01T00:00:00.000Z");
+        let id = entry.id.clone();
+        store.add_entry(entry).expect("entry should be added");
+        store
+            .set_summary(
+                &id,
+                TranscriptSummary {
+                    bullets: vec!["greeting".to_string()],
+                    action_items: Vec::new(),
+                    model: "test".to_string(),
+                    created_at: "2026-01-01T00:00:01.000Z".to_string(),
+                },
+            )
+            .expect("summary should be saved");
+        assert!(!store
+            .delete_entry("missing")
+            .expect("delete should succeed"));
+        store.clear_history().expect("history should clear");
+
+        let changes = changes.lock().expect("changes lock");
+        assert_eq!(
+            *changes,
+            vec![
+                HistoryChange {
+                    revision: 1,
+                    added: vec![id.clone()],
+                    ..HistoryChange::default()
+                },
+                HistoryChange {
+                    revision: 2,
+                    updated: vec![id.clone()],
+                    ..HistoryChange::default()
+                },
+                HistoryChange {
+                    revision: 3,
+                    deleted: vec![id],
+                    ..HistoryChange::default()
+                },
+            ]
+        );
+        assert_eq!(store.revision(), 3);
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn stores_sharing_a_sync_folder_converge() {
+        let (laptop, _laptop_path, laptop_dir) = create_test_store();
+        let (desktop, _desktop_path, desktop_dir) = create_test_store();
+        let sync_dir = laptop_dir.join("shared");
+
+        let first = test_entry("from laptop", "2026-01-01T00:00:00.000Z");
+        let first_id = first.id.clone();
+        laptop.add_entry(first).expect("entry should be added");
+        laptop
+            .configure_sync(Some(&sync_dir))
+            .expect("sync should be enabled");
+        desktop
+            .add_entry(test_entry("from desktop", "2026-01-02T00:00:00.000Z"))
+            .expect("entry should be added");
+        desktop
+            .configure_sync(Some(&sync_dir))
+            .expect("sync should be enabled");
+        assert_eq!(desktop.sync_status().imported, 1);
+
+        laptop.sync_if_changed().expect("laptop should sync");
+        assert_eq!(laptop.list_entries(10, 0).expect("list").len(), 2);
+
+        desktop
+            .delete_entry(&first_id)
+            .expect("delete should succeed");
+        laptop.sync_if_changed().expect("laptop should sync");
+        let remaining = laptop.list_entries(10, 0).expect("list");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].text, "from desktop");
+        assert_eq!(laptop.sync_status().removed, 1);
+
+        laptop
+            .configure_sync(None)
+            .expect("sync should be disabled");
+        assert!(!laptop.sync_status().enabled);
+
+        cleanup_test_dir(&laptop_dir);
+        cleanup_test_dir(&desktop_dir);
+    }
+
+    #[test]
+    fn write_behind_writes_new_entries_through_and_merges_edits_with_other_writers() {
+        let (store, file_path, test_dir) = create_test_store();
+        let store = store.with_write_behind(WriteBehindConfig {
+            debounce: Duration::from_secs(60),
+            max_delay: Duration::from_secs(60),
+        });
+        let texts = |entries: Vec<HistoryEntry>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.text)
+                .collect::<Vec<_>>()
+        };
+
+        let first = test_entry("burst 0", "2026-01-01T00:00:00.000Z");
+        store
+            .add_entry(first.clone())
+            .expect("entry should be added");
+        for index in 1..3 {
+            store
+                .add_entry(test_entry(
+                    &format!("burst {index}"),
+                    &format!("2026-01-01T00:00:0{index}.000Z"),
+                ))
+                .expect("entry should be added");
+        }
+        let on_disk = HistoryStore::new_with_file_path(file_path.clone()).expect("reopen");
+        assert_eq!(on_disk.list_entries(10, 0).expect("list").len(), 3);
+
+        assert!(store.delete_entry(&first.id).expect("delete"));
+        store.flush_if_due().expect("flush if due");
+        assert_eq!(on_disk.list_entries(10, 0).expect("list").len(), 3);
+
+        on_disk
+            .add_entry(test_entry("from the cli", "2026-01-01T00:00:09.000Z"))
+            .expect("other writer should add an entry");
+        store.flush().expect("flush");
+        let expected = vec!["from the cli", "burst 2", "burst 1"];
+        assert_eq!(texts(on_disk.list_entries(10, 0).expect("list")), expected);
+        assert_eq!(texts(store.list_entries(10, 0).expect("list")), expected);
+
+        cleanup_test_dir(&test_dir);
+    }
 }
@@ -0,0 +1,75 @@
+use serde::Serialize;
+
+use super::HistoryEntry;
+
+pub const DEFAULT_PREVIEW_CHARS: usize = 200;
+pub const MAX_PREVIEW_CHARS: usize = 2_000;
+const MIN_PREVIEW_CHARS: usize = 16;
+const ELLIPSIS: char = '…';
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntryPreview {
+    pub id: String,
+    pub timestamp: String,
+    pub provider: String,
+    pub preview: String,
+    pub truncated: bool,
+}
+
+impl HistoryEntryPreview {
+    pub fn new(entry: &HistoryEntry, max_chars: usize) -> Self {
+        let (preview, truncated) = truncate_preview(&entry.text, max_chars);
+        Self {
+            id: entry.id.clone(),
+            timestamp: entry.timestamp.clone(),
+            provider: entry.provider.clone(),
+            preview,
+            truncated,
+        }
+    }
+}
+
+pub fn truncate_preview(text: &str, max_chars: usize) -> (String, bool) {
+    let max_chars = max_chars.clamp(MIN_PREVIEW_CHARS, MAX_PREVIEW_CHARS);
+    let text = text.trim();
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return (text.to_string(), false);
+    };
+
+    // Leave room for the ellipsis.
+    let limit = text[..cut]
+        .char_indices()
+        .nth(max_chars - 1)
+        .map_or(cut, |(index, _)| index);
+    let head = &text[..limit];
+    let at_word_end = text[limit..].starts_with(char::is_whitespace);
+    let head = if at_word_end {
+        head
+    } else {
+        head.rfind(char::is_whitespace)
+            .map_or(head, |index| &head[..index])
+    };
+    let mut preview = head.trim_end().to_string();
+    preview.push(ELLIPSIS);
+    (preview, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_preview;
+
+    #[test]
+    fn previews_break_at_word_boundaries() {
+        let text = "The quarterly numbers look better than expected this time around";
+
+        assert_eq!(truncate_preview(text, 500), (text.to_string(), false));
+        assert_eq!(
+            truncate_preview(text, 30),
+            ("The quarterly numbers look…".to_string(), true)
+        );
+        let (preview, truncated) = truncate_preview(&"é".repeat(40), 20);
+        assert!(truncated);
+        assert_eq!(preview.chars().count(), 20);
+    }
+}
@@ -0,0 +1,183 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+
+use super::{HistoryEntry, HistorySort};
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistoryFilter {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub provider: Option<String>,
+    pub language: Option<String>,
+    pub min_duration_secs: Option<f64>,
+    pub max_duration_secs: Option<f64>,
+    pub text_contains: Option<String>,
+    pub source_contains: Option<String>,
+    pub sort: HistorySort,
+}
+
+pub(super) struct CompiledHistoryFilter {
+    from: Option<DateTime<FixedOffset>>,
+    to: Option<DateTime<FixedOffset>>,
+    provider: Option<String>,
+    language: Option<String>,
+    min_duration_secs: Option<f64>,
+    max_duration_secs: Option<f64>,
+    text_contains: Option<String>,
+    source_contains: Option<String>,
+}
+
+impl HistoryFilter {
+    pub(super) fn compile(&self) -> Result<CompiledHistoryFilter, String> {
+        let from = parse_bound("from", self.from.as_deref())?;
+        let to = parse_bound("to", self.to.as_deref())?;
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Err("History filter `from` must be earlier than `to`".to_string());
+            }
+        }
+        for (name, value) in [
+            ("minDurationSecs", self.min_duration_secs),
+            ("maxDurationSecs", self.max_duration_secs),
+        ] {
+            if value.is_some_and(|value| !value.is_finite() || value < 0.0) {
+                return Err(format!(
+                    "History filter `{name}` must be a non-negative number"
+                ));
+            }
+        }
+
+        Ok(CompiledHistoryFilter {
+            from,
+            to,
+            provider: normalized(self.provider.as_deref()),
+            language: normalized(self.language.as_deref()),
+            min_duration_secs: self.min_duration_secs,
+            max_duration_secs: self.max_duration_secs,
+            text_contains: normalized(self.text_contains.as_deref()),
+            source_contains: normalized(self.source_contains.as_deref()),
+        })
+    }
+}
+
+impl CompiledHistoryFilter {
+    pub(super) fn matches(&self, entry: &HistoryEntry) -> bool {
+        if self.from.is_some() || self.to.is_some() {
+            let Ok(timestamp) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+                return false;
+            };
+            if self.from.is_some_and(|from| timestamp < from)
+                || self.to.is_some_and(|to| timestamp >= to)
+            {
+                return false;
+            }
+        }
+
+        if self
+            .provider
+            .as_deref()
+            .is_some_and(|provider| !entry.provider.eq_ignore_ascii_case(provider))
+        {
+            return false;
+        }
+
+        // `en` matches `en-US` so a filter by base language finds regional variants too.
+        if let Some(language) = self.language.as_deref() {
+            let Some(entry_language) = entry.language.as_deref().map(str::to_ascii_lowercase)
+            else {
+                return false;
+            };
+            if entry_language != language && !entry_language.starts_with(&format!("{language}-")) {
+                return false;
+            }
+        }
+
+        if self.min_duration_secs.is_some() || self.max_duration_secs.is_some() {
+            let Some(duration_secs) = entry.duration_secs else {
+                return false;
+            };
+            if self
+                .min_duration_secs
+                .is_some_and(|min| duration_secs < min)
+                || self
+                    .max_duration_secs
+                    .is_some_and(|max| duration_secs > max)
+            {
+                return false;
+            }
+        }
+
+        if let Some(needle) = self.source_contains.as_deref() {
+            if !entry
+                .source
+                .as_ref()
+                .is_some_and(|source| source.contains(needle))
+            {
+                return false;
+            }
+        }
+
+        self.text_contains
+            .as_deref()
+            .is_none_or(|needle| entry.text.to_lowercase().contains(needle))
+    }
+}
+
+fn parse_bound(name: &str, value: Option<&str>) -> Result<Option<DateTime<FixedOffset>>, String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value).map_err(|error| {
+                format!("History filter `{name}` is not an RFC 3339 timestamp: {error}")
+            })
+        })
+        .transpose()
+}
+
+fn normalized(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HistoryCursor {
+    timestamp: String,
+    id: String,
+}
+
+impl HistoryCursor {
+    pub(super) fn after(entry: &HistoryEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp.clone(),
+            id: entry.id.clone(),
+        }
+    }
+
+    pub(super) fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}\n{}", self.timestamp, self.id))
+    }
+
+    pub(super) fn decode(cursor: &str) -> Result<Self, String> {
+        let decoded = URL_SAFE_NO_PAD
+            .decode(cursor.trim())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| "History cursor is malformed".to_string())?;
+        let (timestamp, id) = decoded
+            .split_once('\n')
+            .ok_or_else(|| "History cursor is malformed".to_string())?;
+        Ok(Self {
+            timestamp: timestamp.to_string(),
+            id: id.to_string(),
+        })
+    }
+
+    pub(super) fn precedes(&self, entry: &HistoryEntry) -> bool {
+        (entry.timestamp.as_str(), entry.id.as_str()) < (self.timestamp.as_str(), self.id.as_str())
+    }
+}
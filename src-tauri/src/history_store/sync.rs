@@ -0,0 +1,368 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{validate_entry, HistoryEntry, MAX_HISTORY_ENTRIES};
+use crate::persistence::JsonDocumentStore;
+
+const RECORD_EXTENSION: &str = "json";
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySyncStatus {
+    pub enabled: bool,
+    pub folder: Option<String>,
+    pub last_synced_at: Option<String>,
+    pub last_error: Option<String>,
+    pub imported: usize,
+    pub exported: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SyncRecord {
+    pub id: String,
+    pub modified_at: String,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<HistoryEntry>,
+}
+
+impl SyncRecord {
+    pub fn entry(entry: &HistoryEntry) -> Self {
+        Self {
+            id: entry.id.clone(),
+            modified_at: entry.modified_at().to_string(),
+            deleted: false,
+            entry: Some(entry.clone()),
+        }
+    }
+
+    pub fn tombstone(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            modified_at: now_timestamp(),
+            deleted: true,
+            entry: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct MergeOutcome {
+    pub entries: Vec<HistoryEntry>,
+    pub to_export: Vec<SyncRecord>,
+    pub imported: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug)]
+pub(super) struct HistorySync {
+    dir: PathBuf,
+    fingerprint: Option<u64>,
+    status: HistorySyncStatus,
+}
+
+impl HistorySync {
+    pub fn new(dir: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&dir).map_err(|error| {
+            format!(
+                "Failed to create history sync folder `{}`: {error}",
+                dir.display()
+            )
+        })?;
+        let status = HistorySyncStatus {
+            enabled: true,
+            folder: Some(dir.display().to_string()),
+            ..HistorySyncStatus::default()
+        };
+        Ok(Self {
+            dir,
+            fingerprint: None,
+            status,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn status(&self) -> HistorySyncStatus {
+        self.status.clone()
+    }
+
+    pub fn has_remote_changes(&self) -> bool {
+        folder_fingerprint(&self.dir).ok() != self.fingerprint
+    }
+
+    pub fn read_records(&self) -> Result<Vec<SyncRecord>, String> {
+        let listing = fs::read_dir(&self.dir).map_err(|error| {
+            format!(
+                "Failed to read history sync folder `{}`: {error}",
+                self.dir.display()
+            )
+        })?;
+
+        let mut records = Vec::new();
+        for path in listing.flatten().map(|entry| entry.path()) {
+            if !is_record_path(&path) {
+                continue;
+            }
+            match read_record(&path) {
+                Ok(record) => records.push(record),
+                Err(error) => {
+                    warn!(path = %path.display(), %error, "skipping unreadable history sync record");
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    // Refreshing the fingerprint keeps our own writes from being mistaken for remote ones.
+    pub fn write_records(&mut self, records: &[SyncRecord]) -> Result<usize, String> {
+        let pending_remote_changes = self.has_remote_changes();
+        let mut written = 0;
+        for record in records {
+            if !is_valid_record_id(&record.id) {
+                warn!(id = %record.id, "skipping history sync record with an unsafe id");
+                continue;
+            }
+            write_record(&self.dir, record)?;
+            written += 1;
+        }
+        if !pending_remote_changes {
+            self.fingerprint = folder_fingerprint(&self.dir).ok();
+        }
+        Ok(written)
+    }
+
+    pub fn record_success(&mut self, outcome: &MergeOutcome, exported: usize) {
+        self.fingerprint = folder_fingerprint(&self.dir).ok();
+        self.status.last_synced_at = Some(now_timestamp());
+        self.status.last_error = None;
+        self.status.imported = outcome.imported;
+        self.status.exported = exported;
+        self.status.removed = outcome.removed;
+    }
+
+    pub fn record_error(&mut self, error: &str) {
+        self.status.last_error = Some(error.to_string());
+    }
+}
+
+pub(super) fn merge(local: Vec<HistoryEntry>, remote: Vec<SyncRecord>) -> MergeOutcome {
+    let mut remote_by_id = HashMap::with_capacity(remote.len());
+    for record in remote {
+        if record
+            .entry
+            .as_ref()
+            .is_some_and(|entry| entry.id != record.id || validate_entry(entry).is_err())
+        {
+            warn!(id = %record.id, "skipping invalid history sync record");
+            continue;
+        }
+        remote_by_id.insert(record.id.clone(), record);
+    }
+
+    let mut outcome = MergeOutcome::default();
+    let mut imported_ids = HashSet::new();
+    let mut seen = HashSet::with_capacity(local.len());
+    for entry in local {
+        seen.insert(entry.id.clone());
+        let Some(record) = remote_by_id.get(&entry.id) else {
+            outcome.to_export.push(SyncRecord::entry(&entry));
+            outcome.entries.push(entry);
+            continue;
+        };
+
+        match record.modified_at.as_str().cmp(entry.modified_at()) {
+            std::cmp::Ordering::Less => {
+                outcome.to_export.push(SyncRecord::entry(&entry));
+                outcome.entries.push(entry);
+            }
+            std::cmp::Ordering::Equal => outcome.entries.push(entry),
+            std::cmp::Ordering::Greater => match &record.entry {
+                Some(remote_entry) if !record.deleted => {
+                    imported_ids.insert(remote_entry.id.clone());
+                    outcome.entries.push(remote_entry.clone());
+                }
+                _ => outcome.removed += 1,
+            },
+        }
+    }
+
+    for (id, record) in remote_by_id {
+        if seen.contains(&id) || record.deleted {
+            continue;
+        }
+        if let Some(entry) = record.entry {
+            imported_ids.insert(entry.id.clone());
+            outcome.entries.push(entry);
+        }
+    }
+
+    outcome
+        .entries
+        .sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    // Entries pruned locally stay in the folder, so only count imports that survive the cap.
+    outcome.entries.truncate(MAX_HISTORY_ENTRIES);
+    outcome.imported = outcome
+        .entries
+        .iter()
+        .filter(|entry| imported_ids.contains(&entry.id))
+        .count();
+    outcome
+}
+
+pub(super) fn now_timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn is_record_path(path: &Path) -> bool {
+    path.extension().and_then(|value| value.to_str()) == Some(RECORD_EXTENSION)
+        && !path
+            .file_name()
+            .and_then(|value| value.to_str())
+            .is_some_and(|name| name.starts_with('.'))
+}
+
+fn is_valid_record_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_'))
+}
+
+fn read_record(path: &Path) -> Result<SyncRecord, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read history sync record: {error}"))?;
+    serde_json::from_str(&contents)
+        .map_err(|error| format!("Failed to parse history sync record: {error}"))
+}
+
+fn write_record(dir: &Path, record: &SyncRecord) -> Result<(), String> {
+    let path = dir.join(format!("{}.{RECORD_EXTENSION}", record.id));
+    JsonDocumentStore::<SyncRecord>::new(path, "history sync record")
+        .without_recovery()
+        .write(record)?;
+
+    debug!(id = %record.id, deleted = record.deleted, "wrote history sync record");
+    Ok(())
+}
+
+fn folder_fingerprint(dir: &Path) -> Result<u64, String> {
+    let listing = fs::read_dir(dir)
+        .map_err(|error| format!("Failed to read history sync folder: {error}"))?;
+    let mut files = listing
+        .flatten()
+        .filter(|entry| is_record_path(&entry.path()))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .unwrap_or_default();
+            Some((entry.file_name(), metadata.len(), modified))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut hasher = DefaultHasher::new();
+    files.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_record_id, merge, SyncRecord};
+    use crate::history_store::{HistoryEntry, TranscriptMetrics, TranscriptionDetails};
+
+    fn entry(id: &str, timestamp: &str, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: timestamp.to_string(),
+            duration_secs: None,
+            language: None,
+            provider: "openai".to_string(),
+            segments: Vec::new(),
+            summary: None,
+            raw_text: None,
+            updated_at: None,
+            waveform: Vec::new(),
+            metrics: TranscriptMetrics::default(),
+            details: TranscriptionDetails::default(),
+            source: None,
+        }
+    }
+
+    #[test]
+    fn merge_keeps_the_newest_side_and_exports_local_only_entries() {
+        let local_only = entry("local", "2026-01-03T00:00:00.000Z", "local");
+        let stale_local = entry("shared", "2026-01-01T00:00:00.000Z", "old");
+        let mut remote_edit = stale_local.clone();
+        remote_edit.text = "renamed".to_string();
+        remote_edit.updated_at = Some("2026-01-02T00:00:00.000Z".to_string());
+        let mut newer_local = entry("edited", "2026-01-01T00:00:00.000Z", "mine");
+        newer_local.updated_at = Some("2026-01-04T00:00:00.000Z".to_string());
+        let older_remote = entry("edited", "2026-01-01T00:00:00.000Z", "theirs");
+        let remote_only = entry("remote", "2026-01-05T00:00:00.000Z", "remote");
+
+        let outcome = merge(
+            vec![local_only, stale_local, newer_local],
+            vec![
+                SyncRecord::entry(&remote_edit),
+                SyncRecord::entry(&older_remote),
+                SyncRecord::entry(&remote_only),
+            ],
+        );
+
+        let texts = outcome
+            .entries
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["remote", "local", "renamed", "mine"]);
+        assert_eq!(outcome.imported, 2);
+        let mut exported = outcome
+            .to_export
+            .iter()
+            .map(|record| record.id.as_str())
+            .collect::<Vec<_>>();
+        exported.sort();
+        assert_eq!(exported, vec!["edited", "local"]);
+    }
+
+    #[test]
+    fn tombstones_only_remove_entries_they_postdate() {
+        let deleted = entry("deleted", "2026-01-01T00:00:00.000Z", "gone");
+        let mut revived = entry("revived", "2026-01-01T00:00:00.000Z", "kept");
+        revived.updated_at = Some("2999-01-01T00:00:00.000Z".to_string());
+
+        let outcome = merge(
+            vec![deleted, revived],
+            vec![
+                SyncRecord::tombstone("deleted"),
+                SyncRecord::tombstone("revived"),
+            ],
+        );
+
+        assert_eq!(outcome.removed, 1);
+        assert_eq!(outcome.entries.len(), 1);
+        assert_eq!(outcome.entries[0].id, "revived");
+        assert_eq!(outcome.to_export.len(), 1);
+        assert!(!is_valid_record_id("../escape"));
+        assert!(is_valid_record_id("0b6f7c1e-58d1-4c51-9d4e-0d3a6f3c1b7a"));
+    }
+}
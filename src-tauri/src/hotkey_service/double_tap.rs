@@ -0,0 +1,457 @@
+use std::{
+    ffi::c_void,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tracing::{debug, info, warn};
+
+const K_CG_SESSION_EVENT_TAP: u32 = 1;
+const K_CG_HEAD_INSERT_EVENT_TAP: u32 = 0;
+const K_CG_EVENT_TAP_OPTION_LISTEN_ONLY: u32 = 1;
+const K_CG_EVENT_KEY_DOWN: u32 = 10;
+const K_CG_EVENT_FLAGS_CHANGED: u32 = 12;
+const K_CG_EVENT_TAP_DISABLED_BY_TIMEOUT: u32 = 0xFFFF_FFFE;
+const K_CG_EVENT_TAP_DISABLED_BY_USER_INPUT: u32 = 0xFFFF_FFFF;
+const K_CG_KEYBOARD_EVENT_KEYCODE: u32 = 9;
+const RUN_LOOP_POLL_SECS: f64 = 0.25;
+
+type CFTypeRef = *const c_void;
+type CFAllocatorRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFMachPortRef = *mut c_void;
+type CFRunLoopRef = *mut c_void;
+type CFRunLoopSourceRef = *mut c_void;
+type CFIndex = isize;
+type Boolean = u8;
+type CGEventRef = *mut c_void;
+type CGEventFlags = u64;
+type CGEventTapProxy = *mut c_void;
+type CGEventTapCallBack = extern "C" fn(
+    proxy: CGEventTapProxy,
+    event_type: u32,
+    event: CGEventRef,
+    user_info: *mut c_void,
+) -> CGEventRef;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+unsafe extern "C" {
+    fn CGEventTapCreate(
+        tap: u32,
+        place: u32,
+        options: u32,
+        events_of_interest: u64,
+        callback: CGEventTapCallBack,
+        user_info: *mut c_void,
+    ) -> CFMachPortRef;
+    fn CGEventTapEnable(tap: CFMachPortRef, enable: bool);
+    fn CGEventGetFlags(event: CGEventRef) -> CGEventFlags;
+    fn CGEventGetIntegerValueField(event: CGEventRef, field: u32) -> i64;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    fn CFMachPortCreateRunLoopSource(
+        allocator: CFAllocatorRef,
+        port: CFMachPortRef,
+        order: CFIndex,
+    ) -> CFRunLoopSourceRef;
+    fn CFMachPortInvalidate(port: CFMachPortRef);
+    fn CFRunLoopGetCurrent() -> CFRunLoopRef;
+    fn CFRunLoopAddSource(run_loop: CFRunLoopRef, source: CFRunLoopSourceRef, mode: CFStringRef);
+    fn CFRunLoopRemoveSource(run_loop: CFRunLoopRef, source: CFRunLoopSourceRef, mode: CFStringRef);
+    fn CFRunLoopRunInMode(
+        mode: CFStringRef,
+        seconds: f64,
+        return_after_source_handled: Boolean,
+    ) -> i32;
+    fn CFRelease(cf: CFTypeRef);
+
+    static kCFRunLoopDefaultMode: CFStringRef;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoubleTapModifier {
+    Fn,
+    LeftCommand,
+    RightCommand,
+    LeftOption,
+    RightOption,
+    LeftControl,
+    RightControl,
+    LeftShift,
+    RightShift,
+}
+
+impl DoubleTapModifier {
+    const ALL: [Self; 9] = [
+        Self::Fn,
+        Self::LeftCommand,
+        Self::RightCommand,
+        Self::LeftOption,
+        Self::RightOption,
+        Self::LeftControl,
+        Self::RightControl,
+        Self::LeftShift,
+        Self::RightShift,
+    ];
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let normalized = value.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|modifier| modifier.as_str() == normalized)
+            .ok_or_else(|| format!("Unsupported double-tap modifier `{value}`"))
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fn => "fn",
+            Self::LeftCommand => "left_cmd",
+            Self::RightCommand => "right_cmd",
+            Self::LeftOption => "left_alt",
+            Self::RightOption => "right_alt",
+            Self::LeftControl => "left_ctrl",
+            Self::RightControl => "right_ctrl",
+            Self::LeftShift => "left_shift",
+            Self::RightShift => "right_shift",
+        }
+    }
+
+    fn key_code(self) -> i64 {
+        match self {
+            Self::Fn => 0x3F,
+            Self::LeftCommand => 0x37,
+            Self::RightCommand => 0x36,
+            Self::LeftOption => 0x3A,
+            Self::RightOption => 0x3D,
+            Self::LeftControl => 0x3B,
+            Self::RightControl => 0x3E,
+            Self::LeftShift => 0x38,
+            Self::RightShift => 0x3C,
+        }
+    }
+
+    // Device-dependent flag bits let us tell the left and right keys apart.
+    fn flag_mask(self) -> CGEventFlags {
+        match self {
+            Self::Fn => 0x0080_0000,
+            Self::LeftCommand => 0x0000_0008,
+            Self::RightCommand => 0x0000_0010,
+            Self::LeftOption => 0x0000_0020,
+            Self::RightOption => 0x0000_0040,
+            Self::LeftControl => 0x0000_0001,
+            Self::RightControl => 0x0000_2000,
+            Self::LeftShift => 0x0000_0002,
+            Self::RightShift => 0x0000_0004,
+        }
+    }
+
+    fn from_key_code(key_code: i64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|modifier| modifier.key_code() == key_code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoubleTapBinding {
+    pub modifier: DoubleTapModifier,
+    pub interval: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModifierKeyEvent {
+    Down(DoubleTapModifier),
+    Up(DoubleTapModifier),
+    Other,
+}
+
+#[derive(Debug)]
+struct DoubleTapDetector {
+    binding: DoubleTapBinding,
+    pressed_at: Option<Instant>,
+    last_tap_at: Option<Instant>,
+}
+
+impl DoubleTapDetector {
+    fn new(binding: DoubleTapBinding) -> Self {
+        Self {
+            binding,
+            pressed_at: None,
+            last_tap_at: None,
+        }
+    }
+
+    fn handle(&mut self, event: ModifierKeyEvent, at: Instant) -> bool {
+        let within_interval =
+            |earlier: Instant| at.saturating_duration_since(earlier) <= self.binding.interval;
+
+        match event {
+            ModifierKeyEvent::Down(modifier) if modifier == self.binding.modifier => {
+                if self.last_tap_at.take().is_some_and(within_interval) {
+                    self.pressed_at = None;
+                    return true;
+                }
+                self.pressed_at = Some(at);
+            }
+            ModifierKeyEvent::Up(modifier) if modifier == self.binding.modifier => {
+                // Holding the modifier past the interval is not a tap.
+                self.last_tap_at = self
+                    .pressed_at
+                    .take()
+                    .filter(|pressed_at| within_interval(*pressed_at))
+                    .map(|_| at);
+            }
+            _ => {
+                self.pressed_at = None;
+                self.last_tap_at = None;
+            }
+        }
+
+        false
+    }
+}
+
+#[derive(Debug)]
+pub struct DoubleTapMonitor {
+    binding: DoubleTapBinding,
+    stop: Arc<AtomicBool>,
+}
+
+impl DoubleTapMonitor {
+    pub fn spawn(binding: DoubleTapBinding, on_double_tap: impl FnMut() + Send + 'static) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = Arc::clone(&stop);
+        thread::spawn(move || run_event_tap(binding, worker_stop, Box::new(on_double_tap)));
+        Self { binding, stop }
+    }
+
+    pub fn binding(&self) -> DoubleTapBinding {
+        self.binding
+    }
+
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+struct EventTapContext {
+    detector: DoubleTapDetector,
+    on_double_tap: Box<dyn FnMut() + Send>,
+    tap: CFMachPortRef,
+}
+
+fn run_event_tap(
+    binding: DoubleTapBinding,
+    stop: Arc<AtomicBool>,
+    on_double_tap: Box<dyn FnMut() + Send>,
+) {
+    let context = Box::into_raw(Box::new(EventTapContext {
+        detector: DoubleTapDetector::new(binding),
+        on_double_tap,
+        tap: ptr::null_mut(),
+    }));
+    let events_of_interest = (1_u64 << K_CG_EVENT_FLAGS_CHANGED) | (1_u64 << K_CG_EVENT_KEY_DOWN);
+
+    unsafe {
+        let tap = CGEventTapCreate(
+            K_CG_SESSION_EVENT_TAP,
+            K_CG_HEAD_INSERT_EVENT_TAP,
+            K_CG_EVENT_TAP_OPTION_LISTEN_ONLY,
+            events_of_interest,
+            handle_tap_event,
+            context.cast(),
+        );
+        if tap.is_null() {
+            warn!(
+                modifier = binding.modifier.as_str(),
+                "failed to create modifier event tap; input monitoring permission may be missing"
+            );
+            drop(Box::from_raw(context));
+            return;
+        }
+        (*context).tap = tap;
+
+        let source = CFMachPortCreateRunLoopSource(ptr::null(), tap, 0);
+        let run_loop = CFRunLoopGetCurrent();
+        CFRunLoopAddSource(run_loop, source, kCFRunLoopDefaultMode);
+        CGEventTapEnable(tap, true);
+        info!(
+            modifier = binding.modifier.as_str(),
+            interval_ms = binding.interval.as_millis() as u64,
+            "listening for double-tap modifier"
+        );
+
+        while !stop.load(Ordering::SeqCst) {
+            CFRunLoopRunInMode(kCFRunLoopDefaultMode, RUN_LOOP_POLL_SECS, 0);
+        }
+
+        CGEventTapEnable(tap, false);
+        CFRunLoopRemoveSource(run_loop, source, kCFRunLoopDefaultMode);
+        CFMachPortInvalidate(tap);
+        CFRelease(source.cast_const());
+        CFRelease(tap.cast_const());
+        drop(Box::from_raw(context));
+    }
+
+    debug!(
+        modifier = binding.modifier.as_str(),
+        "double-tap modifier listener stopped"
+    );
+}
+
+extern "C" fn handle_tap_event(
+    _proxy: CGEventTapProxy,
+    event_type: u32,
+    event: CGEventRef,
+    user_info: *mut c_void,
+) -> CGEventRef {
+    let context = unsafe { &mut *user_info.cast::<EventTapContext>() };
+
+    let key_event = match event_type {
+        K_CG_EVENT_TAP_DISABLED_BY_TIMEOUT | K_CG_EVENT_TAP_DISABLED_BY_USER_INPUT => {
+            warn!("modifier event tap was disabled; re-enabling");
+            unsafe { CGEventTapEnable(context.tap, true) };
+            return event;
+        }
+        K_CG_EVENT_FLAGS_CHANGED => {
+            let (key_code, flags) = unsafe {
+                (
+                    CGEventGetIntegerValueField(event, K_CG_KEYBOARD_EVENT_KEYCODE),
+                    CGEventGetFlags(event),
+                )
+            };
+            match DoubleTapModifier::from_key_code(key_code) {
+                Some(modifier) if flags & modifier.flag_mask() != 0 => {
+                    ModifierKeyEvent::Down(modifier)
+                }
+                Some(modifier) => ModifierKeyEvent::Up(modifier),
+                None => ModifierKeyEvent::Other,
+            }
+        }
+        _ => ModifierKeyEvent::Other,
+    };
+
+    if context.detector.handle(key_event, Instant::now()) {
+        debug!(
+            modifier = context.detector.binding.modifier.as_str(),
+            "double-tap modifier detected"
+        );
+        (context.on_double_tap)();
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{DoubleTapBinding, DoubleTapDetector, DoubleTapModifier, ModifierKeyEvent};
+
+    const INTERVAL: Duration = Duration::from_millis(300);
+
+    fn detector() -> DoubleTapDetector {
+        DoubleTapDetector::new(DoubleTapBinding {
+            modifier: DoubleTapModifier::RightCommand,
+            interval: INTERVAL,
+        })
+    }
+
+    fn tap(detector: &mut DoubleTapDetector, modifier: DoubleTapModifier, at: Instant) -> bool {
+        let fired = detector.handle(ModifierKeyEvent::Down(modifier), at);
+        detector.handle(
+            ModifierKeyEvent::Up(modifier),
+            at + Duration::from_millis(50),
+        );
+        fired
+    }
+
+    #[test]
+    fn parses_modifier_names_case_insensitively() {
+        assert_eq!(
+            DoubleTapModifier::parse(" Right_Cmd "),
+            Ok(DoubleTapModifier::RightCommand)
+        );
+        assert_eq!(DoubleTapModifier::parse("fn"), Ok(DoubleTapModifier::Fn));
+        assert!(DoubleTapModifier::parse("caps_lock").is_err());
+    }
+
+    #[test]
+    fn second_tap_within_interval_fires_once() {
+        let mut detector = detector();
+        let start = Instant::now();
+
+        assert!(!tap(&mut detector, DoubleTapModifier::RightCommand, start));
+        assert!(tap(
+            &mut detector,
+            DoubleTapModifier::RightCommand,
+            start + Duration::from_millis(200)
+        ));
+        assert!(!tap(
+            &mut detector,
+            DoubleTapModifier::RightCommand,
+            start + Duration::from_millis(400)
+        ));
+    }
+
+    #[test]
+    fn slow_taps_and_held_modifiers_do_not_fire() {
+        let mut detector = detector();
+        let start = Instant::now();
+
+        tap(&mut detector, DoubleTapModifier::RightCommand, start);
+        assert!(!tap(
+            &mut detector,
+            DoubleTapModifier::RightCommand,
+            start + Duration::from_millis(700)
+        ));
+
+        let held_at = start + Duration::from_secs(2);
+        detector.handle(
+            ModifierKeyEvent::Down(DoubleTapModifier::RightCommand),
+            held_at,
+        );
+        detector.handle(
+            ModifierKeyEvent::Up(DoubleTapModifier::RightCommand),
+            held_at + Duration::from_millis(500),
+        );
+        assert!(!detector.handle(
+            ModifierKeyEvent::Down(DoubleTapModifier::RightCommand),
+            held_at + Duration::from_millis(600)
+        ));
+    }
+
+    #[test]
+    fn other_keys_between_taps_reset_detection() {
+        let mut detector = detector();
+        let start = Instant::now();
+
+        tap(&mut detector, DoubleTapModifier::RightCommand, start);
+        detector.handle(ModifierKeyEvent::Other, start + Duration::from_millis(80));
+        assert!(!tap(
+            &mut detector,
+            DoubleTapModifier::RightCommand,
+            start + Duration::from_millis(150)
+        ));
+
+        let later = start + Duration::from_secs(2);
+        tap(&mut detector, DoubleTapModifier::RightCommand, later);
+        tap(
+            &mut detector,
+            DoubleTapModifier::LeftCommand,
+            later + Duration::from_millis(100),
+        );
+        assert!(!tap(
+            &mut detector,
+            DoubleTapModifier::RightCommand,
+            later + Duration::from_millis(200)
+        ));
+    }
+}
@@ -1,18 +1,53 @@
+mod double_tap;
+mod suspension;
+
 use std::{
     collections::VecDeque,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
+use chrono::{SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, Runtime, State};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 use tracing::{debug, error, info, warn};
 
+use double_tap::{DoubleTapBinding, DoubleTapModifier, DoubleTapMonitor};
+use suspension::SuspensionWatcher;
+pub use suspension::{frontmost_application, FrontmostApplication};
+
+#[cfg(target_os = "linux")]
+use crate::platform_support::{self, portal::GlobalShortcutsSession};
+
 pub const DEFAULT_SHORTCUT: &str = "Alt+Space";
 pub const EVENT_HOTKEY_CONFIG_CHANGED: &str = "voice://hotkey-config-changed";
 pub const EVENT_RECORDING_STATE_CHANGED: &str = "voice://recording-state-changed";
 pub const EVENT_RECORDING_STARTED: &str = "voice://recording-started";
 pub const EVENT_RECORDING_STOPPED: &str = "voice://recording-stopped";
+pub const EVENT_HOTKEY_CAPTURED: &str = "voice://hotkey-captured";
+pub const EVENT_HOTKEY_SUSPENSION_CHANGED: &str = "voice://hotkey-suspension-changed";
+pub const EVENT_HOTKEY_LONG_PRESSED: &str = "voice://hotkey-long-pressed";
+
+const RESERVED_SHORTCUTS: [(&str, &str); 17] = [
+    ("Cmd+Q", "quits the focused app"),
+    ("Cmd+W", "closes the focused window"),
+    ("Cmd+H", "hides the focused app"),
+    ("Cmd+M", "minimizes the focused window"),
+    ("Cmd+Tab", "switches apps"),
+    ("Cmd+Space", "opens Spotlight"),
+    ("Ctrl+Space", "switches input sources"),
+    ("Cmd+A", "selects all"),
+    ("Cmd+C", "copies"),
+    ("Cmd+V", "pastes"),
+    ("Cmd+X", "cuts"),
+    ("Cmd+Z", "undoes"),
+    ("Cmd+Shift+3", "captures the screen"),
+    ("Cmd+Shift+4", "captures a screen region"),
+    ("Cmd+Shift+5", "opens the screenshot toolbar"),
+    ("Ctrl+Cmd+Q", "locks the screen"),
+    ("Cmd+Alt+Escape", "opens Force Quit"),
+];
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -50,12 +85,46 @@ pub enum RecordingTransition {
     Stopped,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyRecordingState {
+    #[default]
+    Idle,
+    Starting,
+    Recording,
+    Stopping,
+}
+
+impl HotkeyRecordingState {
+    pub fn is_recording(self) -> bool {
+        matches!(self, Self::Recording | Self::Stopping)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransition {
+    pub session_id: u64,
+    pub transition: RecordingTransition,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StopProcessingDecision {
     Ignore,
     DeferUntilStarted,
-    AcknowledgeOnly,
-    Process,
+    AcknowledgeOnly(u64),
+    Process(u64),
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyStateSnapshot {
+    pub state: HotkeyRecordingState,
+    pub session_id: u64,
+    pub pending_transitions: Vec<PendingTransition>,
+    pub last_requested_at: Option<String>,
+    pub last_acknowledged_at: Option<String>,
+    pub rejected_acknowledgements: u64,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
@@ -63,6 +132,7 @@ pub enum StopProcessingDecision {
 pub enum HotkeyTrigger {
     Pressed,
     Released,
+    External,
 }
 
 impl From<ShortcutState> for HotkeyTrigger {
@@ -77,6 +147,7 @@ impl From<ShortcutState> for HotkeyTrigger {
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordingStateChangedEvent {
+    pub session_id: u64,
     pub is_recording: bool,
     pub mode: RecordingMode,
     pub shortcut: String,
@@ -84,28 +155,73 @@ pub struct RecordingStateChangedEvent {
     pub trigger: HotkeyTrigger,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyCaptureStatus {
+    Captured,
+    Rejected,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyCaptureEvent {
+    pub status: HotkeyCaptureStatus,
+    pub shortcut: Option<String>,
+    pub error: Option<String>,
+    pub registered: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeySuspensionChangedEvent {
+    pub suspended: bool,
+    pub application: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct HotkeySuspension {
+    watcher: Option<SuspensionWatcher>,
+    suspended_by: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct HotkeyCapture {
+    previous: HotkeyConfig,
+    register: bool,
+}
+
+#[derive(Debug, Default)]
+struct LongPressState {
+    threshold: Option<Duration>,
+    pressed_at: Option<Instant>,
+    alternate_requested: bool,
+}
+
+#[derive(Debug, Default)]
 struct HotkeyRuntimeState {
     config: HotkeyConfig,
     registered_shortcut: Option<String>,
-    is_recording: bool,
+    recording_state: HotkeyRecordingState,
     desired_recording: bool,
-    pending_transitions: VecDeque<RecordingTransition>,
+    session_id: u64,
+    pending_transitions: VecDeque<PendingTransition>,
+    last_requested_at: Option<String>,
+    last_acknowledged_at: Option<String>,
+    rejected_acknowledgements: u64,
+    capture: Option<HotkeyCapture>,
+    long_press: LongPressState,
 }
 
-impl Default for HotkeyRuntimeState {
-    fn default() -> Self {
-        Self {
-            config: HotkeyConfig::default(),
-            registered_shortcut: None,
-            is_recording: false,
-            desired_recording: false,
-            pending_transitions: VecDeque::new(),
-        }
+impl HotkeyRuntimeState {
+    fn is_recording(&self) -> bool {
+        self.recording_state.is_recording()
+    }
+
+    fn is_busy(&self) -> bool {
+        self.recording_state != HotkeyRecordingState::Idle || self.desired_recording
     }
-}
 
-impl HotkeyRuntimeState {
     fn apply_shortcut_event(
         &mut self,
         shortcut_state: ShortcutState,
@@ -114,50 +230,148 @@ impl HotkeyRuntimeState {
             resolve_transition(self.config.mode, self.desired_recording, shortcut_state)?;
 
         self.desired_recording = next_recording_state;
-        self.pending_transitions.push_back(transition);
+        self.push_transition(transition);
         Some(transition)
     }
 
-    fn acknowledge_transition(&mut self, transition: RecordingTransition, success: bool) {
-        if self.pending_transitions.front().copied() == Some(transition) {
-            self.pending_transitions.pop_front();
-        } else if let Some(index) = self
+    fn push_transition(&mut self, transition: RecordingTransition) {
+        if transition == RecordingTransition::Started {
+            self.session_id += 1;
+        }
+        self.pending_transitions.push_back(PendingTransition {
+            session_id: self.session_id,
+            transition,
+        });
+        self.last_requested_at = Some(timestamp_now());
+        self.advance();
+    }
+
+    fn advance(&mut self) {
+        let next = self
             .pending_transitions
-            .iter()
-            .position(|pending| *pending == transition)
-        {
-            self.pending_transitions.remove(index);
+            .front()
+            .map(|pending| pending.transition);
+        self.recording_state = match (self.recording_state, next) {
+            (HotkeyRecordingState::Idle, Some(RecordingTransition::Started)) => {
+                HotkeyRecordingState::Starting
+            }
+            (HotkeyRecordingState::Recording, Some(RecordingTransition::Stopped)) => {
+                HotkeyRecordingState::Stopping
+            }
+            (state, _) => state,
+        };
+    }
+
+    fn track_long_press(
+        &mut self,
+        shortcut_state: ShortcutState,
+        transition: Option<RecordingTransition>,
+        at: Instant,
+    ) -> bool {
+        let Some(threshold) = self.long_press.threshold else {
+            return false;
+        };
+        if self.config.mode != RecordingMode::Toggle {
+            return false;
+        }
+
+        match (shortcut_state, transition) {
+            (ShortcutState::Pressed, Some(RecordingTransition::Started)) => {
+                self.long_press.pressed_at = Some(at);
+                self.long_press.alternate_requested = false;
+                false
+            }
+            (ShortcutState::Released, _) => {
+                let long_pressed = self.long_press.pressed_at.take().is_some_and(|pressed_at| {
+                    at.saturating_duration_since(pressed_at) >= threshold
+                });
+                if long_pressed {
+                    self.long_press.alternate_requested = true;
+                }
+                long_pressed
+            }
+            _ => false,
         }
+    }
 
-        self.is_recording = match transition {
-            RecordingTransition::Started => success,
-            RecordingTransition::Stopped => false,
+    fn apply_external_toggle(&mut self) -> Option<RecordingTransition> {
+        let requested = if self.desired_recording {
+            RecordingTransition::Stopped
+        } else {
+            RecordingTransition::Started
         };
+        self.apply_external_request(requested)
+    }
 
+    fn apply_external_request(
+        &mut self,
+        requested: RecordingTransition,
+    ) -> Option<RecordingTransition> {
+        let next_recording_state = matches!(requested, RecordingTransition::Started);
+        if self.desired_recording == next_recording_state {
+            return None;
+        }
+
+        self.desired_recording = next_recording_state;
+        self.push_transition(requested);
+        Some(requested)
+    }
+
+    fn acknowledge_transition(
+        &mut self,
+        session_id: u64,
+        transition: RecordingTransition,
+        success: bool,
+    ) -> bool {
+        let expected = PendingTransition {
+            session_id,
+            transition,
+        };
+        let unrequested_stop = transition == RecordingTransition::Stopped
+            && session_id == self.session_id
+            && self.recording_state == HotkeyRecordingState::Recording;
+        if self.pending_transitions.front() == Some(&expected) {
+            self.pending_transitions.pop_front();
+        } else if !unrequested_stop {
+            self.rejected_acknowledgements += 1;
+            return false;
+        }
+
+        self.recording_state = match transition {
+            RecordingTransition::Started if success => HotkeyRecordingState::Recording,
+            _ => HotkeyRecordingState::Idle,
+        };
+        self.last_acknowledged_at = Some(timestamp_now());
         self.recompute_desired_recording();
+        self.advance();
+        true
     }
 
     fn recompute_desired_recording(&mut self) {
-        let mut desired_recording = self.is_recording;
-        for pending_transition in &self.pending_transitions {
-            desired_recording = matches!(pending_transition, RecordingTransition::Started);
+        let mut desired_recording = self.is_recording();
+        for pending in &self.pending_transitions {
+            desired_recording = matches!(pending.transition, RecordingTransition::Started);
         }
 
         self.desired_recording = desired_recording;
     }
 
-    fn clear_registered_shortcut(&mut self) {
-        self.registered_shortcut = None;
-        self.is_recording = false;
+    fn reset_recording(&mut self) {
+        self.recording_state = HotkeyRecordingState::Idle;
         self.desired_recording = false;
         self.pending_transitions.clear();
     }
 
+    fn clear_registered_shortcut(&mut self) {
+        self.registered_shortcut = None;
+        self.reset_recording();
+    }
+
     fn stop_processing_decision(&self) -> StopProcessingDecision {
         let Some(stop_index) = self
             .pending_transitions
             .iter()
-            .position(|transition| matches!(transition, RecordingTransition::Stopped))
+            .position(|pending| matches!(pending.transition, RecordingTransition::Stopped))
         else {
             return StopProcessingDecision::Ignore;
         };
@@ -166,16 +380,43 @@ impl HotkeyRuntimeState {
             .pending_transitions
             .iter()
             .take(stop_index)
-            .any(|transition| matches!(transition, RecordingTransition::Started));
+            .any(|pending| matches!(pending.transition, RecordingTransition::Started));
 
         if has_start_before_stop {
             return StopProcessingDecision::DeferUntilStarted;
         }
 
-        if self.is_recording {
-            StopProcessingDecision::Process
+        let session_id = self.pending_transitions[stop_index].session_id;
+        if self.is_recording() {
+            StopProcessingDecision::Process(session_id)
         } else {
-            StopProcessingDecision::AcknowledgeOnly
+            StopProcessingDecision::AcknowledgeOnly(session_id)
+        }
+    }
+
+    fn snapshot(&self) -> HotkeyStateSnapshot {
+        HotkeyStateSnapshot {
+            state: self.recording_state,
+            session_id: self.session_id,
+            pending_transitions: self.pending_transitions.iter().copied().collect(),
+            last_requested_at: self.last_requested_at.clone(),
+            last_acknowledged_at: self.last_acknowledged_at.clone(),
+            rejected_acknowledgements: self.rejected_acknowledgements,
+        }
+    }
+
+    fn event_payload(
+        &self,
+        transition: RecordingTransition,
+        trigger: HotkeyTrigger,
+    ) -> RecordingStateChangedEvent {
+        RecordingStateChangedEvent {
+            session_id: self.session_id,
+            is_recording: self.is_recording(),
+            mode: self.config.mode,
+            shortcut: self.config.shortcut.clone(),
+            transition,
+            trigger,
         }
     }
 }
@@ -183,6 +424,10 @@ impl HotkeyRuntimeState {
 #[derive(Debug, Clone)]
 pub struct HotkeyService {
     state: Arc<Mutex<HotkeyRuntimeState>>,
+    double_tap_monitor: Arc<Mutex<Option<DoubleTapMonitor>>>,
+    suspension: Arc<Mutex<HotkeySuspension>>,
+    #[cfg(target_os = "linux")]
+    portal_shortcuts: Arc<Mutex<Option<GlobalShortcutsSession>>>,
 }
 
 impl Default for HotkeyService {
@@ -196,6 +441,10 @@ impl HotkeyService {
         debug!("hotkey service initialized");
         Self {
             state: Arc::new(Mutex::new(HotkeyRuntimeState::default())),
+            double_tap_monitor: Arc::new(Mutex::new(None)),
+            suspension: Arc::new(Mutex::new(HotkeySuspension::default())),
+            #[cfg(target_os = "linux")]
+            portal_shortcuts: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -217,17 +466,51 @@ impl HotkeyService {
     pub fn is_recording(&self) -> bool {
         self.state
             .lock()
-            .map(|state| state.is_recording)
+            .map(|state| state.is_recording())
             .unwrap_or(false)
     }
 
-    pub fn acknowledge_transition(&self, transition: RecordingTransition, success: bool) {
-        if let Ok(mut state) = self.state.lock() {
-            debug!(?transition, success, "acknowledging hotkey transition");
-            state.acknowledge_transition(transition, success);
-        } else {
+    pub fn recording_session_id(&self) -> Option<u64> {
+        self.state
+            .lock()
+            .ok()
+            .filter(|state| state.is_recording())
+            .map(|state| state.session_id)
+    }
+
+    pub fn state_snapshot(&self) -> Option<HotkeyStateSnapshot> {
+        self.state.lock().ok().map(|state| state.snapshot())
+    }
+
+    pub fn acknowledge_transition(
+        &self,
+        session_id: u64,
+        transition: RecordingTransition,
+        success: bool,
+    ) -> bool {
+        let Ok(mut state) = self.state.lock() else {
             error!("hotkey state lock poisoned while acknowledging transition");
+            return false;
+        };
+        let accepted = state.acknowledge_transition(session_id, transition, success);
+        if accepted {
+            debug!(
+                session_id,
+                ?transition,
+                success,
+                state = ?state.recording_state,
+                "hotkey transition acknowledged"
+            );
+        } else {
+            warn!(
+                session_id,
+                ?transition,
+                success,
+                pending = ?state.pending_transitions.front(),
+                "rejected out-of-order hotkey acknowledgement"
+            );
         }
+        accepted
     }
 
     pub fn stop_processing_decision(&self) -> StopProcessingDecision {
@@ -247,29 +530,19 @@ impl HotkeyService {
                 }
             };
 
-            let was_active = state.is_recording
-                || state.desired_recording
-                || !state.pending_transitions.is_empty();
+            let was_active = state.is_busy() || !state.pending_transitions.is_empty();
 
             if !was_active {
                 debug!("force stop requested while not recording");
                 return false;
             }
 
-            state.is_recording = false;
-            state.desired_recording = false;
-            state.pending_transitions.clear();
-
-            RecordingStateChangedEvent {
-                is_recording: false,
-                mode: state.config.mode,
-                shortcut: state.config.shortcut.clone(),
-                transition: RecordingTransition::Stopped,
-                trigger: HotkeyTrigger::Released,
-            }
+            state.reset_recording();
+            state.event_payload(RecordingTransition::Stopped, HotkeyTrigger::Released)
         };
 
         info!(
+            session_id = payload.session_id,
             mode = ?payload.mode,
             shortcut = %payload.shortcut,
             "forced hotkey recording stop"
@@ -280,6 +553,25 @@ impl HotkeyService {
         true
     }
 
+    pub fn unregister_all<R: Runtime>(&self, app: &AppHandle<R>) {
+        if let Err(error) = app.global_shortcut().unregister_all() {
+            warn!(%error, "failed to unregister global shortcuts");
+        }
+        if let Some(monitor) = self
+            .double_tap_monitor
+            .lock()
+            .ok()
+            .and_then(|mut monitor| monitor.take())
+        {
+            monitor.stop();
+        }
+        #[cfg(target_os = "linux")]
+        if let Ok(mut session) = self.portal_shortcuts.lock() {
+            session.take();
+        }
+        info!("hotkeys unregistered");
+    }
+
     pub fn apply_config<R: Runtime>(
         &self,
         app: &AppHandle<R>,
@@ -291,6 +583,17 @@ impl HotkeyService {
             "applying hotkey configuration"
         );
         let service = self.clone();
+        #[cfg(target_os = "linux")]
+        if platform_support::prefers_portal_shortcuts() {
+            return apply_config_with_registrar(
+                &self.state,
+                config,
+                // Each bind replaces the session's shortcut, so there is nothing to release.
+                |_shortcut| Ok(()),
+                |shortcut| service.bind_portal_shortcut(app, shortcut),
+                |config| emit_hotkey_config_changed(app, config),
+            );
+        }
         apply_config_with_registrar(
             &self.state,
             config,
@@ -305,13 +608,209 @@ impl HotkeyService {
                     .on_shortcut(shortcut, move |app, _shortcut, event| {
                         callback_service.handle_shortcut_event(app, event.state);
                     })
-                    .map_err(|error| error.to_string())
+                    .map_err(|error| describe_registration_error(&error.to_string()))
             },
             |config| emit_hotkey_config_changed(app, config),
         )
     }
 
-    fn handle_shortcut_event<R: Runtime>(&self, app: &AppHandle<R>, shortcut_state: ShortcutState) {
+    #[cfg(target_os = "linux")]
+    fn bind_portal_shortcut<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        shortcut: &str,
+    ) -> Result<(), String> {
+        let mut session = self.portal_shortcuts.lock().map_err(|_| lock_error())?;
+        if session.is_none() {
+            let callback_service = self.clone();
+            let callback_app = app.clone();
+            *session = Some(GlobalShortcutsSession::create(move |pressed| {
+                let shortcut_state = if pressed {
+                    ShortcutState::Pressed
+                } else {
+                    ShortcutState::Released
+                };
+                callback_service.handle_shortcut_event(&callback_app, shortcut_state);
+            })?);
+        }
+
+        session
+            .as_ref()
+            .ok_or_else(|| "Global shortcuts portal session was unavailable".to_string())?
+            .bind(
+                "Start or stop dictation",
+                &platform_support::portal_trigger(shortcut),
+            )
+    }
+
+    pub fn apply_user_config<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        config: HotkeyConfig,
+    ) -> Result<HotkeyConfig, String> {
+        let config = normalize_config(config);
+        let already_owned = {
+            let state = self.state.lock().map_err(|_| lock_error())?;
+            state
+                .registered_shortcut
+                .as_deref()
+                .is_some_and(|registered| shortcuts_match(registered, &config.shortcut))
+        };
+
+        if !already_owned {
+            let registered_elsewhere = app
+                .global_shortcut()
+                .is_registered(config.shortcut.as_str());
+            if let Err(error) = detect_shortcut_conflict(&config.shortcut, registered_elsewhere) {
+                warn!(shortcut = %config.shortcut, %error, "hotkey conflict detected");
+                return Err(error);
+            }
+        }
+
+        self.apply_config(app, config)
+    }
+
+    pub fn begin_capture<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        register: bool,
+    ) -> Result<(), String> {
+        let registered_shortcut = {
+            let mut state = self.state.lock().map_err(|_| lock_error())?;
+            if state.capture.is_some() {
+                return Err("A hotkey capture is already in progress".to_string());
+            }
+            if state.is_busy() {
+                return Err("Stop recording before capturing a new hotkey".to_string());
+            }
+
+            state.capture = Some(HotkeyCapture {
+                previous: state.config.clone(),
+                register,
+            });
+            state.registered_shortcut.clone()
+        };
+
+        if let Some(shortcut) = registered_shortcut {
+            if let Err(error) = app.global_shortcut().unregister(shortcut.as_str()) {
+                if let Ok(mut state) = self.state.lock() {
+                    state.capture = None;
+                }
+                return Err(format!(
+                    "Failed to suspend hotkey `{shortcut}` for capture: {error}"
+                ));
+            }
+            let mut state = self.state.lock().map_err(|_| lock_error())?;
+            state.clear_registered_shortcut();
+        }
+
+        info!(register, "hotkey capture started");
+        Ok(())
+    }
+
+    pub fn finish_capture<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        shortcut: &str,
+    ) -> Result<HotkeyCaptureEvent, String> {
+        let capture = self
+            .take_capture()?
+            .ok_or_else(|| "No hotkey capture is in progress".to_string())?;
+        let shortcut = shortcut.trim().to_string();
+
+        let event = match validate_captured_shortcut(&shortcut) {
+            Err(error) => {
+                self.restore_after_capture(app, &capture.previous);
+                HotkeyCaptureEvent {
+                    status: HotkeyCaptureStatus::Rejected,
+                    shortcut: Some(shortcut),
+                    error: Some(error),
+                    registered: false,
+                }
+            }
+            Ok(()) if capture.register => {
+                let config = HotkeyConfig {
+                    shortcut: shortcut.clone(),
+                    mode: capture.previous.mode,
+                };
+                match self.apply_user_config(app, config) {
+                    Ok(config) => HotkeyCaptureEvent {
+                        status: HotkeyCaptureStatus::Captured,
+                        shortcut: Some(config.shortcut),
+                        error: None,
+                        registered: true,
+                    },
+                    Err(error) => {
+                        self.restore_after_capture(app, &capture.previous);
+                        HotkeyCaptureEvent {
+                            status: HotkeyCaptureStatus::Rejected,
+                            shortcut: Some(shortcut),
+                            error: Some(error),
+                            registered: false,
+                        }
+                    }
+                }
+            }
+            Ok(()) => {
+                self.restore_after_capture(app, &capture.previous);
+                HotkeyCaptureEvent {
+                    status: HotkeyCaptureStatus::Captured,
+                    shortcut: Some(shortcut),
+                    error: None,
+                    registered: false,
+                }
+            }
+        };
+
+        info!(
+            status = ?event.status,
+            shortcut = ?event.shortcut,
+            registered = event.registered,
+            "hotkey capture finished"
+        );
+        emit_hotkey_captured(app, &event);
+        Ok(event)
+    }
+
+    pub fn cancel_capture<R: Runtime>(&self, app: &AppHandle<R>) -> Result<bool, String> {
+        let Some(capture) = self.take_capture()? else {
+            return Ok(false);
+        };
+
+        self.restore_after_capture(app, &capture.previous);
+        info!("hotkey capture cancelled");
+        emit_hotkey_captured(
+            app,
+            &HotkeyCaptureEvent {
+                status: HotkeyCaptureStatus::Cancelled,
+                shortcut: None,
+                error: None,
+                registered: false,
+            },
+        );
+        Ok(true)
+    }
+
+    fn take_capture(&self) -> Result<Option<HotkeyCapture>, String> {
+        let mut state = self.state.lock().map_err(|_| lock_error())?;
+        Ok(state.capture.take())
+    }
+
+    fn restore_after_capture<R: Runtime>(&self, app: &AppHandle<R>, previous: &HotkeyConfig) {
+        if let Err(error) = self.apply_config(app, previous.clone()) {
+            error!(
+                shortcut = %previous.shortcut,
+                %error,
+                "failed to restore hotkey after capture"
+            );
+        }
+    }
+
+    pub fn handle_shortcut_event<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        shortcut_state: ShortcutState,
+    ) {
         let event_payload = {
             let mut state = match self.state.lock() {
                 Ok(state) => state,
@@ -321,48 +820,273 @@ impl HotkeyService {
                 }
             };
 
-            let transition = match state.apply_shortcut_event(shortcut_state) {
-                Some(transition) => transition,
-                None => {
-                    debug!(
-                        ?shortcut_state,
-                        "ignoring shortcut event with no state transition"
-                    );
+            let transition = state.apply_shortcut_event(shortcut_state);
+            if state.track_long_press(shortcut_state, transition, Instant::now()) {
+                info!(shortcut = %state.config.shortcut, "hotkey long press detected");
+                if let Err(error) = app.emit(EVENT_HOTKEY_LONG_PRESSED, &state.config.shortcut) {
+                    warn!(%error, "failed to emit hotkey long press event");
+                }
+            }
+
+            let Some(transition) = transition else {
+                debug!(
+                    ?shortcut_state,
+                    "ignoring shortcut event with no state transition"
+                );
+                return;
+            };
+
+            state.event_payload(transition, shortcut_state.into())
+        };
+
+        emit_transition_events(app, &event_payload);
+    }
+
+    pub fn set_long_press_threshold(&self, threshold: Option<Duration>) {
+        if let Ok(mut state) = self.state.lock() {
+            debug!(?threshold, "hotkey long press threshold updated");
+            state.long_press.threshold = threshold;
+            state.long_press.pressed_at = None;
+        } else {
+            error!("hotkey state lock poisoned while updating long press threshold");
+        }
+    }
+
+    pub fn take_long_press_request(&self) -> bool {
+        self.state
+            .lock()
+            .map(|mut state| std::mem::take(&mut state.long_press.alternate_requested))
+            .unwrap_or(false)
+    }
+
+    pub fn sync_double_tap<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        modifier: Option<&str>,
+        interval_ms: u64,
+    ) {
+        let binding = match modifier.map(DoubleTapModifier::parse).transpose() {
+            Ok(modifier) => modifier.map(|modifier| DoubleTapBinding {
+                modifier,
+                interval: Duration::from_millis(interval_ms),
+            }),
+            Err(error) => {
+                warn!(%error, "ignoring invalid double-tap modifier");
+                None
+            }
+        };
+
+        let mut monitor = match self.double_tap_monitor.lock() {
+            Ok(monitor) => monitor,
+            Err(_) => {
+                error!("double-tap monitor lock poisoned while applying settings");
+                return;
+            }
+        };
+
+        if monitor.as_ref().map(DoubleTapMonitor::binding) == binding {
+            return;
+        }
+
+        if let Some(previous) = monitor.take() {
+            previous.stop();
+            info!(
+                modifier = previous.binding().modifier.as_str(),
+                "double-tap modifier monitor stopped"
+            );
+        }
+
+        *monitor = binding.map(|binding| {
+            let service = self.clone();
+            let app = app.clone();
+            DoubleTapMonitor::spawn(binding, move || {
+                if service.is_suspended() {
+                    debug!("ignoring double-tap modifier while hotkeys are suspended");
+                    return;
+                }
+                service.request_recording_toggle(&app);
+            })
+        });
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspension
+            .lock()
+            .map(|suspension| suspension.suspended_by.is_some())
+            .unwrap_or(false)
+    }
+
+    pub fn sync_suspended_apps<R: Runtime>(&self, app: &AppHandle<R>, apps: &[String]) {
+        let was_suspended = {
+            let mut suspension = match self.suspension.lock() {
+                Ok(suspension) => suspension,
+                Err(_) => {
+                    error!("hotkey suspension lock poisoned while applying settings");
                     return;
                 }
             };
 
-            RecordingStateChangedEvent {
-                is_recording: state.is_recording,
-                mode: state.config.mode,
-                shortcut: state.config.shortcut.clone(),
-                transition,
-                trigger: shortcut_state.into(),
+            let current_apps = suspension
+                .watcher
+                .as_ref()
+                .map(SuspensionWatcher::apps)
+                .unwrap_or_default();
+            if current_apps == apps {
+                return;
+            }
+
+            if let Some(previous) = suspension.watcher.take() {
+                previous.stop();
             }
+
+            if !apps.is_empty() {
+                let service = self.clone();
+                let app = app.clone();
+                suspension.watcher = Some(SuspensionWatcher::spawn(
+                    apps.to_vec(),
+                    move |suspended_by| service.set_suspended_by(&app, suspended_by),
+                ));
+            }
+            info!(apps = ?apps, "hotkey suspension apps updated");
+            suspension.suspended_by.is_some()
         };
 
-        info!(
-            transition = ?event_payload.transition,
-            trigger = ?event_payload.trigger,
-            mode = ?event_payload.mode,
-            is_recording = event_payload.is_recording,
-            shortcut = %event_payload.shortcut,
-            "hotkey transition emitted"
-        );
-        if let Err(error) = app.emit(EVENT_RECORDING_STATE_CHANGED, &event_payload) {
-            warn!(%error, "failed to emit recording state change event");
+        if was_suspended {
+            self.set_suspended_by(app, None);
         }
+    }
+
+    fn set_suspended_by<R: Runtime>(&self, app: &AppHandle<R>, suspended_by: Option<&str>) -> bool {
+        match suspended_by {
+            Some(application) => {
+                let registered_shortcut = {
+                    let state = match self.state.lock() {
+                        Ok(state) => state,
+                        Err(_) => {
+                            error!("hotkey state lock poisoned while suspending hotkeys");
+                            return false;
+                        }
+                    };
+                    // Never pull the shortcut out from under an active recording or capture.
+                    if state.is_busy() || state.capture.is_some() {
+                        debug!(application, "deferring hotkey suspension while busy");
+                        return false;
+                    }
+                    state.registered_shortcut.clone()
+                };
+
+                if let Some(shortcut) = registered_shortcut {
+                    if let Err(error) = app.global_shortcut().unregister(shortcut.as_str()) {
+                        warn!(%shortcut, %error, "failed to unregister hotkey for suspension");
+                        return false;
+                    }
+                    if let Ok(mut state) = self.state.lock() {
+                        state.clear_registered_shortcut();
+                    }
+                }
 
-        match event_payload.transition {
-            RecordingTransition::Started => {
-                if let Err(error) = app.emit(EVENT_RECORDING_STARTED, &event_payload) {
-                    warn!(%error, "failed to emit recording started event");
+                if let Ok(mut suspension) = self.suspension.lock() {
+                    suspension.suspended_by = Some(application.to_string());
                 }
+                info!(application, "hotkeys suspended for focused application");
             }
-            RecordingTransition::Stopped => {
-                if let Err(error) = app.emit(EVENT_RECORDING_STOPPED, &event_payload) {
-                    warn!(%error, "failed to emit recording stopped event");
+            None => {
+                let resumed_from = match self.suspension.lock() {
+                    Ok(mut suspension) => suspension.suspended_by.take(),
+                    Err(_) => {
+                        error!("hotkey suspension lock poisoned while resuming hotkeys");
+                        return false;
+                    }
+                };
+                if resumed_from.is_none() {
+                    return true;
+                }
+
+                if let Err(error) = self.apply_config(app, self.current_config()) {
+                    error!(%error, "failed to restore hotkey after suspension");
                 }
+                info!(application = ?resumed_from, "hotkeys resumed");
+            }
+        }
+
+        let event = HotkeySuspensionChangedEvent {
+            suspended: suspended_by.is_some(),
+            application: suspended_by.map(str::to_string),
+        };
+        if let Err(error) = app.emit(EVENT_HOTKEY_SUSPENSION_CHANGED, &event) {
+            warn!(%error, "failed to emit hotkey suspension change event");
+        }
+        true
+    }
+
+    pub fn request_recording_transition<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        requested: RecordingTransition,
+    ) -> bool {
+        self.apply_external_transition(app, |state| state.apply_external_request(requested))
+    }
+
+    pub fn request_recording_toggle<R: Runtime>(&self, app: &AppHandle<R>) -> bool {
+        self.apply_external_transition(app, HotkeyRuntimeState::apply_external_toggle)
+    }
+
+    fn apply_external_transition<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        apply: impl FnOnce(&mut HotkeyRuntimeState) -> Option<RecordingTransition>,
+    ) -> bool {
+        let event_payload = {
+            let mut state = match self.state.lock() {
+                Ok(state) => state,
+                Err(_) => {
+                    error!("hotkey state lock poisoned while handling external recording request");
+                    return false;
+                }
+            };
+
+            let Some(transition) = apply(&mut state) else {
+                debug!("ignoring external recording request with no state transition");
+                return false;
+            };
+            if transition == RecordingTransition::Started {
+                state.long_press.alternate_requested = false;
+            }
+
+            state.event_payload(transition, HotkeyTrigger::External)
+        };
+
+        emit_transition_events(app, &event_payload);
+        true
+    }
+}
+
+fn emit_transition_events<R: Runtime>(
+    app: &AppHandle<R>,
+    event_payload: &RecordingStateChangedEvent,
+) {
+    info!(
+        session_id = event_payload.session_id,
+        transition = ?event_payload.transition,
+        trigger = ?event_payload.trigger,
+        mode = ?event_payload.mode,
+        is_recording = event_payload.is_recording,
+        shortcut = %event_payload.shortcut,
+        "hotkey transition emitted"
+    );
+    if let Err(error) = app.emit(EVENT_RECORDING_STATE_CHANGED, event_payload) {
+        warn!(%error, "failed to emit recording state change event");
+    }
+
+    match event_payload.transition {
+        RecordingTransition::Started => {
+            if let Err(error) = app.emit(EVENT_RECORDING_STARTED, event_payload) {
+                warn!(%error, "failed to emit recording started event");
+            }
+        }
+        RecordingTransition::Stopped => {
+            if let Err(error) = app.emit(EVENT_RECORDING_STOPPED, event_payload) {
+                warn!(%error, "failed to emit recording stopped event");
             }
         }
     }
@@ -453,9 +1177,7 @@ where
         let mut state = state.lock().map_err(|_| lock_error())?;
         state.config = next_config.clone();
         state.registered_shortcut = Some(next_config.shortcut.clone());
-        state.is_recording = false;
-        state.desired_recording = false;
-        state.pending_transitions.clear();
+        state.reset_recording();
     }
 
     info!(
@@ -477,13 +1199,58 @@ pub fn get_hotkey_recording_state(service: State<'_, HotkeyService>) -> bool {
     service.is_recording()
 }
 
+#[tauri::command]
+pub fn get_hotkey_state_snapshot(
+    service: State<'_, HotkeyService>,
+) -> Result<HotkeyStateSnapshot, String> {
+    service.state_snapshot().ok_or_else(lock_error)
+}
+
+pub fn event_session_id(payload: &str) -> Option<u64> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct EventSession {
+        session_id: u64,
+    }
+
+    serde_json::from_str::<EventSession>(payload)
+        .ok()
+        .map(|event| event.session_id)
+}
+
 #[tauri::command]
 pub fn set_hotkey_config(
     app: AppHandle,
     service: State<'_, HotkeyService>,
     config: HotkeyConfig,
 ) -> Result<HotkeyConfig, String> {
-    service.apply_config(&app, config)
+    service.apply_user_config(&app, config)
+}
+
+#[tauri::command]
+pub fn begin_hotkey_capture(
+    app: AppHandle,
+    service: State<'_, HotkeyService>,
+    register: Option<bool>,
+) -> Result<(), String> {
+    service.begin_capture(&app, register.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn submit_hotkey_capture(
+    app: AppHandle,
+    service: State<'_, HotkeyService>,
+    shortcut: String,
+) -> Result<HotkeyCaptureEvent, String> {
+    service.finish_capture(&app, &shortcut)
+}
+
+#[tauri::command]
+pub fn cancel_hotkey_capture(
+    app: AppHandle,
+    service: State<'_, HotkeyService>,
+) -> Result<bool, String> {
+    service.cancel_capture(&app)
 }
 
 fn normalize_config(mut config: HotkeyConfig) -> HotkeyConfig {
@@ -504,6 +1271,63 @@ fn validate_shortcut(shortcut: &str) -> Result<(), String> {
         .map_err(|error| format!("Invalid hotkey `{shortcut}`: {error}"))
 }
 
+fn validate_captured_shortcut(shortcut: &str) -> Result<(), String> {
+    let parsed = shortcut
+        .parse::<Shortcut>()
+        .map_err(|error| format!("Invalid hotkey `{shortcut}`: {error}"))?;
+
+    if parsed.mods.is_empty() && !is_function_key(&parsed.key.to_string()) {
+        return Err(format!(
+            "Hotkey `{shortcut}` needs a modifier key unless it uses a function key"
+        ));
+    }
+
+    check_reserved_shortcut(shortcut)
+}
+
+fn check_reserved_shortcut(shortcut: &str) -> Result<(), String> {
+    match RESERVED_SHORTCUTS
+        .iter()
+        .find(|(reserved, _)| shortcuts_match(reserved, shortcut))
+    {
+        Some((reserved, reason)) => Err(format!(
+            "Hotkey `{shortcut}` is reserved by macOS ({reserved} {reason})"
+        )),
+        None => Ok(()),
+    }
+}
+
+fn detect_shortcut_conflict(shortcut: &str, registered_elsewhere: bool) -> Result<(), String> {
+    check_reserved_shortcut(shortcut)?;
+    if registered_elsewhere {
+        return Err(format!(
+            "Hotkey `{shortcut}` conflicts with a shortcut that is already registered"
+        ));
+    }
+
+    Ok(())
+}
+
+fn describe_registration_error(error: &str) -> String {
+    // macOS reports a combo claimed by another app as a generic RegisterEventHotKey failure.
+    if error.contains("already registered") || error.contains("RegisterEventHotKey failed") {
+        format!("{error} (the shortcut is already in use by another application)")
+    } else {
+        error.to_string()
+    }
+}
+
+fn is_function_key(key: &str) -> bool {
+    key.strip_prefix('F')
+        .is_some_and(|number| number.parse::<u8>().is_ok())
+}
+
+fn emit_hotkey_captured<R: Runtime>(app: &AppHandle<R>, event: &HotkeyCaptureEvent) {
+    if let Err(error) = app.emit(EVENT_HOTKEY_CAPTURED, event) {
+        warn!(%error, "failed to emit hotkey captured event");
+    }
+}
+
 fn shortcuts_match(left: &str, right: &str) -> bool {
     match (left.parse::<Shortcut>(), right.parse::<Shortcut>()) {
         (Ok(left_shortcut), Ok(right_shortcut)) => left_shortcut.id() == right_shortcut.id(),
@@ -566,6 +1390,10 @@ fn lock_error() -> String {
     "Hotkey service state lock was poisoned".to_string()
 }
 
+fn timestamp_now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{sync::Mutex, time::Duration};
@@ -574,9 +1402,9 @@ mod tests {
 
     use crate::{
         audio_capture_service::RecordedAudio,
-        status_notifier::AppStatus,
+        status_notifier::{AppStatus, StatusSource},
         voice_pipeline::{
-            PipelineError, PipelineErrorStage, PipelineTranscript, VoicePipeline,
+            PipelineError, PipelineErrorStage, PipelineTranscript, StageError, VoicePipeline,
             VoicePipelineDelegate,
         },
     };
@@ -619,7 +1447,7 @@ mod tests {
 
     #[async_trait]
     impl VoicePipelineDelegate for StartFailurePipelineDelegate {
-        fn set_status(&self, status: AppStatus) {
+        fn set_status(&self, status: AppStatus, _source: StatusSource) {
             self.statuses
                 .lock()
                 .expect("status lock should not be poisoned")
@@ -640,7 +1468,8 @@ mod tests {
                 .hotkey_state
                 .lock()
                 .expect("hotkey state lock should not be poisoned");
-            hotkey_state.acknowledge_transition(RecordingTransition::Started, success);
+            let session_id = hotkey_state.session_id;
+            hotkey_state.acknowledge_transition(session_id, RecordingTransition::Started, success);
         }
 
         fn start_recording(&self) -> Result<(), String> {
@@ -654,15 +1483,22 @@ mod tests {
         async fn transcribe(
             &self,
             _recorded_audio: RecordedAudio,
-        ) -> Result<PipelineTranscript, String> {
+        ) -> Result<PipelineTranscript, StageError> {
             panic!("transcribe should not be called for start failure scenario");
         }
 
-        fn insert_text(&self, _transcript: &str) -> Result<(), String> {
+        async fn insert_text(&self, _transcript: &str) -> Result<(), String> {
             panic!("insert_text should not be called for start failure scenario");
         }
     }
 
+    fn pending(session_id: u64, transition: RecordingTransition) -> PendingTransition {
+        PendingTransition {
+            session_id,
+            transition,
+        }
+    }
+
     #[test]
     fn default_config_is_hold_to_talk_with_option_space() {
         let config = HotkeyConfig::default();
@@ -725,6 +1561,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn long_press_of_starting_toggle_press_requests_alternate_action() {
+        let mut state = HotkeyRuntimeState {
+            config: HotkeyConfig {
+                shortcut: DEFAULT_SHORTCUT.to_string(),
+                mode: RecordingMode::Toggle,
+            },
+            ..HotkeyRuntimeState::default()
+        };
+        state.long_press.threshold = Some(Duration::from_millis(500));
+        let start = Instant::now();
+
+        let transition = state.apply_shortcut_event(ShortcutState::Pressed);
+        assert!(!state.track_long_press(ShortcutState::Pressed, transition, start));
+        assert!(!state.track_long_press(
+            ShortcutState::Released,
+            None,
+            start + Duration::from_millis(200)
+        ));
+        assert!(!state.long_press.alternate_requested);
+
+        state.apply_external_request(RecordingTransition::Stopped);
+        let transition = state.apply_shortcut_event(ShortcutState::Pressed);
+        state.track_long_press(ShortcutState::Pressed, transition, start);
+        assert!(state.track_long_press(
+            ShortcutState::Released,
+            None,
+            start + Duration::from_millis(600)
+        ));
+        assert!(state.long_press.alternate_requested);
+
+        state.config.mode = RecordingMode::HoldToTalk;
+        state.long_press.alternate_requested = false;
+        state.track_long_press(
+            ShortcutState::Pressed,
+            Some(RecordingTransition::Started),
+            start,
+        );
+        assert!(!state.track_long_press(
+            ShortcutState::Released,
+            Some(RecordingTransition::Stopped),
+            start + Duration::from_secs(2)
+        ));
+        assert!(!state.long_press.alternate_requested);
+    }
+
+    #[test]
+    fn captured_shortcuts_reject_reserved_and_modifierless_combos() {
+        assert!(validate_captured_shortcut("Alt+Space").is_ok());
+        assert!(validate_captured_shortcut("F13").is_ok());
+        assert!(validate_captured_shortcut("Ctrl+Shift+K").is_ok());
+
+        let reserved = validate_captured_shortcut("command+space")
+            .expect_err("spotlight shortcut should be rejected");
+        assert!(reserved.contains("opens Spotlight"));
+        assert!(validate_captured_shortcut("Cmd+Shift+4").is_err());
+        assert!(validate_captured_shortcut("K")
+            .expect_err("bare keys should be rejected")
+            .contains("needs a modifier"));
+        assert!(validate_captured_shortcut("Ctrl+NotAKey").is_err());
+    }
+
+    #[test]
+    fn conflict_detection_reports_system_and_registered_combos() {
+        assert!(detect_shortcut_conflict("Alt+Space", false).is_ok());
+        assert!(detect_shortcut_conflict("Ctrl+Space", false)
+            .expect_err("input source shortcut should conflict")
+            .contains("reserved by macOS"));
+        assert!(detect_shortcut_conflict("Ctrl+Shift+K", true)
+            .expect_err("registered shortcut should conflict")
+            .contains("already registered"));
+
+        assert!(describe_registration_error(
+            "Unable to register hotkey: RegisterEventHotKey failed for KeyK"
+        )
+        .contains("already in use by another application"));
+        assert_eq!(describe_registration_error("boom"), "boom");
+    }
+
     #[test]
     fn shortcut_comparison_ignores_case_and_alias_formatting() {
         assert!(shortcuts_match("alt+space", "Alt+Space"));
@@ -738,12 +1653,12 @@ mod tests {
             state.apply_shortcut_event(ShortcutState::Pressed),
             Some(RecordingTransition::Started)
         );
-        assert!(!state.is_recording);
+        assert!(!state.is_recording());
         assert!(state.desired_recording);
 
-        state.acknowledge_transition(RecordingTransition::Started, false);
+        state.acknowledge_transition(1, RecordingTransition::Started, false);
 
-        assert!(!state.is_recording);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert!(state.pending_transitions.is_empty());
     }
@@ -753,9 +1668,9 @@ mod tests {
         let mut state = HotkeyRuntimeState::default();
 
         state.apply_shortcut_event(ShortcutState::Pressed);
-        state.acknowledge_transition(RecordingTransition::Started, true);
+        state.acknowledge_transition(1, RecordingTransition::Started, true);
 
-        assert!(state.is_recording);
+        assert!(state.is_recording());
         assert!(state.desired_recording);
         assert!(state.pending_transitions.is_empty());
     }
@@ -766,13 +1681,13 @@ mod tests {
 
         state.apply_shortcut_event(ShortcutState::Pressed);
         state.apply_shortcut_event(ShortcutState::Released);
-        state.acknowledge_transition(RecordingTransition::Started, false);
+        state.acknowledge_transition(1, RecordingTransition::Started, false);
 
-        assert!(!state.is_recording);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert_eq!(
             state.pending_transitions,
-            VecDeque::from([RecordingTransition::Stopped])
+            VecDeque::from([pending(1, RecordingTransition::Stopped)])
         );
     }
 
@@ -788,15 +1703,15 @@ mod tests {
             StopProcessingDecision::DeferUntilStarted
         );
 
-        state.acknowledge_transition(RecordingTransition::Started, true);
-        assert!(state.is_recording);
+        state.acknowledge_transition(1, RecordingTransition::Started, true);
+        assert!(state.is_recording());
         assert_eq!(
             state.stop_processing_decision(),
-            StopProcessingDecision::Process
+            StopProcessingDecision::Process(1)
         );
 
-        state.acknowledge_transition(RecordingTransition::Stopped, true);
-        assert!(!state.is_recording);
+        state.acknowledge_transition(1, RecordingTransition::Stopped, true);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert!(state.pending_transitions.is_empty());
     }
@@ -807,19 +1722,93 @@ mod tests {
 
         state.apply_shortcut_event(ShortcutState::Pressed);
         state.apply_shortcut_event(ShortcutState::Released);
-        state.acknowledge_transition(RecordingTransition::Started, false);
+        state.acknowledge_transition(1, RecordingTransition::Started, false);
 
         assert_eq!(
             state.stop_processing_decision(),
-            StopProcessingDecision::AcknowledgeOnly
+            StopProcessingDecision::AcknowledgeOnly(1)
         );
 
-        state.acknowledge_transition(RecordingTransition::Stopped, false);
-        assert!(!state.is_recording);
+        state.acknowledge_transition(1, RecordingTransition::Stopped, false);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert!(state.pending_transitions.is_empty());
     }
 
+    #[test]
+    fn acknowledgements_follow_the_session_state_machine_and_reject_stale_ones() {
+        let mut state = HotkeyRuntimeState::default();
+
+        state.apply_shortcut_event(ShortcutState::Pressed);
+        assert_eq!(state.recording_state, HotkeyRecordingState::Starting);
+        assert!(!state.acknowledge_transition(2, RecordingTransition::Started, true));
+        assert!(!state.acknowledge_transition(1, RecordingTransition::Stopped, true));
+        assert_eq!(state.rejected_acknowledgements, 2);
+
+        assert!(state.acknowledge_transition(1, RecordingTransition::Started, true));
+        assert_eq!(state.recording_state, HotkeyRecordingState::Recording);
+        state.apply_shortcut_event(ShortcutState::Released);
+        assert_eq!(state.recording_state, HotkeyRecordingState::Stopping);
+        assert!(state.is_recording());
+
+        state.reset_recording();
+        state.apply_shortcut_event(ShortcutState::Pressed);
+        assert!(!state.acknowledge_transition(1, RecordingTransition::Stopped, true));
+        assert_eq!(state.recording_state, HotkeyRecordingState::Starting);
+        assert!(state.acknowledge_transition(2, RecordingTransition::Started, true));
+
+        // Finishing from the UI stops the recording without a pending hotkey stop.
+        assert!(state.acknowledge_transition(2, RecordingTransition::Stopped, true));
+        assert_eq!(state.recording_state, HotkeyRecordingState::Idle);
+        assert_eq!(state.snapshot().rejected_acknowledgements, 3);
+        assert!(state.snapshot().last_acknowledged_at.is_some());
+    }
+
+    #[test]
+    fn external_request_only_transitions_when_desired_state_differs() {
+        let mut state = HotkeyRuntimeState::default();
+
+        assert_eq!(
+            state.apply_external_request(RecordingTransition::Stopped),
+            None
+        );
+        assert_eq!(
+            state.apply_external_request(RecordingTransition::Started),
+            Some(RecordingTransition::Started)
+        );
+        assert_eq!(
+            state.apply_external_request(RecordingTransition::Started),
+            None
+        );
+        assert!(state.desired_recording);
+        assert_eq!(
+            state.pending_transitions,
+            VecDeque::from([pending(1, RecordingTransition::Started)])
+        );
+    }
+
+    #[test]
+    fn external_toggle_flips_desired_recording_state() {
+        let mut state = HotkeyRuntimeState::default();
+
+        assert_eq!(
+            state.apply_external_toggle(),
+            Some(RecordingTransition::Started)
+        );
+        assert_eq!(
+            state.apply_external_toggle(),
+            Some(RecordingTransition::Stopped)
+        );
+        assert!(!state.desired_recording);
+        assert_eq!(
+            state.pending_transitions,
+            VecDeque::from([
+                pending(1, RecordingTransition::Started),
+                pending(1, RecordingTransition::Stopped),
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn pipeline_start_failure_rolls_back_hotkey_state_and_reports_ui_error() {
         let delegate = StartFailurePipelineDelegate::new_with_pending_start();
@@ -831,17 +1820,17 @@ mod tests {
             .hotkey_state
             .lock()
             .expect("hotkey state lock should not be poisoned");
-        assert!(!state.is_recording);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert!(state.pending_transitions.is_empty());
 
         assert_eq!(delegate.statuses(), vec![AppStatus::Error, AppStatus::Idle]);
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::RecordingStart,
-                message: "microphone unavailable".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::RecordingStart,
+                "microphone unavailable".to_string()
+            )]
         );
     }
 
@@ -850,9 +1839,11 @@ mod tests {
         let state = Arc::new(Mutex::new(HotkeyRuntimeState {
             config: HotkeyConfig::default(),
             registered_shortcut: Some(DEFAULT_SHORTCUT.to_string()),
-            is_recording: true,
+            recording_state: HotkeyRecordingState::Recording,
             desired_recording: true,
-            pending_transitions: VecDeque::from([RecordingTransition::Started]),
+            session_id: 1,
+            pending_transitions: VecDeque::from([pending(1, RecordingTransition::Started)]),
+            ..HotkeyRuntimeState::default()
         }));
         let mut unregister_attempts = Vec::new();
         let mut register_attempts = Vec::new();
@@ -889,7 +1880,7 @@ mod tests {
             .expect("hotkey state lock should not be poisoned");
         assert_eq!(state.config, HotkeyConfig::default());
         assert_eq!(state.registered_shortcut, None);
-        assert!(!state.is_recording);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert!(state.pending_transitions.is_empty());
     }
@@ -899,9 +1890,11 @@ mod tests {
         let state = Arc::new(Mutex::new(HotkeyRuntimeState {
             config: HotkeyConfig::default(),
             registered_shortcut: Some(DEFAULT_SHORTCUT.to_string()),
-            is_recording: true,
+            recording_state: HotkeyRecordingState::Recording,
             desired_recording: true,
-            pending_transitions: VecDeque::from([RecordingTransition::Started]),
+            session_id: 1,
+            pending_transitions: VecDeque::from([pending(1, RecordingTransition::Started)]),
+            ..HotkeyRuntimeState::default()
         }));
         let mut unregister_attempts = Vec::new();
         let mut register_attempts = Vec::new();
@@ -943,11 +1936,11 @@ mod tests {
             .expect("hotkey state lock should not be poisoned");
         assert_eq!(state.config, HotkeyConfig::default());
         assert_eq!(state.registered_shortcut.as_deref(), Some(DEFAULT_SHORTCUT));
-        assert!(state.is_recording);
+        assert!(state.is_recording());
         assert!(state.desired_recording);
         assert_eq!(
             state.pending_transitions,
-            VecDeque::from([RecordingTransition::Started])
+            VecDeque::from([pending(1, RecordingTransition::Started)])
         );
     }
 
@@ -956,15 +1949,17 @@ mod tests {
         let mut state = HotkeyRuntimeState {
             config: HotkeyConfig::default(),
             registered_shortcut: Some("Alt+Space".to_string()),
-            is_recording: true,
+            recording_state: HotkeyRecordingState::Recording,
             desired_recording: true,
-            pending_transitions: VecDeque::from([RecordingTransition::Started]),
+            session_id: 1,
+            pending_transitions: VecDeque::from([pending(1, RecordingTransition::Started)]),
+            ..HotkeyRuntimeState::default()
         };
 
         state.clear_registered_shortcut();
 
         assert_eq!(state.registered_shortcut, None);
-        assert!(!state.is_recording);
+        assert!(!state.is_recording());
         assert!(!state.desired_recording);
         assert!(state.pending_transitions.is_empty());
     }
@@ -0,0 +1,147 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use tracing::debug;
+
+const FRONTMOST_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontmostApplication {
+    pub bundle_id: Option<String>,
+    pub name: Option<String>,
+}
+
+impl FrontmostApplication {
+    pub fn matches(&self, entry: &str) -> bool {
+        [self.bundle_id.as_deref(), self.name.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|candidate| candidate.eq_ignore_ascii_case(entry))
+    }
+}
+
+pub fn find_suspending_app<'a>(
+    frontmost: &FrontmostApplication,
+    suspended_apps: &'a [String],
+) -> Option<&'a str> {
+    suspended_apps
+        .iter()
+        .map(String::as_str)
+        .find(|entry| frontmost.matches(entry))
+}
+
+#[derive(Debug)]
+pub struct SuspensionWatcher {
+    apps: Vec<String>,
+    stop: Arc<AtomicBool>,
+}
+
+impl SuspensionWatcher {
+    pub fn spawn(
+        apps: Vec<String>,
+        mut on_change: impl FnMut(Option<&str>) -> bool + Send + 'static,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = Arc::clone(&stop);
+        let worker_apps = apps.clone();
+        thread::spawn(move || {
+            let mut active: Option<String> = None;
+            while !worker_stop.load(Ordering::SeqCst) {
+                let matched = frontmost_application().and_then(|frontmost| {
+                    find_suspending_app(&frontmost, &worker_apps).map(str::to_string)
+                });
+                if matched != active && on_change(matched.as_deref()) {
+                    active = matched;
+                }
+                thread::sleep(FRONTMOST_POLL_INTERVAL);
+            }
+            debug!("hotkey suspension watcher stopped");
+        });
+        Self { apps, stop }
+    }
+
+    pub fn apps(&self) -> &[String] {
+        &self.apps
+    }
+
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[allow(unexpected_cfgs)]
+pub fn frontmost_application() -> Option<FrontmostApplication> {
+    use objc::{class, msg_send, rc::autoreleasepool, runtime::Object, sel, sel_impl};
+
+    unsafe fn ns_string(value: *mut Object) -> Option<String> {
+        if value.is_null() {
+            return None;
+        }
+        let utf8: *const std::os::raw::c_char = msg_send![value, UTF8String];
+        if utf8.is_null() {
+            return None;
+        }
+        Some(
+            std::ffi::CStr::from_ptr(utf8)
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    autoreleasepool(|| unsafe {
+        let workspace: *mut Object = msg_send![class!(NSWorkspace), sharedWorkspace];
+        if workspace.is_null() {
+            return None;
+        }
+        let application: *mut Object = msg_send![workspace, frontmostApplication];
+        if application.is_null() {
+            return None;
+        }
+        let bundle_id: *mut Object = msg_send![application, bundleIdentifier];
+        let name: *mut Object = msg_send![application, localizedName];
+        Some(FrontmostApplication {
+            bundle_id: ns_string(bundle_id),
+            name: ns_string(name),
+        })
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn frontmost_application() -> Option<FrontmostApplication> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_suspending_app, FrontmostApplication};
+
+    #[test]
+    fn matches_bundle_identifier_or_name_case_insensitively() {
+        let frontmost = FrontmostApplication {
+            bundle_id: Some("com.parallels.desktop.console".to_string()),
+            name: Some("Parallels Desktop".to_string()),
+        };
+        let by_bundle = vec!["COM.PARALLELS.DESKTOP.CONSOLE".to_string()];
+        let by_name = vec!["Steam".to_string(), "parallels desktop".to_string()];
+
+        assert_eq!(
+            find_suspending_app(&frontmost, &by_bundle),
+            Some("COM.PARALLELS.DESKTOP.CONSOLE")
+        );
+        assert_eq!(
+            find_suspending_app(&frontmost, &by_name),
+            Some("parallels desktop")
+        );
+        assert_eq!(
+            find_suspending_app(&frontmost, &["Steam".to_string()]),
+            None
+        );
+    }
+}
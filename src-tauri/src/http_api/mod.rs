@@ -0,0 +1,777 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use reqwest::Url;
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Listener, Manager, State};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, watch},
+};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    get_status_from_state,
+    history_store::HistoryStore,
+    hotkey_service::{HotkeyService, RecordingTransition},
+    AppState, EVENT_PIPELINE_ERROR, EVENT_STATUS_CHANGED, EVENT_TRANSCRIPT_READY,
+};
+
+const HTTP_API_BIND_HOST: &str = "127.0.0.1";
+const TOKEN_FILE_NAME: &str = "http_api_token";
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+const SSE_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+const EVENT_NAME_PREFIX: &str = "voice://";
+const FORWARDED_EVENTS: [&str; 3] = [
+    EVENT_STATUS_CHANGED,
+    EVENT_TRANSCRIPT_READY,
+    EVENT_PIPELINE_ERROR,
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiInfo {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HttpApiEvent {
+    name: String,
+    data: String,
+}
+
+#[derive(Debug)]
+struct RunningServer {
+    port: u16,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HttpRequestHead {
+    method: String,
+    target: String,
+    authorization: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HttpApiRoute {
+    Status,
+    StartDictation,
+    StopDictation,
+    LastTranscript,
+    History { limit: usize, offset: usize },
+    Events,
+    MethodNotAllowed,
+    NotFound,
+}
+
+#[derive(Debug)]
+pub struct HttpApiService {
+    token_path: PathBuf,
+    token: Mutex<Option<String>>,
+    server: Mutex<Option<RunningServer>>,
+    events: broadcast::Sender<HttpApiEvent>,
+}
+
+impl HttpApiService {
+    pub fn new(app_data_dir: PathBuf) -> Self {
+        let token_path = app_data_dir.join(TOKEN_FILE_NAME);
+        debug!(path = %token_path.display(), "http api service initialized");
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            token_path,
+            token: Mutex::new(None),
+            server: Mutex::new(None),
+            events,
+        }
+    }
+
+    pub fn info(&self) -> Result<HttpApiInfo, String> {
+        let port = self
+            .server
+            .lock()
+            .map_err(|_| server_lock_error())?
+            .as_ref()
+            .map(|server| server.port);
+
+        Ok(HttpApiInfo {
+            running: port.is_some(),
+            port,
+            token: self.token()?,
+        })
+    }
+
+    pub fn token(&self) -> Result<String, String> {
+        let mut guard = self.token.lock().map_err(|_| token_lock_error())?;
+        if let Some(token) = guard.as_ref() {
+            return Ok(token.clone());
+        }
+
+        let token = load_or_create_token(&self.token_path)?;
+        *guard = Some(token.clone());
+        Ok(token)
+    }
+
+    pub fn rotate_token(&self) -> Result<String, String> {
+        let mut guard = self.token.lock().map_err(|_| token_lock_error())?;
+        let token = generate_token();
+        write_token_file(&self.token_path, &token)?;
+        *guard = Some(token.clone());
+        info!("rotated http api token");
+        Ok(token)
+    }
+
+    pub fn sync_with_settings(
+        &self,
+        app: &AppHandle,
+        enabled: bool,
+        port: u16,
+    ) -> Result<(), String> {
+        let mut server = self.server.lock().map_err(|_| server_lock_error())?;
+        match (server.as_ref(), enabled) {
+            (Some(running), true) if running.port == port => return Ok(()),
+            (None, false) => return Ok(()),
+            _ => {}
+        }
+
+        if let Some(running) = server.take() {
+            let _ = running.shutdown_tx.send(true);
+            info!(port = running.port, "http api server stopped");
+        }
+
+        if !enabled {
+            return Ok(());
+        }
+
+        self.token()?;
+        let listener =
+            std::net::TcpListener::bind((HTTP_API_BIND_HOST, port)).map_err(|error| {
+                format!("Failed to bind HTTP API server on {HTTP_API_BIND_HOST}:{port}: {error}")
+            })?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|error| format!("Failed to configure HTTP API listener: {error}"))?;
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        tauri::async_runtime::spawn(run_server(app.clone(), listener, shutdown_rx));
+        *server = Some(RunningServer { port, shutdown_tx });
+        info!(host = HTTP_API_BIND_HOST, port, "http api server started");
+        Ok(())
+    }
+
+    fn publish_event(&self, name: &str, data: &str) {
+        let event = HttpApiEvent {
+            name: name.trim_start_matches(EVENT_NAME_PREFIX).to_string(),
+            data: data.to_string(),
+        };
+        let _ = self.events.send(event);
+    }
+}
+
+pub fn register_event_forwarders(app: &AppHandle) {
+    for event_name in FORWARDED_EVENTS {
+        let forward_app = app.clone();
+        app.listen(event_name, move |event| {
+            let service = forward_app.state::<HttpApiService>();
+            service.publish_event(event_name, event.payload());
+        });
+    }
+}
+
+#[tauri::command]
+pub fn get_http_api_info(service: State<'_, HttpApiService>) -> Result<HttpApiInfo, String> {
+    service.info()
+}
+
+#[tauri::command]
+pub fn rotate_http_api_token(service: State<'_, HttpApiService>) -> Result<HttpApiInfo, String> {
+    service.rotate_token()?;
+    service.info()
+}
+
+async fn run_server(
+    app: AppHandle,
+    listener: std::net::TcpListener,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let listener = match TcpListener::from_std(listener) {
+        Ok(listener) => listener,
+        Err(error) => {
+            error!(%error, "failed to start http api listener");
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    debug!(%peer, "http api connection accepted");
+                    tauri::async_runtime::spawn(handle_connection(
+                        app.clone(),
+                        stream,
+                        shutdown_rx.clone(),
+                    ));
+                }
+                Err(error) => warn!(%error, "failed to accept http api connection"),
+            },
+            _ = shutdown_rx.changed() => break,
+        }
+    }
+
+    debug!("http api accept loop exited");
+}
+
+async fn handle_connection(
+    app: AppHandle,
+    mut stream: TcpStream,
+    shutdown_rx: watch::Receiver<bool>,
+) {
+    let head = match read_request_head(&mut stream, REQUEST_HEAD_TIMEOUT)
+        .await
+        .and_then(|raw| parse_request_head(&raw))
+    {
+        Ok(head) => head,
+        Err(error) => {
+            debug!(%error, "rejecting malformed http api request");
+            let _ = respond_json(&mut stream, "400 Bad Request", &json!({ "error": error })).await;
+            return;
+        }
+    };
+
+    let (token, events) = {
+        let service = app.state::<HttpApiService>();
+        (service.token(), service.events.subscribe())
+    };
+    let authorized = match token {
+        Ok(token) => is_authorized(head.authorization.as_deref(), &token),
+        Err(error) => {
+            error!(%error, "failed to load http api token");
+            false
+        }
+    };
+    if !authorized {
+        warn!(method = %head.method, target = %head.target, "unauthorized http api request");
+        let _ = respond_json(
+            &mut stream,
+            "401 Unauthorized",
+            &json!({ "error": "Missing or invalid bearer token" }),
+        )
+        .await;
+        return;
+    }
+
+    let route = resolve_route(&head.method, &head.target);
+    debug!(?route, "handling http api request");
+    let result = match route {
+        HttpApiRoute::Status => {
+            let status = get_status_from_state(&app.state::<AppState>());
+            respond_json(&mut stream, "200 OK", &json!({ "status": status })).await
+        }
+        HttpApiRoute::StartDictation | HttpApiRoute::StopDictation => {
+            let transition = if route == HttpApiRoute::StartDictation {
+                RecordingTransition::Started
+            } else {
+                RecordingTransition::Stopped
+            };
+            let accepted = app
+                .state::<HotkeyService>()
+                .request_recording_transition(&app, transition);
+            respond_json(
+                &mut stream,
+                "202 Accepted",
+                &json!({ "accepted": accepted }),
+            )
+            .await
+        }
+        HttpApiRoute::LastTranscript => match app.state::<HistoryStore>().list_entries(1, 0) {
+            Ok(entries) => match entries.into_iter().next() {
+                Some(entry) => respond_json(&mut stream, "200 OK", &entry).await,
+                None => {
+                    respond_json(
+                        &mut stream,
+                        "404 Not Found",
+                        &json!({ "error": "No transcripts yet" }),
+                    )
+                    .await
+                }
+            },
+            Err(error) => {
+                respond_json(
+                    &mut stream,
+                    "500 Internal Server Error",
+                    &json!({ "error": error }),
+                )
+                .await
+            }
+        },
+        HttpApiRoute::History { limit, offset } => {
+            match app.state::<HistoryStore>().list_entries(limit, offset) {
+                Ok(entries) => respond_json(&mut stream, "200 OK", &entries).await,
+                Err(error) => {
+                    respond_json(
+                        &mut stream,
+                        "500 Internal Server Error",
+                        &json!({ "error": error }),
+                    )
+                    .await
+                }
+            }
+        }
+        HttpApiRoute::Events => stream_events(&mut stream, events, shutdown_rx).await,
+        HttpApiRoute::MethodNotAllowed => {
+            respond_json(
+                &mut stream,
+                "405 Method Not Allowed",
+                &json!({ "error": "Method not allowed" }),
+            )
+            .await
+        }
+        HttpApiRoute::NotFound => {
+            respond_json(
+                &mut stream,
+                "404 Not Found",
+                &json!({ "error": "Not found" }),
+            )
+            .await
+        }
+    };
+
+    if let Err(error) = result {
+        debug!(%error, "failed to write http api response");
+    }
+}
+
+async fn stream_events(
+    stream: &mut TcpStream,
+    mut events: broadcast::Receiver<HttpApiEvent>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), String> {
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await
+        .map_err(|error| format!("Failed to write SSE headers: {error}"))?;
+    info!("http api event stream opened");
+
+    let mut keepalive = tokio::time::interval(SSE_KEEPALIVE_INTERVAL);
+    loop {
+        let chunk = tokio::select! {
+            received = events.recv() => match received {
+                Ok(event) => format_sse_event(&event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "http api event stream lagged; dropping events");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+            _ = shutdown_rx.changed() => break,
+        };
+
+        if stream.write_all(chunk.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+
+    info!("http api event stream closed");
+    let _ = stream.shutdown().await;
+    Ok(())
+}
+
+async fn read_request_head<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    timeout: Duration,
+) -> Result<String, String> {
+    tokio::time::timeout(timeout, read_request_head_bytes(stream))
+        .await
+        .map_err(|_| "Timed out reading HTTP API request".to_string())?
+}
+
+async fn read_request_head_bytes<S: AsyncRead + Unpin>(stream: &mut S) -> Result<String, String> {
+    let mut buffer = Vec::<u8>::with_capacity(1024);
+    let mut chunk = [0_u8; 1024];
+
+    loop {
+        if buffer.len() >= MAX_REQUEST_HEAD_BYTES {
+            return Err("HTTP API request head is too large".to_string());
+        }
+        let read_limit = chunk.len().min(MAX_REQUEST_HEAD_BYTES - buffer.len());
+        let bytes_read = stream
+            .read(&mut chunk[..read_limit])
+            .await
+            .map_err(|error| format!("Failed to read HTTP API request: {error}"))?;
+        if bytes_read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..bytes_read]);
+
+        if buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            break;
+        }
+    }
+
+    String::from_utf8(buffer)
+        .map_err(|error| format!("HTTP API request was not valid UTF-8: {error}"))
+}
+
+fn parse_request_head(raw: &str) -> Result<HttpRequestHead, String> {
+    let mut lines = raw.lines();
+    let request_line = lines
+        .next()
+        .ok_or_else(|| "Missing HTTP request line".to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts
+        .next()
+        .ok_or_else(|| "Missing HTTP method".to_string())?;
+    let target = parts
+        .next()
+        .ok_or_else(|| "Missing HTTP request target".to_string())?;
+
+    let authorization = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .map(|(_, value)| value.trim().to_string());
+
+    Ok(HttpRequestHead {
+        method: method.to_ascii_uppercase(),
+        target: target.to_string(),
+        authorization,
+    })
+}
+
+fn resolve_route(method: &str, target: &str) -> HttpApiRoute {
+    let Ok(url) = Url::parse(&format!("http://localhost{target}")) else {
+        return HttpApiRoute::NotFound;
+    };
+
+    let expected_method = match url.path() {
+        "/v1/status" | "/v1/transcripts/last" | "/v1/history" | "/v1/events" => "GET",
+        "/v1/dictation/start" | "/v1/dictation/stop" => "POST",
+        _ => return HttpApiRoute::NotFound,
+    };
+    if method != expected_method {
+        return HttpApiRoute::MethodNotAllowed;
+    }
+
+    match url.path() {
+        "/v1/status" => HttpApiRoute::Status,
+        "/v1/transcripts/last" => HttpApiRoute::LastTranscript,
+        "/v1/events" => HttpApiRoute::Events,
+        "/v1/dictation/start" => HttpApiRoute::StartDictation,
+        "/v1/dictation/stop" => HttpApiRoute::StopDictation,
+        _ => {
+            let query_usize = |name: &str| {
+                url.query_pairs()
+                    .find_map(|(key, value)| (key == name).then(|| value.parse::<usize>().ok()))
+                    .flatten()
+            };
+            HttpApiRoute::History {
+                limit: query_usize("limit").unwrap_or(DEFAULT_HISTORY_LIMIT),
+                offset: query_usize("offset").unwrap_or(0),
+            }
+        }
+    }
+}
+
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+
+    constant_time_eq(provided.trim().as_bytes(), token.as_bytes())
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    left.iter()
+        .zip(right)
+        .fold(0_u8, |diff, (left, right)| diff | (left ^ right))
+        == 0
+}
+
+fn format_sse_event(event: &HttpApiEvent) -> String {
+    let mut chunk = format!("event: {}\n", event.name);
+    for line in event.data.lines() {
+        chunk.push_str("data: ");
+        chunk.push_str(line);
+        chunk.push('\n');
+    }
+    chunk.push('\n');
+    chunk
+}
+
+async fn respond_json<T: Serialize + ?Sized>(
+    stream: &mut TcpStream,
+    status: &str,
+    body: &T,
+) -> Result<(), String> {
+    let body = serde_json::to_string(body)
+        .map_err(|error| format!("Failed to serialize HTTP API response: {error}"))?;
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    stream
+        .write_all(response.as_bytes())
+        .await
+        .map_err(|error| format!("Failed to write HTTP API response: {error}"))?;
+
+    let _ = stream.shutdown().await;
+    Ok(())
+}
+
+fn load_or_create_token(token_path: &Path) -> Result<String, String> {
+    match fs::read_to_string(token_path) {
+        Ok(contents) if is_valid_token(contents.trim()) => return Ok(contents.trim().to_string()),
+        Ok(_) => warn!(path = %token_path.display(), "http api token file is empty or invalid"),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+            warn!(path = %token_path.display(), "http api token file is not valid UTF-8");
+        }
+        Err(error) => {
+            return Err(format!(
+                "Failed to read HTTP API token file `{}`: {error}",
+                token_path.display()
+            ))
+        }
+    }
+
+    let token = generate_token();
+    write_token_file(token_path, &token)?;
+    info!(path = %token_path.display(), "generated http api token");
+    Ok(token)
+}
+
+fn is_valid_token(token: &str) -> bool {
+    token.len() == 64 && token.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+fn write_token_file(token_path: &Path, token: &str) -> Result<(), String> {
+    if let Some(parent_dir) = token_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(|error| {
+            format!(
+                "Failed to create HTTP API token directory `{}`: {error}",
+                parent_dir.display()
+            )
+        })?;
+    }
+
+    fs::write(token_path, token).map_err(|error| {
+        format!(
+            "Failed to write HTTP API token file `{}`: {error}",
+            token_path.display()
+        )
+    })?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if let Err(error) = fs::set_permissions(token_path, fs::Permissions::from_mode(0o600)) {
+            warn!(%error, "failed to restrict http api token file permissions");
+        }
+    }
+
+    Ok(())
+}
+
+fn token_lock_error() -> String {
+    "HTTP API token lock is poisoned".to_string()
+}
+
+fn server_lock_error() -> String {
+    "HTTP API server lock is poisoned".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use tokio::io::AsyncWriteExt;
+
+    use super::{
+        format_sse_event, generate_token, is_authorized, load_or_create_token, parse_request_head,
+        read_request_head, resolve_route, HttpApiEvent, HttpApiRoute, DEFAULT_HISTORY_LIMIT,
+        MAX_REQUEST_HEAD_BYTES, TOKEN_FILE_NAME,
+    };
+
+    fn unique_token_path(prefix: &str) -> PathBuf {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time should move forward")
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("voice-http-api-{prefix}-{timestamp}"))
+            .join(TOKEN_FILE_NAME)
+    }
+
+    #[test]
+    fn request_head_parses_method_target_and_authorization() {
+        let head = parse_request_head(
+            "get /v1/status HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc\r\n\r\n",
+        )
+        .expect("request head should parse");
+
+        assert_eq!(head.method, "GET");
+        assert_eq!(head.target, "/v1/status");
+        assert_eq!(head.authorization.as_deref(), Some("Bearer abc"));
+    }
+
+    #[test]
+    fn authorization_requires_matching_bearer_token() {
+        assert!(is_authorized(Some("Bearer secret"), "secret"));
+        assert!(!is_authorized(Some("Bearer other"), "secret"));
+        assert!(!is_authorized(Some("secret"), "secret"));
+        assert!(!is_authorized(None, "secret"));
+    }
+
+    #[test]
+    fn routes_enforce_methods_and_parse_history_paging() {
+        assert_eq!(resolve_route("GET", "/v1/status"), HttpApiRoute::Status);
+        assert_eq!(
+            resolve_route("POST", "/v1/dictation/start"),
+            HttpApiRoute::StartDictation
+        );
+        assert_eq!(
+            resolve_route("GET", "/v1/dictation/stop"),
+            HttpApiRoute::MethodNotAllowed
+        );
+        assert_eq!(
+            resolve_route("GET", "/v1/history?limit=5&offset=10"),
+            HttpApiRoute::History {
+                limit: 5,
+                offset: 10
+            }
+        );
+        assert_eq!(
+            resolve_route("GET", "/v1/history?limit=abc"),
+            HttpApiRoute::History {
+                limit: DEFAULT_HISTORY_LIMIT,
+                offset: 0
+            }
+        );
+        assert_eq!(resolve_route("GET", "/unknown"), HttpApiRoute::NotFound);
+    }
+
+    #[test]
+    fn sse_events_prefix_every_data_line() {
+        let chunk = format_sse_event(&HttpApiEvent {
+            name: "transcript-ready".to_string(),
+            data: "{\"text\":\"a\"}\nsecond".to_string(),
+        });
+
+        assert_eq!(
+            chunk,
+            "event: transcript-ready\ndata: {\"text\":\"a\"}\ndata: second\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn request_head_reads_until_the_blank_line() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        client
+            .write_all(b"GET /v1/status HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .expect("request should be written");
+
+        let head = read_request_head(&mut server, Duration::from_secs(5))
+            .await
+            .expect("request head should be read");
+
+        assert!(head.starts_with("GET /v1/status"));
+    }
+
+    #[tokio::test]
+    async fn request_head_times_out_when_the_client_stalls() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        client
+            .write_all(b"GET /v1/status HTTP/1.1\r\n")
+            .await
+            .expect("partial request should be written");
+
+        let error = read_request_head(&mut server, Duration::from_millis(20))
+            .await
+            .expect_err("stalled request should time out");
+
+        assert!(error.contains("Timed out"));
+        drop(client);
+    }
+
+    #[tokio::test]
+    async fn request_head_rejects_oversized_headers() {
+        let (mut client, mut server) = tokio::io::duplex(MAX_REQUEST_HEAD_BYTES * 2);
+        client
+            .write_all(&vec![b'a'; MAX_REQUEST_HEAD_BYTES + 1])
+            .await
+            .expect("oversized request should be written");
+
+        let error = read_request_head(&mut server, Duration::from_secs(5))
+            .await
+            .expect_err("oversized request should be rejected");
+
+        assert!(error.contains("too large"));
+    }
+
+    #[test]
+    fn token_is_regenerated_only_when_missing_empty_or_invalid() {
+        let token_path = unique_token_path("token");
+        let created = load_or_create_token(&token_path).expect("missing token should be created");
+        assert_eq!(
+            fs::read_to_string(&token_path).expect("token file should exist"),
+            created
+        );
+        assert_eq!(
+            load_or_create_token(&token_path).expect("valid token should load"),
+            created
+        );
+
+        for contents in ["", "  \n", "not-a-token"] {
+            fs::write(&token_path, contents).expect("token file should be writable");
+            let regenerated =
+                load_or_create_token(&token_path).expect("bad token should be replaced");
+            assert_ne!(regenerated, created);
+            assert_eq!(regenerated.len(), generate_token().len());
+        }
+
+        let _ = fs::remove_dir_all(token_path.parent().expect("token path has a parent"));
+    }
+
+    #[test]
+    fn token_read_errors_are_returned_without_overwriting() {
+        let token_path = unique_token_path("token-dir");
+        fs::create_dir_all(&token_path).expect("directory should be created");
+
+        let error = load_or_create_token(&token_path).expect_err("unreadable token should fail");
+
+        assert!(error.contains("Failed to read HTTP API token file"));
+        assert!(token_path.is_dir());
+        let _ = fs::remove_dir_all(token_path.parent().expect("token path has a parent"));
+    }
+}
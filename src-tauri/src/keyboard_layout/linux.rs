@@ -0,0 +1,61 @@
+use std::process::Command;
+
+use super::KeyboardLayout;
+
+// Wayland compositors do not expose the active group, so `localectl` is the best guess there.
+pub(super) fn current_layout() -> Option<KeyboardLayout> {
+    let id = command_output("setxkbmap", &["-query"])
+        .and_then(|output| parse_xkb_layout(&output, "layout:", "variant:"))
+        .or_else(|| {
+            command_output("localectl", &["status"])
+                .and_then(|output| parse_xkb_layout(&output, "X11 Layout:", "X11 Variant:"))
+        })?;
+    Some(KeyboardLayout { id, language: None })
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn parse_xkb_layout(output: &str, layout_key: &str, variant_key: &str) -> Option<String> {
+    let first_value = |key: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(key))
+            .and_then(|values| values.split(',').next())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let layout = first_value(layout_key)?;
+    Some(match first_value(variant_key) {
+        Some(variant) => format!("{layout}({variant})"),
+        None => layout.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_xkb_layout;
+
+    #[test]
+    fn reads_the_first_layout_and_variant() {
+        let setxkbmap =
+            "rules:      evdev\nmodel:      pc105\nlayout:     de,us\nvariant:    nodeadkeys,\n";
+        assert_eq!(
+            parse_xkb_layout(setxkbmap, "layout:", "variant:").as_deref(),
+            Some("de(nodeadkeys)")
+        );
+
+        let localectl =
+            "   System Locale: LANG=en_US.UTF-8\n       VC Keymap: us\n      X11 Layout: us\n";
+        assert_eq!(
+            parse_xkb_layout(localectl, "X11 Layout:", "X11 Variant:").as_deref(),
+            Some("us")
+        );
+        assert_eq!(parse_xkb_layout("", "layout:", "variant:"), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    ffi::{c_void, CStr},
+    sync::mpsc,
+    time::Duration,
+};
+
+use core_foundation_sys::{
+    array::{CFArrayGetCount, CFArrayGetValueAtIndex, CFArrayRef},
+    base::CFRelease,
+    data::{CFDataGetBytePtr, CFDataRef},
+    string::{kCFStringEncodingUTF8, CFStringGetCString, CFStringRef},
+};
+use tauri::AppHandle;
+use tracing::warn;
+
+use super::{language_from_locale, KeyStroke, KeyboardLayout};
+
+const MAIN_THREAD_TIMEOUT: Duration = Duration::from_millis(250);
+const KEY_CODE_COUNT: u16 = 128;
+const K_UC_KEY_ACTION_DISPLAY: u16 = 3;
+const K_UC_KEY_TRANSLATE_NO_DEAD_KEYS_MASK: u32 = 1;
+const SHIFT_KEY_STATE: u32 = 0x02;
+const OPTION_KEY_STATE: u32 = 0x08;
+
+type TISInputSourceRef = *const c_void;
+
+#[link(name = "Carbon", kind = "framework")]
+unsafe extern "C" {
+    fn TISCopyCurrentKeyboardInputSource() -> TISInputSourceRef;
+    fn TISCopyCurrentKeyboardLayoutInputSource() -> TISInputSourceRef;
+    fn TISGetInputSourceProperty(source: TISInputSourceRef, key: CFStringRef) -> *const c_void;
+    fn LMGetKbdType() -> u8;
+    fn UCKeyTranslate(
+        key_layout: *const c_void,
+        virtual_key_code: u16,
+        key_action: u16,
+        modifier_key_state: u32,
+        keyboard_type: u32,
+        key_translate_options: u32,
+        dead_key_state: *mut u32,
+        max_string_length: usize,
+        actual_string_length: *mut usize,
+        unicode_string: *mut u16,
+    ) -> i32;
+
+    static kTISPropertyInputSourceLanguages: CFStringRef;
+    static kTISPropertyInputSourceID: CFStringRef;
+    static kTISPropertyUnicodeKeyLayoutData: CFStringRef;
+}
+
+pub(super) fn input_source_locale(app: &AppHandle) -> Option<String> {
+    on_main_thread(app, current_input_source_language).flatten()
+}
+
+// The layout source rather than the input source, so input methods such as Pinyin report the
+// layout their keys type with.
+pub(super) fn current_layout(app: &AppHandle) -> Option<KeyboardLayout> {
+    on_main_thread(app, || unsafe {
+        let source = TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            return None;
+        }
+        let id =
+            cf_string(TISGetInputSourceProperty(source, kTISPropertyInputSourceID) as CFStringRef);
+        CFRelease(source);
+        id
+    })
+    .flatten()
+    .map(|id| KeyboardLayout {
+        id,
+        language: input_source_locale(app).and_then(|locale| language_from_locale(&locale)),
+    })
+}
+
+pub(super) fn key_strokes(app: &AppHandle, characters: &[char]) -> Vec<Option<KeyStroke>> {
+    let owned = characters.to_vec();
+    on_main_thread(app, move || layout_key_strokes(&owned))
+        .unwrap_or_else(|| vec![None; characters.len()])
+}
+
+// Text Input Source calls must run on the main thread on recent macOS versions.
+fn on_main_thread<T: Send + 'static>(
+    app: &AppHandle,
+    read: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let (sender, receiver) = mpsc::channel();
+    if let Err(error) = app.run_on_main_thread(move || {
+        let _ = sender.send(read());
+    }) {
+        warn!(%error, "failed to read keyboard input source on the main thread");
+        return None;
+    }
+    receiver.recv_timeout(MAIN_THREAD_TIMEOUT).ok()
+}
+
+fn layout_key_strokes(characters: &[char]) -> Vec<Option<KeyStroke>> {
+    let mut strokes = vec![None; characters.len()];
+    unsafe {
+        let source = TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            return strokes;
+        }
+        let layout_data =
+            TISGetInputSourceProperty(source, kTISPropertyUnicodeKeyLayoutData) as CFDataRef;
+        if layout_data.is_null() {
+            CFRelease(source);
+            return strokes;
+        }
+        let key_layout = CFDataGetBytePtr(layout_data) as *const c_void;
+        let keyboard_type = u32::from(LMGetKbdType());
+
+        for (shift, alt) in [(false, false), (true, false), (false, true), (true, true)] {
+            let modifiers =
+                if shift { SHIFT_KEY_STATE } else { 0 } | if alt { OPTION_KEY_STATE } else { 0 };
+            for key_code in 0..KEY_CODE_COUNT {
+                let mut dead_key_state = 0_u32;
+                let mut length = 0_usize;
+                let mut output = [0_u16; 4];
+                let status = UCKeyTranslate(
+                    key_layout,
+                    key_code,
+                    K_UC_KEY_ACTION_DISPLAY,
+                    modifiers,
+                    keyboard_type,
+                    K_UC_KEY_TRANSLATE_NO_DEAD_KEYS_MASK,
+                    &mut dead_key_state,
+                    output.len(),
+                    &mut length,
+                    output.as_mut_ptr(),
+                );
+                if status != 0 || length == 0 {
+                    continue;
+                }
+                let mut typed = char::decode_utf16(output[..length].iter().copied());
+                let (Some(Ok(character)), None) = (typed.next(), typed.next()) else {
+                    continue;
+                };
+                for (index, wanted) in characters.iter().enumerate() {
+                    if *wanted == character && strokes[index].is_none() {
+                        strokes[index] = Some(KeyStroke {
+                            key_code,
+                            shift,
+                            alt,
+                        });
+                    }
+                }
+            }
+        }
+        CFRelease(source);
+    }
+    strokes
+}
+
+fn current_input_source_language() -> Option<String> {
+    unsafe {
+        let source = TISCopyCurrentKeyboardInputSource();
+        if source.is_null() {
+            return None;
+        }
+        let languages =
+            TISGetInputSourceProperty(source, kTISPropertyInputSourceLanguages) as CFArrayRef;
+        let language = if languages.is_null() || CFArrayGetCount(languages) == 0 {
+            None
+        } else {
+            cf_string(CFArrayGetValueAtIndex(languages, 0) as CFStringRef)
+        };
+        CFRelease(source);
+        language
+    }
+}
+
+unsafe fn cf_string(value: CFStringRef) -> Option<String> {
+    if value.is_null() {
+        return None;
+    }
+    let mut buffer = [0i8; 64];
+    let copied = CFStringGetCString(
+        value,
+        buffer.as_mut_ptr(),
+        buffer.len() as isize,
+        kCFStringEncodingUTF8,
+    );
+    (copied != 0).then(|| {
+        CStr::from_ptr(buffer.as_ptr())
+            .to_string_lossy()
+            .into_owned()
+    })
+}
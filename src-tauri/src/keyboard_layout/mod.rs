@@ -0,0 +1,183 @@
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tracing::debug;
+
+pub const DEFAULT_LAYOUT_SAMPLE: &str = "qwertyzamvQWZY@#/;äöüé€";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardLayout {
+    pub id: String,
+    pub language: Option<String>,
+}
+
+impl KeyboardLayout {
+    pub fn has_us_key_positions(&self) -> bool {
+        let id = self.id.as_str();
+        matches!(id, "us" | "us(intl)" | "us(altgr-intl)")
+            || id.ends_with(".US")
+            || id.ends_with(".ABC")
+            || id.eq_ignore_ascii_case("04090409")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyStroke {
+    pub key_code: u16,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterKey {
+    pub character: char,
+    pub key: Option<KeyStroke>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardLayoutReport {
+    pub layout: Option<KeyboardLayout>,
+    pub keys: Vec<CharacterKey>,
+}
+
+pub fn current_layout(app: &AppHandle) -> Option<KeyboardLayout> {
+    let layout = platform_layout(app);
+    debug!(?layout, "keyboard layout detected");
+    layout
+}
+
+pub fn key_strokes(app: &AppHandle, characters: &[char]) -> Vec<Option<KeyStroke>> {
+    platform_key_strokes(app, characters)
+}
+
+pub fn layout_report(app: &AppHandle, sample: &str) -> KeyboardLayoutReport {
+    let characters = distinct_characters(sample);
+    let keys = key_strokes(app, &characters);
+    KeyboardLayoutReport {
+        layout: current_layout(app),
+        keys: characters
+            .into_iter()
+            .zip(keys)
+            .map(|(character, key)| CharacterKey { character, key })
+            .collect(),
+    }
+}
+
+fn distinct_characters(sample: &str) -> Vec<char> {
+    let mut characters: Vec<char> = Vec::new();
+    for character in sample
+        .chars()
+        .filter(|character| !character.is_whitespace())
+    {
+        if !characters.contains(&character) {
+            characters.push(character);
+        }
+    }
+    characters
+}
+
+pub fn current_input_language(app: &AppHandle) -> Option<String> {
+    let locale = input_source_locale(app)?;
+    let language = language_from_locale(&locale);
+    debug!(%locale, ?language, "keyboard input source language detected");
+    language
+}
+
+#[cfg(target_os = "macos")]
+fn input_source_locale(app: &AppHandle) -> Option<String> {
+    macos::input_source_locale(app)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_layout(app: &AppHandle) -> Option<KeyboardLayout> {
+    macos::current_layout(app)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_key_strokes(app: &AppHandle, characters: &[char]) -> Vec<Option<KeyStroke>> {
+    macos::key_strokes(app, characters)
+}
+
+#[cfg(target_os = "windows")]
+fn input_source_locale(_app: &AppHandle) -> Option<String> {
+    windows::input_source_locale()
+}
+
+#[cfg(target_os = "windows")]
+fn platform_layout(_app: &AppHandle) -> Option<KeyboardLayout> {
+    windows::current_layout()
+}
+
+#[cfg(target_os = "windows")]
+fn platform_key_strokes(_app: &AppHandle, characters: &[char]) -> Vec<Option<KeyStroke>> {
+    windows::key_strokes(characters)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn input_source_locale(_app: &AppHandle) -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn platform_layout(_app: &AppHandle) -> Option<KeyboardLayout> {
+    linux::current_layout()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn platform_layout(_app: &AppHandle) -> Option<KeyboardLayout> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_key_strokes(_app: &AppHandle, characters: &[char]) -> Vec<Option<KeyStroke>> {
+    vec![None; characters.len()]
+}
+
+fn language_from_locale(locale: &str) -> Option<String> {
+    let language = locale.split(['-', '_']).next()?.trim();
+    (language.len() == 2 && language.chars().all(|ch| ch.is_ascii_alphabetic()))
+        .then(|| language.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distinct_characters, language_from_locale, KeyboardLayout};
+
+    #[test]
+    fn takes_the_two_letter_language_from_locale_tags() {
+        assert_eq!(language_from_locale("de-DE").as_deref(), Some("de"));
+        assert_eq!(language_from_locale("zh-Hans").as_deref(), Some("zh"));
+        assert_eq!(language_from_locale("pt_BR").as_deref(), Some("pt"));
+        assert_eq!(language_from_locale("FR").as_deref(), Some("fr"));
+        assert_eq!(language_from_locale("haw-US"), None);
+        assert_eq!(language_from_locale(""), None);
+    }
+
+    #[test]
+    fn recognises_us_layouts_on_each_platform() {
+        let layout = |id: &str| KeyboardLayout {
+            id: id.to_string(),
+            language: None,
+        };
+
+        assert!(layout("com.apple.keylayout.US").has_us_key_positions());
+        assert!(layout("com.apple.keylayout.ABC").has_us_key_positions());
+        assert!(layout("04090409").has_us_key_positions());
+        assert!(layout("us(intl)").has_us_key_positions());
+        assert!(!layout("us(dvorak)").has_us_key_positions());
+        assert!(!layout("com.apple.keylayout.Dvorak").has_us_key_positions());
+        assert!(!layout("04070407").has_us_key_positions());
+        assert!(!layout("de").has_us_key_positions());
+        assert_eq!(distinct_characters("a b\tab"), vec!['a', 'b']);
+    }
+}
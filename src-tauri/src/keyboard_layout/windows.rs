@@ -0,0 +1,86 @@
+use std::ffi::c_void;
+
+use super::{language_from_locale, KeyStroke, KeyboardLayout};
+
+type Hwnd = *mut c_void;
+type Hkl = *mut c_void;
+
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+const LANGUAGE_ID_MASK: usize = 0xFFFF;
+const LAYOUT_HANDLE_MASK: usize = 0xFFFF_FFFF;
+const SHIFT_STATE_SHIFT: i16 = 0x01;
+const SHIFT_STATE_ALT_GR: i16 = 0x06;
+
+#[link(name = "user32")]
+unsafe extern "system" {
+    fn GetForegroundWindow() -> Hwnd;
+    fn GetWindowThreadProcessId(hwnd: Hwnd, process_id: *mut u32) -> u32;
+    fn GetKeyboardLayout(thread_id: u32) -> Hkl;
+    fn VkKeyScanExW(character: u16, layout: Hkl) -> i16;
+}
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn LCIDToLocaleName(locale: u32, name: *mut u16, name_len: i32, flags: u32) -> i32;
+}
+
+// Layouts are per thread on Windows, so Buzz's own layout would not reflect the target app.
+fn foreground_layout() -> Option<Hkl> {
+    unsafe {
+        let window = GetForegroundWindow();
+        let thread_id = if window.is_null() {
+            0
+        } else {
+            GetWindowThreadProcessId(window, std::ptr::null_mut())
+        };
+        let layout = GetKeyboardLayout(thread_id);
+        (!layout.is_null()).then_some(layout)
+    }
+}
+
+pub(super) fn current_layout() -> Option<KeyboardLayout> {
+    let layout = foreground_layout()?;
+    Some(KeyboardLayout {
+        id: format!("{:08X}", layout as usize & LAYOUT_HANDLE_MASK),
+        language: input_source_locale().and_then(|locale| language_from_locale(&locale)),
+    })
+}
+
+pub(super) fn key_strokes(characters: &[char]) -> Vec<Option<KeyStroke>> {
+    let Some(layout) = foreground_layout() else {
+        return vec![None; characters.len()];
+    };
+    characters
+        .iter()
+        .map(|character| {
+            let mut units = [0_u16; 2];
+            let [unit] = character.encode_utf16(&mut units) else {
+                return None;
+            };
+            let scan = unsafe { VkKeyScanExW(*unit, layout) };
+            if scan == -1 {
+                return None;
+            }
+            let shift_state = (scan >> 8) & 0xFF;
+            Some(KeyStroke {
+                key_code: (scan & 0xFF) as u16,
+                shift: shift_state & SHIFT_STATE_SHIFT != 0,
+                alt: shift_state & SHIFT_STATE_ALT_GR == SHIFT_STATE_ALT_GR,
+            })
+        })
+        .collect()
+}
+
+pub(super) fn input_source_locale() -> Option<String> {
+    unsafe {
+        let layout = foreground_layout()?;
+        let language_id = (layout as usize & LANGUAGE_ID_MASK) as u32;
+
+        let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+        let written = LCIDToLocaleName(language_id, name.as_mut_ptr(), name.len() as i32, 0);
+        if written <= 1 {
+            return None;
+        }
+        Some(String::from_utf16_lossy(&name[..written as usize - 1]))
+    }
+}
@@ -0,0 +1,191 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+use chrono::{Local, SecondsFormat};
+use serde::Serialize;
+use tauri::State;
+use tracing::{debug, info, warn};
+
+use crate::{audio_capture_service::CaptureStart, voice_pipeline::stages::StageTiming};
+
+const MAX_SAMPLES: usize = 50;
+pub const START_LATENCY_TARGET_MS: f64 = 50.0;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StartLatencySample {
+    pub recorded_at: String,
+    pub start: CaptureStart,
+    pub hotkey_to_stream_ms: Option<f64>,
+    pub stream_open_ms: f64,
+}
+
+impl StartLatencySample {
+    fn latency_ms(&self) -> f64 {
+        self.hotkey_to_stream_ms.unwrap_or(self.stream_open_ms)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyReport {
+    pub target_ms: f64,
+    pub last: Option<StartLatencySample>,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub within_target: Option<bool>,
+    pub samples: Vec<StartLatencySample>,
+    pub last_pipeline_stages: Vec<StageTiming>,
+}
+
+#[derive(Debug, Default)]
+pub struct LatencyReportStore {
+    samples: Mutex<VecDeque<StartLatencySample>>,
+    last_pipeline_stages: Mutex<Vec<StageTiming>>,
+}
+
+impl LatencyReportStore {
+    pub fn record_start(
+        &self,
+        start: CaptureStart,
+        hotkey_to_stream: Option<Duration>,
+        stream_open: Duration,
+    ) {
+        let sample = StartLatencySample {
+            recorded_at: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            start,
+            hotkey_to_stream_ms: hotkey_to_stream.map(duration_ms),
+            stream_open_ms: duration_ms(stream_open),
+        };
+        if sample.latency_ms() > START_LATENCY_TARGET_MS {
+            warn!(
+                start = start.as_str(),
+                hotkey_to_stream_ms = ?sample.hotkey_to_stream_ms,
+                stream_open_ms = sample.stream_open_ms,
+                "recording start exceeded latency target"
+            );
+        } else {
+            info!(
+                start = start.as_str(),
+                hotkey_to_stream_ms = ?sample.hotkey_to_stream_ms,
+                stream_open_ms = sample.stream_open_ms,
+                "recording start latency measured"
+            );
+        }
+
+        let Ok(mut samples) = self.samples.lock() else {
+            warn!("latency report lock is poisoned; dropping start latency sample");
+            return;
+        };
+        if samples.len() == MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    pub fn record_pipeline_run(&self, timings: &[StageTiming]) {
+        debug!(stages = ?timings, "pipeline stage timings recorded");
+        match self.last_pipeline_stages.lock() {
+            Ok(mut last) => *last = timings.to_vec(),
+            Err(_) => warn!("latency report lock is poisoned; dropping pipeline stage timings"),
+        }
+    }
+
+    pub fn report(&self) -> Result<LatencyReport, String> {
+        let samples = self
+            .samples
+            .lock()
+            .map_err(|_| "Latency report lock is poisoned".to_string())?;
+        let mut latencies = samples
+            .iter()
+            .map(StartLatencySample::latency_ms)
+            .collect::<Vec<_>>();
+        latencies.sort_by(f64::total_cmp);
+        let p95_ms = percentile(&latencies, 95);
+
+        Ok(LatencyReport {
+            target_ms: START_LATENCY_TARGET_MS,
+            last: samples.back().cloned(),
+            p50_ms: percentile(&latencies, 50),
+            p95_ms,
+            within_target: p95_ms.map(|p95| p95 <= START_LATENCY_TARGET_MS),
+            samples: samples.iter().cloned().collect(),
+            last_pipeline_stages: self
+                .last_pipeline_stages
+                .lock()
+                .map_err(|_| "Latency report lock is poisoned".to_string())?
+                .clone(),
+        })
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 10_000.0).round() / 10.0
+}
+
+fn percentile(sorted: &[f64], percent: usize) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+#[tauri::command]
+pub fn get_latency_report(store: State<'_, LatencyReportStore>) -> Result<LatencyReport, String> {
+    debug!("latency report requested");
+    store.report()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{percentile, LatencyReportStore, MAX_SAMPLES};
+    use crate::audio_capture_service::CaptureStart;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let values = (1..=20).map(f64::from).collect::<Vec<_>>();
+
+        assert_eq!(percentile(&values, 50), Some(10.0));
+        assert_eq!(percentile(&values, 95), Some(19.0));
+        assert_eq!(percentile(&[7.0], 95), Some(7.0));
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn report_prefers_hotkey_latency_and_keeps_a_bounded_window() {
+        let store = LatencyReportStore::default();
+        assert_eq!(store.report().expect("report").within_target, None);
+
+        store.record_start(
+            CaptureStart::Cold,
+            Some(Duration::from_millis(180)),
+            Duration::from_millis(170),
+        );
+        store.record_start(CaptureStart::Prewarmed, None, Duration::from_millis(4));
+
+        let report = store.report().expect("report");
+        assert_eq!(report.samples.len(), 2);
+        assert_eq!(report.p50_ms, Some(4.0));
+        assert_eq!(report.p95_ms, Some(180.0));
+        assert_eq!(report.within_target, Some(false));
+        assert_eq!(
+            report.last.map(|sample| sample.start),
+            Some(CaptureStart::Prewarmed)
+        );
+
+        for _ in 0..MAX_SAMPLES {
+            store.record_start(
+                CaptureStart::Prewarmed,
+                Some(Duration::from_millis(12)),
+                Duration::from_millis(3),
+            );
+        }
+        let report = store.report().expect("report");
+        assert_eq!(report.samples.len(), MAX_SAMPLES);
+        assert_eq!(report.p95_ms, Some(12.0));
+        assert_eq!(report.within_target, Some(true));
+    }
+}
@@ -1,68 +1,155 @@
+mod accessibility_announcer;
 mod api_key_store;
+mod app_blocklist;
 mod audio_capture_service;
 mod auth_store;
+#[cfg(target_os = "macos")]
+mod automation;
+mod backup;
+mod cli;
+mod command_palette;
+mod command_registry;
+mod context_carryover;
+mod error_recovery;
+mod feature_usage;
 mod history_store;
 mod hotkey_service;
+mod http_api;
+mod keyboard_layout;
+mod latency_report;
+mod llm;
 mod logging;
+mod markdown_output;
+mod media_pause;
+mod meeting_mode;
 mod oauth;
+mod offline_queue;
 mod permission_service;
+mod persistence;
+mod platform_support;
+mod privacy_mode;
+mod prompt_templates;
+mod provider_switch;
+mod recording_alerts;
+mod redaction;
+mod routing_rules;
 mod settings_store;
+mod source_context;
+mod startup_manager;
 mod stats_store;
 mod status_notifier;
+mod subtitles;
+mod summarization;
+mod system_health;
+mod text_formatting;
 mod text_insertion_service;
+mod transcript_diff;
 mod transcription;
+mod translation;
+mod tray_popover;
+mod trigger_service;
 mod voice_pipeline;
+mod wake_word;
+mod window_manager;
+mod write_behind;
 
 use std::{
-    fs,
+    collections::HashMap,
+    fmt, fs,
+    future::Future,
     path::{Path, PathBuf},
     process::Command,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex,
     },
-    time::Duration,
+    thread,
+    time::{Duration, Instant},
 };
 
 use api_key_store::ApiKeyStore;
+use app_blocklist::BlockedAction;
 use async_trait::async_trait;
 use audio_capture_service::{
     AudioCaptureDebugSnapshot, AudioCaptureService, AudioInputChunk, AudioInputChunkCallback,
-    AudioInputStreamErrorEvent, MicrophoneInfo, RecordedAudio, AUDIO_INPUT_STREAM_ERROR_EVENT,
-    AUDIO_LEVEL_EVENT,
+    AudioInputStreamErrorEvent, AudioLevel, InputProfile, InputProfiles, MicrophoneInfo,
+    RecordedAudio, StandbyMode, AUDIO_INPUT_STREAM_ERROR_EVENT, AUDIO_LEVEL_EVENT,
 };
 use auth_store::{AuthMethod, AuthStore};
-use history_store::{HistoryEntry, HistoryStore};
+use bytes::Bytes;
+use command_palette::CommandPaletteShortcut;
+use context_carryover::{CarryoverConfig, ContextCarryover};
+use error_recovery::ErrorRecovery;
+use feature_usage::{FeatureCategory, FeatureUsageStore};
+use history_store::{
+    count_words, HistoryEntry, HistoryEntryPreview, HistoryFilter, HistoryPage, HistorySort,
+    HistorySource, HistoryStore, HistorySyncStatus, TranscriptionDetails, DEFAULT_PREVIEW_CHARS,
+};
 use hotkey_service::{
-    HotkeyConfig, HotkeyService, RecordingMode, RecordingTransition, StopProcessingDecision,
+    HotkeyConfig, HotkeyService, HotkeyStateSnapshot, RecordingMode, RecordingTransition,
+    StopProcessingDecision,
 };
+use http_api::HttpApiService;
+use latency_report::LatencyReportStore;
 use logging::LoggingState;
+use media_pause::MediaPause;
+use meeting_mode::MeetingService;
+use offline_queue::OfflineQueue;
 use permission_service::{PermissionService, PermissionSnapshot, PermissionState, PermissionType};
+use privacy_mode::{PrivacyMode, PrivacyModeShortcut};
+use prompt_templates::{TemplateShortcuts, TranscriptCleaner};
+use provider_switch::ProviderSwitchShortcut;
+use recording_alerts::RecordingAlerts;
+use redaction::RedactionRules;
+use routing_rules::{Route, RoutingContext, RoutingTraceStore};
 use serde::{Deserialize, Serialize};
 use settings_store::{
-    SettingsStore, VoiceSettings, VoiceSettingsUpdate, RECORDING_MODE_HOLD_TO_TALK,
-    RECORDING_MODE_TOGGLE, TRANSCRIPTION_STYLE_CASUAL, TRANSCRIPTION_STYLE_CLEAN,
+    SettingsStore, SettingsValidationError, VoiceSettings, VoiceSettingsUpdate,
+    OUTPUT_TARGET_CURSOR, OUTPUT_TARGET_MARKDOWN, OVERLAY_DISPLAY_FOCUSED_WINDOW,
+    PRELOAD_MODE_KEEP_WARM, PRELOAD_MODE_OFF, RECORDING_MODE_HOLD_TO_TALK, RECORDING_MODE_TOGGLE,
+    ROUTE_DESTINATION_CLIPBOARD, ROUTE_DESTINATION_FILE, ROUTE_DESTINATION_INSERT,
+    ROUTE_DESTINATION_WEBHOOK, TRANSCRIPTION_STYLE_CASUAL, TRANSCRIPTION_STYLE_CLEAN,
     TRANSCRIPTION_STYLE_CUSTOM, TRANSCRIPTION_STYLE_VERBATIM,
 };
-use stats_store::{StatsStore, UsageStatsReport};
-use status_notifier::{AppStatus, StatusNotifier};
+use startup_manager::update_checker::{
+    UpdateAvailableEvent, UpdateChecker, EVENT_UPDATE_AVAILABLE,
+};
+use stats_store::{StatsReconciliation, StatsStore, StreakRules, UsageStatsReport};
+use status_notifier::{
+    AppStatus, ScheduledTransition, StatusNotifier, StatusSource, StatusTimeouts, StatusTransition,
+};
+use summarization::Summarizer;
+use system_health::LastPipelineError;
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconEvent},
     AppHandle, Emitter, EventTarget, Listener, LogicalPosition, Manager, Monitor, PhysicalPosition,
-    WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    PhysicalSize, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
 };
-use tauri_plugin_autostart::{MacosLauncher, ManagerExt as AutostartManagerExt};
-use text_insertion_service::TextInsertionService;
+use tauri_plugin_autostart::MacosLauncher;
+use text_formatting::{FillerRemoval, FormattingOptions, ParagraphSegmentation, TrailingOptions};
+use text_insertion_service::{TextInsertionService, TypingConfig, TypingLayout};
 use tracing::{debug, error, info, warn};
 use transcription::chatgpt::{ChatGptTranscriptionConfig, ChatGptTranscriptionProvider};
+use transcription::mock::{MockTranscriptionConfig, MockTranscriptionProvider};
 use transcription::openai::{OpenAiTranscriptionConfig, OpenAiTranscriptionProvider};
 use transcription::realtime::{
     OpenAiRealtimeTranscriptionClient, OpenAiRealtimeTranscriptionConfig, RealtimeAppendOutcome,
     RealtimeTranscriptionSession,
 };
-use transcription::{TranscriptionOptions, TranscriptionOrchestrator, TranscriptionProvider};
-use voice_pipeline::{PipelineError, PipelineTranscript, VoicePipeline, VoicePipelineDelegate};
+use transcription::{
+    NetworkConfig, ProxyConfig, QueuePosition, RequestLimitConfig, TranscriptCacheConfig,
+    TranscriptionError, TranscriptionOptions, TranscriptionOrchestrator, TranscriptionProvider,
+};
+use translation::Translator;
+use trigger_service::TriggerService;
+use voice_pipeline::{
+    stages::{StageTiming, VoiceCommandStage, REDACT_STAGE, TRANSLATE_STAGE},
+    NothingHeardReason, PipelineError, PipelineErrorCode, PipelineTimeouts, PipelineTranscript,
+    StageError, VoicePipeline, VoicePipelineDelegate,
+};
+use wake_word::WakeWordService;
+use window_manager::{WindowParams, WindowTarget, MAIN_WINDOW_LABEL, OVERLAY_WINDOW_LABEL};
 
 #[cfg(target_os = "macos")]
 use objc::{msg_send, runtime::Object, sel, sel_impl};
@@ -71,11 +158,16 @@ const EVENT_STATUS_CHANGED: &str = "voice://status-changed";
 const EVENT_TRANSCRIPT_READY: &str = "voice://transcript-ready";
 const EVENT_TRANSCRIPTION_DELTA: &str = "voice://transcription-delta";
 const EVENT_PIPELINE_ERROR: &str = "voice://pipeline-error";
+const EVENT_NOTHING_HEARD: &str = "voice://nothing-heard";
+const EVENT_TRANSCRIPTION_QUEUE: &str = "voice://transcription-queue";
 const EVENT_OVERLAY_AUDIO_LEVEL: &str = "voice://overlay-audio-level";
-const AUDIO_STREAM_ERROR_RESET_DELAY_MS: u64 = 1_500;
-const MIN_RECORDING_DURATION_MS: u64 = 200;
+const TRANSCRIPTION_WARM_UP_MIN_INTERVAL: Duration = Duration::from_secs(30);
+const TRANSCRIPTION_KEEP_WARM_INTERVAL: Duration = Duration::from_secs(60);
+const HISTORY_SYNC_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const STORE_FLUSH_POLL_INTERVAL: Duration = Duration::from_millis(250);
 const DEFAULT_HISTORY_PAGE_SIZE: usize = 50;
-const OVERLAY_WINDOW_LABEL: &str = "recording-overlay";
+const HISTORY_REINSERT_FOCUS_DELAY_MS: u64 = 300;
+const TRAY_ICON_ID: &str = "voice-tray";
 // Keep these values aligned with src/Overlay.css so the overlay shadow remains inside the window.
 const OVERLAY_PILL_WIDTH: f64 = 300.0;
 const OVERLAY_PILL_HEIGHT: f64 = 56.0;
@@ -85,17 +177,14 @@ const OVERLAY_SHADOW_SAFE_BOTTOM: f64 = 52.0;
 const OVERLAY_WINDOW_WIDTH: f64 = OVERLAY_PILL_WIDTH + (OVERLAY_SHADOW_SAFE_SIDE * 2.0);
 const OVERLAY_WINDOW_HEIGHT: f64 =
     OVERLAY_PILL_HEIGHT + OVERLAY_SHADOW_SAFE_TOP + OVERLAY_SHADOW_SAFE_BOTTOM;
-const OVERLAY_WINDOW_TOP_MARGIN: f64 = 12.0;
+const OVERLAY_WINDOW_EDGE_MARGIN: f64 = 12.0;
 const LEGACY_APP_IDENTIFIER: &str = "com.sawyerhood.voice";
+const START_DICTATION_ARG: &str = "--start-dictation";
 const CLEAN_TRANSCRIPTION_PROMPT: &str =
     "Use proper punctuation, capitalization, and paragraph breaks. Write in complete sentences.";
 const CASUAL_TRANSCRIPTION_PROMPT: &str =
     "Keep it casual and conversational. Use lowercase, minimal punctuation. Like texting a friend.";
 
-fn count_words(text: &str) -> u64 {
-    text.split_whitespace().count() as u64
-}
-
 fn sanitize_recording_duration_secs(duration_secs: f64) -> f64 {
     if duration_secs.is_finite() && duration_secs > 0.0 {
         duration_secs
@@ -104,10 +193,6 @@ fn sanitize_recording_duration_secs(duration_secs: f64) -> f64 {
     }
 }
 
-fn should_discard_recording(duration_ms: u64) -> bool {
-    duration_ms < MIN_RECORDING_DURATION_MS
-}
-
 fn recording_mode_from_settings_value(value: &str) -> Result<RecordingMode, String> {
     match value.trim().to_lowercase().as_str() {
         RECORDING_MODE_HOLD_TO_TALK => Ok(RecordingMode::HoldToTalk),
@@ -142,6 +227,15 @@ fn resolve_transcription_prompt(style: &str, custom_prompt: &str) -> Option<Stri
     }
 }
 
+fn resolve_transcription_prompt_for_settings(settings: &VoiceSettings) -> Option<String> {
+    prompt_templates::active_transcription_prompt(settings).or_else(|| {
+        resolve_transcription_prompt(
+            &settings.transcription_style,
+            &settings.custom_transcription_prompt,
+        )
+    })
+}
+
 fn resolve_hotkey_config_for_settings(
     update: &VoiceSettingsUpdate,
     fallback_hotkey: &HotkeyConfig,
@@ -349,11 +443,28 @@ struct TranscriptReadyEvent {
     text: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NothingHeardEvent {
+    reason: String,
+    duration_ms: u64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct PipelineErrorEvent {
     stage: String,
+    code: String,
     message: String,
+    detail: String,
+    suggested_action: Option<PipelineErrorActionEvent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PipelineErrorActionEvent {
+    kind: String,
+    label: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -381,6 +492,7 @@ struct RendererMemorySnapshot {
 struct AppServices {
     audio_capture_service: AudioCaptureService,
     transcription_orchestrator: TranscriptionOrchestrator,
+    mock_transcription: bool,
     chatgpt_transcription_provider: ChatGptTranscriptionProvider,
     realtime_transcription_client: OpenAiRealtimeTranscriptionClient,
     text_insertion_service: TextInsertionService,
@@ -388,6 +500,9 @@ struct AppServices {
     api_key_store: ApiKeyStore,
     auth_store: AuthStore,
     permission_service: PermissionService,
+    summarizer: Summarizer,
+    translator: Translator,
+    transcript_cleaner: TranscriptCleaner,
 }
 
 impl AppServices {
@@ -396,8 +511,16 @@ impl AppServices {
         let auth_store = AuthStore::new(app_data_dir.clone());
         let mut openai_config = OpenAiTranscriptionConfig::from_env();
         openai_config.api_key_store_app_data_dir = Some(app_data_dir.clone());
-        let provider = OpenAiTranscriptionProvider::new(openai_config.clone());
-        let transcription_orchestrator = TranscriptionOrchestrator::new(Arc::new(provider));
+        let mock_transcription_config = MockTranscriptionConfig::from_env();
+        let mock_transcription = mock_transcription_config.is_some();
+        let transcription_orchestrator = match mock_transcription_config {
+            Some(config) => {
+                TranscriptionOrchestrator::new(Arc::new(MockTranscriptionProvider::new(config)))
+            }
+            None => TranscriptionOrchestrator::new(Arc::new(OpenAiTranscriptionProvider::new(
+                openai_config.clone(),
+            ))),
+        };
         let chatgpt_transcription_provider = ChatGptTranscriptionProvider::new(
             ChatGptTranscriptionConfig::from_env(),
             auth_store.clone(),
@@ -414,6 +537,7 @@ impl AppServices {
         Self {
             audio_capture_service: AudioCaptureService::new(),
             transcription_orchestrator,
+            mock_transcription,
             chatgpt_transcription_provider,
             realtime_transcription_client,
             text_insertion_service: TextInsertionService::new(),
@@ -421,10 +545,17 @@ impl AppServices {
             api_key_store,
             auth_store,
             permission_service: PermissionService::new(),
+            summarizer: Summarizer::from_env(),
+            translator: Translator::from_env(),
+            transcript_cleaner: TranscriptCleaner::from_env(),
         }
     }
 
     fn current_auth_method(&self) -> Result<AuthMethod, String> {
+        // The mock provider replaces the API key provider, so route every caller to it.
+        if self.mock_transcription {
+            return Ok(AuthMethod::ApiKey);
+        }
         self.auth_store.effective_auth_method(&self.api_key_store)
     }
 }
@@ -432,6 +563,7 @@ impl AppServices {
 #[derive(Debug)]
 struct AppState {
     status_notifier: Mutex<StatusNotifier>,
+    last_pipeline_error: Mutex<Option<LastPipelineError>>,
     services: AppServices,
 }
 
@@ -439,6 +571,7 @@ impl AppState {
     fn new(app_data_dir: PathBuf) -> Self {
         Self {
             status_notifier: Mutex::new(StatusNotifier::default()),
+            last_pipeline_error: Mutex::new(None),
             services: AppServices::new(app_data_dir),
         }
     }
@@ -449,7 +582,41 @@ struct PipelineRuntimeState {
     execution_lock: Arc<tokio::sync::Mutex<()>>,
     next_session_id: Arc<AtomicU64>,
     active_session_id: Arc<AtomicU64>,
+    cancelled_through_session_id: Arc<AtomicU64>,
+    queued_mode: Arc<AtomicBool>,
+    processing_tail: Arc<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>>,
     realtime_session: Arc<Mutex<Option<RealtimeTranscriptionSession>>>,
+    last_warm_up: Arc<Mutex<Option<Instant>>>,
+    next_in_flight_id: Arc<AtomicU64>,
+    in_flight_recordings: Arc<Mutex<HashMap<u64, InFlightRecording>>>,
+}
+
+#[derive(Clone)]
+struct InFlightRecording {
+    wav_bytes: Bytes,
+    duration_secs: f64,
+}
+
+impl fmt::Debug for InFlightRecording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InFlightRecording")
+            .field("wav_bytes", &self.wav_bytes.len())
+            .field("duration_secs", &self.duration_secs)
+            .finish()
+    }
+}
+
+struct InFlightRecordingGuard {
+    recordings: Arc<Mutex<HashMap<u64, InFlightRecording>>>,
+    id: u64,
+}
+
+impl Drop for InFlightRecordingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut recordings) = self.recordings.lock() {
+            recordings.remove(&self.id);
+        }
+    }
 }
 
 impl Default for PipelineRuntimeState {
@@ -458,7 +625,13 @@ impl Default for PipelineRuntimeState {
             execution_lock: Arc::new(tokio::sync::Mutex::new(())),
             next_session_id: Arc::new(AtomicU64::new(0)),
             active_session_id: Arc::new(AtomicU64::new(0)),
+            cancelled_through_session_id: Arc::new(AtomicU64::new(0)),
+            queued_mode: Arc::new(AtomicBool::new(false)),
+            processing_tail: Arc::new(Mutex::new(None)),
             realtime_session: Arc::new(Mutex::new(None)),
+            last_warm_up: Arc::new(Mutex::new(None)),
+            next_in_flight_id: Arc::new(AtomicU64::new(0)),
+            in_flight_recordings: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -481,6 +654,94 @@ impl PipelineRuntimeState {
         self.active_session_id.load(Ordering::Relaxed) == session_id
     }
 
+    fn cancel_sessions(&self) -> u64 {
+        let session_id = self.begin_session();
+        self.cancelled_through_session_id
+            .store(session_id - 1, Ordering::Relaxed);
+        debug!(session_id, "pipeline sessions cancelled");
+        session_id
+    }
+
+    fn claim_warm_up(&self, now: Instant) -> bool {
+        let mut last_warm_up = self
+            .last_warm_up
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if last_warm_up
+            .is_some_and(|at| now.duration_since(at) < TRANSCRIPTION_WARM_UP_MIN_INTERVAL)
+        {
+            return false;
+        }
+        *last_warm_up = Some(now);
+        true
+    }
+
+    fn set_queued_mode(&self, enabled: bool) {
+        if self.queued_mode.swap(enabled, Ordering::Relaxed) != enabled {
+            info!(enabled, "pipeline queued mode updated");
+        }
+    }
+
+    fn is_queued_mode(&self) -> bool {
+        self.queued_mode.load(Ordering::Relaxed)
+    }
+
+    fn accepts_session_output(&self, session_id: u64) -> bool {
+        self.is_session_active(session_id)
+            || (self.is_queued_mode()
+                && session_id > self.cancelled_through_session_id.load(Ordering::Relaxed))
+    }
+
+    fn enqueue_processing<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let Ok(mut tail) = self.processing_tail.lock() else {
+            error!("failed to queue pipeline processing because queue lock was poisoned");
+            return;
+        };
+        let previous = tail.take();
+        *tail = Some(tauri::async_runtime::spawn(async move {
+            if let Some(previous) = previous {
+                let _ = previous.await;
+            }
+            task.await;
+        }));
+    }
+
+    fn track_in_flight_recording(
+        &self,
+        wav_bytes: Bytes,
+        duration_secs: f64,
+    ) -> InFlightRecordingGuard {
+        let id = self.next_in_flight_id.fetch_add(1, Ordering::Relaxed);
+        match self.in_flight_recordings.lock() {
+            Ok(mut recordings) => {
+                recordings.insert(
+                    id,
+                    InFlightRecording {
+                        wav_bytes,
+                        duration_secs,
+                    },
+                );
+            }
+            Err(_) => {
+                error!("failed to track in-flight recording because runtime lock was poisoned");
+            }
+        }
+        InFlightRecordingGuard {
+            recordings: Arc::clone(&self.in_flight_recordings),
+            id,
+        }
+    }
+
+    fn take_in_flight_recordings(&self) -> Vec<InFlightRecording> {
+        self.in_flight_recordings
+            .lock()
+            .map(|mut recordings| recordings.drain().map(|(_, recording)| recording).collect())
+            .unwrap_or_default()
+    }
+
     fn clear_realtime_session(&self) {
         match self.realtime_session.lock() {
             Ok(mut guard) => {
@@ -501,6 +762,15 @@ struct AppPipelineDelegate {
     session_id: Option<u64>,
     realtime_session: Arc<Mutex<Option<RealtimeTranscriptionSession>>>,
     recording_duration_secs: Arc<Mutex<Option<f64>>>,
+    selected_text: Arc<Mutex<Option<String>>>,
+    waveform: Arc<Mutex<Vec<u8>>>,
+    input_language: Arc<Mutex<Option<String>>>,
+    history_entry_id: Arc<Mutex<Option<String>>>,
+    history_source: Arc<Mutex<Option<HistorySource>>>,
+    private: bool,
+    hotkey_session_id: Option<u64>,
+    copy_only: bool,
+    triggered_at: Option<Instant>,
 }
 
 impl AppPipelineDelegate {
@@ -509,11 +779,23 @@ impl AppPipelineDelegate {
             let runtime_state = app.state::<PipelineRuntimeState>();
             Arc::clone(&runtime_state.realtime_session)
         };
+        let private = app
+            .try_state::<PrivacyMode>()
+            .is_some_and(|privacy_mode| privacy_mode.is_active());
         Self {
             app,
             session_id: None,
             realtime_session,
             recording_duration_secs: Arc::new(Mutex::new(None)),
+            selected_text: Arc::new(Mutex::new(None)),
+            waveform: Arc::new(Mutex::new(Vec::new())),
+            input_language: Arc::new(Mutex::new(None)),
+            history_entry_id: Arc::new(Mutex::new(None)),
+            history_source: Arc::new(Mutex::new(None)),
+            private,
+            hotkey_session_id: None,
+            copy_only: false,
+            triggered_at: None,
         }
     }
 
@@ -522,14 +804,41 @@ impl AppPipelineDelegate {
             let runtime_state = app.state::<PipelineRuntimeState>();
             Arc::clone(&runtime_state.realtime_session)
         };
+        let private = app
+            .try_state::<PrivacyMode>()
+            .is_some_and(|privacy_mode| privacy_mode.is_active());
         Self {
             app,
             session_id: Some(session_id),
             realtime_session,
             recording_duration_secs: Arc::new(Mutex::new(None)),
+            selected_text: Arc::new(Mutex::new(None)),
+            waveform: Arc::new(Mutex::new(Vec::new())),
+            input_language: Arc::new(Mutex::new(None)),
+            history_entry_id: Arc::new(Mutex::new(None)),
+            history_source: Arc::new(Mutex::new(None)),
+            private,
+            hotkey_session_id: None,
+            copy_only: false,
+            triggered_at: None,
         }
     }
 
+    fn with_hotkey_session(mut self, hotkey_session_id: Option<u64>) -> Self {
+        self.hotkey_session_id = hotkey_session_id;
+        self
+    }
+
+    fn with_copy_only(mut self, copy_only: bool) -> Self {
+        self.copy_only = copy_only;
+        self
+    }
+
+    fn with_triggered_at(mut self, triggered_at: Instant) -> Self {
+        self.triggered_at = Some(triggered_at);
+        self
+    }
+
     fn is_session_active(&self) -> bool {
         match self.session_id {
             Some(session_id) => self
@@ -540,6 +849,24 @@ impl AppPipelineDelegate {
         }
     }
 
+    fn accepts_output(&self) -> bool {
+        match self.session_id {
+            Some(session_id) => self
+                .app
+                .state::<PipelineRuntimeState>()
+                .accepts_session_output(session_id),
+            None => true,
+        }
+    }
+
+    fn detach_realtime_session(&self) -> Self {
+        let realtime_session = self.take_realtime_session();
+        Self {
+            realtime_session: Arc::new(Mutex::new(realtime_session)),
+            ..self.clone()
+        }
+    }
+
     fn current_settings(&self) -> VoiceSettings {
         let state = self.app.state::<AppState>();
         state.services.settings_store.current()
@@ -560,7 +887,7 @@ impl AppPipelineDelegate {
     }
 
     fn store_realtime_session(&self, session: Option<RealtimeTranscriptionSession>) {
-        if self.session_id.is_some() && !self.is_session_active() {
+        if self.session_id.is_some() && !self.accepts_output() {
             if let Some(stale_session) = session {
                 stale_session.close();
             }
@@ -582,7 +909,7 @@ impl AppPipelineDelegate {
     }
 
     fn take_realtime_session(&self) -> Option<RealtimeTranscriptionSession> {
-        if self.session_id.is_some() && !self.is_session_active() {
+        if self.session_id.is_some() && !self.accepts_output() {
             debug!(
                 session_id = ?self.session_id,
                 "ignoring realtime session access for inactive session"
@@ -639,12 +966,113 @@ impl AppPipelineDelegate {
         self.store_recording_duration_secs(None);
     }
 
+    fn carried_context(&self, settings: &VoiceSettings) -> Option<String> {
+        let carryover = CarryoverConfig::from_settings(settings);
+        if !carryover.enabled {
+            return None;
+        }
+        let context = self
+            .app
+            .state::<ContextCarryover>()
+            .context_for(&carryover, hotkey_service::frontmost_application().as_ref());
+        if let Some(context) = &context {
+            debug!(
+                session_id = ?self.session_id,
+                context_chars = context.chars().count(),
+                "carrying previous transcript into transcription context"
+            );
+        }
+        context
+    }
+
+    fn capture_selected_text(&self) {
+        if self.copy_only || !self.current_settings().replace_selection {
+            return;
+        }
+        let selected_text = self
+            .app
+            .state::<AppState>()
+            .services
+            .text_insertion_service
+            .selected_text();
+        if let Some(selected_text) = &selected_text {
+            info!(
+                session_id = ?self.session_id,
+                selection_chars = selected_text.chars().count(),
+                "dictation will replace the current selection"
+            );
+        }
+        match self.selected_text.lock() {
+            Ok(mut guard) => *guard = selected_text,
+            Err(_) => warn!(
+                session_id = ?self.session_id,
+                "failed to store selected text because lock was poisoned"
+            ),
+        }
+    }
+
+    fn capture_history_source(&self, settings: &VoiceSettings) {
+        let source = settings.record_history_source.then(source_context::capture);
+        match self.history_source.lock() {
+            Ok(mut guard) => *guard = source,
+            Err(_) => warn!(
+                session_id = ?self.session_id,
+                "failed to store history source because lock was poisoned"
+            ),
+        }
+    }
+
+    fn detect_input_language(&self, settings: &VoiceSettings) -> Option<String> {
+        if prompt_templates::spoken_language(settings).is_some() || !settings.keyboard_language_hint
+        {
+            return None;
+        }
+        keyboard_layout::current_input_language(&self.app)
+    }
+
+    fn capture_input_language(&self, settings: &VoiceSettings) {
+        let input_language = self.detect_input_language(settings);
+        match self.input_language.lock() {
+            Ok(mut guard) => *guard = input_language,
+            Err(_) => warn!(
+                session_id = ?self.session_id,
+                "failed to store keyboard language because lock was poisoned"
+            ),
+        }
+    }
+
+    fn take_input_language(&self) -> Option<String> {
+        self.input_language
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take())
+    }
+
+    fn take_selected_text(&self) -> Option<String> {
+        self.selected_text
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take())
+    }
+
     fn record_usage_stats_for_transcript(&self, transcript: &str) {
         let word_count = count_words(transcript);
         let recording_duration_secs = self.take_recording_duration_secs().unwrap_or(0.0);
+        if self.private {
+            return;
+        }
+        let history_entry_id = self
+            .history_entry_id
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take());
         let stats_store = self.app.state::<StatsStore>();
 
-        if let Err(error) = stats_store.record_transcription(word_count, recording_duration_secs) {
+        if let Err(error) = stats_store.record_transcription(
+            history_entry_id.as_deref(),
+            word_count,
+            recording_duration_secs,
+        ) {
             warn!(
                 session_id = ?self.session_id,
                 word_count,
@@ -654,14 +1082,305 @@ impl AppPipelineDelegate {
             );
         }
     }
+
+    fn deliver_transcript(&self, transcript: &str) -> Result<(), String> {
+        info!(
+            session_id = ?self.session_id,
+            transcript_chars = transcript.chars().count(),
+            "inserting transcript text"
+        );
+        let state = self.app.state::<AppState>();
+        let settings = state.services.settings_store.current();
+
+        if !settings.routing_rules.is_empty() {
+            let frontmost = hotkey_service::frontmost_application();
+            let route = self.app.state::<RoutingTraceStore>().route(
+                &settings.routing_rules,
+                &RoutingContext::now(frontmost.as_ref(), transcript),
+            );
+            if let Some(route) = route {
+                return self.deliver_route(&state, &settings, route);
+            }
+        }
+
+        let output_target = prompt_templates::output_target(&settings);
+        if output_target != OUTPUT_TARGET_CURSOR {
+            let path_template = settings.markdown_output_path.as_deref().unwrap_or_default();
+            let appended =
+                markdown_output::append_transcript(path_template, transcript, chrono::Local::now());
+            if output_target == OUTPUT_TARGET_MARKDOWN {
+                appended?;
+                self.record_usage_stats_for_transcript(transcript);
+                return Ok(());
+            }
+            // The cursor is the primary target in `both` mode, so a failed append shouldn't
+            // cost the user their transcript.
+            if let Err(error) = appended {
+                warn!(%error, "failed to append transcript to markdown file");
+            }
+        }
+
+        self.insert_or_copy(&state, &settings, transcript)
+    }
+
+    fn insert_or_copy(
+        &self,
+        state: &AppState,
+        settings: &VoiceSettings,
+        transcript: &str,
+    ) -> Result<(), String> {
+        let insertion_result = if settings.auto_insert && !self.copy_only {
+            let result =
+                app_blocklist::ensure_allowed(&self.app, settings, BlockedAction::Insertion)
+                    .and_then(|()| ensure_accessibility_permission_for_insertion(state))
+                    .and_then(|()| {
+                        accessibility_announcer::announce(&self.app, "Inserting text");
+                        let service = &state.services.text_insertion_service;
+                        refresh_typing_layout(&self.app, service);
+                        service.insert_text(transcript)
+                    });
+            if result.is_err() && settings.voice_error_recovery {
+                self.app
+                    .state::<ErrorRecovery>()
+                    .keep_transcript(transcript);
+            }
+            result
+        } else {
+            self.copy_to_clipboard(state, transcript)
+        };
+
+        if insertion_result.is_ok() {
+            self.record_usage_stats_for_transcript(transcript);
+        }
+
+        insertion_result
+    }
+
+    fn copy_to_clipboard(&self, state: &AppState, transcript: &str) -> Result<(), String> {
+        state
+            .services
+            .text_insertion_service
+            .copy_to_clipboard(transcript)
+            .inspect(|()| accessibility_announcer::announce(&self.app, "Copied to clipboard"))
+    }
+
+    fn deliver_route(
+        &self,
+        state: &AppState,
+        settings: &VoiceSettings,
+        route: Route,
+    ) -> Result<(), String> {
+        let target = route.target.as_deref().unwrap_or_default();
+        match route.destination.as_str() {
+            ROUTE_DESTINATION_INSERT => return self.insert_or_copy(state, settings, &route.text),
+            ROUTE_DESTINATION_CLIPBOARD => self.copy_to_clipboard(state, &route.text)?,
+            ROUTE_DESTINATION_FILE => {
+                markdown_output::append_transcript(target, &route.text, chrono::Local::now())?;
+                accessibility_announcer::announce(&self.app, "Saved to file");
+            }
+            ROUTE_DESTINATION_WEBHOOK => {
+                // Network delivery must not hold up the pipeline, so failures are reported
+                // after the fact.
+                let app = self.app.clone();
+                let url = target.to_string();
+                let Route { rule, text, .. } = route.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(error) = routing_rules::post_webhook(&url, &rule, &text).await {
+                        warn!(%error, rule = %rule, "failed to deliver routed transcript");
+                        accessibility_announcer::announce(&app, &error);
+                    }
+                });
+            }
+            destination => {
+                return Err(format!("Unsupported routing destination `{destination}`"));
+            }
+        }
+
+        self.record_usage_stats_for_transcript(&route.text);
+        Ok(())
+    }
+
+    async fn apply_cleanup(
+        &self,
+        transcript: PipelineTranscript,
+        settings: &VoiceSettings,
+    ) -> PipelineTranscript {
+        let selection = self
+            .take_selected_text()
+            .filter(|_| settings.selection_as_cleanup_context);
+        let template = prompt_templates::active_template(settings)
+            .filter(|template| !template.cleanup_instructions.is_empty());
+        if let Some(selection) = selection {
+            return self
+                .rewrite_selection(
+                    transcript,
+                    &selection,
+                    template.map(|template| template.cleanup_instructions.as_str()),
+                )
+                .await;
+        }
+        let Some(template) = template else {
+            return transcript;
+        };
+
+        let state = self.app.state::<AppState>();
+        let api_key = match llm::resolve_openai_api_key(&state.services.api_key_store) {
+            Ok(Some(api_key)) => api_key,
+            Ok(None) => {
+                warn!(
+                    session_id = ?self.session_id,
+                    template = %template.name,
+                    "skipping transcript cleanup without an OpenAI API key"
+                );
+                return transcript;
+            }
+            Err(error) => {
+                warn!(session_id = ?self.session_id, %error, "failed to resolve cleanup api key");
+                return transcript;
+            }
+        };
+
+        let cleaner = state.services.transcript_cleaner.clone();
+        match cleaner
+            .clean_up(&api_key, &transcript.text, &template.cleanup_instructions)
+            .await
+        {
+            Ok(text) => {
+                info!(
+                    session_id = ?self.session_id,
+                    template = %template.name,
+                    transcript_chars = text.chars().count(),
+                    "transcript cleaned up with prompt template"
+                );
+                let raw_text = (text != transcript.text).then(|| transcript.text.clone());
+                PipelineTranscript {
+                    text,
+                    raw_text,
+                    ..transcript
+                }
+            }
+            Err(error) => {
+                warn!(
+                    session_id = ?self.session_id,
+                    template = %template.name,
+                    %error,
+                    "transcript cleanup failed; keeping raw transcript"
+                );
+                transcript
+            }
+        }
+    }
+
+    async fn split_paragraphs_by_topic(
+        &self,
+        transcript: PipelineTranscript,
+    ) -> PipelineTranscript {
+        if !text_formatting::is_long_enough_to_segment(&transcript.text) {
+            return transcript;
+        }
+        let state = self.app.state::<AppState>();
+        let api_key = match llm::resolve_openai_api_key(&state.services.api_key_store) {
+            Ok(Some(api_key)) => api_key,
+            Ok(None) => {
+                debug!(
+                    session_id = ?self.session_id,
+                    "skipping paragraph segmentation without an OpenAI API key"
+                );
+                return transcript;
+            }
+            Err(error) => {
+                warn!(session_id = ?self.session_id, %error, "failed to resolve cleanup api key");
+                return transcript;
+            }
+        };
+
+        let cleaner = state.services.transcript_cleaner.clone();
+        match cleaner.split_paragraphs(&api_key, &transcript.text).await {
+            Ok(text) if text_formatting::keeps_words(&transcript.text, &text) => {
+                info!(
+                    session_id = ?self.session_id,
+                    paragraphs = text.split("\n\n").count(),
+                    "transcript split into paragraphs by topic"
+                );
+                PipelineTranscript { text, ..transcript }
+            }
+            Ok(_) => {
+                warn!(
+                    session_id = ?self.session_id,
+                    "paragraph segmentation changed the wording; keeping transcript as is"
+                );
+                transcript
+            }
+            Err(error) => {
+                warn!(
+                    session_id = ?self.session_id,
+                    %error,
+                    "paragraph segmentation failed; keeping transcript as is"
+                );
+                transcript
+            }
+        }
+    }
+
+    async fn rewrite_selection(
+        &self,
+        transcript: PipelineTranscript,
+        selection: &str,
+        instructions: Option<&str>,
+    ) -> PipelineTranscript {
+        let state = self.app.state::<AppState>();
+        let api_key = match llm::resolve_openai_api_key(&state.services.api_key_store) {
+            Ok(Some(api_key)) => api_key,
+            Ok(None) => {
+                warn!(
+                    session_id = ?self.session_id,
+                    "skipping selection rewrite without an OpenAI API key"
+                );
+                return transcript;
+            }
+            Err(error) => {
+                warn!(session_id = ?self.session_id, %error, "failed to resolve cleanup api key");
+                return transcript;
+            }
+        };
+
+        let cleaner = state.services.transcript_cleaner.clone();
+        match cleaner
+            .rewrite_selection(&api_key, selection, &transcript.text, instructions)
+            .await
+        {
+            Ok(text) => {
+                info!(
+                    session_id = ?self.session_id,
+                    selection_chars = selection.chars().count(),
+                    transcript_chars = text.chars().count(),
+                    "selection rewritten from dictation"
+                );
+                let raw_text = (text != transcript.text).then(|| transcript.text.clone());
+                PipelineTranscript {
+                    text,
+                    raw_text,
+                    ..transcript
+                }
+            }
+            Err(error) => {
+                warn!(
+                    session_id = ?self.session_id,
+                    %error,
+                    "selection rewrite failed; keeping raw transcript"
+                );
+                transcript
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl VoicePipelineDelegate for AppPipelineDelegate {
-    fn set_status(&self, status: AppStatus) {
+    fn set_status(&self, status: AppStatus, source: StatusSource) {
         if self.is_session_active() {
             debug!(?status, session_id = ?self.session_id, "updating app status");
-            set_status_for_app(&self.app, status);
+            set_status_for_app(&self.app, status, source);
         } else {
             debug!(
                 ?status,
@@ -672,7 +1391,7 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
     }
 
     fn emit_transcript(&self, transcript: &str) {
-        if self.is_session_active() {
+        if self.accepts_output() {
             info!(
                 session_id = ?self.session_id,
                 transcript_chars = transcript.chars().count(),
@@ -688,7 +1407,7 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
     }
 
     fn emit_error(&self, error: &PipelineError) {
-        if self.is_session_active() {
+        if self.accepts_output() {
             error!(
                 session_id = ?self.session_id,
                 stage = error.stage.as_str(),
@@ -696,6 +1415,12 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
                 "pipeline error emitted"
             );
             emit_pipeline_error_event(&self.app, error);
+            error_recovery::offer(&self.app, &self.current_settings());
+            feature_usage::record_if_enabled(
+                &self.app,
+                FeatureCategory::Error,
+                error.code.as_str(),
+            );
         } else {
             debug!(
                 session_id = ?self.session_id,
@@ -705,10 +1430,36 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
         }
     }
 
+    fn emit_nothing_heard(&self, reason: NothingHeardReason, duration_ms: u64) {
+        self.clear_realtime_session();
+        self.clear_recording_duration_secs();
+        if !self.accepts_output() {
+            return;
+        }
+
+        let payload = NothingHeardEvent {
+            reason: reason.as_str().to_string(),
+            duration_ms,
+        };
+        if let Err(error) = self.app.emit(EVENT_NOTHING_HEARD, payload) {
+            warn!(%error, "failed to emit nothing heard event");
+        }
+    }
+
+    fn min_recording_duration_ms(&self) -> u64 {
+        self.current_settings().min_recording_duration_ms
+    }
+
     fn on_recording_started(&self, success: bool) {
         debug!(session_id = ?self.session_id, success, "recording start acknowledged");
-        let hotkey_service = self.app.state::<HotkeyService>();
-        hotkey_service.acknowledge_transition(RecordingTransition::Started, success);
+        if let Some(hotkey_session_id) = self.hotkey_session_id {
+            let hotkey_service = self.app.state::<HotkeyService>();
+            hotkey_service.acknowledge_transition(
+                hotkey_session_id,
+                RecordingTransition::Started,
+                success,
+            );
+        }
     }
 
     fn on_recording_stopped(&self, success: bool) {
@@ -717,8 +1468,14 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
             self.clear_realtime_session();
             self.clear_recording_duration_secs();
         }
-        let hotkey_service = self.app.state::<HotkeyService>();
-        hotkey_service.acknowledge_transition(RecordingTransition::Stopped, success);
+        if let Some(hotkey_session_id) = self.hotkey_session_id {
+            let hotkey_service = self.app.state::<HotkeyService>();
+            hotkey_service.acknowledge_transition(
+                hotkey_session_id,
+                RecordingTransition::Stopped,
+                success,
+            );
+        }
     }
 
     fn start_recording(&self) -> Result<(), String> {
@@ -731,6 +1488,9 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
         let state = self.app.state::<AppState>();
         ensure_microphone_permission_for_recording(&state)?;
 
+        if settings.preload_mode != PRELOAD_MODE_OFF {
+            warm_transcription_connection(&self.app);
+        }
         self.clear_realtime_session();
         self.clear_recording_duration_secs();
 
@@ -739,19 +1499,22 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
             .current_auth_method()
             .map_err(|error| format!("Failed to resolve active auth method: {error}"))?;
 
+        // Pause-based paragraphs need segment timestamps, which only the upload returns.
         let realtime_session = if auth_method == AuthMethod::ApiKey
+            && !state.services.mock_transcription
+            && !ParagraphSegmentation::from_settings(&settings).needs_timestamps()
             && state
                 .services
                 .realtime_transcription_client
                 .model_supports_realtime()
         {
-            let transcription_prompt = resolve_transcription_prompt(
-                &settings.transcription_style,
-                &settings.custom_transcription_prompt,
-            );
+            let transcription_prompt = resolve_transcription_prompt_for_settings(&settings);
             let options = TranscriptionOptions {
-                language: settings.language.clone(),
+                language: prompt_templates::spoken_language(&settings)
+                    .map(str::to_string)
+                    .or_else(|| self.detect_input_language(&settings)),
                 prompt: transcription_prompt,
+                context_hint: self.carried_context(&settings),
                 on_delta: Some(self.build_delta_callback()),
                 ..TranscriptionOptions::default()
             };
@@ -829,20 +1592,29 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
                 }) as AudioInputChunkCallback
             });
 
+        let stream_open_started_at = Instant::now();
         let start_result = state.services.audio_capture_service.start_recording(
             self.app.clone(),
             settings.microphone_id.as_deref(),
             chunk_callback,
         );
 
-        if start_result.is_ok() {
-            self.store_realtime_session(realtime_session);
-            start_result
-        } else {
-            if let Some(session) = realtime_session {
-                session.close();
+        match start_result {
+            Ok(start) => {
+                self.app.state::<LatencyReportStore>().record_start(
+                    start,
+                    self.triggered_at.map(|triggered_at| triggered_at.elapsed()),
+                    stream_open_started_at.elapsed(),
+                );
+                self.store_realtime_session(realtime_session);
+                Ok(())
+            }
+            Err(error) => {
+                if let Some(session) = realtime_session {
+                    session.close();
+                }
+                Err(error)
             }
-            start_result
         }
     }
 
@@ -853,27 +1625,16 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
             .services
             .audio_capture_service
             .stop_recording(self.app.clone())
-            .map(|recorded| {
-                if should_discard_recording(recorded.duration_ms) {
-                    debug!(
-                        session_id = ?self.session_id,
-                        duration_ms = recorded.duration_ms,
-                        min_duration_ms = MIN_RECORDING_DURATION_MS,
-                        "recording too short, discarding"
-                    );
-                    self.clear_realtime_session();
-                    self.clear_recording_duration_secs();
-                    return RecordedAudio::empty(
-                        recorded.sample_rate_hz,
-                        recorded.channels,
-                        recorded.duration_ms,
-                        recorded.device_id.clone(),
-                        recorded.device_name.clone(),
-                    );
-                }
+            .inspect(|recorded| {
                 let duration_secs = recorded.duration_ms as f64 / 1000.0;
                 self.store_recording_duration_secs(Some(duration_secs));
-                recorded
+                if let Ok(mut waveform) = self.waveform.lock() {
+                    *waveform = recorded.waveform();
+                }
+                self.capture_selected_text();
+                let settings = self.current_settings();
+                self.capture_input_language(&settings);
+                self.capture_history_source(&settings);
             });
         if result.is_err() {
             self.clear_realtime_session();
@@ -885,15 +1646,19 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
     async fn transcribe(
         &self,
         recorded_audio: RecordedAudio,
-    ) -> Result<PipelineTranscript, String> {
+    ) -> Result<PipelineTranscript, StageError> {
+        let started_at = Instant::now();
         let settings = self.current_settings();
-        let transcription_prompt = resolve_transcription_prompt(
-            &settings.transcription_style,
-            &settings.custom_transcription_prompt,
-        );
+        let transcription_prompt = resolve_transcription_prompt_for_settings(&settings);
+        let context_hint = self.carried_context(&settings);
+        let segmentation = ParagraphSegmentation::from_settings(&settings);
         let options = TranscriptionOptions {
-            language: settings.language,
+            language: prompt_templates::spoken_language(&settings)
+                .map(str::to_string)
+                .or_else(|| self.take_input_language()),
             prompt: transcription_prompt,
+            context_hint,
+            timestamps: segmentation.needs_timestamps(),
             on_delta: Some(self.build_delta_callback()),
             ..TranscriptionOptions::default()
         };
@@ -905,13 +1670,30 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
         let orchestrator = state.services.transcription_orchestrator.clone();
         let chatgpt_provider = state.services.chatgpt_transcription_provider.clone();
         let provider_name = match auth_method {
-            AuthMethod::ApiKey => "openai",
+            AuthMethod::ApiKey => orchestrator.provider_name(),
             AuthMethod::ChatgptOauth => "chatgpt-oauth",
             AuthMethod::None => "none",
         }
         .to_string();
         let provider_name_for_error = provider_name.clone();
 
+        let recording_duration_secs = recorded_audio.duration_ms as f64 / 1000.0;
+        // Enough to rebuild the recording if a spoken "retry" asks for it after a failure.
+        let retry_format = settings.voice_error_recovery.then(|| {
+            (
+                recorded_audio.sample_rate_hz,
+                recorded_audio.channels,
+                recorded_audio.duration_ms,
+                recorded_audio.device_id.clone(),
+                recorded_audio.device_name.clone(),
+            )
+        });
+        let wav_bytes = Bytes::from(recorded_audio.into_wav_bytes()?);
+        let _in_flight = self
+            .app
+            .state::<PipelineRuntimeState>()
+            .track_in_flight_recording(wav_bytes.clone(), recording_duration_secs);
+
         if auth_method == AuthMethod::ApiKey {
             if let Some(realtime_session) = self.take_realtime_session() {
                 info!(
@@ -927,6 +1709,12 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
                             duration_secs: transcription.duration_secs,
                             language: transcription.language,
                             provider: "openai-realtime".to_string(),
+                            raw_text: None,
+                            details: TranscriptionDetails {
+                                model: transcription.model,
+                                latency_ms: Some(started_at.elapsed().as_millis() as u64),
+                                confidence: transcription.confidence,
+                            },
                         };
                         info!(
                             session_id = ?self.session_id,
@@ -934,6 +1722,7 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
                             transcript_chars = transcript.text.chars().count(),
                             "realtime transcription completed"
                         );
+                        offline_queue::flush_in_background(&self.app);
                         return Ok(transcript);
                     }
                     Err(error) => {
@@ -951,14 +1740,12 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
         }
 
         if auth_method == AuthMethod::None {
-            return Err(
-                "No authentication configured. Add an OpenAI API key or login with ChatGPT."
-                    .to_string(),
-            );
+            return Err(StageError::new(
+                PipelineErrorCode::AuthMissing,
+                "No authentication configured. Add an OpenAI API key or login with ChatGPT.",
+            ));
         }
 
-        let wav_bytes = recorded_audio.into_wav_bytes()?;
-
         info!(
             session_id = ?self.session_id,
             provider = %provider_name,
@@ -967,17 +1754,41 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
         );
 
         let transcription = match auth_method {
-            AuthMethod::ApiKey => orchestrator.transcribe(wav_bytes, options).await,
-            AuthMethod::ChatgptOauth => chatgpt_provider.transcribe(wav_bytes, options).await,
+            AuthMethod::ApiKey => orchestrator.transcribe(wav_bytes.clone(), options).await,
+            AuthMethod::ChatgptOauth => {
+                chatgpt_provider
+                    .transcribe(wav_bytes.clone(), options)
+                    .await
+            }
             AuthMethod::None => unreachable!("auth method none is handled above"),
         };
+        if transcription.is_ok() {
+            offline_queue::flush_in_background(&self.app);
+        }
 
         transcription
-            .map(|transcription| PipelineTranscript {
-                text: transcription.text,
-                duration_secs: transcription.duration_secs,
-                language: transcription.language,
-                provider: provider_name.clone(),
+            .map(|transcription| {
+                let text = match segmentation {
+                    ParagraphSegmentation::Pauses(pause)
+                        if text_formatting::is_long_enough_to_segment(&transcription.text) =>
+                    {
+                        text_formatting::paragraphs_from_segments(&transcription.segments, pause)
+                            .unwrap_or(transcription.text)
+                    }
+                    _ => transcription.text,
+                };
+                PipelineTranscript {
+                    text,
+                    duration_secs: transcription.duration_secs,
+                    language: transcription.language,
+                    provider: provider_name.clone(),
+                    raw_text: None,
+                    details: TranscriptionDetails {
+                        model: transcription.model,
+                        latency_ms: Some(started_at.elapsed().as_millis() as u64),
+                        confidence: transcription.confidence,
+                    },
+                }
             })
             .map(|transcript| {
                 info!(
@@ -995,55 +1806,258 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
                     error = %error,
                     "transcription request failed"
                 );
-                error.to_string()
+                let keep_for_retry = |wav_bytes: &[u8]| {
+                    if let Some((sample_rate_hz, channels, duration_ms, device_id, device_name)) =
+                        retry_format
+                    {
+                        self.app.state::<ErrorRecovery>().keep_recording(
+                            RecordedAudio::from_wav_bytes(
+                                wav_bytes.to_vec(),
+                                sample_rate_hz,
+                                channels,
+                                duration_ms,
+                                device_id,
+                                device_name,
+                            ),
+                        );
+                    }
+                };
+                if self.private || !matches!(error, TranscriptionError::Network(_)) {
+                    keep_for_retry(&wav_bytes);
+                    return StageError::from(error);
+                }
+
+                match offline_queue::enqueue_recording(
+                    &self.app,
+                    &wav_bytes,
+                    Some(recording_duration_secs),
+                    &error,
+                ) {
+                    Ok(_) => StageError {
+                        message: format!(
+                            "{error}. The recording was saved and will be transcribed when the \
+                             connection returns."
+                        ),
+                        ..StageError::from(error)
+                    },
+                    Err(queue_error) => {
+                        warn!(
+                            session_id = ?self.session_id,
+                            error = %queue_error,
+                            "failed to save recording to offline queue"
+                        );
+                        keep_for_retry(&wav_bytes);
+                        StageError::from(error)
+                    }
+                }
             })
     }
 
-    fn insert_text(&self, transcript: &str) -> Result<(), String> {
-        if !self.is_session_active() {
-            warn!(
-                session_id = ?self.session_id,
-                "skipping text insertion for inactive session"
-            );
-            return Ok(());
+    fn handle_voice_command(&self, transcript: &PipelineTranscript) -> bool {
+        let Some(enabled) = text_formatting::spoken_punctuation_command(&transcript.text) else {
+            return false;
+        };
+        if self.accepts_output() {
+            set_spoken_punctuation(&self.app, enabled);
+        }
+        true
+    }
+
+    async fn translate(
+        &self,
+        transcript: PipelineTranscript,
+    ) -> Result<PipelineTranscript, String> {
+        let settings = self.current_settings();
+        let Some(target_language) =
+            prompt_templates::translation_target_language(&settings).map(str::to_string)
+        else {
+            return Ok(transcript);
+        };
+        let source_language = prompt_templates::spoken_language(&settings);
+        if source_language.map(str::to_ascii_lowercase) == Some(target_language.clone()) {
+            return Ok(transcript);
         }
 
+        let state = self.app.state::<AppState>();
+        let api_key = llm::resolve_openai_api_key(&state.services.api_key_store)?
+            .ok_or_else(|| "Translation requires an OpenAI API key".to_string())?;
+        let translator = state.services.translator.clone();
+        let text = translator
+            .translate(
+                &api_key,
+                &transcript.text,
+                source_language,
+                &target_language,
+            )
+            .await?;
         info!(
             session_id = ?self.session_id,
-            transcript_chars = transcript.chars().count(),
-            "inserting transcript text"
+            source_language = ?source_language,
+            target_language = %target_language,
+            transcript_chars = text.chars().count(),
+            "transcript translated"
         );
-        let state = self.app.state::<AppState>();
-        let auto_insert = state.services.settings_store.current().auto_insert;
 
-        let insertion_result = if auto_insert {
-            ensure_accessibility_permission_for_insertion(&state)?;
-            state
-                .services
-                .text_insertion_service
-                .insert_text(transcript)
+        Ok(PipelineTranscript {
+            text,
+            language: Some(target_language),
+            ..transcript
+        })
+    }
+
+    async fn clean_up(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+        let settings = self.current_settings();
+        let transcript = self.apply_cleanup(transcript, &settings).await;
+        if ParagraphSegmentation::from_settings(&settings) == ParagraphSegmentation::Topics {
+            return self.split_paragraphs_by_topic(transcript).await;
+        }
+        transcript
+    }
+
+    fn format_transcript(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+        let settings = self.current_settings();
+        let language = transcript
+            .language
+            .clone()
+            .or_else(|| prompt_templates::spoken_language(&settings).map(str::to_string));
+        let transcript = if prompt_templates::spoken_punctuation_enabled(&settings) {
+            PipelineTranscript {
+                text: text_formatting::apply_spoken_punctuation(&transcript.text),
+                ..transcript
+            }
         } else {
-            state
-                .services
-                .text_insertion_service
-                .copy_to_clipboard(transcript)
+            transcript
         };
 
-        if insertion_result.is_ok() {
-            self.record_usage_stats_for_transcript(transcript);
+        let fillers = FillerRemoval::from_settings(&settings);
+        let transcript = if fillers == FillerRemoval::Off {
+            transcript
+        } else {
+            let text =
+                text_formatting::remove_fillers(&transcript.text, fillers, language.as_deref());
+            // History keeps the unfiltered transcript unless cleanup already kept one.
+            let raw_text = transcript
+                .raw_text
+                .or_else(|| (text != transcript.text).then(|| transcript.text.clone()));
+            PipelineTranscript {
+                text,
+                raw_text,
+                ..transcript
+            }
+        };
+
+        let options = FormattingOptions::from_settings(&settings);
+        let transcript = if options.is_noop() {
+            transcript
+        } else {
+            let format = |text: &str| {
+                text_formatting::format_transcript(text, &options, language.as_deref())
+            };
+            PipelineTranscript {
+                text: format(&transcript.text),
+                raw_text: transcript.raw_text.as_deref().map(format),
+                ..transcript
+            }
+        };
+
+        // Only the inserted text gets the trailing separator; the raw text is kept for the
+        // cleanup diff.
+        let frontmost = (!settings.trailing_overrides.is_empty())
+            .then(hotkey_service::frontmost_application)
+            .flatten();
+        let trailing = TrailingOptions::for_app(&settings, frontmost.as_ref());
+        if trailing.is_noop() {
+            return transcript;
+        }
+        debug!(
+            session_id = ?self.session_id,
+            trailing = ?trailing.trailing,
+            strip_trailing_period = trailing.strip_trailing_period,
+            app = ?frontmost.as_ref().and_then(|app| app.name.as_deref()),
+            "applying transcript trailing"
+        );
+        PipelineTranscript {
+            text: text_formatting::apply_trailing(&transcript.text, &trailing),
+            ..transcript
         }
+    }
 
-        insertion_result
+    fn redact(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+        let rules = RedactionRules::from_settings(&self.current_settings());
+        if rules.is_empty() {
+            return transcript;
+        }
+
+        let text = rules.apply(&transcript.text);
+        if text != transcript.text {
+            debug!(session_id = ?self.session_id, "transcript redacted");
+        }
+        // The raw text is kept in history for the cleanup diff, so it must be masked too.
+        let raw_text = transcript.raw_text.as_deref().map(|raw| rules.apply(raw));
+        PipelineTranscript {
+            text,
+            raw_text,
+            ..transcript
+        }
+    }
+
+    async fn insert_text(&self, transcript: &str) -> Result<(), String> {
+        if !self.accepts_output() {
+            warn!(
+                session_id = ?self.session_id,
+                "skipping text insertion for inactive session"
+            );
+            return Ok(());
+        }
+
+        // Insertion talks to the focused app and can hang; running it off the async worker lets
+        // the pipeline's insertion timeout give up on it.
+        let delegate = self.clone();
+        let transcript = transcript.to_string();
+        tauri::async_runtime::spawn_blocking(move || delegate.deliver_transcript(&transcript))
+            .await
+            .map_err(|error| format!("Failed to run text insertion: {error}"))?
     }
 
     fn save_history_entry(&self, transcript: &PipelineTranscript) -> Result<(), String> {
-        if !self.is_session_active() {
+        if !self.accepts_output() {
             warn!(
                 session_id = ?self.session_id,
                 "skipping history persistence for inactive session"
             );
             return Ok(());
         }
+        if self.private {
+            debug!(
+                session_id = ?self.session_id,
+                "skipping history persistence in privacy mode"
+            );
+            return Ok(());
+        }
+        feature_usage::record_if_enabled(
+            &self.app,
+            FeatureCategory::Provider,
+            &transcript.provider,
+        );
+        if let Err(error) = self
+            .app
+            .state::<StatsStore>()
+            .record_provider_performance(&transcript.provider, &transcript.details)
+        {
+            warn!(
+                session_id = ?self.session_id,
+                %error,
+                "failed to persist provider performance stats"
+            );
+        }
+        let carryover = CarryoverConfig::from_settings(&self.current_settings());
+        if carryover.enabled {
+            self.app.state::<ContextCarryover>().record(
+                &carryover,
+                hotkey_service::frontmost_application().as_ref(),
+                &transcript.text,
+            );
+        }
 
         let history_store = self.app.state::<HistoryStore>();
         let entry = HistoryEntry::new(
@@ -1051,6 +2065,20 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
             transcript.duration_secs,
             transcript.language.clone(),
             transcript.provider.clone(),
+        )
+        .with_raw_text(transcript.raw_text.clone())
+        .with_details(transcript.details.clone())
+        .with_source(
+            self.history_source
+                .lock()
+                .ok()
+                .and_then(|mut source| source.take()),
+        )
+        .with_waveform(
+            self.waveform
+                .lock()
+                .map(|mut waveform| std::mem::take(&mut *waveform))
+                .unwrap_or_default(),
         );
         debug!(
             session_id = ?self.session_id,
@@ -1059,8 +2087,279 @@ impl VoicePipelineDelegate for AppPipelineDelegate {
             "persisting transcript history entry"
         );
 
+        if let Ok(mut history_entry_id) = self.history_entry_id.lock() {
+            *history_entry_id = Some(entry.id.clone());
+        }
         history_store.add_entry(entry)
     }
+
+    fn record_stage_timings(&self, timings: &[StageTiming]) {
+        self.app
+            .state::<LatencyReportStore>()
+            .record_pipeline_run(timings);
+    }
+}
+
+fn pipeline_for_settings(settings: &VoiceSettings) -> VoicePipeline {
+    let mut pipeline = VoicePipeline::default()
+        .with_error_reset_delay(Duration::from_millis(settings.status_error_reset_ms))
+        .with_timeouts(pipeline_timeouts(settings))
+        .with_stage_before(TRANSLATE_STAGE, Arc::new(VoiceCommandStage));
+    if prompt_templates::translation_target_language(settings).is_none() {
+        pipeline = pipeline.without_stage(TRANSLATE_STAGE);
+    }
+    if RedactionRules::from_settings(settings).is_empty() {
+        pipeline = pipeline.without_stage(REDACT_STAGE);
+    }
+    pipeline
+}
+
+fn pipeline_timeouts(settings: &VoiceSettings) -> PipelineTimeouts {
+    let limit = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+    PipelineTimeouts {
+        max_recording: limit(settings.max_recording_secs),
+        transcription: limit(settings.transcription_stage_timeout_secs),
+        insertion: limit(settings.insertion_timeout_secs),
+    }
+}
+
+fn arm_recording_limit(app: &AppHandle, session_id: u64, hotkey_session_id: Option<u64>) {
+    let settings = app.state::<AppState>().services.settings_store.current();
+    let Some(limit) = pipeline_timeouts(&settings).max_recording else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(limit).await;
+        let hotkey_service = app.state::<HotkeyService>();
+        let still_recording = app
+            .state::<PipelineRuntimeState>()
+            .is_session_active(session_id)
+            && get_status_from_state(&app.state::<AppState>()) == AppStatus::Listening
+            && hotkey_service.recording_session_id() == hotkey_session_id;
+        if !still_recording {
+            return;
+        }
+
+        warn!(
+            session_id,
+            limit_secs = limit.as_secs(),
+            "recording reached its maximum length"
+        );
+        emit_pipeline_error_event(
+            &app,
+            &PipelineError::new(
+                voice_pipeline::PipelineErrorStage::RecordingRuntime,
+                StageError::new(
+                    PipelineErrorCode::RecordingTimeLimit,
+                    voice_pipeline::recording_limit_message(limit),
+                ),
+            ),
+        );
+        if !hotkey_service.request_recording_transition(&app, RecordingTransition::Stopped) {
+            warn!(session_id, "failed to stop recording at its maximum length");
+        }
+    });
+}
+
+fn warm_transcription_connection(app: &AppHandle) {
+    if !app
+        .state::<PipelineRuntimeState>()
+        .claim_warm_up(Instant::now())
+    {
+        return;
+    }
+
+    let state = app.state::<AppState>();
+    let auth_method = match state.services.current_auth_method() {
+        Ok(auth_method) => auth_method,
+        Err(error) => {
+            debug!(%error, "skipping transcription warm-up without an auth method");
+            return;
+        }
+    };
+    let orchestrator = state.services.transcription_orchestrator.clone();
+    let chatgpt_provider = state.services.chatgpt_transcription_provider.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match auth_method {
+            AuthMethod::ApiKey => orchestrator.warm_up().await,
+            AuthMethod::ChatgptOauth => chatgpt_provider.warm_up().await,
+            AuthMethod::None => return,
+        };
+        match result {
+            Ok(()) => debug!(
+                auth_method = auth_method.as_str(),
+                "transcription connection warmed"
+            ),
+            Err(error) => debug!(%error, "transcription connection warm-up failed"),
+        }
+    });
+}
+
+fn spawn_transcription_keep_warm_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let preload_mode = app
+                .state::<AppState>()
+                .services
+                .settings_store
+                .current()
+                .preload_mode;
+            if preload_mode == PRELOAD_MODE_KEEP_WARM {
+                warm_transcription_connection(&app);
+            }
+            tokio::time::sleep(TRANSCRIPTION_KEEP_WARM_INTERVAL).await;
+        }
+    });
+}
+
+fn spawn_history_sync_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(HISTORY_SYNC_POLL_INTERVAL).await;
+            let app = app.clone();
+            let result = tauri::async_runtime::spawn_blocking(move || {
+                app.state::<HistoryStore>().sync_if_changed()
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => warn!(%error, "history sync failed"),
+                Err(error) => warn!(%error, "history sync task failed"),
+            }
+        }
+    });
+}
+
+fn spawn_store_flush_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(STORE_FLUSH_POLL_INTERVAL).await;
+            let app = app.clone();
+            let result = tauri::async_runtime::spawn_blocking(move || {
+                if let Err(error) = app.state::<HistoryStore>().flush_if_due() {
+                    warn!(%error, "failed to flush history");
+                }
+                if let Err(error) = app.state::<StatsStore>().flush_if_due() {
+                    warn!(%error, "failed to flush usage stats");
+                }
+            })
+            .await;
+            if let Err(error) = result {
+                warn!(%error, "store flush task failed");
+            }
+        }
+    });
+}
+
+fn flush_stores(app: &AppHandle) {
+    if let Some(history_store) = app.try_state::<HistoryStore>() {
+        if let Err(error) = history_store.flush() {
+            error!(%error, "failed to flush history before exit");
+        }
+    }
+    if let Some(stats_store) = app.try_state::<StatsStore>() {
+        if let Err(error) = stats_store.flush() {
+            error!(%error, "failed to flush usage stats before exit");
+        }
+    }
+    debug!("stores flushed");
+}
+
+fn shutdown_gracefully(app: &AppHandle) {
+    static SHUTDOWN_STARTED: AtomicBool = AtomicBool::new(false);
+    if SHUTDOWN_STARTED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    info!("graceful shutdown started");
+
+    let mut interrupted = Vec::new();
+    if let Some(runtime_state) = app.try_state::<PipelineRuntimeState>() {
+        runtime_state.cancel_sessions();
+        interrupted.extend(runtime_state.take_in_flight_recordings());
+    }
+    if let Some(hotkey_service) = app.try_state::<HotkeyService>() {
+        hotkey_service.force_stop_recording(app);
+    }
+    if let Some(state) = app.try_state::<AppState>() {
+        let audio_capture_service = &state.services.audio_capture_service;
+        if audio_capture_service.is_recording() {
+            let stopped = audio_capture_service
+                .stop_recording(app.clone())
+                .and_then(|recorded| {
+                    if !recorded.has_audio() {
+                        return Ok(None);
+                    }
+                    let duration_secs = recorded.duration_ms as f64 / 1000.0;
+                    recorded.into_wav_bytes().map(|wav_bytes| {
+                        Some(InFlightRecording {
+                            wav_bytes: wav_bytes.into(),
+                            duration_secs,
+                        })
+                    })
+                });
+            match stopped {
+                Ok(recording) => interrupted.extend(recording),
+                Err(error) => warn!(%error, "failed to stop recording during shutdown"),
+            }
+        }
+    }
+
+    if app.try_state::<OfflineQueue>().is_some() {
+        for recording in interrupted {
+            match offline_queue::enqueue_interrupted_recording(
+                app,
+                &recording.wav_bytes,
+                Some(recording.duration_secs),
+            ) {
+                Ok(pending) => info!(
+                    id = %pending.id,
+                    duration_secs = recording.duration_secs,
+                    "interrupted recording saved for next launch"
+                ),
+                Err(error) => error!(%error, "failed to save interrupted recording"),
+            }
+        }
+    }
+
+    flush_stores(app);
+    if let Some(hotkey_service) = app.try_state::<HotkeyService>() {
+        hotkey_service.unregister_all(app);
+    }
+    info!("graceful shutdown finished");
+}
+
+#[cfg(unix)]
+fn spawn_termination_signal_listener(app: AppHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tauri::async_runtime::spawn(async move {
+        let mut terminate = match signal(SignalKind::terminate()) {
+            Ok(terminate) => terminate,
+            Err(error) => {
+                warn!(%error, "failed to listen for SIGTERM");
+                return;
+            }
+        };
+        if terminate.recv().await.is_some() {
+            info!("SIGTERM received; quitting");
+            app.exit(0);
+        }
+    });
+}
+
+// Flush before the previous hook runs so a crash loses at most the write in progress.
+fn install_crash_flush_hook(app: AppHandle) {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        if let Some(history_store) = app.try_state::<HistoryStore>() {
+            history_store.flush_on_crash();
+        }
+        if let Some(stats_store) = app.try_state::<StatsStore>() {
+            stats_store.flush_on_crash();
+        }
+        previous_hook(panic_info);
+    }));
 }
 
 fn get_status_from_state(state: &AppState) -> AppStatus {
@@ -1074,14 +2373,33 @@ fn get_status_from_state(state: &AppState) -> AppStatus {
         })
 }
 
-fn set_status_for_state(app: &AppHandle, state: &AppState, status: AppStatus) {
-    if let Ok(mut notifier) = state.status_notifier.lock() {
-        notifier.set(status);
-    } else {
-        error!("status notifier lock poisoned while setting status");
+fn set_status_for_state(
+    app: &AppHandle,
+    state: &AppState,
+    status: AppStatus,
+    source: StatusSource,
+) {
+    let transition = match state.status_notifier.lock() {
+        Ok(mut notifier) => notifier.set(status, source),
+        Err(_) => {
+            error!("status notifier lock poisoned while setting status");
+            None
+        }
+    };
+
+    publish_status(app, status);
+    if let Some(transition) = transition {
+        schedule_status_transition(app.clone(), transition);
     }
+}
 
+fn publish_status(app: &AppHandle, status: AppStatus) {
     set_overlay_visible_for_status(app, status);
+    app.state::<RecordingAlerts>().on_status(app, status);
+    app.state::<MediaPause>().on_status(app, status);
+    if let Some(message) = accessibility_announcer::status_announcement(status) {
+        accessibility_announcer::announce(app, message);
+    }
 
     if let Err(error) = app.emit(EVENT_STATUS_CHANGED, status) {
         warn!(?status, %error, "failed to emit status changed event");
@@ -1090,9 +2408,128 @@ fn set_status_for_state(app: &AppHandle, state: &AppState, status: AppStatus) {
     log_memory_snapshot(app, &format!("status:{status:?}"));
 }
 
-fn set_status_for_app(app: &AppHandle, status: AppStatus) {
+fn schedule_status_transition(app: AppHandle, transition: ScheduledTransition) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(transition.after).await;
+        let state = app.state::<AppState>();
+        let (from, next) = {
+            let Ok(mut notifier) = state.status_notifier.lock() else {
+                error!("status notifier lock poisoned while applying scheduled status");
+                return;
+            };
+            if !notifier.is_pending(&transition) {
+                return;
+            }
+            let from = notifier.current();
+            info!(
+                ?from,
+                to = ?transition.to,
+                after_ms = transition.after.as_millis(),
+                "applying scheduled status transition"
+            );
+            (from, notifier.set(transition.to, StatusSource::Timeout))
+        };
+
+        if transition.to == AppStatus::Error {
+            terminate_stuck_session(&app, from, transition.after);
+            let secs = transition.after.as_secs();
+            let error = if from == AppStatus::Listening {
+                PipelineError::new(
+                    voice_pipeline::PipelineErrorStage::RecordingRuntime,
+                    format!("Recording stopped after {secs} seconds without finishing"),
+                )
+            } else {
+                PipelineError::new(
+                    voice_pipeline::PipelineErrorStage::Transcription,
+                    format!("Transcription timed out after {secs} seconds"),
+                )
+            };
+            emit_pipeline_error_event(&app, &error);
+        }
+        publish_status(&app, transition.to);
+        if let Some(next) = next {
+            schedule_status_transition(app.clone(), next);
+        }
+    });
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StuckSessionSnapshot {
+    stuck_in: AppStatus,
+    stuck_for_secs: u64,
+    active_session_id: Option<u64>,
+    hotkey: Option<HotkeyStateSnapshot>,
+    realtime_session_active: bool,
+    in_flight_recordings: usize,
+    audio_capture: Option<AudioCaptureDebugSnapshot>,
+    recent_transitions: Vec<StatusTransition>,
+}
+
+impl StuckSessionSnapshot {
+    const RECENT_TRANSITIONS: usize = 5;
+
+    fn capture(app: &AppHandle, stuck_in: AppStatus, stuck_for: Duration) -> Self {
+        let state = app.state::<AppState>();
+        let runtime_state = app.state::<PipelineRuntimeState>();
+        let mut recent_transitions = state
+            .status_notifier
+            .lock()
+            .map(|notifier| notifier.history())
+            .unwrap_or_default();
+        recent_transitions.drain(
+            ..recent_transitions
+                .len()
+                .saturating_sub(Self::RECENT_TRANSITIONS),
+        );
+        Self {
+            stuck_in,
+            stuck_for_secs: stuck_for.as_secs(),
+            active_session_id: runtime_state.active_session_id(),
+            hotkey: app.state::<HotkeyService>().state_snapshot(),
+            realtime_session_active: realtime_session_active(&runtime_state),
+            in_flight_recordings: runtime_state
+                .in_flight_recordings
+                .lock()
+                .map(|recordings| recordings.len())
+                .unwrap_or_default(),
+            audio_capture: state
+                .services
+                .audio_capture_service
+                .debug_snapshot()
+                .ok()
+                .flatten(),
+            recent_transitions,
+        }
+    }
+}
+
+fn terminate_stuck_session(app: &AppHandle, stuck_in: AppStatus, stuck_for: Duration) {
+    let snapshot = StuckSessionSnapshot::capture(app, stuck_in, stuck_for);
+    match serde_json::to_string(&snapshot) {
+        Ok(snapshot) => error!(%snapshot, "pipeline watchdog terminating stuck session"),
+        Err(_) => error!(?snapshot, "pipeline watchdog terminating stuck session"),
+    }
+
+    app.state::<PipelineRuntimeState>().cancel_sessions();
+    app.state::<HotkeyService>().force_stop_recording(app);
+    // Aborting joins the capture thread, which may be the thing that hung.
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        if let Err(error) = state
+            .services
+            .audio_capture_service
+            .abort_recording(app.clone())
+        {
+            warn!(%error, "failed to abort recording of stuck session");
+        }
+    });
+}
+
+fn set_status_for_app(app: &AppHandle, status: AppStatus, source: StatusSource) {
     let state = app.state::<AppState>();
-    set_status_for_state(app, &state, status);
+    set_status_for_state(app, &state, status, source);
 }
 
 fn emit_transcript_event(app: &AppHandle, transcript: &str) {
@@ -1111,9 +2548,29 @@ fn emit_transcription_delta_event(app: &AppHandle, delta: &str) {
 }
 
 fn emit_pipeline_error_event(app: &AppHandle, error: &PipelineError) {
+    if let Some(state) = app.try_state::<AppState>() {
+        if let Ok(mut last_error) = state.last_pipeline_error.lock() {
+            *last_error = Some(LastPipelineError::from_pipeline_error(error));
+        }
+    }
+
+    let code = error.code;
+    let message = code
+        .summary()
+        .map(str::to_string)
+        .unwrap_or_else(|| error.message.clone());
+    accessibility_announcer::announce(app, &format!("Error: {message}"));
     let payload = PipelineErrorEvent {
         stage: error.stage.as_str().to_string(),
-        message: error.message.clone(),
+        code: code.as_str().to_string(),
+        message,
+        detail: error.message.clone(),
+        suggested_action: code
+            .suggested_action()
+            .map(|action| PipelineErrorActionEvent {
+                kind: action.as_str().to_string(),
+                label: action.label().to_string(),
+            }),
     };
 
     if let Err(emit_error) = app.emit(EVENT_PIPELINE_ERROR, payload) {
@@ -1294,29 +2751,104 @@ fn overlay_window_action(status: AppStatus, overlay_exists: bool) -> OverlayWind
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverlayAnchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl OverlayAnchor {
+    fn from_setting(value: &str) -> Self {
+        match value {
+            "top_left" => Self::TopLeft,
+            "top_right" => Self::TopRight,
+            "bottom_left" => Self::BottomLeft,
+            "bottom_center" => Self::BottomCenter,
+            "bottom_right" => Self::BottomRight,
+            _ => Self::TopCenter,
+        }
+    }
+}
+
+// Convert with the target display's own scale factor so the overlay lands correctly on
+// displays with different densities.
 fn overlay_position_from_work_area(
     work_area_position: PhysicalPosition<i32>,
-    work_area_width: u32,
+    work_area_size: PhysicalSize<u32>,
     scale_factor: f64,
+    anchor: OverlayAnchor,
 ) -> LogicalPosition<f64> {
     let work_area_x = f64::from(work_area_position.x) / scale_factor;
     let work_area_y = f64::from(work_area_position.y) / scale_factor;
-    let work_area_width = f64::from(work_area_width) / scale_factor;
+    let work_area_width = f64::from(work_area_size.width) / scale_factor;
+    let work_area_height = f64::from(work_area_size.height) / scale_factor;
+
+    let left = work_area_x + OVERLAY_WINDOW_EDGE_MARGIN;
+    let center = work_area_x + ((work_area_width - OVERLAY_WINDOW_WIDTH) / 2.0).max(0.0);
+    let right = (work_area_x + work_area_width - OVERLAY_WINDOW_WIDTH - OVERLAY_WINDOW_EDGE_MARGIN)
+        .max(left);
+    let top = work_area_y + OVERLAY_WINDOW_EDGE_MARGIN;
+    let bottom =
+        (work_area_y + work_area_height - OVERLAY_WINDOW_HEIGHT - OVERLAY_WINDOW_EDGE_MARGIN)
+            .max(top);
+
+    let (x, y) = match anchor {
+        OverlayAnchor::TopLeft => (left, top),
+        OverlayAnchor::TopCenter => (center, top),
+        OverlayAnchor::TopRight => (right, top),
+        OverlayAnchor::BottomLeft => (left, bottom),
+        OverlayAnchor::BottomCenter => (center, bottom),
+        OverlayAnchor::BottomRight => (right, bottom),
+    };
+    LogicalPosition::new(x, y)
+}
 
-    LogicalPosition::new(
-        work_area_x + ((work_area_width - OVERLAY_WINDOW_WIDTH) / 2.0).max(0.0),
-        work_area_y + OVERLAY_WINDOW_TOP_MARGIN,
-    )
+fn monitor_contains_logical_point(
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+    point: (f64, f64),
+) -> bool {
+    let x = f64::from(position.x) / scale_factor;
+    let y = f64::from(position.y) / scale_factor;
+    let width = f64::from(size.width) / scale_factor;
+    let height = f64::from(size.height) / scale_factor;
+
+    (x..x + width).contains(&point.0) && (y..y + height).contains(&point.1)
 }
 
-fn resolve_overlay_monitor(app: &AppHandle) -> Option<Monitor> {
-    if let Ok(cursor) = app.cursor_position() {
-        if let Ok(Some(cursor_monitor)) = app.monitor_from_point(cursor.x, cursor.y) {
-            return Some(cursor_monitor);
-        }
+fn cursor_monitor(app: &AppHandle) -> Option<Monitor> {
+    let cursor = app.cursor_position().ok()?;
+    app.monitor_from_point(cursor.x, cursor.y).ok().flatten()
+}
+
+fn focused_window_monitor(app: &AppHandle) -> Option<Monitor> {
+    let center = text_insertion_service::focused_window_frame()?.center();
+    app.available_monitors().ok()?.into_iter().find(|monitor| {
+        monitor_contains_logical_point(
+            *monitor.position(),
+            *monitor.size(),
+            monitor.scale_factor(),
+            center,
+        )
+    })
+}
+
+fn resolve_overlay_monitor(app: &AppHandle, display: &str) -> Option<Monitor> {
+    let preferred = if display == OVERLAY_DISPLAY_FOCUSED_WINDOW {
+        focused_window_monitor(app).or_else(|| cursor_monitor(app))
+    } else {
+        cursor_monitor(app).or_else(|| focused_window_monitor(app))
+    };
+    if preferred.is_some() {
+        return preferred;
     }
 
-    if let Some(main_window) = app.get_webview_window("main") {
+    if let Some(main_window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
         if let Ok(Some(main_monitor)) = main_window.current_monitor() {
             return Some(main_monitor);
         }
@@ -1326,15 +2858,25 @@ fn resolve_overlay_monitor(app: &AppHandle) -> Option<Monitor> {
 }
 
 fn position_overlay_window(window: &WebviewWindow, app: &AppHandle) {
-    if let Some(monitor) = resolve_overlay_monitor(app) {
-        let position = overlay_position_from_work_area(
-            monitor.work_area().position,
-            monitor.work_area().size.width,
-            monitor.scale_factor(),
-        );
-        if let Err(error) = window.set_position(position) {
-            warn!(%error, "failed to position recording overlay");
-        }
+    let settings = app.state::<AppState>().services.settings_store.current();
+    let Some(monitor) = resolve_overlay_monitor(app, &settings.overlay_display) else {
+        debug!("no monitor resolved for recording overlay; keeping position");
+        return;
+    };
+
+    let position = overlay_position_from_work_area(
+        monitor.work_area().position,
+        monitor.work_area().size,
+        monitor.scale_factor(),
+        OverlayAnchor::from_setting(&settings.overlay_anchor),
+    );
+    debug!(
+        monitor = monitor.name().map(String::as_str).unwrap_or("unknown"),
+        anchor = %settings.overlay_anchor,
+        "positioning recording overlay"
+    );
+    if let Err(error) = window.set_position(position) {
+        warn!(%error, "failed to position recording overlay");
     }
 }
 
@@ -1423,39 +2965,44 @@ fn set_overlay_visible_for_status(app: &AppHandle, status: AppStatus) {
         app.get_webview_window(OVERLAY_WINDOW_LABEL).is_some(),
     );
 
-    if matches!(action, OverlayWindowAction::CreateAndShow) {
-        setup_recording_overlay_window(app);
-    }
-
     match action {
         OverlayWindowAction::CreateAndShow | OverlayWindowAction::ShowExisting => {
-            let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
-                return;
-            };
-            position_overlay_window(&window, app);
-            if let Err(error) = show_recording_overlay_window(&window) {
-                warn!(%error, "failed to show recording overlay window");
-            }
-        }
-        OverlayWindowAction::HideExisting => {
-            let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
-                return;
-            };
-            if let Err(error) = hide_recording_overlay_window(&window) {
-                warn!(%error, "failed to hide recording overlay window");
-            }
+            show_recording_overlay(app);
         }
+        OverlayWindowAction::HideExisting => hide_recording_overlay(app),
         OverlayWindowAction::Noop => {}
     }
 }
 
+fn show_recording_overlay(app: &AppHandle) {
+    setup_recording_overlay_window(app);
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return;
+    };
+    position_overlay_window(&window, app);
+    if let Err(error) = show_recording_overlay_window(&window) {
+        warn!(%error, "failed to show recording overlay window");
+    }
+}
+
+fn hide_recording_overlay(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) else {
+        return;
+    };
+    if let Err(error) = hide_recording_overlay_window(&window) {
+        warn!(%error, "failed to hide recording overlay window");
+    }
+}
+
 fn register_overlay_audio_forwarder(app: &AppHandle) {
     let overlay_app = app.clone();
     app.listen(AUDIO_LEVEL_EVENT, move |event| {
-        let level = serde_json::from_str::<f32>(event.payload()).unwrap_or_else(|error| {
-            warn!(%error, payload = event.payload(), "invalid audio-level payload");
-            0.0
-        });
+        let level = serde_json::from_str::<AudioLevel>(event.payload())
+            .map(|level| level.peak)
+            .unwrap_or_else(|error| {
+                warn!(%error, payload = event.payload(), "invalid audio level payload");
+                0.0
+            });
         if overlay_app
             .get_webview_window(OVERLAY_WINDOW_LABEL)
             .is_none()
@@ -1483,23 +3030,6 @@ fn parse_audio_stream_error_message(payload: &str) -> String {
         })
 }
 
-fn get_launch_at_login_state(app: &AppHandle) -> Result<bool, String> {
-    app.autolaunch()
-        .is_enabled()
-        .map_err(|error| format!("Failed to get launch-at-login state: {error}"))
-}
-
-fn set_launch_at_login_state(app: &AppHandle, enabled: bool) -> Result<(), String> {
-    let autolaunch = app.autolaunch();
-    let result = if enabled {
-        autolaunch.enable()
-    } else {
-        autolaunch.disable()
-    };
-
-    result.map_err(|error| format!("Failed to set launch-at-login state: {error}"))
-}
-
 fn ensure_microphone_permission_for_recording(state: &AppState) -> Result<(), String> {
     ensure_permission_for_action(
         state.services.permission_service.microphone_permission(),
@@ -1508,6 +3038,23 @@ fn ensure_microphone_permission_for_recording(state: &AppState) -> Result<(), St
     )
 }
 
+// Waits on the main thread on macOS, so it must not be called from it.
+fn refresh_typing_layout(app: &AppHandle, service: &TextInsertionService) {
+    let layout = keyboard_layout::current_layout(app);
+    let paste_key_code = keyboard_layout::key_strokes(app, &['v'])
+        .first()
+        .copied()
+        .flatten()
+        .filter(|key| !key.shift && !key.alt)
+        .map(|key| key.key_code);
+    service.set_typing_layout(TypingLayout {
+        paste_key_code,
+        us_key_positions: layout
+            .as_ref()
+            .is_none_or(keyboard_layout::KeyboardLayout::has_us_key_positions),
+    });
+}
+
 fn ensure_accessibility_permission_for_insertion(state: &AppState) -> Result<(), String> {
     ensure_permission_for_action(
         state.services.permission_service.accessibility_permission(),
@@ -1606,10 +3153,10 @@ fn handle_audio_input_stream_error_with_hooks<
         error!(%error, "failed to abort recording after stream error");
     }
 
-    let pipeline_error = PipelineError {
-        stage: voice_pipeline::PipelineErrorStage::RecordingRuntime,
+    let pipeline_error = PipelineError::new(
+        voice_pipeline::PipelineErrorStage::RecordingRuntime,
         message,
-    };
+    );
     emit_pipeline_error(&pipeline_error);
     set_status(AppStatus::Error);
     schedule_reset();
@@ -1622,7 +3169,7 @@ fn handle_audio_input_stream_error(app: &AppHandle, message: String) {
         message,
         || {
             let runtime_state = app.state::<PipelineRuntimeState>();
-            runtime_state.begin_session();
+            runtime_state.cancel_sessions();
         },
         || {
             let hotkey_service = app.state::<HotkeyService>();
@@ -1639,18 +3186,29 @@ fn handle_audio_input_stream_error(app: &AppHandle, message: String) {
         |error| emit_pipeline_error_event(app, error),
         |status| {
             let state = app.state::<AppState>();
-            set_status_for_state(app, &state, status);
+            set_status_for_state(app, &state, status, StatusSource::AudioStream);
         },
         move || {
+            let delay = reset_app
+                .state::<AppState>()
+                .status_notifier
+                .lock()
+                .map(|notifier| notifier.timeouts().error_reset)
+                .unwrap_or(status_notifier::DEFAULT_ERROR_RESET_DELAY);
             tauri::async_runtime::spawn(async move {
-                tokio::time::sleep(Duration::from_millis(AUDIO_STREAM_ERROR_RESET_DELAY_MS)).await;
+                tokio::time::sleep(delay).await;
                 let state = reset_app.state::<AppState>();
                 if get_status_from_state(&state) == AppStatus::Error {
                     info!(
-                        delay_ms = AUDIO_STREAM_ERROR_RESET_DELAY_MS,
+                        delay_ms = delay.as_millis(),
                         "resetting status to idle after stream error"
                     );
-                    set_status_for_state(&reset_app, &state, AppStatus::Idle);
+                    set_status_for_state(
+                        &reset_app,
+                        &state,
+                        AppStatus::Idle,
+                        StatusSource::AudioStream,
+                    );
                 }
             });
         },
@@ -1661,14 +3219,15 @@ fn spawn_pipeline_stage_error_reset<D>(
     pipeline: VoicePipeline,
     delegate: D,
     stage: voice_pipeline::PipelineErrorStage,
-    message: String,
+    error: impl Into<StageError>,
 ) -> tauri::async_runtime::JoinHandle<()>
 where
     D: VoicePipelineDelegate + Send + Sync + 'static,
 {
-    error!(stage = stage.as_str(), message = %message, "scheduling stage error reset");
+    let error = error.into();
+    error!(stage = stage.as_str(), message = %error, "scheduling stage error reset");
     tauri::async_runtime::spawn(async move {
-        pipeline.handle_stage_error(&delegate, stage, message).await;
+        pipeline.handle_stage_error(&delegate, stage, error).await;
     })
 }
 
@@ -1682,6 +3241,55 @@ fn resolve_or_begin_pipeline_session(runtime_state: &PipelineRuntimeState) -> u6
         .unwrap_or_else(|| runtime_state.begin_session())
 }
 
+async fn finish_pipeline_session(
+    runtime_state: &PipelineRuntimeState,
+    delegate: AppPipelineDelegate,
+) {
+    let pipeline = pipeline_for_settings(&delegate.current_settings());
+    let Some(recorded_audio) = pipeline.finish_recording(&delegate).await else {
+        return;
+    };
+    let delegate = delegate.detach_realtime_session();
+    let processing = async move {
+        pipeline.process_recording(&delegate, recorded_audio).await;
+    };
+
+    if runtime_state.is_queued_mode() {
+        debug!("queueing recorded dictation for ordered processing");
+        runtime_state.enqueue_processing(processing);
+    } else {
+        debug!("processing recorded dictation in the background");
+        tauri::async_runtime::spawn(processing);
+    }
+}
+
+fn retry_recording(app: &AppHandle, recorded_audio: RecordedAudio) {
+    let app = app.clone();
+    let runtime_state = app.state::<PipelineRuntimeState>().inner().clone();
+    tauri::async_runtime::spawn(async move {
+        let session_id = {
+            let _guard = runtime_state.execution_lock.lock().await;
+            runtime_state.begin_session()
+        };
+        info!(session_id, "retrying failed recording");
+        let delegate = AppPipelineDelegate::for_session(app, session_id);
+        let pipeline = pipeline_for_settings(&delegate.current_settings());
+        pipeline.process_recording(&delegate, recorded_audio).await;
+    });
+}
+
+fn register_transcription_queue_forwarder(app: &AppHandle) {
+    let emitter = app.clone();
+    app.state::<AppState>()
+        .services
+        .transcription_orchestrator
+        .set_queue_observer(Arc::new(move |position: QueuePosition| {
+            if let Err(error) = emitter.emit(EVENT_TRANSCRIPTION_QUEUE, position) {
+                warn!(%error, "failed to emit transcription queue event");
+            }
+        }));
+}
+
 fn register_pipeline_handlers(app: &AppHandle) {
     info!("registering pipeline event handlers");
     let start_app = app.clone();
@@ -1690,15 +3298,33 @@ fn register_pipeline_handlers(app: &AppHandle) {
             event_id = event.id(),
             "received recording started hotkey event"
         );
+        let triggered_at = Instant::now();
+        let hotkey_session_id = hotkey_service::event_session_id(event.payload());
         let app = start_app.clone();
+        error_recovery::dismiss(&app);
+        let settings = app.state::<AppState>().services.settings_store.current();
+        if app_blocklist::ensure_allowed(&app, &settings, BlockedAction::Recording).is_err() {
+            if let Some(hotkey_session_id) = hotkey_session_id {
+                app.state::<HotkeyService>().acknowledge_transition(
+                    hotkey_session_id,
+                    RecordingTransition::Started,
+                    false,
+                );
+            }
+            return;
+        }
+        feature_usage::record_if_enabled(&app, FeatureCategory::Action, "start_recording");
         let runtime_state = app.state::<PipelineRuntimeState>().inner().clone();
         tauri::async_runtime::spawn(async move {
             let _guard = runtime_state.execution_lock.lock().await;
             let session_id = runtime_state.begin_session();
-            let delegate = AppPipelineDelegate::for_session(app.clone(), session_id);
+            let delegate = AppPipelineDelegate::for_session(app.clone(), session_id)
+                .with_hotkey_session(hotkey_session_id)
+                .with_triggered_at(triggered_at);
             VoicePipeline::default()
                 .handle_hotkey_started(&delegate)
                 .await;
+            arm_recording_limit(&app, session_id, hotkey_session_id);
 
             handle_pending_stop_transition(&app, &delegate).await;
         });
@@ -1719,25 +3345,34 @@ fn register_pipeline_handlers(app: &AppHandle) {
             drop(hotkey_service);
 
             match stop_decision {
-                StopProcessingDecision::Process => {
+                StopProcessingDecision::Process(hotkey_session_id) => {
                     let Some(session_id) = active_pipeline_session_id(&runtime_state) else {
                         warn!(
                             "received stop event with no active pipeline session; acknowledging stop as failed"
                         );
                         let hotkey_service = app.state::<HotkeyService>();
-                        hotkey_service
-                            .acknowledge_transition(RecordingTransition::Stopped, false);
+                        hotkey_service.acknowledge_transition(
+                            hotkey_session_id,
+                            RecordingTransition::Stopped,
+                            false,
+                        );
                         return;
                     };
-                    let delegate = AppPipelineDelegate::for_session(app.clone(), session_id);
-                    VoicePipeline::default()
-                        .handle_hotkey_stopped(&delegate)
-                        .await;
+                    let copy_only = app.state::<HotkeyService>().take_long_press_request();
+                    record_stop_action(&app, copy_only);
+                    let delegate = AppPipelineDelegate::for_session(app.clone(), session_id)
+                        .with_hotkey_session(Some(hotkey_session_id))
+                        .with_copy_only(copy_only);
+                    finish_pipeline_session(&runtime_state, delegate).await;
                 }
-                StopProcessingDecision::AcknowledgeOnly => {
+                StopProcessingDecision::AcknowledgeOnly(hotkey_session_id) => {
                     warn!("received stop event while hotkey service was not recording");
                     let hotkey_service = app.state::<HotkeyService>();
-                    hotkey_service.acknowledge_transition(RecordingTransition::Stopped, false);
+                    hotkey_service.acknowledge_transition(
+                        hotkey_session_id,
+                        RecordingTransition::Stopped,
+                        false,
+                    );
                 }
                 StopProcessingDecision::DeferUntilStarted => {
                     debug!("deferring stop processing until start transition is acknowledged");
@@ -1761,6 +3396,15 @@ fn register_pipeline_handlers(app: &AppHandle) {
     });
 }
 
+fn record_stop_action(app: &AppHandle, copy_only: bool) {
+    let action = if copy_only {
+        "stop_recording_copy_only"
+    } else {
+        "stop_recording"
+    };
+    feature_usage::record_if_enabled(app, FeatureCategory::Action, action);
+}
+
 async fn handle_pending_stop_transition(app: &AppHandle, delegate: &AppPipelineDelegate) {
     let stop_decision = {
         let hotkey_service = app.state::<HotkeyService>();
@@ -1768,14 +3412,23 @@ async fn handle_pending_stop_transition(app: &AppHandle, delegate: &AppPipelineD
     };
 
     match stop_decision {
-        StopProcessingDecision::Process => {
-            VoicePipeline::default()
-                .handle_hotkey_stopped(delegate)
-                .await;
+        StopProcessingDecision::Process(hotkey_session_id) => {
+            let runtime_state = app.state::<PipelineRuntimeState>().inner().clone();
+            let copy_only = app.state::<HotkeyService>().take_long_press_request();
+            record_stop_action(app, copy_only);
+            let delegate = delegate
+                .clone()
+                .with_hotkey_session(Some(hotkey_session_id))
+                .with_copy_only(copy_only);
+            finish_pipeline_session(&runtime_state, delegate).await;
         }
-        StopProcessingDecision::AcknowledgeOnly => {
+        StopProcessingDecision::AcknowledgeOnly(hotkey_session_id) => {
             let hotkey_service = app.state::<HotkeyService>();
-            hotkey_service.acknowledge_transition(RecordingTransition::Stopped, false);
+            hotkey_service.acknowledge_transition(
+                hotkey_session_id,
+                RecordingTransition::Stopped,
+                false,
+            );
         }
         StopProcessingDecision::DeferUntilStarted | StopProcessingDecision::Ignore => {}
     }
@@ -1791,13 +3444,36 @@ fn get_status(state: tauri::State<'_, AppState>) -> AppStatus {
 #[tauri::command]
 fn set_status(app: AppHandle, status: AppStatus, state: tauri::State<'_, AppState>) {
     info!(?status, "status set requested");
-    set_status_for_state(&app, &state, status);
+    set_status_for_state(&app, &state, status, StatusSource::Command("set_status"));
+}
+
+#[tauri::command]
+fn get_status_history(state: tauri::State<'_, AppState>) -> Vec<StatusTransition> {
+    state
+        .status_notifier
+        .lock()
+        .map(|notifier| notifier.history())
+        .unwrap_or_else(|_| {
+            error!("status notifier lock poisoned while reading status history");
+            Vec::new()
+        })
 }
 
 #[tauri::command]
 fn get_settings(state: tauri::State<'_, AppState>) -> VoiceSettings {
     let settings = state.services.settings_store.current();
     debug!("settings requested");
+    with_proxy_password_flag(settings, &state.services.api_key_store)
+}
+
+fn with_proxy_password_flag(
+    mut settings: VoiceSettings,
+    api_key_store: &ApiKeyStore,
+) -> VoiceSettings {
+    settings.has_proxy_password = api_key_store.has_proxy_password().unwrap_or_else(|error| {
+        warn!(%error, "failed to read proxy password presence");
+        false
+    });
     settings
 }
 
@@ -1821,11 +3497,11 @@ fn complete_onboarding(app: AppHandle, state: tauri::State<'_, AppState>) -> Res
 #[tauri::command]
 fn update_settings(
     app: AppHandle,
-    update: VoiceSettingsUpdate,
+    update: serde_json::Map<String, serde_json::Value>,
     state: tauri::State<'_, AppState>,
-) -> Result<VoiceSettings, String> {
-    info!("settings update requested");
-    let updated = state.services.settings_store.update(&app, update);
+) -> Result<VoiceSettings, SettingsValidationError> {
+    info!(field_count = update.len(), "settings update requested");
+    let updated = state.services.settings_store.update_patch(&app, update);
     match &updated {
         Ok(settings) => {
             info!(
@@ -1833,12 +3509,13 @@ fn update_settings(
                 auto_insert = settings.auto_insert,
                 "settings updated"
             );
+            sync_services_with_settings(&app, settings);
         }
         Err(error) => {
-            error!(%error, "settings update failed");
+            error!(error = %error.message, "settings update failed");
         }
     }
-    updated
+    updated.map(|settings| with_proxy_password_flag(settings, &state.services.api_key_store))
 }
 
 #[tauri::command]
@@ -1850,7 +3527,7 @@ fn apply_settings(
 ) -> Result<VoiceSettings, String> {
     let previous_hotkey = hotkey_service.current_config();
     let requested_hotkey = resolve_hotkey_config_for_settings(&update, &previous_hotkey)?;
-    let previous_launch_at_login = get_launch_at_login_state(&app)?;
+    let previous_launch_at_login = startup_manager::is_launch_at_login_enabled(&app)?;
     let requested_launch_at_login = update.launch_at_login.unwrap_or(previous_launch_at_login);
 
     apply_settings_transaction_with_hooks(
@@ -1859,17 +3536,239 @@ fn apply_settings(
         requested_hotkey,
         previous_launch_at_login,
         requested_launch_at_login,
-        |config| hotkey_service.apply_config(&app, config),
-        |enabled| set_launch_at_login_state(&app, enabled),
+        |config| hotkey_service.apply_user_config(&app, config),
+        |enabled| startup_manager::set_launch_at_login(&app, enabled),
         |persist_update| state.services.settings_store.update(&app, persist_update),
-        |enabled| set_launch_at_login_state(&app, enabled),
+        |enabled| startup_manager::set_launch_at_login(&app, enabled),
         |config| hotkey_service.apply_config(&app, config),
     )
+    .inspect(|settings| sync_services_with_settings(&app, settings))
+    .map(|settings| with_proxy_password_flag(settings, &state.services.api_key_store))
+}
+
+fn network_config_for_provider(
+    settings: &VoiceSettings,
+    api_key_store: &ApiKeyStore,
+    provider: &str,
+) -> NetworkConfig {
+    let connection = settings.provider_connection(provider);
+    NetworkConfig {
+        proxy: settings.proxy_url.as_ref().map(|url| ProxyConfig {
+            url: url.clone(),
+            username: settings.proxy_username.clone(),
+            password: api_key_store.get_proxy_password().unwrap_or_else(|error| {
+                warn!(%error, "failed to read proxy password");
+                None
+            }),
+        }),
+        openai_organization: settings.openai_organization.clone(),
+        openai_project: settings.openai_project.clone(),
+        ca_bundle_path: connection
+            .and_then(|connection| connection.ca_bundle_path.as_deref())
+            .map(expand_home_path),
+        bypass_proxy: connection.is_some_and(|connection| connection.bypass_proxy),
+    }
+}
+
+fn sync_transcription_network_with_settings(app: &AppHandle, settings: &VoiceSettings) {
+    let state = app.state::<AppState>();
+    let api_key_store = &state.services.api_key_store;
+    let orchestrator = &state.services.transcription_orchestrator;
+    if let Err(error) = orchestrator.configure_network(&network_config_for_provider(
+        settings,
+        api_key_store,
+        orchestrator.provider_name(),
+    )) {
+        warn!(%error, "failed to apply network settings to transcription client");
+    }
+    let chatgpt_provider = &state.services.chatgpt_transcription_provider;
+    if let Err(error) = chatgpt_provider.configure_network(&network_config_for_provider(
+        settings,
+        api_key_store,
+        chatgpt_provider.name(),
+    )) {
+        warn!(%error, "failed to apply network settings to ChatGPT transcription client");
+    }
+    state
+        .services
+        .realtime_transcription_client
+        .configure_network(&network_config_for_provider(
+            settings,
+            api_key_store,
+            "openai",
+        ));
+}
+
+fn sync_services_with_settings(app: &AppHandle, settings: &VoiceSettings) {
+    if let Ok(mut notifier) = app.state::<AppState>().status_notifier.lock() {
+        notifier.set_timeouts(StatusTimeouts {
+            error_reset: Duration::from_millis(settings.status_error_reset_ms),
+            transcribing: Duration::from_secs(settings.transcribing_timeout_secs),
+            listening: Duration::from_secs(settings.listening_timeout_secs),
+        });
+    }
+
+    app.state::<PipelineRuntimeState>()
+        .set_queued_mode(settings.queued_dictation_enabled);
+    let state = app.state::<AppState>();
+    let text_insertion_service = &state.services.text_insertion_service;
+    text_insertion_service.configure_typing(TypingConfig::from_settings(settings));
+    text_insertion_service.set_conceal_clipboard_writes(settings.conceal_clipboard_writes);
+
+    let hotkey_service = app.state::<HotkeyService>();
+    hotkey_service.sync_double_tap(
+        app,
+        settings.double_tap_modifier.as_deref(),
+        settings.double_tap_interval_ms,
+    );
+    hotkey_service.sync_suspended_apps(app, &settings.hotkey_suspended_apps);
+    hotkey_service
+        .set_long_press_threshold(settings.long_press_threshold_ms.map(Duration::from_millis));
+
+    app.state::<TriggerService>()
+        .sync_with_settings(app, settings);
+
+    sync_microphone_standby_with_settings(app, settings);
+
+    app.state::<AppState>()
+        .services
+        .transcription_orchestrator
+        .configure_cache(TranscriptCacheConfig {
+            capacity: settings.transcript_cache_size as usize,
+            ttl: Duration::from_secs(settings.transcript_cache_ttl_secs),
+        });
+    app.state::<AppState>()
+        .services
+        .transcription_orchestrator
+        .configure_limits(RequestLimitConfig {
+            max_concurrent: settings.max_concurrent_transcriptions as usize,
+            requests_per_minute: settings.transcription_requests_per_minute as usize,
+        });
+    app.state::<AppState>()
+        .services
+        .transcription_orchestrator
+        .configure_model(settings.openai_transcription_model.as_deref());
+    sync_transcription_network_with_settings(app, settings);
+    app.state::<ProviderSwitchShortcut>()
+        .sync(app, settings.provider_switch_shortcut.as_deref());
+    app.state::<CommandPaletteShortcut>()
+        .sync(app, settings.command_palette_shortcut.as_deref());
+    app.state::<TemplateShortcuts>()
+        .sync(app, &settings.prompt_templates);
+    app.state::<PrivacyModeShortcut>()
+        .sync(app, settings.privacy_mode_shortcut.as_deref());
+    if !settings.context_carryover_enabled {
+        app.state::<ContextCarryover>().clear();
+    }
+    if let Err(error) = app.state::<StatsStore>().set_streak_rules(StreakRules {
+        min_words: settings.streak_min_words,
+        workdays_only: settings.streak_workdays_only,
+        freezes: settings.streak_freezes,
+    }) {
+        warn!(%error, "failed to apply streak rules");
+    }
+
+    let history_sync_folder = settings
+        .history_sync_folder
+        .as_deref()
+        .map(expand_home_path);
+    if let Err(error) = app
+        .state::<HistoryStore>()
+        .configure_sync(history_sync_folder.as_deref())
+    {
+        warn!(%error, "failed to apply history sync settings");
+    }
+
+    let http_api = app.state::<HttpApiService>();
+    if let Err(error) =
+        http_api.sync_with_settings(app, settings.http_api_enabled, settings.http_api_port)
+    {
+        warn!(%error, port = settings.http_api_port, "failed to apply http api settings");
+    }
+}
+
+fn apply_restored_settings(app: &AppHandle, settings: &VoiceSettings) {
+    let hotkey_service = app.state::<HotkeyService>();
+    if let Err(error) = apply_hotkey_from_settings_with_fallback(
+        settings,
+        |config| hotkey_service.apply_config(app, config).map(|_| ()),
+        || hotkey_service.register_default_shortcut(app),
+    ) {
+        warn!(%error, "failed to apply restored hotkey configuration");
+    }
+    if let Err(error) = startup_manager::set_launch_at_login(app, settings.launch_at_login) {
+        warn!(%error, "failed to apply restored launch-at-login preference");
+    }
+    sync_services_with_settings(app, settings);
+}
+
+fn expand_home_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(relative), Some(home)) => PathBuf::from(home).join(relative),
+        _ => PathBuf::from(path),
+    }
+}
+
+// Only arm the stream once microphone access is granted so these settings never trigger the
+// permission prompt on their own.
+fn sync_microphone_standby_with_settings(app: &AppHandle, settings: &VoiceSettings) {
+    let state = app.state::<AppState>();
+    let microphone_granted =
+        state.services.permission_service.microphone_permission() == PermissionState::Granted;
+    let wake_word_listener =
+        app.state::<WakeWordService>()
+            .sync_with_settings(app, settings, microphone_granted);
+    let mode = if !microphone_granted {
+        None
+    } else if settings.pre_roll_enabled {
+        Some(StandbyMode::PreRoll(Duration::from_millis(
+            settings.pre_roll_ms,
+        )))
+    } else if wake_word_listener.is_some() {
+        Some(StandbyMode::PreRoll(Duration::ZERO))
+    } else if settings.prewarm_microphone {
+        Some(StandbyMode::Paused)
+    } else {
+        None
+    };
+    let input_profiles = settings
+        .microphone_profiles
+        .iter()
+        .map(|profile| {
+            (
+                profile.device_id.clone(),
+                InputProfile {
+                    gain_db: f32::from(profile.gain_db),
+                    noise_suppression: f32::from(profile.noise_suppression) / 100.0,
+                    input_channel: profile.input_channel.map(usize::from),
+                },
+            )
+        })
+        .collect::<InputProfiles>();
+    if let Err(error) = state
+        .services
+        .audio_capture_service
+        .configure_input_profiles(app, input_profiles)
+    {
+        warn!(%error, "failed to apply microphone input profiles");
+    }
+    state
+        .services
+        .audio_capture_service
+        .set_prefer_built_in_microphone(app, settings.prefer_built_in_microphone);
+    if let Err(error) = state.services.audio_capture_service.configure_standby(
+        app,
+        settings.microphone_id.as_deref(),
+        mode,
+        wake_word_listener,
+    ) {
+        warn!(%error, "failed to apply microphone standby settings");
+    }
 }
 
 #[tauri::command]
 fn get_launch_at_login(app: AppHandle) -> Result<bool, String> {
-    get_launch_at_login_state(&app)
+    startup_manager::is_launch_at_login_enabled(&app)
 }
 
 #[tauri::command]
@@ -1878,8 +3777,8 @@ fn set_launch_at_login(
     enabled: bool,
     state: tauri::State<'_, AppState>,
 ) -> Result<bool, String> {
-    let previous = get_launch_at_login_state(&app)?;
-    set_launch_at_login_state(&app, enabled)?;
+    let previous = startup_manager::is_launch_at_login_enabled(&app)?;
+    startup_manager::set_launch_at_login(&app, enabled)?;
 
     if let Err(error) = state.services.settings_store.update(
         &app,
@@ -1888,7 +3787,7 @@ fn set_launch_at_login(
             ..VoiceSettingsUpdate::default()
         },
     ) {
-        if let Err(rollback_error) = set_launch_at_login_state(&app, previous) {
+        if let Err(rollback_error) = startup_manager::set_launch_at_login(&app, previous) {
             return Err(format!(
                 "Failed to persist launch-at-login setting: {error}. Failed to roll back launch-at-login state: {rollback_error}"
             ));
@@ -2038,6 +3937,34 @@ fn delete_api_key(provider: String, state: tauri::State<'_, AppState>) -> Result
     result
 }
 
+#[tauri::command]
+fn set_proxy_password(
+    app: AppHandle,
+    password: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    info!("proxy password set requested");
+    state
+        .services
+        .api_key_store
+        .set_proxy_password(password.as_str())
+        .inspect_err(|error| error!(%error, "proxy password set failed"))?;
+    sync_transcription_network_with_settings(&app, &state.services.settings_store.current());
+    Ok(())
+}
+
+#[tauri::command]
+fn delete_proxy_password(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    info!("proxy password delete requested");
+    state
+        .services
+        .api_key_store
+        .delete_proxy_password()
+        .inspect_err(|error| error!(%error, "proxy password delete failed"))?;
+    sync_transcription_network_with_settings(&app, &state.services.settings_store.current());
+    Ok(())
+}
+
 #[tauri::command]
 fn list_microphones(state: tauri::State<'_, AppState>) -> Result<Vec<MicrophoneInfo>, String> {
     let result = state.services.audio_capture_service.list_microphones();
@@ -2089,14 +4016,19 @@ fn start_recording(
     );
     ensure_microphone_permission_for_recording(&state)?;
 
-    let result = state.services.audio_capture_service.start_recording(
-        app.clone(),
-        microphone_id.as_deref(),
-        None,
-    );
+    let result = state
+        .services
+        .audio_capture_service
+        .start_recording(app.clone(), microphone_id.as_deref(), None)
+        .map(|_| ());
 
     if result.is_ok() {
-        set_status_for_state(&app, &state, AppStatus::Listening);
+        set_status_for_state(
+            &app,
+            &state,
+            AppStatus::Listening,
+            StatusSource::Command("start_recording"),
+        );
         info!("manual recording started");
     } else if let Err(error) = &result {
         error!(%error, "manual recording start failed");
@@ -2121,7 +4053,12 @@ fn stop_recording(
         })?;
     recorded.ensure_wav_bytes()?;
 
-    set_status_for_state(&app, &state, AppStatus::Idle);
+    set_status_for_state(
+        &app,
+        &state,
+        AppStatus::Idle,
+        StatusSource::Command("stop_recording"),
+    );
     info!(
         duration_ms = recorded.duration_ms,
         sample_rate_hz = recorded.sample_rate_hz,
@@ -2155,21 +4092,22 @@ async fn complete_recording(
     }
 
     let session_id = resolve_or_begin_pipeline_session(&runtime_state);
-    let delegate = AppPipelineDelegate::for_session(app, session_id);
-    VoicePipeline::default()
-        .handle_hotkey_stopped(&delegate)
-        .await;
-    info!(session_id, "recording completion pipeline finished");
+    let hotkey_session_id = app.state::<HotkeyService>().recording_session_id();
+    let delegate =
+        AppPipelineDelegate::for_session(app, session_id).with_hotkey_session(hotkey_session_id);
+    finish_pipeline_session(&runtime_state, delegate).await;
+    info!(session_id, "recording completion handed off for processing");
     Ok(())
 }
 
 #[tauri::command]
 fn cancel_recording(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<(), String> {
     info!("recording/transcription cancel requested");
+    feature_usage::record_if_enabled(&app, FeatureCategory::Action, "cancel_recording");
     let cancel_result = cancel_recording_with_hooks(
         || {
             let runtime_state = app.state::<PipelineRuntimeState>();
-            runtime_state.begin_session();
+            runtime_state.cancel_sessions();
         },
         || {
             let hotkey_service = app.state::<HotkeyService>();
@@ -2181,7 +4119,14 @@ fn cancel_recording(app: AppHandle, state: tauri::State<'_, AppState>) -> Result
                 .audio_capture_service
                 .abort_recording(app.clone())
         },
-        |status| set_status_for_state(&app, &state, status),
+        |status| {
+            set_status_for_state(
+                &app,
+                &state,
+                status,
+                StatusSource::Command("cancel_recording"),
+            )
+        },
     );
 
     match cancel_result {
@@ -2199,11 +4144,6 @@ fn cancel_recording(app: AppHandle, state: tauri::State<'_, AppState>) -> Result
     }
 }
 
-#[tauri::command]
-fn get_audio_level(state: tauri::State<'_, AppState>) -> f32 {
-    state.services.audio_capture_service.get_audio_level()
-}
-
 #[tauri::command]
 fn insert_text(text: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
     info!(
@@ -2226,6 +4166,32 @@ fn copy_to_clipboard(text: String, state: tauri::State<'_, AppState>) -> Result<
         .copy_to_clipboard(&text)
 }
 
+#[tauri::command]
+async fn preview_insertion(app: AppHandle, text: String) -> Result<String, String> {
+    debug!(chars = text.chars().count(), "insertion preview requested");
+    let delegate = AppPipelineDelegate::new(app);
+    pipeline_for_settings(&delegate.current_settings())
+        .preview_text(&delegate, text)
+        .await
+}
+
+#[tauri::command]
+async fn test_keyboard_layout(
+    app: AppHandle,
+    sample: Option<String>,
+) -> keyboard_layout::KeyboardLayoutReport {
+    let sample = sample
+        .filter(|sample| !sample.trim().is_empty())
+        .unwrap_or_else(|| keyboard_layout::DEFAULT_LAYOUT_SAMPLE.to_string());
+    let report = keyboard_layout::layout_report(&app, &sample);
+    info!(
+        layout = ?report.layout.as_ref().map(|layout| &layout.id),
+        unmapped = report.keys.iter().filter(|key| key.key.is_none()).count(),
+        "keyboard layout checked"
+    );
+    report
+}
+
 #[tauri::command]
 async fn transcribe_audio(
     app: AppHandle,
@@ -2237,7 +4203,12 @@ async fn transcribe_audio(
         audio_bytes = audio_bytes.len(),
         "command transcription requested"
     );
-    set_status_for_state(&app, &state, AppStatus::Transcribing);
+    set_status_for_state(
+        &app,
+        &state,
+        AppStatus::Transcribing,
+        StatusSource::Command("transcribe_audio"),
+    );
     let app_for_delta = app.clone();
     let mut request_options = options.unwrap_or_default();
     let has_explicit_prompt = request_options
@@ -2247,10 +4218,7 @@ async fn transcribe_audio(
         .unwrap_or(false);
     if !has_explicit_prompt {
         let settings = state.services.settings_store.current();
-        request_options.prompt = resolve_transcription_prompt(
-            &settings.transcription_style,
-            &settings.custom_transcription_prompt,
-        );
+        request_options.prompt = resolve_transcription_prompt_for_settings(&settings);
     }
     request_options.on_delta = Some(Arc::new(move |delta| {
         emit_transcription_delta_event(&app_for_delta, &delta);
@@ -2260,10 +4228,14 @@ async fn transcribe_audio(
     let chatgpt_provider = state.services.chatgpt_transcription_provider.clone();
 
     let result = match auth_method {
-        AuthMethod::ApiKey => orchestrator.transcribe(audio_bytes, request_options).await,
+        AuthMethod::ApiKey => {
+            orchestrator
+                .transcribe(audio_bytes.into(), request_options)
+                .await
+        }
         AuthMethod::ChatgptOauth => {
             chatgpt_provider
-                .transcribe(audio_bytes, request_options)
+                .transcribe(audio_bytes.into(), request_options)
                 .await
         }
         AuthMethod::None => Err(transcription::TranscriptionError::Provider(
@@ -2274,7 +4246,12 @@ async fn transcribe_audio(
 
     match result {
         Ok(transcription) => {
-            set_status_for_state(&app, &state, AppStatus::Idle);
+            set_status_for_state(
+                &app,
+                &state,
+                AppStatus::Idle,
+                StatusSource::Command("transcribe_audio"),
+            );
             info!(
                 transcript_chars = transcription.text.chars().count(),
                 language = ?transcription.language,
@@ -2291,7 +4268,7 @@ async fn transcribe_audio(
                 VoicePipeline::default(),
                 delegate,
                 voice_pipeline::PipelineErrorStage::Transcription,
-                message.clone(),
+                error,
             );
 
             Err(message)
@@ -2304,15 +4281,68 @@ fn list_history(
     history_store: tauri::State<'_, HistoryStore>,
     limit: Option<usize>,
     offset: Option<usize>,
+    sort: Option<HistorySort>,
 ) -> Result<Vec<HistoryEntry>, String> {
     let page_limit = limit.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE);
     let page_offset = offset.unwrap_or(0);
+    let sort = sort.unwrap_or_default();
     debug!(
         limit = page_limit,
         offset = page_offset,
+        ?sort,
         "history list requested"
     );
-    history_store.list_entries(page_limit, page_offset)
+    history_store.list_entries_sorted(page_limit, page_offset, sort)
+}
+
+#[tauri::command]
+fn list_history_page(
+    history_store: tauri::State<'_, HistoryStore>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<HistoryPage, String> {
+    let page_limit = limit.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE);
+    debug!(limit = page_limit, "history page requested");
+    history_store.list_entries_after(cursor.as_deref(), page_limit)
+}
+
+#[tauri::command]
+fn query_history(
+    history_store: tauri::State<'_, HistoryStore>,
+    filter: HistoryFilter,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Vec<HistoryEntry>, String> {
+    let page_limit = limit.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE);
+    let page_offset = offset.unwrap_or(0);
+    debug!(
+        limit = page_limit,
+        offset = page_offset,
+        sort = ?filter.sort,
+        "history query requested"
+    );
+    history_store.query_entries(&filter, page_limit, page_offset)
+}
+
+#[tauri::command]
+fn list_entry_previews(
+    history_store: tauri::State<'_, HistoryStore>,
+    filter: Option<HistoryFilter>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    max_chars: Option<usize>,
+) -> Result<Vec<HistoryEntryPreview>, String> {
+    let filter = filter.unwrap_or_default();
+    let page_limit = limit.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE);
+    let page_offset = offset.unwrap_or(0);
+    let max_chars = max_chars.unwrap_or(DEFAULT_PREVIEW_CHARS);
+    debug!(
+        limit = page_limit,
+        offset = page_offset,
+        max_chars,
+        "history previews requested"
+    );
+    history_store.list_entry_previews(&filter, page_limit, page_offset, max_chars)
 }
 
 #[tauri::command]
@@ -2324,13 +4354,95 @@ fn get_history_entry(
     history_store.get_entry(&id)
 }
 
-#[tauri::command]
-fn delete_history_entry(
-    history_store: tauri::State<'_, HistoryStore>,
-    id: String,
-) -> Result<bool, String> {
-    info!(id = %id, "history delete requested");
-    history_store.delete_entry(&id)
+#[tauri::command]
+fn get_history_waveform(
+    history_store: tauri::State<'_, HistoryStore>,
+    id: String,
+) -> Result<Vec<u8>, String> {
+    history_store
+        .get_entry(&id)?
+        .map(|entry| entry.waveform)
+        .ok_or_else(|| format!("History entry `{id}` was not found"))
+}
+
+#[tauri::command]
+fn delete_history_entry(
+    history_store: tauri::State<'_, HistoryStore>,
+    id: String,
+) -> Result<bool, String> {
+    info!(id = %id, "history delete requested");
+    history_store.delete_entry(&id)
+}
+
+#[tauri::command]
+fn rename_history_speaker(
+    history_store: tauri::State<'_, HistoryStore>,
+    id: String,
+    from: String,
+    to: String,
+) -> Result<HistoryEntry, String> {
+    info!(id = %id, "history speaker rename requested");
+    history_store
+        .rename_speaker(&id, &from, &to)?
+        .ok_or_else(|| format!("History entry `{id}` was not found"))
+}
+
+#[tauri::command]
+async fn reinsert_entry(app: AppHandle, id: String) -> Result<(), String> {
+    info!(id = %id, "history reinsertion requested");
+    insert_history_entry(app, id, None).await
+}
+
+#[tauri::command]
+async fn insert_entry_into_app(
+    app: AppHandle,
+    id: String,
+    app_identifier: String,
+) -> Result<(), String> {
+    info!(id = %id, app_identifier = %app_identifier, "history insertion into app requested");
+    insert_history_entry(app, id, Some(app_identifier)).await
+}
+
+async fn insert_history_entry(
+    app: AppHandle,
+    id: String,
+    app_identifier: Option<String>,
+) -> Result<(), String> {
+    let text = history_entry_text(&app.state::<HistoryStore>(), &id)?;
+    ensure_accessibility_permission_for_insertion(&app.state::<AppState>())?;
+    // These commands come from Buzz's own windows, so hand focus back before inserting.
+    yield_focus_for_insertion(&app);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let insertion = &state.services.text_insertion_service;
+        match app_identifier.as_deref() {
+            Some(identifier) => insertion.activate_application(identifier)?,
+            None => thread::sleep(Duration::from_millis(HISTORY_REINSERT_FOCUS_DELAY_MS)),
+        }
+        insertion.insert_text(&text)
+    })
+    .await
+    .map_err(|error| format!("Failed to run history insertion: {error}"))?
+}
+
+fn history_entry_text(history_store: &HistoryStore, id: &str) -> Result<String, String> {
+    history_store
+        .get_entry(id)?
+        .map(|entry| entry.text)
+        .ok_or_else(|| format!("History entry `{id}` was not found"))
+}
+
+#[cfg(target_os = "macos")]
+fn yield_focus_for_insertion(app: &AppHandle) {
+    if let Err(error) = app.hide() {
+        warn!(%error, "failed to hide app before history insertion");
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn yield_focus_for_insertion(app: &AppHandle) {
+    window_manager::hide(app, WindowTarget::Main);
 }
 
 #[tauri::command]
@@ -2339,12 +4451,32 @@ fn clear_history(history_store: tauri::State<'_, HistoryStore>) -> Result<(), St
     history_store.clear_history()
 }
 
+#[tauri::command]
+fn get_history_revision(history_store: tauri::State<'_, HistoryStore>) -> u64 {
+    history_store.revision()
+}
+
+#[tauri::command]
+fn get_history_sync_status(history_store: tauri::State<'_, HistoryStore>) -> HistorySyncStatus {
+    debug!("history sync status requested");
+    history_store.sync_status()
+}
+
 #[tauri::command]
 fn get_usage_stats(stats_store: tauri::State<'_, StatsStore>) -> Result<UsageStatsReport, String> {
     debug!("usage stats requested");
     stats_store.get_usage_stats()
 }
 
+#[tauri::command]
+fn reconcile_usage_stats(
+    stats_store: tauri::State<'_, StatsStore>,
+    history_store: tauri::State<'_, HistoryStore>,
+) -> Result<StatsReconciliation, String> {
+    info!("usage stats reconciliation requested");
+    stats_store.reconcile_with_history(&history_store.all_entries()?)
+}
+
 #[tauri::command]
 fn reset_usage_stats(stats_store: tauri::State<'_, StatsStore>) -> Result<(), String> {
     info!("usage stats reset requested");
@@ -2360,64 +4492,210 @@ fn export_logs(log_state: tauri::State<'_, LoggingState>) -> Result<String, Stri
     logging::export_log_contents(&log_state)
 }
 
-fn show_main_window(app: &AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        info!("showing main window");
-        if let Err(error) = window.show() {
-            warn!(%error, "failed to show main window");
-        }
-        if let Err(error) = window.set_focus() {
-            warn!(%error, "failed to focus main window");
-        }
+fn should_hide_main_window_on_startup(settings: &VoiceSettings) -> bool {
+    settings.onboarding_completed
+}
+
+fn build_tray_menu(
+    app: &AppHandle,
+    available_update: Option<&UpdateAvailableEvent>,
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let show_item = MenuItem::with_id(app, "show_window", "Open Voice", true, None::<&str>)?;
+    let hide_item = MenuItem::with_id(app, "hide_window", "Hide Voice", true, None::<&str>)?;
+    let prompt_item = MenuItem::with_id(
+        app,
+        "choose_prompt_template",
+        "Choose Prompt Template…",
+        true,
+        None::<&str>,
+    )?;
+    let quit_item = MenuItem::with_id(app, "quit", "Quit Voice", true, None::<&str>)?;
+    let wake_word = app.state::<WakeWordService>();
+    let wake_word_item = if wake_word.is_ready() {
+        let enabled = app
+            .state::<AppState>()
+            .services
+            .settings_store
+            .current()
+            .wake_word_enabled;
+        Some(CheckMenuItem::with_id(
+            app,
+            "toggle_wake_word",
+            "Listen for Wake Word",
+            true,
+            enabled,
+            None::<&str>,
+        )?)
     } else {
-        warn!("main window was not found while attempting to show");
+        None
+    };
+    let privacy_item = CheckMenuItem::with_id(
+        app,
+        "toggle_privacy_mode",
+        "Privacy Mode",
+        true,
+        app.state::<PrivacyMode>().is_active(),
+        None::<&str>,
+    )?;
+    let provider_item = match provider_switch_target(app) {
+        Some(next) => Some(MenuItem::with_id(
+            app,
+            "cycle_transcription_provider",
+            format!("Transcribe with {}", provider_switch::provider_label(next)),
+            true,
+            None::<&str>,
+        )?),
+        None => None,
+    };
+    let mut items: Vec<&dyn IsMenuItem<tauri::Wry>> = vec![&show_item, &hide_item, &prompt_item];
+    if let Some(wake_word_item) = wake_word_item.as_ref() {
+        items.push(wake_word_item);
+    }
+    items.push(&privacy_item);
+    if let Some(provider_item) = provider_item.as_ref() {
+        items.push(provider_item);
+    }
+    items.push(&quit_item);
+    let menu = Menu::with_items(app, &items)?;
+
+    if let Some(update) = available_update {
+        let update_item = MenuItem::with_id(
+            app,
+            "update_available",
+            format!("Update Available ({})…", update.version),
+            true,
+            None::<&str>,
+        )?;
+        menu.prepend(&update_item)?;
     }
+
+    Ok(menu)
 }
 
-fn hide_main_window(app: &AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        info!("hiding main window");
-        if let Err(error) = window.hide() {
-            warn!(%error, "failed to hide main window");
-        }
+fn provider_switch_target(app: &AppHandle) -> Option<AuthMethod> {
+    let state = app.state::<AppState>();
+    let current = state.services.current_auth_method().ok()?;
+    let available = state
+        .services
+        .auth_store
+        .configured_methods(&state.services.api_key_store)
+        .inspect_err(|error| warn!(%error, "failed to list configured transcription providers"))
+        .ok()?;
+    provider_switch::next_provider(current, &available)
+}
+
+fn tray_tooltip(app: &AppHandle) -> &'static str {
+    if app.state::<PrivacyMode>().is_active() {
+        "Voice — Privacy Mode"
+    } else if app.state::<WakeWordService>().is_listening() {
+        "Voice — Listening for Wake Word"
     } else {
-        warn!("main window was not found while attempting to hide");
+        "Voice"
     }
 }
 
-fn should_hide_main_window_on_startup(settings: &VoiceSettings) -> bool {
-    settings.onboarding_completed
+fn tray_icon_image(private: bool) -> tauri::image::Image<'static> {
+    let icon = tauri::image::Image::from_bytes(include_bytes!("../icons/tray-icon.png"))
+        .expect("failed to decode tray icon PNG");
+    if !private {
+        return icon;
+    }
+    let rgba = privacy_mode::badge_tray_icon(icon.rgba(), icon.width(), icon.height());
+    tauri::image::Image::new_owned(rgba, icon.width(), icon.height())
 }
 
-fn toggle_main_window(app: &AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        match window.is_visible() {
-            Ok(true) => {
-                info!("toggling main window to hidden");
-                if let Err(error) = window.hide() {
-                    warn!(%error, "failed to hide main window while toggling");
-                }
+fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ICON_ID) else {
+        return;
+    };
+    let update = app.state::<UpdateChecker>().available_update();
+    match build_tray_menu(app, update.as_ref()) {
+        Ok(menu) => {
+            if let Err(error) = tray.set_menu(Some(menu)) {
+                warn!(%error, "failed to refresh tray menu");
             }
-            _ => {
-                info!("toggling main window to visible");
-                if let Err(error) = window.show() {
-                    warn!(%error, "failed to show main window while toggling");
-                }
-                if let Err(error) = window.set_focus() {
-                    warn!(%error, "failed to focus main window while toggling");
-                }
+        }
+        Err(error) => warn!(%error, "failed to rebuild tray menu"),
+    }
+    if let Err(error) = tray.set_tooltip(Some(tray_tooltip(app))) {
+        warn!(%error, "failed to refresh tray tooltip");
+    }
+    let private = app.state::<PrivacyMode>().is_active();
+    if let Err(error) = tray.set_icon(Some(tray_icon_image(private))) {
+        warn!(%error, "failed to refresh tray icon");
+    }
+}
+
+fn toggle_wake_word(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let enabled = !state.services.settings_store.current().wake_word_enabled;
+    match state.services.settings_store.update(
+        app,
+        VoiceSettingsUpdate {
+            wake_word_enabled: Some(enabled),
+            ..VoiceSettingsUpdate::default()
+        },
+    ) {
+        Ok(settings) => {
+            info!(enabled, "wake word toggled");
+            sync_services_with_settings(app, &settings);
+        }
+        Err(error) => warn!(%error, "failed to toggle wake word"),
+    }
+    refresh_tray_menu(app);
+}
+
+fn set_spoken_punctuation(app: &AppHandle, enabled: bool) {
+    let state = app.state::<AppState>();
+    let settings = state.services.settings_store.current();
+    let update = prompt_templates::spoken_punctuation_update(&settings, enabled);
+    match state.services.settings_store.update(app, update) {
+        Ok(settings) => {
+            info!(enabled, "spoken punctuation toggled by voice command");
+            sync_services_with_settings(app, &settings);
+        }
+        Err(error) => warn!(%error, "failed to toggle spoken punctuation"),
+    }
+}
+
+fn show_update_in_tray(app: &AppHandle, update: &UpdateAvailableEvent) {
+    let Some(tray) = app.tray_by_id(TRAY_ICON_ID) else {
+        warn!("tray icon was not found while surfacing an update");
+        return;
+    };
+    match build_tray_menu(app, Some(update)) {
+        Ok(menu) => {
+            if let Err(error) = tray.set_menu(Some(menu)) {
+                warn!(%error, "failed to add update item to tray menu");
             }
         }
-    } else {
-        warn!("main window was not found while toggling visibility");
+        Err(error) => warn!(%error, "failed to build tray menu with update item"),
     }
 }
 
 fn handle_tray_menu_event(app: &AppHandle, menu_id: &str) {
     info!(menu_id, "tray menu event received");
     match menu_id {
-        "show_window" => show_main_window(app),
-        "hide_window" => hide_main_window(app),
+        "show_window" => window_manager::open(app, WindowTarget::Main, WindowParams::default()),
+        "update_available" => {
+            window_manager::open(app, WindowTarget::Main, WindowParams::default());
+            if let Some(update) = app.state::<UpdateChecker>().available_update() {
+                if let Err(error) = app.emit(EVENT_UPDATE_AVAILABLE, update) {
+                    warn!(%error, "failed to emit update available event");
+                }
+            }
+        }
+        "hide_window" => window_manager::hide(app, WindowTarget::Main),
+        "choose_prompt_template" => prompt_templates::show_prompt_picker(app),
+        "toggle_wake_word" => toggle_wake_word(app),
+        "toggle_privacy_mode" => {
+            privacy_mode::toggle(app);
+        }
+        "cycle_transcription_provider" => {
+            if let Err(error) = provider_switch::cycle_provider(app) {
+                warn!(%error, "failed to switch transcription provider from tray");
+            }
+        }
         "quit" => {
             info!("quitting app from tray menu");
             app.exit(0);
@@ -2426,10 +4704,65 @@ fn handle_tray_menu_event(app: &AppHandle, menu_id: &str) {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SecondInstanceAction {
+    ShowMainWindow,
+    StartDictation,
+}
+
+fn second_instance_action_from_args(args: &[String]) -> SecondInstanceAction {
+    if args
+        .iter()
+        .skip(1)
+        .any(|arg| arg.trim().eq_ignore_ascii_case(START_DICTATION_ARG))
+    {
+        SecondInstanceAction::StartDictation
+    } else {
+        SecondInstanceAction::ShowMainWindow
+    }
+}
+
+fn handle_second_instance(app: &AppHandle, args: Vec<String>) {
+    let action = second_instance_action_from_args(&args);
+    info!(
+        ?action,
+        arg_count = args.len(),
+        "second app instance launched"
+    );
+    match action {
+        SecondInstanceAction::ShowMainWindow => {
+            window_manager::open(app, WindowTarget::Main, WindowParams::default())
+        }
+        SecondInstanceAction::StartDictation => start_dictation_from_launch_args(app),
+    }
+}
+
+fn handle_launch_args(app: &AppHandle, args: &[String]) {
+    if second_instance_action_from_args(args) == SecondInstanceAction::StartDictation {
+        info!("start dictation requested at launch");
+        start_dictation_from_launch_args(app);
+    }
+}
+
+fn start_dictation_from_launch_args(app: &AppHandle) {
+    let hotkey_service = app.state::<HotkeyService>();
+    if !hotkey_service.request_recording_transition(app, RecordingTransition::Started) {
+        debug!("start dictation request ignored because recording is already active");
+    }
+}
+
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    cli::run_from_args(args)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     info!("starting tauri app builder");
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            handle_second_instance(app, args);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
             None::<Vec<&str>>,
@@ -2438,6 +4771,16 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .manage(HotkeyService::new())
         .manage(PipelineRuntimeState::default())
+        .manage(UpdateChecker::new())
+        .manage(ProviderSwitchShortcut::default())
+        .manage(CommandPaletteShortcut::default())
+        .manage(TemplateShortcuts::default())
+        .manage(PrivacyModeShortcut::default())
+        .manage(PrivacyMode::default())
+        .manage(ContextCarryover::default())
+        .manage(ErrorRecovery::default())
+        .manage(RecordingAlerts::default())
+        .manage(MediaPause::default())
         .setup(|app| {
             let logging_state = logging::initialize(app.handle()).map_err(std::io::Error::other)?;
             app.manage(logging_state);
@@ -2446,10 +4789,7 @@ pub fn run() {
             app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             info!("setup started");
 
-            let app_data_dir = app
-                .path()
-                .app_data_dir()
-                .map_err(std::io::Error::other)?;
+            let app_data_dir = app.path().app_data_dir().map_err(std::io::Error::other)?;
             migrate_legacy_app_data_dir(&app_data_dir);
             app.manage(AppState::new(app_data_dir.clone()));
             info!(path = %app_data_dir.display(), "app state initialized");
@@ -2462,6 +4802,31 @@ pub fn run() {
             app.manage(stats_store);
             info!("usage stats store initialized");
 
+            let feature_usage_store =
+                FeatureUsageStore::new(app.handle()).map_err(std::io::Error::other)?;
+            app.manage(feature_usage_store);
+
+            let offline_queue =
+                OfflineQueue::from_app_data_dir(&app_data_dir).map_err(std::io::Error::other)?;
+            app.manage(offline_queue);
+            offline_queue::spawn_retry_loop(app.handle().clone());
+            spawn_transcription_keep_warm_loop(app.handle().clone());
+            spawn_history_sync_loop(app.handle().clone());
+            spawn_store_flush_loop(app.handle().clone());
+            install_crash_flush_hook(app.handle().clone());
+            #[cfg(unix)]
+            spawn_termination_signal_listener(app.handle().clone());
+            info!("offline queue initialized");
+
+            app.manage(HttpApiService::new(app_data_dir.clone()));
+            app.manage(TriggerService::new());
+            let wake_word_service =
+                WakeWordService::new(app.handle()).map_err(std::io::Error::other)?;
+            app.manage(wake_word_service);
+            app.manage(MeetingService::new());
+            app.manage(LatencyReportStore::default());
+            app.manage(RoutingTraceStore::default());
+
             app.handle()
                 .plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
             info!("global shortcut plugin initialized");
@@ -2492,39 +4857,40 @@ pub fn run() {
             .map_err(std::io::Error::other)?;
             info!("hotkey configuration applied");
 
-            if let Err(error) = set_launch_at_login_state(app.handle(), launch_at_login) {
+            if let Err(error) = startup_manager::set_launch_at_login(app.handle(), launch_at_login)
+            {
                 warn!(%error, "failed to apply launch-at-login preference");
             }
 
             register_overlay_audio_forwarder(app.handle());
             register_pipeline_handlers(app.handle());
+            register_transcription_queue_forwarder(app.handle());
             register_debug_memory_probe(app.handle());
-            set_status_for_app(app.handle(), AppStatus::Idle);
+            http_api::register_event_forwarders(app.handle());
+            trigger_service::register_url_scheme_handler(app.handle());
+            #[cfg(target_os = "macos")]
+            automation::register_script_commands(app.handle());
+            sync_services_with_settings(app.handle(), &settings);
+            set_status_for_app(app.handle(), AppStatus::Idle, StatusSource::Startup);
             info!("overlay, pipeline handlers, and initial status configured");
 
-            let show_item =
-                MenuItem::with_id(app, "show_window", "Open Voice", true, None::<&str>)?;
-            let hide_item =
-                MenuItem::with_id(app, "hide_window", "Hide Voice", true, None::<&str>)?;
-            let quit_item = MenuItem::with_id(app, "quit", "Quit Voice", true, None::<&str>)?;
-            let tray_menu = Menu::with_items(app, &[&show_item, &hide_item, &quit_item])?;
-
-            let tray_icon_image = tauri::image::Image::from_bytes(include_bytes!("../icons/tray-icon.png"))
-                .expect("failed to decode tray icon PNG");
+            let tray_menu = build_tray_menu(app.handle(), None)?;
 
-            tauri::tray::TrayIconBuilder::with_id("voice-tray")
-                .icon(tray_icon_image)
+            tauri::tray::TrayIconBuilder::with_id(TRAY_ICON_ID)
+                .icon(tray_icon_image(false))
                 .icon_as_template(true)
+                .tooltip(tray_tooltip(app.handle()))
                 .menu(&tray_menu)
                 .show_menu_on_left_click(false)
                 .on_tray_icon_event(|tray, event| {
                     if let TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
+                        rect,
                         ..
                     } = event
                     {
-                        toggle_main_window(&tray.app_handle());
+                        tray_popover::toggle_popover(tray.app_handle(), rect);
                     }
                 })
                 .on_menu_event(|app, event| {
@@ -2533,32 +4899,28 @@ pub fn run() {
                 .build(app)?;
             info!("tray icon initialized");
 
+            startup_manager::update_checker::spawn_background_checks(
+                app.handle().clone(),
+                show_update_in_tray,
+            );
+
             if should_hide_main_window_on_startup(&settings) {
-                hide_main_window(app.handle());
+                window_manager::hide(app.handle(), WindowTarget::Main);
                 info!("setup complete in tray-only mode");
             } else {
-                show_main_window(app.handle());
+                window_manager::open(app.handle(), WindowTarget::Main, WindowParams::default());
                 info!("setup complete with onboarding window visible");
             }
 
+            handle_launch_args(app.handle(), &std::env::args().collect::<Vec<_>>());
+
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                if window.label() == OVERLAY_WINDOW_LABEL {
-                    info!("allowing overlay window close request to proceed");
-                    return;
-                }
-                api.prevent_close();
-                info!(window = %window.label(), "window close requested; hiding instead");
-                if let Err(error) = window.hide() {
-                    warn!(%error, window = %window.label(), "failed to hide window on close request");
-                }
-            }
-        })
+        .on_window_event(window_manager::handle_window_event)
         .invoke_handler(tauri::generate_handler![
             get_status,
             set_status,
+            get_status_history,
             get_settings,
             get_onboarding_status,
             complete_onboarding,
@@ -2567,6 +4929,8 @@ pub fn run() {
             get_launch_at_login,
             set_launch_at_login,
             has_api_key,
+            set_proxy_password,
+            delete_proxy_password,
             get_auth_method,
             set_auth_method,
             get_chatgpt_auth_status,
@@ -2586,24 +4950,77 @@ pub fn run() {
             stop_recording,
             complete_recording,
             cancel_recording,
-            get_audio_level,
             insert_text,
             copy_to_clipboard,
+            preview_insertion,
+            test_keyboard_layout,
             transcribe_audio,
             list_history,
+            list_history_page,
+            query_history,
+            list_entry_previews,
             get_history_entry,
+            get_history_waveform,
             delete_history_entry,
+            reinsert_entry,
+            insert_entry_into_app,
+            rename_history_speaker,
+            summarization::summarize_entry,
+            transcript_diff::get_transcript_diff,
+            feature_usage::get_feature_usage,
+            feature_usage::reset_feature_usage,
+            wake_word::get_wake_word_status,
+            wake_word::start_wake_word_enrollment,
+            wake_word::cancel_wake_word_enrollment,
+            wake_word::clear_wake_word_samples,
             clear_history,
+            get_history_sync_status,
+            get_history_revision,
             get_usage_stats,
+            reconcile_usage_stats,
             reset_usage_stats,
             export_logs,
             debug_report_renderer_memory,
             hotkey_service::get_hotkey_config,
             hotkey_service::get_hotkey_recording_state,
-            hotkey_service::set_hotkey_config
+            hotkey_service::get_hotkey_state_snapshot,
+            hotkey_service::set_hotkey_config,
+            hotkey_service::begin_hotkey_capture,
+            hotkey_service::submit_hotkey_capture,
+            hotkey_service::cancel_hotkey_capture,
+            http_api::get_http_api_info,
+            latency_report::get_latency_report,
+            routing_rules::get_routing_trace,
+            http_api::rotate_http_api_token,
+            meeting_mode::start_meeting,
+            meeting_mode::stop_meeting,
+            meeting_mode::get_meeting_snapshot,
+            offline_queue::list_pending_recordings,
+            offline_queue::flush_pending_recordings,
+            offline_queue::discard_pending_recording,
+            backup::create_backup,
+            backup::restore_backup,
+            system_health::get_system_health,
+            system_health::test_provider_connection,
+            provider_switch::cycle_transcription_provider,
+            privacy_mode::get_privacy_mode,
+            privacy_mode::set_privacy_mode,
+            prompt_templates::choose_prompt_template,
+            prompt_templates::close_prompt_picker,
+            command_palette::search_palette_commands,
+            command_palette::run_palette_command,
+            command_palette::close_command_palette,
+            window_manager::open_window,
+            window_manager::open_history_window,
+            tray_popover::open_main_window_from_popover
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                shutdown_gracefully(app);
+            }
+        });
 }
 
 #[cfg(test)]
@@ -2611,22 +5028,24 @@ mod tests {
     use std::{
         path::{Path, PathBuf},
         sync::{atomic::Ordering, Arc, Mutex},
-        time::Duration,
+        time::{Duration, Instant},
     };
 
     use async_trait::async_trait;
-    use tauri::PhysicalPosition;
+    use bytes::Bytes;
+    use tauri::{PhysicalPosition, PhysicalSize};
     use tokio::sync::{oneshot, Notify};
     use uuid::Uuid;
 
     use crate::{
         audio_capture_service::RecordedAudio,
+        history_store::{HistoryEntry, HistoryStore, TranscriptionDetails},
         hotkey_service::{HotkeyConfig, RecordingMode},
         settings_store::{VoiceSettings, VoiceSettingsUpdate, RECORDING_MODE_TOGGLE},
-        status_notifier::AppStatus,
+        status_notifier::{AppStatus, StatusSource},
         voice_pipeline::{
-            PipelineError, PipelineErrorStage, PipelineTranscript, VoicePipeline,
-            VoicePipelineDelegate,
+            harness::stop_and_process, PipelineError, PipelineErrorStage, PipelineTranscript,
+            StageError, VoicePipeline, VoicePipelineDelegate,
         },
     };
 
@@ -2634,11 +5053,14 @@ mod tests {
         active_pipeline_session_id, apply_hotkey_from_settings_with_fallback,
         apply_settings_transaction_with_hooks, cancel_recording_with_hooks,
         copy_directory_contents, handle_audio_input_stream_error_with_hooks, has_api_key,
-        load_startup_settings_with_fallback, migrate_legacy_app_data_dir,
-        overlay_position_from_work_area, overlay_window_action, permission_preflight_error_message,
-        resolve_transcription_prompt, should_hide_main_window_on_startup,
-        should_show_overlay_for_status, spawn_pipeline_stage_error_reset, AppState,
-        OverlayWindowAction, PipelineRuntimeState, OVERLAY_WINDOW_TOP_MARGIN, OVERLAY_WINDOW_WIDTH,
+        history_entry_text, load_startup_settings_with_fallback, migrate_legacy_app_data_dir,
+        monitor_contains_logical_point, overlay_position_from_work_area, overlay_window_action,
+        permission_preflight_error_message, resolve_transcription_prompt,
+        second_instance_action_from_args, should_hide_main_window_on_startup,
+        should_show_overlay_for_status, spawn_pipeline_stage_error_reset, AppState, OverlayAnchor,
+        OverlayWindowAction, PipelineRuntimeState, SecondInstanceAction,
+        OVERLAY_WINDOW_EDGE_MARGIN, OVERLAY_WINDOW_HEIGHT, OVERLAY_WINDOW_WIDTH,
+        TRANSCRIPTION_WARM_UP_MIN_INTERVAL,
     };
     use crate::permission_service::{PermissionState, PermissionType};
 
@@ -2756,11 +5178,15 @@ mod tests {
         fn is_active(&self) -> bool {
             self.runtime.is_session_active(self.session_id)
         }
+
+        fn accepts_output(&self) -> bool {
+            self.runtime.accepts_session_output(self.session_id)
+        }
     }
 
     #[async_trait]
     impl VoicePipelineDelegate for SessionAwareDelegate {
-        fn set_status(&self, status: AppStatus) {
+        fn set_status(&self, status: AppStatus, _source: StatusSource) {
             if self.is_active() {
                 self.event_log
                     .statuses
@@ -2771,7 +5197,7 @@ mod tests {
         }
 
         fn emit_transcript(&self, transcript: &str) {
-            if self.is_active() {
+            if self.accepts_output() {
                 self.event_log
                     .transcripts
                     .lock()
@@ -2781,7 +5207,7 @@ mod tests {
         }
 
         fn emit_error(&self, error: &PipelineError) {
-            if self.is_active() {
+            if self.accepts_output() {
                 self.event_log
                     .errors
                     .lock()
@@ -2801,7 +5227,7 @@ mod tests {
         async fn transcribe(
             &self,
             _recorded_audio: RecordedAudio,
-        ) -> Result<PipelineTranscript, String> {
+        ) -> Result<PipelineTranscript, StageError> {
             if let Some(started_tx) = self
                 .transcribe_started_tx
                 .lock()
@@ -2820,11 +5246,13 @@ mod tests {
                 duration_secs: None,
                 language: None,
                 provider: "test".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
             })
         }
 
-        fn insert_text(&self, transcript: &str) -> Result<(), String> {
-            if self.is_active() {
+        async fn insert_text(&self, transcript: &str) -> Result<(), String> {
+            if self.accepts_output() {
                 self.event_log
                     .insertions
                     .lock()
@@ -2876,7 +5304,7 @@ mod tests {
 
     #[async_trait]
     impl VoicePipelineDelegate for TranscriptionFailureDelegate {
-        fn set_status(&self, status: AppStatus) {
+        fn set_status(&self, status: AppStatus, _source: StatusSource) {
             self.statuses
                 .lock()
                 .expect("status lock should not be poisoned")
@@ -2908,11 +5336,11 @@ mod tests {
         async fn transcribe(
             &self,
             _recorded_audio: RecordedAudio,
-        ) -> Result<PipelineTranscript, String> {
-            Err("provider unavailable".to_string())
+        ) -> Result<PipelineTranscript, StageError> {
+            Err("provider unavailable".to_string().into())
         }
 
-        fn insert_text(&self, transcript: &str) -> Result<(), String> {
+        async fn insert_text(&self, transcript: &str) -> Result<(), String> {
             self.insertions
                 .lock()
                 .expect("insertion lock should not be poisoned")
@@ -2961,7 +5389,7 @@ mod tests {
 
     #[async_trait]
     impl VoicePipelineDelegate for InsertionFailureDelegate {
-        fn set_status(&self, status: AppStatus) {
+        fn set_status(&self, status: AppStatus, _source: StatusSource) {
             self.statuses
                 .lock()
                 .expect("status lock should not be poisoned")
@@ -2993,16 +5421,18 @@ mod tests {
         async fn transcribe(
             &self,
             _recorded_audio: RecordedAudio,
-        ) -> Result<PipelineTranscript, String> {
+        ) -> Result<PipelineTranscript, StageError> {
             Ok(PipelineTranscript {
                 text: "hello world".to_string(),
                 duration_secs: Some(2.4),
                 language: Some("en".to_string()),
                 provider: "test".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
             })
         }
 
-        fn insert_text(&self, _transcript: &str) -> Result<(), String> {
+        async fn insert_text(&self, _transcript: &str) -> Result<(), String> {
             Err("accessibility denied".to_string())
         }
 
@@ -3039,7 +5469,7 @@ mod tests {
 
     #[async_trait]
     impl VoicePipelineDelegate for SharedStageErrorDelegate {
-        fn set_status(&self, status: AppStatus) {
+        fn set_status(&self, status: AppStatus, _source: StatusSource) {
             self.statuses
                 .lock()
                 .expect("status lock should not be poisoned")
@@ -3066,16 +5496,18 @@ mod tests {
         async fn transcribe(
             &self,
             _recorded_audio: RecordedAudio,
-        ) -> Result<PipelineTranscript, String> {
+        ) -> Result<PipelineTranscript, StageError> {
             Ok(PipelineTranscript {
                 text: String::new(),
                 duration_secs: None,
                 language: None,
                 provider: "test".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
             })
         }
 
-        fn insert_text(&self, _transcript: &str) -> Result<(), String> {
+        async fn insert_text(&self, _transcript: &str) -> Result<(), String> {
             Ok(())
         }
     }
@@ -3091,6 +5523,58 @@ mod tests {
         assert!(runtime.is_session_active(second));
     }
 
+    #[test]
+    fn warm_up_is_skipped_while_previous_connection_is_fresh() {
+        let runtime = PipelineRuntimeState::default();
+        let now = Instant::now();
+
+        assert!(runtime.claim_warm_up(now));
+        assert!(!runtime.claim_warm_up(now + Duration::from_secs(5)));
+        assert!(runtime.claim_warm_up(now + TRANSCRIPTION_WARM_UP_MIN_INTERVAL));
+    }
+
+    #[test]
+    fn superseded_sessions_keep_output_only_in_queued_mode_until_cancelled() {
+        let runtime = PipelineRuntimeState::default();
+
+        let first = runtime.begin_session();
+        let second = runtime.begin_session();
+
+        assert!(!runtime.is_session_active(first));
+        assert!(!runtime.accepts_session_output(first));
+        assert!(runtime.accepts_session_output(second));
+
+        runtime.set_queued_mode(true);
+        assert!(runtime.accepts_session_output(first));
+        assert!(runtime.accepts_session_output(second));
+
+        let cancelled = runtime.cancel_sessions();
+        assert!(!runtime.accepts_session_output(first));
+        assert!(!runtime.accepts_session_output(second));
+        assert!(runtime.accepts_session_output(cancelled));
+
+        let third = runtime.begin_session();
+        assert!(runtime.accepts_session_output(cancelled));
+        assert!(runtime.accepts_session_output(third));
+    }
+
+    #[test]
+    fn in_flight_recordings_are_released_when_the_attempt_ends() {
+        let runtime = PipelineRuntimeState::default();
+
+        let finished = runtime.track_in_flight_recording(Bytes::from_static(&[1, 2]), 1.0);
+        let pending = runtime.track_in_flight_recording(Bytes::from_static(&[3, 4, 5]), 2.5);
+        drop(finished);
+
+        let interrupted = runtime.take_in_flight_recordings();
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].wav_bytes, vec![3, 4, 5]);
+        assert_eq!(interrupted[0].duration_secs, 2.5);
+
+        drop(pending);
+        assert!(runtime.take_in_flight_recordings().is_empty());
+    }
+
     #[test]
     fn active_pipeline_session_id_returns_current_session_without_mutating_counter() {
         let runtime = PipelineRuntimeState::default();
@@ -3128,19 +5612,6 @@ mod tests {
         assert_eq!(runtime.active_session_id(), Some(1));
     }
 
-    #[test]
-    fn short_recordings_are_discarded_before_transcription() {
-        assert!(crate::should_discard_recording(
-            crate::MIN_RECORDING_DURATION_MS - 1
-        ));
-        assert!(!crate::should_discard_recording(
-            crate::MIN_RECORDING_DURATION_MS
-        ));
-        assert!(!crate::should_discard_recording(
-            crate::MIN_RECORDING_DURATION_MS + 1
-        ));
-    }
-
     #[tokio::test]
     async fn overlapping_pipeline_sessions_ignore_stale_mutations() {
         let runtime = PipelineRuntimeState::default();
@@ -3161,7 +5632,7 @@ mod tests {
         let first_task = {
             let pipeline = pipeline.clone();
             tokio::spawn(async move {
-                pipeline.handle_hotkey_stopped(&first_delegate).await;
+                stop_and_process(&pipeline, &first_delegate).await;
             })
         };
 
@@ -3177,7 +5648,7 @@ mod tests {
             "second transcript",
         );
 
-        pipeline.handle_hotkey_stopped(&second_delegate).await;
+        stop_and_process(&pipeline, &second_delegate).await;
         first_blocker.notify_waiters();
         first_task
             .await
@@ -3202,12 +5673,92 @@ mod tests {
         assert!(event_log.errors().is_empty());
     }
 
+    #[tokio::test]
+    async fn queued_pipeline_sessions_insert_transcripts_in_order() {
+        let runtime = PipelineRuntimeState::default();
+        runtime.set_queued_mode(true);
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+        let event_log = Arc::new(SessionEventLog::default());
+
+        let first_session_id = runtime.begin_session();
+        let (first_started_tx, first_started_rx) = oneshot::channel();
+        let first_blocker = Arc::new(Notify::new());
+        let first_delegate = SessionAwareDelegate::new(
+            runtime.clone(),
+            first_session_id,
+            Arc::clone(&event_log),
+            "first transcript",
+        )
+        .with_transcription_gate(first_started_tx, Arc::clone(&first_blocker));
+        let first_audio = pipeline
+            .finish_recording(&first_delegate)
+            .await
+            .expect("first recording should produce audio");
+        runtime.enqueue_processing({
+            let pipeline = pipeline.clone();
+            async move {
+                pipeline
+                    .process_recording(&first_delegate, first_audio)
+                    .await;
+            }
+        });
+        first_started_rx
+            .await
+            .expect("first pipeline should reach transcription");
+
+        let second_session_id = runtime.begin_session();
+        let second_delegate = SessionAwareDelegate::new(
+            runtime.clone(),
+            second_session_id,
+            Arc::clone(&event_log),
+            "second transcript",
+        );
+        let second_audio = pipeline
+            .finish_recording(&second_delegate)
+            .await
+            .expect("second recording should produce audio");
+        runtime.enqueue_processing({
+            let pipeline = pipeline.clone();
+            async move {
+                pipeline
+                    .process_recording(&second_delegate, second_audio)
+                    .await;
+            }
+        });
+
+        first_blocker.notify_waiters();
+        let tail = runtime
+            .processing_tail
+            .lock()
+            .expect("processing queue lock should not be poisoned")
+            .take()
+            .expect("queued processing should be pending");
+        tail.await.expect("queued processing should finish cleanly");
+
+        assert_eq!(
+            event_log.statuses_for(first_session_id),
+            vec![AppStatus::Transcribing]
+        );
+        assert_eq!(
+            event_log.statuses_for(second_session_id),
+            vec![AppStatus::Transcribing, AppStatus::Idle]
+        );
+        assert_eq!(
+            event_log.insertions(),
+            vec![
+                (first_session_id, "first transcript".to_string()),
+                (second_session_id, "second transcript".to_string()),
+            ]
+        );
+        assert!(event_log.errors().is_empty());
+    }
+
     #[tokio::test]
     async fn transcription_failure_emits_error_resets_idle_and_skips_insertion() {
         let pipeline = VoicePipeline::new(Duration::ZERO);
         let delegate = TranscriptionFailureDelegate::default();
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(
             delegate.statuses(),
@@ -3215,10 +5766,10 @@ mod tests {
         );
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::Transcription,
-                message: "provider unavailable".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::Transcription,
+                "provider unavailable".to_string()
+            )]
         );
         assert!(delegate.transcripts().is_empty());
         assert!(delegate.insertions().is_empty());
@@ -3229,7 +5780,7 @@ mod tests {
         let pipeline = VoicePipeline::new(Duration::ZERO);
         let delegate = InsertionFailureDelegate::default();
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(
             delegate.statuses(),
@@ -3237,10 +5788,10 @@ mod tests {
         );
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::TextInsertion,
-                message: "accessibility denied".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::TextInsertion,
+                "accessibility denied".to_string()
+            )]
         );
         assert_eq!(delegate.transcripts(), vec!["hello world".to_string()]);
         assert_eq!(
@@ -3250,6 +5801,8 @@ mod tests {
                 duration_secs: Some(2.4),
                 language: Some("en".to_string()),
                 provider: "test".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
             }]
         );
     }
@@ -3349,10 +5902,10 @@ mod tests {
                 .lock()
                 .expect("error lock should not be poisoned")
                 .clone(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::RecordingRuntime,
-                message: "stream disconnected".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::RecordingRuntime,
+                "stream disconnected".to_string()
+            )]
         );
     }
 
@@ -3502,10 +6055,10 @@ mod tests {
         assert_eq!(observer.statuses(), vec![AppStatus::Error, AppStatus::Idle]);
         assert_eq!(
             observer.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::Transcription,
-                message: "command transcription failed".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::Transcription,
+                "command transcription failed".to_string()
+            )]
         );
     }
 
@@ -3881,6 +6434,23 @@ mod tests {
         assert!(error.contains("Failed to roll back hotkey config"));
     }
 
+    #[test]
+    fn history_entry_text_returns_stored_transcript_or_not_found() {
+        let temp_dir = TempDirGuard::new("voice-history-reinsert");
+        let store = HistoryStore::new_with_file_path(temp_dir.path().join("history.json"))
+            .expect("history store should initialize");
+        let entry = HistoryEntry::new("hello again".to_string(), None, None, "openai".to_string());
+        let id = entry.id.clone();
+        store.add_entry(entry).expect("entry should be saved");
+
+        assert_eq!(
+            history_entry_text(&store, &id).expect("entry should be found"),
+            "hello again"
+        );
+        let error = history_entry_text(&store, "missing").expect_err("lookup should fail");
+        assert!(error.contains("was not found"));
+    }
+
     #[test]
     fn copy_directory_contents_copies_nested_files() {
         let temp_dir = TempDirGuard::new("voice-copy-directory-contents");
@@ -3982,6 +6552,29 @@ mod tests {
         assert!(!should_hide_main_window_on_startup(&settings));
     }
 
+    #[test]
+    fn second_instance_without_arguments_shows_main_window() {
+        let args = vec!["/Applications/Buzz.app/Contents/MacOS/buzz".to_string()];
+
+        assert_eq!(
+            second_instance_action_from_args(&args),
+            SecondInstanceAction::ShowMainWindow
+        );
+    }
+
+    #[test]
+    fn second_instance_start_dictation_argument_is_forwarded() {
+        let args = vec![
+            "/Applications/Buzz.app/Contents/MacOS/buzz".to_string(),
+            "--start-dictation".to_string(),
+        ];
+
+        assert_eq!(
+            second_instance_action_from_args(&args),
+            SecondInstanceAction::StartDictation
+        );
+    }
+
     #[test]
     fn overlay_is_visible_while_listening_or_transcribing() {
         assert!(should_show_overlay_for_status(AppStatus::Listening));
@@ -4024,15 +6617,77 @@ mod tests {
 
     #[test]
     fn overlay_position_is_top_centered_in_work_area() {
-        let position = overlay_position_from_work_area(PhysicalPosition::new(100, 32), 1600, 2.0);
+        let position = overlay_position_from_work_area(
+            PhysicalPosition::new(100, 32),
+            PhysicalSize::new(1600, 1000),
+            2.0,
+            OverlayAnchor::TopCenter,
+        );
 
         let expected_x = (100.0 / 2.0) + ((1600.0 / 2.0 - OVERLAY_WINDOW_WIDTH) / 2.0);
-        let expected_y = (32.0 / 2.0) + OVERLAY_WINDOW_TOP_MARGIN;
+        let expected_y = (32.0 / 2.0) + OVERLAY_WINDOW_EDGE_MARGIN;
 
         assert!((position.x - expected_x).abs() < f64::EPSILON);
         assert!((position.y - expected_y).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn overlay_position_anchors_to_corners_of_secondary_display() {
+        let work_area_position = PhysicalPosition::new(-1920, 50);
+        let work_area_size = PhysicalSize::new(1920, 1030);
+
+        let top_left = overlay_position_from_work_area(
+            work_area_position,
+            work_area_size,
+            1.0,
+            OverlayAnchor::from_setting("top_left"),
+        );
+        let bottom_right = overlay_position_from_work_area(
+            work_area_position,
+            work_area_size,
+            1.0,
+            OverlayAnchor::from_setting("bottom_right"),
+        );
+
+        assert_eq!(top_left.x, -1920.0 + OVERLAY_WINDOW_EDGE_MARGIN);
+        assert_eq!(top_left.y, 50.0 + OVERLAY_WINDOW_EDGE_MARGIN);
+        assert_eq!(
+            bottom_right.x,
+            -OVERLAY_WINDOW_WIDTH - OVERLAY_WINDOW_EDGE_MARGIN
+        );
+        assert_eq!(
+            bottom_right.y,
+            1080.0 - OVERLAY_WINDOW_HEIGHT - OVERLAY_WINDOW_EDGE_MARGIN
+        );
+    }
+
+    #[test]
+    fn monitor_lookup_uses_each_display_scale_factor() {
+        let retina_position = PhysicalPosition::new(0, 0);
+        let retina_size = PhysicalSize::new(3024, 1964);
+        let external_position = PhysicalPosition::new(1512, 0);
+        let external_size = PhysicalSize::new(2560, 1440);
+
+        assert!(monitor_contains_logical_point(
+            retina_position,
+            retina_size,
+            2.0,
+            (1400.0, 500.0)
+        ));
+        assert!(!monitor_contains_logical_point(
+            retina_position,
+            retina_size,
+            2.0,
+            (1600.0, 500.0)
+        ));
+        assert!(monitor_contains_logical_point(
+            external_position,
+            external_size,
+            1.0,
+            (1600.0, 500.0)
+        ));
+    }
+
     #[test]
     fn has_api_key_command_contract_returns_boolean_presence_only() {
         let _: for<'a> fn(String, tauri::State<'a, AppState>) -> Result<bool, String> = has_api_key;
@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, warn};
+
+use crate::api_key_store::ApiKeyStore;
+
+const DEFAULT_CHAT_COMPLETIONS_ENDPOINT: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_CHAT_MODEL: &str = "gpt-4o-mini";
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+#[derive(Debug, Clone)]
+pub struct ChatCompletionConfig {
+    pub endpoint: String,
+    pub model: String,
+    pub request_timeout_secs: u64,
+}
+
+impl Default for ChatCompletionConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: DEFAULT_CHAT_COMPLETIONS_ENDPOINT.to_string(),
+            model: DEFAULT_CHAT_MODEL.to_string(),
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl ChatCompletionConfig {
+    pub fn from_env(model_env: &str) -> Self {
+        let mut config = Self::default();
+
+        if let Some(model) = read_non_empty_env(model_env) {
+            config.model = model;
+        }
+
+        if let Some(endpoint) = read_non_empty_env("OPENAI_CHAT_COMPLETIONS_ENDPOINT") {
+            config.endpoint = endpoint;
+        }
+
+        debug!(
+            endpoint = %config.endpoint,
+            model = %config.model,
+            "loaded chat completion config"
+        );
+        config
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatCompletionClient {
+    client: Client,
+    config: ChatCompletionConfig,
+}
+
+impl ChatCompletionClient {
+    pub fn new(config: ChatCompletionConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .unwrap_or_else(|error| {
+                warn!(%error, "failed to build chat completion http client; using defaults");
+                Client::new()
+            });
+
+        Self { client, config }
+    }
+
+    pub fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    pub async fn complete(
+        &self,
+        api_key: &str,
+        system_prompt: &str,
+        user_content: &str,
+        json_response: bool,
+    ) -> Result<String, String> {
+        let mut body = json!({
+            "model": self.config.model,
+            "messages": [
+                { "role": "system", "content": system_prompt },
+                { "role": "user", "content": user_content },
+            ],
+        });
+        if json_response {
+            body["response_format"] = json!({ "type": "json_object" });
+        }
+
+        let response = self
+            .client
+            .post(&self.config.endpoint)
+            .bearer_auth(api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|error| format!("Failed to request chat completion: {error}"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!(
+                "Chat completion request failed with status {status}: {}",
+                body.trim()
+            ));
+        }
+
+        response
+            .json::<ChatCompletionResponse>()
+            .await
+            .map_err(|error| format!("Failed to decode chat completion response: {error}"))?
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .filter(|content| !content.trim().is_empty())
+            .ok_or_else(|| "Chat completion response did not include any content".to_string())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    #[serde(default)]
+    choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+pub fn resolve_openai_api_key(api_key_store: &ApiKeyStore) -> Result<Option<String>, String> {
+    Ok(api_key_store
+        .get_api_key("openai")?
+        .or_else(|| read_non_empty_env("OPENAI_API_KEY")))
+}
+
+fn read_non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().and_then(|value| {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server};
+
+    use super::{ChatCompletionClient, ChatCompletionConfig};
+
+    fn client_for_test(server: &Server) -> ChatCompletionClient {
+        ChatCompletionClient::new(ChatCompletionConfig {
+            endpoint: format!("{}/v1/chat/completions", server.url()),
+            ..ChatCompletionConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn complete_returns_first_choice_content() {
+        let mut server = Server::new_async().await;
+        let request_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer test-key")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "model": "gpt-4o-mini",
+                "response_format": { "type": "json_object" },
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices": [{"message": {"content": "{\"ok\": true}"}}]}"#)
+            .create_async()
+            .await;
+
+        let content = client_for_test(&server)
+            .complete("test-key", "system", "user", true)
+            .await
+            .expect("completion should succeed");
+
+        request_mock.assert_async().await;
+        assert_eq!(content, r#"{"ok": true}"#);
+    }
+
+    #[tokio::test]
+    async fn complete_reports_error_status_and_empty_content() {
+        let mut server = Server::new_async().await;
+        let _error_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::Regex("fail".to_string()))
+            .with_status(500)
+            .with_body("upstream down")
+            .create_async()
+            .await;
+        let _empty_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices": []}"#)
+            .create_async()
+            .await;
+        let client = client_for_test(&server);
+
+        let error = client
+            .complete("test-key", "fail", "user", false)
+            .await
+            .expect_err("server errors should fail");
+        assert!(error.contains("500"));
+        assert!(error.contains("upstream down"));
+        assert!(client
+            .complete("test-key", "system", "user", false)
+            .await
+            .is_err());
+    }
+}
@@ -1,8 +1,13 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-#[cfg(not(target_os = "macos"))]
-compile_error!("This scaffold currently targets macOS only.");
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+compile_error!("This scaffold currently targets macOS and Linux only.");
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(exit_code) = tauri_app_lib::run_cli(&args) {
+        std::process::exit(exit_code);
+    }
+
     tauri_app_lib::run()
 }
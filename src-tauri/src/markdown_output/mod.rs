@@ -0,0 +1,208 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local,
+};
+use tracing::info;
+
+const DATE_TOKEN_PREFIX: &str = "{date";
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const ENTRY_TIME_FORMAT: &str = "%H:%M";
+
+pub fn append_transcript(
+    path_template: &str,
+    transcript: &str,
+    now: DateTime<Local>,
+) -> Result<PathBuf, String> {
+    let home_dir = std::env::var_os("HOME").map(PathBuf::from);
+    let file_path = resolve_output_path(path_template, now, home_dir.as_deref())?;
+
+    if let Some(parent_dir) = file_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(|error| {
+            format!(
+                "Failed to create markdown output directory `{}`: {error}",
+                parent_dir.display()
+            )
+        })?;
+    }
+
+    let needs_leading_newline = fs::read(&file_path)
+        .map(|contents| contents.last().is_some_and(|byte| *byte != b'\n'))
+        .unwrap_or(false);
+    let mut entry = format_entry(transcript, now);
+    if needs_leading_newline {
+        entry.insert(0, '\n');
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file_path)
+        .map_err(|error| {
+            format!(
+                "Failed to open markdown output file `{}`: {error}",
+                file_path.display()
+            )
+        })?;
+    file.write_all(entry.as_bytes()).map_err(|error| {
+        format!(
+            "Failed to append to markdown output file `{}`: {error}",
+            file_path.display()
+        )
+    })?;
+
+    info!(
+        path = %file_path.display(),
+        transcript_chars = transcript.chars().count(),
+        "transcript appended to markdown file"
+    );
+    Ok(file_path)
+}
+
+fn resolve_output_path(
+    path_template: &str,
+    now: DateTime<Local>,
+    home_dir: Option<&Path>,
+) -> Result<PathBuf, String> {
+    let expanded = expand_date_tokens(path_template.trim(), now)?;
+    if expanded.is_empty() {
+        return Err("Markdown output path is empty".to_string());
+    }
+
+    match expanded.strip_prefix("~/") {
+        Some(relative) => {
+            let home_dir =
+                home_dir.ok_or_else(|| "Failed to resolve home directory".to_string())?;
+            Ok(home_dir.join(relative))
+        }
+        None => Ok(PathBuf::from(expanded)),
+    }
+}
+
+fn expand_date_tokens(template: &str, now: DateTime<Local>) -> Result<String, String> {
+    let mut expanded = String::with_capacity(template.len());
+    let mut remaining = template;
+
+    while let Some(start) = remaining.find(DATE_TOKEN_PREFIX) {
+        expanded.push_str(&remaining[..start]);
+        let token = &remaining[start + DATE_TOKEN_PREFIX.len()..];
+        let end = token
+            .find('}')
+            .ok_or_else(|| format!("Unterminated date token in `{template}`"))?;
+        let format = match &token[..end] {
+            "" => DEFAULT_DATE_FORMAT,
+            custom => custom
+                .strip_prefix(':')
+                .filter(|format| !format.is_empty())
+                .ok_or_else(|| format!("Invalid date token in `{template}`"))?,
+        };
+
+        expanded.push_str(&format_date(now, format)?);
+        remaining = &token[end + 1..];
+    }
+
+    expanded.push_str(remaining);
+    Ok(expanded)
+}
+
+fn format_date(now: DateTime<Local>, format: &str) -> Result<String, String> {
+    let items = StrftimeItems::new(format).collect::<Vec<_>>();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(format!("Invalid date format `{format}`"));
+    }
+
+    Ok(now.format_with_items(items.into_iter()).to_string())
+}
+
+fn format_entry(transcript: &str, now: DateTime<Local>) -> String {
+    let mut lines = transcript.trim().lines();
+    let mut entry = format!(
+        "- {} {}\n",
+        now.format(ENTRY_TIME_FORMAT),
+        lines.next().unwrap_or_default()
+    );
+    for line in lines {
+        entry.push_str("  ");
+        entry.push_str(line);
+        entry.push('\n');
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use chrono::{DateTime, Local, TimeZone};
+
+    use super::{append_transcript, expand_date_tokens, format_entry, resolve_output_path};
+
+    fn fixed_now() -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2025, 3, 7, 9, 5, 0)
+            .single()
+            .expect("fixed timestamp should be unambiguous")
+    }
+
+    #[test]
+    fn date_tokens_expand_with_default_and_custom_formats() {
+        assert_eq!(
+            expand_date_tokens("Daily/{date}.md", fixed_now()),
+            Ok("Daily/2025-03-07.md".to_string())
+        );
+        assert_eq!(
+            expand_date_tokens("{date:%Y}/{date:%m-%d}.md", fixed_now()),
+            Ok("2025/03-07.md".to_string())
+        );
+        assert!(expand_date_tokens("{date", fixed_now()).is_err());
+        assert!(expand_date_tokens("{date:}", fixed_now()).is_err());
+        assert!(expand_date_tokens("{date:%Q}", fixed_now()).is_err());
+    }
+
+    #[test]
+    fn home_prefix_resolves_against_home_directory() {
+        assert_eq!(
+            resolve_output_path(
+                "~/Vault/{date}.md",
+                fixed_now(),
+                Some(Path::new("/Users/a"))
+            ),
+            Ok(PathBuf::from("/Users/a/Vault/2025-03-07.md"))
+        );
+        assert!(resolve_output_path("~/Vault.md", fixed_now(), None).is_err());
+    }
+
+    #[test]
+    fn entries_are_timestamped_list_items_with_indented_continuations() {
+        assert_eq!(
+            format_entry("first line\nsecond line", fixed_now()),
+            "- 09:05 first line\n  second line\n"
+        );
+    }
+
+    #[test]
+    fn append_adds_newline_before_entry_when_file_lacks_one() {
+        let dir = std::env::temp_dir().join(format!("buzz-markdown-{}", uuid::Uuid::new_v4()));
+        let file_path = dir.join("note.md");
+        std::fs::create_dir_all(&dir).expect("temp dir should be created");
+        std::fs::write(&file_path, "# Notes").expect("note should be written");
+
+        append_transcript(
+            file_path.to_str().expect("path should be utf-8"),
+            "hello",
+            fixed_now(),
+        )
+        .expect("append should succeed");
+
+        assert_eq!(
+            std::fs::read_to_string(&file_path).expect("note should be readable"),
+            "# Notes\n- 09:05 hello\n"
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
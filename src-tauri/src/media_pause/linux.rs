@@ -0,0 +1,74 @@
+use zbus::blocking::{fdo::DBusProxy, Connection, Proxy};
+
+const MPRIS_NAME_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const MPRIS_PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+const PLAYING: &str = "Playing";
+
+pub struct PausedMedia(Vec<String>);
+
+pub fn pause_playing() -> Result<Option<PausedMedia>, String> {
+    let connection = Connection::session()
+        .map_err(|error| format!("Failed to connect to the session bus: {error}"))?;
+    let names = DBusProxy::new(&connection)
+        .map_err(zbus::fdo::Error::from)
+        .and_then(|proxy| proxy.list_names())
+        .map_err(|error| format!("Failed to list media players: {error}"))?;
+
+    let mut paused = Vec::new();
+    for name in player_names(names.iter().map(|name| name.as_str())) {
+        let Ok(player) = player_proxy(&connection, name) else {
+            continue;
+        };
+        let playing = player
+            .get_property::<String>("PlaybackStatus")
+            .is_ok_and(|status| status == PLAYING);
+        if playing && player.call_method("Pause", &()).is_ok() {
+            paused.push(name.to_string());
+        }
+    }
+    Ok((!paused.is_empty()).then_some(PausedMedia(paused)))
+}
+
+pub fn resume(media: PausedMedia) -> Result<(), String> {
+    let connection = Connection::session()
+        .map_err(|error| format!("Failed to connect to the session bus: {error}"))?;
+    for name in &media.0 {
+        // A player that quit while we were recording has nothing to resume.
+        player_proxy(&connection, name)
+            .and_then(|player| player.call_method("Play", &()).map(|_| ()))
+            .map_err(|error| format!("Failed to resume {name}: {error}"))?;
+    }
+    Ok(())
+}
+
+fn player_proxy<'a>(connection: &Connection, name: &'a str) -> zbus::Result<Proxy<'a>> {
+    Proxy::new(connection, name, MPRIS_PATH, MPRIS_PLAYER_INTERFACE)
+}
+
+fn player_names<'a>(names: impl Iterator<Item = &'a str>) -> impl Iterator<Item = &'a str> {
+    names.filter(|name| name.starts_with(MPRIS_NAME_PREFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::player_names;
+
+    #[test]
+    fn only_mpris_players_are_considered() {
+        let names = [
+            "org.freedesktop.DBus",
+            "org.mpris.MediaPlayer2.spotify",
+            ":1.42",
+            "org.mpris.MediaPlayer2.firefox.instance_1_23",
+        ];
+
+        assert_eq!(
+            player_names(names.into_iter()).collect::<Vec<_>>(),
+            [
+                "org.mpris.MediaPlayer2.spotify",
+                "org.mpris.MediaPlayer2.firefox.instance_1_23"
+            ]
+        );
+    }
+}
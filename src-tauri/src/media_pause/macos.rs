@@ -0,0 +1,131 @@
+use std::{
+    ffi::{c_char, c_void, CString},
+    ptr,
+    sync::{mpsc, OnceLock},
+    time::Duration,
+};
+
+use block2::{Block, RcBlock};
+
+type CFTypeRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFUrlRef = *const c_void;
+type CFBundleRef = *const c_void;
+type Boolean = u8;
+type SendCommandFn = unsafe extern "C" fn(command: u32, options: *const c_void) -> Boolean;
+type IsPlayingFn = unsafe extern "C" fn(queue: *mut c_void, completion: *const c_void);
+
+// MediaRemote is private, so it is looked up at runtime and the feature turns off without it.
+const MEDIA_REMOTE_PATH: &str = "/System/Library/PrivateFrameworks/MediaRemote.framework";
+const COMMAND_PLAY: u32 = 0;
+const COMMAND_PAUSE: u32 = 1;
+const IS_PLAYING_TIMEOUT: Duration = Duration::from_secs(1);
+const UTF8_ENCODING: u32 = 0x0800_0100;
+const POSIX_PATH_STYLE: isize = 0;
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    fn CFStringCreateWithCString(
+        allocator: *const c_void,
+        value: *const c_char,
+        encoding: u32,
+    ) -> CFStringRef;
+    fn CFURLCreateWithFileSystemPath(
+        allocator: *const c_void,
+        path: CFStringRef,
+        path_style: isize,
+        is_directory: Boolean,
+    ) -> CFUrlRef;
+    fn CFBundleCreate(allocator: *const c_void, url: CFUrlRef) -> CFBundleRef;
+    fn CFBundleGetFunctionPointerForName(bundle: CFBundleRef, name: CFStringRef) -> *const c_void;
+    fn CFRelease(value: CFTypeRef);
+}
+
+unsafe extern "C" {
+    fn dispatch_get_global_queue(identifier: isize, flags: usize) -> *mut c_void;
+}
+
+pub struct PausedMedia;
+
+struct MediaRemote {
+    send_command: SendCommandFn,
+    is_playing: IsPlayingFn,
+}
+
+pub fn pause_playing() -> Result<Option<PausedMedia>, String> {
+    let remote = media_remote().ok_or_else(|| "MediaRemote is unavailable".to_string())?;
+    if !is_playing(remote)? {
+        return Ok(None);
+    }
+    send_command(remote, COMMAND_PAUSE)?;
+    Ok(Some(PausedMedia))
+}
+
+pub fn resume(_media: PausedMedia) -> Result<(), String> {
+    let remote = media_remote().ok_or_else(|| "MediaRemote is unavailable".to_string())?;
+    send_command(remote, COMMAND_PLAY)
+}
+
+fn is_playing(remote: &MediaRemote) -> Result<bool, String> {
+    let (tx, rx) = mpsc::channel::<bool>();
+    let completion: RcBlock<dyn Fn(Boolean)> = RcBlock::new(move |playing: Boolean| {
+        let _ = tx.send(playing != 0);
+    });
+    unsafe {
+        (remote.is_playing)(
+            dispatch_get_global_queue(0, 0),
+            &*completion as *const Block<dyn Fn(Boolean)> as *const c_void,
+        );
+    }
+    rx.recv_timeout(IS_PLAYING_TIMEOUT)
+        .map_err(|_| "Timed out asking whether media is playing".to_string())
+}
+
+fn send_command(remote: &MediaRemote, command: u32) -> Result<(), String> {
+    if unsafe { (remote.send_command)(command, ptr::null()) } == 0 {
+        return Err(format!("MediaRemote rejected command {command}"));
+    }
+    Ok(())
+}
+
+fn media_remote() -> Option<&'static MediaRemote> {
+    static MEDIA_REMOTE: OnceLock<Option<MediaRemote>> = OnceLock::new();
+    MEDIA_REMOTE
+        .get_or_init(|| unsafe { load_media_remote() })
+        .as_ref()
+}
+
+// The bundle is never released, so the function pointers stay valid.
+unsafe fn load_media_remote() -> Option<MediaRemote> {
+    let path = cf_string(MEDIA_REMOTE_PATH)?;
+    let url = CFURLCreateWithFileSystemPath(ptr::null(), path, POSIX_PATH_STYLE, 1);
+    CFRelease(path);
+    if url.is_null() {
+        return None;
+    }
+    let bundle = CFBundleCreate(ptr::null(), url);
+    CFRelease(url);
+    if bundle.is_null() {
+        return None;
+    }
+
+    let send_command = function_pointer(bundle, "MRMediaRemoteSendCommand")?;
+    let is_playing = function_pointer(bundle, "MRMediaRemoteGetNowPlayingApplicationIsPlaying")?;
+    Some(MediaRemote {
+        send_command: std::mem::transmute::<*const c_void, SendCommandFn>(send_command),
+        is_playing: std::mem::transmute::<*const c_void, IsPlayingFn>(is_playing),
+    })
+}
+
+unsafe fn function_pointer(bundle: CFBundleRef, name: &str) -> Option<*const c_void> {
+    let name = cf_string(name)?;
+    let pointer = CFBundleGetFunctionPointerForName(bundle, name);
+    CFRelease(name);
+    (!pointer.is_null()).then_some(pointer)
+}
+
+unsafe fn cf_string(value: &str) -> Option<CFStringRef> {
+    let value = CString::new(value).ok()?;
+    let string = CFStringCreateWithCString(ptr::null(), value.as_ptr(), UTF8_ENCODING);
+    (!string.is_null()).then_some(string)
+}
@@ -0,0 +1,117 @@
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, warn};
+
+use crate::{status_notifier::AppStatus, AppState};
+
+#[cfg(target_os = "linux")]
+use linux as platform;
+#[cfg(target_os = "macos")]
+use macos as platform;
+#[cfg(target_os = "windows")]
+use windows as platform;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MediaCommand {
+    Pause,
+    Resume,
+}
+
+// Media calls can block on the session bus or the system media service.
+#[derive(Debug, Default)]
+pub struct MediaPause {
+    worker: Mutex<Option<Sender<MediaCommand>>>,
+    recording: AtomicBool,
+}
+
+impl MediaPause {
+    pub fn on_status(&self, app: &AppHandle, status: AppStatus) {
+        let recording = status == AppStatus::Listening;
+        if self.recording.swap(recording, Ordering::Relaxed) == recording {
+            return;
+        }
+        if !recording {
+            self.send(MediaCommand::Resume);
+            return;
+        }
+        if app
+            .state::<AppState>()
+            .services
+            .settings_store
+            .current()
+            .pause_media_while_recording
+        {
+            self.send(MediaCommand::Pause);
+        }
+    }
+
+    fn send(&self, command: MediaCommand) {
+        let Ok(mut worker) = self.worker.lock() else {
+            warn!("media pause worker lock is poisoned");
+            return;
+        };
+        let sender = worker.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || run_worker(receiver));
+            sender
+        });
+        if sender.send(command).is_err() {
+            warn!(?command, "media pause worker stopped");
+            *worker = None;
+        }
+    }
+}
+
+fn run_worker(commands: Receiver<MediaCommand>) {
+    let mut paused: Option<platform::PausedMedia> = None;
+    for command in commands {
+        match command {
+            MediaCommand::Pause if paused.is_none() => match platform::pause_playing() {
+                Ok(Some(media)) => {
+                    info!("paused media playback for recording");
+                    paused = Some(media);
+                }
+                Ok(None) => debug!("no media playing to pause"),
+                Err(error) => warn!(%error, "failed to pause media playback"),
+            },
+            MediaCommand::Pause => {}
+            MediaCommand::Resume => {
+                let Some(media) = paused.take() else {
+                    continue;
+                };
+                match platform::resume(media) {
+                    Ok(()) => info!("resumed media playback after recording"),
+                    Err(error) => warn!(%error, "failed to resume media playback"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub struct PausedMedia;
+
+    pub fn pause_playing() -> Result<Option<PausedMedia>, String> {
+        Ok(None)
+    }
+
+    pub fn resume(_media: PausedMedia) -> Result<(), String> {
+        Ok(())
+    }
+}
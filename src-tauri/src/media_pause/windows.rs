@@ -0,0 +1,170 @@
+use std::{
+    ffi::c_void,
+    ptr, thread,
+    time::{Duration, Instant},
+};
+
+type HString = *mut c_void;
+type GetObjectFn = unsafe extern "system" fn(*mut c_void, *mut *mut c_void) -> i32;
+type GetStatusFn = unsafe extern "system" fn(*mut c_void, *mut i32) -> i32;
+type ReleaseFn = unsafe extern "system" fn(*mut c_void) -> u32;
+
+#[repr(C)]
+struct Guid {
+    data1: u32,
+    data2: u16,
+    data3: u16,
+    data4: [u8; 8],
+}
+
+const MANAGER_CLASS: &str =
+    "Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager";
+const MANAGER_STATICS_IID: Guid = Guid {
+    data1: 0x2050_c4ee,
+    data2: 0x11a0,
+    data3: 0x57de,
+    data4: [0xae, 0xd7, 0xc9, 0x7c, 0x70, 0x33, 0x82, 0x45],
+};
+const RO_INIT_MULTITHREADED: i32 = 1;
+const E_ILLEGAL_METHOD_CALL: i32 = 0x8000_000e_u32 as i32;
+const PLAYBACK_STATUS_PLAYING: i32 = 4;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// Vtable slots; every interface starts with IUnknown (0-2) and IInspectable (3-5).
+const RELEASE: usize = 2;
+const MANAGER_STATICS_REQUEST_ASYNC: usize = 6;
+const ASYNC_OPERATION_GET_RESULTS: usize = 8;
+const MANAGER_GET_CURRENT_SESSION: usize = 6;
+const SESSION_GET_PLAYBACK_INFO: usize = 9;
+const SESSION_TRY_PLAY_ASYNC: usize = 10;
+const SESSION_TRY_PAUSE_ASYNC: usize = 11;
+const PLAYBACK_INFO_PLAYBACK_STATUS: usize = 7;
+
+#[link(name = "combase")]
+unsafe extern "system" {
+    fn RoInitialize(init_type: i32) -> i32;
+    fn RoGetActivationFactory(
+        class_id: HString,
+        iid: *const Guid,
+        factory: *mut *mut c_void,
+    ) -> i32;
+    fn WindowsCreateString(source: *const u16, length: u32, string: *mut HString) -> i32;
+    fn WindowsDeleteString(string: HString) -> i32;
+}
+
+pub struct PausedMedia;
+
+pub fn pause_playing() -> Result<Option<PausedMedia>, String> {
+    unsafe {
+        let Some(session) = current_session()? else {
+            return Ok(None);
+        };
+        let playback_info = session.get(SESSION_GET_PLAYBACK_INFO, "playback info")?;
+        let playback_status: GetStatusFn = playback_info.slot(PLAYBACK_INFO_PLAYBACK_STATUS);
+        let mut status = 0;
+        check(
+            playback_status(playback_info.0, &mut status),
+            "playback status",
+        )?;
+        if status != PLAYBACK_STATUS_PLAYING {
+            return Ok(None);
+        }
+        // The operation only reports whether the player accepted, so it is not awaited.
+        session.get(SESSION_TRY_PAUSE_ASYNC, "pause request")?;
+        Ok(Some(PausedMedia))
+    }
+}
+
+pub fn resume(_media: PausedMedia) -> Result<(), String> {
+    unsafe {
+        let Some(session) = current_session()? else {
+            return Ok(());
+        };
+        session.get(SESSION_TRY_PLAY_ASYNC, "play request")?;
+        Ok(())
+    }
+}
+
+unsafe fn current_session() -> Result<Option<ComObject>, String> {
+    // The media worker thread joins the multithreaded apartment once; later calls are no-ops.
+    RoInitialize(RO_INIT_MULTITHREADED);
+    let statics = activation_factory()?;
+    let request = statics.get(
+        MANAGER_STATICS_REQUEST_ASYNC,
+        "media session manager request",
+    )?;
+    let manager = wait_for_results(&request, "media session manager")?;
+    let get_current_session: GetObjectFn = manager.slot(MANAGER_GET_CURRENT_SESSION);
+    let mut session = ptr::null_mut();
+    check(
+        get_current_session(manager.0, &mut session),
+        "current media session",
+    )?;
+    Ok((!session.is_null()).then_some(ComObject(session)))
+}
+
+unsafe fn activation_factory() -> Result<ComObject, String> {
+    let class_name: Vec<u16> = MANAGER_CLASS.encode_utf16().collect();
+    let mut class_id = ptr::null_mut();
+    check(
+        WindowsCreateString(class_name.as_ptr(), class_name.len() as u32, &mut class_id),
+        "media session manager class name",
+    )?;
+    let mut factory = ptr::null_mut();
+    let result = RoGetActivationFactory(class_id, &MANAGER_STATICS_IID, &mut factory);
+    WindowsDeleteString(class_id);
+    check(result, "media session manager factory")?;
+    Ok(ComObject(factory))
+}
+
+unsafe fn wait_for_results(operation: &ComObject, what: &str) -> Result<ComObject, String> {
+    let get_results: GetObjectFn = operation.slot(ASYNC_OPERATION_GET_RESULTS);
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    loop {
+        let mut result = ptr::null_mut();
+        let hresult = get_results(operation.0, &mut result);
+        if hresult >= 0 && !result.is_null() {
+            return Ok(ComObject(result));
+        }
+        if hresult != E_ILLEGAL_METHOD_CALL || Instant::now() >= deadline {
+            return Err(format!("Failed to get {what}: HRESULT {hresult:#010x}"));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn check(hresult: i32, what: &str) -> Result<(), String> {
+    if hresult < 0 {
+        return Err(format!("Failed to get {what}: HRESULT {hresult:#010x}"));
+    }
+    Ok(())
+}
+
+struct ComObject(*mut c_void);
+
+impl ComObject {
+    unsafe fn slot<F: Copy>(&self, index: usize) -> F {
+        let vtable = *(self.0 as *const *const usize);
+        std::mem::transmute_copy::<usize, F>(&*vtable.add(index))
+    }
+
+    unsafe fn get(&self, index: usize, what: &str) -> Result<ComObject, String> {
+        let method: GetObjectFn = self.slot(index);
+        let mut object = ptr::null_mut();
+        check(method(self.0, &mut object), what)?;
+        if object.is_null() {
+            return Err(format!("Failed to get {what}: no object returned"));
+        }
+        Ok(ComObject(object))
+    }
+}
+
+impl Drop for ComObject {
+    fn drop(&mut self) {
+        unsafe {
+            let release: ReleaseFn = self.slot(RELEASE);
+            release(self.0);
+        }
+    }
+}
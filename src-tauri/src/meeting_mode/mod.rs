@@ -0,0 +1,631 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use tokio::sync::watch;
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    audio_capture_service::{AudioCaptureService, AudioInputChunk},
+    auth_store::AuthMethod,
+    history_store::{HistoryEntry, HistorySegment, HistoryStore},
+    resolve_transcription_prompt,
+    transcription::{
+        TranscriptionError, TranscriptionOptions, TranscriptionProvider, TranscriptionResult,
+    },
+    AppState,
+};
+
+pub const EVENT_MEETING_SEGMENT: &str = "voice://meeting-segment";
+pub const EVENT_MEETING_STATE_CHANGED: &str = "voice://meeting-state-changed";
+
+const MEETING_WINDOW_LABEL: &str = "meeting";
+const MEETING_WINDOW_WIDTH: f64 = 440.0;
+const MEETING_WINDOW_HEIGHT: f64 = 600.0;
+const MEETING_CHUNK_INTERVAL: Duration = Duration::from_secs(30);
+const MIN_TRANSCRIBABLE_CHUNK_SECS: f64 = 0.5;
+const MEETING_HISTORY_PROVIDER: &str = "meeting";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingAudioSource {
+    Microphone,
+    System,
+}
+
+impl MeetingAudioSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Microphone => "Microphone",
+            Self::System => "System audio",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingSegment {
+    pub source: MeetingAudioSource,
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingSnapshot {
+    pub is_active: bool,
+    pub started_at: Option<String>,
+    pub segments: Vec<MeetingSegment>,
+}
+
+#[derive(Debug)]
+struct ActiveMeeting {
+    started_at: String,
+    stop_tx: watch::Sender<bool>,
+}
+
+// One meeting's transcript buffer; `finished` flips once its history entry is saved.
+#[derive(Debug, Clone, Default)]
+struct MeetingRun {
+    segments: Arc<Mutex<Vec<MeetingSegment>>>,
+    finished: Arc<AtomicBool>,
+}
+
+struct RunFinishedGuard(Arc<AtomicBool>);
+
+impl Drop for RunFinishedGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+#[derive(Debug)]
+struct MeetingCapture {
+    source: MeetingAudioSource,
+    capture: AudioCaptureService,
+    elapsed_secs: f64,
+    // Diarization labels seen so far in this run, so a speaker keeps their number across chunks.
+    speakers: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct MeetingService {
+    active: Mutex<Option<ActiveMeeting>>,
+    latest_run: Mutex<Option<MeetingRun>>,
+}
+
+impl MeetingService {
+    pub fn new() -> Self {
+        debug!("meeting service initialized");
+        Self::default()
+    }
+
+    pub fn start(&self, app: &AppHandle) -> Result<MeetingSnapshot, String> {
+        let mut active = self.active.lock().map_err(|_| lock_error())?;
+        if active.is_some() {
+            return Err("A meeting is already in progress".to_string());
+        }
+        let mut latest_run = self.latest_run.lock().map_err(|_| lock_error())?;
+        if latest_run
+            .as_ref()
+            .is_some_and(|run| !run.finished.load(Ordering::Acquire))
+        {
+            return Err(
+                "The previous meeting is still being saved. Try again in a moment".to_string(),
+            );
+        }
+
+        let settings = app.state::<AppState>().services.settings_store.current();
+        let mut captures = vec![start_capture(
+            app,
+            MeetingAudioSource::Microphone,
+            settings.microphone_id.as_deref(),
+        )?];
+        if let Some(device_id) = settings.meeting_system_audio_device_id.as_deref() {
+            match start_capture(app, MeetingAudioSource::System, Some(device_id)) {
+                Ok(capture) => captures.push(capture),
+                Err(error) => warn!(%error, device_id, "meeting system audio capture unavailable"),
+            }
+        }
+
+        let run = MeetingRun::default();
+        let started_at = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let (stop_tx, stop_rx) = watch::channel(false);
+        tauri::async_runtime::spawn(run_meeting(app.clone(), captures, run.clone(), stop_rx));
+        *latest_run = Some(run);
+        *active = Some(ActiveMeeting {
+            started_at: started_at.clone(),
+            stop_tx,
+        });
+        drop(latest_run);
+        drop(active);
+
+        info!(%started_at, "meeting started");
+        show_meeting_window(app);
+        let snapshot = self.snapshot()?;
+        emit_meeting_state_changed(app, &snapshot);
+        Ok(snapshot)
+    }
+
+    pub fn stop(&self) -> Result<bool, String> {
+        let Some(active) = self.active.lock().map_err(|_| lock_error())?.take() else {
+            return Ok(false);
+        };
+
+        let _ = active.stop_tx.send(true);
+        info!(started_at = %active.started_at, "meeting stop requested");
+        Ok(true)
+    }
+
+    pub fn snapshot(&self) -> Result<MeetingSnapshot, String> {
+        let started_at = self
+            .active
+            .lock()
+            .map_err(|_| lock_error())?
+            .as_ref()
+            .map(|active| active.started_at.clone());
+        let segments = match self.latest_run.lock().map_err(|_| lock_error())?.as_ref() {
+            Some(run) => run.segments.lock().map_err(|_| lock_error())?.clone(),
+            None => Vec::new(),
+        };
+
+        Ok(MeetingSnapshot {
+            is_active: started_at.is_some(),
+            started_at,
+            segments,
+        })
+    }
+}
+
+#[tauri::command]
+pub fn start_meeting(
+    app: AppHandle,
+    service: State<'_, MeetingService>,
+) -> Result<MeetingSnapshot, String> {
+    service.start(&app)
+}
+
+#[tauri::command]
+pub fn stop_meeting(service: State<'_, MeetingService>) -> Result<bool, String> {
+    service.stop()
+}
+
+#[tauri::command]
+pub fn get_meeting_snapshot(service: State<'_, MeetingService>) -> Result<MeetingSnapshot, String> {
+    service.snapshot()
+}
+
+fn start_capture(
+    app: &AppHandle,
+    source: MeetingAudioSource,
+    device_id: Option<&str>,
+) -> Result<MeetingCapture, String> {
+    let capture = AudioCaptureService::new();
+    capture.start_recording(app.clone(), device_id, None)?;
+
+    if source == MeetingAudioSource::System {
+        let selected_device_id = capture
+            .debug_snapshot()?
+            .map(|snapshot| snapshot.device_id)
+            .unwrap_or_default();
+        if Some(selected_device_id.as_str()) != device_id {
+            let _ = capture.abort_recording(app.clone());
+            return Err(format!(
+                "System audio device `{}` is not available",
+                device_id.unwrap_or_default()
+            ));
+        }
+    }
+
+    info!(?source, device_id = ?device_id, "meeting audio capture started");
+    Ok(MeetingCapture {
+        source,
+        capture,
+        elapsed_secs: 0.0,
+        speakers: Vec::new(),
+    })
+}
+
+async fn run_meeting(
+    app: AppHandle,
+    mut captures: Vec<MeetingCapture>,
+    run: MeetingRun,
+    mut stop_rx: watch::Receiver<bool>,
+) {
+    let finished = RunFinishedGuard(Arc::clone(&run.finished));
+    let segments = run.segments;
+    let mut chunk_timer = tokio::time::interval(MEETING_CHUNK_INTERVAL);
+    chunk_timer.tick().await;
+
+    loop {
+        let stopping = tokio::select! {
+            _ = chunk_timer.tick() => false,
+            _ = stop_rx.changed() => true,
+        };
+
+        for capture in captures.iter_mut() {
+            let chunk = if stopping {
+                capture
+                    .capture
+                    .stop_recording(app.clone())
+                    .map(|recorded| Some(recorded.into_input_chunk()))
+            } else {
+                capture.capture.drain_buffered_samples()
+            };
+
+            match chunk {
+                Ok(Some(chunk)) => transcribe_chunk(&app, capture, chunk, &segments).await,
+                Ok(None) => {}
+                Err(error) => {
+                    warn!(source = ?capture.source, %error, "failed to collect meeting audio")
+                }
+            }
+        }
+
+        if stopping {
+            break;
+        }
+    }
+
+    let duration_secs = captures
+        .iter()
+        .map(|capture| capture.elapsed_secs)
+        .fold(0.0_f64, f64::max);
+    save_meeting_to_history(&app, &segments, duration_secs);
+    drop(finished);
+
+    match app.state::<MeetingService>().snapshot() {
+        Ok(snapshot) => emit_meeting_state_changed(&app, &snapshot),
+        Err(error) => warn!(%error, "failed to read meeting snapshot after stop"),
+    }
+    info!(duration_secs, "meeting finished");
+}
+
+async fn transcribe_chunk(
+    app: &AppHandle,
+    capture: &mut MeetingCapture,
+    chunk: AudioInputChunk,
+    segments: &Arc<Mutex<Vec<MeetingSegment>>>,
+) {
+    let chunk_secs = chunk.duration_secs();
+    let start_secs = capture.elapsed_secs;
+    capture.elapsed_secs += chunk_secs;
+    if chunk_secs < MIN_TRANSCRIBABLE_CHUNK_SECS {
+        debug!(source = ?capture.source, chunk_secs, "skipping short meeting chunk");
+        return;
+    }
+
+    let transcription = match chunk.to_wav_bytes() {
+        Ok(wav_bytes) => transcribe_wav(app, wav_bytes).await,
+        Err(error) => Err(error),
+    };
+    let transcription = match transcription {
+        Ok(transcription) => transcription,
+        Err(error) => {
+            error!(source = ?capture.source, start_secs, %error, "meeting chunk transcription failed");
+            return;
+        }
+    };
+
+    let chunk_segments = chunk_segments(
+        capture.source,
+        start_secs,
+        capture.elapsed_secs,
+        transcription,
+        &mut capture.speakers,
+    );
+    for segment in chunk_segments {
+        debug!(
+            source = ?segment.source,
+            start_secs = segment.start_secs,
+            speaker = ?segment.speaker,
+            transcript_chars = segment.text.chars().count(),
+            "meeting segment transcribed"
+        );
+        if let Err(error) = app.emit(EVENT_MEETING_SEGMENT, &segment) {
+            warn!(%error, "failed to emit meeting segment event");
+        }
+        match segments.lock() {
+            Ok(mut segments) => segments.push(segment),
+            Err(_) => error!("meeting segment lock poisoned while appending segment"),
+        }
+    }
+}
+
+fn chunk_segments(
+    source: MeetingAudioSource,
+    start_secs: f64,
+    end_secs: f64,
+    transcription: TranscriptionResult,
+    speakers: &mut Vec<String>,
+) -> Vec<MeetingSegment> {
+    if transcription.segments.is_empty() {
+        let text = transcription.text.trim().to_string();
+        if text.is_empty() {
+            return Vec::new();
+        }
+        return vec![MeetingSegment {
+            source,
+            start_secs,
+            end_secs,
+            speaker: None,
+            text,
+        }];
+    }
+
+    transcription
+        .segments
+        .into_iter()
+        .map(|segment| {
+            let speaker = segment.speaker.map(|raw_speaker| {
+                let index = match speakers.iter().position(|known| *known == raw_speaker) {
+                    Some(index) => index,
+                    None => {
+                        speakers.push(raw_speaker);
+                        speakers.len() - 1
+                    }
+                };
+                format!("Speaker {}", index + 1)
+            });
+
+            MeetingSegment {
+                source,
+                start_secs: start_secs + segment.start_secs,
+                end_secs: start_secs + segment.end_secs,
+                speaker,
+                text: segment.text,
+            }
+        })
+        .collect()
+}
+
+async fn transcribe_wav(
+    app: &AppHandle,
+    wav_bytes: Vec<u8>,
+) -> Result<TranscriptionResult, String> {
+    let state = app.state::<AppState>();
+    let settings = state.services.settings_store.current();
+    let options = TranscriptionOptions {
+        language: settings.language,
+        prompt: resolve_transcription_prompt(
+            &settings.transcription_style,
+            &settings.custom_transcription_prompt,
+        ),
+        diarize: settings.meeting_diarization_enabled,
+        ..TranscriptionOptions::default()
+    };
+    let auth_method = state
+        .services
+        .current_auth_method()
+        .map_err(|error| format!("Failed to resolve active auth method: {error}"))?;
+    let orchestrator = state.services.transcription_orchestrator.clone();
+    let chatgpt_provider = state.services.chatgpt_transcription_provider.clone();
+
+    let result = match auth_method {
+        AuthMethod::ApiKey => orchestrator.transcribe(wav_bytes.into(), options).await,
+        AuthMethod::ChatgptOauth => chatgpt_provider.transcribe(wav_bytes.into(), options).await,
+        AuthMethod::None => Err(TranscriptionError::Provider(
+            "No authentication configured. Add an OpenAI API key or login with ChatGPT."
+                .to_string(),
+        )),
+    };
+
+    result.map_err(|error| error.to_string())
+}
+
+fn save_meeting_to_history(
+    app: &AppHandle,
+    segments: &Arc<Mutex<Vec<MeetingSegment>>>,
+    duration_secs: f64,
+) {
+    let history_segments = match segments.lock() {
+        Ok(segments) => history_segments(&segments),
+        Err(_) => {
+            error!("meeting segment lock poisoned while saving history");
+            return;
+        }
+    };
+    if history_segments.is_empty() {
+        info!("meeting produced no transcript; skipping history entry");
+        return;
+    }
+
+    let language = app
+        .state::<AppState>()
+        .services
+        .settings_store
+        .current()
+        .language;
+    let entry = HistoryEntry::new(
+        String::new(),
+        Some(duration_secs),
+        language,
+        MEETING_HISTORY_PROVIDER.to_string(),
+    )
+    .with_segments(history_segments);
+    if let Err(error) = app.state::<HistoryStore>().add_entry(entry) {
+        error!(%error, "failed to save meeting transcript to history");
+    }
+}
+
+fn history_segments(segments: &[MeetingSegment]) -> Vec<HistorySegment> {
+    let mut ordered = segments.iter().collect::<Vec<_>>();
+    ordered.sort_by(|left, right| left.start_secs.total_cmp(&right.start_secs));
+    let has_multiple_sources = ordered
+        .windows(2)
+        .any(|pair| pair[0].source != pair[1].source);
+
+    ordered
+        .into_iter()
+        .map(|segment| HistorySegment {
+            start_secs: segment.start_secs,
+            end_secs: segment.end_secs,
+            speaker: segment
+                .speaker
+                .clone()
+                .or_else(|| has_multiple_sources.then(|| segment.source.label().to_string())),
+            text: segment.text.clone(),
+        })
+        .collect()
+}
+
+fn show_meeting_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MEETING_WINDOW_LABEL) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+
+    if let Err(error) = WebviewWindowBuilder::new(
+        app,
+        MEETING_WINDOW_LABEL,
+        WebviewUrl::App("meeting.html".into()),
+    )
+    .title("Buzz Meeting")
+    .inner_size(MEETING_WINDOW_WIDTH, MEETING_WINDOW_HEIGHT)
+    .build()
+    {
+        warn!(%error, "failed to open meeting window");
+    }
+}
+
+fn emit_meeting_state_changed(app: &AppHandle, snapshot: &MeetingSnapshot) {
+    if let Err(error) = app.emit(EVENT_MEETING_STATE_CHANGED, snapshot) {
+        warn!(%error, "failed to emit meeting state changed event");
+    }
+}
+
+fn lock_error() -> String {
+    "Meeting state lock is poisoned".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chunk_segments, history_segments, MeetingAudioSource, MeetingSegment};
+    use crate::{
+        history_store::render_segment_transcript,
+        transcription::{TranscriptionResult, TranscriptionSegment},
+    };
+
+    fn segment(source: MeetingAudioSource, start_secs: f64, text: &str) -> MeetingSegment {
+        MeetingSegment {
+            source,
+            start_secs,
+            end_secs: start_secs + 30.0,
+            speaker: None,
+            text: text.to_string(),
+        }
+    }
+
+    fn format_meeting_transcript(segments: &[MeetingSegment]) -> String {
+        render_segment_transcript(&history_segments(segments))
+    }
+
+    #[test]
+    fn single_source_transcripts_omit_source_labels() {
+        let transcript = format_meeting_transcript(&[
+            segment(MeetingAudioSource::Microphone, 30.0, "second"),
+            segment(MeetingAudioSource::Microphone, 0.0, "first"),
+        ]);
+
+        assert_eq!(transcript, "[00:00:00] first\n[00:00:30] second");
+    }
+
+    #[test]
+    fn mixed_source_transcripts_are_labeled_and_ordered() {
+        let transcript = format_meeting_transcript(&[
+            segment(MeetingAudioSource::Microphone, 0.0, "hello"),
+            segment(MeetingAudioSource::System, 0.0, "hi there"),
+            segment(MeetingAudioSource::Microphone, 30.0, "bye"),
+        ]);
+
+        assert_eq!(
+            transcript,
+            "[00:00:00] Microphone: hello\n[00:00:00] System audio: hi there\n[00:00:30] Microphone: bye"
+        );
+    }
+
+    #[test]
+    fn diarized_chunks_number_speakers_consistently_across_a_run() {
+        let diarized_segment = |start_secs: f64, speaker: &str, text: &str| TranscriptionSegment {
+            start_secs,
+            end_secs: start_secs + 2.0,
+            text: text.to_string(),
+            speaker: Some(speaker.to_string()),
+        };
+        let mut speakers = Vec::new();
+        let segments = chunk_segments(
+            MeetingAudioSource::Microphone,
+            30.0,
+            60.0,
+            TranscriptionResult {
+                text: "Hi. Hello. Bye.".to_string(),
+                segments: vec![
+                    diarized_segment(0.0, "B", "Hi."),
+                    diarized_segment(3.0, "A", "Hello."),
+                    diarized_segment(6.0, "B", "Bye."),
+                ],
+                ..TranscriptionResult::default()
+            },
+            &mut speakers,
+        );
+        let next_chunk = chunk_segments(
+            MeetingAudioSource::Microphone,
+            60.0,
+            90.0,
+            TranscriptionResult {
+                text: "Back. Again.".to_string(),
+                segments: vec![
+                    diarized_segment(0.0, "A", "Back."),
+                    diarized_segment(2.0, "C", "Again."),
+                ],
+                ..TranscriptionResult::default()
+            },
+            &mut speakers,
+        );
+
+        assert_eq!(segments[1].start_secs, 33.0);
+        assert_eq!(
+            format_meeting_transcript(&segments),
+            "[00:00:30] Speaker 1: Hi.\n[00:00:33] Speaker 2: Hello.\n[00:00:36] Speaker 1: Bye."
+        );
+        assert_eq!(
+            format_meeting_transcript(&next_chunk),
+            "[00:01:00] Speaker 2: Back.\n[00:01:02] Speaker 3: Again."
+        );
+    }
+
+    #[test]
+    fn undiarized_chunks_become_a_single_segment() {
+        let segments = chunk_segments(
+            MeetingAudioSource::System,
+            0.0,
+            30.0,
+            TranscriptionResult {
+                text: "  all together  ".to_string(),
+                ..TranscriptionResult::default()
+            },
+            &mut Vec::new(),
+        );
+
+        assert_eq!(
+            segments,
+            vec![MeetingSegment {
+                source: MeetingAudioSource::System,
+                start_secs: 0.0,
+                end_secs: 30.0,
+                speaker: None,
+                text: "all together".to_string(),
+            }]
+        );
+    }
+}
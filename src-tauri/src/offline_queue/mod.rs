@@ -0,0 +1,438 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    auth_store::AuthMethod,
+    history_store::{HistoryEntry, HistoryStore},
+    persistence::JsonDocumentStore,
+    resolve_transcription_prompt,
+    transcription::{TranscriptionError, TranscriptionOptions, TranscriptionProvider},
+    AppState,
+};
+
+pub const EVENT_PENDING_QUEUE_CHANGED: &str = "voice://pending-queue-changed";
+
+const PENDING_DIR_NAME: &str = "pending_recordings";
+const PENDING_INDEX_FILE_NAME: &str = "pending.json";
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+const INTERRUPTED_BY_QUIT_REASON: &str = "Buzz quit before the transcription finished";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRecording {
+    pub id: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub duration_secs: Option<f64>,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingFlushReport {
+    pub transcribed: usize,
+    pub remaining: Vec<PendingRecording>,
+}
+
+#[derive(Debug)]
+pub struct OfflineQueue {
+    dir: PathBuf,
+    index: JsonDocumentStore<Vec<PendingRecording>>,
+    flushing: AtomicBool,
+}
+
+impl OfflineQueue {
+    pub fn from_app_data_dir(app_data_dir: &Path) -> Result<Self, String> {
+        Self::new_with_dir(app_data_dir.join(PENDING_DIR_NAME))
+    }
+
+    pub fn new_with_dir(dir: PathBuf) -> Result<Self, String> {
+        fs::create_dir_all(&dir)
+            .map_err(|error| format!("Failed to create pending recordings directory: {error}"))?;
+        debug!(path = %dir.display(), "offline queue initialized");
+        // A malformed index is an error rather than reset, which would orphan the queued audio.
+        let index = JsonDocumentStore::new(
+            dir.join(PENDING_INDEX_FILE_NAME),
+            "pending recordings index",
+        )
+        .without_recovery();
+        Ok(Self {
+            dir,
+            index,
+            flushing: AtomicBool::new(false),
+        })
+    }
+
+    pub fn enqueue(
+        &self,
+        wav_bytes: &[u8],
+        duration_secs: Option<f64>,
+        reason: &str,
+    ) -> Result<PendingRecording, String> {
+        let pending = PendingRecording {
+            id: Uuid::new_v4().to_string(),
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            duration_secs,
+            attempts: 1,
+            last_error: Some(reason.to_string()),
+        };
+
+        let _guard = self.index.lock()?;
+        fs::write(self.audio_path(&pending.id), wav_bytes)
+            .map_err(|error| format!("Failed to save pending recording audio: {error}"))?;
+        let mut recordings = self.index.read()?;
+        recordings.push(pending.clone());
+        self.index.write(&recordings)?;
+
+        info!(
+            id = %pending.id,
+            audio_bytes = wav_bytes.len(),
+            pending = recordings.len(),
+            "recording saved to offline queue"
+        );
+        Ok(pending)
+    }
+
+    pub fn list(&self) -> Result<Vec<PendingRecording>, String> {
+        let _guard = self.index.lock()?;
+        self.index.read()
+    }
+
+    pub fn read_audio(&self, id: &str) -> Result<Vec<u8>, String> {
+        fs::read(self.audio_path(id))
+            .map_err(|error| format!("Failed to read pending recording audio: {error}"))
+    }
+
+    pub fn restore(&self, recording: PendingRecording, wav_bytes: &[u8]) -> Result<bool, String> {
+        Uuid::parse_str(&recording.id)
+            .map_err(|error| format!("Invalid pending recording id `{}`: {error}", recording.id))?;
+
+        let _guard = self.index.lock()?;
+        let mut recordings = self.index.read()?;
+        if recordings
+            .iter()
+            .any(|existing| existing.id == recording.id)
+        {
+            return Ok(false);
+        }
+
+        fs::write(self.audio_path(&recording.id), wav_bytes)
+            .map_err(|error| format!("Failed to save pending recording audio: {error}"))?;
+        recordings.push(recording);
+        self.index.write(&recordings)?;
+        Ok(true)
+    }
+
+    pub fn record_failure(&self, id: &str, reason: &str) -> Result<(), String> {
+        let _guard = self.index.lock()?;
+        let mut recordings = self.index.read()?;
+        let Some(recording) = recordings.iter_mut().find(|recording| recording.id == id) else {
+            return Ok(());
+        };
+
+        recording.attempts = recording.attempts.saturating_add(1);
+        recording.last_error = Some(reason.to_string());
+        self.index.write(&recordings)
+    }
+
+    pub fn remove(&self, id: &str) -> Result<bool, String> {
+        let _guard = self.index.lock()?;
+        let mut recordings = self.index.read()?;
+        let original_len = recordings.len();
+        recordings.retain(|recording| recording.id != id);
+        if recordings.len() == original_len {
+            return Ok(false);
+        }
+
+        self.index.write(&recordings)?;
+        if let Err(error) = fs::remove_file(self.audio_path(id)) {
+            warn!(id, %error, "failed to delete pending recording audio");
+        }
+        Ok(true)
+    }
+
+    pub fn disk_usage_bytes(&self) -> u64 {
+        crate::system_health::directory_size_bytes(&self.dir)
+    }
+
+    fn audio_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.wav"))
+    }
+}
+
+#[tauri::command]
+pub fn list_pending_recordings(
+    queue: State<'_, OfflineQueue>,
+) -> Result<Vec<PendingRecording>, String> {
+    queue.list()
+}
+
+#[tauri::command]
+pub async fn flush_pending_recordings(app: AppHandle) -> Result<PendingFlushReport, String> {
+    info!("pending recordings flush requested");
+    flush(&app).await
+}
+
+#[tauri::command]
+pub fn discard_pending_recording(
+    app: AppHandle,
+    queue: State<'_, OfflineQueue>,
+    id: String,
+) -> Result<bool, String> {
+    info!(id = %id, "pending recording discard requested");
+    let removed = queue.remove(&id)?;
+    if removed {
+        emit_pending_queue_changed(&app, &queue);
+    }
+    Ok(removed)
+}
+
+pub fn enqueue_recording(
+    app: &AppHandle,
+    wav_bytes: &[u8],
+    duration_secs: Option<f64>,
+    error: &TranscriptionError,
+) -> Result<PendingRecording, String> {
+    let queue = app.state::<OfflineQueue>();
+    let pending = queue.enqueue(wav_bytes, duration_secs, &error.to_string())?;
+    emit_pending_queue_changed(app, &queue);
+    Ok(pending)
+}
+
+pub fn enqueue_interrupted_recording(
+    app: &AppHandle,
+    wav_bytes: &[u8],
+    duration_secs: Option<f64>,
+) -> Result<PendingRecording, String> {
+    let queue = app.state::<OfflineQueue>();
+    let pending = queue.enqueue(wav_bytes, duration_secs, INTERRUPTED_BY_QUIT_REASON)?;
+    emit_pending_queue_changed(app, &queue);
+    Ok(pending)
+}
+
+pub fn flush_in_background(app: &AppHandle) {
+    let has_pending = app
+        .state::<OfflineQueue>()
+        .list()
+        .map(|recordings| !recordings.is_empty())
+        .unwrap_or(false);
+    if !has_pending {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(error) = flush(&app).await {
+            warn!(%error, "background pending recordings flush failed");
+        }
+    });
+}
+
+pub fn spawn_retry_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(RETRY_INTERVAL).await;
+            flush_in_background(&app);
+        }
+    });
+}
+
+async fn flush(app: &AppHandle) -> Result<PendingFlushReport, String> {
+    let queue = app.state::<OfflineQueue>();
+    if queue.flushing.swap(true, Ordering::AcqRel) {
+        debug!("pending recordings flush already running");
+        return Ok(PendingFlushReport {
+            transcribed: 0,
+            remaining: queue.list()?,
+        });
+    }
+
+    let result = flush_pending(app, &queue).await;
+    queue.flushing.store(false, Ordering::Release);
+    emit_pending_queue_changed(app, &queue);
+
+    let transcribed = result?;
+    Ok(PendingFlushReport {
+        transcribed,
+        remaining: queue.list()?,
+    })
+}
+
+async fn flush_pending(app: &AppHandle, queue: &OfflineQueue) -> Result<usize, String> {
+    let mut transcribed = 0;
+    for pending in queue.list()? {
+        let wav_bytes = match queue.read_audio(&pending.id) {
+            Ok(wav_bytes) => wav_bytes,
+            Err(error) => {
+                error!(id = %pending.id, %error, "dropping unreadable pending recording");
+                queue.remove(&pending.id)?;
+                continue;
+            }
+        };
+
+        match transcribe_pending(app, wav_bytes).await {
+            Ok((text, language, provider)) => {
+                if !text.trim().is_empty() {
+                    let entry = HistoryEntry::new(text, pending.duration_secs, language, provider);
+                    app.state::<HistoryStore>().add_entry(entry)?;
+                }
+                queue.remove(&pending.id)?;
+                transcribed += 1;
+                info!(id = %pending.id, "pending recording transcribed");
+            }
+            Err(error) => {
+                warn!(
+                    id = %pending.id,
+                    attempts = pending.attempts + 1,
+                    %error,
+                    "pending recording transcription failed"
+                );
+                queue.record_failure(&pending.id, &error.to_string())?;
+                if matches!(error, TranscriptionError::Network(_)) {
+                    debug!("network still unavailable; pausing pending recordings flush");
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(transcribed)
+}
+
+async fn transcribe_pending(
+    app: &AppHandle,
+    wav_bytes: Vec<u8>,
+) -> Result<(String, Option<String>, String), TranscriptionError> {
+    let state = app.state::<AppState>();
+    let settings = state.services.settings_store.current();
+    let options = TranscriptionOptions {
+        language: settings.language,
+        prompt: resolve_transcription_prompt(
+            &settings.transcription_style,
+            &settings.custom_transcription_prompt,
+        ),
+        ..TranscriptionOptions::default()
+    };
+    transcribe_with_active_provider(app, wav_bytes, options).await
+}
+
+pub async fn transcribe_with_active_provider(
+    app: &AppHandle,
+    wav_bytes: Vec<u8>,
+    options: TranscriptionOptions,
+) -> Result<(String, Option<String>, String), TranscriptionError> {
+    let state = app.state::<AppState>();
+    let auth_method = state.services.current_auth_method().map_err(|error| {
+        TranscriptionError::Provider(format!("Failed to resolve active auth method: {error}"))
+    })?;
+    let orchestrator = state.services.transcription_orchestrator.clone();
+    let chatgpt_provider = state.services.chatgpt_transcription_provider.clone();
+
+    let (result, provider) = match auth_method {
+        AuthMethod::ApiKey => {
+            let provider = orchestrator.provider_name();
+            (
+                orchestrator.transcribe(wav_bytes.into(), options).await,
+                provider,
+            )
+        }
+        AuthMethod::ChatgptOauth => (
+            chatgpt_provider.transcribe(wav_bytes.into(), options).await,
+            "chatgpt-oauth",
+        ),
+        AuthMethod::None => {
+            return Err(TranscriptionError::Provider(
+                "No authentication configured. Add an OpenAI API key or login with ChatGPT."
+                    .to_string(),
+            ))
+        }
+    };
+
+    result.map(|result| (result.text, result.language, provider.to_string()))
+}
+
+fn emit_pending_queue_changed(app: &AppHandle, queue: &OfflineQueue) {
+    let recordings = match queue.list() {
+        Ok(recordings) => recordings,
+        Err(error) => {
+            warn!(%error, "failed to list pending recordings for change event");
+            return;
+        }
+    };
+    if let Err(error) = app.emit(EVENT_PENDING_QUEUE_CHANGED, &recordings) {
+        warn!(%error, "failed to emit pending queue changed event");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use uuid::Uuid;
+
+    use super::OfflineQueue;
+
+    fn test_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("voice-offline-queue-{}", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn enqueued_recordings_persist_across_instances() {
+        let dir = test_dir();
+        let queue = OfflineQueue::new_with_dir(dir.clone()).expect("queue should initialize");
+
+        let pending = queue
+            .enqueue(&[1, 2, 3], Some(2.5), "Network error: offline")
+            .expect("enqueue should succeed");
+        queue
+            .record_failure(&pending.id, "Network error: still offline")
+            .expect("failure should be recorded");
+
+        let reopened = OfflineQueue::new_with_dir(dir.clone()).expect("queue should reopen");
+        let recordings = reopened.list().expect("list should succeed");
+        assert_eq!(recordings.len(), 1);
+        assert_eq!(recordings[0].id, pending.id);
+        assert_eq!(recordings[0].attempts, 2);
+        assert_eq!(
+            recordings[0].last_error.as_deref(),
+            Some("Network error: still offline")
+        );
+        assert_eq!(
+            reopened.read_audio(&pending.id).expect("audio should read"),
+            vec![1, 2, 3]
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn remove_deletes_index_entry_and_audio() {
+        let dir = test_dir();
+        let queue = OfflineQueue::new_with_dir(dir.clone()).expect("queue should initialize");
+        let pending = queue
+            .enqueue(&[4, 5], None, "Network error: offline")
+            .expect("enqueue should succeed");
+
+        assert!(queue.remove(&pending.id).expect("remove should succeed"));
+        assert!(!queue
+            .remove(&pending.id)
+            .expect("second remove should succeed"));
+        assert!(queue.list().expect("list should succeed").is_empty());
+        assert!(queue.read_audio(&pending.id).is_err());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
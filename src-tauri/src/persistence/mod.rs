@@ -0,0 +1,525 @@
+use std::{
+    borrow::Borrow,
+    fs,
+    io::Write,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+const FILE_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const FILE_LOCK_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+pub type Migration<T> = fn(&mut T);
+pub type Normalize<T> = fn(T) -> Result<T, String>;
+
+pub struct JsonDocumentStore<T: 'static> {
+    file_path: PathBuf,
+    label: &'static str,
+    lock: Mutex<()>,
+    lock_timeout: Duration,
+    migrations: &'static [Migration<T>],
+    normalize: Option<Normalize<T>>,
+    recover_malformed: bool,
+    _document: PhantomData<fn() -> T>,
+}
+
+impl<T> std::fmt::Debug for JsonDocumentStore<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonDocumentStore")
+            .field("file_path", &self.file_path)
+            .field("label", &self.label)
+            .field("schema_version", &self.schema_version())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> JsonDocumentStore<T> {
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    pub fn schema_version(&self) -> u64 {
+        self.migrations.len() as u64 + 1
+    }
+
+    pub fn lock(&self) -> Result<DocumentLock<'_>, String> {
+        let guard = self
+            .lock
+            .lock()
+            .map_err(|_| format!("The {} store lock is poisoned", self.label))?;
+        let file = acquire_file_lock(&self.file_path, self.lock_timeout)?;
+        Ok(DocumentLock {
+            _file: file,
+            _guard: guard,
+        })
+    }
+
+    // Panic hooks must not wait on a thread that may never release the lock.
+    pub fn try_lock(&self) -> Option<DocumentLock<'_>> {
+        let guard = self.lock.try_lock().ok()?;
+        let file = acquire_file_lock(&self.file_path, Duration::ZERO).ok()?;
+        Some(DocumentLock {
+            _file: file,
+            _guard: guard,
+        })
+    }
+}
+
+pub struct DocumentLock<'a> {
+    _file: fs::File,
+    _guard: MutexGuard<'a, ()>,
+}
+
+impl<T: Serialize + DeserializeOwned + Default> JsonDocumentStore<T> {
+    pub fn new(file_path: PathBuf, label: &'static str) -> Self {
+        Self {
+            file_path,
+            label,
+            lock: Mutex::new(()),
+            lock_timeout: FILE_LOCK_TIMEOUT,
+            migrations: &[],
+            normalize: None,
+            recover_malformed: true,
+            _document: PhantomData,
+        }
+    }
+
+    pub fn with_migrations(mut self, migrations: &'static [Migration<T>]) -> Self {
+        self.migrations = migrations;
+        self
+    }
+
+    pub fn with_normalize(mut self, normalize: Normalize<T>) -> Self {
+        self.normalize = Some(normalize);
+        self
+    }
+
+    pub fn without_recovery(mut self) -> Self {
+        self.recover_malformed = false;
+        self
+    }
+
+    pub fn ensure_exists(&self) -> Result<(), String> {
+        if self.file_path.exists() {
+            return Ok(());
+        }
+        self.write(&T::default())?;
+        info!(document = self.label, path = %self.file_path.display(), "created document file");
+        Ok(())
+    }
+
+    pub fn read(&self) -> Result<T, String> {
+        if !self.file_path.exists() {
+            debug!(document = self.label, path = %self.file_path.display(), "document file missing; using defaults");
+            return Ok(T::default());
+        }
+
+        let raw_contents = fs::read_to_string(&self.file_path).map_err(|error| {
+            format!(
+                "Failed to read {} file `{}`: {error}",
+                self.label,
+                self.file_path.display()
+            )
+        })?;
+        if raw_contents.trim().is_empty() {
+            return Ok(T::default());
+        }
+
+        match self.parse(&raw_contents)? {
+            Ok(document) => Ok(document),
+            Err(reason) if self.recover_malformed => {
+                self.recover(reason)?;
+                Ok(T::default())
+            }
+            Err(reason) => Err(reason),
+        }
+    }
+
+    pub fn write<D>(&self, document: &D) -> Result<(), String>
+    where
+        T: Borrow<D>,
+        D: Serialize + ?Sized,
+    {
+        let serialized = if self.migrations.is_empty() {
+            serde_json::to_vec_pretty(document)
+        } else {
+            serde_json::to_value(document).and_then(|mut value| {
+                if let Value::Object(fields) = &mut value {
+                    fields.insert(SCHEMA_VERSION_KEY.to_string(), self.schema_version().into());
+                }
+                serde_json::to_vec_pretty(&value)
+            })
+        }
+        .map_err(|error| format!("Failed to serialize {}: {error}", self.label))?;
+        write_atomic_file(&self.file_path, &serialized)
+    }
+
+    pub fn update<R>(&self, apply: impl FnOnce(&mut T) -> Result<R, String>) -> Result<R, String> {
+        let _guard = self.lock()?;
+        let mut document = self.read()?;
+        let result = apply(&mut document)?;
+        self.write(&document)?;
+        Ok(result)
+    }
+
+    fn parse(&self, raw_contents: &str) -> Result<Result<T, String>, String> {
+        let malformed = |error: &dyn std::fmt::Display| {
+            format!(
+                "Failed to parse {} file `{}`: {error}",
+                self.label,
+                self.file_path.display()
+            )
+        };
+        let mut value = match serde_json::from_str::<Value>(raw_contents) {
+            Ok(value) => value,
+            Err(error) => return Ok(Err(malformed(&error))),
+        };
+
+        let version = match &mut value {
+            Value::Object(fields) => fields
+                .remove(SCHEMA_VERSION_KEY)
+                .and_then(|version| version.as_u64())
+                .unwrap_or(1)
+                .max(1),
+            _ => 1,
+        };
+        if version > self.schema_version() {
+            return Err(format!(
+                "The {} file `{}` was written by a newer version of the app (schema {version})",
+                self.label,
+                self.file_path.display()
+            ));
+        }
+
+        let mut document = match serde_json::from_value::<T>(value) {
+            Ok(document) => document,
+            Err(error) => return Ok(Err(malformed(&error))),
+        };
+        let pending = &self.migrations[(version - 1) as usize..];
+        for migrate in pending {
+            migrate(&mut document);
+        }
+        if !pending.is_empty() {
+            info!(
+                document = self.label,
+                from = version,
+                to = self.schema_version(),
+                "migrated document schema"
+            );
+        }
+
+        let Some(normalize) = self.normalize else {
+            return Ok(Ok(document));
+        };
+        Ok(normalize(document).map_err(|error| {
+            format!(
+                "Failed to validate {} file `{}`: {error}",
+                self.label,
+                self.file_path.display()
+            )
+        }))
+    }
+
+    fn recover(&self, reason: String) -> Result<(), String> {
+        let backup_path = backup_corrupt_file(&self.file_path)?;
+        self.write(&T::default())?;
+        warn!(
+            document = self.label,
+            path = %self.file_path.display(),
+            backup = %backup_path.display(),
+            reason = %reason,
+            "recovered malformed document file"
+        );
+        Ok(())
+    }
+}
+
+fn acquire_file_lock(file_path: &Path, timeout: Duration) -> Result<fs::File, String> {
+    let lock_path = file_path.with_file_name(format!(".{}.lock", file_name(file_path)));
+    if let Some(parent_dir) = lock_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(|error| {
+            format!(
+                "Failed to create directory `{}`: {error}",
+                parent_dir.display()
+            )
+        })?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|error| {
+            format!(
+                "Failed to open lock file `{}`: {error}",
+                lock_path.display()
+            )
+        })?;
+
+    let started_at = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => break,
+            Err(fs::TryLockError::WouldBlock) if started_at.elapsed() < timeout => {
+                thread::sleep(FILE_LOCK_POLL_INTERVAL);
+            }
+            Err(fs::TryLockError::WouldBlock) => {
+                let holder = fs::read_to_string(&lock_path)
+                    .ok()
+                    .filter(|pid| !pid.trim().is_empty())
+                    .map(|pid| format!(" held by process {}", pid.trim()))
+                    .unwrap_or_default();
+                return Err(format!(
+                    "Timed out waiting for the lock on `{}`{holder}",
+                    file_path.display()
+                ));
+            }
+            Err(fs::TryLockError::Error(error)) => {
+                return Err(format!("Failed to lock `{}`: {error}", lock_path.display()));
+            }
+        }
+    }
+
+    // Records the holder so a process that times out waiting can say which one it waited on.
+    if let Err(error) = file
+        .set_len(0)
+        .and_then(|()| write!(file, "{}", std::process::id()))
+    {
+        debug!(%error, path = %lock_path.display(), "failed to record lock holder");
+    }
+    Ok(file)
+}
+
+pub fn write_atomic_file(file_path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent_dir) = file_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(|error| {
+            format!(
+                "Failed to create directory `{}`: {error}",
+                parent_dir.display()
+            )
+        })?;
+    }
+
+    let temp_path = temp_file_path_for(file_path);
+    let mut temp_file = fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&temp_path)
+        .map_err(|error| {
+            format!(
+                "Failed to create temp file `{}`: {error}",
+                temp_path.display()
+            )
+        })?;
+
+    if let Err(error) = temp_file.write_all(contents) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!(
+            "Failed to write temp file `{}`: {error}",
+            temp_path.display()
+        ));
+    }
+
+    if let Err(error) = temp_file.sync_all() {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!(
+            "Failed to flush temp file `{}`: {error}",
+            temp_path.display()
+        ));
+    }
+
+    drop(temp_file);
+
+    fs::rename(&temp_path, file_path).map_err(|error| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to finalize `{}`: {error}", file_path.display())
+    })
+}
+
+pub fn backup_corrupt_file(file_path: &Path) -> Result<PathBuf, String> {
+    let backup_path = file_path.with_file_name(format!(
+        "{}.corrupt-{}-{}.bak",
+        file_name(file_path),
+        std::process::id(),
+        timestamp_nanos()
+    ));
+
+    fs::rename(file_path, &backup_path).map_err(|error| {
+        format!(
+            "Failed to backup malformed file `{}` to `{}`: {error}",
+            file_path.display(),
+            backup_path.display()
+        )
+    })?;
+
+    Ok(backup_path)
+}
+
+fn temp_file_path_for(file_path: &Path) -> PathBuf {
+    file_path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name(file_path),
+        std::process::id(),
+        timestamp_nanos()
+    ))
+}
+
+fn file_name(file_path: &Path) -> &str {
+    file_path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("document.json")
+}
+
+fn timestamp_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Counter {
+        count: u64,
+        label: String,
+    }
+
+    fn unique_document_path(prefix: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "voice-persistence-{prefix}-{}",
+                uuid::Uuid::new_v4()
+            ))
+            .join("counter.json")
+    }
+
+    fn cleanup_document_path(path: &Path) {
+        if let Some(parent_dir) = path.parent() {
+            let _ = fs::remove_dir_all(parent_dir);
+        }
+    }
+
+    fn double_count(counter: &mut Counter) {
+        counter.count *= 2;
+    }
+
+    fn label_counter(counter: &mut Counter) {
+        counter.label = "migrated".to_string();
+    }
+
+    #[test]
+    fn malformed_documents_are_backed_up_and_reset() {
+        let path = unique_document_path("malformed");
+        let store = JsonDocumentStore::<Counter>::new(path.clone(), "counter");
+        store
+            .update(|counter| {
+                counter.count += 1;
+                Ok(())
+            })
+            .expect("update should write the document");
+        assert_eq!(store.read().expect("document should read").count, 1);
+
+        fs::write(&path, "{ not json").expect("malformed document should be written");
+        assert_eq!(
+            store.read().expect("malformed document should recover"),
+            Counter::default()
+        );
+        let backups = fs::read_dir(path.parent().expect("document has a parent"))
+            .expect("document directory should list")
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("counter.json.corrupt-")
+            })
+            .count();
+        assert_eq!(backups, 1);
+
+        fs::write(&path, "{ not json").expect("malformed document should be written");
+        let strict = JsonDocumentStore::<Counter>::new(path.clone(), "counter").without_recovery();
+        assert!(strict
+            .read()
+            .expect_err("strict stores should report malformed files")
+            .contains("Failed to parse counter file"));
+        cleanup_document_path(&path);
+    }
+
+    #[test]
+    fn migrations_run_from_the_stored_schema_version() {
+        const MIGRATIONS: &[Migration<Counter>] = &[double_count, label_counter];
+        let path = unique_document_path("migrate");
+        fs::create_dir_all(path.parent().expect("document has a parent"))
+            .expect("document directory should be created");
+        fs::write(&path, r#"{ "count": 3 }"#).expect("unversioned document should be written");
+        let store =
+            JsonDocumentStore::<Counter>::new(path.clone(), "counter").with_migrations(MIGRATIONS);
+
+        let migrated = store.read().expect("unversioned document should migrate");
+        assert_eq!(migrated.count, 6);
+        assert_eq!(migrated.label, "migrated");
+
+        store
+            .write(&migrated)
+            .expect("migrated document should write");
+        let raw: Value = serde_json::from_str(&fs::read_to_string(&path).expect("readable"))
+            .expect("written document should be JSON");
+        assert_eq!(raw[SCHEMA_VERSION_KEY], 3);
+        assert_eq!(
+            store.read().expect("current document should read"),
+            migrated
+        );
+
+        fs::write(&path, r#"{ "count": 3, "schema_version": 9 }"#)
+            .expect("newer document should be written");
+        assert!(store
+            .read()
+            .expect_err("newer schemas should not be read")
+            .contains("newer version"));
+        assert!(path.exists());
+        cleanup_document_path(&path);
+    }
+
+    #[test]
+    fn another_handle_waits_for_the_file_lock() {
+        let path = unique_document_path("lock");
+        let app = JsonDocumentStore::<Counter>::new(path.clone(), "counter");
+        let mut cli = JsonDocumentStore::<Counter>::new(path.clone(), "counter");
+        cli.lock_timeout = Duration::from_millis(50);
+
+        let held = app.lock().expect("first handle should lock");
+        let error = cli
+            .lock()
+            .err()
+            .expect("second handle should time out while the lock is held");
+        assert!(error.contains("Timed out waiting for the lock"));
+        assert!(error.contains(&format!("held by process {}", std::process::id())));
+        assert!(cli.try_lock().is_none());
+
+        drop(held);
+        cli.update(|counter| {
+            counter.count = 7;
+            Ok(())
+        })
+        .expect("second handle should lock once the first releases");
+        assert_eq!(app.read().expect("document should read").count, 7);
+        cleanup_document_path(&path);
+    }
+}
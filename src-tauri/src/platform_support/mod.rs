@@ -0,0 +1,314 @@
+#[cfg(target_os = "linux")]
+pub mod portal;
+
+use std::{env, path::Path};
+
+use serde::Serialize;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplaySession {
+    Macos,
+    Wayland,
+    X11,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyBackend {
+    GlobalShortcutPlugin,
+    GlobalShortcutsPortal,
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TypingBackend {
+    Native,
+    Wtype,
+    Ydotool,
+    Xdotool,
+    RemoteDesktopPortal,
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardBackend {
+    Native,
+    WlClipboard,
+    Xclip,
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformCapabilities {
+    pub os: String,
+    pub session: DisplaySession,
+    pub hotkeys: HotkeyBackend,
+    pub typing: TypingBackend,
+    pub clipboard: ClipboardBackend,
+    pub can_insert_text: bool,
+    pub issues: Vec<String>,
+}
+
+pub fn detect_capabilities() -> PlatformCapabilities {
+    let session = detect_session(env::consts::OS, |name| env::var(name).ok());
+    let capabilities = resolve_capabilities(
+        env::consts::OS,
+        session,
+        env::var_os("DISPLAY").is_some_and(|display| !display.is_empty()),
+        command_on_path,
+        portal_interface_available,
+    );
+    debug!(?capabilities, "platform capabilities detected");
+    capabilities
+}
+
+pub fn prefers_portal_shortcuts() -> bool {
+    detect_session(env::consts::OS, |name| env::var(name).ok()) == DisplaySession::Wayland
+        && portal_interface_available(GLOBAL_SHORTCUTS_PORTAL)
+}
+
+const GLOBAL_SHORTCUTS_PORTAL: &str = "org.freedesktop.portal.GlobalShortcuts";
+const REMOTE_DESKTOP_PORTAL: &str = "org.freedesktop.portal.RemoteDesktop";
+
+fn detect_session(os: &str, read_env: impl Fn(&str) -> Option<String>) -> DisplaySession {
+    if os == "macos" {
+        return DisplaySession::Macos;
+    }
+
+    let read_non_empty = |name: &str| read_env(name).filter(|value| !value.trim().is_empty());
+    match read_non_empty("XDG_SESSION_TYPE")
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("wayland") => return DisplaySession::Wayland,
+        Some("x11") => return DisplaySession::X11,
+        _ => {}
+    }
+
+    if read_non_empty("WAYLAND_DISPLAY").is_some() {
+        DisplaySession::Wayland
+    } else if read_non_empty("DISPLAY").is_some() {
+        DisplaySession::X11
+    } else {
+        DisplaySession::Unknown
+    }
+}
+
+fn resolve_capabilities(
+    os: &str,
+    session: DisplaySession,
+    has_xwayland: bool,
+    has_command: impl Fn(&str) -> bool,
+    has_portal: impl Fn(&str) -> bool,
+) -> PlatformCapabilities {
+    let mut issues = Vec::new();
+    let (hotkeys, typing, clipboard) = match session {
+        DisplaySession::Macos => (
+            HotkeyBackend::GlobalShortcutPlugin,
+            TypingBackend::Native,
+            ClipboardBackend::Native,
+        ),
+        DisplaySession::X11 => {
+            let typing = if has_command("xdotool") {
+                TypingBackend::Xdotool
+            } else if has_command("ydotool") {
+                TypingBackend::Ydotool
+            } else {
+                TypingBackend::Unavailable
+            };
+            let clipboard = if has_command("xclip") {
+                ClipboardBackend::Xclip
+            } else {
+                ClipboardBackend::Unavailable
+            };
+            (HotkeyBackend::GlobalShortcutPlugin, typing, clipboard)
+        }
+        DisplaySession::Wayland => {
+            let hotkeys = if has_portal(GLOBAL_SHORTCUTS_PORTAL) {
+                HotkeyBackend::GlobalShortcutsPortal
+            } else if has_xwayland {
+                issues.push(
+                    "The desktop does not offer the GlobalShortcuts portal; the hotkey only fires while an XWayland window is focused."
+                        .to_string(),
+                );
+                HotkeyBackend::GlobalShortcutPlugin
+            } else {
+                HotkeyBackend::Unavailable
+            };
+            let typing = if has_command("wtype") {
+                TypingBackend::Wtype
+            } else if has_command("ydotool") {
+                TypingBackend::Ydotool
+            } else if has_portal(REMOTE_DESKTOP_PORTAL) {
+                TypingBackend::RemoteDesktopPortal
+            } else {
+                TypingBackend::Unavailable
+            };
+            let clipboard = if has_command("wl-copy") && has_command("wl-paste") {
+                ClipboardBackend::WlClipboard
+            } else if has_xwayland && has_command("xclip") {
+                ClipboardBackend::Xclip
+            } else {
+                ClipboardBackend::Unavailable
+            };
+            (hotkeys, typing, clipboard)
+        }
+        DisplaySession::Unknown => (
+            HotkeyBackend::Unavailable,
+            TypingBackend::Unavailable,
+            ClipboardBackend::Unavailable,
+        ),
+    };
+
+    if hotkeys == HotkeyBackend::Unavailable {
+        issues.push(
+            "Global hotkeys are unavailable; use the tray menu, CLI or HTTP API to start dictation."
+                .to_string(),
+        );
+    }
+    if typing == TypingBackend::Unavailable {
+        issues.push(match session {
+            DisplaySession::Wayland => {
+                "Install wtype or ydotool to type transcripts into other apps.".to_string()
+            }
+            _ => "Install xdotool or ydotool to type transcripts into other apps.".to_string(),
+        });
+    }
+    if clipboard == ClipboardBackend::Unavailable {
+        issues.push(match session {
+            DisplaySession::Wayland => {
+                "Install wl-clipboard to paste long transcripts.".to_string()
+            }
+            _ => "Install xclip to paste long transcripts.".to_string(),
+        });
+    }
+
+    PlatformCapabilities {
+        os: os.to_string(),
+        session,
+        hotkeys,
+        typing,
+        clipboard,
+        can_insert_text: typing != TypingBackend::Unavailable
+            || clipboard != ClipboardBackend::Unavailable,
+        issues,
+    }
+}
+
+fn command_on_path(command: &str) -> bool {
+    env::var_os("PATH").is_some_and(|path| {
+        env::split_paths(&path).any(|directory| is_executable(&directory.join(command)))
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(target_os = "linux")]
+fn portal_interface_available(interface: &str) -> bool {
+    portal::interface_available(interface)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn portal_interface_available(_interface: &str) -> bool {
+    false
+}
+
+pub fn portal_trigger(shortcut: &str) -> String {
+    shortcut
+        .split('+')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.to_ascii_lowercase().as_str() {
+            "commandorcontrol" | "cmdorctrl" | "control" | "ctrl" => "CTRL".to_string(),
+            "command" | "cmd" | "super" | "meta" => "LOGO".to_string(),
+            "alt" | "option" => "ALT".to_string(),
+            "shift" => "SHIFT".to_string(),
+            "space" => "space".to_string(),
+            "enter" | "return" => "Return".to_string(),
+            "escape" | "esc" => "Escape".to_string(),
+            "tab" => "Tab".to_string(),
+            key if key.chars().count() == 1 => key.to_string(),
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        detect_session, portal_trigger, resolve_capabilities, ClipboardBackend, DisplaySession,
+        HotkeyBackend, TypingBackend, GLOBAL_SHORTCUTS_PORTAL,
+    };
+
+    #[test]
+    fn session_detection_prefers_explicit_session_type() {
+        let env = HashMap::from([
+            ("XDG_SESSION_TYPE", "x11"),
+            ("WAYLAND_DISPLAY", "wayland-0"),
+        ]);
+        let read = |name: &str| env.get(name).map(|value| value.to_string());
+
+        assert_eq!(detect_session("linux", read), DisplaySession::X11);
+        assert_eq!(
+            detect_session("linux", |name| (name == "WAYLAND_DISPLAY")
+                .then(|| "wayland-0".to_string())),
+            DisplaySession::Wayland
+        );
+        assert_eq!(detect_session("linux", |_| None), DisplaySession::Unknown);
+        assert_eq!(detect_session("macos", |_| None), DisplaySession::Macos);
+    }
+
+    #[test]
+    fn wayland_capabilities_follow_available_portals_and_tools() {
+        let with_portal = resolve_capabilities(
+            "linux",
+            DisplaySession::Wayland,
+            true,
+            |command| matches!(command, "ydotool" | "wl-copy" | "wl-paste"),
+            |interface| interface == GLOBAL_SHORTCUTS_PORTAL,
+        );
+        assert_eq!(with_portal.hotkeys, HotkeyBackend::GlobalShortcutsPortal);
+        assert_eq!(with_portal.typing, TypingBackend::Ydotool);
+        assert_eq!(with_portal.clipboard, ClipboardBackend::WlClipboard);
+        assert!(with_portal.issues.is_empty());
+
+        let bare = resolve_capabilities(
+            "linux",
+            DisplaySession::Wayland,
+            false,
+            |_| false,
+            |_| false,
+        );
+        assert_eq!(bare.hotkeys, HotkeyBackend::Unavailable);
+        assert_eq!(bare.typing, TypingBackend::Unavailable);
+        assert!(!bare.can_insert_text);
+        assert_eq!(bare.issues.len(), 3);
+    }
+
+    #[test]
+    fn portal_trigger_uses_xdg_modifier_names() {
+        assert_eq!(portal_trigger("Alt+Space"), "ALT+space");
+        assert_eq!(portal_trigger("CommandOrControl+Shift+K"), "CTRL+SHIFT+k");
+        assert_eq!(portal_trigger("Super+F5"), "LOGO+F5");
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
+
+use tracing::{debug, info, warn};
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value},
+};
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+const SESSION_INTERFACE: &str = "org.freedesktop.portal.Session";
+const GLOBAL_SHORTCUTS_INTERFACE: &str = "org.freedesktop.portal.GlobalShortcuts";
+const REMOTE_DESKTOP_INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+const DICTATION_SHORTCUT_ID: &str = "buzz-dictation";
+const REMOTE_DESKTOP_KEYBOARD: u32 = 1;
+const KEYSYM_CONTROL_L: i32 = 0xffe3;
+const KEYSYM_LOWER_V: i32 = 0x0076;
+
+static TOKEN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+type PortalOptions<'a> = HashMap<&'a str, Value<'a>>;
+
+pub fn interface_available(interface: &str) -> bool {
+    let available = Connection::session()
+        .and_then(|connection| {
+            Proxy::new(&connection, PORTAL_DESTINATION, PORTAL_PATH, interface)?
+                .get_property::<u32>("version")
+        })
+        .is_ok();
+    debug!(interface, available, "probed desktop portal interface");
+    available
+}
+
+pub struct GlobalShortcutsSession {
+    connection: Connection,
+    proxy: Proxy<'static>,
+    session_handle: OwnedObjectPath,
+}
+
+impl GlobalShortcutsSession {
+    pub fn create(on_event: impl Fn(bool) + Send + 'static) -> Result<Self, String> {
+        let connection = Connection::session()
+            .map_err(|error| format!("Failed to connect to the session bus: {error}"))?;
+        let proxy = Proxy::new(
+            &connection,
+            PORTAL_DESTINATION,
+            PORTAL_PATH,
+            GLOBAL_SHORTCUTS_INTERFACE,
+        )
+        .map_err(|error| format!("Failed to open the GlobalShortcuts portal: {error}"))?;
+        let session_handle = create_session(&connection, &proxy)?;
+
+        let signals = proxy
+            .receive_all_signals()
+            .map_err(|error| format!("Failed to subscribe to shortcut signals: {error}"))?;
+        let listened_session = session_handle.clone();
+        thread::spawn(move || {
+            for message in signals {
+                let pressed = match message.header().member().map(|member| member.as_str()) {
+                    Some("Activated") => true,
+                    Some("Deactivated") => false,
+                    _ => continue,
+                };
+                let body = message.body();
+                let Ok((session, shortcut_id, _timestamp, _options)) = body.deserialize::<(
+                    OwnedObjectPath,
+                    String,
+                    u64,
+                    HashMap<String, OwnedValue>,
+                )>() else {
+                    warn!("ignoring malformed global shortcut signal");
+                    continue;
+                };
+                if session == listened_session && shortcut_id == DICTATION_SHORTCUT_ID {
+                    on_event(pressed);
+                }
+            }
+            debug!("global shortcuts portal listener exited");
+        });
+
+        info!(session = %session_handle.as_str(), "global shortcuts portal session created");
+        Ok(Self {
+            connection,
+            proxy,
+            session_handle,
+        })
+    }
+
+    pub fn bind(&self, description: &str, preferred_trigger: &str) -> Result<(), String> {
+        let token = next_token("bind");
+        let shortcut_options = PortalOptions::from([
+            ("description", Value::from(description)),
+            ("preferred_trigger", Value::from(preferred_trigger)),
+        ]);
+        let options = PortalOptions::from([("handle_token", Value::from(token.as_str()))]);
+        let session = ObjectPath::from(&self.session_handle);
+        request(
+            &self.connection,
+            &token,
+            || {
+                self.proxy.call_method(
+                    "BindShortcuts",
+                    &(
+                        &session,
+                        vec![(DICTATION_SHORTCUT_ID, &shortcut_options)],
+                        "",
+                        &options,
+                    ),
+                )
+            },
+            "BindShortcuts",
+        )?;
+        info!(preferred_trigger, "global shortcut bound through portal");
+        Ok(())
+    }
+}
+
+impl fmt::Debug for GlobalShortcutsSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalShortcutsSession")
+            .field("session_handle", &self.session_handle.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for GlobalShortcutsSession {
+    fn drop(&mut self) {
+        close_session(&self.connection, &self.session_handle);
+    }
+}
+
+pub struct RemoteDesktopKeyboard {
+    connection: Connection,
+    proxy: Proxy<'static>,
+    session_handle: OwnedObjectPath,
+}
+
+impl RemoteDesktopKeyboard {
+    pub fn start() -> Result<Self, String> {
+        let connection = Connection::session()
+            .map_err(|error| format!("Failed to connect to the session bus: {error}"))?;
+        let proxy = Proxy::new(
+            &connection,
+            PORTAL_DESTINATION,
+            PORTAL_PATH,
+            REMOTE_DESKTOP_INTERFACE,
+        )
+        .map_err(|error| format!("Failed to open the RemoteDesktop portal: {error}"))?;
+        let session_handle = create_session(&connection, &proxy)?;
+        let session = ObjectPath::from(&session_handle);
+
+        let select_token = next_token("devices");
+        let select_options = PortalOptions::from([
+            ("handle_token", Value::from(select_token.as_str())),
+            ("types", Value::from(REMOTE_DESKTOP_KEYBOARD)),
+        ]);
+        request(
+            &connection,
+            &select_token,
+            || proxy.call_method("SelectDevices", &(&session, &select_options)),
+            "SelectDevices",
+        )?;
+
+        let start_token = next_token("start");
+        let start_options =
+            PortalOptions::from([("handle_token", Value::from(start_token.as_str()))]);
+        let results = request(
+            &connection,
+            &start_token,
+            || proxy.call_method("Start", &(&session, "", &start_options)),
+            "Start",
+        )?;
+        let devices = results
+            .get("devices")
+            .and_then(|devices| u32::try_from(devices).ok())
+            .unwrap_or_default();
+        if devices & REMOTE_DESKTOP_KEYBOARD == 0 {
+            close_session(&connection, &session_handle);
+            return Err("Keyboard access was not granted by the RemoteDesktop portal".to_string());
+        }
+
+        info!("remote desktop portal keyboard session started");
+        Ok(Self {
+            connection,
+            proxy,
+            session_handle,
+        })
+    }
+
+    pub fn type_text(&self, text: &str) -> Result<(), String> {
+        for character in text.chars() {
+            let keysym = keysym_for_char(character);
+            self.notify_keysym(keysym, true)?;
+            self.notify_keysym(keysym, false)?;
+        }
+        Ok(())
+    }
+
+    pub fn paste(&self) -> Result<(), String> {
+        self.notify_keysym(KEYSYM_CONTROL_L, true)?;
+        self.notify_keysym(KEYSYM_LOWER_V, true)?;
+        self.notify_keysym(KEYSYM_LOWER_V, false)?;
+        self.notify_keysym(KEYSYM_CONTROL_L, false)
+    }
+
+    fn notify_keysym(&self, keysym: i32, pressed: bool) -> Result<(), String> {
+        let options = PortalOptions::new();
+        self.proxy
+            .call_method(
+                "NotifyKeyboardKeysym",
+                &(
+                    ObjectPath::from(&self.session_handle),
+                    &options,
+                    keysym,
+                    u32::from(pressed),
+                ),
+            )
+            .map(|_| ())
+            .map_err(|error| {
+                format!("Failed to send key through the RemoteDesktop portal: {error}")
+            })
+    }
+}
+
+impl fmt::Debug for RemoteDesktopKeyboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteDesktopKeyboard")
+            .field("session_handle", &self.session_handle.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for RemoteDesktopKeyboard {
+    fn drop(&mut self) {
+        close_session(&self.connection, &self.session_handle);
+    }
+}
+
+fn create_session(connection: &Connection, proxy: &Proxy<'_>) -> Result<OwnedObjectPath, String> {
+    let token = next_token("create");
+    let session_token = next_token("session");
+    let options = PortalOptions::from([
+        ("handle_token", Value::from(token.as_str())),
+        ("session_handle_token", Value::from(session_token.as_str())),
+    ]);
+    request(
+        connection,
+        &token,
+        || proxy.call_method("CreateSession", &(&options,)),
+        "CreateSession",
+    )?;
+
+    // The portal derives session paths from the caller's unique name and the token we chose.
+    let session_path = format!(
+        "{PORTAL_PATH}/session/{}/{session_token}",
+        sender_path_segment(connection)?
+    );
+    OwnedObjectPath::try_from(session_path)
+        .map_err(|error| format!("Portal returned an invalid session path: {error}"))
+}
+
+fn close_session(connection: &Connection, session_handle: &OwnedObjectPath) {
+    let result = Proxy::new(
+        connection,
+        PORTAL_DESTINATION,
+        session_handle.as_str(),
+        SESSION_INTERFACE,
+    )
+    .and_then(|session| session.call_method("Close", &()));
+    if let Err(error) = result {
+        debug!(%error, "failed to close portal session");
+    }
+}
+
+fn request(
+    connection: &Connection,
+    token: &str,
+    call: impl FnOnce() -> zbus::Result<zbus::Message>,
+    method: &str,
+) -> Result<HashMap<String, OwnedValue>, String> {
+    let request_path = format!(
+        "{PORTAL_PATH}/request/{}/{token}",
+        sender_path_segment(connection)?
+    );
+    // Subscribe before calling so a fast response cannot slip past us.
+    let request_proxy = Proxy::new(
+        connection,
+        PORTAL_DESTINATION,
+        request_path.as_str(),
+        REQUEST_INTERFACE,
+    )
+    .map_err(|error| format!("Failed to watch portal request: {error}"))?;
+    let mut responses = request_proxy
+        .receive_signal("Response")
+        .map_err(|error| format!("Failed to watch portal request: {error}"))?;
+
+    call().map_err(|error| format!("Portal call {method} failed: {error}"))?;
+    let response = responses
+        .next()
+        .ok_or_else(|| format!("Portal closed before answering {method}"))?;
+    let (code, results) = response
+        .body()
+        .deserialize::<(u32, HashMap<String, OwnedValue>)>()
+        .map_err(|error| format!("Portal sent a malformed {method} response: {error}"))?;
+
+    match code {
+        0 => Ok(results),
+        1 => Err(format!(
+            "{method} was cancelled in the desktop portal dialog"
+        )),
+        _ => Err(format!("{method} failed in the desktop portal")),
+    }
+}
+
+fn sender_path_segment(connection: &Connection) -> Result<String, String> {
+    connection
+        .unique_name()
+        .map(|name| name.trim_start_matches(':').replace('.', "_"))
+        .ok_or_else(|| "Session bus connection has no unique name".to_string())
+}
+
+fn next_token(purpose: &str) -> String {
+    format!(
+        "buzz_{purpose}_{}_{}",
+        std::process::id(),
+        TOKEN_COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+fn keysym_for_char(character: char) -> i32 {
+    match character {
+        '\n' => 0xff0d,
+        '\t' => 0xff09,
+        '\u{20}'..='\u{7e}' | '\u{a0}'..='\u{ff}' => character as i32,
+        _ => 0x0100_0000 | character as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::keysym_for_char;
+
+    #[test]
+    fn keysyms_cover_latin1_control_keys_and_unicode() {
+        assert_eq!(keysym_for_char('a'), 0x61);
+        assert_eq!(keysym_for_char('é'), 0xe9);
+        assert_eq!(keysym_for_char('\n'), 0xff0d);
+        assert_eq!(keysym_for_char('€'), 0x0100_20ac);
+    }
+}
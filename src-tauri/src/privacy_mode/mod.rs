@@ -0,0 +1,246 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tracing::{debug, info, warn};
+
+use crate::AppState;
+
+pub const EVENT_PRIVACY_MODE_CHANGED: &str = "voice://privacy-mode-changed";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyModeStatus {
+    pub active: bool,
+    pub remaining_secs: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct PrivacyState {
+    active: bool,
+    expires_at: Option<Instant>,
+    // Bumped on every change so an expiry timer from an earlier toggle does nothing.
+    generation: u64,
+}
+
+impl PrivacyState {
+    fn status_at(&self, now: Instant) -> PrivacyModeStatus {
+        let remaining = self
+            .expires_at
+            .map(|expires_at| expires_at.saturating_duration_since(now));
+        let active = self.active && remaining.is_none_or(|remaining| !remaining.is_zero());
+        PrivacyModeStatus {
+            active,
+            remaining_secs: remaining
+                .filter(|_| active)
+                .map(|remaining| remaining.as_secs()),
+        }
+    }
+
+    fn set(&mut self, active: bool, duration: Option<Duration>, now: Instant) -> u64 {
+        self.active = active;
+        self.expires_at = duration.filter(|_| active).map(|duration| now + duration);
+        self.generation += 1;
+        self.generation
+    }
+
+    fn expire(&mut self, generation: u64) -> bool {
+        if generation != self.generation || !self.active {
+            return false;
+        }
+        self.set(false, None, Instant::now());
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PrivacyMode {
+    state: Mutex<PrivacyState>,
+}
+
+impl PrivacyMode {
+    pub fn status(&self) -> PrivacyModeStatus {
+        match self.state.lock() {
+            Ok(state) => state.status_at(Instant::now()),
+            Err(_) => {
+                warn!("privacy mode lock poisoned");
+                PrivacyModeStatus {
+                    active: false,
+                    remaining_secs: None,
+                }
+            }
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status().active
+    }
+}
+
+pub fn set_active(app: &AppHandle, active: bool) -> PrivacyModeStatus {
+    let duration_mins = app
+        .state::<AppState>()
+        .services
+        .settings_store
+        .current()
+        .privacy_mode_duration_mins;
+    let duration = (duration_mins > 0).then(|| Duration::from_secs(u64::from(duration_mins) * 60));
+    let generation = match app.state::<PrivacyMode>().state.lock() {
+        Ok(mut state) => state.set(active, duration, Instant::now()),
+        Err(_) => {
+            warn!("privacy mode lock poisoned");
+            return app.state::<PrivacyMode>().status();
+        }
+    };
+    info!(active, duration_mins, "privacy mode changed");
+
+    if let Some(duration) = duration.filter(|_| active) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(duration).await;
+            let expired = app
+                .state::<PrivacyMode>()
+                .state
+                .lock()
+                .is_ok_and(|mut state| state.expire(generation));
+            if expired {
+                info!("privacy mode expired");
+                notify_changed(&app);
+            }
+        });
+    }
+    notify_changed(app)
+}
+
+pub fn toggle(app: &AppHandle) -> PrivacyModeStatus {
+    let active = app.state::<PrivacyMode>().is_active();
+    set_active(app, !active)
+}
+
+fn notify_changed(app: &AppHandle) -> PrivacyModeStatus {
+    let status = app.state::<PrivacyMode>().status();
+    if let Err(error) = app.emit(EVENT_PRIVACY_MODE_CHANGED, status) {
+        warn!(%error, "failed to emit privacy mode changed event");
+    }
+    crate::refresh_tray_menu(app);
+    status
+}
+
+// Only the alpha channel changes so it still works as a macOS template image.
+pub fn badge_tray_icon(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut badged = rgba.to_vec();
+    let radius = (width.min(height) as f32 / 4.0).max(2.0);
+    let center_x = width as f32 - radius;
+    let center_y = height as f32 - radius;
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+            let index = ((y * width + x) * 4) as usize;
+            if let Some(pixel) = badged.get_mut(index..index + 4) {
+                if pixel[3] == 0 {
+                    pixel[..3].fill(0);
+                }
+                pixel[3] = u8::MAX;
+            }
+        }
+    }
+    badged
+}
+
+#[tauri::command]
+pub fn get_privacy_mode(privacy_mode: tauri::State<'_, PrivacyMode>) -> PrivacyModeStatus {
+    privacy_mode.status()
+}
+
+#[tauri::command]
+pub fn set_privacy_mode(app: AppHandle, active: bool) -> PrivacyModeStatus {
+    set_active(&app, active)
+}
+
+#[derive(Debug, Default)]
+pub struct PrivacyModeShortcut {
+    registered: Mutex<Option<String>>,
+}
+
+impl PrivacyModeShortcut {
+    pub fn sync(&self, app: &AppHandle, shortcut: Option<&str>) {
+        let Ok(mut registered) = self.registered.lock() else {
+            warn!("privacy mode shortcut lock poisoned");
+            return;
+        };
+        if registered.as_deref() == shortcut {
+            return;
+        }
+
+        if let Some(previous) = registered.take() {
+            if let Err(error) = app.global_shortcut().unregister(previous.as_str()) {
+                warn!(%error, shortcut = %previous, "failed to unregister privacy mode shortcut");
+            }
+        }
+        let Some(shortcut) = shortcut else {
+            debug!("privacy mode shortcut cleared");
+            return;
+        };
+
+        let result = app
+            .global_shortcut()
+            .on_shortcut(shortcut, |app, _shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    toggle(app);
+                }
+            });
+        match result {
+            Ok(()) => {
+                info!(shortcut, "privacy mode shortcut registered");
+                *registered = Some(shortcut.to_string());
+            }
+            Err(error) => {
+                warn!(%error, shortcut, "failed to register privacy mode shortcut");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{badge_tray_icon, PrivacyState};
+
+    #[test]
+    fn privacy_mode_expires_and_ignores_stale_timers() {
+        let now = Instant::now();
+        let mut state = PrivacyState::default();
+
+        let first = state.set(true, Some(Duration::from_secs(60)), now);
+        let status = state.status_at(now + Duration::from_secs(15));
+        assert!(status.active);
+        assert_eq!(status.remaining_secs, Some(45));
+        assert!(!state.status_at(now + Duration::from_secs(60)).active);
+
+        let second = state.set(true, None, now);
+        assert!(!state.expire(first));
+        assert!(state.status_at(now + Duration::from_secs(3_600)).active);
+        assert!(state.expire(second));
+        assert!(!state.status_at(now).active);
+    }
+
+    #[test]
+    fn tray_badge_only_fills_the_corner() {
+        let icon = vec![0; 16 * 16 * 4];
+        let badged = badge_tray_icon(&icon, 16, 16);
+
+        let alpha = |x: usize, y: usize| badged[(y * 16 + x) * 4 + 3];
+        assert_eq!(alpha(13, 13), u8::MAX);
+        assert_eq!(alpha(2, 2), 0);
+        assert_eq!(badged.len(), icon.len());
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tracing::{debug, info, warn};
+
+use crate::{auth_store::AuthMethod, AppState};
+
+pub const EVENT_TRANSCRIPTION_PROVIDER_CHANGED: &str = "voice://transcription-provider-changed";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionProviderChangedEvent {
+    pub auth_method: &'static str,
+    pub label: &'static str,
+}
+
+pub fn provider_label(method: AuthMethod) -> &'static str {
+    match method {
+        AuthMethod::ApiKey => "OpenAI API Key",
+        AuthMethod::ChatgptOauth => "ChatGPT Account",
+        AuthMethod::None => "No Provider",
+    }
+}
+
+pub fn next_provider(current: AuthMethod, available: &[AuthMethod]) -> Option<AuthMethod> {
+    let next = match available.iter().position(|method| *method == current) {
+        Some(index) => available[(index + 1) % available.len()],
+        None => *available.first()?,
+    };
+    (next != current).then_some(next)
+}
+
+pub fn cycle_provider(app: &AppHandle) -> Result<AuthMethod, String> {
+    let state = app.state::<AppState>();
+    let auth_store = &state.services.auth_store;
+    let current = state.services.current_auth_method()?;
+    let available = auth_store.configured_methods(&state.services.api_key_store)?;
+    let next = next_provider(current, &available)
+        .ok_or_else(|| "Configure another transcription provider to switch to".to_string())?;
+
+    auth_store.set_auth_method(next)?;
+    info!(
+        from = current.as_str(),
+        to = next.as_str(),
+        "transcription provider switched"
+    );
+    let payload = TranscriptionProviderChangedEvent {
+        auth_method: next.as_str(),
+        label: provider_label(next),
+    };
+    if let Err(error) = app.emit(EVENT_TRANSCRIPTION_PROVIDER_CHANGED, payload) {
+        warn!(%error, "failed to emit transcription provider changed event");
+    }
+    crate::refresh_tray_menu(app);
+    Ok(next)
+}
+
+#[tauri::command]
+pub fn cycle_transcription_provider(app: AppHandle) -> Result<String, String> {
+    cycle_provider(&app).map(|method| method.as_str().to_string())
+}
+
+#[derive(Debug, Default)]
+pub struct ProviderSwitchShortcut {
+    registered: Mutex<Option<String>>,
+}
+
+impl ProviderSwitchShortcut {
+    pub fn sync(&self, app: &AppHandle, shortcut: Option<&str>) {
+        let Ok(mut registered) = self.registered.lock() else {
+            warn!("provider switch shortcut lock poisoned");
+            return;
+        };
+        if registered.as_deref() == shortcut {
+            return;
+        }
+
+        if let Some(previous) = registered.take() {
+            if let Err(error) = app.global_shortcut().unregister(previous.as_str()) {
+                warn!(%error, shortcut = %previous, "failed to unregister provider switch shortcut");
+            }
+        }
+        let Some(shortcut) = shortcut else {
+            debug!("provider switch shortcut cleared");
+            return;
+        };
+
+        let result = app
+            .global_shortcut()
+            .on_shortcut(shortcut, |app, _shortcut, event| {
+                if event.state != ShortcutState::Pressed {
+                    return;
+                }
+                if let Err(error) = cycle_provider(app) {
+                    warn!(%error, "provider switch shortcut had nothing to switch to");
+                }
+            });
+        match result {
+            Ok(()) => {
+                info!(shortcut, "provider switch shortcut registered");
+                *registered = Some(shortcut.to_string());
+            }
+            Err(error) => {
+                warn!(%error, shortcut, "failed to register provider switch shortcut");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_provider;
+    use crate::auth_store::AuthMethod;
+
+    #[test]
+    fn cycles_through_configured_providers_only() {
+        let both = [AuthMethod::ApiKey, AuthMethod::ChatgptOauth];
+
+        assert_eq!(
+            next_provider(AuthMethod::ApiKey, &both),
+            Some(AuthMethod::ChatgptOauth)
+        );
+        assert_eq!(
+            next_provider(AuthMethod::ChatgptOauth, &both),
+            Some(AuthMethod::ApiKey)
+        );
+        assert_eq!(
+            next_provider(AuthMethod::None, &[AuthMethod::ChatgptOauth]),
+            Some(AuthMethod::ChatgptOauth)
+        );
+        assert_eq!(
+            next_provider(AuthMethod::ApiKey, &[AuthMethod::ApiKey]),
+            None
+        );
+        assert_eq!(next_provider(AuthMethod::None, &[]), None);
+    }
+}
@@ -0,0 +1,109 @@
+use std::{thread, time::Duration};
+
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    Device, SampleFormat, SizedSample, Stream, StreamConfig,
+};
+use tracing::warn;
+
+const CHIME_VOLUME: f32 = 0.12;
+const NOTES_HZ: [f32; 2] = [880.0, 1_318.5];
+const NOTE_DURATION: Duration = Duration::from_millis(140);
+const ATTACK: f32 = 0.08;
+const TAIL: Duration = Duration::from_millis(100);
+
+pub fn play() {
+    thread::spawn(|| {
+        if let Err(error) = play_blocking() {
+            warn!(%error, "failed to play recording alert chime");
+        }
+    });
+}
+
+fn play_blocking() -> Result<(), String> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "No audio output device is available".to_string())?;
+    let supported_config = device
+        .default_output_config()
+        .map_err(|error| format!("Failed to read output device config: {error}"))?;
+    let sample_format = supported_config.sample_format();
+    let config: StreamConfig = supported_config.into();
+    let samples = chime_samples(config.sample_rate.0);
+    let duration = Duration::from_secs_f64(samples.len() as f64 / f64::from(config.sample_rate.0));
+
+    let stream = match sample_format {
+        SampleFormat::F32 => build_output_stream(&device, &config, samples, |sample| sample),
+        SampleFormat::I16 => build_output_stream(&device, &config, samples, |sample| {
+            (sample * f32::from(i16::MAX)) as i16
+        }),
+        SampleFormat::U16 => build_output_stream(&device, &config, samples, |sample| {
+            ((sample + 1.0) / 2.0 * f32::from(u16::MAX)) as u16
+        }),
+        _ => Err(format!(
+            "Unsupported output sample format: {sample_format:?}"
+        )),
+    }?;
+    stream
+        .play()
+        .map_err(|error| format!("Failed to start output stream: {error}"))?;
+    thread::sleep(duration + TAIL);
+    Ok(())
+}
+
+fn build_output_stream<T>(
+    device: &Device,
+    config: &StreamConfig,
+    samples: Vec<f32>,
+    convert: fn(f32) -> T,
+) -> Result<Stream, String>
+where
+    T: SizedSample + Send + 'static,
+{
+    let channels = usize::from(config.channels).max(1);
+    let mut position = 0;
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _| {
+                for frame in data.chunks_mut(channels) {
+                    frame.fill(convert(samples.get(position).copied().unwrap_or_default()));
+                    position += 1;
+                }
+            },
+            |error| warn!(%error, "recording alert chime stream error"),
+            None,
+        )
+        .map_err(|error| format!("Failed to build output stream: {error}"))
+}
+
+fn chime_samples(sample_rate_hz: u32) -> Vec<f32> {
+    let rate = sample_rate_hz as f32;
+    let note_samples = (NOTE_DURATION.as_secs_f32() * rate) as usize;
+    NOTES_HZ
+        .iter()
+        .flat_map(|frequency| {
+            (0..note_samples).map(move |index| {
+                let progress = index as f32 / note_samples as f32;
+                let envelope = (progress / ATTACK).min(1.0) * (1.0 - progress);
+                let phase = std::f32::consts::TAU * frequency * index as f32 / rate;
+                CHIME_VOLUME * envelope * phase.sin()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chime_samples, CHIME_VOLUME};
+
+    #[test]
+    fn chime_is_quiet_and_starts_and_ends_silent() {
+        let samples = chime_samples(48_000);
+
+        assert_eq!(samples.len(), 2 * 6_720);
+        assert!(samples.iter().all(|sample| sample.abs() <= CHIME_VOLUME));
+        assert!(samples[0].abs() < 1e-6);
+        assert!(samples.last().is_some_and(|sample| sample.abs() < 1e-3));
+    }
+}
@@ -0,0 +1,82 @@
+mod chime;
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{debug, info, warn};
+
+use crate::{status_notifier::AppStatus, AppState};
+
+pub const EVENT_RECORDING_TIME_ALERT: &str = "voice://recording-time-alert";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingTimeAlertEvent {
+    pub elapsed_secs: u32,
+    pub remaining_alerts: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct RecordingAlerts {
+    generation: AtomicU64,
+    recording: AtomicBool,
+}
+
+impl RecordingAlerts {
+    pub fn on_status(&self, app: &AppHandle, status: AppStatus) {
+        if status != AppStatus::Listening {
+            if self.recording.swap(false, Ordering::Relaxed) {
+                self.generation.fetch_add(1, Ordering::Relaxed);
+            }
+            return;
+        }
+        if self.recording.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let settings = app.state::<AppState>().services.settings_store.current();
+        let thresholds = settings.recording_alert_thresholds_secs;
+        if thresholds.is_empty() {
+            return;
+        }
+        debug!(?thresholds, "recording alerts armed");
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let started_at = tokio::time::Instant::now();
+            for (index, threshold) in thresholds.iter().enumerate() {
+                tokio::time::sleep_until(started_at + Duration::from_secs(u64::from(*threshold)))
+                    .await;
+                let alerts = app.state::<RecordingAlerts>();
+                if alerts.generation.load(Ordering::Relaxed) != generation {
+                    return;
+                }
+                alert(&app, *threshold, thresholds.len() - index - 1);
+            }
+        });
+    }
+}
+
+fn alert(app: &AppHandle, elapsed_secs: u32, remaining_alerts: usize) {
+    info!(elapsed_secs, "recording still running");
+    if app
+        .state::<AppState>()
+        .services
+        .settings_store
+        .current()
+        .recording_alert_chime
+    {
+        chime::play();
+    }
+    let payload = RecordingTimeAlertEvent {
+        elapsed_secs,
+        remaining_alerts,
+    };
+    if let Err(error) = app.emit(EVENT_RECORDING_TIME_ALERT, payload) {
+        warn!(%error, "failed to emit recording time alert event");
+    }
+}
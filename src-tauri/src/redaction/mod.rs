@@ -0,0 +1,277 @@
+use crate::settings_store::VoiceSettings;
+
+const CUSTOM_TERM_REPLACEMENT: &str = "[REDACTED]";
+const CARD_NUMBER_REPLACEMENT: &str = "[CARD]";
+const EMAIL_REPLACEMENT: &str = "[EMAIL]";
+const MIN_CARD_DIGITS: usize = 13;
+const MAX_CARD_DIGITS: usize = 19;
+const PROFANITY: &[&str] = &[
+    "asshole",
+    "bastard",
+    "bitch",
+    "bullshit",
+    "cunt",
+    "dickhead",
+    "fuck",
+    "fucked",
+    "fucker",
+    "fucking",
+    "motherfucker",
+    "shit",
+    "shitty",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedactionRules {
+    pub profanity: bool,
+    pub custom_terms: Vec<String>,
+    pub card_numbers: bool,
+    pub emails: bool,
+}
+
+impl RedactionRules {
+    pub fn from_settings(settings: &VoiceSettings) -> Self {
+        Self {
+            profanity: settings.redact_profanity,
+            custom_terms: if settings.redact_custom_terms {
+                settings.redaction_terms.clone()
+            } else {
+                Vec::new()
+            },
+            card_numbers: settings.redact_card_numbers,
+            emails: settings.redact_emails,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.profanity && self.custom_terms.is_empty() && !self.card_numbers && !self.emails
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut redacted = text.to_string();
+        if self.emails {
+            redacted = mask_emails(&redacted);
+        }
+        if self.card_numbers {
+            redacted = mask_card_numbers(&redacted);
+        }
+        for term in &self.custom_terms {
+            redacted =
+                replace_whole_words(&redacted, term, |_| CUSTOM_TERM_REPLACEMENT.to_string());
+        }
+        if self.profanity {
+            for word in PROFANITY {
+                redacted = replace_whole_words(&redacted, word, |matched| {
+                    "*".repeat(matched.chars().count())
+                });
+            }
+        }
+
+        redacted
+    }
+}
+
+fn replace_whole_words(text: &str, term: &str, mask: impl Fn(&str) -> String) -> String {
+    let term = term.trim();
+    if term.is_empty() {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut cursor = 0;
+    let mut search_from = 0;
+    while let Some(offset) = find_ignore_ascii_case(&text[search_from..], term) {
+        let start = search_from + offset;
+        let end = start + term.len();
+        let before_is_word = text[..start].chars().next_back().is_some_and(is_word_char);
+        let after_is_word = text[end..].chars().next().is_some_and(is_word_char);
+        if !before_is_word && !after_is_word {
+            output.push_str(&text[cursor..start]);
+            output.push_str(&mask(&text[start..end]));
+            cursor = end;
+            search_from = end;
+        } else {
+            search_from = start + text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    output.push_str(&text[cursor..]);
+
+    output
+}
+
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .char_indices()
+        .map(|(index, _)| index)
+        .find(|index| {
+            haystack
+                .get(*index..*index + needle.len())
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(needle))
+        })
+}
+
+fn is_word_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+fn mask_emails(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for (index, token) in text.split(' ').enumerate() {
+        if index > 0 {
+            output.push(' ');
+        }
+        let core =
+            token.trim_matches(|character: char| !character.is_alphanumeric() && character != '@');
+        if looks_like_email(core) {
+            output.push_str(&token.replacen(core, EMAIL_REPLACEMENT, 1));
+        } else {
+            output.push_str(token);
+        }
+    }
+
+    output
+}
+
+fn looks_like_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    let local_valid = !local.is_empty()
+        && local
+            .chars()
+            .all(|character| character.is_alphanumeric() || "._%+-".contains(character));
+    let labels = domain.split('.').collect::<Vec<_>>();
+    let domain_valid = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|character| character.is_alphanumeric() || character == '-')
+        });
+
+    local_valid && domain_valid
+}
+
+fn mask_card_numbers(text: &str) -> String {
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let mut output = String::with_capacity(text.len());
+    let mut cursor = 0;
+    let mut index = 0;
+    while index < chars.len() {
+        let (start, character) = chars[index];
+        let preceded_by_word = index > 0 && is_word_char(chars[index - 1].1);
+        if !character.is_ascii_digit() || preceded_by_word {
+            index += 1;
+            continue;
+        }
+
+        let mut digits = Vec::new();
+        let mut end_index = index;
+        let mut probe = index;
+        while probe < chars.len() {
+            let (_, current) = chars[probe];
+            if current.is_ascii_digit() {
+                digits.push(current as u8 - b'0');
+                end_index = probe;
+                probe += 1;
+            } else if (current == ' ' || current == '-')
+                && chars
+                    .get(probe + 1)
+                    .is_some_and(|(_, next)| next.is_ascii_digit())
+            {
+                probe += 1;
+            } else {
+                break;
+            }
+        }
+
+        let followed_by_word = chars
+            .get(end_index + 1)
+            .is_some_and(|(_, next)| is_word_char(*next));
+        let end = chars
+            .get(end_index + 1)
+            .map_or(text.len(), |(offset, _)| *offset);
+        if (MIN_CARD_DIGITS..=MAX_CARD_DIGITS).contains(&digits.len())
+            && !followed_by_word
+            && passes_luhn(&digits)
+        {
+            output.push_str(&text[cursor..start]);
+            output.push_str(CARD_NUMBER_REPLACEMENT);
+            cursor = end;
+        }
+        index = end_index + 1;
+    }
+    output.push_str(&text[cursor..]);
+
+    output
+}
+
+fn passes_luhn(digits: &[u8]) -> bool {
+    let checksum = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(position, digit)| {
+            let digit = u32::from(*digit);
+            if position % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum::<u32>();
+
+    checksum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RedactionRules;
+
+    fn all_rules(custom_terms: &[&str]) -> RedactionRules {
+        RedactionRules {
+            profanity: true,
+            custom_terms: custom_terms.iter().map(|term| term.to_string()).collect(),
+            card_numbers: true,
+            emails: true,
+        }
+    }
+
+    #[test]
+    fn masks_emails_and_luhn_valid_card_numbers() {
+        let rules = all_rules(&[]);
+
+        assert_eq!(
+            rules.apply("Email me at jane.doe@example.com, card 4111 1111 1111 1111."),
+            "Email me at [EMAIL], card [CARD]."
+        );
+        assert_eq!(
+            rules.apply("Order 4111-1111-1111-1112 shipped"),
+            "Order 4111-1111-1111-1112 shipped"
+        );
+    }
+
+    #[test]
+    fn masks_profanity_and_custom_terms_as_whole_words() {
+        let rules = all_rules(&["Project Falcon"]);
+
+        assert_eq!(
+            rules.apply("Shit, project falcon slipped again. Shiitake is fine."),
+            "****, [REDACTED] slipped again. Shiitake is fine."
+        );
+    }
+
+    #[test]
+    fn disabled_rules_leave_text_untouched() {
+        let rules = RedactionRules::default();
+        let text = "shit, mail jane@example.com or 4111111111111111";
+
+        assert!(rules.is_empty());
+        assert_eq!(rules.apply(text), text);
+    }
+}
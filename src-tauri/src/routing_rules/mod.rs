@@ -0,0 +1,293 @@
+use std::{sync::Mutex, time::Duration};
+
+use chrono::{Local, SecondsFormat, Timelike};
+use serde::Serialize;
+use tauri::State;
+use tracing::{debug, info};
+
+use crate::{
+    hotkey_service::FrontmostApplication,
+    settings_store::{parse_clock_time, RoutingRule},
+};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy)]
+pub struct RoutingContext<'a> {
+    pub frontmost: Option<&'a FrontmostApplication>,
+    pub minute_of_day: u32,
+    pub text: &'a str,
+}
+
+impl<'a> RoutingContext<'a> {
+    pub fn now(frontmost: Option<&'a FrontmostApplication>, text: &'a str) -> Self {
+        let now = Local::now();
+        Self {
+            frontmost,
+            minute_of_day: now.hour() * 60 + now.minute(),
+            text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub rule: String,
+    pub destination: String,
+    pub target: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleEvaluation {
+    pub rule: String,
+    pub matched: bool,
+    pub failed_condition: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutingTrace {
+    pub evaluated_at: String,
+    pub app: Option<String>,
+    pub rules: Vec<RuleEvaluation>,
+    pub destination: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct RoutingTraceStore {
+    last: Mutex<Option<RoutingTrace>>,
+}
+
+impl RoutingTraceStore {
+    pub fn route(&self, rules: &[RoutingRule], context: &RoutingContext) -> Option<Route> {
+        let (route, evaluations) = evaluate(rules, context);
+        for evaluation in &evaluations {
+            debug!(
+                rule = %evaluation.rule,
+                matched = evaluation.matched,
+                failed_condition = ?evaluation.failed_condition,
+                "routing rule evaluated"
+            );
+        }
+        if let Some(route) = &route {
+            info!(
+                rule = %route.rule,
+                destination = %route.destination,
+                "transcript routed by rule"
+            );
+        }
+
+        let trace = RoutingTrace {
+            evaluated_at: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            app: context
+                .frontmost
+                .and_then(|app| app.name.clone().or_else(|| app.bundle_id.clone())),
+            rules: evaluations,
+            destination: route.as_ref().map(|route| route.destination.clone()),
+        };
+        if let Ok(mut last) = self.last.lock() {
+            *last = Some(trace);
+        }
+        route
+    }
+
+    pub fn last(&self) -> Result<Option<RoutingTrace>, String> {
+        self.last
+            .lock()
+            .map(|last| last.clone())
+            .map_err(|_| "Routing trace lock is poisoned".to_string())
+    }
+}
+
+pub fn evaluate(
+    rules: &[RoutingRule],
+    context: &RoutingContext,
+) -> (Option<Route>, Vec<RuleEvaluation>) {
+    let mut evaluations = Vec::new();
+    for rule in rules {
+        let outcome = check_rule(rule, context);
+        evaluations.push(RuleEvaluation {
+            rule: rule.name.clone(),
+            matched: outcome.is_ok(),
+            failed_condition: outcome.as_ref().err().cloned(),
+        });
+        if let Ok(text) = outcome {
+            let route = Route {
+                rule: rule.name.clone(),
+                destination: rule.destination.clone(),
+                target: rule.target.clone(),
+                text,
+            };
+            return (Some(route), evaluations);
+        }
+    }
+    (None, evaluations)
+}
+
+fn check_rule(rule: &RoutingRule, context: &RoutingContext) -> Result<String, String> {
+    if let Some(app) = &rule.app {
+        if !context
+            .frontmost
+            .is_some_and(|frontmost| frontmost.matches(app))
+        {
+            return Err(format!("app is not {app}"));
+        }
+    }
+    if let (Some(from), Some(until)) = (&rule.active_from, &rule.active_until) {
+        if !in_time_window(context.minute_of_day, from, until) {
+            return Err(format!("time is outside {from}-{until}"));
+        }
+    }
+    match &rule.prefix {
+        Some(prefix) => strip_spoken_prefix(context.text, prefix)
+            .map(str::to_string)
+            .ok_or_else(|| format!("transcript does not start with `{prefix}`")),
+        None => Ok(context.text.to_string()),
+    }
+}
+
+fn in_time_window(minute_of_day: u32, from: &str, until: &str) -> bool {
+    let (Some(from), Some(until)) = (parse_clock_time(from), parse_clock_time(until)) else {
+        return false;
+    };
+    if from < until {
+        (from..until).contains(&minute_of_day)
+    } else {
+        minute_of_day >= from || minute_of_day < until
+    }
+}
+
+fn strip_spoken_prefix<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let is_separator = |c: char| c.is_whitespace() || c.is_ascii_punctuation();
+    let key = prefix.trim_matches(is_separator);
+    let text = text.trim_start();
+    let head = text.get(..key.len())?;
+    if key.is_empty() || !head.eq_ignore_ascii_case(key) {
+        return None;
+    }
+    let rest = &text[key.len()..];
+    if rest.starts_with(|c: char| !is_separator(c)) {
+        return None;
+    }
+    Some(rest.trim_start_matches(is_separator))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    text: &'a str,
+    rule: &'a str,
+    created_at: String,
+}
+
+pub async fn post_webhook(url: &str, rule: &str, text: &str) -> Result<(), String> {
+    let payload = WebhookPayload {
+        text,
+        rule,
+        created_at: Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+    };
+    reqwest::Client::new()
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&payload)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|error| format!("Failed to send transcript to webhook: {error}"))?;
+    info!(rule, "transcript sent to webhook");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_routing_trace(
+    store: State<'_, RoutingTraceStore>,
+) -> Result<Option<RoutingTrace>, String> {
+    debug!("routing trace requested");
+    store.last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, RoutingContext};
+    use crate::{hotkey_service::FrontmostApplication, settings_store::RoutingRule};
+
+    fn rule(name: &str, destination: &str) -> RoutingRule {
+        RoutingRule {
+            name: name.to_string(),
+            destination: destination.to_string(),
+            ..RoutingRule::default()
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins_and_its_prefix_is_removed() {
+        let mail = FrontmostApplication {
+            bundle_id: Some("com.apple.mail".to_string()),
+            name: Some("Mail".to_string()),
+        };
+        let rules = vec![
+            RoutingRule {
+                app: Some("com.apple.mail".to_string()),
+                active_from: Some("22:00".to_string()),
+                active_until: Some("06:00".to_string()),
+                ..rule("Late mail", "clipboard")
+            },
+            RoutingRule {
+                prefix: Some("note:".to_string()),
+                target: Some("~/Obsidian/Inbox.md".to_string()),
+                ..rule("Notes", "file")
+            },
+        ];
+
+        let (route, trace) = evaluate(
+            &rules,
+            &RoutingContext {
+                frontmost: Some(&mail),
+                minute_of_day: 12 * 60,
+                text: "Note, buy milk.",
+            },
+        );
+        let route = route.expect("the prefix rule should match");
+        assert_eq!(route.rule, "Notes");
+        assert_eq!(route.text, "buy milk.");
+        assert_eq!(
+            trace[0].failed_condition.as_deref(),
+            Some("time is outside 22:00-06:00")
+        );
+        assert!(trace[1].matched);
+
+        let (route, _) = evaluate(
+            &rules,
+            &RoutingContext {
+                frontmost: Some(&mail),
+                minute_of_day: 23 * 60,
+                text: "Notebooks are on sale",
+            },
+        );
+        assert_eq!(route.map(|route| route.rule).as_deref(), Some("Late mail"));
+    }
+
+    #[test]
+    fn prefix_must_be_whole_words() {
+        let rules = vec![RoutingRule {
+            prefix: Some("note:".to_string()),
+            ..rule("Notes", "clipboard")
+        }];
+
+        let (route, trace) = evaluate(
+            &rules,
+            &RoutingContext {
+                frontmost: None,
+                minute_of_day: 0,
+                text: "Notebooks are on sale",
+            },
+        );
+        assert_eq!(route, None);
+        assert_eq!(
+            trace[0].failed_condition.as_deref(),
+            Some("transcript does not start with `note:`")
+        );
+    }
+}
@@ -1,14 +1,15 @@
 use std::{
-    fs,
-    io::Write,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
-    sync::{Mutex, RwLock},
-    time::{SystemTime, UNIX_EPOCH},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use tauri::{AppHandle, Manager, Runtime};
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
+
+use crate::persistence::JsonDocumentStore;
 
 pub const DEFAULT_HOTKEY_SHORTCUT: &str = "Alt+Space";
 pub const RECORDING_MODE_HOLD_TO_TALK: &str = "hold_to_talk";
@@ -19,9 +20,232 @@ pub const TRANSCRIPTION_STYLE_CASUAL: &str = "casual";
 pub const TRANSCRIPTION_STYLE_VERBATIM: &str = "verbatim";
 pub const TRANSCRIPTION_STYLE_CUSTOM: &str = "custom";
 pub const DEFAULT_TRANSCRIPTION_STYLE: &str = TRANSCRIPTION_STYLE_CLEAN;
-
+pub const OUTPUT_TARGET_CURSOR: &str = "cursor";
+pub const OUTPUT_TARGET_MARKDOWN: &str = "markdown";
+pub const OUTPUT_TARGET_BOTH: &str = "both";
+pub const DEFAULT_HTTP_API_PORT: u16 = 47_615;
+pub const DEFAULT_DOUBLE_TAP_INTERVAL_MS: u64 = 350;
+pub const DOUBLE_TAP_MODIFIERS: [&str; 9] = [
+    "fn",
+    "left_cmd",
+    "right_cmd",
+    "left_alt",
+    "right_alt",
+    "left_ctrl",
+    "right_ctrl",
+    "left_shift",
+    "right_shift",
+];
+pub const OVERLAY_ANCHORS: [&str; 6] = [
+    "top_left",
+    "top_center",
+    "top_right",
+    "bottom_left",
+    "bottom_center",
+    "bottom_right",
+];
+pub const DEFAULT_OVERLAY_ANCHOR: &str = "top_center";
+pub const OVERLAY_DISPLAY_CURSOR: &str = "cursor";
+pub const OVERLAY_DISPLAY_FOCUSED_WINDOW: &str = "focused_window";
+pub const PRELOAD_MODE_OFF: &str = "off";
+pub const PRELOAD_MODE_ON_HOTKEY: &str = "on_hotkey";
+pub const PRELOAD_MODE_KEEP_WARM: &str = "keep_warm";
+pub const DATE_FORMAT_OFF: &str = "off";
+pub const DATE_FORMAT_ISO: &str = "iso";
+pub const DATE_FORMAT_MONTH_DAY_YEAR: &str = "month_day_year";
+pub const DATE_FORMAT_DAY_MONTH_YEAR: &str = "day_month_year";
+pub const TIME_FORMAT_OFF: &str = "off";
+pub const TIME_FORMAT_12H: &str = "12h";
+pub const TIME_FORMAT_24H: &str = "24h";
+pub const INSERTION_TRAILING_NONE: &str = "none";
+pub const INSERTION_TRAILING_SPACE: &str = "space";
+pub const INSERTION_TRAILING_NEWLINE: &str = "newline";
+pub const ROUTE_DESTINATION_INSERT: &str = "insert";
+pub const ROUTE_DESTINATION_CLIPBOARD: &str = "clipboard";
+pub const ROUTE_DESTINATION_FILE: &str = "file";
+pub const ROUTE_DESTINATION_WEBHOOK: &str = "webhook";
+const MIN_HTTP_API_PORT: u16 = 1024;
+const MAX_MIDI_NOTE: u8 = 127;
+const MIN_DOUBLE_TAP_INTERVAL_MS: u64 = 150;
+const MAX_DOUBLE_TAP_INTERVAL_MS: u64 = 1_000;
+const MIN_LONG_PRESS_THRESHOLD_MS: u64 = 200;
+const MAX_LONG_PRESS_THRESHOLD_MS: u64 = 5_000;
+
+pub const DEFAULT_MIN_RECORDING_DURATION_MS: u64 = 200;
+const MAX_MIN_RECORDING_DURATION_MS: u64 = 5_000;
+pub const DEFAULT_PRE_ROLL_MS: u64 = 1_500;
+const MIN_PRE_ROLL_MS: u64 = 1_000;
+const MAX_PRE_ROLL_MS: u64 = 3_000;
+pub const DEFAULT_TRANSCRIPT_CACHE_SIZE: u32 = 16;
+const MAX_TRANSCRIPT_CACHE_SIZE: u32 = 256;
+pub const DEFAULT_TRANSCRIPT_CACHE_TTL_SECS: u64 = 600;
+const MIN_TRANSCRIPT_CACHE_TTL_SECS: u64 = 10;
+const MAX_TRANSCRIPT_CACHE_TTL_SECS: u64 = 86_400;
+pub const DEFAULT_WAKE_WORD_SENSITIVITY: u8 = 50;
+const MAX_WAKE_WORD_SENSITIVITY: u8 = 100;
+const MIN_MICROPHONE_GAIN_DB: i8 = -20;
+const MAX_MICROPHONE_GAIN_DB: i8 = 20;
+const MAX_NOISE_SUPPRESSION: u8 = 100;
+const MAX_INPUT_CHANNEL: u16 = 63;
+pub const DEFAULT_STATUS_ERROR_RESET_MS: u64 = 1_500;
+const MIN_STATUS_ERROR_RESET_MS: u64 = 500;
+const MAX_STATUS_ERROR_RESET_MS: u64 = 60_000;
+pub const DEFAULT_TRANSCRIBING_TIMEOUT_SECS: u64 = 120;
+const MIN_TRANSCRIBING_TIMEOUT_SECS: u64 = 10;
+const MAX_TRANSCRIBING_TIMEOUT_SECS: u64 = 3_600;
+pub const DEFAULT_LISTENING_TIMEOUT_SECS: u64 = 3_600;
+const MIN_LISTENING_TIMEOUT_SECS: u64 = 60;
+const MAX_LISTENING_TIMEOUT_SECS: u64 = 86_400;
+const MIN_MAX_RECORDING_SECS: u64 = 10;
+const MAX_MAX_RECORDING_SECS: u64 = 86_400;
+pub const DEFAULT_TRANSCRIPTION_STAGE_TIMEOUT_SECS: u64 = 90;
+const MIN_TRANSCRIPTION_STAGE_TIMEOUT_SECS: u64 = 5;
+const MAX_TRANSCRIPTION_STAGE_TIMEOUT_SECS: u64 = 3_600;
+pub const DEFAULT_INSERTION_TIMEOUT_SECS: u64 = 15;
+const MIN_INSERTION_TIMEOUT_SECS: u64 = 1;
+const MAX_INSERTION_TIMEOUT_SECS: u64 = 300;
+pub const DEFAULT_TYPING_CHARS_PER_SECOND: u32 = 1_000;
+const MIN_TYPING_CHARS_PER_SECOND: u32 = 10;
+const MAX_TYPING_CHARS_PER_SECOND: u32 = 10_000;
+pub const DEFAULT_TYPING_CHUNK_CHARS: u32 = 20;
+const MAX_TYPING_CHUNK_CHARS: u32 = 200;
+const SUPPORTED_PROXY_SCHEMES: [&str; 4] = ["http://", "https://", "socks5://", "socks5h://"];
+const CONNECTION_PROVIDERS: [&str; 2] = ["openai", "chatgpt-oauth"];
+pub const OPENAI_TRANSCRIPTION_MODELS: [&str; 3] =
+    ["gpt-4o-transcribe", "gpt-4o-mini-transcribe", "whisper-1"];
+pub const DEFAULT_MAX_CONCURRENT_TRANSCRIPTIONS: u32 = 2;
+const MAX_CONCURRENT_TRANSCRIPTIONS: u32 = 16;
+const MAX_TRANSCRIPTION_REQUESTS_PER_MINUTE: u32 = 600;
+pub const DEFAULT_CONTEXT_CARRYOVER_WINDOW_SECS: u32 = 120;
+const MIN_CONTEXT_CARRYOVER_WINDOW_SECS: u32 = 10;
+const MAX_CONTEXT_CARRYOVER_WINDOW_SECS: u32 = 1_800;
+pub const DEFAULT_CONTEXT_CARRYOVER_MAX_CHARS: u32 = 300;
+const MIN_CONTEXT_CARRYOVER_MAX_CHARS: u32 = 50;
+const MAX_CONTEXT_CARRYOVER_MAX_CHARS: u32 = 2_000;
+pub const PARAGRAPH_SEGMENTATION_OFF: &str = "off";
+pub const PARAGRAPH_SEGMENTATION_PAUSES: &str = "pauses";
+pub const PARAGRAPH_SEGMENTATION_TOPICS: &str = "topics";
+pub const DEFAULT_PARAGRAPH_PAUSE_MS: u32 = 1_500;
+const MIN_PARAGRAPH_PAUSE_MS: u32 = 300;
+const MAX_PARAGRAPH_PAUSE_MS: u32 = 10_000;
+pub const FILLER_REMOVAL_OFF: &str = "off";
+pub const FILLER_REMOVAL_LIGHT: &str = "light";
+pub const FILLER_REMOVAL_MODERATE: &str = "moderate";
+pub const FILLER_REMOVAL_AGGRESSIVE: &str = "aggressive";
+pub const DEFAULT_RECORDING_ALERT_THRESHOLDS_SECS: [u32; 2] = [60, 120];
+const MIN_RECORDING_ALERT_THRESHOLD_SECS: u32 = 10;
+const MAX_RECORDING_ALERT_THRESHOLD_SECS: u32 = 3_600;
+const MAX_RECORDING_ALERT_THRESHOLDS: usize = 10;
+
+const MIN_WINDOW_WIDTH: u32 = 320;
+const MIN_WINDOW_HEIGHT: u32 = 240;
+const MAX_WINDOW_DIMENSION: u32 = 16_384;
+const MAX_STREAK_MIN_WORDS: u64 = 100_000;
+const MAX_STREAK_FREEZES: u32 = 30;
+const DEFAULT_PRIVACY_MODE_DURATION_MINS: u32 = 60;
+const MAX_PRIVACY_MODE_DURATION_MINS: u32 = 1_440;
 const SETTINGS_FILE_NAME: &str = "settings.json";
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub prompt: String,
+    pub cleanup_instructions: String,
+    pub spoken_punctuation: Option<bool>,
+    pub output_target: Option<String>,
+    pub language: Option<String>,
+    pub translation_target_language: Option<String>,
+    pub shortcut: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct TrailingOverride {
+    pub app: String,
+    pub insertion_trailing: String,
+    pub strip_trailing_period: bool,
+}
+
+impl Default for TrailingOverride {
+    fn default() -> Self {
+        Self {
+            app: String::new(),
+            insertion_trailing: INSERTION_TRAILING_NONE.to_string(),
+            strip_trailing_period: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct TypingSpeedOverride {
+    pub app: String,
+    pub typing_chars_per_second: u32,
+    pub typing_chunk_chars: u32,
+}
+
+impl Default for TypingSpeedOverride {
+    fn default() -> Self {
+        Self {
+            app: String::new(),
+            typing_chars_per_second: DEFAULT_TYPING_CHARS_PER_SECOND,
+            typing_chunk_chars: DEFAULT_TYPING_CHUNK_CHARS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RoutingRule {
+    pub name: String,
+    pub app: Option<String>,
+    pub active_from: Option<String>,
+    pub active_until: Option<String>,
+    pub prefix: Option<String>,
+    pub destination: String,
+    pub target: Option<String>,
+}
+
+impl Default for RoutingRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            app: None,
+            active_from: None,
+            active_until: None,
+            prefix: None,
+            destination: ROUTE_DESTINATION_INSERT.to_string(),
+            target: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MicrophoneProfile {
+    pub device_id: String,
+    pub gain_db: i8,
+    pub noise_suppression: u8,
+    pub input_channel: Option<u16>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ProviderConnection {
+    pub provider: String,
+    pub ca_bundle_path: Option<String>,
+    pub bypass_proxy: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(default)]
 pub struct VoiceSettings {
@@ -35,6 +259,95 @@ pub struct VoiceSettings {
     pub auto_insert: bool,
     pub launch_at_login: bool,
     pub onboarding_completed: bool,
+    pub http_api_enabled: bool,
+    pub http_api_port: u16,
+    pub midi_trigger_note: Option<u8>,
+    pub hid_trigger_device: Option<String>,
+    pub output_target: String,
+    pub markdown_output_path: Option<String>,
+    pub meeting_system_audio_device_id: Option<String>,
+    pub meeting_diarization_enabled: bool,
+    pub translation_target_language: Option<String>,
+    pub queued_dictation_enabled: bool,
+    pub double_tap_modifier: Option<String>,
+    pub double_tap_interval_ms: u64,
+    pub hotkey_suspended_apps: Vec<String>,
+    pub long_press_threshold_ms: Option<u64>,
+    pub overlay_anchor: String,
+    pub overlay_display: String,
+    pub preload_mode: String,
+    pub min_recording_duration_ms: u64,
+    pub redact_profanity: bool,
+    pub redact_custom_terms: bool,
+    pub redaction_terms: Vec<String>,
+    pub redact_card_numbers: bool,
+    pub redact_emails: bool,
+    pub format_numbers_as_digits: bool,
+    pub date_format: String,
+    pub time_format: String,
+    pub abbreviate_units: bool,
+    pub insertion_trailing: String,
+    pub strip_trailing_period: bool,
+    pub trailing_overrides: Vec<TrailingOverride>,
+    pub typing_chars_per_second: u32,
+    pub typing_chunk_chars: u32,
+    pub typing_speed_overrides: Vec<TypingSpeedOverride>,
+    pub conceal_clipboard_writes: bool,
+    pub replace_selection: bool,
+    pub selection_as_cleanup_context: bool,
+    pub prompt_templates: Vec<PromptTemplate>,
+    pub active_prompt_template: Option<String>,
+    pub local_analytics_enabled: bool,
+    pub pre_roll_enabled: bool,
+    pub pre_roll_ms: u64,
+    pub prewarm_microphone: bool,
+    pub transcript_cache_size: u32,
+    pub transcript_cache_ttl_secs: u64,
+    pub history_sync_folder: Option<String>,
+    pub wake_word_enabled: bool,
+    pub wake_word_sensitivity: u8,
+    pub microphone_profiles: Vec<MicrophoneProfile>,
+    pub prefer_built_in_microphone: bool,
+    pub status_error_reset_ms: u64,
+    pub transcribing_timeout_secs: u64,
+    pub listening_timeout_secs: u64,
+    pub max_recording_secs: u64,
+    pub transcription_stage_timeout_secs: u64,
+    pub insertion_timeout_secs: u64,
+    pub proxy_url: Option<String>,
+    pub proxy_username: Option<String>,
+    #[serde(skip_deserializing)]
+    pub has_proxy_password: bool,
+    pub openai_organization: Option<String>,
+    pub openai_project: Option<String>,
+    pub openai_transcription_model: Option<String>,
+    pub provider_connections: Vec<ProviderConnection>,
+    pub max_concurrent_transcriptions: u32,
+    pub transcription_requests_per_minute: u32,
+    pub provider_switch_shortcut: Option<String>,
+    pub command_palette_shortcut: Option<String>,
+    pub context_carryover_enabled: bool,
+    pub context_carryover_window_secs: u32,
+    pub context_carryover_max_chars: u32,
+    pub keyboard_language_hint: bool,
+    pub paragraph_segmentation: String,
+    pub paragraph_pause_ms: u32,
+    pub filler_removal: String,
+    pub spoken_punctuation: bool,
+    pub screen_reader_announcements: bool,
+    pub voice_error_recovery: bool,
+    pub recording_alert_thresholds_secs: Vec<u32>,
+    pub recording_alert_chime: bool,
+    pub pause_media_while_recording: bool,
+    pub blocked_apps: Vec<String>,
+    pub routing_rules: Vec<RoutingRule>,
+    pub history_window_bounds: Option<WindowBounds>,
+    pub streak_min_words: u64,
+    pub streak_workdays_only: bool,
+    pub streak_freezes: u32,
+    pub record_history_source: bool,
+    pub privacy_mode_shortcut: Option<String>,
+    pub privacy_mode_duration_mins: u32,
 }
 
 impl Default for VoiceSettings {
@@ -50,11 +363,105 @@ impl Default for VoiceSettings {
             auto_insert: true,
             launch_at_login: false,
             onboarding_completed: false,
+            http_api_enabled: false,
+            http_api_port: DEFAULT_HTTP_API_PORT,
+            midi_trigger_note: None,
+            hid_trigger_device: None,
+            output_target: OUTPUT_TARGET_CURSOR.to_string(),
+            markdown_output_path: None,
+            meeting_system_audio_device_id: None,
+            meeting_diarization_enabled: false,
+            translation_target_language: None,
+            queued_dictation_enabled: false,
+            double_tap_modifier: None,
+            double_tap_interval_ms: DEFAULT_DOUBLE_TAP_INTERVAL_MS,
+            hotkey_suspended_apps: Vec::new(),
+            long_press_threshold_ms: None,
+            overlay_anchor: DEFAULT_OVERLAY_ANCHOR.to_string(),
+            overlay_display: OVERLAY_DISPLAY_CURSOR.to_string(),
+            preload_mode: PRELOAD_MODE_ON_HOTKEY.to_string(),
+            min_recording_duration_ms: DEFAULT_MIN_RECORDING_DURATION_MS,
+            redact_profanity: false,
+            redact_custom_terms: false,
+            redaction_terms: Vec::new(),
+            redact_card_numbers: false,
+            redact_emails: false,
+            format_numbers_as_digits: false,
+            date_format: DATE_FORMAT_OFF.to_string(),
+            time_format: TIME_FORMAT_OFF.to_string(),
+            abbreviate_units: false,
+            insertion_trailing: INSERTION_TRAILING_NONE.to_string(),
+            strip_trailing_period: false,
+            trailing_overrides: Vec::new(),
+            typing_chars_per_second: DEFAULT_TYPING_CHARS_PER_SECOND,
+            typing_chunk_chars: DEFAULT_TYPING_CHUNK_CHARS,
+            typing_speed_overrides: Vec::new(),
+            conceal_clipboard_writes: true,
+            replace_selection: false,
+            selection_as_cleanup_context: false,
+            prompt_templates: Vec::new(),
+            active_prompt_template: None,
+            local_analytics_enabled: false,
+            pre_roll_enabled: false,
+            pre_roll_ms: DEFAULT_PRE_ROLL_MS,
+            prewarm_microphone: false,
+            transcript_cache_size: DEFAULT_TRANSCRIPT_CACHE_SIZE,
+            transcript_cache_ttl_secs: DEFAULT_TRANSCRIPT_CACHE_TTL_SECS,
+            history_sync_folder: None,
+            wake_word_enabled: false,
+            wake_word_sensitivity: DEFAULT_WAKE_WORD_SENSITIVITY,
+            microphone_profiles: Vec::new(),
+            prefer_built_in_microphone: false,
+            status_error_reset_ms: DEFAULT_STATUS_ERROR_RESET_MS,
+            transcribing_timeout_secs: DEFAULT_TRANSCRIBING_TIMEOUT_SECS,
+            listening_timeout_secs: DEFAULT_LISTENING_TIMEOUT_SECS,
+            max_recording_secs: 0,
+            transcription_stage_timeout_secs: DEFAULT_TRANSCRIPTION_STAGE_TIMEOUT_SECS,
+            insertion_timeout_secs: DEFAULT_INSERTION_TIMEOUT_SECS,
+            proxy_url: None,
+            proxy_username: None,
+            has_proxy_password: false,
+            openai_organization: None,
+            openai_project: None,
+            openai_transcription_model: None,
+            provider_connections: Vec::new(),
+            max_concurrent_transcriptions: DEFAULT_MAX_CONCURRENT_TRANSCRIPTIONS,
+            transcription_requests_per_minute: 0,
+            provider_switch_shortcut: None,
+            command_palette_shortcut: None,
+            context_carryover_enabled: false,
+            context_carryover_window_secs: DEFAULT_CONTEXT_CARRYOVER_WINDOW_SECS,
+            context_carryover_max_chars: DEFAULT_CONTEXT_CARRYOVER_MAX_CHARS,
+            keyboard_language_hint: true,
+            paragraph_segmentation: PARAGRAPH_SEGMENTATION_OFF.to_string(),
+            paragraph_pause_ms: DEFAULT_PARAGRAPH_PAUSE_MS,
+            filler_removal: FILLER_REMOVAL_OFF.to_string(),
+            spoken_punctuation: false,
+            screen_reader_announcements: false,
+            voice_error_recovery: false,
+            recording_alert_thresholds_secs: DEFAULT_RECORDING_ALERT_THRESHOLDS_SECS.to_vec(),
+            recording_alert_chime: true,
+            pause_media_while_recording: false,
+            blocked_apps: Vec::new(),
+            routing_rules: Vec::new(),
+            history_window_bounds: None,
+            streak_min_words: 0,
+            streak_workdays_only: false,
+            streak_freezes: 0,
+            record_history_source: false,
+            privacy_mode_shortcut: None,
+            privacy_mode_duration_mins: DEFAULT_PRIVACY_MODE_DURATION_MINS,
         }
     }
 }
 
 impl VoiceSettings {
+    pub fn provider_connection(&self, provider: &str) -> Option<&ProviderConnection> {
+        self.provider_connections
+            .iter()
+            .find(|connection| connection.provider == provider)
+    }
+
     fn normalized(mut self) -> Result<Self, String> {
         self.hotkey_shortcut = normalize_required_string(self.hotkey_shortcut, "hotkey_shortcut")?;
         self.recording_mode = normalize_recording_mode(self.recording_mode)?;
@@ -65,6 +472,121 @@ impl VoiceSettings {
         self.transcription_style = normalize_transcription_style(self.transcription_style);
         self.custom_transcription_prompt =
             normalize_optional_string(Some(self.custom_transcription_prompt)).unwrap_or_default();
+        self.http_api_port = normalize_http_api_port(self.http_api_port)?;
+        self.midi_trigger_note = normalize_midi_trigger_note(self.midi_trigger_note)?;
+        self.hid_trigger_device = normalize_hid_trigger_device(self.hid_trigger_device)?;
+        self.markdown_output_path = normalize_optional_string(self.markdown_output_path);
+        self.history_sync_folder = normalize_optional_string(self.history_sync_folder);
+        self.meeting_system_audio_device_id =
+            normalize_optional_string(self.meeting_system_audio_device_id);
+        self.translation_target_language =
+            normalize_translation_target_language(self.translation_target_language)?;
+        self.output_target =
+            normalize_output_target(self.output_target, self.markdown_output_path.as_deref())?;
+        self.double_tap_modifier = normalize_double_tap_modifier(self.double_tap_modifier)?;
+        self.double_tap_interval_ms =
+            normalize_double_tap_interval_ms(self.double_tap_interval_ms)?;
+        self.hotkey_suspended_apps = normalize_app_entries(self.hotkey_suspended_apps);
+        self.blocked_apps = normalize_app_entries(self.blocked_apps);
+        self.long_press_threshold_ms =
+            normalize_long_press_threshold_ms(self.long_press_threshold_ms)?;
+        self.overlay_anchor = normalize_overlay_anchor(self.overlay_anchor)?;
+        self.overlay_display = normalize_overlay_display(self.overlay_display)?;
+        self.preload_mode = normalize_preload_mode(self.preload_mode)?;
+        self.min_recording_duration_ms =
+            normalize_min_recording_duration_ms(self.min_recording_duration_ms)?;
+        self.pre_roll_ms = normalize_pre_roll_ms(self.pre_roll_ms)?;
+        self.transcript_cache_size = normalize_transcript_cache_size(self.transcript_cache_size)?;
+        self.transcript_cache_ttl_secs =
+            normalize_transcript_cache_ttl_secs(self.transcript_cache_ttl_secs)?;
+        self.wake_word_sensitivity = normalize_wake_word_sensitivity(self.wake_word_sensitivity)?;
+        self.microphone_profiles = normalize_microphone_profiles(self.microphone_profiles)?;
+        self.status_error_reset_ms = normalize_status_error_reset_ms(self.status_error_reset_ms)?;
+        self.transcribing_timeout_secs =
+            normalize_transcribing_timeout_secs(self.transcribing_timeout_secs)?;
+        self.listening_timeout_secs =
+            normalize_listening_timeout_secs(self.listening_timeout_secs)?;
+        self.max_recording_secs = normalize_pipeline_timeout_secs(
+            self.max_recording_secs,
+            "maximum recording length",
+            MIN_MAX_RECORDING_SECS..=MAX_MAX_RECORDING_SECS,
+        )?;
+        self.transcription_stage_timeout_secs = normalize_pipeline_timeout_secs(
+            self.transcription_stage_timeout_secs,
+            "transcription stage timeout",
+            MIN_TRANSCRIPTION_STAGE_TIMEOUT_SECS..=MAX_TRANSCRIPTION_STAGE_TIMEOUT_SECS,
+        )?;
+        self.insertion_timeout_secs = normalize_pipeline_timeout_secs(
+            self.insertion_timeout_secs,
+            "insertion timeout",
+            MIN_INSERTION_TIMEOUT_SECS..=MAX_INSERTION_TIMEOUT_SECS,
+        )?;
+        self.proxy_url = normalize_proxy_url(self.proxy_url)?;
+        self.proxy_username = normalize_optional_string(self.proxy_username);
+        self.openai_organization = normalize_optional_string(self.openai_organization);
+        self.openai_project = normalize_optional_string(self.openai_project);
+        self.openai_transcription_model =
+            normalize_openai_transcription_model(self.openai_transcription_model)?;
+        self.provider_connections = normalize_provider_connections(self.provider_connections)?;
+        self.max_concurrent_transcriptions =
+            normalize_max_concurrent_transcriptions(self.max_concurrent_transcriptions)?;
+        self.transcription_requests_per_minute =
+            normalize_transcription_requests_per_minute(self.transcription_requests_per_minute)?;
+        self.provider_switch_shortcut = normalize_provider_switch_shortcut(
+            self.provider_switch_shortcut,
+            &self.hotkey_shortcut,
+        )?;
+        self.command_palette_shortcut = normalize_command_palette_shortcut(
+            self.command_palette_shortcut,
+            &self.hotkey_shortcut,
+            self.provider_switch_shortcut.as_deref(),
+        )?;
+        self.context_carryover_window_secs =
+            normalize_context_carryover_window_secs(self.context_carryover_window_secs)?;
+        self.context_carryover_max_chars =
+            normalize_context_carryover_max_chars(self.context_carryover_max_chars)?;
+        self.paragraph_segmentation =
+            normalize_paragraph_segmentation(self.paragraph_segmentation)?;
+        self.paragraph_pause_ms = normalize_paragraph_pause_ms(self.paragraph_pause_ms)?;
+        self.filler_removal = normalize_filler_removal(self.filler_removal)?;
+        self.recording_alert_thresholds_secs =
+            normalize_recording_alert_thresholds_secs(self.recording_alert_thresholds_secs)?;
+        self.redaction_terms = normalize_redaction_terms(self.redaction_terms);
+        self.date_format = normalize_date_format(self.date_format)?;
+        self.time_format = normalize_time_format(self.time_format)?;
+        self.insertion_trailing = normalize_insertion_trailing(self.insertion_trailing)?;
+        self.trailing_overrides = normalize_trailing_overrides(self.trailing_overrides)?;
+        self.typing_chars_per_second =
+            normalize_typing_chars_per_second(self.typing_chars_per_second)?;
+        self.typing_chunk_chars = normalize_typing_chunk_chars(self.typing_chunk_chars)?;
+        self.typing_speed_overrides =
+            normalize_typing_speed_overrides(self.typing_speed_overrides)?;
+        self.routing_rules = normalize_routing_rules(self.routing_rules)?;
+        self.history_window_bounds = normalize_window_bounds(self.history_window_bounds)?;
+        self.streak_min_words = normalize_streak_min_words(self.streak_min_words)?;
+        self.streak_freezes = normalize_streak_freezes(self.streak_freezes)?;
+        self.privacy_mode_shortcut = normalize_privacy_mode_shortcut(
+            self.privacy_mode_shortcut,
+            &self.hotkey_shortcut,
+            [
+                self.provider_switch_shortcut.as_deref(),
+                self.command_palette_shortcut.as_deref(),
+            ],
+        )?;
+        self.privacy_mode_duration_mins =
+            normalize_privacy_mode_duration_mins(self.privacy_mode_duration_mins)?;
+        self.prompt_templates = normalize_prompt_templates(
+            self.prompt_templates,
+            self.markdown_output_path.as_deref(),
+            &self.hotkey_shortcut,
+            [
+                self.provider_switch_shortcut.as_deref(),
+                self.command_palette_shortcut.as_deref(),
+                self.privacy_mode_shortcut.as_deref(),
+            ],
+        )?;
+        self.active_prompt_template =
+            normalize_active_prompt_template(self.active_prompt_template, &self.prompt_templates)?;
 
         Ok(self)
     }
@@ -110,6 +632,354 @@ impl VoiceSettings {
             self.onboarding_completed = onboarding_completed;
         }
 
+        if let Some(http_api_enabled) = update.http_api_enabled {
+            self.http_api_enabled = http_api_enabled;
+        }
+
+        if let Some(http_api_port) = update.http_api_port {
+            self.http_api_port = http_api_port;
+        }
+
+        if let Some(midi_trigger_note) = update.midi_trigger_note {
+            self.midi_trigger_note = midi_trigger_note;
+        }
+
+        if let Some(hid_trigger_device) = update.hid_trigger_device {
+            self.hid_trigger_device = hid_trigger_device;
+        }
+
+        if let Some(output_target) = update.output_target {
+            self.output_target = output_target;
+        }
+
+        if let Some(markdown_output_path) = update.markdown_output_path {
+            self.markdown_output_path = markdown_output_path;
+        }
+
+        if let Some(meeting_system_audio_device_id) = update.meeting_system_audio_device_id {
+            self.meeting_system_audio_device_id = meeting_system_audio_device_id;
+        }
+
+        if let Some(meeting_diarization_enabled) = update.meeting_diarization_enabled {
+            self.meeting_diarization_enabled = meeting_diarization_enabled;
+        }
+
+        if let Some(translation_target_language) = update.translation_target_language {
+            self.translation_target_language = translation_target_language;
+        }
+
+        if let Some(queued_dictation_enabled) = update.queued_dictation_enabled {
+            self.queued_dictation_enabled = queued_dictation_enabled;
+        }
+
+        if let Some(double_tap_modifier) = update.double_tap_modifier {
+            self.double_tap_modifier = double_tap_modifier;
+        }
+
+        if let Some(double_tap_interval_ms) = update.double_tap_interval_ms {
+            self.double_tap_interval_ms = double_tap_interval_ms;
+        }
+
+        if let Some(hotkey_suspended_apps) = update.hotkey_suspended_apps {
+            self.hotkey_suspended_apps = hotkey_suspended_apps;
+        }
+
+        if let Some(long_press_threshold_ms) = update.long_press_threshold_ms {
+            self.long_press_threshold_ms = long_press_threshold_ms;
+        }
+
+        if let Some(overlay_anchor) = update.overlay_anchor {
+            self.overlay_anchor = overlay_anchor;
+        }
+
+        if let Some(overlay_display) = update.overlay_display {
+            self.overlay_display = overlay_display;
+        }
+
+        if let Some(preload_mode) = update.preload_mode {
+            self.preload_mode = preload_mode;
+        }
+
+        if let Some(min_recording_duration_ms) = update.min_recording_duration_ms {
+            self.min_recording_duration_ms = min_recording_duration_ms;
+        }
+
+        if let Some(redact_profanity) = update.redact_profanity {
+            self.redact_profanity = redact_profanity;
+        }
+
+        if let Some(redact_custom_terms) = update.redact_custom_terms {
+            self.redact_custom_terms = redact_custom_terms;
+        }
+
+        if let Some(redaction_terms) = update.redaction_terms {
+            self.redaction_terms = redaction_terms;
+        }
+
+        if let Some(redact_card_numbers) = update.redact_card_numbers {
+            self.redact_card_numbers = redact_card_numbers;
+        }
+
+        if let Some(redact_emails) = update.redact_emails {
+            self.redact_emails = redact_emails;
+        }
+
+        if let Some(format_numbers_as_digits) = update.format_numbers_as_digits {
+            self.format_numbers_as_digits = format_numbers_as_digits;
+        }
+
+        if let Some(date_format) = update.date_format {
+            self.date_format = date_format;
+        }
+
+        if let Some(time_format) = update.time_format {
+            self.time_format = time_format;
+        }
+
+        if let Some(abbreviate_units) = update.abbreviate_units {
+            self.abbreviate_units = abbreviate_units;
+        }
+
+        if let Some(insertion_trailing) = update.insertion_trailing {
+            self.insertion_trailing = insertion_trailing;
+        }
+
+        if let Some(strip_trailing_period) = update.strip_trailing_period {
+            self.strip_trailing_period = strip_trailing_period;
+        }
+
+        if let Some(trailing_overrides) = update.trailing_overrides {
+            self.trailing_overrides = trailing_overrides;
+        }
+
+        if let Some(typing_chars_per_second) = update.typing_chars_per_second {
+            self.typing_chars_per_second = typing_chars_per_second;
+        }
+
+        if let Some(typing_chunk_chars) = update.typing_chunk_chars {
+            self.typing_chunk_chars = typing_chunk_chars;
+        }
+
+        if let Some(typing_speed_overrides) = update.typing_speed_overrides {
+            self.typing_speed_overrides = typing_speed_overrides;
+        }
+
+        if let Some(conceal_clipboard_writes) = update.conceal_clipboard_writes {
+            self.conceal_clipboard_writes = conceal_clipboard_writes;
+        }
+
+        if let Some(replace_selection) = update.replace_selection {
+            self.replace_selection = replace_selection;
+        }
+
+        if let Some(selection_as_cleanup_context) = update.selection_as_cleanup_context {
+            self.selection_as_cleanup_context = selection_as_cleanup_context;
+        }
+
+        if let Some(prompt_templates) = update.prompt_templates {
+            self.prompt_templates = prompt_templates;
+        }
+
+        if let Some(active_prompt_template) = update.active_prompt_template {
+            self.active_prompt_template = active_prompt_template;
+        }
+
+        if let Some(local_analytics_enabled) = update.local_analytics_enabled {
+            self.local_analytics_enabled = local_analytics_enabled;
+        }
+
+        if let Some(pre_roll_enabled) = update.pre_roll_enabled {
+            self.pre_roll_enabled = pre_roll_enabled;
+        }
+
+        if let Some(pre_roll_ms) = update.pre_roll_ms {
+            self.pre_roll_ms = pre_roll_ms;
+        }
+
+        if let Some(prewarm_microphone) = update.prewarm_microphone {
+            self.prewarm_microphone = prewarm_microphone;
+        }
+
+        if let Some(transcript_cache_size) = update.transcript_cache_size {
+            self.transcript_cache_size = transcript_cache_size;
+        }
+
+        if let Some(transcript_cache_ttl_secs) = update.transcript_cache_ttl_secs {
+            self.transcript_cache_ttl_secs = transcript_cache_ttl_secs;
+        }
+
+        if let Some(history_sync_folder) = update.history_sync_folder {
+            self.history_sync_folder = history_sync_folder;
+        }
+
+        if let Some(wake_word_enabled) = update.wake_word_enabled {
+            self.wake_word_enabled = wake_word_enabled;
+        }
+
+        if let Some(wake_word_sensitivity) = update.wake_word_sensitivity {
+            self.wake_word_sensitivity = wake_word_sensitivity;
+        }
+
+        if let Some(microphone_profiles) = update.microphone_profiles {
+            self.microphone_profiles = microphone_profiles;
+        }
+
+        if let Some(prefer_built_in_microphone) = update.prefer_built_in_microphone {
+            self.prefer_built_in_microphone = prefer_built_in_microphone;
+        }
+
+        if let Some(status_error_reset_ms) = update.status_error_reset_ms {
+            self.status_error_reset_ms = status_error_reset_ms;
+        }
+
+        if let Some(transcribing_timeout_secs) = update.transcribing_timeout_secs {
+            self.transcribing_timeout_secs = transcribing_timeout_secs;
+        }
+
+        if let Some(listening_timeout_secs) = update.listening_timeout_secs {
+            self.listening_timeout_secs = listening_timeout_secs;
+        }
+
+        if let Some(max_recording_secs) = update.max_recording_secs {
+            self.max_recording_secs = max_recording_secs;
+        }
+
+        if let Some(transcription_stage_timeout_secs) = update.transcription_stage_timeout_secs {
+            self.transcription_stage_timeout_secs = transcription_stage_timeout_secs;
+        }
+
+        if let Some(insertion_timeout_secs) = update.insertion_timeout_secs {
+            self.insertion_timeout_secs = insertion_timeout_secs;
+        }
+
+        if let Some(proxy_url) = update.proxy_url {
+            self.proxy_url = proxy_url;
+        }
+
+        if let Some(proxy_username) = update.proxy_username {
+            self.proxy_username = proxy_username;
+        }
+
+        if let Some(openai_organization) = update.openai_organization {
+            self.openai_organization = openai_organization;
+        }
+
+        if let Some(openai_project) = update.openai_project {
+            self.openai_project = openai_project;
+        }
+
+        if let Some(openai_transcription_model) = update.openai_transcription_model {
+            self.openai_transcription_model = openai_transcription_model;
+        }
+
+        if let Some(provider_connections) = update.provider_connections {
+            self.provider_connections = provider_connections;
+        }
+
+        if let Some(max_concurrent_transcriptions) = update.max_concurrent_transcriptions {
+            self.max_concurrent_transcriptions = max_concurrent_transcriptions;
+        }
+
+        if let Some(transcription_requests_per_minute) = update.transcription_requests_per_minute {
+            self.transcription_requests_per_minute = transcription_requests_per_minute;
+        }
+
+        if let Some(provider_switch_shortcut) = update.provider_switch_shortcut {
+            self.provider_switch_shortcut = provider_switch_shortcut;
+        }
+
+        if let Some(command_palette_shortcut) = update.command_palette_shortcut {
+            self.command_palette_shortcut = command_palette_shortcut;
+        }
+
+        if let Some(context_carryover_enabled) = update.context_carryover_enabled {
+            self.context_carryover_enabled = context_carryover_enabled;
+        }
+
+        if let Some(context_carryover_window_secs) = update.context_carryover_window_secs {
+            self.context_carryover_window_secs = context_carryover_window_secs;
+        }
+
+        if let Some(context_carryover_max_chars) = update.context_carryover_max_chars {
+            self.context_carryover_max_chars = context_carryover_max_chars;
+        }
+
+        if let Some(keyboard_language_hint) = update.keyboard_language_hint {
+            self.keyboard_language_hint = keyboard_language_hint;
+        }
+
+        if let Some(paragraph_segmentation) = update.paragraph_segmentation {
+            self.paragraph_segmentation = paragraph_segmentation;
+        }
+
+        if let Some(paragraph_pause_ms) = update.paragraph_pause_ms {
+            self.paragraph_pause_ms = paragraph_pause_ms;
+        }
+
+        if let Some(filler_removal) = update.filler_removal {
+            self.filler_removal = filler_removal;
+        }
+
+        if let Some(spoken_punctuation) = update.spoken_punctuation {
+            self.spoken_punctuation = spoken_punctuation;
+        }
+
+        if let Some(screen_reader_announcements) = update.screen_reader_announcements {
+            self.screen_reader_announcements = screen_reader_announcements;
+        }
+
+        if let Some(voice_error_recovery) = update.voice_error_recovery {
+            self.voice_error_recovery = voice_error_recovery;
+        }
+
+        if let Some(recording_alert_thresholds_secs) = update.recording_alert_thresholds_secs {
+            self.recording_alert_thresholds_secs = recording_alert_thresholds_secs;
+        }
+
+        if let Some(recording_alert_chime) = update.recording_alert_chime {
+            self.recording_alert_chime = recording_alert_chime;
+        }
+
+        if let Some(pause_media_while_recording) = update.pause_media_while_recording {
+            self.pause_media_while_recording = pause_media_while_recording;
+        }
+
+        if let Some(blocked_apps) = update.blocked_apps {
+            self.blocked_apps = blocked_apps;
+        }
+
+        if let Some(routing_rules) = update.routing_rules {
+            self.routing_rules = routing_rules;
+        }
+
+        if let Some(history_window_bounds) = update.history_window_bounds {
+            self.history_window_bounds = history_window_bounds;
+        }
+
+        if let Some(streak_min_words) = update.streak_min_words {
+            self.streak_min_words = streak_min_words;
+        }
+
+        if let Some(streak_workdays_only) = update.streak_workdays_only {
+            self.streak_workdays_only = streak_workdays_only;
+        }
+
+        if let Some(streak_freezes) = update.streak_freezes {
+            self.streak_freezes = streak_freezes;
+        }
+
+        if let Some(record_history_source) = update.record_history_source {
+            self.record_history_source = record_history_source;
+        }
+
+        if let Some(privacy_mode_shortcut) = update.privacy_mode_shortcut {
+            self.privacy_mode_shortcut = privacy_mode_shortcut;
+        }
+
+        if let Some(privacy_mode_duration_mins) = update.privacy_mode_duration_mins {
+            self.privacy_mode_duration_mins = privacy_mode_duration_mins;
+        }
+
         self.normalized()
     }
 }
@@ -127,12 +997,162 @@ pub struct VoiceSettingsUpdate {
     pub auto_insert: Option<bool>,
     pub launch_at_login: Option<bool>,
     pub onboarding_completed: Option<bool>,
+    pub http_api_enabled: Option<bool>,
+    pub http_api_port: Option<u16>,
+    pub midi_trigger_note: Option<Option<u8>>,
+    pub hid_trigger_device: Option<Option<String>>,
+    pub output_target: Option<String>,
+    pub markdown_output_path: Option<Option<String>>,
+    pub meeting_system_audio_device_id: Option<Option<String>>,
+    pub meeting_diarization_enabled: Option<bool>,
+    pub translation_target_language: Option<Option<String>>,
+    pub queued_dictation_enabled: Option<bool>,
+    pub double_tap_modifier: Option<Option<String>>,
+    pub double_tap_interval_ms: Option<u64>,
+    pub hotkey_suspended_apps: Option<Vec<String>>,
+    pub long_press_threshold_ms: Option<Option<u64>>,
+    pub overlay_anchor: Option<String>,
+    pub overlay_display: Option<String>,
+    pub preload_mode: Option<String>,
+    pub min_recording_duration_ms: Option<u64>,
+    pub redact_profanity: Option<bool>,
+    pub redact_custom_terms: Option<bool>,
+    pub redaction_terms: Option<Vec<String>>,
+    pub redact_card_numbers: Option<bool>,
+    pub redact_emails: Option<bool>,
+    pub format_numbers_as_digits: Option<bool>,
+    pub date_format: Option<String>,
+    pub time_format: Option<String>,
+    pub abbreviate_units: Option<bool>,
+    pub insertion_trailing: Option<String>,
+    pub strip_trailing_period: Option<bool>,
+    pub trailing_overrides: Option<Vec<TrailingOverride>>,
+    pub typing_chars_per_second: Option<u32>,
+    pub typing_chunk_chars: Option<u32>,
+    pub typing_speed_overrides: Option<Vec<TypingSpeedOverride>>,
+    pub conceal_clipboard_writes: Option<bool>,
+    pub replace_selection: Option<bool>,
+    pub selection_as_cleanup_context: Option<bool>,
+    pub prompt_templates: Option<Vec<PromptTemplate>>,
+    pub active_prompt_template: Option<Option<String>>,
+    pub local_analytics_enabled: Option<bool>,
+    pub pre_roll_enabled: Option<bool>,
+    pub pre_roll_ms: Option<u64>,
+    pub prewarm_microphone: Option<bool>,
+    pub transcript_cache_size: Option<u32>,
+    pub transcript_cache_ttl_secs: Option<u64>,
+    pub history_sync_folder: Option<Option<String>>,
+    pub wake_word_enabled: Option<bool>,
+    pub wake_word_sensitivity: Option<u8>,
+    pub microphone_profiles: Option<Vec<MicrophoneProfile>>,
+    pub prefer_built_in_microphone: Option<bool>,
+    pub status_error_reset_ms: Option<u64>,
+    pub transcribing_timeout_secs: Option<u64>,
+    pub listening_timeout_secs: Option<u64>,
+    pub max_recording_secs: Option<u64>,
+    pub transcription_stage_timeout_secs: Option<u64>,
+    pub insertion_timeout_secs: Option<u64>,
+    pub proxy_url: Option<Option<String>>,
+    pub proxy_username: Option<Option<String>>,
+    pub openai_organization: Option<Option<String>>,
+    pub openai_project: Option<Option<String>>,
+    pub openai_transcription_model: Option<Option<String>>,
+    pub provider_connections: Option<Vec<ProviderConnection>>,
+    pub max_concurrent_transcriptions: Option<u32>,
+    pub transcription_requests_per_minute: Option<u32>,
+    pub provider_switch_shortcut: Option<Option<String>>,
+    pub command_palette_shortcut: Option<Option<String>>,
+    pub context_carryover_enabled: Option<bool>,
+    pub context_carryover_window_secs: Option<u32>,
+    pub context_carryover_max_chars: Option<u32>,
+    pub keyboard_language_hint: Option<bool>,
+    pub paragraph_segmentation: Option<String>,
+    pub paragraph_pause_ms: Option<u32>,
+    pub filler_removal: Option<String>,
+    pub spoken_punctuation: Option<bool>,
+    pub screen_reader_announcements: Option<bool>,
+    pub voice_error_recovery: Option<bool>,
+    pub recording_alert_thresholds_secs: Option<Vec<u32>>,
+    pub recording_alert_chime: Option<bool>,
+    pub pause_media_while_recording: Option<bool>,
+    pub blocked_apps: Option<Vec<String>>,
+    pub routing_rules: Option<Vec<RoutingRule>>,
+    pub history_window_bounds: Option<Option<WindowBounds>>,
+    pub streak_min_words: Option<u64>,
+    pub streak_workdays_only: Option<bool>,
+    pub streak_freezes: Option<u32>,
+    pub record_history_source: Option<bool>,
+    pub privacy_mode_shortcut: Option<Option<String>>,
+    pub privacy_mode_duration_mins: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SettingsFieldError {
+    pub field: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SettingsValidationError {
+    pub message: String,
+    pub fields: Vec<SettingsFieldError>,
+}
+
+impl SettingsValidationError {
+    fn from_fields(fields: Vec<SettingsFieldError>) -> Self {
+        let names = fields
+            .iter()
+            .map(|error| error.field.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self {
+            message: format!("Invalid settings: {names}"),
+            fields,
+        }
+    }
+}
+
+impl From<String> for SettingsValidationError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            fields: Vec::new(),
+        }
+    }
+}
+
+pub fn validate_settings_patch(
+    current: &VoiceSettings,
+    patch: &Map<String, Value>,
+) -> Vec<SettingsFieldError> {
+    let known_fields = match serde_json::to_value(current) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    patch
+        .iter()
+        .filter_map(|(field, value)| {
+            let message = if !known_fields.contains_key(field) {
+                "Unknown setting".to_string()
+            } else {
+                let single = Map::from_iter([(field.clone(), value.clone())]);
+                match serde_json::from_value::<VoiceSettingsUpdate>(Value::Object(single)) {
+                    Ok(update) => current.clone().with_update(update).err()?,
+                    Err(error) => format!("Invalid value: {error}"),
+                }
+            };
+            Some(SettingsFieldError {
+                field: field.clone(),
+                message,
+            })
+        })
+        .collect()
 }
 
 #[derive(Debug)]
 pub struct SettingsStore {
     settings: RwLock<VoiceSettings>,
-    io_lock: Mutex<()>,
+    document: Mutex<Option<Arc<JsonDocumentStore<VoiceSettings>>>>,
 }
 
 impl Default for SettingsStore {
@@ -146,7 +1166,7 @@ impl SettingsStore {
         debug!("settings store initialized");
         Self {
             settings: RwLock::new(VoiceSettings::default()),
-            io_lock: Mutex::new(()),
+            document: Mutex::new(None),
         }
     }
 
@@ -173,6 +1193,36 @@ impl SettingsStore {
         self.update_at_path(&settings_path, update)
     }
 
+    pub fn update_patch<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        patch: Map<String, Value>,
+    ) -> Result<VoiceSettings, SettingsValidationError> {
+        let field_errors = validate_settings_patch(&self.current(), &patch);
+        if !field_errors.is_empty() {
+            return Err(SettingsValidationError::from_fields(field_errors));
+        }
+        let update = serde_json::from_value::<VoiceSettingsUpdate>(Value::Object(patch))
+            .map_err(|error| format!("Failed to parse settings update: {error}"))?;
+        Ok(self.update(app, update)?)
+    }
+
+    pub fn restore<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        settings: VoiceSettings,
+    ) -> Result<VoiceSettings, String> {
+        let settings_path = self.settings_path(app)?;
+        info!(path = %settings_path.display(), "restoring settings");
+        self.restore_at_path(&settings_path, settings)
+    }
+
+    pub fn load_from_app_data_dir(&self, app_data_dir: &Path) -> Result<VoiceSettings, String> {
+        let settings_path = app_data_dir.join(SETTINGS_FILE_NAME);
+        debug!(path = %settings_path.display(), "loading settings from disk");
+        self.load_from_path(&settings_path)
+    }
+
     fn settings_path<R: Runtime>(&self, app: &AppHandle<R>) -> Result<PathBuf, String> {
         let app_data_dir = app
             .path()
@@ -182,9 +1232,41 @@ impl SettingsStore {
         Ok(app_data_dir.join(SETTINGS_FILE_NAME))
     }
 
-    fn load_from_path(&self, settings_path: &Path) -> Result<VoiceSettings, String> {
-        let _io_guard = self.io_lock.lock().map_err(|_| io_lock_error())?;
-        let settings = read_settings_file_with_recovery(settings_path)?;
+    fn document(
+        &self,
+        settings_path: &Path,
+    ) -> Result<Arc<JsonDocumentStore<VoiceSettings>>, String> {
+        let mut document = self.document.lock().map_err(|_| io_lock_error())?;
+        if let Some(current) = document
+            .as_ref()
+            .filter(|current| current.file_path() == settings_path)
+        {
+            return Ok(current.clone());
+        }
+        let created = Arc::new(settings_document(settings_path));
+        *document = Some(created.clone());
+        Ok(created)
+    }
+
+    fn load_from_path(&self, settings_path: &Path) -> Result<VoiceSettings, String> {
+        let document = self.document(settings_path)?;
+        let _io_guard = document.lock()?;
+        let settings = document.read()?;
+        let mut guard = self.settings.write().map_err(|_| lock_error())?;
+        *guard = settings.clone();
+        Ok(settings)
+    }
+
+    fn restore_at_path(
+        &self,
+        settings_path: &Path,
+        settings: VoiceSettings,
+    ) -> Result<VoiceSettings, String> {
+        let settings = settings.normalized()?;
+        let document = self.document(settings_path)?;
+        let _io_guard = document.lock()?;
+        write_settings_file(&document, &settings)?;
+
         let mut guard = self.settings.write().map_err(|_| lock_error())?;
         *guard = settings.clone();
         Ok(settings)
@@ -195,10 +1277,11 @@ impl SettingsStore {
         settings_path: &Path,
         update: VoiceSettingsUpdate,
     ) -> Result<VoiceSettings, String> {
-        let _io_guard = self.io_lock.lock().map_err(|_| io_lock_error())?;
-        let current_settings = read_settings_file_with_recovery(settings_path)?;
+        let document = self.document(settings_path)?;
+        let _io_guard = document.lock()?;
+        let current_settings = document.read()?;
         let updated_settings = current_settings.with_update(update)?;
-        write_settings_file(settings_path, &updated_settings)?;
+        write_settings_file(&document, &updated_settings)?;
 
         let mut guard = self.settings.write().map_err(|_| lock_error())?;
         *guard = updated_settings.clone();
@@ -206,93 +1289,18 @@ impl SettingsStore {
     }
 }
 
-#[derive(Debug)]
-struct SettingsReadError {
-    message: String,
-    recoverable: bool,
-}
-
-impl SettingsReadError {
-    fn read(message: String) -> Self {
-        Self {
-            message,
-            recoverable: false,
-        }
-    }
-
-    fn malformed(message: String) -> Self {
-        Self {
-            message,
-            recoverable: true,
-        }
-    }
-}
-
-fn read_settings_file_with_recovery(settings_path: &Path) -> Result<VoiceSettings, String> {
-    match read_settings_file(settings_path) {
-        Ok(settings) => Ok(settings),
-        Err(error) if error.recoverable => {
-            let backup_path = backup_corrupt_settings_file(settings_path)?;
-            let defaults = VoiceSettings::default();
-            write_settings_file(settings_path, &defaults)?;
-            warn!(
-                path = %settings_path.display(),
-                backup = %backup_path.display(),
-                reason = %error.message,
-                "recovered malformed settings file"
-            );
-            Ok(defaults)
-        }
-        Err(error) => Err(error.message),
-    }
-}
-
-fn read_settings_file(settings_path: &Path) -> Result<VoiceSettings, SettingsReadError> {
-    if !settings_path.exists() {
-        info!(path = %settings_path.display(), "settings file missing; using defaults");
-        return Ok(VoiceSettings::default());
-    }
-
-    let file_contents = fs::read_to_string(settings_path)
-        .map_err(|error| {
-            format!(
-                "Failed to read settings file `{}`: {error}",
-                settings_path.display()
-            )
-        })
-        .map_err(SettingsReadError::read)?;
-
-    let parsed = serde_json::from_str::<VoiceSettings>(&file_contents).map_err(|error| {
-        SettingsReadError::malformed(format!(
-            "Failed to parse settings file `{}`: {error}",
-            settings_path.display()
-        ))
-    })?;
-
-    parsed.normalized().map_err(|error| {
-        SettingsReadError::malformed(format!(
-            "Failed to validate settings file `{}`: {error}",
-            settings_path.display()
-        ))
-    })
+fn settings_document(settings_path: &Path) -> JsonDocumentStore<VoiceSettings> {
+    JsonDocumentStore::new(settings_path.to_path_buf(), "settings")
+        .with_normalize(VoiceSettings::normalized)
 }
 
-fn write_settings_file(settings_path: &Path, settings: &VoiceSettings) -> Result<(), String> {
-    if let Some(parent_dir) = settings_path.parent() {
-        fs::create_dir_all(parent_dir).map_err(|error| {
-            format!(
-                "Failed to create settings directory `{}`: {error}",
-                parent_dir.display()
-            )
-        })?;
-    }
-
-    let serialized = serde_json::to_vec_pretty(settings)
-        .map_err(|error| format!("Failed to serialize settings: {error}"))?;
-    write_atomic_file(settings_path, &serialized)?;
-
+fn write_settings_file(
+    document: &JsonDocumentStore<VoiceSettings>,
+    settings: &VoiceSettings,
+) -> Result<(), String> {
+    document.write(settings)?;
     info!(
-        path = %settings_path.display(),
+        path = %document.file_path().display(),
         recording_mode = %settings.recording_mode,
         auto_insert = settings.auto_insert,
         "settings file written"
@@ -300,87 +1308,6 @@ fn write_settings_file(settings_path: &Path, settings: &VoiceSettings) -> Result
     Ok(())
 }
 
-fn write_atomic_file(file_path: &Path, contents: &[u8]) -> Result<(), String> {
-    let temp_path = temp_file_path_for(file_path);
-    let mut temp_file = fs::OpenOptions::new()
-        .create_new(true)
-        .write(true)
-        .open(&temp_path)
-        .map_err(|error| {
-            format!(
-                "Failed to create temp settings file `{}`: {error}",
-                temp_path.display()
-            )
-        })?;
-
-    if let Err(error) = temp_file.write_all(contents) {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Failed to write temp settings file `{}`: {error}",
-            temp_path.display()
-        ));
-    }
-
-    if let Err(error) = temp_file.sync_all() {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Failed to flush temp settings file `{}`: {error}",
-            temp_path.display()
-        ));
-    }
-
-    drop(temp_file);
-
-    fs::rename(&temp_path, file_path).map_err(|error| {
-        let _ = fs::remove_file(&temp_path);
-        format!(
-            "Failed to finalize settings file `{}`: {error}",
-            file_path.display()
-        )
-    })?;
-
-    Ok(())
-}
-
-fn temp_file_path_for(file_path: &Path) -> PathBuf {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("settings.json");
-    let pid = std::process::id();
-
-    file_path.with_file_name(format!(".{file_name}.{pid}.{timestamp}.tmp"))
-}
-
-fn backup_corrupt_settings_file(settings_path: &Path) -> Result<PathBuf, String> {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = settings_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("settings.json");
-    let backup_path = settings_path.with_file_name(format!(
-        "{file_name}.corrupt-{}-{timestamp}.bak",
-        std::process::id()
-    ));
-
-    fs::rename(settings_path, &backup_path).map_err(|error| {
-        format!(
-            "Failed to backup malformed settings file `{}` to `{}`: {error}",
-            settings_path.display(),
-            backup_path.display()
-        )
-    })?;
-
-    Ok(backup_path)
-}
-
 fn normalize_optional_string(value: Option<String>) -> Option<String> {
     value.and_then(|candidate| {
         let trimmed = candidate.trim();
@@ -411,6 +1338,23 @@ fn normalize_recording_mode(value: String) -> Result<String, String> {
     }
 }
 
+fn normalize_output_target(
+    value: String,
+    markdown_output_path: Option<&str>,
+) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "output_target")?.to_lowercase();
+    match normalized.as_str() {
+        OUTPUT_TARGET_CURSOR => Ok(normalized),
+        OUTPUT_TARGET_MARKDOWN | OUTPUT_TARGET_BOTH if markdown_output_path.is_none() => Err(
+            format!("Output target `{normalized}` requires a markdown_output_path"),
+        ),
+        OUTPUT_TARGET_MARKDOWN | OUTPUT_TARGET_BOTH => Ok(normalized),
+        _ => Err(format!(
+            "Unsupported output target `{normalized}`. Expected `{OUTPUT_TARGET_CURSOR}`, `{OUTPUT_TARGET_MARKDOWN}`, or `{OUTPUT_TARGET_BOTH}`"
+        )),
+    }
+}
+
 fn normalize_transcription_provider(value: String) -> Result<String, String> {
     let normalized = normalize_required_string(value, "transcription_provider")?.to_lowercase();
     match normalized.as_str() {
@@ -431,257 +1375,2316 @@ fn normalize_transcription_style(value: String) -> String {
     }
 }
 
-fn lock_error() -> String {
-    "Settings store lock was poisoned".to_string()
+fn normalize_http_api_port(value: u16) -> Result<u16, String> {
+    if value == 0 {
+        return Ok(DEFAULT_HTTP_API_PORT);
+    }
+
+    if value < MIN_HTTP_API_PORT {
+        return Err(format!(
+            "Unsupported HTTP API port `{value}`. Expected a port between {MIN_HTTP_API_PORT} and 65535"
+        ));
+    }
+
+    Ok(value)
 }
 
-fn io_lock_error() -> String {
-    "Settings store IO lock was poisoned".to_string()
+fn normalize_midi_trigger_note(value: Option<u8>) -> Result<Option<u8>, String> {
+    match value {
+        Some(note) if note > MAX_MIDI_NOTE => Err(format!(
+            "Unsupported MIDI trigger note `{note}`. Expected a note between 0 and {MAX_MIDI_NOTE}"
+        )),
+        _ => Ok(value),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::{
-        path::PathBuf,
-        time::{SystemTime, UNIX_EPOCH},
+fn normalize_translation_target_language(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(language) = normalize_optional_string(value) else {
+        return Ok(None);
     };
 
-    use super::*;
+    if !language
+        .chars()
+        .all(|character| character.is_ascii_alphabetic() || character == '-')
+    {
+        return Err(format!(
+            "Unsupported translation target language `{language}`. Expected a language code like `en` or `pt-BR`"
+        ));
+    }
 
-    fn unique_settings_path(prefix: &str) -> PathBuf {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("time should move forward")
-            .as_nanos();
-        std::env::temp_dir()
-            .join(format!("voice-settings-store-{prefix}-{timestamp}"))
-            .join("settings.json")
+    Ok(Some(language.to_ascii_lowercase()))
+}
+
+fn normalize_double_tap_modifier(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(modifier) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+
+    let modifier = modifier.to_ascii_lowercase();
+    if !DOUBLE_TAP_MODIFIERS.contains(&modifier.as_str()) {
+        return Err(format!(
+            "Unsupported double-tap modifier `{modifier}`. Expected one of: {}",
+            DOUBLE_TAP_MODIFIERS.join(", ")
+        ));
     }
 
-    fn cleanup_settings_path(path: &Path) {
-        if let Some(parent_dir) = path.parent() {
-            let _ = fs::remove_dir_all(parent_dir);
-        }
+    Ok(Some(modifier))
+}
+
+fn normalize_double_tap_interval_ms(value: u64) -> Result<u64, String> {
+    if !(MIN_DOUBLE_TAP_INTERVAL_MS..=MAX_DOUBLE_TAP_INTERVAL_MS).contains(&value) {
+        return Err(format!(
+            "Unsupported double-tap interval `{value}ms`. Expected a value between {MIN_DOUBLE_TAP_INTERVAL_MS} and {MAX_DOUBLE_TAP_INTERVAL_MS}"
+        ));
     }
 
-    fn corrupt_backup_paths(settings_path: &Path) -> Vec<PathBuf> {
-        let Some(parent_dir) = settings_path.parent() else {
-            return Vec::new();
-        };
-        let Some(file_name) = settings_path.file_name().and_then(|name| name.to_str()) else {
-            return Vec::new();
-        };
+    Ok(value)
+}
 
-        let mut backups = Vec::new();
-        if let Ok(entries) = fs::read_dir(parent_dir) {
-            for entry in entries.flatten() {
-                if let Some(candidate) = entry.file_name().to_str() {
-                    if candidate.starts_with(&format!("{file_name}.corrupt-"))
-                        && candidate.ends_with(".bak")
-                    {
-                        backups.push(entry.path());
-                    }
-                }
-            }
+fn normalize_long_press_threshold_ms(value: Option<u64>) -> Result<Option<u64>, String> {
+    match value {
+        Some(threshold)
+            if !(MIN_LONG_PRESS_THRESHOLD_MS..=MAX_LONG_PRESS_THRESHOLD_MS)
+                .contains(&threshold) =>
+        {
+            Err(format!(
+                "Unsupported long-press threshold `{threshold}ms`. Expected a value between {MIN_LONG_PRESS_THRESHOLD_MS} and {MAX_LONG_PRESS_THRESHOLD_MS}"
+            ))
         }
-
-        backups
+        _ => Ok(value),
     }
+}
 
-    #[test]
-    fn defaults_match_expected_schema() {
-        let defaults = VoiceSettings::default();
-
-        assert_eq!(defaults.hotkey_shortcut, DEFAULT_HOTKEY_SHORTCUT);
-        assert_eq!(defaults.recording_mode, RECORDING_MODE_TOGGLE);
-        assert_eq!(defaults.microphone_id, None);
-        assert_eq!(defaults.language, None);
-        assert_eq!(
-            defaults.transcription_provider,
-            DEFAULT_TRANSCRIPTION_PROVIDER
-        );
-        assert_eq!(defaults.transcription_style, DEFAULT_TRANSCRIPTION_STYLE);
-        assert_eq!(defaults.custom_transcription_prompt, "");
-        assert!(defaults.auto_insert);
-        assert!(!defaults.launch_at_login);
-        assert!(!defaults.onboarding_completed);
+fn normalize_overlay_anchor(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "overlay_anchor")?.to_lowercase();
+    if !OVERLAY_ANCHORS.contains(&normalized.as_str()) {
+        return Err(format!(
+            "Unsupported overlay anchor `{normalized}`. Expected one of: {}",
+            OVERLAY_ANCHORS.join(", ")
+        ));
     }
 
-    #[test]
-    fn load_uses_defaults_when_settings_file_is_missing() {
-        let store = SettingsStore::new();
-        let settings_path = unique_settings_path("missing");
+    Ok(normalized)
+}
 
-        let loaded = store
-            .load_from_path(&settings_path)
-            .expect("loading missing settings should succeed");
+fn normalize_overlay_display(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "overlay_display")?.to_lowercase();
+    match normalized.as_str() {
+        OVERLAY_DISPLAY_CURSOR | OVERLAY_DISPLAY_FOCUSED_WINDOW => Ok(normalized),
+        _ => Err(format!(
+            "Unsupported overlay display `{normalized}`. Expected `{OVERLAY_DISPLAY_CURSOR}` or `{OVERLAY_DISPLAY_FOCUSED_WINDOW}`"
+        )),
+    }
+}
 
-        assert_eq!(loaded, VoiceSettings::default());
-        cleanup_settings_path(&settings_path);
+fn normalize_preload_mode(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "preload_mode")?.to_lowercase();
+    match normalized.as_str() {
+        PRELOAD_MODE_OFF | PRELOAD_MODE_ON_HOTKEY | PRELOAD_MODE_KEEP_WARM => Ok(normalized),
+        _ => Err(format!(
+            "Unsupported preload mode `{normalized}`. Expected `{PRELOAD_MODE_OFF}`, `{PRELOAD_MODE_ON_HOTKEY}`, or `{PRELOAD_MODE_KEEP_WARM}`"
+        )),
     }
+}
 
-    #[test]
-    fn load_backfills_launch_at_login_for_legacy_settings_files() {
-        let store = SettingsStore::new();
-        let settings_path = unique_settings_path("legacy");
+fn normalize_min_recording_duration_ms(value: u64) -> Result<u64, String> {
+    if value > MAX_MIN_RECORDING_DURATION_MS {
+        return Err(format!(
+            "Unsupported minimum recording duration `{value}ms`. Expected a value between 0 and {MAX_MIN_RECORDING_DURATION_MS}"
+        ));
+    }
 
-        if let Some(parent_dir) = settings_path.parent() {
-            fs::create_dir_all(parent_dir).expect("legacy test directory should be created");
-        }
+    Ok(value)
+}
 
-        let legacy_payload = serde_json::json!({
-            "hotkey_shortcut": "Alt+Space",
-            "recording_mode": "hold_to_talk",
-            "microphone_id": null,
-            "language": null,
-            "transcription_provider": "openai",
-            "auto_insert": true
-        });
-        fs::write(
-            &settings_path,
-            serde_json::to_string_pretty(&legacy_payload)
-                .expect("legacy settings payload should serialize"),
-        )
-        .expect("legacy settings file should be written");
+fn normalize_pre_roll_ms(value: u64) -> Result<u64, String> {
+    if !(MIN_PRE_ROLL_MS..=MAX_PRE_ROLL_MS).contains(&value) {
+        return Err(format!(
+            "Unsupported pre-roll duration `{value}ms`. Expected a value between {MIN_PRE_ROLL_MS} and {MAX_PRE_ROLL_MS}"
+        ));
+    }
 
-        let loaded = store
-            .load_from_path(&settings_path)
-            .expect("legacy settings should load");
+    Ok(value)
+}
 
-        assert!(!loaded.launch_at_login);
-        assert!(!loaded.onboarding_completed);
-        assert_eq!(loaded.transcription_style, DEFAULT_TRANSCRIPTION_STYLE);
-        assert_eq!(loaded.custom_transcription_prompt, "");
-        cleanup_settings_path(&settings_path);
+fn normalize_transcript_cache_size(value: u32) -> Result<u32, String> {
+    if value > MAX_TRANSCRIPT_CACHE_SIZE {
+        return Err(format!(
+            "Unsupported transcript cache size `{value}`. Expected a value between 0 and {MAX_TRANSCRIPT_CACHE_SIZE}"
+        ));
     }
 
-    #[test]
-    fn update_persists_settings_to_disk() {
-        let store = SettingsStore::new();
-        let settings_path = unique_settings_path("persist");
-
-        let updated = store
-            .update_at_path(
-                &settings_path,
-                VoiceSettingsUpdate {
-                    hotkey_shortcut: Some("Cmd+Shift+Space".to_string()),
-                    recording_mode: Some("toggle".to_string()),
-                    microphone_id: Some(Some("mic-42".to_string())),
-                    language: Some(Some("en".to_string())),
-                    transcription_provider: Some("OpenAI".to_string()),
-                    transcription_style: Some("Casual".to_string()),
-                    custom_transcription_prompt: Some("   Keep filler words.  ".to_string()),
-                    auto_insert: Some(false),
-                    launch_at_login: Some(true),
-                    onboarding_completed: Some(true),
-                },
-            )
-            .expect("update should succeed");
+    Ok(value)
+}
 
-        let reloaded = read_settings_file(&settings_path).expect("reloading persisted settings");
+fn normalize_transcript_cache_ttl_secs(value: u64) -> Result<u64, String> {
+    if !(MIN_TRANSCRIPT_CACHE_TTL_SECS..=MAX_TRANSCRIPT_CACHE_TTL_SECS).contains(&value) {
+        return Err(format!(
+            "Unsupported transcript cache TTL `{value}s`. Expected a value between {MIN_TRANSCRIPT_CACHE_TTL_SECS} and {MAX_TRANSCRIPT_CACHE_TTL_SECS}"
+        ));
+    }
 
-        assert_eq!(updated.hotkey_shortcut, "Cmd+Shift+Space");
-        assert_eq!(updated.recording_mode, RECORDING_MODE_TOGGLE);
-        assert_eq!(updated.microphone_id.as_deref(), Some("mic-42"));
-        assert_eq!(updated.language.as_deref(), Some("en"));
-        assert_eq!(updated.transcription_provider, "openai");
-        assert_eq!(updated.transcription_style, "casual");
-        assert_eq!(updated.custom_transcription_prompt, "Keep filler words.");
-        assert!(!updated.auto_insert);
-        assert!(updated.launch_at_login);
-        assert!(updated.onboarding_completed);
-        assert_eq!(reloaded, updated);
+    Ok(value)
+}
 
-        cleanup_settings_path(&settings_path);
+fn normalize_wake_word_sensitivity(value: u8) -> Result<u8, String> {
+    if !(1..=MAX_WAKE_WORD_SENSITIVITY).contains(&value) {
+        return Err(format!(
+            "Unsupported wake word sensitivity `{value}`. Expected a value between 1 and {MAX_WAKE_WORD_SENSITIVITY}"
+        ));
     }
 
-    #[test]
-    fn update_accepts_null_to_clear_optional_values() {
-        let store = SettingsStore::new();
-        let settings_path = unique_settings_path("clear-options");
+    Ok(value)
+}
+
+fn normalize_status_error_reset_ms(value: u64) -> Result<u64, String> {
+    if !(MIN_STATUS_ERROR_RESET_MS..=MAX_STATUS_ERROR_RESET_MS).contains(&value) {
+        return Err(format!(
+            "Unsupported error status duration `{value}ms`. Expected a value between {MIN_STATUS_ERROR_RESET_MS} and {MAX_STATUS_ERROR_RESET_MS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_transcribing_timeout_secs(value: u64) -> Result<u64, String> {
+    if value != 0
+        && !(MIN_TRANSCRIBING_TIMEOUT_SECS..=MAX_TRANSCRIBING_TIMEOUT_SECS).contains(&value)
+    {
+        return Err(format!(
+            "Unsupported transcription timeout `{value}s`. Expected 0 to disable it or a value between {MIN_TRANSCRIBING_TIMEOUT_SECS} and {MAX_TRANSCRIBING_TIMEOUT_SECS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_listening_timeout_secs(value: u64) -> Result<u64, String> {
+    if value != 0 && !(MIN_LISTENING_TIMEOUT_SECS..=MAX_LISTENING_TIMEOUT_SECS).contains(&value) {
+        return Err(format!(
+            "Unsupported listening timeout `{value}s`. Expected 0 to disable it or a value between {MIN_LISTENING_TIMEOUT_SECS} and {MAX_LISTENING_TIMEOUT_SECS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_pipeline_timeout_secs(
+    value: u64,
+    name: &str,
+    range: RangeInclusive<u64>,
+) -> Result<u64, String> {
+    if value != 0 && !range.contains(&value) {
+        return Err(format!(
+            "Unsupported {name} `{value}s`. Expected 0 to disable it or a value between {} and {}",
+            range.start(),
+            range.end()
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_openai_transcription_model(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(model) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+
+    let model = model.to_ascii_lowercase();
+    if !OPENAI_TRANSCRIPTION_MODELS.contains(&model.as_str()) {
+        return Err(format!(
+            "Unsupported OpenAI transcription model `{model}`. Expected one of: {}",
+            OPENAI_TRANSCRIPTION_MODELS.join(", ")
+        ));
+    }
+
+    Ok(Some(model))
+}
+
+fn normalize_proxy_url(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(url) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+    let lowercase = url.to_ascii_lowercase();
+    if !SUPPORTED_PROXY_SCHEMES
+        .iter()
+        .any(|scheme| lowercase.starts_with(scheme) && lowercase.len() > scheme.len())
+    {
+        return Err(format!(
+            "Unsupported proxy URL `{url}`. Expected an http://, https://, socks5:// or socks5h:// URL"
+        ));
+    }
+
+    Ok(Some(url))
+}
+
+fn normalize_provider_switch_shortcut(
+    value: Option<String>,
+    hotkey_shortcut: &str,
+) -> Result<Option<String>, String> {
+    let shortcut = normalize_optional_string(value);
+    if shortcut
+        .as_deref()
+        .is_some_and(|shortcut| shortcut.eq_ignore_ascii_case(hotkey_shortcut))
+    {
+        return Err(format!(
+            "Provider switch shortcut `{hotkey_shortcut}` is already the dictation hotkey"
+        ));
+    }
+
+    Ok(shortcut)
+}
+
+fn normalize_command_palette_shortcut(
+    value: Option<String>,
+    hotkey_shortcut: &str,
+    provider_switch_shortcut: Option<&str>,
+) -> Result<Option<String>, String> {
+    let Some(shortcut) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+    if shortcut.eq_ignore_ascii_case(hotkey_shortcut) {
+        return Err(format!(
+            "Command palette shortcut `{shortcut}` is already the dictation hotkey"
+        ));
+    }
+    if provider_switch_shortcut.is_some_and(|other| shortcut.eq_ignore_ascii_case(other)) {
+        return Err(format!(
+            "Command palette shortcut `{shortcut}` is already the provider switch shortcut"
+        ));
+    }
+
+    Ok(Some(shortcut))
+}
+
+fn normalize_max_concurrent_transcriptions(value: u32) -> Result<u32, String> {
+    if !(1..=MAX_CONCURRENT_TRANSCRIPTIONS).contains(&value) {
+        return Err(format!(
+            "Unsupported concurrent transcription limit `{value}`. Expected a value between 1 and {MAX_CONCURRENT_TRANSCRIPTIONS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_transcription_requests_per_minute(value: u32) -> Result<u32, String> {
+    if value > MAX_TRANSCRIPTION_REQUESTS_PER_MINUTE {
+        return Err(format!(
+            "Unsupported transcription rate `{value}` per minute. Expected 0 for no limit or a value up to {MAX_TRANSCRIPTION_REQUESTS_PER_MINUTE}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_context_carryover_window_secs(value: u32) -> Result<u32, String> {
+    if !(MIN_CONTEXT_CARRYOVER_WINDOW_SECS..=MAX_CONTEXT_CARRYOVER_WINDOW_SECS).contains(&value) {
+        return Err(format!(
+            "Unsupported context carryover window `{value}s`. Expected a value between {MIN_CONTEXT_CARRYOVER_WINDOW_SECS} and {MAX_CONTEXT_CARRYOVER_WINDOW_SECS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_context_carryover_max_chars(value: u32) -> Result<u32, String> {
+    if !(MIN_CONTEXT_CARRYOVER_MAX_CHARS..=MAX_CONTEXT_CARRYOVER_MAX_CHARS).contains(&value) {
+        return Err(format!(
+            "Unsupported context carryover length `{value}` characters. Expected a value between {MIN_CONTEXT_CARRYOVER_MAX_CHARS} and {MAX_CONTEXT_CARRYOVER_MAX_CHARS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_paragraph_segmentation(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "paragraph_segmentation")?.to_lowercase();
+    match normalized.as_str() {
+        PARAGRAPH_SEGMENTATION_OFF | PARAGRAPH_SEGMENTATION_PAUSES | PARAGRAPH_SEGMENTATION_TOPICS => {
+            Ok(normalized)
+        }
+        _ => Err(format!(
+            "Unsupported paragraph segmentation `{normalized}`. Expected `{PARAGRAPH_SEGMENTATION_OFF}`, `{PARAGRAPH_SEGMENTATION_PAUSES}`, or `{PARAGRAPH_SEGMENTATION_TOPICS}`"
+        )),
+    }
+}
+
+fn normalize_paragraph_pause_ms(value: u32) -> Result<u32, String> {
+    if !(MIN_PARAGRAPH_PAUSE_MS..=MAX_PARAGRAPH_PAUSE_MS).contains(&value) {
+        return Err(format!(
+            "Unsupported paragraph pause `{value}ms`. Expected a value between {MIN_PARAGRAPH_PAUSE_MS} and {MAX_PARAGRAPH_PAUSE_MS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_filler_removal(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "filler_removal")?.to_lowercase();
+    match normalized.as_str() {
+        FILLER_REMOVAL_OFF
+        | FILLER_REMOVAL_LIGHT
+        | FILLER_REMOVAL_MODERATE
+        | FILLER_REMOVAL_AGGRESSIVE => Ok(normalized),
+        _ => Err(format!(
+            "Unsupported filler removal level `{normalized}`. Expected `{FILLER_REMOVAL_OFF}`, `{FILLER_REMOVAL_LIGHT}`, `{FILLER_REMOVAL_MODERATE}`, or `{FILLER_REMOVAL_AGGRESSIVE}`"
+        )),
+    }
+}
+
+fn normalize_recording_alert_thresholds_secs(mut thresholds: Vec<u32>) -> Result<Vec<u32>, String> {
+    if let Some(value) = thresholds.iter().find(|value| {
+        !(MIN_RECORDING_ALERT_THRESHOLD_SECS..=MAX_RECORDING_ALERT_THRESHOLD_SECS).contains(value)
+    }) {
+        return Err(format!(
+            "Unsupported recording alert threshold `{value}s`. Expected a value between {MIN_RECORDING_ALERT_THRESHOLD_SECS} and {MAX_RECORDING_ALERT_THRESHOLD_SECS}"
+        ));
+    }
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    if thresholds.len() > MAX_RECORDING_ALERT_THRESHOLDS {
+        return Err(format!(
+            "Too many recording alert thresholds. Expected at most {MAX_RECORDING_ALERT_THRESHOLDS}"
+        ));
+    }
+
+    Ok(thresholds)
+}
+
+fn normalize_provider_connections(
+    connections: Vec<ProviderConnection>,
+) -> Result<Vec<ProviderConnection>, String> {
+    let mut normalized = Vec::<ProviderConnection>::new();
+    for connection in connections {
+        let provider = connection.provider.trim().to_lowercase();
+        if !CONNECTION_PROVIDERS.contains(&provider.as_str()) {
+            return Err(format!(
+                "Unsupported connection provider `{provider}`. Expected one of: {}",
+                CONNECTION_PROVIDERS.join(", ")
+            ));
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.provider == provider)
+        {
+            return Err(format!("Duplicate connection settings for `{provider}`"));
+        }
+
+        normalized.push(ProviderConnection {
+            provider,
+            ca_bundle_path: normalize_optional_string(connection.ca_bundle_path),
+            bypass_proxy: connection.bypass_proxy,
+        });
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_microphone_profiles(
+    profiles: Vec<MicrophoneProfile>,
+) -> Result<Vec<MicrophoneProfile>, String> {
+    let mut normalized = Vec::<MicrophoneProfile>::new();
+    for profile in profiles {
+        let device_id = profile.device_id.trim().to_string();
+        if device_id.is_empty() {
+            return Err("Microphone profile device ids cannot be empty".to_string());
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.device_id == device_id)
+        {
+            return Err(format!("Duplicate microphone profile for `{device_id}`"));
+        }
+        if !(MIN_MICROPHONE_GAIN_DB..=MAX_MICROPHONE_GAIN_DB).contains(&profile.gain_db) {
+            return Err(format!(
+                "Unsupported microphone gain `{}dB`. Expected a value between {MIN_MICROPHONE_GAIN_DB} and {MAX_MICROPHONE_GAIN_DB}",
+                profile.gain_db
+            ));
+        }
+        if profile.noise_suppression > MAX_NOISE_SUPPRESSION {
+            return Err(format!(
+                "Unsupported noise suppression `{}`. Expected a value between 0 and {MAX_NOISE_SUPPRESSION}",
+                profile.noise_suppression
+            ));
+        }
+        if profile
+            .input_channel
+            .is_some_and(|channel| channel > MAX_INPUT_CHANNEL)
+        {
+            return Err(format!(
+                "Unsupported input channel. Expected a value between 0 and {MAX_INPUT_CHANNEL}"
+            ));
+        }
+
+        normalized.push(MicrophoneProfile {
+            device_id,
+            ..profile
+        });
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_app_entries(apps: Vec<String>) -> Vec<String> {
+    let mut normalized = Vec::<String>::new();
+    for app in apps {
+        let app = app.trim();
+        if !app.is_empty()
+            && !normalized
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(app))
+        {
+            normalized.push(app.to_string());
+        }
+    }
+
+    normalized
+}
+
+fn normalize_date_format(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "date_format")?.to_lowercase();
+    match normalized.as_str() {
+        DATE_FORMAT_OFF
+        | DATE_FORMAT_ISO
+        | DATE_FORMAT_MONTH_DAY_YEAR
+        | DATE_FORMAT_DAY_MONTH_YEAR => Ok(normalized),
+        _ => Err(format!(
+            "Unsupported date format `{normalized}`. Expected `{DATE_FORMAT_OFF}`, `{DATE_FORMAT_ISO}`, `{DATE_FORMAT_MONTH_DAY_YEAR}`, or `{DATE_FORMAT_DAY_MONTH_YEAR}`"
+        )),
+    }
+}
+
+fn normalize_time_format(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "time_format")?.to_lowercase();
+    match normalized.as_str() {
+        TIME_FORMAT_OFF | TIME_FORMAT_12H | TIME_FORMAT_24H => Ok(normalized),
+        _ => Err(format!(
+            "Unsupported time format `{normalized}`. Expected `{TIME_FORMAT_OFF}`, `{TIME_FORMAT_12H}`, or `{TIME_FORMAT_24H}`"
+        )),
+    }
+}
+
+fn normalize_insertion_trailing(value: String) -> Result<String, String> {
+    let normalized = normalize_required_string(value, "insertion_trailing")?.to_lowercase();
+    match normalized.as_str() {
+        INSERTION_TRAILING_NONE | INSERTION_TRAILING_SPACE | INSERTION_TRAILING_NEWLINE => {
+            Ok(normalized)
+        }
+        _ => Err(format!(
+            "Unsupported insertion trailing `{normalized}`. Expected `{INSERTION_TRAILING_NONE}`, `{INSERTION_TRAILING_SPACE}`, or `{INSERTION_TRAILING_NEWLINE}`"
+        )),
+    }
+}
+
+fn normalize_trailing_overrides(
+    overrides: Vec<TrailingOverride>,
+) -> Result<Vec<TrailingOverride>, String> {
+    let mut normalized = Vec::<TrailingOverride>::new();
+    for trailing_override in overrides {
+        let app = trailing_override.app.trim().to_string();
+        if app.is_empty() {
+            return Err("Trailing override apps cannot be empty".to_string());
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.app.eq_ignore_ascii_case(&app))
+        {
+            return Err(format!("Duplicate trailing override for `{app}`"));
+        }
+
+        normalized.push(TrailingOverride {
+            app,
+            insertion_trailing: normalize_insertion_trailing(trailing_override.insertion_trailing)?,
+            strip_trailing_period: trailing_override.strip_trailing_period,
+        });
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_typing_chars_per_second(value: u32) -> Result<u32, String> {
+    if value != 0 && !(MIN_TYPING_CHARS_PER_SECOND..=MAX_TYPING_CHARS_PER_SECOND).contains(&value) {
+        return Err(format!(
+            "Unsupported typing speed `{value}` characters per second. Expected 0 to type at full speed or a value between {MIN_TYPING_CHARS_PER_SECOND} and {MAX_TYPING_CHARS_PER_SECOND}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_typing_chunk_chars(value: u32) -> Result<u32, String> {
+    if !(1..=MAX_TYPING_CHUNK_CHARS).contains(&value) {
+        return Err(format!(
+            "Unsupported typing chunk size `{value}`. Expected a value between 1 and {MAX_TYPING_CHUNK_CHARS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_typing_speed_overrides(
+    overrides: Vec<TypingSpeedOverride>,
+) -> Result<Vec<TypingSpeedOverride>, String> {
+    let mut normalized = Vec::<TypingSpeedOverride>::new();
+    for speed_override in overrides {
+        let app = speed_override.app.trim().to_string();
+        if app.is_empty() {
+            return Err("Typing speed override apps cannot be empty".to_string());
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.app.eq_ignore_ascii_case(&app))
+        {
+            return Err(format!("Duplicate typing speed override for `{app}`"));
+        }
+
+        normalized.push(TypingSpeedOverride {
+            app,
+            typing_chars_per_second: normalize_typing_chars_per_second(
+                speed_override.typing_chars_per_second,
+            )?,
+            typing_chunk_chars: normalize_typing_chunk_chars(speed_override.typing_chunk_chars)?,
+        });
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_routing_rules(rules: Vec<RoutingRule>) -> Result<Vec<RoutingRule>, String> {
+    let mut normalized = Vec::<RoutingRule>::new();
+    for rule in rules {
+        let name = rule.name.trim().to_string();
+        if name.is_empty() {
+            return Err("Routing rule names cannot be empty".to_string());
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(format!("Duplicate routing rule `{name}`"));
+        }
+
+        let app = normalize_optional_string(rule.app);
+        let prefix = normalize_optional_string(rule.prefix);
+        let active_from = normalize_optional_string(rule.active_from);
+        let active_until = normalize_optional_string(rule.active_until);
+        match (active_from.as_deref(), active_until.as_deref()) {
+            (None, None) => {}
+            (Some(from), Some(until)) => {
+                for time in [from, until] {
+                    if parse_clock_time(time).is_none() {
+                        return Err(format!(
+                            "Unsupported time `{time}` in routing rule `{name}`. Expected HH:MM"
+                        ));
+                    }
+                }
+                if parse_clock_time(from) == parse_clock_time(until) {
+                    return Err(format!(
+                        "Routing rule `{name}` must start and end at different times"
+                    ));
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "Routing rule `{name}` needs both active_from and active_until"
+                ))
+            }
+        }
+        if app.is_none() && prefix.is_none() && active_from.is_none() {
+            return Err(format!(
+                "Routing rule `{name}` needs an app, time window or prefix"
+            ));
+        }
+
+        let destination = rule.destination.trim().to_lowercase();
+        let target = normalize_optional_string(rule.target);
+        match destination.as_str() {
+            ROUTE_DESTINATION_INSERT | ROUTE_DESTINATION_CLIPBOARD => {}
+            ROUTE_DESTINATION_FILE if target.is_none() => {
+                return Err(format!("Routing rule `{name}` needs a file path"));
+            }
+            ROUTE_DESTINATION_FILE => {}
+            ROUTE_DESTINATION_WEBHOOK => {
+                let is_http_url = target.as_deref().is_some_and(|url| {
+                    let lowercase = url.to_ascii_lowercase();
+                    ["http://", "https://"].iter().any(|scheme| {
+                        lowercase.starts_with(scheme) && lowercase.len() > scheme.len()
+                    })
+                });
+                if !is_http_url {
+                    return Err(format!(
+                        "Routing rule `{name}` needs an http:// or https:// webhook URL"
+                    ));
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "Unsupported destination `{destination}` in routing rule `{name}`. Expected `{ROUTE_DESTINATION_INSERT}`, `{ROUTE_DESTINATION_CLIPBOARD}`, `{ROUTE_DESTINATION_FILE}`, or `{ROUTE_DESTINATION_WEBHOOK}`"
+                ));
+            }
+        }
+
+        normalized.push(RoutingRule {
+            name,
+            app,
+            active_from,
+            active_until,
+            prefix,
+            destination,
+            target,
+        });
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_window_bounds(value: Option<WindowBounds>) -> Result<Option<WindowBounds>, String> {
+    let Some(bounds) = value else {
+        return Ok(None);
+    };
+    if !(MIN_WINDOW_WIDTH..=MAX_WINDOW_DIMENSION).contains(&bounds.width)
+        || !(MIN_WINDOW_HEIGHT..=MAX_WINDOW_DIMENSION).contains(&bounds.height)
+    {
+        return Err(format!(
+            "Unsupported window size `{}x{}`. Expected at least {MIN_WINDOW_WIDTH}x{MIN_WINDOW_HEIGHT} and at most {MAX_WINDOW_DIMENSION} on each side",
+            bounds.width, bounds.height
+        ));
+    }
+
+    Ok(Some(bounds))
+}
+
+fn normalize_streak_min_words(value: u64) -> Result<u64, String> {
+    if value > MAX_STREAK_MIN_WORDS {
+        return Err(format!(
+            "Unsupported streak minimum `{value}` words. Expected at most {MAX_STREAK_MIN_WORDS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_streak_freezes(value: u32) -> Result<u32, String> {
+    if value > MAX_STREAK_FREEZES {
+        return Err(format!(
+            "Unsupported streak freeze count `{value}`. Expected at most {MAX_STREAK_FREEZES}"
+        ));
+    }
+
+    Ok(value)
+}
+
+fn normalize_privacy_mode_shortcut(
+    value: Option<String>,
+    hotkey_shortcut: &str,
+    other_shortcuts: [Option<&str>; 2],
+) -> Result<Option<String>, String> {
+    let Some(shortcut) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+    if shortcut.eq_ignore_ascii_case(hotkey_shortcut) {
+        return Err(format!(
+            "Privacy mode shortcut `{shortcut}` is already the dictation hotkey"
+        ));
+    }
+    if other_shortcuts
+        .into_iter()
+        .flatten()
+        .any(|other| shortcut.eq_ignore_ascii_case(other))
+    {
+        return Err(format!(
+            "Privacy mode shortcut `{shortcut}` is already used by another shortcut"
+        ));
+    }
+
+    Ok(Some(shortcut))
+}
+
+fn normalize_privacy_mode_duration_mins(value: u32) -> Result<u32, String> {
+    if value > MAX_PRIVACY_MODE_DURATION_MINS {
+        return Err(format!(
+            "Unsupported privacy mode duration `{value}` minutes. Expected 0 to stay on until turned off, or at most {MAX_PRIVACY_MODE_DURATION_MINS}"
+        ));
+    }
+
+    Ok(value)
+}
+
+pub fn parse_clock_time(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let hours = hours.parse::<u32>().ok().filter(|hours| *hours < 24)?;
+    let minutes = minutes
+        .parse::<u32>()
+        .ok()
+        .filter(|minutes| *minutes < 60)?;
+    Some(hours * 60 + minutes)
+}
+
+fn normalize_prompt_templates(
+    templates: Vec<PromptTemplate>,
+    markdown_output_path: Option<&str>,
+    hotkey_shortcut: &str,
+    other_shortcuts: [Option<&str>; 3],
+) -> Result<Vec<PromptTemplate>, String> {
+    let mut normalized = Vec::<PromptTemplate>::new();
+    for template in templates {
+        let name = template.name.trim().to_string();
+        if name.is_empty() {
+            return Err("Prompt template names cannot be empty".to_string());
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(format!("Duplicate prompt template `{name}`"));
+        }
+        let output_target = template
+            .output_target
+            .map(|target| normalize_output_target(target, markdown_output_path))
+            .transpose()?;
+        let shortcut = normalize_optional_string(template.shortcut);
+        if let Some(shortcut) = shortcut.as_deref() {
+            let taken = shortcut.eq_ignore_ascii_case(hotkey_shortcut)
+                || other_shortcuts
+                    .into_iter()
+                    .flatten()
+                    .any(|other| shortcut.eq_ignore_ascii_case(other))
+                || normalized.iter().any(|existing| {
+                    existing
+                        .shortcut
+                        .as_deref()
+                        .is_some_and(|other| shortcut.eq_ignore_ascii_case(other))
+                });
+            if taken {
+                return Err(format!(
+                    "Shortcut `{shortcut}` for prompt template `{name}` is already in use"
+                ));
+            }
+        }
+
+        normalized.push(PromptTemplate {
+            name,
+            prompt: template.prompt.trim().to_string(),
+            cleanup_instructions: template.cleanup_instructions.trim().to_string(),
+            spoken_punctuation: template.spoken_punctuation,
+            output_target,
+            language: normalize_optional_string(template.language),
+            translation_target_language: normalize_translation_target_language(
+                template.translation_target_language,
+            )?,
+            shortcut,
+        });
+    }
+
+    Ok(normalized)
+}
+
+fn normalize_active_prompt_template(
+    value: Option<String>,
+    templates: &[PromptTemplate],
+) -> Result<Option<String>, String> {
+    let Some(name) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+
+    templates
+        .iter()
+        .find(|template| template.name.eq_ignore_ascii_case(&name))
+        .map(|template| Some(template.name.clone()))
+        .ok_or_else(|| format!("Unknown prompt template `{name}`"))
+}
+
+fn normalize_redaction_terms(terms: Vec<String>) -> Vec<String> {
+    let mut normalized = Vec::<String>::new();
+    for term in terms {
+        let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !term.is_empty()
+            && !normalized
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&term))
+        {
+            normalized.push(term);
+        }
+    }
+
+    normalized
+}
+
+fn normalize_hid_trigger_device(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(device) = normalize_optional_string(value) else {
+        return Ok(None);
+    };
+
+    let (vendor_id, product_id) = parse_hid_trigger_device(&device)?;
+    Ok(Some(format!("{vendor_id:04x}:{product_id:04x}")))
+}
+
+pub fn parse_hid_trigger_device(value: &str) -> Result<(u16, u16), String> {
+    let invalid =
+        || format!("Unsupported HID trigger device `{value}`. Expected `vendor:product` in hex");
+    let (vendor_id, product_id) = value.trim().split_once(':').ok_or_else(invalid)?;
+    let vendor_id = u16::from_str_radix(vendor_id.trim(), 16).map_err(|_| invalid())?;
+    let product_id = u16::from_str_radix(product_id.trim(), 16).map_err(|_| invalid())?;
+
+    Ok((vendor_id, product_id))
+}
+
+fn lock_error() -> String {
+    "Settings store lock was poisoned".to_string()
+}
+
+fn io_lock_error() -> String {
+    "Settings store IO lock was poisoned".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::*;
+
+    fn unique_settings_path(prefix: &str) -> PathBuf {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time should move forward")
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("voice-settings-store-{prefix}-{timestamp}"))
+            .join("settings.json")
+    }
+
+    fn cleanup_settings_path(path: &Path) {
+        if let Some(parent_dir) = path.parent() {
+            let _ = fs::remove_dir_all(parent_dir);
+        }
+    }
+
+    fn corrupt_backup_paths(settings_path: &Path) -> Vec<PathBuf> {
+        let Some(parent_dir) = settings_path.parent() else {
+            return Vec::new();
+        };
+        let Some(file_name) = settings_path.file_name().and_then(|name| name.to_str()) else {
+            return Vec::new();
+        };
+
+        let mut backups = Vec::new();
+        if let Ok(entries) = fs::read_dir(parent_dir) {
+            for entry in entries.flatten() {
+                if let Some(candidate) = entry.file_name().to_str() {
+                    if candidate.starts_with(&format!("{file_name}.corrupt-"))
+                        && candidate.ends_with(".bak")
+                    {
+                        backups.push(entry.path());
+                    }
+                }
+            }
+        }
+
+        backups
+    }
+
+    #[test]
+    fn defaults_match_expected_schema() {
+        let defaults = VoiceSettings::default();
+
+        assert_eq!(defaults.hotkey_shortcut, DEFAULT_HOTKEY_SHORTCUT);
+        assert_eq!(defaults.recording_mode, RECORDING_MODE_TOGGLE);
+        assert_eq!(defaults.microphone_id, None);
+        assert_eq!(defaults.language, None);
+        assert_eq!(
+            defaults.transcription_provider,
+            DEFAULT_TRANSCRIPTION_PROVIDER
+        );
+        assert_eq!(defaults.transcription_style, DEFAULT_TRANSCRIPTION_STYLE);
+        assert_eq!(defaults.custom_transcription_prompt, "");
+        assert!(defaults.auto_insert);
+        assert!(!defaults.launch_at_login);
+        assert!(!defaults.onboarding_completed);
+        assert!(!defaults.http_api_enabled);
+        assert_eq!(defaults.http_api_port, DEFAULT_HTTP_API_PORT);
+        assert_eq!(defaults.midi_trigger_note, None);
+        assert_eq!(defaults.hid_trigger_device, None);
+        assert_eq!(defaults.output_target, OUTPUT_TARGET_CURSOR);
+        assert_eq!(defaults.markdown_output_path, None);
+        assert_eq!(defaults.meeting_system_audio_device_id, None);
+        assert!(!defaults.meeting_diarization_enabled);
+        assert_eq!(defaults.translation_target_language, None);
+        assert!(!defaults.queued_dictation_enabled);
+        assert_eq!(defaults.double_tap_modifier, None);
+        assert_eq!(
+            defaults.double_tap_interval_ms,
+            DEFAULT_DOUBLE_TAP_INTERVAL_MS
+        );
+        assert!(defaults.hotkey_suspended_apps.is_empty());
+        assert_eq!(defaults.long_press_threshold_ms, None);
+        assert_eq!(defaults.overlay_anchor, DEFAULT_OVERLAY_ANCHOR);
+        assert_eq!(defaults.overlay_display, OVERLAY_DISPLAY_CURSOR);
+        assert_eq!(defaults.preload_mode, PRELOAD_MODE_ON_HOTKEY);
+        assert_eq!(
+            defaults.min_recording_duration_ms,
+            DEFAULT_MIN_RECORDING_DURATION_MS
+        );
+        assert!(!defaults.redact_profanity);
+        assert!(!defaults.redact_custom_terms);
+        assert!(defaults.redaction_terms.is_empty());
+        assert!(!defaults.redact_card_numbers);
+        assert!(!defaults.redact_emails);
+        assert!(!defaults.format_numbers_as_digits);
+        assert_eq!(defaults.date_format, DATE_FORMAT_OFF);
+        assert_eq!(defaults.time_format, TIME_FORMAT_OFF);
+        assert!(!defaults.abbreviate_units);
+        assert_eq!(defaults.insertion_trailing, INSERTION_TRAILING_NONE);
+        assert!(!defaults.strip_trailing_period);
+        assert!(defaults.trailing_overrides.is_empty());
+        assert_eq!(
+            defaults.typing_chars_per_second,
+            DEFAULT_TYPING_CHARS_PER_SECOND
+        );
+        assert_eq!(defaults.typing_chunk_chars, DEFAULT_TYPING_CHUNK_CHARS);
+        assert!(defaults.typing_speed_overrides.is_empty());
+        assert!(defaults.conceal_clipboard_writes);
+        assert!(!defaults.replace_selection);
+        assert!(!defaults.selection_as_cleanup_context);
+        assert!(defaults.prompt_templates.is_empty());
+        assert_eq!(defaults.active_prompt_template, None);
+        assert!(!defaults.local_analytics_enabled);
+        assert!(!defaults.pre_roll_enabled);
+        assert_eq!(defaults.pre_roll_ms, DEFAULT_PRE_ROLL_MS);
+        assert!(!defaults.prewarm_microphone);
+        assert_eq!(
+            defaults.transcript_cache_size,
+            DEFAULT_TRANSCRIPT_CACHE_SIZE
+        );
+        assert_eq!(
+            defaults.transcript_cache_ttl_secs,
+            DEFAULT_TRANSCRIPT_CACHE_TTL_SECS
+        );
+        assert_eq!(defaults.history_sync_folder, None);
+        assert!(!defaults.wake_word_enabled);
+        assert_eq!(
+            defaults.wake_word_sensitivity,
+            DEFAULT_WAKE_WORD_SENSITIVITY
+        );
+        assert!(defaults.microphone_profiles.is_empty());
+        assert!(!defaults.prefer_built_in_microphone);
+        assert_eq!(
+            defaults.status_error_reset_ms,
+            DEFAULT_STATUS_ERROR_RESET_MS
+        );
+        assert_eq!(
+            defaults.transcribing_timeout_secs,
+            DEFAULT_TRANSCRIBING_TIMEOUT_SECS
+        );
+        assert_eq!(
+            defaults.listening_timeout_secs,
+            DEFAULT_LISTENING_TIMEOUT_SECS
+        );
+        assert_eq!(defaults.max_recording_secs, 0);
+        assert_eq!(
+            defaults.transcription_stage_timeout_secs,
+            DEFAULT_TRANSCRIPTION_STAGE_TIMEOUT_SECS
+        );
+        assert_eq!(
+            defaults.insertion_timeout_secs,
+            DEFAULT_INSERTION_TIMEOUT_SECS
+        );
+        assert_eq!(defaults.proxy_url, None);
+        assert_eq!(defaults.openai_organization, None);
+        assert_eq!(defaults.openai_project, None);
+        assert_eq!(defaults.openai_transcription_model, None);
+        assert!(defaults.provider_connections.is_empty());
+        assert_eq!(
+            defaults.max_concurrent_transcriptions,
+            DEFAULT_MAX_CONCURRENT_TRANSCRIPTIONS
+        );
+        assert_eq!(defaults.transcription_requests_per_minute, 0);
+        assert_eq!(defaults.provider_switch_shortcut, None);
+        assert_eq!(defaults.command_palette_shortcut, None);
+        assert!(!defaults.context_carryover_enabled);
+        assert_eq!(
+            defaults.context_carryover_window_secs,
+            DEFAULT_CONTEXT_CARRYOVER_WINDOW_SECS
+        );
+        assert_eq!(
+            defaults.context_carryover_max_chars,
+            DEFAULT_CONTEXT_CARRYOVER_MAX_CHARS
+        );
+        assert!(defaults.keyboard_language_hint);
+        assert_eq!(defaults.paragraph_segmentation, PARAGRAPH_SEGMENTATION_OFF);
+        assert_eq!(defaults.paragraph_pause_ms, DEFAULT_PARAGRAPH_PAUSE_MS);
+        assert_eq!(defaults.filler_removal, FILLER_REMOVAL_OFF);
+        assert!(!defaults.spoken_punctuation);
+        assert!(!defaults.screen_reader_announcements);
+        assert!(!defaults.voice_error_recovery);
+        assert_eq!(defaults.recording_alert_thresholds_secs, vec![60, 120]);
+        assert!(defaults.recording_alert_chime);
+        assert!(!defaults.pause_media_while_recording);
+        assert!(defaults.blocked_apps.is_empty());
+        assert!(defaults.routing_rules.is_empty());
+        assert_eq!(defaults.history_window_bounds, None);
+        assert_eq!(defaults.streak_min_words, 0);
+        assert!(!defaults.streak_workdays_only);
+        assert_eq!(defaults.streak_freezes, 0);
+        assert!(!defaults.record_history_source);
+        assert_eq!(defaults.privacy_mode_shortcut, None);
+        assert_eq!(defaults.privacy_mode_duration_mins, 60);
+    }
+
+    #[test]
+    fn load_uses_defaults_when_settings_file_is_missing() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("missing");
+
+        let loaded = store
+            .load_from_path(&settings_path)
+            .expect("loading missing settings should succeed");
+
+        assert_eq!(loaded, VoiceSettings::default());
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn load_backfills_launch_at_login_for_legacy_settings_files() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("legacy");
+
+        if let Some(parent_dir) = settings_path.parent() {
+            fs::create_dir_all(parent_dir).expect("legacy test directory should be created");
+        }
+
+        let legacy_payload = serde_json::json!({
+            "hotkey_shortcut": "Alt+Space",
+            "recording_mode": "hold_to_talk",
+            "microphone_id": null,
+            "language": null,
+            "transcription_provider": "openai",
+            "auto_insert": true
+        });
+        fs::write(
+            &settings_path,
+            serde_json::to_string_pretty(&legacy_payload)
+                .expect("legacy settings payload should serialize"),
+        )
+        .expect("legacy settings file should be written");
+
+        let loaded = store
+            .load_from_path(&settings_path)
+            .expect("legacy settings should load");
+
+        assert!(!loaded.launch_at_login);
+        assert!(!loaded.onboarding_completed);
+        assert_eq!(loaded.transcription_style, DEFAULT_TRANSCRIPTION_STYLE);
+        assert_eq!(loaded.custom_transcription_prompt, "");
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_persists_settings_to_disk() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("persist");
+
+        let updated = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    hotkey_shortcut: Some("Cmd+Shift+Space".to_string()),
+                    recording_mode: Some("toggle".to_string()),
+                    microphone_id: Some(Some("mic-42".to_string())),
+                    language: Some(Some("en".to_string())),
+                    transcription_provider: Some("OpenAI".to_string()),
+                    transcription_style: Some("Casual".to_string()),
+                    custom_transcription_prompt: Some("   Keep filler words.  ".to_string()),
+                    auto_insert: Some(false),
+                    launch_at_login: Some(true),
+                    onboarding_completed: Some(true),
+                    http_api_enabled: Some(true),
+                    http_api_port: Some(52_000),
+                    midi_trigger_note: Some(Some(60)),
+                    hid_trigger_device: Some(Some(" 05F3:00FF ".to_string())),
+                    output_target: Some(" Both ".to_string()),
+                    markdown_output_path: Some(Some(" ~/Notes/{date}.md ".to_string())),
+                    meeting_system_audio_device_id: Some(Some(" blackhole ".to_string())),
+                    meeting_diarization_enabled: Some(true),
+                    translation_target_language: Some(Some(" PT-br ".to_string())),
+                    queued_dictation_enabled: Some(true),
+                    double_tap_modifier: Some(Some(" Right_Cmd ".to_string())),
+                    double_tap_interval_ms: Some(400),
+                    hotkey_suspended_apps: Some(vec![
+                        " com.valvesoftware.steam ".to_string(),
+                        "COM.valvesoftware.steam".to_string(),
+                        "".to_string(),
+                        "Parallels Desktop".to_string(),
+                    ]),
+                    long_press_threshold_ms: Some(Some(800)),
+                    overlay_anchor: Some(" Bottom_Right ".to_string()),
+                    overlay_display: Some("focused_window".to_string()),
+                    preload_mode: Some(" Keep_Warm ".to_string()),
+                    min_recording_duration_ms: Some(500),
+                    redact_profanity: Some(true),
+                    redact_custom_terms: Some(true),
+                    redaction_terms: Some(vec![
+                        "  Project   Falcon ".to_string(),
+                        "project falcon".to_string(),
+                        " ".to_string(),
+                    ]),
+                    redact_card_numbers: Some(true),
+                    redact_emails: Some(true),
+                    format_numbers_as_digits: Some(true),
+                    date_format: Some(" ISO ".to_string()),
+                    time_format: Some("24H".to_string()),
+                    abbreviate_units: Some(true),
+                    insertion_trailing: Some(" Space ".to_string()),
+                    strip_trailing_period: Some(true),
+                    trailing_overrides: Some(vec![TrailingOverride {
+                        app: " com.tinyspeck.slackmacgap ".to_string(),
+                        insertion_trailing: "NEWLINE".to_string(),
+                        strip_trailing_period: false,
+                    }]),
+                    typing_chars_per_second: Some(0),
+                    typing_chunk_chars: Some(8),
+                    typing_speed_overrides: Some(vec![TypingSpeedOverride {
+                        app: " Microsoft Remote Desktop ".to_string(),
+                        typing_chars_per_second: 60,
+                        typing_chunk_chars: 1,
+                    }]),
+                    conceal_clipboard_writes: Some(false),
+                    replace_selection: Some(true),
+                    selection_as_cleanup_context: Some(true),
+                    prompt_templates: Some(vec![PromptTemplate {
+                        name: "  Email ".to_string(),
+                        prompt: " Formal email to a colleague. ".to_string(),
+                        cleanup_instructions: "Add a greeting and sign-off.".to_string(),
+                        spoken_punctuation: Some(false),
+                        output_target: Some(" Markdown ".to_string()),
+                        language: Some(" de ".to_string()),
+                        translation_target_language: Some(" EN ".to_string()),
+                        shortcut: Some(" Cmd+Shift+E ".to_string()),
+                    }]),
+                    active_prompt_template: Some(Some("email".to_string())),
+                    local_analytics_enabled: Some(true),
+                    pre_roll_enabled: Some(true),
+                    pre_roll_ms: Some(2_500),
+                    prewarm_microphone: Some(true),
+                    transcript_cache_size: Some(0),
+                    transcript_cache_ttl_secs: Some(120),
+                    history_sync_folder: Some(Some(" ~/Dropbox/Buzz ".to_string())),
+                    wake_word_enabled: Some(true),
+                    wake_word_sensitivity: Some(70),
+                    microphone_profiles: Some(vec![MicrophoneProfile {
+                        device_id: " usb-headset ".to_string(),
+                        gain_db: 6,
+                        noise_suppression: 40,
+                        input_channel: Some(1),
+                    }]),
+                    prefer_built_in_microphone: Some(true),
+                    status_error_reset_ms: Some(4_000),
+                    transcribing_timeout_secs: Some(0),
+                    listening_timeout_secs: Some(900),
+                    max_recording_secs: Some(600),
+                    transcription_stage_timeout_secs: Some(45),
+                    insertion_timeout_secs: Some(0),
+                    proxy_url: Some(Some(" socks5h://proxy.corp.example:1080 ".to_string())),
+                    proxy_username: Some(Some("buzz".to_string())),
+                    openai_organization: Some(Some("org-123".to_string())),
+                    openai_project: Some(Some(" ".to_string())),
+                    openai_transcription_model: Some(Some(" GPT-4o-Transcribe ".to_string())),
+                    provider_connections: Some(vec![ProviderConnection {
+                        provider: " OpenAI ".to_string(),
+                        ca_bundle_path: Some(" /etc/whisper/ca.pem ".to_string()),
+                        bypass_proxy: true,
+                    }]),
+                    max_concurrent_transcriptions: Some(1),
+                    transcription_requests_per_minute: Some(30),
+                    provider_switch_shortcut: Some(Some(" Cmd+Shift+P ".to_string())),
+                    command_palette_shortcut: Some(Some(" Cmd+Shift+K ".to_string())),
+                    context_carryover_enabled: Some(true),
+                    context_carryover_window_secs: Some(300),
+                    context_carryover_max_chars: Some(500),
+                    keyboard_language_hint: Some(false),
+                    paragraph_segmentation: Some(" Pauses ".to_string()),
+                    paragraph_pause_ms: Some(2_000),
+                    filler_removal: Some(" Moderate ".to_string()),
+                    spoken_punctuation: Some(true),
+                    screen_reader_announcements: Some(true),
+                    voice_error_recovery: Some(true),
+                    recording_alert_thresholds_secs: Some(vec![300, 90, 300]),
+                    recording_alert_chime: Some(false),
+                    pause_media_while_recording: Some(true),
+                    blocked_apps: Some(vec![
+                        " com.agilebits.onepassword7 ".to_string(),
+                        "Microsoft Remote Desktop".to_string(),
+                        "com.AgileBits.onepassword7".to_string(),
+                    ]),
+                    routing_rules: Some(vec![RoutingRule {
+                        name: " Notes ".to_string(),
+                        prefix: Some(" note: ".to_string()),
+                        destination: "File".to_string(),
+                        target: Some("~/Obsidian/Inbox.md".to_string()),
+                        ..RoutingRule::default()
+                    }]),
+                    history_window_bounds: Some(Some(WindowBounds {
+                        x: -1200,
+                        y: 80,
+                        width: 720,
+                        height: 640,
+                    })),
+                    streak_min_words: Some(50),
+                    streak_workdays_only: Some(true),
+                    streak_freezes: Some(2),
+                    record_history_source: Some(true),
+                    privacy_mode_shortcut: Some(Some(" Cmd+Shift+I ".to_string())),
+                    privacy_mode_duration_mins: Some(15),
+                },
+            )
+            .expect("update should succeed");
+
+        let reloaded = settings_document(&settings_path)
+            .read()
+            .expect("reloading persisted settings");
+
+        assert_eq!(updated.hotkey_shortcut, "Cmd+Shift+Space");
+        assert_eq!(updated.recording_mode, RECORDING_MODE_TOGGLE);
+        assert_eq!(updated.microphone_id.as_deref(), Some("mic-42"));
+        assert_eq!(updated.language.as_deref(), Some("en"));
+        assert_eq!(updated.transcription_provider, "openai");
+        assert_eq!(updated.transcription_style, "casual");
+        assert_eq!(updated.custom_transcription_prompt, "Keep filler words.");
+        assert!(!updated.auto_insert);
+        assert!(updated.launch_at_login);
+        assert!(updated.onboarding_completed);
+        assert!(updated.http_api_enabled);
+        assert_eq!(updated.http_api_port, 52_000);
+        assert_eq!(updated.midi_trigger_note, Some(60));
+        assert_eq!(updated.hid_trigger_device.as_deref(), Some("05f3:00ff"));
+        assert_eq!(updated.output_target, OUTPUT_TARGET_BOTH);
+        assert_eq!(
+            updated.markdown_output_path.as_deref(),
+            Some("~/Notes/{date}.md")
+        );
+        assert_eq!(
+            updated.meeting_system_audio_device_id.as_deref(),
+            Some("blackhole")
+        );
+        assert!(updated.meeting_diarization_enabled);
+        assert!(updated.queued_dictation_enabled);
+        assert_eq!(updated.double_tap_modifier.as_deref(), Some("right_cmd"));
+        assert_eq!(updated.double_tap_interval_ms, 400);
+        assert_eq!(
+            updated.hotkey_suspended_apps,
+            vec!["com.valvesoftware.steam", "Parallels Desktop"]
+        );
+        assert_eq!(updated.long_press_threshold_ms, Some(800));
+        assert_eq!(updated.overlay_anchor, "bottom_right");
+        assert_eq!(updated.overlay_display, OVERLAY_DISPLAY_FOCUSED_WINDOW);
+        assert_eq!(updated.preload_mode, PRELOAD_MODE_KEEP_WARM);
+        assert_eq!(updated.min_recording_duration_ms, 500);
+        assert!(updated.redact_profanity);
+        assert!(updated.redact_custom_terms);
+        assert_eq!(updated.redaction_terms, vec!["Project Falcon".to_string()]);
+        assert!(updated.redact_card_numbers);
+        assert!(updated.redact_emails);
+        assert!(updated.format_numbers_as_digits);
+        assert_eq!(updated.date_format, DATE_FORMAT_ISO);
+        assert_eq!(updated.time_format, TIME_FORMAT_24H);
+        assert!(updated.abbreviate_units);
+        assert_eq!(updated.insertion_trailing, INSERTION_TRAILING_SPACE);
+        assert!(updated.strip_trailing_period);
+        assert!(updated.replace_selection);
+        assert!(updated.selection_as_cleanup_context);
+        assert_eq!(
+            updated.trailing_overrides,
+            vec![TrailingOverride {
+                app: "com.tinyspeck.slackmacgap".to_string(),
+                insertion_trailing: INSERTION_TRAILING_NEWLINE.to_string(),
+                strip_trailing_period: false,
+            }]
+        );
+        assert_eq!(updated.typing_chars_per_second, 0);
+        assert_eq!(updated.typing_chunk_chars, 8);
+        assert_eq!(
+            updated.typing_speed_overrides,
+            vec![TypingSpeedOverride {
+                app: "Microsoft Remote Desktop".to_string(),
+                typing_chars_per_second: 60,
+                typing_chunk_chars: 1,
+            }]
+        );
+        assert!(!updated.conceal_clipboard_writes);
+        assert_eq!(
+            updated.prompt_templates,
+            vec![PromptTemplate {
+                name: "Email".to_string(),
+                prompt: "Formal email to a colleague.".to_string(),
+                cleanup_instructions: "Add a greeting and sign-off.".to_string(),
+                spoken_punctuation: Some(false),
+                output_target: Some(OUTPUT_TARGET_MARKDOWN.to_string()),
+                language: Some("de".to_string()),
+                translation_target_language: Some("en".to_string()),
+                shortcut: Some("Cmd+Shift+E".to_string()),
+            }]
+        );
+        assert_eq!(updated.active_prompt_template, Some("Email".to_string()));
+        assert!(updated.local_analytics_enabled);
+        assert!(updated.pre_roll_enabled);
+        assert_eq!(updated.pre_roll_ms, 2_500);
+        assert!(updated.prewarm_microphone);
+        assert_eq!(updated.transcript_cache_size, 0);
+        assert_eq!(updated.transcript_cache_ttl_secs, 120);
+        assert_eq!(
+            updated.history_sync_folder.as_deref(),
+            Some("~/Dropbox/Buzz")
+        );
+        assert!(updated.wake_word_enabled);
+        assert_eq!(updated.wake_word_sensitivity, 70);
+        assert_eq!(updated.microphone_profiles.len(), 1);
+        assert_eq!(updated.microphone_profiles[0].device_id, "usb-headset");
+        assert_eq!(updated.microphone_profiles[0].input_channel, Some(1));
+        assert!(updated.prefer_built_in_microphone);
+        assert_eq!(updated.status_error_reset_ms, 4_000);
+        assert_eq!(updated.transcribing_timeout_secs, 0);
+        assert_eq!(updated.listening_timeout_secs, 900);
+        assert_eq!(updated.max_recording_secs, 600);
+        assert_eq!(updated.transcription_stage_timeout_secs, 45);
+        assert_eq!(updated.insertion_timeout_secs, 0);
+        assert_eq!(
+            updated.proxy_url.as_deref(),
+            Some("socks5h://proxy.corp.example:1080")
+        );
+        assert_eq!(updated.proxy_username.as_deref(), Some("buzz"));
+        assert_eq!(updated.openai_organization.as_deref(), Some("org-123"));
+        assert_eq!(updated.openai_project, None);
+        assert_eq!(
+            updated.openai_transcription_model.as_deref(),
+            Some("gpt-4o-transcribe")
+        );
+        assert_eq!(
+            updated.provider_connection("openai"),
+            Some(&ProviderConnection {
+                provider: "openai".to_string(),
+                ca_bundle_path: Some("/etc/whisper/ca.pem".to_string()),
+                bypass_proxy: true,
+            })
+        );
+        assert_eq!(updated.max_concurrent_transcriptions, 1);
+        assert_eq!(updated.transcription_requests_per_minute, 30);
+        assert_eq!(
+            updated.provider_switch_shortcut.as_deref(),
+            Some("Cmd+Shift+P")
+        );
+        assert_eq!(
+            updated.command_palette_shortcut.as_deref(),
+            Some("Cmd+Shift+K")
+        );
+        assert!(updated.context_carryover_enabled);
+        assert_eq!(updated.context_carryover_window_secs, 300);
+        assert_eq!(updated.context_carryover_max_chars, 500);
+        assert!(!updated.keyboard_language_hint);
+        assert_eq!(
+            updated.paragraph_segmentation,
+            PARAGRAPH_SEGMENTATION_PAUSES
+        );
+        assert_eq!(updated.paragraph_pause_ms, 2_000);
+        assert_eq!(updated.filler_removal, FILLER_REMOVAL_MODERATE);
+        assert!(updated.spoken_punctuation);
+        assert!(updated.screen_reader_announcements);
+        assert!(updated.voice_error_recovery);
+        assert_eq!(updated.recording_alert_thresholds_secs, vec![90, 300]);
+        assert!(!updated.recording_alert_chime);
+        assert!(updated.pause_media_while_recording);
+        assert_eq!(
+            updated.blocked_apps,
+            vec!["com.agilebits.onepassword7", "Microsoft Remote Desktop"]
+        );
+        assert_eq!(
+            updated.routing_rules,
+            vec![RoutingRule {
+                name: "Notes".to_string(),
+                prefix: Some("note:".to_string()),
+                destination: ROUTE_DESTINATION_FILE.to_string(),
+                target: Some("~/Obsidian/Inbox.md".to_string()),
+                ..RoutingRule::default()
+            }]
+        );
+        assert_eq!(
+            updated.history_window_bounds,
+            Some(WindowBounds {
+                x: -1200,
+                y: 80,
+                width: 720,
+                height: 640,
+            })
+        );
+        assert_eq!(updated.streak_min_words, 50);
+        assert!(updated.streak_workdays_only);
+        assert_eq!(updated.streak_freezes, 2);
+        assert!(updated.record_history_source);
+        assert_eq!(
+            updated.privacy_mode_shortcut.as_deref(),
+            Some("Cmd+Shift+I")
+        );
+        assert_eq!(updated.privacy_mode_duration_mins, 15);
+        assert_eq!(
+            updated.translation_target_language.as_deref(),
+            Some("pt-br")
+        );
+        assert_eq!(reloaded, updated);
+
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_accepts_null_to_clear_optional_values() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("clear-options");
 
         store
             .update_at_path(
                 &settings_path,
                 VoiceSettingsUpdate {
-                    microphone_id: Some(Some("mic-a".to_string())),
-                    language: Some(Some("fr".to_string())),
+                    microphone_id: Some(Some("mic-a".to_string())),
+                    language: Some(Some("fr".to_string())),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect("initial update should succeed");
+
+        let cleared = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    microphone_id: Some(None),
+                    language: Some(None),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect("clearing update should succeed");
+
+        assert_eq!(cleared.microphone_id, None);
+        assert_eq!(cleared.language, None);
+
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_accepts_hold_to_talk_recording_mode() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("hold-to-talk-mode");
+
+        let updated = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    recording_mode: Some(RECORDING_MODE_HOLD_TO_TALK.to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect("hold-to-talk mode should be supported");
+
+        assert_eq!(updated.recording_mode, RECORDING_MODE_HOLD_TO_TALK);
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_recording_mode() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-mode");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    recording_mode: Some("invalid".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("invalid mode should fail");
+
+        assert!(error.contains("Unsupported recording mode"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_unknown_transcription_provider() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-provider");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    transcription_provider: Some("anthropic".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unsupported provider should fail");
+
+        assert!(error.contains("Unsupported transcription provider"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_translation_target_language() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-translation-target");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    translation_target_language: Some(Some("en; drop".to_string())),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("invalid translation target should fail");
+
+        assert!(error.contains("Unsupported translation target language"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_privileged_http_api_port() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-http-port");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    http_api_port: Some(80),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("privileged port should fail");
+
+        assert!(error.contains("Unsupported HTTP API port"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_hardware_trigger_bindings() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-trigger-bindings");
+
+        let note_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    midi_trigger_note: Some(Some(128)),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("out-of-range midi note should fail");
+        let device_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    hid_trigger_device: Some(Some("pedal".to_string())),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("malformed hid device should fail");
+
+        assert!(note_error.contains("Unsupported MIDI trigger note"));
+        assert!(device_error.contains("Unsupported HID trigger device"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_double_tap_settings() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-double-tap");
+
+        let modifier_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    double_tap_modifier: Some(Some("caps_lock".to_string())),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown modifier should fail");
+        let interval_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    double_tap_interval_ms: Some(50),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too-short interval should fail");
+
+        assert!(modifier_error.contains("Unsupported double-tap modifier"));
+        assert!(interval_error.contains("Unsupported double-tap interval"));
+
+        let long_press_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    long_press_threshold_ms: Some(Some(10_000)),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too-long long-press threshold should fail");
+        assert!(long_press_error.contains("Unsupported long-press threshold"));
+
+        let min_duration_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    min_recording_duration_ms: Some(60_000),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too-long minimum recording duration should fail");
+        assert!(min_duration_error.contains("Unsupported minimum recording duration"));
+
+        let pre_roll_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    pre_roll_ms: Some(5_000),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("pre-roll longer than three seconds should fail");
+        assert!(pre_roll_error.contains("Unsupported pre-roll duration"));
+
+        let cache_size_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    transcript_cache_size: Some(1_000),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("oversized transcript cache should fail");
+        assert!(cache_size_error.contains("Unsupported transcript cache size"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_unknown_date_and_time_formats() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-text-formats");
+
+        let date_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    date_format: Some("julian".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown date format should fail");
+        let time_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    time_format: Some("36h".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown time format should fail");
+
+        assert!(date_error.contains("Unsupported date format"));
+        assert!(time_error.contains("Unsupported time format"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_trailing_settings() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-trailing");
+        let trailing_override = |app: &str| TrailingOverride {
+            app: app.to_string(),
+            ..TrailingOverride::default()
+        };
+
+        let trailing_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    insertion_trailing: Some("tab".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown trailing should fail");
+        let duplicate_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    trailing_overrides: Some(vec![
+                        trailing_override("Slack"),
+                        trailing_override("slack"),
+                    ]),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("duplicate override apps should fail");
+
+        assert!(trailing_error.contains("Unsupported insertion trailing"));
+        assert!(duplicate_error.contains("Duplicate trailing override"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_out_of_range_wake_word_sensitivity() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-wake-word");
+
+        for sensitivity in [0, 101] {
+            let error = store
+                .update_at_path(
+                    &settings_path,
+                    VoiceSettingsUpdate {
+                        wake_word_sensitivity: Some(sensitivity),
+                        ..VoiceSettingsUpdate::default()
+                    },
+                )
+                .expect_err("out-of-range sensitivity should fail");
+            assert!(error.contains("Unsupported wake word sensitivity"));
+        }
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_unknown_or_duplicate_provider_connections() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-provider-connections");
+        let connection = |provider: &str| ProviderConnection {
+            provider: provider.to_string(),
+            ..ProviderConnection::default()
+        };
+        let update = |connections: Vec<ProviderConnection>| VoiceSettingsUpdate {
+            provider_connections: Some(connections),
+            ..VoiceSettingsUpdate::default()
+        };
+
+        let unknown_error = store
+            .update_at_path(&settings_path, update(vec![connection("whisper")]))
+            .expect_err("unknown providers should fail");
+        let duplicate_error = store
+            .update_at_path(
+                &settings_path,
+                update(vec![connection("openai"), connection("OpenAI")]),
+            )
+            .expect_err("duplicate providers should fail");
+
+        assert!(unknown_error.contains("Unsupported connection provider"));
+        assert!(duplicate_error.contains("Duplicate connection settings"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn proxy_password_is_never_read_from_or_written_to_settings() {
+        let settings: VoiceSettings =
+            serde_json::from_str(r#"{"has_proxy_password": true, "proxy_password": "hunter2"}"#)
+                .expect("settings should parse");
+
+        assert!(!settings.has_proxy_password);
+        let serialized = serde_json::to_string(&settings).expect("settings should serialize");
+        assert!(!serialized.contains("hunter2"));
+    }
+
+    #[test]
+    fn update_rejects_unsupported_proxy_urls() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-proxy-url");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    proxy_url: Some(Some("ftp://proxy.corp.example".to_string())),
                     ..VoiceSettingsUpdate::default()
                 },
             )
-            .expect("initial update should succeed");
+            .expect_err("ftp proxy should fail");
 
-        let cleared = store
+        assert!(error.contains("Unsupported proxy URL"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_out_of_range_status_timeouts() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-status-timeouts");
+
+        let reset_error = store
             .update_at_path(
                 &settings_path,
                 VoiceSettingsUpdate {
-                    microphone_id: Some(None),
-                    language: Some(None),
+                    status_error_reset_ms: Some(100),
                     ..VoiceSettingsUpdate::default()
                 },
             )
-            .expect("clearing update should succeed");
+            .expect_err("too short error status should fail");
+        let timeout_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    transcribing_timeout_secs: Some(5),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too short transcription timeout should fail");
+        let insertion_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    insertion_timeout_secs: Some(900),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too long insertion timeout should fail");
 
-        assert_eq!(cleared.microphone_id, None);
-        assert_eq!(cleared.language, None);
+        assert!(reset_error.contains("Unsupported error status duration"));
+        assert!(timeout_error.contains("Unsupported transcription timeout"));
+        assert!(insertion_error.contains("Unsupported insertion timeout"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_provider_switch_shortcut_matching_the_dictation_hotkey() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("provider-switch-shortcut");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    provider_switch_shortcut: Some(Some(DEFAULT_HOTKEY_SHORTCUT.to_lowercase())),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("reusing the dictation hotkey should fail");
 
+        assert!(error.contains("already the dictation hotkey"));
         cleanup_settings_path(&settings_path);
     }
 
     #[test]
-    fn update_accepts_hold_to_talk_recording_mode() {
+    fn update_rejects_command_palette_shortcut_matching_another_shortcut() {
         let store = SettingsStore::new();
-        let settings_path = unique_settings_path("hold-to-talk-mode");
+        let settings_path = unique_settings_path("command-palette-shortcut");
 
-        let updated = store
+        let error = store
             .update_at_path(
                 &settings_path,
                 VoiceSettingsUpdate {
-                    recording_mode: Some(RECORDING_MODE_HOLD_TO_TALK.to_string()),
+                    provider_switch_shortcut: Some(Some("Cmd+Shift+P".to_string())),
+                    command_palette_shortcut: Some(Some("cmd+shift+p".to_string())),
                     ..VoiceSettingsUpdate::default()
                 },
             )
-            .expect("hold-to-talk mode should be supported");
+            .expect_err("reusing the provider switch shortcut should fail");
 
-        assert_eq!(updated.recording_mode, RECORDING_MODE_HOLD_TO_TALK);
+        assert!(error.contains("already the provider switch shortcut"));
         cleanup_settings_path(&settings_path);
     }
 
     #[test]
-    fn update_rejects_invalid_recording_mode() {
+    fn update_rejects_out_of_range_transcription_limits() {
         let store = SettingsStore::new();
-        let settings_path = unique_settings_path("invalid-mode");
+        let settings_path = unique_settings_path("invalid-transcription-limits");
+
+        let concurrency_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    max_concurrent_transcriptions: Some(0),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("zero concurrent transcriptions should fail");
+        let rate_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    transcription_requests_per_minute: Some(1_000),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too high a rate should fail");
+
+        assert!(concurrency_error.contains("Unsupported concurrent transcription limit"));
+        assert!(rate_error.contains("Unsupported transcription rate"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_out_of_range_context_carryover() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-context-carryover");
+
+        let window_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    context_carryover_window_secs: Some(5),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too short a window should fail");
+        let length_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    context_carryover_max_chars: Some(10_000),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too long a carried context should fail");
+
+        assert!(window_error.contains("Unsupported context carryover window"));
+        assert!(length_error.contains("Unsupported context carryover length"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_unknown_paragraph_segmentation() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-paragraph-segmentation");
+
+        let mode_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    paragraph_segmentation: Some("sentences".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown segmentation mode should fail");
+        let pause_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    paragraph_pause_ms: Some(50),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too short a paragraph pause should fail");
+
+        assert!(mode_error.contains("Unsupported paragraph segmentation"));
+        assert!(pause_error.contains("Unsupported paragraph pause"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_unknown_filler_removal_level() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-filler-removal");
 
         let error = store
             .update_at_path(
                 &settings_path,
                 VoiceSettingsUpdate {
-                    recording_mode: Some("invalid".to_string()),
+                    filler_removal: Some("maximum".to_string()),
                     ..VoiceSettingsUpdate::default()
                 },
             )
-            .expect_err("invalid mode should fail");
+            .expect_err("unknown filler removal level should fail");
 
-        assert!(error.contains("Unsupported recording mode"));
+        assert!(error.contains("Unsupported filler removal level"));
         cleanup_settings_path(&settings_path);
     }
 
     #[test]
-    fn update_rejects_unknown_transcription_provider() {
+    fn update_rejects_out_of_range_recording_alert_thresholds() {
         let store = SettingsStore::new();
-        let settings_path = unique_settings_path("invalid-provider");
+        let settings_path = unique_settings_path("invalid-recording-alert-thresholds");
 
         let error = store
             .update_at_path(
                 &settings_path,
                 VoiceSettingsUpdate {
-                    transcription_provider: Some("anthropic".to_string()),
+                    recording_alert_thresholds_secs: Some(vec![60, 5]),
                     ..VoiceSettingsUpdate::default()
                 },
             )
-            .expect_err("unsupported provider should fail");
+            .expect_err("a threshold below the minimum should fail");
 
-        assert!(error.contains("Unsupported transcription provider"));
+        assert!(error.contains("Unsupported recording alert threshold `5s`"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_routing_rules() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-routing-rules");
+        let update = |rule: RoutingRule| VoiceSettingsUpdate {
+            routing_rules: Some(vec![rule]),
+            ..VoiceSettingsUpdate::default()
+        };
+
+        let unconditional_error = store
+            .update_at_path(
+                &settings_path,
+                update(RoutingRule {
+                    name: "Everything".to_string(),
+                    ..RoutingRule::default()
+                }),
+            )
+            .expect_err("a rule without conditions should fail");
+        let time_error = store
+            .update_at_path(
+                &settings_path,
+                update(RoutingRule {
+                    name: "Evenings".to_string(),
+                    active_from: Some("18:00".to_string()),
+                    active_until: Some("25:00".to_string()),
+                    ..RoutingRule::default()
+                }),
+            )
+            .expect_err("an invalid time should fail");
+        let webhook_error = store
+            .update_at_path(
+                &settings_path,
+                update(RoutingRule {
+                    name: "Email".to_string(),
+                    prefix: Some("email:".to_string()),
+                    destination: ROUTE_DESTINATION_WEBHOOK.to_string(),
+                    target: Some("ftp://example.com".to_string()),
+                    ..RoutingRule::default()
+                }),
+            )
+            .expect_err("a non-http webhook should fail");
+
+        assert!(unconditional_error.contains("needs an app, time window or prefix"));
+        assert!(time_error.contains("Unsupported time `25:00`"));
+        assert!(webhook_error.contains("needs an http:// or https:// webhook URL"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_window_bounds_too_small_to_use() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-window-bounds");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    history_window_bounds: Some(Some(WindowBounds {
+                        x: 0,
+                        y: 0,
+                        width: 40,
+                        height: 640,
+                    })),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("a window narrower than the minimum should fail");
+
+        assert!(error.contains("Unsupported window size `40x640`"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_more_streak_freezes_than_supported() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-streak-freezes");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    streak_freezes: Some(MAX_STREAK_FREEZES + 1),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("too many streak freezes should fail");
+
+        assert!(error.contains("Unsupported streak freeze count"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn settings_patch_reports_each_invalid_field() {
+        let patch = serde_json::json!({
+            "auto_insert": "yes",
+            "language": "en",
+            "no_such_setting": 1,
+            "recording_mode": "shout",
+        });
+        let Value::Object(patch) = patch else {
+            unreachable!();
+        };
+
+        let errors = validate_settings_patch(&VoiceSettings::default(), &patch);
+
+        let fields = errors
+            .iter()
+            .map(|error| error.field.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, ["auto_insert", "no_such_setting", "recording_mode"]);
+        assert!(errors[0].message.starts_with("Invalid value:"));
+        assert_eq!(errors[1].message, "Unknown setting");
+        assert!(errors[2]
+            .message
+            .contains("Unsupported recording mode `shout`"));
+        assert_eq!(
+            SettingsValidationError::from_fields(errors).message,
+            "Invalid settings: auto_insert, no_such_setting, recording_mode"
+        );
+    }
+
+    #[test]
+    fn update_rejects_invalid_microphone_profiles() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-microphone-profiles");
+        let profile = |device_id: &str| MicrophoneProfile {
+            device_id: device_id.to_string(),
+            ..MicrophoneProfile::default()
+        };
+        let update = |profiles: Vec<MicrophoneProfile>| VoiceSettingsUpdate {
+            microphone_profiles: Some(profiles),
+            ..VoiceSettingsUpdate::default()
+        };
+
+        let duplicate_error = store
+            .update_at_path(
+                &settings_path,
+                update(vec![profile("mic"), profile(" mic ")]),
+            )
+            .expect_err("duplicate device ids should fail");
+        let gain_error = store
+            .update_at_path(
+                &settings_path,
+                update(vec![MicrophoneProfile {
+                    gain_db: 30,
+                    ..profile("mic")
+                }]),
+            )
+            .expect_err("out-of-range gain should fail");
+        let suppression_error = store
+            .update_at_path(
+                &settings_path,
+                update(vec![MicrophoneProfile {
+                    noise_suppression: 101,
+                    ..profile("mic")
+                }]),
+            )
+            .expect_err("out-of-range suppression should fail");
+
+        assert!(duplicate_error.contains("Duplicate microphone profile"));
+        assert!(gain_error.contains("Unsupported microphone gain"));
+        assert!(suppression_error.contains("Unsupported noise suppression"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_invalid_prompt_templates() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-prompt-templates");
+        let template = |name: &str| PromptTemplate {
+            name: name.to_string(),
+            ..PromptTemplate::default()
+        };
+
+        let duplicate_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    prompt_templates: Some(vec![template("Email"), template("email")]),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("duplicate template names should fail");
+        let unknown_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    prompt_templates: Some(vec![template("Email")]),
+                    active_prompt_template: Some(Some("Medical note".to_string())),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown active template should fail");
+        let shortcut_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    prompt_templates: Some(vec![PromptTemplate {
+                        shortcut: Some(DEFAULT_HOTKEY_SHORTCUT.to_string()),
+                        ..template("Email")
+                    }]),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("template shortcut matching the hotkey should fail");
+        let output_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    prompt_templates: Some(vec![PromptTemplate {
+                        output_target: Some(OUTPUT_TARGET_BOTH.to_string()),
+                        ..template("Email")
+                    }]),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("template markdown output without a path should fail");
+
+        assert!(duplicate_error.contains("Duplicate prompt template"));
+        assert!(unknown_error.contains("Unknown prompt template"));
+        assert!(shortcut_error.contains("already in use"));
+        assert!(output_error.contains("requires a markdown_output_path"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_rejects_unknown_overlay_placement() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("invalid-overlay-placement");
+
+        let anchor_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    overlay_anchor: Some("middle".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown overlay anchor should fail");
+        let display_error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    overlay_display: Some("primary".to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("unknown overlay display should fail");
+
+        assert!(anchor_error.contains("Unsupported overlay anchor"));
+        assert!(display_error.contains("Unsupported overlay display"));
+        cleanup_settings_path(&settings_path);
+    }
+
+    #[test]
+    fn update_requires_markdown_path_for_markdown_output_target() {
+        let store = SettingsStore::new();
+        let settings_path = unique_settings_path("markdown-output-without-path");
+
+        let error = store
+            .update_at_path(
+                &settings_path,
+                VoiceSettingsUpdate {
+                    output_target: Some(OUTPUT_TARGET_MARKDOWN.to_string()),
+                    ..VoiceSettingsUpdate::default()
+                },
+            )
+            .expect_err("markdown output without a path should fail");
+
+        assert!(error.contains("requires a markdown_output_path"));
         cleanup_settings_path(&settings_path);
     }
 
@@ -702,7 +3705,9 @@ mod tests {
 
         assert_eq!(recovered, VoiceSettings::default());
         assert_eq!(
-            read_settings_file(&settings_path)
+            settings_document(&settings_path)
+                .without_recovery()
+                .read()
                 .expect("recovered settings file should be readable")
                 .normalized()
                 .expect("recovered settings should validate"),
@@ -0,0 +1,90 @@
+use tracing::debug;
+
+use crate::{history_store::HistorySource, hotkey_service, text_insertion_service};
+
+#[cfg(any(target_os = "macos", test))]
+const SAFARI_BUNDLE_IDS: [&str; 2] = ["com.apple.Safari", "com.apple.SafariTechnologyPreview"];
+#[cfg(any(target_os = "macos", test))]
+const CHROMIUM_BUNDLE_IDS: [&str; 6] = [
+    "com.google.Chrome",
+    "com.google.Chrome.canary",
+    "com.brave.Browser",
+    "com.microsoft.edgemac",
+    "com.vivaldi.Vivaldi",
+    "company.thebrowser.Browser",
+];
+
+pub fn capture() -> HistorySource {
+    let (app_name, bundle_id) = hotkey_service::frontmost_application()
+        .map(|app| (app.name, app.bundle_id))
+        .unwrap_or_default();
+    let url = bundle_id.as_deref().and_then(browser_page_url);
+    let source = HistorySource {
+        app_name,
+        bundle_id,
+        window_title: text_insertion_service::focused_window_title(),
+        url,
+    };
+    debug!(
+        app = ?source.app_name,
+        has_window_title = source.window_title.is_some(),
+        has_url = source.url.is_some(),
+        "captured dictation source"
+    );
+    source
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn browser_url_script(bundle_id: &str) -> Option<String> {
+    let matches = |known: &[&str]| known.iter().any(|id| id.eq_ignore_ascii_case(bundle_id));
+    if matches(&SAFARI_BUNDLE_IDS) {
+        Some(format!(
+            "tell application id \"{bundle_id}\" to get URL of front document"
+        ))
+    } else if matches(&CHROMIUM_BUNDLE_IDS) {
+        Some(format!(
+            "tell application id \"{bundle_id}\" to get URL of active tab of front window"
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn browser_page_url(bundle_id: &str) -> Option<String> {
+    let script = browser_url_script(bundle_id)?;
+    let output = std::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        debug!(
+            bundle_id,
+            status = %output.status,
+            "browser did not report its page url"
+        );
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn browser_page_url(_bundle_id: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::browser_url_script;
+
+    #[test]
+    fn browser_scripts_match_each_scripting_dialect() {
+        assert_eq!(
+            browser_url_script("com.apple.Safari").as_deref(),
+            Some("tell application id \"com.apple.Safari\" to get URL of front document")
+        );
+        assert!(browser_url_script("COM.GOOGLE.CHROME")
+            .is_some_and(|script| script.ends_with("URL of active tab of front window")));
+        assert_eq!(browser_url_script("org.mozilla.firefox"), None);
+    }
+}
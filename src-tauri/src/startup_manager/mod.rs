@@ -0,0 +1,24 @@
+pub mod update_checker;
+
+use tauri::AppHandle;
+use tauri_plugin_autostart::ManagerExt as AutostartManagerExt;
+use tracing::info;
+
+pub fn is_launch_at_login_enabled(app: &AppHandle) -> Result<bool, String> {
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|error| format!("Failed to get launch-at-login state: {error}"))
+}
+
+pub fn set_launch_at_login(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+
+    result.map_err(|error| format!("Failed to set launch-at-login state: {error}"))?;
+    info!(enabled, "launch-at-login state applied");
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::{sync::Mutex, time::Duration};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::UpdaterExt;
+use tracing::{debug, info, warn};
+
+pub const EVENT_UPDATE_AVAILABLE: &str = "voice://update-available";
+
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(30);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAvailableEvent {
+    pub version: String,
+    pub current_version: String,
+}
+
+#[derive(Debug, Default)]
+pub struct UpdateChecker {
+    available: Mutex<Option<UpdateAvailableEvent>>,
+}
+
+impl UpdateChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn available_update(&self) -> Option<UpdateAvailableEvent> {
+        self.available
+            .lock()
+            .map(|available| available.clone())
+            .unwrap_or_else(|error| error.into_inner().clone())
+    }
+
+    fn record(&self, update: UpdateAvailableEvent) -> bool {
+        let mut available = self
+            .available
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if available.as_ref() == Some(&update) {
+            return false;
+        }
+        *available = Some(update);
+        true
+    }
+}
+
+pub fn spawn_background_checks(
+    app: AppHandle,
+    on_update_found: impl Fn(&AppHandle, &UpdateAvailableEvent) + Send + 'static,
+) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+        loop {
+            match check_for_update(&app).await {
+                Ok(Some(update)) => {
+                    if app.state::<UpdateChecker>().record(update.clone()) {
+                        info!(version = %update.version, "update available");
+                        on_update_found(&app, &update);
+                        if let Err(error) = app.emit(EVENT_UPDATE_AVAILABLE, &update) {
+                            warn!(%error, "failed to emit update available event");
+                        }
+                    }
+                }
+                Ok(None) => debug!("no update available"),
+                Err(error) => warn!(%error, "background update check failed"),
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn check_for_update(app: &AppHandle) -> Result<Option<UpdateAvailableEvent>, String> {
+    let updater = app
+        .updater()
+        .map_err(|error| format!("Failed to initialize updater: {error}"))?;
+    let update = updater
+        .check()
+        .await
+        .map_err(|error| format!("Failed to check for updates: {error}"))?;
+
+    Ok(update.map(|update| UpdateAvailableEvent {
+        version: update.version,
+        current_version: update.current_version,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UpdateAvailableEvent, UpdateChecker};
+
+    #[test]
+    fn records_each_new_version_once() {
+        let checker = UpdateChecker::new();
+        let update = UpdateAvailableEvent {
+            version: "0.3.0".to_string(),
+            current_version: "0.2.0".to_string(),
+        };
+
+        assert!(checker.record(update.clone()));
+        assert!(!checker.record(update.clone()));
+        assert_eq!(checker.available_update(), Some(update));
+        assert!(checker.record(UpdateAvailableEvent {
+            version: "0.3.1".to_string(),
+            current_version: "0.2.0".to_string(),
+        }));
+    }
+}
@@ -1,19 +1,35 @@
 use std::{
+    cmp::Reverse,
     collections::BTreeMap,
-    fs,
-    io::Write,
-    path::{Path, PathBuf},
-    sync::Mutex,
-    time::{SystemTime, UNIX_EPOCH},
+    fmt,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Instant,
 };
 
-use chrono::{Duration, Local, NaiveDate};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc, Weekday,
+};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
 use tracing::{debug, info, warn};
 
+use crate::{
+    history_store::{count_words, HistoryEntry, TranscriptionDetails},
+    persistence::{JsonDocumentStore, Migration},
+    write_behind::{WriteBehind, WriteBehindConfig},
+};
+
+pub const EVENT_STATS_UPDATED: &str = "voice://stats-updated";
+pub const EVENT_RECORD_BROKEN: &str = "voice://record-broken";
+
 const STATS_FILE_NAME: &str = "stats.json";
 const DEFAULT_HISTORY_WINDOW_DAYS: usize = 30;
+const STATS_MIGRATIONS: &[Migration<UsageStats>] = &[migrate_legacy_daily_stats];
+const MIN_RECORD_WPM_SECONDS: f64 = 10.0;
+const RECORDED_ENTRY_IDS_LIMIT: usize = 1_000;
+const HOUR_KEY_FORMAT: &str = "%Y-%m-%dT%H:00Z";
+const HOUR_KEY_PARSE_FORMAT: &str = "%Y-%m-%dT%H:%MZ";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +42,14 @@ pub struct DailyStats {
     pub recording_seconds: f64,
 }
 
+impl DailyStats {
+    fn add(&mut self, other: &DailyStats) {
+        self.transcriptions = self.transcriptions.saturating_add(other.transcriptions);
+        self.words = self.words.saturating_add(other.words);
+        self.recording_seconds = sanitize_seconds(self.recording_seconds + other.recording_seconds);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct UsageStats {
@@ -35,24 +59,91 @@ pub struct UsageStats {
     pub total_words: u64,
     #[serde(default)]
     pub total_recording_seconds: f64,
+    // Keyed by UTC hour so changing timezone moves activity between days instead of splitting one.
     #[serde(default)]
+    pub hourly_stats: BTreeMap<String, DailyStats>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub daily_stats: BTreeMap<String, DailyStats>,
+    #[serde(default)]
+    pub provider_stats: Vec<ProviderStats>,
+    #[serde(default)]
+    pub records: PersonalRecords,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recorded_entry_ids: Vec<String>,
     #[serde(default = "today_date_key")]
     pub last_updated: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderStats {
+    pub provider: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub transcriptions: u64,
+    #[serde(default)]
+    pub latency_samples: u64,
+    #[serde(default)]
+    pub total_latency_ms: u64,
+    #[serde(default)]
+    pub confidence_samples: u64,
+    #[serde(default)]
+    pub total_confidence: f64,
+}
+
 impl Default for UsageStats {
     fn default() -> Self {
         Self {
             total_transcriptions: 0,
             total_words: 0,
             total_recording_seconds: 0.0,
+            hourly_stats: BTreeMap::new(),
             daily_stats: BTreeMap::new(),
+            provider_stats: Vec::new(),
+            records: PersonalRecords::default(),
+            recorded_entry_ids: Vec::new(),
             last_updated: today_date_key(),
         }
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PersonalRecords {
+    pub longest_streak_days: u64,
+    pub most_words_in_day: u64,
+    pub longest_dictation_seconds: f64,
+    pub fastest_words_per_minute: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    LongestStreak,
+    MostWordsInDay,
+    LongestDictation,
+    FastestWordsPerMinute,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordBroken {
+    pub record: RecordKind,
+    pub previous: f64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsReconciliation {
+    pub drift_detected: bool,
+    pub previous_transcriptions: u64,
+    pub previous_words: u64,
+    pub reconciled_transcriptions: u64,
+    pub reconciled_words: u64,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct DailyWordCount {
@@ -60,6 +151,16 @@ pub struct DailyWordCount {
     pub words: u64,
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderPerformance {
+    pub provider: String,
+    pub model: Option<String>,
+    pub transcriptions: u64,
+    pub average_latency_ms: Option<f64>,
+    pub average_confidence: Option<f64>,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct UsageStatsReport {
@@ -71,13 +172,37 @@ pub struct UsageStatsReport {
     pub streak_days: u64,
     pub today: DailyStats,
     pub daily_word_history: Vec<DailyWordCount>,
+    pub usage_heatmap: [[u64; 24]; 7],
+    pub provider_performance: Vec<ProviderPerformance>,
+    pub records: PersonalRecords,
     pub last_updated: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreakRules {
+    pub min_words: u64,
+    pub workdays_only: bool,
+    pub freezes: u32,
+}
+
+pub type StatsUpdateListener = Arc<dyn Fn(&UsageStatsReport) + Send + Sync + 'static>;
+pub type RecordBrokenListener = Arc<dyn Fn(&RecordBroken) + Send + Sync + 'static>;
+
 pub struct StatsStore {
-    file_path: PathBuf,
-    io_lock: Mutex<()>,
+    document: JsonDocumentStore<UsageStats>,
+    update_listener: Mutex<Option<StatsUpdateListener>>,
+    record_listener: Mutex<Option<RecordBrokenListener>>,
+    streak_rules: Mutex<StreakRules>,
+    write_behind: Option<WriteBehind<UsageStats>>,
+}
+
+impl fmt::Debug for StatsStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatsStore")
+            .field("file_path", &self.document.file_path())
+            .field("write_behind", &self.write_behind.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl StatsStore {
@@ -89,198 +214,470 @@ impl StatsStore {
 
         let file_path = app_data_dir.join(STATS_FILE_NAME);
         debug!(path = %file_path.display(), "initializing usage stats store");
-        Self::new_with_file_path(file_path)
+        let store =
+            Self::new_with_file_path(file_path)?.with_write_behind(WriteBehindConfig::default());
+        let update_app = app.clone();
+        store.set_update_listener(Arc::new(move |report| {
+            if let Err(error) = update_app.emit(EVENT_STATS_UPDATED, report) {
+                warn!(%error, "failed to emit stats updated event");
+            }
+        }));
+        let record_app = app.clone();
+        store.set_record_listener(Arc::new(move |broken| {
+            if let Err(error) = record_app.emit(EVENT_RECORD_BROKEN, broken) {
+                warn!(%error, "failed to emit record broken event");
+            }
+        }));
+        Ok(store)
     }
 
     pub fn new_with_file_path(file_path: PathBuf) -> Result<Self, String> {
-        ensure_stats_file(&file_path)?;
+        let document = JsonDocumentStore::new(file_path, "usage stats")
+            .with_migrations(STATS_MIGRATIONS)
+            .with_normalize(normalize_usage_stats);
+        document.ensure_exists()?;
         Ok(Self {
-            file_path,
-            io_lock: Mutex::new(()),
+            document,
+            update_listener: Mutex::new(None),
+            record_listener: Mutex::new(None),
+            streak_rules: Mutex::new(StreakRules::default()),
+            write_behind: None,
         })
     }
 
+    pub fn with_write_behind(mut self, config: WriteBehindConfig) -> Self {
+        self.write_behind = Some(WriteBehind::new(config));
+        self
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        self.flush_pending(true)
+    }
+
+    pub fn flush_if_due(&self) -> Result<(), String> {
+        self.flush_pending(false)
+    }
+
+    pub fn flush_on_crash(&self) {
+        let Some(_guard) = self.document.try_lock() else {
+            warn!("stats store is busy; skipping crash flush");
+            return;
+        };
+        if let Err(error) = self.flush_pending_locked(true) {
+            warn!(%error, "failed to flush usage stats on crash");
+        }
+    }
+
+    fn flush_pending(&self, force: bool) -> Result<(), String> {
+        let _guard = self.document.lock()?;
+        self.flush_pending_locked(force)
+    }
+
+    fn flush_pending_locked(&self, force: bool) -> Result<(), String> {
+        let Some(cache) = &self.write_behind else {
+            return Ok(());
+        };
+        let flushed = cache.flush_with(Instant::now(), force, |stats| {
+            self.write_usage_stats_to_disk(stats)
+        })?;
+        if flushed {
+            debug!(path = %self.document.file_path().display(), "flushed usage stats");
+        }
+        Ok(())
+    }
+
+    pub fn set_update_listener(&self, listener: StatsUpdateListener) {
+        match self.update_listener.lock() {
+            Ok(mut current) => *current = Some(listener),
+            Err(_) => warn!("stats update listener lock is poisoned"),
+        }
+    }
+
+    pub fn set_record_listener(&self, listener: RecordBrokenListener) {
+        match self.record_listener.lock() {
+            Ok(mut current) => *current = Some(listener),
+            Err(_) => warn!("record listener lock is poisoned"),
+        }
+    }
+
+    pub fn set_streak_rules(&self, rules: StreakRules) -> Result<(), String> {
+        let _guard = self.document.lock()?;
+        {
+            let mut current = self
+                .streak_rules
+                .lock()
+                .map_err(|_| "Streak rules lock is poisoned".to_string())?;
+            if *current == rules {
+                return Ok(());
+            }
+            *current = rules;
+        }
+        debug!(?rules, "streak rules updated");
+        let stats = self.read_usage_stats()?;
+        self.publish_update(&stats);
+        Ok(())
+    }
+
+    fn streak_rules(&self) -> StreakRules {
+        self.streak_rules
+            .lock()
+            .map(|rules| *rules)
+            .unwrap_or_default()
+    }
+
     pub fn record_transcription(
         &self,
+        entry_id: Option<&str>,
         word_count: u64,
         recording_duration_secs: f64,
-    ) -> Result<(), String> {
+    ) -> Result<UsageStatsReport, String> {
         let sanitized_duration = sanitize_seconds(recording_duration_secs);
-        let today = today_date_key();
+        let hour = hour_key(Utc::now());
         debug!(
+            entry_id,
             word_count,
             recording_duration_secs = sanitized_duration,
-            date = %today,
+            hour = %hour,
             "recording usage stats for transcription"
         );
 
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "Stats store lock is poisoned".to_string())?;
+        let _guard = self.document.lock()?;
         let mut stats = self.read_usage_stats()?;
+        if let Some(entry_id) = entry_id {
+            if stats.recorded_entry_ids.iter().any(|id| id == entry_id) {
+                info!(entry_id, "usage stats already include history entry");
+                return Ok(build_usage_report(
+                    &stats,
+                    &Local::now(),
+                    DEFAULT_HISTORY_WINDOW_DAYS,
+                    &self.streak_rules(),
+                ));
+            }
+            remember_entry_id(&mut stats.recorded_entry_ids, entry_id);
+        }
 
         stats.total_transcriptions = stats.total_transcriptions.saturating_add(1);
         stats.total_words = stats.total_words.saturating_add(word_count);
         stats.total_recording_seconds =
             sanitize_seconds(stats.total_recording_seconds + sanitized_duration);
 
-        let day_stats = stats.daily_stats.entry(today.clone()).or_default();
-        day_stats.transcriptions = day_stats.transcriptions.saturating_add(1);
-        day_stats.words = day_stats.words.saturating_add(word_count);
-        day_stats.recording_seconds =
-            sanitize_seconds(day_stats.recording_seconds + sanitized_duration);
-
-        stats.last_updated = today;
-        self.write_usage_stats(&stats)
-    }
+        stats
+            .hourly_stats
+            .entry(hour)
+            .or_default()
+            .add(&DailyStats {
+                transcriptions: 1,
+                words: word_count,
+                recording_seconds: sanitized_duration,
+            });
 
-    pub fn get_usage_stats(&self) -> Result<UsageStatsReport, String> {
-        let _guard = self
-            .io_lock
-            .lock()
-            .map_err(|_| "Stats store lock is poisoned".to_string())?;
-        let stats = self.read_usage_stats()?;
-        Ok(build_usage_report(
+        stats.last_updated = today_date_key();
+        let report = build_usage_report(
             &stats,
-            today_local_date(),
+            &Local::now(),
             DEFAULT_HISTORY_WINDOW_DAYS,
-        ))
+            &self.streak_rules(),
+        );
+        let broken = update_records(&mut stats.records, &report, word_count, sanitized_duration);
+        self.write_usage_stats(&stats)?;
+        let report = self.publish_update(&stats);
+        self.publish_records(&broken);
+        Ok(report)
     }
 
-    pub fn reset_usage_stats(&self) -> Result<(), String> {
-        info!("resetting usage stats");
-        let _guard = self
-            .io_lock
+    fn publish_records(&self, broken: &[RecordBroken]) {
+        if broken.is_empty() {
+            return;
+        }
+        let listener = self
+            .record_listener
             .lock()
-            .map_err(|_| "Stats store lock is poisoned".to_string())?;
-        self.write_usage_stats(&UsageStats::default())
+            .ok()
+            .and_then(|listener| listener.clone());
+        for record in broken {
+            info!(record = ?record.record, value = record.value, "personal record broken");
+            if let Some(listener) = &listener {
+                listener(record);
+            }
+        }
     }
 
-    fn read_usage_stats(&self) -> Result<UsageStats, String> {
-        if !self.file_path.exists() {
-            return Ok(UsageStats::default());
-        }
+    pub fn record_provider_performance(
+        &self,
+        provider: &str,
+        details: &TranscriptionDetails,
+    ) -> Result<UsageStatsReport, String> {
+        debug!(
+            provider,
+            model = ?details.model,
+            latency_ms = ?details.latency_ms,
+            confidence = ?details.confidence,
+            "recording provider performance"
+        );
+
+        let _guard = self.document.lock()?;
+        let mut stats = self.read_usage_stats()?;
 
-        let raw_contents = fs::read_to_string(&self.file_path)
-            .map_err(|error| format!("Failed to read usage stats file: {error}"))?;
-        if raw_contents.trim().is_empty() {
-            return Ok(UsageStats::default());
+        let index = stats
+            .provider_stats
+            .iter()
+            .position(|entry| entry.provider == provider && entry.model == details.model)
+            .unwrap_or_else(|| {
+                stats.provider_stats.push(ProviderStats {
+                    provider: provider.to_string(),
+                    model: details.model.clone(),
+                    ..ProviderStats::default()
+                });
+                stats.provider_stats.len() - 1
+            });
+        let entry = &mut stats.provider_stats[index];
+        entry.transcriptions = entry.transcriptions.saturating_add(1);
+        if let Some(latency_ms) = details.latency_ms {
+            entry.latency_samples = entry.latency_samples.saturating_add(1);
+            entry.total_latency_ms = entry.total_latency_ms.saturating_add(latency_ms);
+        }
+        if let Some(confidence) = details
+            .confidence
+            .filter(|confidence| confidence.is_finite())
+        {
+            entry.confidence_samples = entry.confidence_samples.saturating_add(1);
+            entry.total_confidence += f64::from(confidence.clamp(0.0, 1.0));
         }
 
-        let mut stats = match serde_json::from_str::<UsageStats>(&raw_contents) {
-            Ok(parsed) => parsed,
-            Err(error) => {
-                self.recover_malformed_stats_file(format!(
-                    "Failed to parse usage stats file: {error}"
-                ))?;
-                return Ok(UsageStats::default());
-            }
-        };
+        self.write_usage_stats(&stats)?;
+        Ok(self.publish_update(&stats))
+    }
 
-        normalize_usage_stats(&mut stats);
-        Ok(stats)
+    pub fn get_usage_stats(&self) -> Result<UsageStatsReport, String> {
+        let _guard = self.document.lock()?;
+        let stats = self.read_usage_stats()?;
+        Ok(build_usage_report(
+            &stats,
+            &Local::now(),
+            DEFAULT_HISTORY_WINDOW_DAYS,
+            &self.streak_rules(),
+        ))
     }
 
-    fn write_usage_stats(&self, stats: &UsageStats) -> Result<(), String> {
-        let serialized = serde_json::to_vec_pretty(stats)
-            .map_err(|error| format!("Failed to serialize usage stats: {error}"))?;
-        let temp_path = temp_file_path_for(&self.file_path);
-
-        let mut temp_file = fs::OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .open(&temp_path)
-            .map_err(|error| {
-                format!(
-                    "Failed to create usage stats temp file `{}`: {error}",
-                    temp_path.display()
-                )
-            })?;
-
-        if let Err(error) = temp_file.write_all(&serialized) {
-            let _ = fs::remove_file(&temp_path);
-            return Err(format!(
-                "Failed to write usage stats temp file `{}`: {error}",
-                temp_path.display()
-            ));
+    pub fn reconcile_with_history(
+        &self,
+        entries: &[HistoryEntry],
+    ) -> Result<StatsReconciliation, String> {
+        let _guard = self.document.lock()?;
+        let mut stats = self.read_usage_stats()?;
+        let rebuilt = stats_from_history(entries);
+        let reconciliation = StatsReconciliation {
+            drift_detected: rebuilt.total_transcriptions != stats.total_transcriptions
+                || rebuilt.total_words != stats.total_words,
+            previous_transcriptions: stats.total_transcriptions,
+            previous_words: stats.total_words,
+            reconciled_transcriptions: rebuilt.total_transcriptions,
+            reconciled_words: rebuilt.total_words,
+        };
+        if !reconciliation.drift_detected {
+            debug!("usage stats match history");
+            return Ok(reconciliation);
         }
 
-        if let Err(error) = temp_file.sync_all() {
-            let _ = fs::remove_file(&temp_path);
-            return Err(format!(
-                "Failed to flush usage stats temp file `{}`: {error}",
-                temp_path.display()
-            ));
-        }
+        warn!(
+            previous_transcriptions = reconciliation.previous_transcriptions,
+            reconciled_transcriptions = reconciliation.reconciled_transcriptions,
+            previous_words = reconciliation.previous_words,
+            reconciled_words = reconciliation.reconciled_words,
+            "usage stats drifted from history; rebuilding"
+        );
+        stats.total_transcriptions = rebuilt.total_transcriptions;
+        stats.total_words = rebuilt.total_words;
+        stats.total_recording_seconds = rebuilt.total_recording_seconds;
+        stats.hourly_stats = rebuilt.hourly_stats;
+        stats.recorded_entry_ids = rebuilt.recorded_entry_ids;
+        self.write_usage_stats(&stats)?;
+        self.publish_update(&stats);
+        Ok(reconciliation)
+    }
 
-        drop(temp_file);
+    pub fn reset_usage_stats(&self) -> Result<(), String> {
+        info!("resetting usage stats");
+        let _guard = self.document.lock()?;
+        let stats = UsageStats::default();
+        self.write_usage_stats(&stats)?;
+        self.publish_update(&stats);
+        Ok(())
+    }
 
-        fs::rename(&temp_path, &self.file_path).map_err(|error| {
-            let _ = fs::remove_file(&temp_path);
-            format!("Failed to finalize usage stats file: {error}")
-        })?;
+    pub fn snapshot(&self) -> Result<UsageStats, String> {
+        let _guard = self.document.lock()?;
+        self.read_usage_stats()
+    }
 
+    pub fn restore(&self, stats: &UsageStats) -> Result<(), String> {
+        info!(
+            total_transcriptions = stats.total_transcriptions,
+            "restoring usage stats"
+        );
+        let _guard = self.document.lock()?;
+        // Backups made before hourly buckets still carry local-date keys.
+        let mut stats = stats.clone();
+        migrate_legacy_daily_stats(&mut stats);
+        let stats = normalize_usage_stats(stats)?;
+        self.write_usage_stats(&stats)?;
+        self.publish_update(&stats);
         Ok(())
     }
 
-    fn recover_malformed_stats_file(&self, reason: String) -> Result<(), String> {
-        let backup_path = backup_corrupt_stats_file(&self.file_path)?;
-        self.write_usage_stats(&UsageStats::default())?;
-        warn!(
-            path = %self.file_path.display(),
-            backup = %backup_path.display(),
-            reason = %reason,
-            "recovered malformed usage stats file"
+    fn publish_update(&self, stats: &UsageStats) -> UsageStatsReport {
+        let report = build_usage_report(
+            stats,
+            &Local::now(),
+            DEFAULT_HISTORY_WINDOW_DAYS,
+            &self.streak_rules(),
         );
-        Ok(())
+        let listener = self
+            .update_listener
+            .lock()
+            .ok()
+            .and_then(|listener| listener.clone());
+        if let Some(listener) = listener {
+            listener(&report);
+        }
+        report
     }
-}
 
-fn ensure_stats_file(file_path: &Path) -> Result<(), String> {
-    if let Some(parent_dir) = file_path.parent() {
-        fs::create_dir_all(parent_dir)
-            .map_err(|error| format!("Failed to create usage stats directory: {error}"))?;
+    fn read_usage_stats(&self) -> Result<UsageStats, String> {
+        let Some(cache) = &self.write_behind else {
+            return self.document.read();
+        };
+        if let Some(stats) = cache.cached() {
+            return Ok(stats);
+        }
+        let stats = self.document.read()?;
+        cache.prime(stats.clone());
+        Ok(stats)
     }
 
-    if !file_path.exists() {
-        let initial_stats = serde_json::to_vec_pretty(&UsageStats::default())
-            .map_err(|error| format!("Failed to serialize initial usage stats: {error}"))?;
-        fs::write(file_path, initial_stats)
-            .map_err(|error| format!("Failed to initialize usage stats file: {error}"))?;
-        info!(path = %file_path.display(), "created usage stats file");
+    fn write_usage_stats(&self, stats: &UsageStats) -> Result<(), String> {
+        match &self.write_behind {
+            Some(cache) => cache.store(stats.clone(), Instant::now()),
+            None => self.write_usage_stats_to_disk(stats),
+        }
     }
 
-    Ok(())
+    fn write_usage_stats_to_disk(&self, stats: &UsageStats) -> Result<(), String> {
+        self.document.write(stats)
+    }
 }
 
-fn normalize_usage_stats(stats: &mut UsageStats) {
+fn normalize_usage_stats(mut stats: UsageStats) -> Result<UsageStats, String> {
     stats.total_recording_seconds = sanitize_seconds(stats.total_recording_seconds);
     if parse_date_key(&stats.last_updated).is_none() {
         stats.last_updated = today_date_key();
     }
 
-    stats.daily_stats.retain(|date, day_stats| {
-        if parse_date_key(date).is_none() {
+    stats.hourly_stats.retain(|hour, hour_stats| {
+        if parse_hour_key(hour).is_none() {
             return false;
         }
 
-        day_stats.recording_seconds = sanitize_seconds(day_stats.recording_seconds);
+        hour_stats.recording_seconds = sanitize_seconds(hour_stats.recording_seconds);
         true
     });
+    for entry in &mut stats.provider_stats {
+        entry.total_confidence = sanitize_seconds(entry.total_confidence);
+    }
+    Ok(stats)
+}
+
+fn migrate_legacy_daily_stats(stats: &mut UsageStats) {
+    migrate_daily_stats(stats, &Local);
 }
 
-fn build_usage_report(
+fn migrate_daily_stats<Tz: TimeZone>(stats: &mut UsageStats, timezone: &Tz) {
+    if stats.daily_stats.is_empty() {
+        return;
+    }
+    let legacy_days = std::mem::take(&mut stats.daily_stats);
+    info!(
+        days = legacy_days.len(),
+        "migrating daily usage stats to hourly buckets"
+    );
+    for (date, day_stats) in legacy_days {
+        let noon = parse_date_key(&date)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .and_then(|noon| timezone.from_local_datetime(&noon).earliest());
+        let Some(noon) = noon else {
+            warn!(date, "dropping usage stats for unreadable date");
+            continue;
+        };
+        stats
+            .hourly_stats
+            .entry(hour_key(noon.with_timezone(&Utc)))
+            .or_default()
+            .add(&day_stats);
+    }
+}
+
+fn local_daily_stats<Tz: TimeZone>(
+    hourly_stats: &BTreeMap<String, DailyStats>,
+    timezone: &Tz,
+) -> BTreeMap<String, DailyStats> {
+    let mut daily_stats = BTreeMap::<String, DailyStats>::new();
+    for (hour, hour_stats) in hourly_stats {
+        let Some(hour) = parse_hour_key(hour) else {
+            continue;
+        };
+        let date = date_key(hour.with_timezone(timezone).date_naive());
+        daily_stats.entry(date).or_default().add(hour_stats);
+    }
+    daily_stats
+}
+
+fn remember_entry_id(recorded_entry_ids: &mut Vec<String>, entry_id: &str) {
+    recorded_entry_ids.push(entry_id.to_string());
+    let overflow = recorded_entry_ids
+        .len()
+        .saturating_sub(RECORDED_ENTRY_IDS_LIMIT);
+    recorded_entry_ids.drain(..overflow);
+}
+
+fn stats_from_history(entries: &[HistoryEntry]) -> UsageStats {
+    let mut entries = entries.iter().collect::<Vec<_>>();
+    entries.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+
+    let mut stats = UsageStats::default();
+    for entry in entries {
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+            warn!(entry_id = %entry.id, "skipping history entry with unreadable timestamp");
+            continue;
+        };
+        let day_stats = DailyStats {
+            transcriptions: 1,
+            words: count_words(&entry.text),
+            recording_seconds: sanitize_seconds(entry.duration_secs.unwrap_or(0.0)),
+        };
+        stats.total_transcriptions = stats.total_transcriptions.saturating_add(1);
+        stats.total_words = stats.total_words.saturating_add(day_stats.words);
+        stats.total_recording_seconds =
+            sanitize_seconds(stats.total_recording_seconds + day_stats.recording_seconds);
+        stats
+            .hourly_stats
+            .entry(hour_key(timestamp.with_timezone(&Utc)))
+            .or_default()
+            .add(&day_stats);
+        remember_entry_id(&mut stats.recorded_entry_ids, &entry.id);
+    }
+    stats
+}
+
+fn build_usage_report<Tz: TimeZone>(
     stats: &UsageStats,
-    today: NaiveDate,
+    now: &DateTime<Tz>,
     history_days: usize,
+    streak_rules: &StreakRules,
 ) -> UsageStatsReport {
+    let today = now.date_naive();
+    let daily_stats = local_daily_stats(&stats.hourly_stats, &now.timezone());
     let today_key = date_key(today);
-    let today_stats = stats
-        .daily_stats
-        .get(&today_key)
-        .cloned()
-        .unwrap_or_default();
+    let today_stats = daily_stats.get(&today_key).cloned().unwrap_or_default();
     let words_per_minute = if stats.total_recording_seconds > 0.0 {
         stats.total_words as f64 / (stats.total_recording_seconds / 60.0)
     } else {
@@ -298,29 +695,128 @@ fn build_usage_report(
         total_recording_seconds: stats.total_recording_seconds,
         words_per_minute,
         average_transcription_length,
-        streak_days: calculate_streak_days(&stats.daily_stats, today),
+        streak_days: calculate_streak_days(&daily_stats, today, streak_rules),
         today: today_stats,
-        daily_word_history: build_daily_word_history(&stats.daily_stats, today, history_days),
+        daily_word_history: build_daily_word_history(&daily_stats, today, history_days),
+        usage_heatmap: build_usage_heatmap(&stats.hourly_stats, &now.timezone()),
+        provider_performance: build_provider_performance(&stats.provider_stats),
+        records: stats.records.clone(),
         last_updated: stats.last_updated.clone(),
     }
 }
 
-fn calculate_streak_days(daily_stats: &BTreeMap<String, DailyStats>, today: NaiveDate) -> u64 {
+fn build_usage_heatmap<Tz: TimeZone>(
+    hourly_stats: &BTreeMap<String, DailyStats>,
+    timezone: &Tz,
+) -> [[u64; 24]; 7] {
+    let mut heatmap = [[0_u64; 24]; 7];
+    for (hour, hour_stats) in hourly_stats {
+        let Some(hour) = parse_hour_key(hour) else {
+            continue;
+        };
+        let local = hour.with_timezone(timezone);
+        let cell =
+            &mut heatmap[local.weekday().num_days_from_monday() as usize][local.hour() as usize];
+        *cell = cell.saturating_add(hour_stats.words);
+    }
+    heatmap
+}
+
+fn update_records(
+    records: &mut PersonalRecords,
+    report: &UsageStatsReport,
+    word_count: u64,
+    recording_duration_secs: f64,
+) -> Vec<RecordBroken> {
+    let mut broken = Vec::new();
+    let mut raise = |record: RecordKind, current: &mut f64, value: f64| {
+        if value <= *current {
+            return;
+        }
+        if *current > 0.0 {
+            broken.push(RecordBroken {
+                record,
+                previous: *current,
+                value,
+            });
+        }
+        *current = value;
+    };
+
+    let mut longest_streak = records.longest_streak_days as f64;
+    raise(
+        RecordKind::LongestStreak,
+        &mut longest_streak,
+        report.streak_days as f64,
+    );
+    records.longest_streak_days = longest_streak as u64;
+
+    let mut most_words = records.most_words_in_day as f64;
+    raise(
+        RecordKind::MostWordsInDay,
+        &mut most_words,
+        report.today.words as f64,
+    );
+    records.most_words_in_day = most_words as u64;
+
+    raise(
+        RecordKind::LongestDictation,
+        &mut records.longest_dictation_seconds,
+        recording_duration_secs,
+    );
+    if recording_duration_secs >= MIN_RECORD_WPM_SECONDS {
+        raise(
+            RecordKind::FastestWordsPerMinute,
+            &mut records.fastest_words_per_minute,
+            word_count as f64 / (recording_duration_secs / 60.0),
+        );
+    }
+    broken
+}
+
+fn build_provider_performance(provider_stats: &[ProviderStats]) -> Vec<ProviderPerformance> {
+    let average = |total: f64, samples: u64| (samples > 0).then(|| total / samples as f64);
+    let mut performance: Vec<ProviderPerformance> = provider_stats
+        .iter()
+        .map(|entry| ProviderPerformance {
+            provider: entry.provider.clone(),
+            model: entry.model.clone(),
+            transcriptions: entry.transcriptions,
+            average_latency_ms: average(entry.total_latency_ms as f64, entry.latency_samples),
+            average_confidence: average(entry.total_confidence, entry.confidence_samples),
+        })
+        .collect();
+    performance.sort_by_key(|entry| Reverse(entry.transcriptions));
+    performance
+}
+
+fn calculate_streak_days(
+    daily_stats: &BTreeMap<String, DailyStats>,
+    today: NaiveDate,
+    rules: &StreakRules,
+) -> u64 {
     let mut streak = 0_u64;
+    let mut freezes_left = rules.freezes;
     let mut cursor = today;
 
     loop {
         let cursor_key = date_key(cursor);
-        let has_activity = daily_stats
+        let counts = daily_stats
             .get(&cursor_key)
-            .map(|stats| stats.transcriptions > 0)
+            .map(|stats| stats.transcriptions > 0 && stats.words >= rules.min_words)
             .unwrap_or(false);
-
-        if !has_activity {
+        let is_weekend = matches!(cursor.weekday(), Weekday::Sat | Weekday::Sun);
+
+        if counts {
+            streak = streak.saturating_add(1);
+        } else if rules.workdays_only && is_weekend {
+            // Weekends neither count nor break a workday streak.
+        } else if freezes_left > 0 {
+            freezes_left -= 1;
+        } else {
             break;
         }
 
-        streak = streak.saturating_add(1);
         let Some(previous_date) = cursor.checked_sub_signed(Duration::days(1)) else {
             break;
         };
@@ -382,50 +878,22 @@ fn parse_date_key(value: &str) -> Option<NaiveDate> {
     NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
 }
 
-fn temp_file_path_for(file_path: &Path) -> PathBuf {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or(STATS_FILE_NAME);
-
-    file_path.with_file_name(format!(
-        ".{file_name}.{}.{timestamp}.tmp",
-        std::process::id()
-    ))
-}
-
-fn backup_corrupt_stats_file(file_path: &Path) -> Result<PathBuf, String> {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let file_name = file_path
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or(STATS_FILE_NAME);
-    let backup_path = file_path.with_file_name(format!(
-        "{file_name}.corrupt-{}-{timestamp}.bak",
-        std::process::id()
-    ));
-
-    fs::rename(file_path, &backup_path).map_err(|error| {
-        format!(
-            "Failed to backup malformed usage stats file `{}` to `{}`: {error}",
-            file_path.display(),
-            backup_path.display()
-        )
-    })?;
+fn hour_key(at: DateTime<Utc>) -> String {
+    at.format(HOUR_KEY_FORMAT).to_string()
+}
 
-    Ok(backup_path)
+fn parse_hour_key(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim(), HOUR_KEY_PARSE_FORMAT)
+        .ok()
+        .map(|hour| hour.and_utc())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{fs, path::Path};
+
     use super::*;
+    use chrono::FixedOffset;
     use uuid::Uuid;
 
     fn create_test_store() -> (StatsStore, PathBuf, PathBuf) {
@@ -476,7 +944,7 @@ mod tests {
         let (store, _file_path, test_dir) = create_test_store();
 
         store
-            .record_transcription(12, 45.5)
+            .record_transcription(None, 12, 45.5)
             .expect("stats recording should succeed");
         let report = store
             .get_usage_stats()
@@ -498,10 +966,10 @@ mod tests {
         let (store, _file_path, test_dir) = create_test_store();
 
         store
-            .record_transcription(120, 60.0)
+            .record_transcription(None, 120, 60.0)
             .expect("first record should succeed");
         store
-            .record_transcription(60, 30.0)
+            .record_transcription(None, 60, 30.0)
             .expect("second record should succeed");
 
         let report = store
@@ -516,12 +984,76 @@ mod tests {
         cleanup_test_dir(&test_dir);
     }
 
+    #[test]
+    fn replayed_history_entries_are_counted_once() {
+        let (store, _file_path, test_dir) = create_test_store();
+
+        store
+            .record_transcription(Some("entry-1"), 10, 5.0)
+            .expect("first record should succeed");
+        let report = store
+            .record_transcription(Some("entry-1"), 10, 5.0)
+            .expect("replayed record should succeed");
+        assert_eq!(report.total_transcriptions, 1);
+        assert_eq!(report.total_words, 10);
+
+        let mut ids = (0..RECORDED_ENTRY_IDS_LIMIT + 5)
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>();
+        remember_entry_id(&mut ids, "latest");
+        assert_eq!(ids.len(), RECORDED_ENTRY_IDS_LIMIT);
+        assert_eq!(ids.last().map(String::as_str), Some("latest"));
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn reconcile_rebuilds_drifted_totals_from_history() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let entry = |id: &str, text: &str, timestamp: &str| {
+            let mut entry = HistoryEntry::new(text.to_string(), Some(4.0), None, "openai".into());
+            entry.id = id.to_string();
+            entry.timestamp = timestamp.to_string();
+            entry
+        };
+        let history = vec![
+            entry("a", "one two three", "2026-03-01T09:15:00.000Z"),
+            entry("b", "four five", "2026-03-01T09:45:00.000Z"),
+        ];
+        for _ in 0..3 {
+            store
+                .record_transcription(None, 3, 4.0)
+                .expect("drifting record should succeed");
+        }
+
+        let reconciliation = store
+            .reconcile_with_history(&history)
+            .expect("reconciliation should succeed");
+        assert!(reconciliation.drift_detected);
+        assert_eq!(reconciliation.previous_transcriptions, 3);
+        assert_eq!(reconciliation.reconciled_words, 5);
+
+        let stats = store.snapshot().expect("stats should load");
+        assert_eq!(stats.total_transcriptions, 2);
+        assert_almost_eq(stats.total_recording_seconds, 8.0);
+        assert_eq!(stats.hourly_stats["2026-03-01T09:00Z"].words, 5);
+        assert_eq!(stats.recorded_entry_ids, ["a", "b"]);
+        assert!(
+            !store
+                .reconcile_with_history(&history)
+                .expect("second reconciliation should succeed")
+                .drift_detected
+        );
+
+        cleanup_test_dir(&test_dir);
+    }
+
     #[test]
     fn reset_usage_stats_clears_all_counters() {
         let (store, _file_path, test_dir) = create_test_store();
 
         store
-            .record_transcription(25, 15.0)
+            .record_transcription(None, 25, 15.0)
             .expect("stats recording should succeed");
         store
             .reset_usage_stats()
@@ -545,6 +1077,115 @@ mod tests {
         cleanup_test_dir(&test_dir);
     }
 
+    #[test]
+    fn writes_publish_the_fresh_report() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&published);
+        store.set_update_listener(Arc::new(move |report: &UsageStatsReport| {
+            recorded.lock().expect("reports lock").push(report.clone());
+        }));
+
+        let returned = store
+            .record_transcription(None, 30, 20.0)
+            .expect("stats recording should succeed");
+        store
+            .reset_usage_stats()
+            .expect("stats reset should succeed");
+
+        let published = published.lock().expect("reports lock");
+        assert_eq!(published.len(), 2);
+        assert_eq!(published[0], returned);
+        assert_eq!(published[0].total_words, 30);
+        assert_eq!(published[1].total_words, 0);
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn records_rise_and_announce_only_when_beaten() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let broken = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&broken);
+        store.set_record_listener(Arc::new(move |record: &RecordBroken| {
+            recorded.lock().expect("records lock").push(record.clone());
+        }));
+
+        store
+            .record_transcription(None, 100, 60.0)
+            .expect("first record should succeed");
+        assert!(broken.lock().expect("records lock").is_empty());
+
+        let report = store
+            .record_transcription(None, 20, 5.0)
+            .expect("second record should succeed");
+        assert_eq!(report.records.most_words_in_day, 120);
+        assert_eq!(report.records.longest_streak_days, 1);
+        assert_almost_eq(report.records.longest_dictation_seconds, 60.0);
+        // Five seconds is too short to count as a speed record.
+        assert_almost_eq(report.records.fastest_words_per_minute, 100.0);
+
+        let report = store
+            .record_transcription(None, 90, 30.0)
+            .expect("third record should succeed");
+        assert_almost_eq(report.records.fastest_words_per_minute, 180.0);
+        let broken = broken.lock().expect("records lock");
+        let kinds = broken
+            .iter()
+            .map(|record| record.record)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                RecordKind::MostWordsInDay,
+                RecordKind::MostWordsInDay,
+                RecordKind::FastestWordsPerMinute
+            ]
+        );
+        assert_almost_eq(broken[2].previous, 100.0);
+
+        cleanup_test_dir(&test_dir);
+    }
+
+    #[test]
+    fn provider_performance_averages_latency_and_confidence_per_model() {
+        let (store, _file_path, test_dir) = create_test_store();
+        let details =
+            |model: &str, latency_ms: u64, confidence: Option<f32>| TranscriptionDetails {
+                model: Some(model.to_string()),
+                latency_ms: Some(latency_ms),
+                confidence,
+            };
+
+        store
+            .record_provider_performance("openai", &details("whisper-1", 900, Some(0.8)))
+            .expect("first record should succeed");
+        store
+            .record_provider_performance("openai", &details("whisper-1", 1_100, None))
+            .expect("second record should succeed");
+        let report = store
+            .record_provider_performance(
+                "openai-realtime",
+                &details("gpt-4o-transcribe", 300, None),
+            )
+            .expect("third record should succeed");
+
+        assert_eq!(report.provider_performance.len(), 2);
+        let whisper = &report.provider_performance[0];
+        assert_eq!(whisper.provider, "openai");
+        assert_eq!(whisper.transcriptions, 2);
+        assert_almost_eq(whisper.average_latency_ms.expect("latency"), 1_000.0);
+        assert_almost_eq(
+            whisper.average_confidence.expect("confidence"),
+            f64::from(0.8_f32),
+        );
+        let realtime = &report.provider_performance[1];
+        assert_eq!(realtime.model.as_deref(), Some("gpt-4o-transcribe"));
+        assert_eq!(realtime.average_confidence, None);
+
+        cleanup_test_dir(&test_dir);
+    }
+
     #[test]
     fn streak_counts_consecutive_days_with_activity() {
         let (store, file_path, test_dir) = create_test_store();
@@ -586,7 +1227,11 @@ mod tests {
             total_transcriptions: 4,
             total_words: 68,
             total_recording_seconds: 33.0,
+            hourly_stats: BTreeMap::new(),
             daily_stats,
+            provider_stats: Vec::new(),
+            records: PersonalRecords::default(),
+            recorded_entry_ids: Vec::new(),
             last_updated: today_date_key(),
         };
 
@@ -605,6 +1250,111 @@ mod tests {
         cleanup_test_dir(&test_dir);
     }
 
+    #[test]
+    fn streak_rules_apply_minimum_words_weekends_and_freezes() {
+        let day = |words| DailyStats {
+            transcriptions: 1,
+            words,
+            recording_seconds: 10.0,
+        };
+        // Wednesday back to the previous Thursday, with nothing on the weekend.
+        let daily_stats = BTreeMap::from([
+            ("2026-03-04".to_string(), day(30)),
+            ("2026-03-03".to_string(), day(5)),
+            ("2026-03-02".to_string(), day(20)),
+            ("2026-02-27".to_string(), day(12)),
+            ("2026-02-26".to_string(), day(15)),
+        ]);
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).expect("valid date");
+        let workdays = StreakRules {
+            min_words: 10,
+            workdays_only: true,
+            freezes: 1,
+        };
+
+        assert_eq!(
+            calculate_streak_days(&daily_stats, today, &StreakRules::default()),
+            3
+        );
+        assert_eq!(calculate_streak_days(&daily_stats, today, &workdays), 4);
+        assert_eq!(
+            calculate_streak_days(
+                &daily_stats,
+                today,
+                &StreakRules {
+                    freezes: 0,
+                    ..workdays
+                }
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn local_days_are_derived_from_utc_hours_in_the_reporting_timezone() {
+        let hour = |words| DailyStats {
+            transcriptions: 1,
+            words,
+            recording_seconds: 10.0,
+        };
+        let mut stats = UsageStats::default();
+        stats
+            .hourly_stats
+            .insert("2026-03-01T20:00Z".to_string(), hour(10));
+        stats
+            .hourly_stats
+            .insert("2026-03-01T23:00Z".to_string(), hour(5));
+
+        let new_york = FixedOffset::west_opt(5 * 3600).expect("offset");
+        let berlin = FixedOffset::east_opt(3600).expect("offset");
+        assert_eq!(
+            local_daily_stats(&stats.hourly_stats, &new_york)["2026-03-01"].words,
+            15
+        );
+        let in_berlin = local_daily_stats(&stats.hourly_stats, &berlin);
+        assert_eq!(in_berlin["2026-03-01"].words, 10);
+        assert_eq!(in_berlin["2026-03-02"].words, 5);
+
+        let now = berlin
+            .with_ymd_and_hms(2026, 3, 2, 9, 0, 0)
+            .single()
+            .expect("valid time");
+        let report = build_usage_report(&stats, &now, 2, &StreakRules::default());
+        assert_eq!(report.today.words, 5);
+        assert_eq!(report.streak_days, 2);
+        // 2026-03-01 is a Sunday, so the late hour lands on Monday just after midnight.
+        assert_eq!(report.usage_heatmap[6][21], 10);
+        assert_eq!(report.usage_heatmap[0][0], 5);
+        assert_eq!(report.usage_heatmap.iter().flatten().sum::<u64>(), 15);
+    }
+
+    #[test]
+    fn legacy_daily_keys_migrate_to_local_noon() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).expect("offset");
+        let mut stats = UsageStats::default();
+        stats.daily_stats.insert(
+            "2026-03-01".to_string(),
+            DailyStats {
+                transcriptions: 3,
+                words: 42,
+                recording_seconds: 30.0,
+            },
+        );
+        stats
+            .daily_stats
+            .insert("not-a-date".to_string(), DailyStats::default());
+
+        migrate_daily_stats(&mut stats, &tokyo);
+
+        assert!(stats.daily_stats.is_empty());
+        assert_eq!(stats.hourly_stats.len(), 1);
+        assert_eq!(stats.hourly_stats["2026-03-01T03:00Z"].words, 42);
+        assert_eq!(
+            local_daily_stats(&stats.hourly_stats, &tokyo)["2026-03-01"].transcriptions,
+            3
+        );
+    }
+
     #[test]
     fn recovers_from_malformed_stats_file() {
         let (store, file_path, test_dir) = create_test_store();
@@ -625,10 +1375,10 @@ mod tests {
         let (store, _file_path, test_dir) = create_test_store();
 
         store
-            .record_transcription(5, f64::NAN)
+            .record_transcription(None, 5, f64::NAN)
             .expect("stats record should ignore NaN duration");
         store
-            .record_transcription(5, -10.0)
+            .record_transcription(None, 5, -10.0)
             .expect("stats record should clamp negative duration");
 
         let report = store.get_usage_stats().expect("stats should load");
@@ -1,6 +1,13 @@
+use std::{collections::VecDeque, time::Duration};
+
+use chrono::{SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+pub const DEFAULT_ERROR_RESET_DELAY: Duration = Duration::from_millis(1_500);
+pub const DEFAULT_TRANSCRIBING_TIMEOUT: Duration = Duration::from_secs(120);
+const STATUS_HISTORY_CAPACITY: usize = 50;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum AppStatus {
@@ -8,6 +15,8 @@ pub enum AppStatus {
     Listening,
     Transcribing,
     Error,
+    #[serde(rename = "nothing_heard")]
+    NothingHeard,
 }
 
 impl Default for AppStatus {
@@ -16,9 +25,56 @@ impl Default for AppStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "name", rename_all = "camelCase")]
+pub enum StatusSource {
+    Startup,
+    Hotkey,
+    Command(&'static str),
+    Pipeline(&'static str),
+    AudioStream,
+    Timeout,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusTransition {
+    pub at: String,
+    pub from: AppStatus,
+    pub to: AppStatus,
+    pub source: StatusSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusTimeouts {
+    pub error_reset: Duration,
+    pub transcribing: Duration,
+    pub listening: Duration,
+}
+
+impl Default for StatusTimeouts {
+    fn default() -> Self {
+        Self {
+            error_reset: DEFAULT_ERROR_RESET_DELAY,
+            transcribing: DEFAULT_TRANSCRIBING_TIMEOUT,
+            listening: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledTransition {
+    generation: u64,
+    pub after: Duration,
+    pub to: AppStatus,
+}
+
 #[derive(Debug, Default)]
 pub struct StatusNotifier {
     current: AppStatus,
+    generation: u64,
+    timeouts: StatusTimeouts,
+    history: VecDeque<StatusTransition>,
 }
 
 impl StatusNotifier {
@@ -26,8 +82,144 @@ impl StatusNotifier {
         self.current
     }
 
-    pub fn set(&mut self, status: AppStatus) {
-        debug!(from = ?self.current, to = ?status, "status notifier updated");
+    pub fn timeouts(&self) -> StatusTimeouts {
+        self.timeouts
+    }
+
+    pub fn set_timeouts(&mut self, timeouts: StatusTimeouts) {
+        self.timeouts = timeouts;
+    }
+
+    pub fn history(&self) -> Vec<StatusTransition> {
+        self.history.iter().cloned().collect()
+    }
+
+    pub fn set(&mut self, status: AppStatus, source: StatusSource) -> Option<ScheduledTransition> {
+        debug!(from = ?self.current, to = ?status, ?source, "status notifier updated");
+        if self.history.len() == STATUS_HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back(StatusTransition {
+            at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            from: self.current,
+            to: status,
+            source,
+        });
         self.current = status;
+        self.generation += 1;
+
+        let (after, to) = match status {
+            AppStatus::Error | AppStatus::NothingHeard => {
+                (self.timeouts.error_reset, AppStatus::Idle)
+            }
+            AppStatus::Transcribing if !self.timeouts.transcribing.is_zero() => {
+                (self.timeouts.transcribing, AppStatus::Error)
+            }
+            AppStatus::Listening if !self.timeouts.listening.is_zero() => {
+                (self.timeouts.listening, AppStatus::Error)
+            }
+            _ => return None,
+        };
+        Some(ScheduledTransition {
+            generation: self.generation,
+            after,
+            to,
+        })
+    }
+
+    pub fn is_pending(&self, transition: &ScheduledTransition) -> bool {
+        self.generation == transition.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{AppStatus, StatusNotifier, StatusSource, StatusTimeouts, STATUS_HISTORY_CAPACITY};
+
+    #[test]
+    fn schedules_idle_after_errors_and_a_watchdog_while_transcribing() {
+        let mut notifier = StatusNotifier::default();
+        notifier.set_timeouts(StatusTimeouts {
+            error_reset: Duration::from_secs(3),
+            transcribing: Duration::from_secs(60),
+            ..StatusTimeouts::default()
+        });
+
+        assert_eq!(
+            notifier.set(AppStatus::Listening, StatusSource::Hotkey),
+            None
+        );
+
+        let watchdog = notifier
+            .set(AppStatus::Transcribing, StatusSource::Hotkey)
+            .expect("transcribing should arm the watchdog");
+        assert_eq!(watchdog.to, AppStatus::Error);
+        assert_eq!(watchdog.after, Duration::from_secs(60));
+        assert!(notifier.is_pending(&watchdog));
+
+        let reset = notifier
+            .set(AppStatus::Error, StatusSource::Pipeline("transcription"))
+            .expect("errors should reset to idle");
+        assert_eq!(reset.to, AppStatus::Idle);
+        assert_eq!(reset.after, Duration::from_secs(3));
+        assert!(!notifier.is_pending(&watchdog));
+        assert!(notifier.is_pending(&reset));
+    }
+
+    #[test]
+    fn zero_transcribing_timeout_disables_the_watchdog() {
+        let mut notifier = StatusNotifier::default();
+        notifier.set_timeouts(StatusTimeouts {
+            transcribing: Duration::ZERO,
+            ..StatusTimeouts::default()
+        });
+
+        assert_eq!(
+            notifier.set(
+                AppStatus::Transcribing,
+                StatusSource::Command("transcribe_audio")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn listening_timeout_arms_a_watchdog_until_recording_stops() {
+        let mut notifier = StatusNotifier::default();
+        notifier.set_timeouts(StatusTimeouts {
+            listening: Duration::from_secs(600),
+            ..StatusTimeouts::default()
+        });
+
+        let watchdog = notifier
+            .set(AppStatus::Listening, StatusSource::Hotkey)
+            .expect("listening should arm the watchdog");
+        assert_eq!(watchdog.to, AppStatus::Error);
+        assert_eq!(watchdog.after, Duration::from_secs(600));
+
+        notifier.set(AppStatus::Transcribing, StatusSource::Hotkey);
+        assert!(!notifier.is_pending(&watchdog));
+    }
+
+    #[test]
+    fn history_keeps_the_most_recent_transitions_with_their_source() {
+        let mut notifier = StatusNotifier::default();
+        notifier.set(AppStatus::Listening, StatusSource::Hotkey);
+        for _ in 0..STATUS_HISTORY_CAPACITY {
+            notifier.set(AppStatus::Idle, StatusSource::Command("set_status"));
+        }
+        notifier.set(AppStatus::Error, StatusSource::AudioStream);
+
+        let history = notifier.history();
+        assert_eq!(history.len(), STATUS_HISTORY_CAPACITY);
+        assert!(history
+            .iter()
+            .all(|transition| transition.source != StatusSource::Hotkey));
+        let last = history.last().expect("history should not be empty");
+        assert_eq!(last.from, AppStatus::Idle);
+        assert_eq!(last.to, AppStatus::Error);
+        assert_eq!(last.source, StatusSource::AudioStream);
     }
 }
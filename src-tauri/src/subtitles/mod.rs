@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use crate::transcription::TranscriptionSegment;
+
+pub const DEFAULT_MAX_LINE_CHARS: usize = 42;
+pub const DEFAULT_MAX_CAPTION_DURATION: Duration = Duration::from_secs(6);
+const MAX_LINES_PER_CAPTION: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubtitleOptions {
+    pub max_line_chars: usize,
+    pub max_caption_duration: Duration,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_chars: DEFAULT_MAX_LINE_CHARS,
+            max_caption_duration: DEFAULT_MAX_CAPTION_DURATION,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub lines: Vec<String>,
+}
+
+pub fn build_cues(segments: &[TranscriptionSegment], options: SubtitleOptions) -> Vec<SubtitleCue> {
+    let max_line_chars = options.max_line_chars.max(1);
+    let max_caption_secs = options.max_caption_duration.as_secs_f64();
+    let mut cues = Vec::new();
+
+    for segment in segments {
+        let words = segment.text.split_whitespace().collect::<Vec<_>>();
+        if words.is_empty() {
+            continue;
+        }
+
+        let segment_secs = (segment.end_secs - segment.start_secs).max(0.0);
+        let total_chars = words.join(" ").chars().count() as f64;
+        let secs_for = |chars: usize| chars as f64 / total_chars * segment_secs;
+        let mut consumed_chars = 0;
+        let mut lines = Vec::<String>::new();
+        let mut caption_chars = 0;
+
+        for word in words {
+            let word_chars = word.chars().count();
+            let mut next_chars = caption_chars + 1 + word_chars;
+            let mut fits_line = lines
+                .last()
+                .is_some_and(|line| line.chars().count() + 1 + word_chars <= max_line_chars);
+            let fits_caption = fits_line || lines.len() < MAX_LINES_PER_CAPTION;
+            let too_long = max_caption_secs > 0.0 && secs_for(next_chars) > max_caption_secs;
+            if lines.is_empty() {
+                next_chars = word_chars;
+            } else if !fits_caption || too_long {
+                let start_secs = segment.start_secs + secs_for(consumed_chars);
+                cues.push(SubtitleCue {
+                    start_secs,
+                    end_secs: start_secs + secs_for(caption_chars),
+                    lines: std::mem::take(&mut lines),
+                });
+                consumed_chars += caption_chars + 1;
+                next_chars = word_chars;
+                fits_line = false;
+            }
+
+            match lines.last_mut() {
+                Some(line) if fits_line => {
+                    line.push(' ');
+                    line.push_str(word);
+                }
+                _ => lines.push(word.to_string()),
+            }
+            caption_chars = next_chars;
+        }
+
+        let start_secs = segment.start_secs + secs_for(consumed_chars);
+        cues.push(SubtitleCue {
+            start_secs,
+            end_secs: segment.end_secs.max(start_secs),
+            lines,
+        });
+    }
+
+    cues
+}
+
+pub fn render_srt(cues: &[SubtitleCue]) -> String {
+    cues.iter()
+        .enumerate()
+        .map(|(index, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                index + 1,
+                format_timestamp(cue.start_secs, ','),
+                format_timestamp(cue.end_secs, ','),
+                cue.lines.join("\n")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render_vtt(cues: &[SubtitleCue]) -> String {
+    let body = cues
+        .iter()
+        .map(|cue| {
+            format!(
+                "{} --> {}\n{}\n",
+                format_timestamp(cue.start_secs, '.'),
+                format_timestamp(cue.end_secs, '.'),
+                cue.lines.join("\n")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("WEBVTT\n\n{body}")
+}
+
+fn format_timestamp(secs: f64, millis_separator: char) -> String {
+    let total_millis = (secs.max(0.0) * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = (total_millis / 60_000) % 60;
+    let seconds = (total_millis / 1000) % 60;
+    let millis = total_millis % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}{millis_separator}{millis:03}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{build_cues, format_timestamp, render_srt, render_vtt, SubtitleOptions};
+    use crate::transcription::TranscriptionSegment;
+
+    fn segment(start_secs: f64, end_secs: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            start_secs,
+            end_secs,
+            text: text.to_string(),
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn timestamps_roll_over_into_hours() {
+        assert_eq!(format_timestamp(3_723.004, ','), "01:02:03,004");
+        assert_eq!(format_timestamp(-1.0, '.'), "00:00:00.000");
+    }
+
+    #[test]
+    fn long_segments_wrap_lines_and_split_captions() {
+        let cues = build_cues(
+            &[segment(10.0, 14.0, "one two three four five six seven")],
+            SubtitleOptions {
+                max_line_chars: 9,
+                max_caption_duration: Duration::from_secs(60),
+            },
+        );
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].lines, vec!["one two", "three"]);
+        assert_eq!(cues[1].lines, vec!["four five", "six seven"]);
+        assert_eq!(cues[0].start_secs, 10.0);
+        assert_eq!(cues[1].end_secs, 14.0);
+        assert!(cues[0].end_secs <= cues[1].start_secs);
+    }
+
+    #[test]
+    fn caption_duration_limit_splits_slow_segments() {
+        let cues = build_cues(
+            &[segment(0.0, 9.0, "aaaa bbbb cccc")],
+            SubtitleOptions {
+                max_line_chars: 42,
+                max_caption_duration: Duration::from_secs(4),
+            },
+        );
+
+        assert_eq!(
+            cues.iter()
+                .map(|cue| cue.lines.join(" "))
+                .collect::<Vec<_>>(),
+            vec!["aaaa", "bbbb", "cccc"]
+        );
+        assert!(cues.iter().all(|cue| cue.end_secs - cue.start_secs <= 4.0));
+    }
+
+    #[test]
+    fn renders_numbered_srt_and_vtt_cues() {
+        let cues = build_cues(
+            &[segment(0.0, 1.5, "hello"), segment(2.0, 3.25, "world")],
+            SubtitleOptions::default(),
+        );
+
+        assert_eq!(
+            render_srt(&cues),
+            "1\n00:00:00,000 --> 00:00:01,500\nhello\n\n2\n00:00:02,000 --> 00:00:03,250\nworld\n"
+        );
+        assert_eq!(
+            render_vtt(&cues),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nhello\n\n00:00:02.000 --> 00:00:03.250\nworld\n"
+        );
+    }
+}
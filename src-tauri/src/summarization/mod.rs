@@ -0,0 +1,176 @@
+use chrono::{SecondsFormat, Utc};
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info};
+
+use crate::{
+    history_store::{HistoryEntry, HistoryStore, TranscriptSummary},
+    llm::{resolve_openai_api_key, ChatCompletionClient, ChatCompletionConfig},
+    AppState,
+};
+
+const SUMMARY_MODEL_ENV: &str = "OPENAI_SUMMARY_MODEL";
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize voice transcripts. Respond with a JSON object \
+with two string arrays: \"bullets\" holding a concise bullet summary of the key points, and \
+\"actionItems\" holding concrete follow-up tasks (empty when there are none). Write in the \
+transcript's language and do not invent details.";
+
+#[derive(Debug, Clone)]
+pub struct Summarizer {
+    client: ChatCompletionClient,
+}
+
+impl Summarizer {
+    pub fn new(config: ChatCompletionConfig) -> Self {
+        Self {
+            client: ChatCompletionClient::new(config),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(ChatCompletionConfig::from_env(SUMMARY_MODEL_ENV))
+    }
+
+    pub async fn summarize(
+        &self,
+        api_key: &str,
+        transcript: &str,
+    ) -> Result<TranscriptSummary, String> {
+        let transcript = transcript.trim();
+        if transcript.is_empty() {
+            return Err("Transcript is empty".to_string());
+        }
+
+        debug!(
+            model = %self.client.model(),
+            transcript_chars = transcript.chars().count(),
+            "requesting transcript summary"
+        );
+        let content = self
+            .client
+            .complete(api_key, SUMMARY_SYSTEM_PROMPT, transcript, true)
+            .await?;
+
+        parse_summary(&content, self.client.model())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SummaryPayload {
+    #[serde(default)]
+    bullets: Vec<String>,
+    #[serde(default)]
+    action_items: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn summarize_entry(app: AppHandle, id: String) -> Result<HistoryEntry, String> {
+    info!(id = %id, "history summary requested");
+    let entry = app
+        .state::<HistoryStore>()
+        .get_entry(&id)?
+        .ok_or_else(|| format!("History entry `{id}` was not found"))?;
+
+    let state = app.state::<AppState>();
+    let api_key = resolve_openai_api_key(&state.services.api_key_store)?
+        .ok_or_else(|| "Summaries require an OpenAI API key".to_string())?;
+    let summarizer = state.services.summarizer.clone();
+
+    let summary = summarizer.summarize(&api_key, &entry.text).await?;
+    info!(
+        id = %id,
+        bullets = summary.bullets.len(),
+        action_items = summary.action_items.len(),
+        "history summary generated"
+    );
+
+    app.state::<HistoryStore>()
+        .set_summary(&id, summary)?
+        .ok_or_else(|| format!("History entry `{id}` was deleted while summarizing"))
+}
+
+fn parse_summary(content: &str, model: &str) -> Result<TranscriptSummary, String> {
+    let payload = serde_json::from_str::<SummaryPayload>(content)
+        .map_err(|error| format!("Failed to parse summary: {error}"))?;
+    let bullets = normalize_items(payload.bullets);
+    if bullets.is_empty() {
+        return Err("Summary did not include any bullet points".to_string());
+    }
+
+    Ok(TranscriptSummary {
+        bullets,
+        action_items: normalize_items(payload.action_items),
+        model: model.to_string(),
+        created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+    })
+}
+
+fn normalize_items(items: Vec<String>) -> Vec<String> {
+    items
+        .into_iter()
+        .map(|item| {
+            item.trim()
+                .trim_start_matches(['-', '*', '•'])
+                .trim()
+                .to_string()
+        })
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server};
+
+    use super::{parse_summary, Summarizer};
+    use crate::llm::ChatCompletionConfig;
+
+    #[test]
+    fn parse_summary_strips_list_markers_and_blank_items() {
+        let summary = parse_summary(
+            r#"{"bullets": ["- Shipped the beta", "  ", "* Fixed login"], "actionItems": ["• Email Sam"]}"#,
+            "gpt-4o-mini",
+        )
+        .expect("summary should parse");
+
+        assert_eq!(summary.bullets, vec!["Shipped the beta", "Fixed login"]);
+        assert_eq!(summary.action_items, vec!["Email Sam"]);
+        assert_eq!(summary.model, "gpt-4o-mini");
+        assert!(parse_summary(r#"{"bullets": []}"#, "gpt-4o-mini").is_err());
+        assert!(parse_summary("not json", "gpt-4o-mini").is_err());
+    }
+
+    #[tokio::test]
+    async fn summarize_posts_transcript_and_reads_json_content() {
+        let mut server = Server::new_async().await;
+        let request_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer test-key")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "model": "gpt-4o-mini",
+                "response_format": { "type": "json_object" },
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"choices": [{"message": {"content": "{\"bullets\": [\"Planned launch\"], \"actionItems\": []}"}}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let summarizer = Summarizer::new(ChatCompletionConfig {
+            endpoint: format!("{}/v1/chat/completions", server.url()),
+            ..ChatCompletionConfig::default()
+        });
+        let summary = summarizer
+            .summarize("test-key", "we planned the launch")
+            .await
+            .expect("summary should succeed");
+
+        request_mock.assert_async().await;
+        assert_eq!(summary.bullets, vec!["Planned launch"]);
+        assert!(summary.action_items.is_empty());
+        assert!(summarizer.summarize("test-key", "   ").await.is_err());
+    }
+}
@@ -0,0 +1,452 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, warn};
+
+use crate::{
+    api_key_store::ApiKeyStore,
+    audio_capture_service::MicrophoneInfo,
+    auth_store::{now_epoch_seconds, AuthMethod, ChatGptStoredCredentials},
+    history_store::HistoryStore,
+    llm::resolve_openai_api_key,
+    logging::LoggingState,
+    offline_queue::OfflineQueue,
+    permission_service::PermissionSnapshot,
+    platform_support::{detect_capabilities, PlatformCapabilities},
+    settings_store::VoiceSettings,
+    transcription::{
+        build_probe_client, chatgpt::ChatGptTranscriptionConfig, openai::OpenAiTranscriptionConfig,
+        NetworkConfig,
+    },
+    voice_pipeline::PipelineError,
+    AppState,
+};
+
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemHealthReport {
+    pub checked_at: String,
+    pub provider: ProviderHealth,
+    pub auth: AuthHealth,
+    pub microphone: MicrophoneHealth,
+    pub permissions: PermissionSnapshot,
+    pub platform: PlatformCapabilities,
+    pub last_error: Option<LastPipelineError>,
+    pub disk_usage: DiskUsage,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealth {
+    pub name: String,
+    pub endpoint: Option<String>,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthHealth {
+    pub method: String,
+    pub valid: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MicrophoneHealth {
+    pub available: bool,
+    pub device_count: usize,
+    pub selected_device_id: Option<String>,
+    pub selected_device_found: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LastPipelineError {
+    pub stage: String,
+    pub code: String,
+    pub message: String,
+    pub occurred_at: String,
+}
+
+impl LastPipelineError {
+    pub fn from_pipeline_error(error: &PipelineError) -> Self {
+        Self {
+            stage: error.stage.as_str().to_string(),
+            code: error.code.as_str().to_string(),
+            message: error.message.clone(),
+            occurred_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    pub history_bytes: u64,
+    pub pending_audio_bytes: u64,
+    pub log_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[tauri::command]
+pub async fn get_system_health(app: AppHandle) -> Result<SystemHealthReport, String> {
+    info!("system health report requested");
+    let state = app.state::<AppState>();
+    let auth_method = state.services.current_auth_method()?;
+    let auth = match auth_method {
+        AuthMethod::ApiKey => {
+            assess_api_key_auth(resolve_openai_api_key(&state.services.api_key_store)?)
+        }
+        AuthMethod::ChatgptOauth => assess_chatgpt_auth(
+            state.services.auth_store.chatgpt_credentials()?.as_ref(),
+            now_epoch_seconds(),
+        ),
+        AuthMethod::None => AuthHealth {
+            method: AuthMethod::None.as_str().to_string(),
+            valid: false,
+            message: Some(
+                "No authentication configured. Add an OpenAI API key or login with ChatGPT."
+                    .to_string(),
+            ),
+        },
+    };
+
+    let settings = state.services.settings_store.current();
+    let provider = probe_provider(auth_method, &settings, &state.services.api_key_store).await;
+    let microphone = assess_microphones(
+        state.services.audio_capture_service.list_microphones(),
+        settings.microphone_id,
+    );
+    let permissions = state.services.permission_service.check_permissions();
+    let platform = detect_capabilities();
+    let last_error = state
+        .last_pipeline_error
+        .lock()
+        .map(|last_error| last_error.clone())
+        .unwrap_or_else(|_| {
+            warn!("last pipeline error lock poisoned while building health report");
+            None
+        });
+    let disk_usage = disk_usage(&app);
+
+    debug!(
+        provider = %provider.name,
+        reachable = provider.reachable,
+        auth_valid = auth.valid,
+        microphone_available = microphone.available,
+        "system health report built"
+    );
+
+    Ok(SystemHealthReport {
+        checked_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        provider,
+        auth,
+        microphone,
+        permissions,
+        platform,
+        last_error,
+        disk_usage,
+    })
+}
+
+#[tauri::command]
+pub async fn test_provider_connection(
+    app: AppHandle,
+    provider: String,
+) -> Result<ProviderHealth, String> {
+    info!(%provider, "provider connection test requested");
+    let endpoint = provider_endpoint(&provider)
+        .ok_or_else(|| format!("Unknown transcription provider `{provider}`"))?;
+    let services = &app.state::<AppState>().services;
+    let settings = services.settings_store.current();
+    let network = crate::network_config_for_provider(&settings, &services.api_key_store, &provider);
+    let health = probe_endpoint(&provider, endpoint, &network).await;
+    info!(
+        %provider,
+        reachable = health.reachable,
+        latency_ms = ?health.latency_ms,
+        "provider connection test finished"
+    );
+    Ok(health)
+}
+
+fn provider_endpoint(provider: &str) -> Option<String> {
+    match provider {
+        "openai" => Some(OpenAiTranscriptionConfig::from_env().endpoint),
+        "chatgpt-oauth" => Some(ChatGptTranscriptionConfig::from_env().endpoint),
+        _ => None,
+    }
+}
+
+async fn probe_provider(
+    auth_method: AuthMethod,
+    settings: &VoiceSettings,
+    api_key_store: &ApiKeyStore,
+) -> ProviderHealth {
+    let name = match auth_method {
+        AuthMethod::ApiKey => "openai",
+        AuthMethod::ChatgptOauth => "chatgpt-oauth",
+        AuthMethod::None => {
+            return ProviderHealth {
+                name: "none".to_string(),
+                endpoint: None,
+                reachable: false,
+                latency_ms: None,
+                error: Some("No transcription provider is configured".to_string()),
+            }
+        }
+    };
+    let endpoint = provider_endpoint(name).expect("known providers have an endpoint");
+    let network = crate::network_config_for_provider(settings, api_key_store, name);
+    probe_endpoint(name, endpoint, &network).await
+}
+
+async fn probe_endpoint(name: &str, endpoint: String, network: &NetworkConfig) -> ProviderHealth {
+    let client = match build_probe_client(REACHABILITY_TIMEOUT, network) {
+        Ok(client) => client,
+        Err(error) => {
+            return ProviderHealth {
+                name: name.to_string(),
+                endpoint: Some(endpoint),
+                reachable: false,
+                latency_ms: None,
+                error: Some(error),
+            }
+        }
+    };
+
+    // Any HTTP response, even an auth or method error, proves the endpoint is reachable.
+    let started = Instant::now();
+    let result = client.head(&endpoint).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(_) => ProviderHealth {
+            name: name.to_string(),
+            endpoint: Some(endpoint),
+            reachable: true,
+            latency_ms: Some(latency_ms),
+            error: None,
+        },
+        Err(error) => ProviderHealth {
+            name: name.to_string(),
+            endpoint: Some(endpoint),
+            reachable: false,
+            latency_ms: None,
+            error: Some(describe_request_error(&error)),
+        },
+    }
+}
+
+fn describe_request_error(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+fn assess_api_key_auth(api_key: Option<String>) -> AuthHealth {
+    let valid = api_key.is_some_and(|key| !key.trim().is_empty());
+    AuthHealth {
+        method: AuthMethod::ApiKey.as_str().to_string(),
+        valid,
+        message: (!valid).then(|| "OpenAI API key is missing".to_string()),
+    }
+}
+
+fn assess_chatgpt_auth(
+    credentials: Option<&ChatGptStoredCredentials>,
+    now_secs: u64,
+) -> AuthHealth {
+    let method = AuthMethod::ChatgptOauth.as_str().to_string();
+    let Some(credentials) = credentials else {
+        return AuthHealth {
+            method,
+            valid: false,
+            message: Some("ChatGPT login is missing".to_string()),
+        };
+    };
+
+    let expired = credentials.expires_at <= now_secs;
+    let can_refresh = !credentials.refresh_token.trim().is_empty();
+    let (valid, message) = match (expired, can_refresh) {
+        (false, _) => (true, None),
+        (true, true) => (
+            true,
+            Some("ChatGPT session expired and will refresh on next use".to_string()),
+        ),
+        (true, false) => (
+            false,
+            Some("ChatGPT session expired; log in again".to_string()),
+        ),
+    };
+
+    AuthHealth {
+        method,
+        valid,
+        message,
+    }
+}
+
+fn assess_microphones(
+    microphones: Result<Vec<MicrophoneInfo>, String>,
+    selected_device_id: Option<String>,
+) -> MicrophoneHealth {
+    match microphones {
+        Ok(microphones) => {
+            let selected_device_found =
+                selected_device_id
+                    .as_deref()
+                    .map_or(!microphones.is_empty(), |selected| {
+                        microphones
+                            .iter()
+                            .any(|microphone| microphone.id == selected)
+                    });
+            MicrophoneHealth {
+                available: selected_device_found,
+                device_count: microphones.len(),
+                selected_device_id,
+                selected_device_found,
+                error: None,
+            }
+        }
+        Err(error) => MicrophoneHealth {
+            available: false,
+            device_count: 0,
+            selected_device_id,
+            selected_device_found: false,
+            error: Some(error),
+        },
+    }
+}
+
+fn disk_usage(app: &AppHandle) -> DiskUsage {
+    let history_bytes = app.state::<HistoryStore>().disk_usage_bytes();
+    let pending_audio_bytes = app.state::<OfflineQueue>().disk_usage_bytes();
+    let log_bytes = app
+        .try_state::<LoggingState>()
+        .map(|logging| file_size_bytes(logging.log_file_path()))
+        .unwrap_or_default();
+
+    DiskUsage {
+        history_bytes,
+        pending_audio_bytes,
+        log_bytes,
+        total_bytes: history_bytes + pending_audio_bytes + log_bytes,
+    }
+}
+
+pub fn file_size_bytes(path: &Path) -> u64 {
+    fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or_default()
+}
+
+pub fn directory_size_bytes(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => directory_size_bytes(&entry.path()),
+            Ok(_) => file_size_bytes(&entry.path()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{
+        assess_api_key_auth, assess_chatgpt_auth, assess_microphones, directory_size_bytes,
+    };
+    use crate::{
+        audio_capture_service::{MicrophoneInfo, MicrophoneTransport},
+        auth_store::ChatGptStoredCredentials,
+    };
+
+    fn credentials(expires_at: u64, refresh_token: &str) -> ChatGptStoredCredentials {
+        ChatGptStoredCredentials {
+            access_token: "access".to_string(),
+            refresh_token: refresh_token.to_string(),
+            expires_at,
+            account_id: "account".to_string(),
+        }
+    }
+
+    fn microphone(id: &str) -> MicrophoneInfo {
+        MicrophoneInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            is_default: false,
+            sample_rate_hz: None,
+            channels: None,
+            transport: MicrophoneTransport::Other,
+        }
+    }
+
+    #[test]
+    fn auth_health_reports_missing_and_expired_credentials() {
+        assert!(assess_api_key_auth(Some("sk-test".to_string())).valid);
+        assert!(!assess_api_key_auth(Some("  ".to_string())).valid);
+
+        assert!(!assess_chatgpt_auth(None, 100).valid);
+        let fresh = assess_chatgpt_auth(Some(&credentials(200, "")), 100);
+        assert!(fresh.valid);
+        assert_eq!(fresh.message, None);
+        let refreshable = assess_chatgpt_auth(Some(&credentials(50, "refresh")), 100);
+        assert!(refreshable.valid);
+        assert!(refreshable.message.is_some());
+        assert!(!assess_chatgpt_auth(Some(&credentials(50, "")), 100).valid);
+    }
+
+    #[test]
+    fn microphone_health_checks_selected_device() {
+        let found = assess_microphones(
+            Ok(vec![microphone("built-in"), microphone("usb")]),
+            Some("usb".to_string()),
+        );
+        assert!(found.available);
+        assert_eq!(found.device_count, 2);
+
+        let missing = assess_microphones(Ok(vec![microphone("built-in")]), Some("usb".to_string()));
+        assert!(!missing.available);
+        assert!(!missing.selected_device_found);
+
+        assert!(!assess_microphones(Ok(Vec::new()), None).available);
+        let failed = assess_microphones(Err("no host".to_string()), None);
+        assert_eq!(failed.error.as_deref(), Some("no host"));
+    }
+
+    #[test]
+    fn directory_size_sums_nested_files() {
+        let dir = std::env::temp_dir().join(format!("voice-health-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("nested")).expect("test dir should be created");
+        std::fs::write(dir.join("a.wav"), [0_u8; 10]).expect("file should be written");
+        std::fs::write(dir.join("nested").join("b.wav"), [0_u8; 5])
+            .expect("file should be written");
+
+        assert_eq!(directory_size_bytes(&dir), 15);
+        assert_eq!(directory_size_bytes(&dir.join("missing")), 0);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::settings_store::{
+    VoiceSettings, FILLER_REMOVAL_AGGRESSIVE, FILLER_REMOVAL_LIGHT, FILLER_REMOVAL_MODERATE,
+};
+
+use super::{push_token, tokenize, Token};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FillerRemoval {
+    Off,
+    Light,
+    Moderate,
+    Aggressive,
+}
+
+impl FillerRemoval {
+    pub fn from_settings(settings: &VoiceSettings) -> Self {
+        match settings.filler_removal.as_str() {
+            FILLER_REMOVAL_LIGHT => Self::Light,
+            FILLER_REMOVAL_MODERATE => Self::Moderate,
+            FILLER_REMOVAL_AGGRESSIVE => Self::Aggressive,
+            _ => Self::Off,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Anywhere,
+    SetOff,
+}
+
+type Filler = (&'static str, FillerRemoval, Placement);
+
+const ENGLISH_FILLERS: &[Filler] = &[
+    ("um", FillerRemoval::Light, Placement::Anywhere),
+    ("umm", FillerRemoval::Light, Placement::Anywhere),
+    ("uh", FillerRemoval::Light, Placement::Anywhere),
+    ("uhh", FillerRemoval::Light, Placement::Anywhere),
+    ("uhm", FillerRemoval::Light, Placement::Anywhere),
+    ("er", FillerRemoval::Light, Placement::Anywhere),
+    ("erm", FillerRemoval::Light, Placement::Anywhere),
+    ("hmm", FillerRemoval::Light, Placement::Anywhere),
+    ("you know", FillerRemoval::Moderate, Placement::SetOff),
+    ("i mean", FillerRemoval::Moderate, Placement::SetOff),
+    ("like", FillerRemoval::Moderate, Placement::SetOff),
+    ("you see", FillerRemoval::Moderate, Placement::SetOff),
+    ("well", FillerRemoval::Aggressive, Placement::SetOff),
+    ("so", FillerRemoval::Aggressive, Placement::SetOff),
+    ("basically", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("literally", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("actually", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("kind of", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("sort of", FillerRemoval::Aggressive, Placement::Anywhere),
+];
+
+const SPANISH_FILLERS: &[Filler] = &[
+    ("eh", FillerRemoval::Light, Placement::Anywhere),
+    ("em", FillerRemoval::Light, Placement::Anywhere),
+    ("este", FillerRemoval::Moderate, Placement::SetOff),
+    ("o sea", FillerRemoval::Moderate, Placement::SetOff),
+    ("pues", FillerRemoval::Aggressive, Placement::SetOff),
+    ("bueno", FillerRemoval::Aggressive, Placement::SetOff),
+    ("en plan", FillerRemoval::Aggressive, Placement::Anywhere),
+];
+
+const FRENCH_FILLERS: &[Filler] = &[
+    ("euh", FillerRemoval::Light, Placement::Anywhere),
+    ("heu", FillerRemoval::Light, Placement::Anywhere),
+    ("tu vois", FillerRemoval::Moderate, Placement::SetOff),
+    ("genre", FillerRemoval::Moderate, Placement::SetOff),
+    ("bon", FillerRemoval::Aggressive, Placement::SetOff),
+    ("du coup", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("en fait", FillerRemoval::Aggressive, Placement::Anywhere),
+];
+
+const GERMAN_FILLERS: &[Filler] = &[
+    ("äh", FillerRemoval::Light, Placement::Anywhere),
+    ("ähm", FillerRemoval::Light, Placement::Anywhere),
+    ("öh", FillerRemoval::Light, Placement::Anywhere),
+    ("weißt du", FillerRemoval::Moderate, Placement::SetOff),
+    ("naja", FillerRemoval::Moderate, Placement::SetOff),
+    ("also", FillerRemoval::Aggressive, Placement::SetOff),
+    ("halt", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("sozusagen", FillerRemoval::Aggressive, Placement::Anywhere),
+    ("quasi", FillerRemoval::Aggressive, Placement::Anywhere),
+];
+
+fn fillers_for(language: Option<&str>) -> &'static [Filler] {
+    let Some(language) = language else {
+        return ENGLISH_FILLERS;
+    };
+    let language = language.trim().to_lowercase();
+    match language.split(['-', '_']).next().unwrap_or_default() {
+        "en" | "english" => ENGLISH_FILLERS,
+        "es" | "spanish" => SPANISH_FILLERS,
+        "fr" | "french" => FRENCH_FILLERS,
+        "de" | "german" => GERMAN_FILLERS,
+        _ => &[],
+    }
+}
+
+#[derive(Debug)]
+struct Word<'a> {
+    space: &'a str,
+    lead: String,
+    core: String,
+    trail: String,
+}
+
+#[derive(Debug)]
+struct Removed<'a> {
+    space: &'a str,
+    lead: String,
+    capitalize: bool,
+}
+
+pub fn remove_fillers(text: &str, level: FillerRemoval, language: Option<&str>) -> String {
+    let mut fillers: Vec<_> = fillers_for(language)
+        .iter()
+        .filter(|(_, filler_level, _)| level != FillerRemoval::Off && *filler_level <= level)
+        .collect();
+    if fillers.is_empty() {
+        return text.to_string();
+    }
+    // Longest phrases first, so "you know" wins over anything it starts with.
+    fillers.sort_by_key(|(phrase, _, _)| std::cmp::Reverse(phrase.split(' ').count()));
+
+    let (tokens, tail) = tokenize(text);
+    let mut kept: Vec<Word<'_>> = Vec::with_capacity(tokens.len());
+    let mut removed: Option<Removed<'_>> = None;
+    let mut index = 0;
+    while index < tokens.len() {
+        if let Some(end) = match_filler(&tokens, index, &fillers) {
+            let first = &tokens[index];
+            let sentence_start = kept.last().is_none_or(|word| ends_sentence(&word.trail));
+            let closing = tokens[end].trail.trim_start_matches([',', ';']);
+            if let Some(previous) = kept.last_mut() {
+                if ends_sentence(closing) {
+                    previous.trail =
+                        format!("{}{closing}", previous.trail.trim_end_matches([',', ';']));
+                } else if tokens[end].trail.starts_with(',') && previous.trail.ends_with(',') {
+                    previous.trail.pop();
+                }
+            }
+            let removed = removed.get_or_insert(Removed {
+                space: first.space,
+                lead: String::new(),
+                capitalize: false,
+            });
+            removed.lead.push_str(first.lead);
+            removed.capitalize |= sentence_start && first.core.starts_with(char::is_uppercase);
+            index = end + 1;
+            continue;
+        }
+
+        let token = &tokens[index];
+        let mut word = Word {
+            space: token.space,
+            lead: token.lead.to_string(),
+            core: token.core.to_string(),
+            trail: token.trail.to_string(),
+        };
+        if let Some(removed) = removed.take() {
+            word.space = removed.space;
+            word.lead = removed.lead + &word.lead;
+            if removed.capitalize {
+                word.core = capitalize_first(&word.core);
+            }
+        }
+        kept.push(word);
+        index += 1;
+    }
+
+    let mut output = String::with_capacity(text.len());
+    for word in &kept {
+        push_token(&mut output, word.space, &word.lead, &word.core, &word.trail);
+    }
+    output.push_str(tail);
+    output
+}
+
+fn match_filler(tokens: &[Token<'_>], index: usize, fillers: &[&Filler]) -> Option<usize> {
+    fillers.iter().find_map(|(phrase, _, placement)| {
+        let words: Vec<&str> = phrase.split(' ').collect();
+        let end = index + words.len() - 1;
+        let candidate = tokens.get(index..=end)?;
+        let matches = candidate
+            .iter()
+            .zip(&words)
+            .enumerate()
+            .all(|(offset, (token, word))| {
+                let inner_punctuation = (offset > 0 && !token.lead.is_empty())
+                    || (offset + 1 < words.len() && !token.trail.is_empty());
+                !inner_punctuation && token.core.to_lowercase() == *word
+            });
+        if !matches {
+            return None;
+        }
+        let set_off = || {
+            let before = index == 0 || tokens[index - 1].trail.ends_with(is_boundary);
+            let after = end + 1 == tokens.len() || tokens[end].trail.starts_with(is_boundary);
+            before && after
+        };
+        (*placement == Placement::Anywhere || set_off()).then_some(end)
+    })
+}
+
+fn is_boundary(character: char) -> bool {
+    matches!(character, ',' | '.' | '!' | '?' | ';' | ':' | '…')
+}
+
+fn ends_sentence(trail: &str) -> bool {
+    trail.contains(['.', '!', '?', '…'])
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut characters = text.chars();
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{remove_fillers, FillerRemoval};
+
+    #[test]
+    fn light_removes_hesitations_and_tidies_punctuation() {
+        assert_eq!(
+            remove_fillers(
+                "Um, so I think, uh, we should ship it, um.",
+                FillerRemoval::Light,
+                Some("en")
+            ),
+            "So I think we should ship it."
+        );
+        assert_eq!(
+            remove_fillers("I like it, you know.", FillerRemoval::Light, None),
+            "I like it, you know."
+        );
+    }
+
+    #[test]
+    fn moderate_removes_discourse_markers_only_when_set_off() {
+        assert_eq!(
+            remove_fillers(
+                "It was, like, huge. I like it, you know.",
+                FillerRemoval::Moderate,
+                Some("english")
+            ),
+            "It was huge. I like it."
+        );
+        assert_eq!(
+            remove_fillers("You know what I mean?", FillerRemoval::Moderate, Some("en")),
+            "You know what I mean?"
+        );
+    }
+
+    #[test]
+    fn aggressive_removes_hedges_and_uses_the_transcript_language() {
+        assert_eq!(
+            remove_fillers(
+                "So, it basically works, kind of.",
+                FillerRemoval::Aggressive,
+                Some("en")
+            ),
+            "It works."
+        );
+        assert_eq!(
+            remove_fillers(
+                "Euh, on part demain, du coup.",
+                FillerRemoval::Aggressive,
+                Some("fr")
+            ),
+            "On part demain."
+        );
+        assert_eq!(
+            remove_fillers("Um, hola.", FillerRemoval::Aggressive, Some("es")),
+            "Um, hola."
+        );
+    }
+}
@@ -0,0 +1,866 @@
+use crate::settings_store::{
+    VoiceSettings, DATE_FORMAT_DAY_MONTH_YEAR, DATE_FORMAT_ISO, DATE_FORMAT_MONTH_DAY_YEAR,
+    TIME_FORMAT_12H, TIME_FORMAT_24H,
+};
+
+mod fillers;
+mod paragraphs;
+mod spoken_punctuation;
+mod trailing;
+
+pub use fillers::{remove_fillers, FillerRemoval};
+pub use paragraphs::{
+    is_long_enough_to_segment, keeps_words, paragraphs_from_segments, ParagraphSegmentation,
+};
+pub use spoken_punctuation::{apply_spoken_punctuation, spoken_punctuation_command};
+pub use trailing::{apply_trailing, TrailingOptions};
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+const UNIT_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("percent", "%"),
+    ("kilometers", "km"),
+    ("kilometer", "km"),
+    ("kilometres", "km"),
+    ("kilometre", "km"),
+    ("centimeters", "cm"),
+    ("centimeter", "cm"),
+    ("centimetres", "cm"),
+    ("centimetre", "cm"),
+    ("millimeters", "mm"),
+    ("millimeter", "mm"),
+    ("millimetres", "mm"),
+    ("millimetre", "mm"),
+    ("meters", "m"),
+    ("meter", "m"),
+    ("metres", "m"),
+    ("metre", "m"),
+    ("miles", "mi"),
+    ("mile", "mi"),
+    ("feet", "ft"),
+    ("foot", "ft"),
+    ("inches", "in"),
+    ("inch", "in"),
+    ("kilograms", "kg"),
+    ("kilogram", "kg"),
+    ("milligrams", "mg"),
+    ("milligram", "mg"),
+    ("grams", "g"),
+    ("gram", "g"),
+    ("pounds", "lb"),
+    ("pound", "lb"),
+    ("ounces", "oz"),
+    ("ounce", "oz"),
+    ("milliliters", "mL"),
+    ("milliliter", "mL"),
+    ("millilitres", "mL"),
+    ("millilitre", "mL"),
+    ("liters", "L"),
+    ("liter", "L"),
+    ("litres", "L"),
+    ("litre", "L"),
+    ("kilobytes", "KB"),
+    ("megabytes", "MB"),
+    ("gigabytes", "GB"),
+    ("terabytes", "TB"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    Off,
+    Iso,
+    MonthDayYear,
+    DayMonthYear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFormat {
+    Off,
+    TwelveHour,
+    TwentyFourHour,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormattingOptions {
+    pub numbers_as_digits: bool,
+    pub date_format: DateFormat,
+    pub time_format: TimeFormat,
+    pub abbreviate_units: bool,
+}
+
+impl FormattingOptions {
+    pub fn from_settings(settings: &VoiceSettings) -> Self {
+        Self {
+            numbers_as_digits: settings.format_numbers_as_digits,
+            date_format: match settings.date_format.as_str() {
+                DATE_FORMAT_ISO => DateFormat::Iso,
+                DATE_FORMAT_MONTH_DAY_YEAR => DateFormat::MonthDayYear,
+                DATE_FORMAT_DAY_MONTH_YEAR => DateFormat::DayMonthYear,
+                _ => DateFormat::Off,
+            },
+            time_format: match settings.time_format.as_str() {
+                TIME_FORMAT_12H => TimeFormat::TwelveHour,
+                TIME_FORMAT_24H => TimeFormat::TwentyFourHour,
+                _ => TimeFormat::Off,
+            },
+            abbreviate_units: settings.abbreviate_units,
+        }
+    }
+
+    pub fn is_noop(&self) -> bool {
+        !self.numbers_as_digits
+            && self.date_format == DateFormat::Off
+            && self.time_format == TimeFormat::Off
+            && !self.abbreviate_units
+    }
+
+    fn format_date(&self, tokens: &[Token<'_>], index: usize) -> Option<(String, usize)> {
+        if self.date_format == DateFormat::Off || !tokens[index].trail.is_empty() {
+            return None;
+        }
+        let month = month_number(tokens[index].core)?;
+        let day = match_number(tokens, index + 1)?;
+        let day_value = day
+            .single_integer()
+            .filter(|value| (1..=31).contains(value))?;
+
+        let year = ["", ","]
+            .contains(&tokens[day.end].trail)
+            .then(|| match_number(tokens, day.end + 1))
+            .flatten()
+            .and_then(|year| year.as_year().map(|value| (value, year.end)));
+
+        let month_name = tokens[index].core;
+        let formatted = match (self.date_format, year) {
+            (DateFormat::Iso, Some((year, _))) => format!("{year:04}-{month:02}-{day_value:02}"),
+            (DateFormat::MonthDayYear, Some((year, _))) => {
+                format!("{month:02}/{day_value:02}/{year:04}")
+            }
+            (DateFormat::DayMonthYear, Some((year, _))) => {
+                format!("{day_value:02}/{month:02}/{year:04}")
+            }
+            (DateFormat::DayMonthYear, None) => format!("{day_value} {month_name}"),
+            _ => format!("{month_name} {day_value}"),
+        };
+        let end = year.map_or(day.end, |(_, end)| end);
+
+        Some((formatted, end))
+    }
+
+    fn format_time(&self, tokens: &[Token<'_>], index: usize) -> Option<(String, usize)> {
+        if self.time_format == TimeFormat::Off {
+            return None;
+        }
+
+        if let Some((hour, minute, meridiem)) = parse_clock_token(tokens[index].core) {
+            let (meridiem, end) = match (meridiem, meridiem_at(tokens, index + 1)) {
+                (Some(meridiem), _) => (Some(meridiem), index),
+                (None, Some(following)) => (following, index + 1),
+                (None, None) if tokens[index].core.contains(':') => (None, index),
+                (None, None) => return None,
+            };
+            return self
+                .render_time(hour, minute, meridiem)
+                .map(|time| (time, end));
+        }
+
+        let number = match_number(tokens, index)?;
+        if number.fraction.is_some() || number.ordinal {
+            return None;
+        }
+        let (hour, minute) = match number.chunks.as_slice() {
+            [hour] => (*hour, 0),
+            [hour, minute] if *minute >= 10 => (*hour, *minute),
+            _ => return None,
+        };
+        let meridiem = meridiem_at(tokens, number.end + 1)?;
+
+        self.render_time(hour, minute, meridiem)
+            .map(|time| (time, number.end + 1))
+    }
+
+    fn render_time(&self, hour: u64, minute: u64, meridiem: Option<Meridiem>) -> Option<String> {
+        if minute >= 60 {
+            return None;
+        }
+        let Some(meridiem) = meridiem else {
+            return (hour < 24).then(|| match self.time_format {
+                TimeFormat::TwentyFourHour => format!("{hour:02}:{minute:02}"),
+                _ => format!("{hour}:{minute:02}"),
+            });
+        };
+        if !(1..=12).contains(&hour) {
+            return None;
+        }
+
+        Some(match (self.time_format, meridiem) {
+            (TimeFormat::TwentyFourHour, Meridiem::Am) => format!("{:02}:{minute:02}", hour % 12),
+            (TimeFormat::TwentyFourHour, Meridiem::Pm) => {
+                format!("{:02}:{minute:02}", hour % 12 + 12)
+            }
+            (_, Meridiem::Am) => format!("{hour}:{minute:02} AM"),
+            (_, Meridiem::Pm) => format!("{hour}:{minute:02} PM"),
+        })
+    }
+}
+
+pub fn format_transcript(
+    text: &str,
+    options: &FormattingOptions,
+    language: Option<&str>,
+) -> String {
+    if options.is_noop() || !is_english(language) {
+        return text.to_string();
+    }
+
+    let (tokens, tail) = tokenize(text);
+    let mut output = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        if let Some((formatted, end)) = options
+            .format_date(&tokens, index)
+            .or_else(|| options.format_time(&tokens, index))
+        {
+            let trail = trail_after_abbreviation(&tokens, end);
+            push_token(&mut output, token.space, token.lead, &formatted, trail);
+            index = end + 1;
+            continue;
+        }
+
+        let Some(number) = match_number(&tokens, index) else {
+            push_token(
+                &mut output,
+                token.space,
+                token.lead,
+                token.core,
+                token.trail,
+            );
+            index += 1;
+            continue;
+        };
+        let unit = options
+            .abbreviate_units
+            .then(|| unit_at(&tokens, number.end + 1))
+            .flatten();
+        let rendered = if !number.from_words {
+            Some(token.core.to_string())
+        } else if options.numbers_as_digits && (!number.is_small_word(index) || unit.is_some()) {
+            number.render()
+        } else {
+            None
+        };
+        let Some(rendered) = rendered else {
+            for token in &tokens[index..=number.end] {
+                push_token(
+                    &mut output,
+                    token.space,
+                    token.lead,
+                    token.core,
+                    token.trail,
+                );
+            }
+            index = number.end + 1;
+            continue;
+        };
+        match unit {
+            Some("%") => {
+                let unit_token = &tokens[number.end + 1];
+                push_token(
+                    &mut output,
+                    token.space,
+                    token.lead,
+                    &format!("{rendered}%"),
+                    unit_token.trail,
+                );
+                index = number.end + 2;
+            }
+            Some(abbreviation) => {
+                let unit_token = &tokens[number.end + 1];
+                push_token(&mut output, token.space, token.lead, &rendered, "");
+                push_token(
+                    &mut output,
+                    unit_token.space,
+                    unit_token.lead,
+                    abbreviation,
+                    unit_token.trail,
+                );
+                index = number.end + 2;
+            }
+            None => {
+                push_token(
+                    &mut output,
+                    token.space,
+                    token.lead,
+                    &rendered,
+                    tokens[number.end].trail,
+                );
+                index = number.end + 1;
+            }
+        }
+    }
+    output.push_str(tail);
+
+    output
+}
+
+fn trail_after_abbreviation<'a>(tokens: &[Token<'a>], end: usize) -> &'a str {
+    let token = &tokens[end];
+    let is_dotted_meridiem = matches!(token.core.to_ascii_lowercase().as_str(), "a.m" | "p.m");
+    let continues_sentence = tokens.get(end + 1).is_some_and(|next| {
+        next.lead.is_empty() && !next.core.chars().next().is_some_and(char::is_uppercase)
+    });
+    match token.trail.strip_prefix('.') {
+        Some(rest) if is_dotted_meridiem && continues_sentence => rest,
+        _ => token.trail,
+    }
+}
+
+fn is_english(language: Option<&str>) -> bool {
+    language.is_none_or(|language| language.trim().to_ascii_lowercase().starts_with("en"))
+}
+
+fn push_token(output: &mut String, space: &str, lead: &str, core: &str, trail: &str) {
+    output.push_str(space);
+    output.push_str(lead);
+    output.push_str(core);
+    output.push_str(trail);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Token<'a> {
+    space: &'a str,
+    lead: &'a str,
+    core: &'a str,
+    trail: &'a str,
+}
+
+fn tokenize(text: &str) -> (Vec<Token<'_>>, &str) {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    loop {
+        let word_start = rest
+            .find(|character: char| !character.is_whitespace())
+            .unwrap_or(rest.len());
+        if word_start == rest.len() {
+            return (tokens, rest);
+        }
+        let (space, after_space) = rest.split_at(word_start);
+        let word_end = after_space
+            .find(char::is_whitespace)
+            .unwrap_or(after_space.len());
+        let (word, remaining) = after_space.split_at(word_end);
+
+        let core_start = word.find(char::is_alphanumeric).unwrap_or(word.len());
+        let core_end = word
+            .rfind(char::is_alphanumeric)
+            .map_or(core_start, |index| {
+                index + word[index..].chars().next().map_or(0, char::len_utf8)
+            });
+        tokens.push(Token {
+            space,
+            lead: &word[..core_start],
+            core: &word[core_start..core_end],
+            trail: &word[core_end..],
+        });
+        rest = remaining;
+    }
+}
+
+fn month_number(word: &str) -> Option<u32> {
+    if !word.chars().next().is_some_and(char::is_uppercase) {
+        return None;
+    }
+    let lower = word.to_ascii_lowercase();
+    MONTHS
+        .iter()
+        .position(|month| *month == lower)
+        .map(|index| index as u32 + 1)
+}
+
+fn unit_at(tokens: &[Token<'_>], index: usize) -> Option<&'static str> {
+    let token = tokens.get(index)?;
+    if !token.lead.is_empty() || !tokens[index - 1].trail.is_empty() {
+        return None;
+    }
+    let lower = token.core.to_ascii_lowercase();
+    UNIT_ABBREVIATIONS
+        .iter()
+        .find(|(unit, _)| *unit == lower)
+        .map(|(_, abbreviation)| *abbreviation)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Meridiem {
+    Am,
+    Pm,
+}
+
+fn meridiem_at(tokens: &[Token<'_>], index: usize) -> Option<Option<Meridiem>> {
+    let token = tokens.get(index)?;
+    if !token.lead.is_empty() || !tokens[index - 1].trail.is_empty() {
+        return None;
+    }
+    match token.core.to_ascii_lowercase().as_str() {
+        "am" | "a.m" => Some(Some(Meridiem::Am)),
+        "pm" | "p.m" => Some(Some(Meridiem::Pm)),
+        "o'clock" | "o\u{2019}clock" => Some(None),
+        _ => None,
+    }
+}
+
+fn parse_clock_token(core: &str) -> Option<(u64, u64, Option<Meridiem>)> {
+    let lower = core.to_ascii_lowercase();
+    let (clock, meridiem) = if let Some(clock) = lower.strip_suffix("am") {
+        (clock, Some(Meridiem::Am))
+    } else if let Some(clock) = lower.strip_suffix("pm") {
+        (clock, Some(Meridiem::Pm))
+    } else {
+        (lower.as_str(), None)
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour, minute),
+        Some(_) => return None,
+        None => (clock, "00"),
+    };
+    if hour.is_empty() || hour.len() > 2 || !hour.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if !minute.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((hour.parse().ok()?, minute.parse().ok()?, meridiem))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NumberMatch {
+    chunks: Vec<u64>,
+    fraction: Option<String>,
+    ordinal: bool,
+    from_words: bool,
+    end: usize,
+}
+
+impl NumberMatch {
+    fn single_integer(&self) -> Option<u64> {
+        match self.chunks.as_slice() {
+            [value] if self.fraction.is_none() => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn as_year(&self) -> Option<u64> {
+        if self.ordinal || self.fraction.is_some() {
+            return None;
+        }
+        match self.chunks.as_slice() {
+            [year] if (1000..=2999).contains(year) => Some(*year),
+            [century, decade] if (10..=99).contains(century) && (10..=99).contains(decade) => {
+                Some(century * 100 + decade)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_small_word(&self, start: usize) -> bool {
+        self.from_words
+            && self.end == start
+            && self.fraction.is_none()
+            && matches!(self.chunks.as_slice(), [value] if *value < 10)
+    }
+
+    fn render(&self) -> Option<String> {
+        let value = match self.chunks.as_slice() {
+            [value] => *value,
+            _ => return self.as_year().map(|year| year.to_string()),
+        };
+
+        let mut rendered = group_thousands(value);
+        if let Some(fraction) = &self.fraction {
+            rendered.push('.');
+            rendered.push_str(fraction);
+        }
+        if self.ordinal {
+            rendered.push_str(ordinal_suffix(value));
+        }
+
+        Some(rendered)
+    }
+}
+
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    if value < 10_000 {
+        return digits;
+    }
+
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+fn match_number(tokens: &[Token<'_>], index: usize) -> Option<NumberMatch> {
+    let token = tokens.get(index)?;
+    match_digit_token(token.core, index).or_else(|| match_number_words(tokens, index))
+}
+
+fn match_digit_token(core: &str, index: usize) -> Option<NumberMatch> {
+    let lower = core.to_ascii_lowercase();
+    let (digits, ordinal) = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| lower.strip_suffix(suffix).map(|digits| (digits, true)))
+        .unwrap_or((lower.as_str(), false));
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction.to_string())),
+        None => (digits, None),
+    };
+    let integer = integer.replace(',', "");
+    if integer.is_empty()
+        || !integer.chars().all(|c| c.is_ascii_digit())
+        || fraction.as_deref().is_some_and(|fraction| {
+            fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit())
+        })
+        || (ordinal && fraction.is_some())
+    {
+        return None;
+    }
+
+    Some(NumberMatch {
+        chunks: vec![integer.parse().ok()?],
+        fraction,
+        ordinal,
+        from_words: false,
+        end: index,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WordKind {
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+fn number_word(word: &str) -> Option<(WordKind, u64, bool)> {
+    let (kind, value, ordinal) = match word {
+        "zero" => (WordKind::Teen, 0, false),
+        "one" => (WordKind::Unit, 1, false),
+        "first" => (WordKind::Unit, 1, true),
+        "two" => (WordKind::Unit, 2, false),
+        "second" => (WordKind::Unit, 2, true),
+        "three" => (WordKind::Unit, 3, false),
+        "third" => (WordKind::Unit, 3, true),
+        "four" => (WordKind::Unit, 4, false),
+        "fourth" => (WordKind::Unit, 4, true),
+        "five" => (WordKind::Unit, 5, false),
+        "fifth" => (WordKind::Unit, 5, true),
+        "six" => (WordKind::Unit, 6, false),
+        "sixth" => (WordKind::Unit, 6, true),
+        "seven" => (WordKind::Unit, 7, false),
+        "seventh" => (WordKind::Unit, 7, true),
+        "eight" => (WordKind::Unit, 8, false),
+        "eighth" => (WordKind::Unit, 8, true),
+        "nine" => (WordKind::Unit, 9, false),
+        "ninth" => (WordKind::Unit, 9, true),
+        "ten" => (WordKind::Teen, 10, false),
+        "tenth" => (WordKind::Teen, 10, true),
+        "eleven" => (WordKind::Teen, 11, false),
+        "eleventh" => (WordKind::Teen, 11, true),
+        "twelve" => (WordKind::Teen, 12, false),
+        "twelfth" => (WordKind::Teen, 12, true),
+        "thirteen" => (WordKind::Teen, 13, false),
+        "thirteenth" => (WordKind::Teen, 13, true),
+        "fourteen" => (WordKind::Teen, 14, false),
+        "fourteenth" => (WordKind::Teen, 14, true),
+        "fifteen" => (WordKind::Teen, 15, false),
+        "fifteenth" => (WordKind::Teen, 15, true),
+        "sixteen" => (WordKind::Teen, 16, false),
+        "sixteenth" => (WordKind::Teen, 16, true),
+        "seventeen" => (WordKind::Teen, 17, false),
+        "seventeenth" => (WordKind::Teen, 17, true),
+        "eighteen" => (WordKind::Teen, 18, false),
+        "eighteenth" => (WordKind::Teen, 18, true),
+        "nineteen" => (WordKind::Teen, 19, false),
+        "nineteenth" => (WordKind::Teen, 19, true),
+        "twenty" => (WordKind::Tens, 20, false),
+        "twentieth" => (WordKind::Tens, 20, true),
+        "thirty" => (WordKind::Tens, 30, false),
+        "thirtieth" => (WordKind::Tens, 30, true),
+        "forty" => (WordKind::Tens, 40, false),
+        "fortieth" => (WordKind::Tens, 40, true),
+        "fifty" => (WordKind::Tens, 50, false),
+        "fiftieth" => (WordKind::Tens, 50, true),
+        "sixty" => (WordKind::Tens, 60, false),
+        "sixtieth" => (WordKind::Tens, 60, true),
+        "seventy" => (WordKind::Tens, 70, false),
+        "seventieth" => (WordKind::Tens, 70, true),
+        "eighty" => (WordKind::Tens, 80, false),
+        "eightieth" => (WordKind::Tens, 80, true),
+        "ninety" => (WordKind::Tens, 90, false),
+        "ninetieth" => (WordKind::Tens, 90, true),
+        "hundred" => (WordKind::Hundred, 100, false),
+        "hundredth" => (WordKind::Hundred, 100, true),
+        "thousand" => (WordKind::Scale, 1_000, false),
+        "thousandth" => (WordKind::Scale, 1_000, true),
+        "million" => (WordKind::Scale, 1_000_000, false),
+        "millionth" => (WordKind::Scale, 1_000_000, true),
+        "billion" => (WordKind::Scale, 1_000_000_000, false),
+        "billionth" => (WordKind::Scale, 1_000_000_000, true),
+        _ => return None,
+    };
+
+    Some((kind, value, ordinal))
+}
+
+fn digit_word(word: &str) -> Option<char> {
+    match word {
+        "zero" | "oh" => Some('0'),
+        _ => match number_word(word) {
+            Some((WordKind::Unit, value, false)) => char::from_digit(value as u32, 10),
+            _ => None,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct WordNumberParser {
+    chunks: Vec<u64>,
+    total: u64,
+    current: u64,
+    largest_scale: u64,
+    last: Option<WordKind>,
+    ordinal: bool,
+}
+
+impl WordNumberParser {
+    fn accepts(&self, kind: WordKind, value: u64) -> bool {
+        match kind {
+            WordKind::Unit => matches!(
+                self.last,
+                None | Some(WordKind::Tens | WordKind::Hundred | WordKind::Scale)
+            ),
+            WordKind::Teen | WordKind::Tens => {
+                matches!(self.last, None | Some(WordKind::Hundred | WordKind::Scale))
+            }
+            WordKind::Hundred => {
+                matches!(
+                    self.last,
+                    Some(WordKind::Unit | WordKind::Teen | WordKind::Tens)
+                ) && self.current < 100
+            }
+            WordKind::Scale => {
+                self.current > 0 && (self.largest_scale == 0 || value < self.largest_scale)
+            }
+        }
+    }
+
+    fn feed(&mut self, word: &str) -> bool {
+        let Some((kind, value, ordinal)) = number_word(word) else {
+            return false;
+        };
+        if self.ordinal {
+            return false;
+        }
+        if !self.accepts(kind, value) {
+            let starts_new_chunk = self.last.is_some()
+                && matches!(kind, WordKind::Unit | WordKind::Teen | WordKind::Tens);
+            if !starts_new_chunk {
+                return false;
+            }
+            self.chunks.push(self.total + self.current);
+            self.total = 0;
+            self.current = 0;
+            self.largest_scale = 0;
+        }
+
+        match kind {
+            WordKind::Unit | WordKind::Teen | WordKind::Tens => self.current += value,
+            WordKind::Hundred => self.current *= 100,
+            WordKind::Scale => {
+                self.total += self.current * value;
+                self.current = 0;
+                self.largest_scale = value;
+            }
+        }
+        self.last = Some(kind);
+        self.ordinal = ordinal;
+        true
+    }
+
+    fn finish(mut self) -> Option<(Vec<u64>, bool)> {
+        self.last?;
+        self.chunks.push(self.total + self.current);
+        Some((self.chunks, self.ordinal))
+    }
+}
+
+fn match_number_words(tokens: &[Token<'_>], start: usize) -> Option<NumberMatch> {
+    let joins = |index: usize| {
+        index > start
+            && index < tokens.len()
+            && tokens[index - 1].trail.is_empty()
+            && tokens[index].lead.is_empty()
+    };
+    let mut parser = WordNumberParser::default();
+    let mut end = start;
+    let mut index = start;
+    let mut fraction = None;
+    while index == start || joins(index) {
+        let lower = tokens[index].core.to_ascii_lowercase();
+        if lower == "and"
+            && matches!(parser.last, Some(WordKind::Hundred | WordKind::Scale))
+            && joins(index + 1)
+            && number_word(&tokens[index + 1].core.to_ascii_lowercase()).is_some()
+        {
+            index += 1;
+            continue;
+        }
+        if lower == "point" && parser.last.is_some() && !parser.ordinal && parser.chunks.is_empty()
+        {
+            let digits = (index + 1..tokens.len())
+                .take_while(|next| joins(*next))
+                .map_while(|next| digit_word(&tokens[next].core.to_ascii_lowercase()))
+                .collect::<String>();
+            if !digits.is_empty() {
+                end = index + digits.len();
+                fraction = Some(digits);
+            }
+            break;
+        }
+
+        let mut candidate = parser.clone();
+        if !lower.split('-').all(|part| candidate.feed(part)) {
+            if index == start {
+                return None;
+            }
+            break;
+        }
+        parser = candidate;
+        end = index;
+        index += 1;
+    }
+
+    let (chunks, ordinal) = parser.finish()?;
+    Some(NumberMatch {
+        chunks,
+        fraction,
+        ordinal,
+        from_words: true,
+        end,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_transcript, DateFormat, FormattingOptions, TimeFormat};
+
+    const NUMBERS_ONLY: FormattingOptions = FormattingOptions {
+        numbers_as_digits: true,
+        date_format: DateFormat::Off,
+        time_format: TimeFormat::Off,
+        abbreviate_units: false,
+    };
+
+    #[test]
+    fn spoken_numbers_become_digits_but_small_lone_words_stay() {
+        assert_eq!(
+            format_transcript(
+                "I need twenty three copies, one hundred and five pages, and one more.",
+                &NUMBERS_ONLY,
+                None,
+            ),
+            "I need 23 copies, 105 pages, and one more."
+        );
+        assert_eq!(
+            format_transcript(
+                "It costs two point five million.",
+                &NUMBERS_ONLY,
+                Some("en")
+            ),
+            "It costs 2.5 million."
+        );
+        assert_eq!(
+            format_transcript("the twenty-first of them", &NUMBERS_ONLY, None),
+            "the 21st of them"
+        );
+    }
+
+    #[test]
+    fn dates_and_times_use_the_chosen_formats() {
+        let options = FormattingOptions {
+            numbers_as_digits: false,
+            date_format: DateFormat::Iso,
+            time_format: TimeFormat::TwentyFourHour,
+            abbreviate_units: false,
+        };
+
+        assert_eq!(
+            format_transcript(
+                "Meet on March fifth, twenty twenty four at three thirty PM.",
+                &options,
+                None,
+            ),
+            "Meet on 2024-03-05 at 15:30."
+        );
+        assert_eq!(
+            format_transcript("Call at 9:05 a.m. on May 1st", &options, None),
+            "Call at 09:05 on May 1"
+        );
+    }
+
+    #[test]
+    fn units_are_abbreviated_after_numbers() {
+        let options = FormattingOptions {
+            abbreviate_units: true,
+            ..NUMBERS_ONLY
+        };
+
+        assert_eq!(
+            format_transcript("Run five kilometers at 80 percent effort.", &options, None),
+            "Run 5 km at 80% effort."
+        );
+        assert_eq!(
+            format_transcript("Several kilometers away.", &options, None),
+            "Several kilometers away."
+        );
+    }
+
+    #[test]
+    fn non_english_transcripts_pass_through() {
+        assert_eq!(
+            format_transcript("twenty three", &NUMBERS_ONLY, Some("de")),
+            "twenty three"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use crate::{
+    settings_store::{VoiceSettings, PARAGRAPH_SEGMENTATION_PAUSES, PARAGRAPH_SEGMENTATION_TOPICS},
+    transcription::TranscriptionSegment,
+};
+
+pub const MIN_SEGMENTED_CHARS: usize = 400;
+const PARAGRAPH_BREAK: &str = "\n\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParagraphSegmentation {
+    Off,
+    Pauses(Duration),
+    Topics,
+}
+
+impl ParagraphSegmentation {
+    pub fn from_settings(settings: &VoiceSettings) -> Self {
+        match settings.paragraph_segmentation.as_str() {
+            PARAGRAPH_SEGMENTATION_PAUSES => Self::Pauses(Duration::from_millis(u64::from(
+                settings.paragraph_pause_ms,
+            ))),
+            PARAGRAPH_SEGMENTATION_TOPICS => Self::Topics,
+            _ => Self::Off,
+        }
+    }
+
+    pub fn needs_timestamps(&self) -> bool {
+        matches!(self, Self::Pauses(_))
+    }
+}
+
+pub fn is_long_enough_to_segment(text: &str) -> bool {
+    text.chars().count() >= MIN_SEGMENTED_CHARS
+}
+
+pub fn paragraphs_from_segments(
+    segments: &[TranscriptionSegment],
+    pause: Duration,
+) -> Option<String> {
+    let pause_secs = pause.as_secs_f64();
+    let mut output = String::new();
+    let mut previous: Option<(&str, f64)> = None;
+    let mut breaks = 0;
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if let Some((previous_text, previous_end)) = previous {
+            if ends_sentence(previous_text) && segment.start_secs - previous_end >= pause_secs {
+                output.push_str(PARAGRAPH_BREAK);
+                breaks += 1;
+            } else {
+                output.push(' ');
+            }
+        }
+        output.push_str(text);
+        previous = Some((text, segment.end_secs));
+    }
+    (breaks > 0).then_some(output)
+}
+
+pub fn keeps_words(original: &str, segmented: &str) -> bool {
+    original.split_whitespace().eq(segmented.split_whitespace())
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(['"', '\'', ')', '”', '’'])
+        .ends_with(['.', '!', '?', '…'])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{keeps_words, paragraphs_from_segments};
+    use crate::transcription::TranscriptionSegment;
+
+    fn segment(start_secs: f64, end_secs: f64, text: &str) -> TranscriptionSegment {
+        TranscriptionSegment {
+            start_secs,
+            end_secs,
+            text: text.to_string(),
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn breaks_paragraphs_at_long_pauses_after_a_sentence() {
+        let segments = [
+            segment(0.0, 4.0, " The launch moved to Thursday."),
+            segment(4.2, 7.5, " Marketing needs the final copy"),
+            segment(9.8, 12.0, " by Tuesday."),
+            segment(14.5, 18.0, " Separately, the offsite is booked."),
+        ];
+
+        assert_eq!(
+            paragraphs_from_segments(&segments, Duration::from_millis(1_500)).as_deref(),
+            Some(
+                "The launch moved to Thursday. Marketing needs the final copy by Tuesday.\n\n\
+                 Separately, the offsite is booked."
+            )
+        );
+        assert_eq!(
+            paragraphs_from_segments(&segments, Duration::from_secs(5)),
+            None
+        );
+    }
+
+    #[test]
+    fn only_accepts_segmentation_that_keeps_every_word() {
+        let original = "First point here. Second point there.";
+
+        assert!(keeps_words(
+            original,
+            "First point here.\n\nSecond point there."
+        ));
+        assert!(!keeps_words(
+            original,
+            "First point.\n\nSecond point there."
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+use super::tokenize;
+
+const LITERAL: &str = "literal";
+const IMPLICIT_PUNCTUATION: [char; 6] = ['.', ',', '!', '?', ';', ':'];
+const ENABLE_COMMANDS: [&str; 3] = [
+    "spoken punctuation on",
+    "enable spoken punctuation",
+    "start spoken punctuation",
+];
+const DISABLE_COMMANDS: [&str; 3] = [
+    "spoken punctuation off",
+    "disable spoken punctuation",
+    "stop spoken punctuation",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spacing {
+    AfterWord,
+    BeforeWord,
+    Joined,
+    Spaced,
+    Break,
+}
+
+const SPOKEN_MARKS: &[(&str, &str, Spacing)] = &[
+    ("period", ".", Spacing::AfterWord),
+    ("full stop", ".", Spacing::AfterWord),
+    ("comma", ",", Spacing::AfterWord),
+    ("question mark", "?", Spacing::AfterWord),
+    ("exclamation mark", "!", Spacing::AfterWord),
+    ("exclamation point", "!", Spacing::AfterWord),
+    ("colon", ":", Spacing::AfterWord),
+    ("semicolon", ";", Spacing::AfterWord),
+    ("ellipsis", "...", Spacing::AfterWord),
+    ("close quote", "\"", Spacing::AfterWord),
+    ("end quote", "\"", Spacing::AfterWord),
+    ("unquote", "\"", Spacing::AfterWord),
+    ("close paren", ")", Spacing::AfterWord),
+    ("close parenthesis", ")", Spacing::AfterWord),
+    ("open quote", "\"", Spacing::BeforeWord),
+    ("open paren", "(", Spacing::BeforeWord),
+    ("open parenthesis", "(", Spacing::BeforeWord),
+    ("hyphen", "-", Spacing::Joined),
+    ("dash", "-", Spacing::Spaced),
+    ("new line", "\n", Spacing::Break),
+    ("new paragraph", "\n\n", Spacing::Break),
+];
+
+pub fn spoken_punctuation_command(text: &str) -> Option<bool> {
+    let normalized = text
+        .split_whitespace()
+        .map(|word| word.trim_matches(|character: char| !character.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if ENABLE_COMMANDS.contains(&normalized.as_str()) {
+        Some(true)
+    } else if DISABLE_COMMANDS.contains(&normalized.as_str()) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+pub fn apply_spoken_punctuation(text: &str) -> String {
+    let (tokens, _) = tokenize(text);
+    let words: Vec<String> = tokens
+        .iter()
+        .map(|token| {
+            format!(
+                "{}{}{}",
+                token.lead,
+                token.core,
+                token.trail.replace(IMPLICIT_PUNCTUATION, "")
+            )
+        })
+        .collect();
+    let cores: Vec<String> = tokens
+        .iter()
+        .map(|token| token.core.to_lowercase())
+        .collect();
+
+    let mut output = String::with_capacity(text.len());
+    let mut attach_next = true;
+    let mut capitalize_next = false;
+    let mut index = 0;
+    while index < words.len() {
+        if cores[index] == LITERAL {
+            if let Some(end) = match_mark(&cores, index + 1).map(|(end, _, _)| end) {
+                for word in &words[index + 1..=end] {
+                    push_word(&mut output, word, &mut attach_next, &mut capitalize_next);
+                }
+                index = end + 1;
+                continue;
+            }
+        }
+
+        let Some((end, mark, spacing)) = match_mark(&cores, index) else {
+            if !words[index].is_empty() {
+                push_word(
+                    &mut output,
+                    &words[index],
+                    &mut attach_next,
+                    &mut capitalize_next,
+                );
+            }
+            index += 1;
+            continue;
+        };
+        match spacing {
+            Spacing::AfterWord => {
+                output.push_str(mark);
+                attach_next = false;
+                capitalize_next = mark.ends_with(['.', '?', '!']);
+            }
+            Spacing::BeforeWord => {
+                if !attach_next {
+                    output.push(' ');
+                }
+                output.push_str(mark);
+                attach_next = true;
+            }
+            Spacing::Joined => {
+                output.push_str(mark);
+                attach_next = true;
+            }
+            Spacing::Spaced => {
+                if !output.is_empty() {
+                    output.push(' ');
+                }
+                output.push_str(mark);
+                attach_next = false;
+            }
+            Spacing::Break => {
+                output.truncate(output.trim_end_matches(' ').len());
+                output.push_str(mark);
+                attach_next = true;
+                capitalize_next = true;
+            }
+        }
+        index = end + 1;
+    }
+    output
+}
+
+fn push_word(output: &mut String, word: &str, attach_next: &mut bool, capitalize_next: &mut bool) {
+    if !*attach_next {
+        output.push(' ');
+    }
+    if std::mem::take(capitalize_next) {
+        let mut characters = word.chars();
+        if let Some(first) = characters.next() {
+            output.extend(first.to_uppercase());
+            output.push_str(characters.as_str());
+        }
+    } else {
+        output.push_str(word);
+    }
+    *attach_next = false;
+}
+
+fn match_mark(cores: &[String], index: usize) -> Option<(usize, &'static str, Spacing)> {
+    SPOKEN_MARKS
+        .iter()
+        .filter_map(|(phrase, mark, spacing)| {
+            let length = phrase.split(' ').count();
+            let candidate = cores.get(index..index + length)?;
+            phrase
+                .split(' ')
+                .eq(candidate.iter().map(String::as_str))
+                .then_some((index + length - 1, *mark, *spacing))
+        })
+        .max_by_key(|(end, _, _)| *end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_spoken_punctuation, spoken_punctuation_command};
+
+    #[test]
+    fn spoken_marks_replace_the_guessed_punctuation() {
+        assert_eq!(
+            apply_spoken_punctuation(
+                "Dear Sir, comma. New paragraph. The tenant shall, open quote, vacate, close quote, period."
+            ),
+            "Dear Sir,\n\nThe tenant shall \"vacate\"."
+        );
+        assert_eq!(
+            apply_spoken_punctuation("TODO colon handle the error dash see issue forty two"),
+            "TODO: handle the error - see issue forty two"
+        );
+        assert_eq!(
+            apply_spoken_punctuation("Add a literal comma here period is it done question mark"),
+            "Add a comma here. Is it done?"
+        );
+    }
+
+    #[test]
+    fn recognizes_toggle_commands_only_as_the_whole_dictation() {
+        assert_eq!(
+            spoken_punctuation_command("Spoken punctuation on."),
+            Some(true)
+        );
+        assert_eq!(
+            spoken_punctuation_command("disable spoken punctuation"),
+            Some(false)
+        );
+        assert_eq!(
+            spoken_punctuation_command("Please turn spoken punctuation on for me."),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    hotkey_service::FrontmostApplication,
+    settings_store::{VoiceSettings, INSERTION_TRAILING_NEWLINE, INSERTION_TRAILING_SPACE},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trailing {
+    None,
+    Space,
+    Newline,
+}
+
+impl Trailing {
+    fn from_setting(value: &str) -> Self {
+        match value {
+            INSERTION_TRAILING_SPACE => Self::Space,
+            INSERTION_TRAILING_NEWLINE => Self::Newline,
+            _ => Self::None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Space => " ",
+            Self::Newline => "\n",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrailingOptions {
+    pub trailing: Trailing,
+    pub strip_trailing_period: bool,
+}
+
+impl TrailingOptions {
+    pub fn for_app(settings: &VoiceSettings, frontmost: Option<&FrontmostApplication>) -> Self {
+        let matched = frontmost.and_then(|frontmost| {
+            settings
+                .trailing_overrides
+                .iter()
+                .find(|trailing_override| frontmost.matches(&trailing_override.app))
+        });
+        match matched {
+            Some(trailing_override) => Self {
+                trailing: Trailing::from_setting(&trailing_override.insertion_trailing),
+                strip_trailing_period: trailing_override.strip_trailing_period,
+            },
+            None => Self {
+                trailing: Trailing::from_setting(&settings.insertion_trailing),
+                strip_trailing_period: settings.strip_trailing_period,
+            },
+        }
+    }
+
+    pub fn is_noop(&self) -> bool {
+        self.trailing == Trailing::None && !self.strip_trailing_period
+    }
+}
+
+pub fn apply_trailing(text: &str, options: &TrailingOptions) -> String {
+    if options.is_noop() || text.trim().is_empty() {
+        return text.to_string();
+    }
+
+    let mut output = text.trim_end().to_string();
+    if options.strip_trailing_period && output.ends_with('.') && !output.ends_with("..") {
+        output.pop();
+    }
+    output.push_str(options.trailing.as_str());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_trailing, Trailing, TrailingOptions};
+    use crate::{
+        hotkey_service::FrontmostApplication,
+        settings_store::{TrailingOverride, VoiceSettings, INSERTION_TRAILING_NEWLINE},
+    };
+
+    #[test]
+    fn appends_separator_and_strips_lone_period() {
+        let chat = TrailingOptions {
+            trailing: Trailing::Space,
+            strip_trailing_period: true,
+        };
+        assert_eq!(apply_trailing("See you soon. ", &chat), "See you soon ");
+        assert_eq!(apply_trailing("Well...", &chat), "Well... ");
+        assert_eq!(apply_trailing("  ", &chat), "  ");
+
+        let notes = TrailingOptions {
+            trailing: Trailing::Newline,
+            strip_trailing_period: false,
+        };
+        assert_eq!(apply_trailing("First point.", &notes), "First point.\n");
+    }
+
+    #[test]
+    fn frontmost_app_override_wins_over_global_setting() {
+        let settings = VoiceSettings {
+            strip_trailing_period: true,
+            trailing_overrides: vec![TrailingOverride {
+                app: "Notes".to_string(),
+                insertion_trailing: INSERTION_TRAILING_NEWLINE.to_string(),
+                strip_trailing_period: false,
+            }],
+            ..VoiceSettings::default()
+        };
+        let notes = FrontmostApplication {
+            bundle_id: Some("com.apple.Notes".to_string()),
+            name: Some("Notes".to_string()),
+        };
+
+        assert_eq!(
+            TrailingOptions::for_app(&settings, Some(&notes)),
+            TrailingOptions {
+                trailing: Trailing::Newline,
+                strip_trailing_period: false,
+            }
+        );
+        assert_eq!(
+            TrailingOptions::for_app(&settings, None),
+            TrailingOptions {
+                trailing: Trailing::None,
+                strip_trailing_period: true,
+            }
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+pub(super) fn grapheme_chunks(
+    text: &str,
+    max: usize,
+    measure: impl Fn(&str) -> usize,
+) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut size = 0;
+    let mut offset = 0;
+
+    for cluster in text.graphemes(true) {
+        let cluster_size = measure(cluster);
+        if size + cluster_size > max && offset > start {
+            chunks.push(&text[start..offset]);
+            start = offset;
+            size = 0;
+        }
+        size += cluster_size;
+        offset += cluster.len();
+    }
+
+    if offset > start {
+        chunks.push(&text[start..offset]);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use super::grapheme_chunks;
+
+    #[test]
+    fn clusters_match_what_a_reader_sees_as_one_character() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        let cases: &[(&str, &[&str])] = &[
+            (
+                "Zo\u{EB} Bront\u{EB}",
+                &["Z", "o", "\u{EB}", " ", "B", "r", "o", "n", "t", "\u{EB}"],
+            ),
+            ("Jose\u{301}", &["J", "o", "s", "e\u{301}"]),
+            ("北京市", &["北", "京", "市"]),
+            (
+                "\u{634}\u{643}\u{631}\u{64B}\u{627}",
+                &["\u{634}", "\u{643}", "\u{631}\u{64B}", "\u{627}"],
+            ),
+            (
+                "\u{5E9}\u{5C1}\u{5B8}\u{5DC}\u{5D5}\u{5B9}\u{5DD}",
+                &[
+                    "\u{5E9}\u{5C1}\u{5B8}",
+                    "\u{5DC}",
+                    "\u{5D5}\u{5B9}",
+                    "\u{5DD}",
+                ],
+            ),
+            ("\u{D55C}\u{AD6D}", &["\u{D55C}", "\u{AD6D}"]),
+            ("\u{1112}\u{1161}\u{11AB}", &["\u{1112}\u{1161}\u{11AB}"]),
+            ("\u{1F44D}\u{1F3FD}ok", &["\u{1F44D}\u{1F3FD}", "o", "k"]),
+            (&format!("{family}!"), &[family, "!"]),
+            ("\u{2764}\u{FE0F}", &["\u{2764}\u{FE0F}"]),
+            ("1\u{FE0F}\u{20E3}", &["1\u{FE0F}\u{20E3}"]),
+            (
+                "\u{1F1EF}\u{1F1F5}\u{1F1EB}\u{1F1F7}\u{1F1E9}",
+                &["\u{1F1EF}\u{1F1F5}", "\u{1F1EB}\u{1F1F7}", "\u{1F1E9}"],
+            ),
+            (
+                "\u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}",
+                &["\u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}"],
+            ),
+            ("a\r\nb", &["a", "\r\n", "b"]),
+            (
+                "\u{915}\u{93F}\u{924}\u{93E}\u{92C}",
+                &["\u{915}\u{93F}", "\u{924}\u{93E}", "\u{92C}"],
+            ),
+            ("\u{928}\u{947}", &["\u{928}\u{947}"]),
+            (
+                "\u{E01}\u{E31}\u{E19}\u{E19}\u{E33}",
+                &["\u{E01}\u{E31}", "\u{E19}", "\u{E19}\u{E33}"],
+            ),
+            ("a\u{200D}b", &["a\u{200D}", "b"]),
+        ];
+
+        for (text, expected) in cases {
+            let clusters: Vec<&str> = text.graphemes(true).collect();
+            assert_eq!(&clusters, expected, "clusters of {text:?}");
+            assert_eq!(clusters.concat(), *text);
+        }
+    }
+
+    #[test]
+    fn chunks_keep_clusters_whole_even_when_one_exceeds_the_limit() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        let text = format!("ab{family}e\u{301}\u{308}f");
+        let chunks = grapheme_chunks(&text, 3, |cluster| cluster.encode_utf16().count());
+
+        assert_eq!(chunks, vec!["ab", family, "e\u{301}\u{308}", "f"]);
+        assert_eq!(
+            grapheme_chunks(&text, 2, |_| 1),
+            vec![
+                "ab".to_string(),
+                format!("{family}e\u{301}\u{308}"),
+                "f".to_string()
+            ]
+        );
+        assert!(grapheme_chunks("", 4, |_| 1).is_empty());
+    }
+}
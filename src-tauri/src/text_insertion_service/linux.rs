@@ -0,0 +1,206 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    sync::Mutex,
+    thread::sleep,
+    time::Duration,
+};
+
+use tracing::{debug, info};
+
+use super::{ClipboardWrite, FocusedWindowFrame, InsertionBackend};
+use crate::platform_support::{
+    detect_capabilities, portal::RemoteDesktopKeyboard, ClipboardBackend, TypingBackend,
+};
+
+const PASTE_REGISTER_DELAY_MS: u64 = 75;
+// Linux input event codes for Left Ctrl and V, as expected by `ydotool key`.
+const YDOTOOL_CTRL_V_SEQUENCE: [&str; 4] = ["29:1", "47:1", "47:0", "29:0"];
+
+#[derive(Debug)]
+pub(super) struct LinuxInsertionBackend {
+    typing: TypingBackend,
+    clipboard: ClipboardBackend,
+    remote_desktop: Mutex<Option<RemoteDesktopKeyboard>>,
+}
+
+impl Default for LinuxInsertionBackend {
+    fn default() -> Self {
+        let capabilities = detect_capabilities();
+        info!(
+            typing = ?capabilities.typing,
+            clipboard = ?capabilities.clipboard,
+            "linux text insertion backend selected"
+        );
+        Self {
+            typing: capabilities.typing,
+            clipboard: capabilities.clipboard,
+            remote_desktop: Mutex::new(None),
+        }
+    }
+}
+
+impl LinuxInsertionBackend {
+    fn with_remote_desktop<T>(
+        &self,
+        action: impl FnOnce(&RemoteDesktopKeyboard) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let mut keyboard = self
+            .remote_desktop
+            .lock()
+            .map_err(|_| "Remote desktop session lock is poisoned".to_string())?;
+        if keyboard.is_none() {
+            *keyboard = Some(RemoteDesktopKeyboard::start()?);
+        }
+        let keyboard = keyboard
+            .as_ref()
+            .ok_or_else(|| "Remote desktop session was unavailable".to_string())?;
+        action(keyboard)
+    }
+}
+
+impl InsertionBackend for LinuxInsertionBackend {
+    // Neither Wayland nor X11 exposes the focused widget portably; the typing tools target
+    // whatever has keyboard focus.
+    fn has_focused_input_target(&self) -> bool {
+        true
+    }
+
+    fn type_unicode_text(&self, text: &str) -> Result<(), String> {
+        match self.typing {
+            TypingBackend::Wtype => run_command("wtype", &["--", text]),
+            TypingBackend::Ydotool => run_command("ydotool", &["type", "--", text]),
+            TypingBackend::Xdotool => {
+                run_command("xdotool", &["type", "--clearmodifiers", "--", text])
+            }
+            TypingBackend::RemoteDesktopPortal => {
+                self.with_remote_desktop(|keyboard| keyboard.type_text(text))
+            }
+            TypingBackend::Native | TypingBackend::Unavailable => Err(
+                "No text input tool is available; install wtype, ydotool or xdotool".to_string(),
+            ),
+        }
+    }
+
+    fn read_text_from_clipboard(&self) -> Result<String, String> {
+        match self.clipboard {
+            ClipboardBackend::WlClipboard => read_command_output("wl-paste", &["--no-newline"]),
+            ClipboardBackend::Xclip => {
+                read_command_output("xclip", &["-selection", "clipboard", "-out"])
+            }
+            ClipboardBackend::Native | ClipboardBackend::Unavailable => {
+                Err("No clipboard tool is available; install wl-clipboard or xclip".to_string())
+            }
+        }
+    }
+
+    fn write_text_to_clipboard(&self, text: &str, _write: ClipboardWrite) -> Result<(), String> {
+        match self.clipboard {
+            ClipboardBackend::WlClipboard => pipe_to_command("wl-copy", &[], text),
+            ClipboardBackend::Xclip => pipe_to_command("xclip", &["-selection", "clipboard"], text),
+            ClipboardBackend::Native | ClipboardBackend::Unavailable => {
+                Err("No clipboard tool is available; install wl-clipboard or xclip".to_string())
+            }
+        }
+    }
+
+    fn post_command_v(&self, _key_code: Option<u16>) -> Result<(), String> {
+        match self.typing {
+            TypingBackend::Wtype => run_command("wtype", &["-M", "ctrl", "v", "-m", "ctrl"]),
+            TypingBackend::Ydotool => {
+                let mut args = vec!["key"];
+                args.extend(YDOTOOL_CTRL_V_SEQUENCE);
+                run_command("ydotool", &args)
+            }
+            TypingBackend::Xdotool => {
+                run_command("xdotool", &["key", "--clearmodifiers", "ctrl+v"])
+            }
+            TypingBackend::RemoteDesktopPortal => {
+                self.with_remote_desktop(RemoteDesktopKeyboard::paste)
+            }
+            TypingBackend::Native | TypingBackend::Unavailable => {
+                Err("No key input tool is available to paste".to_string())
+            }
+        }
+    }
+
+    fn wait_for_paste_to_register(&self) {
+        sleep(Duration::from_millis(PASTE_REGISTER_DELAY_MS));
+    }
+
+    fn types_us_key_positions(&self) -> bool {
+        self.typing == TypingBackend::Ydotool
+    }
+}
+
+pub(super) fn activate_application(bundle_id: &str) -> Result<(), String> {
+    Err(format!(
+        "Activating `{bundle_id}` is not supported on Linux; focus the app and re-insert instead"
+    ))
+}
+
+pub fn focused_window_frame() -> Option<FocusedWindowFrame> {
+    None
+}
+
+pub fn focused_window_title() -> Option<String> {
+    None
+}
+
+fn run_command(program: &str, args: &[&str]) -> Result<(), String> {
+    debug!(program, "running text insertion command");
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|error| format!("Failed to start {program}: {error}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{program} exited with status {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+fn read_command_output(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|error| format!("Failed to start {program}: {error}"))?;
+    if !output.status.success() {
+        return Err(format!("{program} exited with status: {}", output.status));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|error| format!("Clipboard is not UTF-8 text: {error}"))
+}
+
+fn pipe_to_command(program: &str, args: &[&str], input: &str) -> Result<(), String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("Failed to start {program}: {error}"))?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| format!("Failed to open {program} stdin"))?;
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|error| format!("Failed writing text to {program}: {error}"))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|error| format!("Failed waiting for {program}: {error}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program} exited with status: {status}"))
+    }
+}
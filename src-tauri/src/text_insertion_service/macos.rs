@@ -0,0 +1,628 @@
+use std::{
+    ffi::{c_void, CStr, CString},
+    io::Write,
+    process::{Command, Stdio},
+    ptr,
+    thread::sleep,
+    time::Duration,
+};
+
+use objc::{
+    class, msg_send,
+    rc::autoreleasepool,
+    runtime::{Object, BOOL, NO},
+    sel, sel_impl,
+};
+use tracing::warn;
+
+use super::{graphemes::grapheme_chunks, ClipboardWrite, FocusedWindowFrame, InsertionBackend};
+
+const AX_SUCCESS: i32 = 0;
+const K_CG_ANNOTATED_SESSION_EVENT_TAP: u32 = 2;
+const K_CG_EVENT_FLAG_MASK_COMMAND: u64 = 0x0010_0000;
+const VIRTUAL_KEY_V: u16 = 0x09;
+const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const K_AX_VALUE_CG_POINT_TYPE: u32 = 1;
+const K_AX_VALUE_CG_SIZE_TYPE: u32 = 2;
+
+const UNICODE_CHUNK_SIZE: usize = 48;
+const PASTE_REGISTER_DELAY_MS: u64 = 75;
+const APP_ACTIVATION_DELAY_MS: u64 = 300;
+
+const CONCEALED_PASTEBOARD_TYPES: [&CStr; 2] = [
+    c"org.nspasteboard.ConcealedType",
+    c"org.nspasteboard.TransientType",
+];
+const PLAIN_TEXT_PASTEBOARD_TYPE: &CStr = c"public.utf8-plain-text";
+
+type CFTypeRef = *const c_void;
+type CFAllocatorRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFIndex = isize;
+type CFTypeID = usize;
+type UniChar = u16;
+type Boolean = u8;
+type CGKeyCode = u16;
+type CGEventSourceRef = *mut c_void;
+type CGEventRef = *mut c_void;
+type CGEventFlags = u64;
+type CGEventTapLocation = u32;
+type AXUIElementRef = *const c_void;
+type AXError = i32;
+type AXValueRef = *const c_void;
+type AXValueType = u32;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct CGPoint {
+    x: f64,
+    y: f64,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct CGSize {
+    width: f64,
+    height: f64,
+}
+
+#[link(name = "ApplicationServices", kind = "framework")]
+unsafe extern "C" {
+    fn CGEventCreateKeyboardEvent(
+        source: CGEventSourceRef,
+        virtualKey: CGKeyCode,
+        keyDown: Boolean,
+    ) -> CGEventRef;
+    fn CGEventKeyboardSetUnicodeString(
+        event: CGEventRef,
+        stringLength: CFIndex,
+        unicodeString: *const UniChar,
+    );
+    fn CGEventPost(tap: CGEventTapLocation, event: CGEventRef);
+    fn CGEventSetFlags(event: CGEventRef, flags: CGEventFlags);
+
+    fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+    fn AXUIElementCopyAttributeValue(
+        element: AXUIElementRef,
+        attribute: CFStringRef,
+        value: *mut CFTypeRef,
+    ) -> AXError;
+    fn AXValueGetValue(
+        value: AXValueRef,
+        value_type: AXValueType,
+        value_ptr: *mut c_void,
+    ) -> Boolean;
+
+    fn CFRelease(cf: CFTypeRef);
+}
+
+#[link(name = "Carbon", kind = "framework")]
+unsafe extern "C" {
+    fn IsSecureEventInputEnabled() -> Boolean;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    fn CFStringCreateWithCString(
+        alloc: CFAllocatorRef,
+        cStr: *const i8,
+        encoding: u32,
+    ) -> CFStringRef;
+
+    fn CFGetTypeID(cf: CFTypeRef) -> CFTypeID;
+    fn CFStringGetTypeID() -> CFTypeID;
+    fn CFStringGetLength(string: CFStringRef) -> CFIndex;
+    fn CFStringGetMaximumSizeForEncoding(length: CFIndex, encoding: u32) -> CFIndex;
+    fn CFStringGetCString(
+        string: CFStringRef,
+        buffer: *mut i8,
+        buffer_size: CFIndex,
+        encoding: u32,
+    ) -> Boolean;
+
+    static kCFAllocatorDefault: CFAllocatorRef;
+}
+
+#[derive(Debug, Default)]
+pub(super) struct MacOsInsertionBackend;
+
+impl InsertionBackend for MacOsInsertionBackend {
+    fn has_focused_input_target(&self) -> bool {
+        has_focused_input_target()
+    }
+
+    fn type_unicode_text(&self, text: &str) -> Result<(), String> {
+        type_unicode_text(text)
+    }
+
+    fn read_text_from_clipboard(&self) -> Result<String, String> {
+        read_text_from_clipboard()
+    }
+
+    fn write_text_to_clipboard(&self, text: &str, write: ClipboardWrite) -> Result<(), String> {
+        match write {
+            ClipboardWrite::Recorded => write_text_to_clipboard(text),
+            ClipboardWrite::Concealed => write_concealed_text_to_clipboard(text),
+        }
+    }
+
+    fn post_command_v(&self, key_code: Option<u16>) -> Result<(), String> {
+        post_command_v(key_code.unwrap_or(VIRTUAL_KEY_V))
+    }
+
+    fn wait_for_paste_to_register(&self) {
+        wait_for_paste_to_register();
+    }
+
+    fn secure_input_owner(&self) -> Option<String> {
+        secure_input_owner()
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        selected_text()
+    }
+}
+
+pub(super) fn activate_application(bundle_id: &str) -> Result<(), String> {
+    let status = Command::new("open")
+        .args(["-b", bundle_id])
+        .status()
+        .map_err(|error| format!("Failed to start open: {error}"))?;
+    if !status.success() {
+        return Err(format!(
+            "Failed to activate app `{bundle_id}`: open exited with status {status}"
+        ));
+    }
+
+    sleep(Duration::from_millis(APP_ACTIVATION_DELAY_MS));
+    Ok(())
+}
+
+// `pbcopy` and `pbpaste` fall back to Mac Roman when `LANG` is unset, as it is for apps
+// launched from Finder.
+fn pasteboard_command(program: &str) -> Command {
+    let mut command = Command::new(program);
+    command.env("LANG", "en_US.UTF-8");
+    command
+}
+
+fn read_text_from_clipboard() -> Result<String, String> {
+    let output = pasteboard_command("pbpaste")
+        .output()
+        .map_err(|error| format!("Failed to start pbpaste: {error}"))?;
+
+    if !output.status.success() {
+        return Err(format!("pbpaste exited with status: {}", output.status));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|error| format!("Clipboard is not UTF-8 text: {error}"))
+}
+
+fn write_text_to_clipboard(text: &str) -> Result<(), String> {
+    let mut child = pasteboard_command("pbcopy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Failed to start pbcopy: {error}"))?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| "Failed to open pbcopy stdin".to_string())?;
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|error| format!("Failed writing text to pbcopy: {error}"))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|error| format!("Failed waiting for pbcopy: {error}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("pbcopy exited with status: {status}"))
+    }
+}
+
+// `pbcopy` can only write the plain text type.
+#[allow(unexpected_cfgs)]
+fn write_concealed_text_to_clipboard(text: &str) -> Result<(), String> {
+    let text = CString::new(text)
+        .map_err(|_| "Clipboard text must not contain NUL characters".to_string())?;
+
+    autoreleasepool(|| unsafe {
+        let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+        if pasteboard.is_null() {
+            return Err("Failed to open the general pasteboard".to_string());
+        }
+        let _: isize = msg_send![pasteboard, clearContents];
+        if !set_pasteboard_string(pasteboard, &text, PLAIN_TEXT_PASTEBOARD_TYPE) {
+            return Err("Failed to write text to the pasteboard".to_string());
+        }
+        for marker in CONCEALED_PASTEBOARD_TYPES {
+            if !set_pasteboard_string(pasteboard, c"", marker) {
+                warn!(?marker, "failed to mark pasteboard contents as concealed");
+            }
+        }
+        Ok(())
+    })
+}
+
+#[allow(unexpected_cfgs)]
+unsafe fn set_pasteboard_string(
+    pasteboard: *mut Object,
+    value: &CStr,
+    pasteboard_type: &CStr,
+) -> bool {
+    let value: *mut Object = msg_send![class!(NSString), stringWithUTF8String: value.as_ptr()];
+    let pasteboard_type: *mut Object =
+        msg_send![class!(NSString), stringWithUTF8String: pasteboard_type.as_ptr()];
+    if value.is_null() || pasteboard_type.is_null() {
+        return false;
+    }
+    let written: BOOL = msg_send![pasteboard, setString: value forType: pasteboard_type];
+    written != NO
+}
+
+// Secure Input is global, so the owner is usually another app rather than the focused one.
+fn secure_input_owner() -> Option<String> {
+    if unsafe { IsSecureEventInputEnabled() } == 0 {
+        return None;
+    }
+
+    let owner = secure_input_pid()
+        .and_then(process_name)
+        .unwrap_or_else(|| "another app".to_string());
+    warn!(owner, "secure event input is enabled");
+    Some(owner)
+}
+
+fn secure_input_pid() -> Option<u32> {
+    let output = Command::new("ioreg")
+        .args(["-l", "-w", "0", "-d", "1"])
+        .output()
+        .ok()?;
+    parse_secure_input_pid(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_secure_input_pid(ioreg_output: &str) -> Option<u32> {
+    const SECURE_INPUT_PID_KEY: &str = "\"kCGSSessionSecureInputPID\"=";
+
+    let start = ioreg_output.find(SECURE_INPUT_PID_KEY)? + SECURE_INPUT_PID_KEY.len();
+    let digits: String = ioreg_output[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok().filter(|pid| *pid != 0)
+}
+
+fn process_name(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .ok()?;
+    let command = String::from_utf8_lossy(&output.stdout);
+    let name = command.trim().rsplit('/').next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn has_focused_input_target() -> bool {
+    const AX_FOCUSED_APPLICATION_ATTRIBUTE: &[u8] = b"AXFocusedApplication\0";
+    const AX_FOCUSED_UI_ELEMENT_ATTRIBUTE: &[u8] = b"AXFocusedUIElement\0";
+
+    unsafe {
+        let system_wide = AXUIElementCreateSystemWide();
+        if system_wide.is_null() {
+            return false;
+        }
+
+        let focused_app_attribute = CFStringCreateWithCString(
+            kCFAllocatorDefault,
+            AX_FOCUSED_APPLICATION_ATTRIBUTE.as_ptr() as *const i8,
+            K_CF_STRING_ENCODING_UTF8,
+        );
+        let focused_ui_element_attribute = CFStringCreateWithCString(
+            kCFAllocatorDefault,
+            AX_FOCUSED_UI_ELEMENT_ATTRIBUTE.as_ptr() as *const i8,
+            K_CF_STRING_ENCODING_UTF8,
+        );
+
+        if focused_app_attribute.is_null() || focused_ui_element_attribute.is_null() {
+            if !focused_app_attribute.is_null() {
+                CFRelease(focused_app_attribute);
+            }
+            if !focused_ui_element_attribute.is_null() {
+                CFRelease(focused_ui_element_attribute);
+            }
+            CFRelease(system_wide as CFTypeRef);
+            return false;
+        }
+
+        let mut focused_app: CFTypeRef = ptr::null();
+        let app_status =
+            AXUIElementCopyAttributeValue(system_wide, focused_app_attribute, &mut focused_app);
+
+        let mut focused_element: CFTypeRef = ptr::null();
+        let element_status = AXUIElementCopyAttributeValue(
+            system_wide,
+            focused_ui_element_attribute,
+            &mut focused_element,
+        );
+
+        if !focused_app.is_null() {
+            CFRelease(focused_app);
+        }
+        if !focused_element.is_null() {
+            CFRelease(focused_element);
+        }
+        CFRelease(focused_app_attribute);
+        CFRelease(focused_ui_element_attribute);
+        CFRelease(system_wide as CFTypeRef);
+
+        app_status == AX_SUCCESS && element_status == AX_SUCCESS
+    }
+}
+
+pub fn focused_window_frame() -> Option<FocusedWindowFrame> {
+    unsafe {
+        let system_wide = AXUIElementCreateSystemWide();
+        if system_wide.is_null() {
+            return None;
+        }
+        let focused_app = copy_ax_attribute(system_wide, b"AXFocusedApplication\0");
+        CFRelease(system_wide as CFTypeRef);
+
+        let focused_app = focused_app?;
+        let focused_window = copy_ax_attribute(focused_app, b"AXFocusedWindow\0");
+        CFRelease(focused_app);
+
+        let focused_window = focused_window?;
+        let position = copy_ax_attribute(focused_window, b"AXPosition\0");
+        let size = copy_ax_attribute(focused_window, b"AXSize\0");
+        CFRelease(focused_window);
+
+        let mut origin = CGPoint::default();
+        let mut extent = CGSize::default();
+        let resolved = match (position, size) {
+            (Some(position), Some(size)) => {
+                AXValueGetValue(
+                    position,
+                    K_AX_VALUE_CG_POINT_TYPE,
+                    &mut origin as *mut CGPoint as *mut c_void,
+                ) != 0
+                    && AXValueGetValue(
+                        size,
+                        K_AX_VALUE_CG_SIZE_TYPE,
+                        &mut extent as *mut CGSize as *mut c_void,
+                    ) != 0
+            }
+            _ => false,
+        };
+        for value in [position, size].into_iter().flatten() {
+            CFRelease(value);
+        }
+
+        resolved.then_some(FocusedWindowFrame {
+            x: origin.x,
+            y: origin.y,
+            width: extent.width,
+            height: extent.height,
+        })
+    }
+}
+
+pub fn focused_window_title() -> Option<String> {
+    unsafe {
+        let system_wide = AXUIElementCreateSystemWide();
+        if system_wide.is_null() {
+            return None;
+        }
+        let focused_app = copy_ax_attribute(system_wide, b"AXFocusedApplication\0");
+        CFRelease(system_wide as CFTypeRef);
+
+        let focused_app = focused_app?;
+        let focused_window = copy_ax_attribute(focused_app, b"AXFocusedWindow\0");
+        CFRelease(focused_app);
+
+        let focused_window = focused_window?;
+        let title = copy_ax_attribute(focused_window, b"AXTitle\0");
+        CFRelease(focused_window);
+
+        let title = title?;
+        let text = cf_string_to_string(title);
+        CFRelease(title);
+        text
+    }
+}
+
+fn selected_text() -> Option<String> {
+    unsafe {
+        let system_wide = AXUIElementCreateSystemWide();
+        if system_wide.is_null() {
+            return None;
+        }
+        let focused_element = copy_ax_attribute(system_wide, b"AXFocusedUIElement\0");
+        CFRelease(system_wide as CFTypeRef);
+
+        let focused_element = focused_element?;
+        let selected = copy_ax_attribute(focused_element, b"AXSelectedText\0");
+        CFRelease(focused_element);
+
+        let selected = selected?;
+        let text = cf_string_to_string(selected);
+        CFRelease(selected);
+        text
+    }
+}
+
+unsafe fn cf_string_to_string(value: CFTypeRef) -> Option<String> {
+    if CFGetTypeID(value) != CFStringGetTypeID() {
+        return None;
+    }
+    let capacity =
+        CFStringGetMaximumSizeForEncoding(CFStringGetLength(value), K_CF_STRING_ENCODING_UTF8) + 1;
+    let mut buffer = vec![0_u8; usize::try_from(capacity).ok()?];
+    if CFStringGetCString(
+        value,
+        buffer.as_mut_ptr() as *mut i8,
+        capacity,
+        K_CF_STRING_ENCODING_UTF8,
+    ) == 0
+    {
+        return None;
+    }
+    let end = buffer
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(buffer.len());
+    buffer.truncate(end);
+    String::from_utf8(buffer).ok()
+}
+
+unsafe fn copy_ax_attribute(element: CFTypeRef, attribute: &[u8]) -> Option<CFTypeRef> {
+    let attribute_name = CFStringCreateWithCString(
+        kCFAllocatorDefault,
+        attribute.as_ptr() as *const i8,
+        K_CF_STRING_ENCODING_UTF8,
+    );
+    if attribute_name.is_null() {
+        return None;
+    }
+
+    let mut value: CFTypeRef = ptr::null();
+    let status = AXUIElementCopyAttributeValue(element, attribute_name, &mut value);
+    CFRelease(attribute_name);
+
+    if status == AX_SUCCESS && !value.is_null() {
+        Some(value)
+    } else {
+        if !value.is_null() {
+            CFRelease(value);
+        }
+        None
+    }
+}
+
+fn type_unicode_text(text: &str) -> Result<(), String> {
+    for chunk in utf16_chunks_preserving_graphemes(text, UNICODE_CHUNK_SIZE) {
+        post_unicode_keystroke(&chunk, true)?;
+        post_unicode_keystroke(&chunk, false)?;
+    }
+
+    Ok(())
+}
+
+fn utf16_chunks_preserving_graphemes(text: &str, max_units: usize) -> Vec<Vec<u16>> {
+    if max_units == 0 {
+        return Vec::new();
+    }
+
+    grapheme_chunks(text, max_units, |cluster| cluster.encode_utf16().count())
+        .into_iter()
+        .map(|chunk| chunk.encode_utf16().collect())
+        .collect()
+}
+
+fn post_unicode_keystroke(chunk: &[u16], key_down: bool) -> Result<(), String> {
+    unsafe {
+        let event = CGEventCreateKeyboardEvent(ptr::null_mut(), 0, key_down as Boolean);
+        if event.is_null() {
+            return Err("Failed to create keyboard event".to_string());
+        }
+
+        CGEventKeyboardSetUnicodeString(event, chunk.len() as CFIndex, chunk.as_ptr());
+        CGEventPost(K_CG_ANNOTATED_SESSION_EVENT_TAP, event);
+        CFRelease(event as CFTypeRef);
+    }
+
+    Ok(())
+}
+
+fn post_command_v(key_code: u16) -> Result<(), String> {
+    unsafe {
+        let key_down = CGEventCreateKeyboardEvent(ptr::null_mut(), key_code, true as Boolean);
+        if key_down.is_null() {
+            return Err("Failed to create key-down event for Cmd+V".to_string());
+        }
+        CGEventSetFlags(key_down, K_CG_EVENT_FLAG_MASK_COMMAND as CGEventFlags);
+        CGEventPost(K_CG_ANNOTATED_SESSION_EVENT_TAP, key_down);
+        CFRelease(key_down as CFTypeRef);
+
+        let key_up = CGEventCreateKeyboardEvent(ptr::null_mut(), key_code, false as Boolean);
+        if key_up.is_null() {
+            return Err("Failed to create key-up event for Cmd+V".to_string());
+        }
+        CGEventSetFlags(key_up, K_CG_EVENT_FLAG_MASK_COMMAND as CGEventFlags);
+        CGEventPost(K_CG_ANNOTATED_SESSION_EVENT_TAP, key_up);
+        CFRelease(key_up as CFTypeRef);
+    }
+
+    Ok(())
+}
+
+fn wait_for_paste_to_register() {
+    sleep(Duration::from_millis(PASTE_REGISTER_DELAY_MS));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_secure_input_pid, utf16_chunks_preserving_graphemes, UNICODE_CHUNK_SIZE};
+
+    #[test]
+    fn utf16_chunking_preserves_non_bmp_characters() {
+        let text = format!("{}{}{}", "a".repeat(UNICODE_CHUNK_SIZE - 1), "😀😀", "𐍈");
+        let chunks = utf16_chunks_preserving_graphemes(&text, UNICODE_CHUNK_SIZE);
+
+        assert!(!chunks.is_empty());
+        assert!(chunks.iter().all(|chunk| chunk.len() <= UNICODE_CHUNK_SIZE));
+
+        let flattened: Vec<u16> = chunks.into_iter().flatten().collect();
+        let reconstructed = String::from_utf16(&flattened).expect("valid UTF-16 chunks");
+        assert_eq!(reconstructed, text);
+    }
+
+    #[test]
+    fn utf16_chunking_never_splits_surrogate_pairs() {
+        let text = format!("{}{}", "a".repeat(UNICODE_CHUNK_SIZE - 1), "😀😀😀");
+        let chunks = utf16_chunks_preserving_graphemes(&text, UNICODE_CHUNK_SIZE);
+
+        assert!(chunks.iter().all(|chunk| {
+            chunk
+                .last()
+                .is_none_or(|unit| !(0xD800..=0xDBFF).contains(unit))
+        }));
+    }
+
+    #[test]
+    fn utf16_chunking_keeps_accents_and_emoji_sequences_with_their_base() {
+        let text = format!(
+            "{}{}{}",
+            "a".repeat(UNICODE_CHUNK_SIZE - 3),
+            "Jose\u{301}",
+            "\u{1F44D}\u{1F3FD}"
+        );
+        let chunks = utf16_chunks_preserving_graphemes(&text, UNICODE_CHUNK_SIZE);
+
+        let chunk_strings: Vec<String> = chunks
+            .iter()
+            .map(|chunk| String::from_utf16(chunk).expect("valid UTF-16 chunks"))
+            .collect();
+        assert_eq!(chunk_strings.len(), 2);
+        assert!(chunk_strings[1].starts_with("e\u{301}"));
+        assert_eq!(chunk_strings.concat(), text);
+    }
+
+    #[test]
+    fn parses_secure_input_pid_from_ioreg_output() {
+        let output = r#"  | "IOConsoleUsers" = ({"kCGSSessionOnConsoleKey"=Yes,"kCGSSessionSecureInputPID"=4821,"kCGSessionLoginDoneKey"=Yes})"#;
+
+        assert_eq!(parse_secure_input_pid(output), Some(4821));
+        assert_eq!(
+            parse_secure_input_pid(r#"{"kCGSSessionSecureInputPID"=0}"#),
+            None
+        );
+        assert_eq!(parse_secure_input_pid("{}"), None);
+    }
+}
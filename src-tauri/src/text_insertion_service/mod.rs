@@ -1,71 +1,109 @@
+mod graphemes;
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+
 use std::{
-    ffi::c_void,
-    io::Write,
-    process::{Command, Stdio},
-    ptr,
-    thread::sleep,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
     time::Duration,
 };
+
 use tracing::{debug, info, warn};
 
-const AX_SUCCESS: i32 = 0;
-const K_CG_ANNOTATED_SESSION_EVENT_TAP: u32 = 2;
-const K_CG_EVENT_FLAG_MASK_COMMAND: u64 = 0x0010_0000;
-const VIRTUAL_KEY_V: u16 = 0x09;
-const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+use crate::{
+    hotkey_service::{self, FrontmostApplication},
+    settings_store::VoiceSettings,
+};
+
+#[cfg(target_os = "linux")]
+use linux::{activate_application, LinuxInsertionBackend as PlatformInsertionBackend};
+#[cfg(target_os = "linux")]
+pub use linux::{focused_window_frame, focused_window_title};
+#[cfg(target_os = "macos")]
+use macos::{activate_application, MacOsInsertionBackend as PlatformInsertionBackend};
+#[cfg(target_os = "macos")]
+pub use macos::{focused_window_frame, focused_window_title};
 
 const DIRECT_TYPE_THRESHOLD_CHARS: usize = 400;
-const UNICODE_CHUNK_SIZE: usize = 48;
-const PASTE_REGISTER_DELAY_MS: u64 = 75;
-
-type CFTypeRef = *const c_void;
-type CFAllocatorRef = *const c_void;
-type CFStringRef = *const c_void;
-type CFIndex = isize;
-type UniChar = u16;
-type Boolean = u8;
-type CGKeyCode = u16;
-type CGEventSourceRef = *mut c_void;
-type CGEventRef = *mut c_void;
-type CGEventFlags = u64;
-type CGEventTapLocation = u32;
-type AXUIElementRef = *const c_void;
-type AXError = i32;
-
-#[link(name = "ApplicationServices", kind = "framework")]
-unsafe extern "C" {
-    fn CGEventCreateKeyboardEvent(
-        source: CGEventSourceRef,
-        virtualKey: CGKeyCode,
-        keyDown: Boolean,
-    ) -> CGEventRef;
-    fn CGEventKeyboardSetUnicodeString(
-        event: CGEventRef,
-        stringLength: CFIndex,
-        unicodeString: *const UniChar,
-    );
-    fn CGEventPost(tap: CGEventTapLocation, event: CGEventRef);
-    fn CGEventSetFlags(event: CGEventRef, flags: CGEventFlags);
-
-    fn AXUIElementCreateSystemWide() -> AXUIElementRef;
-    fn AXUIElementCopyAttributeValue(
-        element: AXUIElementRef,
-        attribute: CFStringRef,
-        value: *mut CFTypeRef,
-    ) -> AXError;
-
-    fn CFRelease(cf: CFTypeRef);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusedWindowFrame {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl FocusedWindowFrame {
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypingSpeed {
+    pub chars_per_second: u32,
+    pub chunk_chars: usize,
+}
+
+impl TypingSpeed {
+    fn new(chars_per_second: u32, chunk_chars: u32) -> Self {
+        Self {
+            chars_per_second,
+            chunk_chars: chunk_chars as usize,
+        }
+    }
+
+    fn chunk_pause(&self) -> Option<Duration> {
+        (self.chars_per_second > 0 && self.chunk_chars > 0).then(|| {
+            Duration::from_secs_f64(self.chunk_chars as f64 / f64::from(self.chars_per_second))
+        })
+    }
 }
 
-#[link(name = "CoreFoundation", kind = "framework")]
-unsafe extern "C" {
-    fn CFStringCreateWithCString(
-        alloc: CFAllocatorRef,
-        cStr: *const i8,
-        encoding: u32,
-    ) -> CFStringRef;
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypingConfig {
+    pub default: TypingSpeed,
+    pub overrides: Vec<(String, TypingSpeed)>,
+}
+
+impl TypingConfig {
+    pub fn from_settings(settings: &VoiceSettings) -> Self {
+        Self {
+            default: TypingSpeed::new(
+                settings.typing_chars_per_second,
+                settings.typing_chunk_chars,
+            ),
+            overrides: settings
+                .typing_speed_overrides
+                .iter()
+                .map(|speed_override| {
+                    (
+                        speed_override.app.clone(),
+                        TypingSpeed::new(
+                            speed_override.typing_chars_per_second,
+                            speed_override.typing_chunk_chars,
+                        ),
+                    )
+                })
+                .collect(),
+        }
+    }
 
-    static kCFAllocatorDefault: CFAllocatorRef;
+    pub fn speed_for(&self, frontmost: Option<&FrontmostApplication>) -> TypingSpeed {
+        frontmost
+            .and_then(|frontmost| {
+                self.overrides
+                    .iter()
+                    .find(|(app, _)| frontmost.matches(app))
+            })
+            .map_or(self.default, |(_, speed)| *speed)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -74,47 +112,63 @@ pub enum InsertionMode {
     CopyOnly,
 }
 
-trait InsertionBackend {
-    fn has_focused_input_target(&self) -> bool;
-    fn type_unicode_text(&self, text: &str) -> Result<(), String>;
-    fn read_text_from_clipboard(&self) -> Result<String, String>;
-    fn write_text_to_clipboard(&self, text: &str) -> Result<(), String>;
-    fn post_command_v(&self) -> Result<(), String>;
-    fn wait_for_paste_to_register(&self);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ClipboardWrite {
+    #[default]
+    Recorded,
+    Concealed,
 }
 
-#[derive(Debug, Default)]
-struct MacOsInsertionBackend;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypingLayout {
+    pub paste_key_code: Option<u16>,
+    pub us_key_positions: bool,
+}
 
-impl InsertionBackend for MacOsInsertionBackend {
-    fn has_focused_input_target(&self) -> bool {
-        has_focused_input_target()
+impl Default for TypingLayout {
+    fn default() -> Self {
+        Self {
+            paste_key_code: None,
+            us_key_positions: true,
+        }
     }
+}
 
-    fn type_unicode_text(&self, text: &str) -> Result<(), String> {
-        type_unicode_text(text)
-    }
+#[derive(Debug, Clone, Copy, Default)]
+struct InsertionOptions {
+    speed: TypingSpeed,
+    paste_write: ClipboardWrite,
+    layout: TypingLayout,
+}
 
-    fn read_text_from_clipboard(&self) -> Result<String, String> {
-        read_text_from_clipboard()
-    }
+trait InsertionBackend {
+    fn has_focused_input_target(&self) -> bool;
+    fn type_unicode_text(&self, text: &str) -> Result<(), String>;
+    fn read_text_from_clipboard(&self) -> Result<String, String>;
+    fn write_text_to_clipboard(&self, text: &str, write: ClipboardWrite) -> Result<(), String>;
+    fn post_command_v(&self, key_code: Option<u16>) -> Result<(), String>;
+    fn wait_for_paste_to_register(&self);
 
-    fn write_text_to_clipboard(&self, text: &str) -> Result<(), String> {
-        write_text_to_clipboard(text)
+    fn types_us_key_positions(&self) -> bool {
+        false
     }
 
-    fn post_command_v(&self) -> Result<(), String> {
-        post_command_v()
+    fn secure_input_owner(&self) -> Option<String> {
+        None
     }
 
-    fn wait_for_paste_to_register(&self) {
-        wait_for_paste_to_register();
+    // The X11/Wayland primary selection outlives the visible highlight, so only macOS reports one.
+    fn selected_text(&self) -> Option<String> {
+        None
     }
 }
 
 #[derive(Debug, Default)]
 pub struct TextInsertionService {
-    backend: MacOsInsertionBackend,
+    backend: PlatformInsertionBackend,
+    typing: Mutex<TypingConfig>,
+    conceal_clipboard_writes: AtomicBool,
+    layout: Mutex<TypingLayout>,
 }
 
 impl TextInsertionService {
@@ -123,14 +177,83 @@ impl TextInsertionService {
         Self::default()
     }
 
+    pub fn configure_typing(&self, config: TypingConfig) {
+        match self.typing.lock() {
+            Ok(mut typing) => *typing = config,
+            Err(_) => warn!("typing config lock poisoned"),
+        }
+    }
+
+    pub fn set_conceal_clipboard_writes(&self, conceal: bool) {
+        self.conceal_clipboard_writes
+            .store(conceal, Ordering::Relaxed);
+    }
+
+    pub fn set_typing_layout(&self, layout: TypingLayout) {
+        match self.layout.lock() {
+            Ok(mut current) => *current = layout,
+            Err(_) => warn!("typing layout lock poisoned"),
+        }
+    }
+
     pub fn insert_text(&self, text: &str) -> Result<(), String> {
-        info!(chars = text.chars().count(), "text insertion requested");
-        insert_text_with_backend(&self.backend, text, InsertionMode::Auto)
+        let speed = self.typing_speed();
+        let layout = self.layout.lock().map(|layout| *layout).unwrap_or_default();
+        info!(
+            chars = text.chars().count(),
+            chars_per_second = speed.chars_per_second,
+            chunk_chars = speed.chunk_chars,
+            paste_key_code = ?layout.paste_key_code,
+            "text insertion requested"
+        );
+        let paste_write = if self.conceal_clipboard_writes.load(Ordering::Relaxed) {
+            ClipboardWrite::Concealed
+        } else {
+            ClipboardWrite::Recorded
+        };
+        let options = InsertionOptions {
+            speed,
+            paste_write,
+            layout,
+        };
+        insert_text_with_backend(&self.backend, text, InsertionMode::Auto, options)
     }
 
     pub fn copy_to_clipboard(&self, text: &str) -> Result<(), String> {
         info!(chars = text.chars().count(), "copy to clipboard requested");
-        insert_text_with_backend(&self.backend, text, InsertionMode::CopyOnly)
+        insert_text_with_backend(
+            &self.backend,
+            text,
+            InsertionMode::CopyOnly,
+            InsertionOptions::default(),
+        )
+    }
+
+    fn typing_speed(&self) -> TypingSpeed {
+        let Some(config) = self.typing.lock().ok().map(|typing| typing.clone()) else {
+            warn!("typing config lock poisoned");
+            return TypingSpeed::default();
+        };
+        if config.overrides.is_empty() {
+            return config.default;
+        }
+        config.speed_for(hotkey_service::frontmost_application().as_ref())
+    }
+
+    pub fn selected_text(&self) -> Option<String> {
+        self.backend
+            .selected_text()
+            .filter(|text| !text.trim().is_empty())
+    }
+
+    pub fn activate_application(&self, bundle_id: &str) -> Result<(), String> {
+        let bundle_id = bundle_id.trim();
+        if bundle_id.is_empty() {
+            return Err("App identifier must not be empty".to_string());
+        }
+
+        info!(bundle_id, "activating target application");
+        activate_application(bundle_id)
     }
 }
 
@@ -138,6 +261,7 @@ fn insert_text_with_backend<B: InsertionBackend>(
     backend: &B,
     text: &str,
     mode: InsertionMode,
+    options: InsertionOptions,
 ) -> Result<(), String> {
     if text.is_empty() {
         debug!("skipping text insertion because payload is empty");
@@ -146,34 +270,80 @@ fn insert_text_with_backend<B: InsertionBackend>(
 
     if matches!(mode, InsertionMode::CopyOnly) {
         debug!("executing clipboard-only insertion mode");
-        return backend.write_text_to_clipboard(text);
+        return backend.write_text_to_clipboard(text, ClipboardWrite::Recorded);
+    }
+
+    if let Some(owner) = backend.secure_input_owner() {
+        warn!(
+            owner,
+            "secure input is active; copying transcript instead of typing"
+        );
+        backend.write_text_to_clipboard(text, ClipboardWrite::Recorded)?;
+        return Err(format!(
+            "Secure input is enabled by {owner}; the transcript was copied to the clipboard instead"
+        ));
     }
 
-    let should_use_paste_fallback =
-        text.chars().count() > DIRECT_TYPE_THRESHOLD_CHARS || !backend.has_focused_input_target();
+    let layout_mismatch = backend.types_us_key_positions() && !options.layout.us_key_positions;
+    let should_use_paste_fallback = text.chars().count() > DIRECT_TYPE_THRESHOLD_CHARS
+        || !backend.has_focused_input_target()
+        || layout_mismatch;
 
     if should_use_paste_fallback {
         warn!(
             chars = text.chars().count(),
-            "using clipboard paste fallback instead of direct typing"
+            layout_mismatch, "using clipboard paste fallback instead of direct typing"
         );
-        return paste_via_clipboard(backend, text);
+        return paste_via_clipboard(backend, text, options);
     }
 
-    match backend.type_unicode_text(text) {
+    match type_in_chunks(backend, text, options.speed) {
         Ok(()) => {
             debug!("direct unicode typing succeeded");
             Ok(())
         }
-        Err(direct_error) => paste_via_clipboard(backend, text).map_err(|paste_error| {
-            format!(
-                "Direct insertion failed ({direct_error}); clipboard fallback failed ({paste_error})"
-            )
-        }),
+        Err((typed, direct_error)) => {
+            paste_via_clipboard(backend, &text[typed..], options).map_err(|paste_error| {
+                format!(
+                    "Direct insertion failed ({direct_error}); clipboard fallback failed ({paste_error})"
+                )
+            })
+        }
     }
 }
 
-fn paste_via_clipboard<B: InsertionBackend>(backend: &B, text: &str) -> Result<(), String> {
+fn type_in_chunks<B: InsertionBackend>(
+    backend: &B,
+    text: &str,
+    speed: TypingSpeed,
+) -> Result<(), (usize, String)> {
+    let Some(pause) = speed.chunk_pause() else {
+        return backend.type_unicode_text(text).map_err(|error| (0, error));
+    };
+
+    let mut typed = 0;
+    while typed < text.len() {
+        if typed > 0 {
+            thread::sleep(pause);
+        }
+        let rest = &text[typed..];
+        let chunk = graphemes::grapheme_chunks(rest, speed.chunk_chars, |_| 1)[0];
+        backend
+            .type_unicode_text(chunk)
+            .map_err(|error| (typed, error))?;
+        typed += chunk.len();
+    }
+    Ok(())
+}
+
+// The restored clipboard uses the paste write mode too, or history managers would record the
+// old contents a second time.
+fn paste_via_clipboard<B: InsertionBackend>(
+    backend: &B,
+    text: &str,
+    options: InsertionOptions,
+) -> Result<(), String> {
+    let write = options.paste_write;
     let previous_clipboard = match backend.read_text_from_clipboard() {
         Ok(clipboard) => Some(clipboard),
         Err(error) => {
@@ -183,15 +353,15 @@ fn paste_via_clipboard<B: InsertionBackend>(backend: &B, text: &str) -> Result<(
     };
 
     debug!("writing fallback text to clipboard");
-    backend.write_text_to_clipboard(text)?;
-    let paste_result = backend.post_command_v();
+    backend.write_text_to_clipboard(text, write)?;
+    let paste_result = backend.post_command_v(options.layout.paste_key_code);
     if paste_result.is_ok() {
         debug!("clipboard paste shortcut posted successfully");
         backend.wait_for_paste_to_register();
     }
 
     if let Some(previous_clipboard) = previous_clipboard {
-        if let Err(error) = backend.write_text_to_clipboard(&previous_clipboard) {
+        if let Err(error) = backend.write_text_to_clipboard(&previous_clipboard, write) {
             warn!(%error, "failed to restore clipboard after paste fallback");
         }
     }
@@ -199,187 +369,13 @@ fn paste_via_clipboard<B: InsertionBackend>(backend: &B, text: &str) -> Result<(
     paste_result
 }
 
-fn read_text_from_clipboard() -> Result<String, String> {
-    let output = Command::new("pbpaste")
-        .output()
-        .map_err(|error| format!("Failed to start pbpaste: {error}"))?;
-
-    if !output.status.success() {
-        return Err(format!("pbpaste exited with status: {}", output.status));
-    }
-
-    String::from_utf8(output.stdout)
-        .map_err(|error| format!("Clipboard is not UTF-8 text: {error}"))
-}
-
-fn write_text_to_clipboard(text: &str) -> Result<(), String> {
-    let mut child = Command::new("pbcopy")
-        .stdin(Stdio::piped())
-        .spawn()
-        .map_err(|error| format!("Failed to start pbcopy: {error}"))?;
-
-    {
-        let stdin = child
-            .stdin
-            .as_mut()
-            .ok_or_else(|| "Failed to open pbcopy stdin".to_string())?;
-        stdin
-            .write_all(text.as_bytes())
-            .map_err(|error| format!("Failed writing text to pbcopy: {error}"))?;
-    }
-
-    let status = child
-        .wait()
-        .map_err(|error| format!("Failed waiting for pbcopy: {error}"))?;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(format!("pbcopy exited with status: {status}"))
-    }
-}
-
-fn has_focused_input_target() -> bool {
-    const AX_FOCUSED_APPLICATION_ATTRIBUTE: &[u8] = b"AXFocusedApplication\0";
-    const AX_FOCUSED_UI_ELEMENT_ATTRIBUTE: &[u8] = b"AXFocusedUIElement\0";
-
-    unsafe {
-        let system_wide = AXUIElementCreateSystemWide();
-        if system_wide.is_null() {
-            return false;
-        }
-
-        let focused_app_attribute = CFStringCreateWithCString(
-            kCFAllocatorDefault,
-            AX_FOCUSED_APPLICATION_ATTRIBUTE.as_ptr() as *const i8,
-            K_CF_STRING_ENCODING_UTF8,
-        );
-        let focused_ui_element_attribute = CFStringCreateWithCString(
-            kCFAllocatorDefault,
-            AX_FOCUSED_UI_ELEMENT_ATTRIBUTE.as_ptr() as *const i8,
-            K_CF_STRING_ENCODING_UTF8,
-        );
-
-        if focused_app_attribute.is_null() || focused_ui_element_attribute.is_null() {
-            if !focused_app_attribute.is_null() {
-                CFRelease(focused_app_attribute);
-            }
-            if !focused_ui_element_attribute.is_null() {
-                CFRelease(focused_ui_element_attribute);
-            }
-            CFRelease(system_wide as CFTypeRef);
-            return false;
-        }
-
-        let mut focused_app: CFTypeRef = ptr::null();
-        let app_status =
-            AXUIElementCopyAttributeValue(system_wide, focused_app_attribute, &mut focused_app);
-
-        let mut focused_element: CFTypeRef = ptr::null();
-        let element_status = AXUIElementCopyAttributeValue(
-            system_wide,
-            focused_ui_element_attribute,
-            &mut focused_element,
-        );
-
-        if !focused_app.is_null() {
-            CFRelease(focused_app);
-        }
-        if !focused_element.is_null() {
-            CFRelease(focused_element);
-        }
-        CFRelease(focused_app_attribute);
-        CFRelease(focused_ui_element_attribute);
-        CFRelease(system_wide as CFTypeRef);
-
-        app_status == AX_SUCCESS && element_status == AX_SUCCESS
-    }
-}
-
-fn type_unicode_text(text: &str) -> Result<(), String> {
-    for chunk in utf16_chunks_preserving_char_boundaries(text, UNICODE_CHUNK_SIZE) {
-        post_unicode_keystroke(&chunk, true)?;
-        post_unicode_keystroke(&chunk, false)?;
-    }
-
-    Ok(())
-}
-
-fn utf16_chunks_preserving_char_boundaries(text: &str, max_units: usize) -> Vec<Vec<u16>> {
-    if max_units == 0 {
-        return Vec::new();
-    }
-
-    let mut chunks: Vec<Vec<u16>> = Vec::new();
-    let mut current_chunk: Vec<u16> = Vec::with_capacity(max_units);
-
-    for character in text.chars() {
-        let mut character_utf16 = [0_u16; 2];
-        let encoded_character = character.encode_utf16(&mut character_utf16);
-
-        if current_chunk.len() + encoded_character.len() > max_units && !current_chunk.is_empty() {
-            chunks.push(current_chunk);
-            current_chunk = Vec::with_capacity(max_units);
-        }
-
-        current_chunk.extend_from_slice(encoded_character);
-    }
-
-    if !current_chunk.is_empty() {
-        chunks.push(current_chunk);
-    }
-
-    chunks
-}
-
-fn post_unicode_keystroke(chunk: &[u16], key_down: bool) -> Result<(), String> {
-    unsafe {
-        let event = CGEventCreateKeyboardEvent(ptr::null_mut(), 0, key_down as Boolean);
-        if event.is_null() {
-            return Err("Failed to create keyboard event".to_string());
-        }
-
-        CGEventKeyboardSetUnicodeString(event, chunk.len() as CFIndex, chunk.as_ptr());
-        CGEventPost(K_CG_ANNOTATED_SESSION_EVENT_TAP, event);
-        CFRelease(event as CFTypeRef);
-    }
-
-    Ok(())
-}
-
-fn post_command_v() -> Result<(), String> {
-    unsafe {
-        let key_down = CGEventCreateKeyboardEvent(ptr::null_mut(), VIRTUAL_KEY_V, true as Boolean);
-        if key_down.is_null() {
-            return Err("Failed to create key-down event for Cmd+V".to_string());
-        }
-        CGEventSetFlags(key_down, K_CG_EVENT_FLAG_MASK_COMMAND as CGEventFlags);
-        CGEventPost(K_CG_ANNOTATED_SESSION_EVENT_TAP, key_down);
-        CFRelease(key_down as CFTypeRef);
-
-        let key_up = CGEventCreateKeyboardEvent(ptr::null_mut(), VIRTUAL_KEY_V, false as Boolean);
-        if key_up.is_null() {
-            return Err("Failed to create key-up event for Cmd+V".to_string());
-        }
-        CGEventSetFlags(key_up, K_CG_EVENT_FLAG_MASK_COMMAND as CGEventFlags);
-        CGEventPost(K_CG_ANNOTATED_SESSION_EVENT_TAP, key_up);
-        CFRelease(key_up as CFTypeRef);
-    }
-
-    Ok(())
-}
-
-fn wait_for_paste_to_register() {
-    sleep(Duration::from_millis(PASTE_REGISTER_DELAY_MS));
-}
-
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
 
     use super::{
-        insert_text_with_backend, utf16_chunks_preserving_char_boundaries, InsertionBackend,
-        InsertionMode, DIRECT_TYPE_THRESHOLD_CHARS, UNICODE_CHUNK_SIZE,
+        insert_text_with_backend, ClipboardWrite, InsertionBackend, InsertionMode,
+        InsertionOptions, TypingLayout, TypingSpeed, DIRECT_TYPE_THRESHOLD_CHARS,
     };
 
     #[derive(Debug)]
@@ -390,8 +386,14 @@ mod tests {
         restore_result: Result<(), String>,
         paste_result: Result<(), String>,
         clipboard_read_result: Result<String, String>,
+        secure_input_owner: Option<String>,
+        fail_typing_call: Option<usize>,
+        types_us_key_positions: bool,
         calls: RefCell<Vec<&'static str>>,
+        typed: RefCell<Vec<String>>,
         clipboard_writes: RefCell<Vec<String>>,
+        clipboard_write_kinds: RefCell<Vec<ClipboardWrite>>,
+        paste_key_codes: RefCell<Vec<Option<u16>>>,
     }
 
     impl Default for MockBackend {
@@ -403,8 +405,14 @@ mod tests {
                 restore_result: Ok(()),
                 paste_result: Ok(()),
                 clipboard_read_result: Ok("previous clipboard".to_string()),
+                secure_input_owner: None,
+                fail_typing_call: None,
+                types_us_key_positions: false,
                 calls: RefCell::new(Vec::new()),
+                typed: RefCell::new(Vec::new()),
                 clipboard_writes: RefCell::new(Vec::new()),
+                clipboard_write_kinds: RefCell::new(Vec::new()),
+                paste_key_codes: RefCell::new(Vec::new()),
             }
         }
     }
@@ -425,8 +433,13 @@ mod tests {
             self.focused_input
         }
 
-        fn type_unicode_text(&self, _text: &str) -> Result<(), String> {
+        fn type_unicode_text(&self, text: &str) -> Result<(), String> {
             self.calls.borrow_mut().push("direct_type");
+            let mut typed = self.typed.borrow_mut();
+            if self.fail_typing_call == Some(typed.len()) {
+                return Err("dropped keystrokes".to_string());
+            }
+            typed.push(text.to_string());
             self.type_result.clone()
         }
 
@@ -435,8 +448,9 @@ mod tests {
             self.clipboard_read_result.clone()
         }
 
-        fn write_text_to_clipboard(&self, text: &str) -> Result<(), String> {
+        fn write_text_to_clipboard(&self, text: &str, write: ClipboardWrite) -> Result<(), String> {
             self.calls.borrow_mut().push("copy");
+            self.clipboard_write_kinds.borrow_mut().push(write);
             let mut clipboard_writes = self.clipboard_writes.borrow_mut();
             let write_index = clipboard_writes.len();
             clipboard_writes.push(text.to_string());
@@ -448,21 +462,36 @@ mod tests {
             }
         }
 
-        fn post_command_v(&self) -> Result<(), String> {
+        fn post_command_v(&self, key_code: Option<u16>) -> Result<(), String> {
             self.calls.borrow_mut().push("paste");
+            self.paste_key_codes.borrow_mut().push(key_code);
             self.paste_result.clone()
         }
 
+        fn types_us_key_positions(&self) -> bool {
+            self.types_us_key_positions
+        }
+
         fn wait_for_paste_to_register(&self) {
             self.calls.borrow_mut().push("wait");
         }
+
+        fn secure_input_owner(&self) -> Option<String> {
+            self.calls.borrow_mut().push("secure_input_check");
+            self.secure_input_owner.clone()
+        }
     }
 
     #[test]
     fn copy_only_mode_only_updates_clipboard() {
         let backend = MockBackend::default();
 
-        let result = insert_text_with_backend(&backend, "hello", InsertionMode::CopyOnly);
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::CopyOnly,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(backend.call_order(), vec!["copy"]);
@@ -472,10 +501,38 @@ mod tests {
     fn auto_mode_prefers_direct_typing_for_short_text_with_focus() {
         let backend = MockBackend::default();
 
-        let result = insert_text_with_backend(&backend, "short text", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "short text",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
-        assert_eq!(backend.call_order(), vec!["focus_check", "direct_type"]);
+        assert_eq!(
+            backend.call_order(),
+            vec!["secure_input_check", "focus_check", "direct_type"]
+        );
+    }
+
+    #[test]
+    fn secure_input_copies_to_clipboard_and_reports_the_owner() {
+        let backend = MockBackend {
+            secure_input_owner: Some("Terminal".to_string()),
+            ..Default::default()
+        };
+
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
+
+        let error = result.unwrap_err();
+        assert!(error.contains("Secure input is enabled by Terminal"));
+        assert_eq!(backend.call_order(), vec!["secure_input_check", "copy"]);
+        assert_eq!(backend.clipboard_writes(), vec!["hello".to_string()]);
     }
 
     #[test]
@@ -485,12 +542,18 @@ mod tests {
             ..Default::default()
         };
 
-        let result = insert_text_with_backend(&backend, "hello", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(
             backend.call_order(),
             vec![
+                "secure_input_check",
                 "focus_check",
                 "clipboard_read",
                 "copy",
@@ -505,17 +568,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn paste_fallback_conceals_its_writes_but_explicit_copies_stay_recorded() {
+        let backend = MockBackend {
+            focused_input: false,
+            ..Default::default()
+        };
+
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions {
+                paste_write: ClipboardWrite::Concealed,
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *backend.clipboard_write_kinds.borrow(),
+            vec![ClipboardWrite::Concealed, ClipboardWrite::Concealed]
+        );
+
+        let backend = MockBackend::default();
+
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::CopyOnly,
+            InsertionOptions {
+                paste_write: ClipboardWrite::Concealed,
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *backend.clipboard_write_kinds.borrow(),
+            vec![ClipboardWrite::Recorded]
+        );
+    }
+
+    #[test]
+    fn key_position_typing_pastes_with_the_layout_key_on_other_layouts() {
+        let backend = MockBackend {
+            types_us_key_positions: true,
+            ..Default::default()
+        };
+        let options = InsertionOptions {
+            layout: TypingLayout {
+                paste_key_code: Some(47),
+                us_key_positions: false,
+            },
+            ..Default::default()
+        };
+
+        let result = insert_text_with_backend(&backend, "hallo", InsertionMode::Auto, options);
+
+        assert!(result.is_ok());
+        assert!(backend.typed.borrow().is_empty());
+        assert_eq!(*backend.paste_key_codes.borrow(), vec![Some(47)]);
+
+        let backend = MockBackend {
+            types_us_key_positions: true,
+            ..Default::default()
+        };
+
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(*backend.typed.borrow(), vec!["hello"]);
+    }
+
     #[test]
     fn auto_mode_uses_clipboard_for_long_text() {
         let backend = MockBackend::default();
         let text = "a".repeat(DIRECT_TYPE_THRESHOLD_CHARS + 1);
 
-        let result = insert_text_with_backend(&backend, &text, InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            &text,
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(
             backend.call_order(),
-            vec!["clipboard_read", "copy", "paste", "wait", "copy"]
+            vec![
+                "secure_input_check",
+                "clipboard_read",
+                "copy",
+                "paste",
+                "wait",
+                "copy"
+            ]
         );
     }
 
@@ -526,12 +679,18 @@ mod tests {
             ..Default::default()
         };
 
-        let result = insert_text_with_backend(&backend, "hello", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(
             backend.call_order(),
             vec![
+                "secure_input_check",
                 "focus_check",
                 "direct_type",
                 "clipboard_read",
@@ -551,12 +710,23 @@ mod tests {
             ..Default::default()
         };
 
-        let result = insert_text_with_backend(&backend, "hello", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_err());
         assert_eq!(
             backend.call_order(),
-            vec!["focus_check", "direct_type", "clipboard_read", "copy"]
+            vec![
+                "secure_input_check",
+                "focus_check",
+                "direct_type",
+                "clipboard_read",
+                "copy"
+            ]
         );
         let error = result.unwrap_err();
         assert!(error.contains("direct failed"));
@@ -571,12 +741,18 @@ mod tests {
             ..Default::default()
         };
 
-        let result = insert_text_with_backend(&backend, "hello", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(
             backend.call_order(),
             vec![
+                "secure_input_check",
                 "focus_check",
                 "clipboard_read",
                 "copy",
@@ -599,48 +775,109 @@ mod tests {
             ..Default::default()
         };
 
-        let result = insert_text_with_backend(&backend, "hello", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "hello",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(
             backend.call_order(),
-            vec!["focus_check", "clipboard_read", "copy", "paste", "wait"]
+            vec![
+                "secure_input_check",
+                "focus_check",
+                "clipboard_read",
+                "copy",
+                "paste",
+                "wait"
+            ]
         );
         assert_eq!(backend.clipboard_writes(), vec!["hello".to_string()]);
     }
 
     #[test]
-    fn empty_text_is_noop() {
+    fn throttled_typing_sends_chunks_and_pastes_only_what_was_not_typed() {
+        let speed = TypingSpeed {
+            chars_per_second: 10_000,
+            chunk_chars: 4,
+        };
         let backend = MockBackend::default();
 
-        let result = insert_text_with_backend(&backend, "", InsertionMode::Auto);
+        let result = insert_text_with_backend(
+            &backend,
+            "héllo wörld",
+            InsertionMode::Auto,
+            InsertionOptions {
+                speed,
+                ..Default::default()
+            },
+        );
 
         assert!(result.is_ok());
-        assert!(backend.call_order().is_empty());
+        assert_eq!(*backend.typed.borrow(), vec!["héll", "o wö", "rld"]);
+
+        let backend = MockBackend {
+            fail_typing_call: Some(1),
+            ..Default::default()
+        };
+
+        let result = insert_text_with_backend(
+            &backend,
+            "héllo wörld",
+            InsertionMode::Auto,
+            InsertionOptions {
+                speed,
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(*backend.typed.borrow(), vec!["héll"]);
+        assert_eq!(
+            backend.clipboard_writes(),
+            vec!["o wörld".to_string(), "previous clipboard".to_string()]
+        );
     }
 
     #[test]
-    fn utf16_chunking_preserves_non_bmp_characters() {
-        let text = format!("{}{}{}", "a".repeat(UNICODE_CHUNK_SIZE - 1), "😀😀", "𐍈");
-        let chunks = utf16_chunks_preserving_char_boundaries(&text, UNICODE_CHUNK_SIZE);
+    fn throttled_typing_never_separates_accents_from_their_letters() {
+        let speed = TypingSpeed {
+            chars_per_second: 10_000,
+            chunk_chars: 4,
+        };
+        let backend = MockBackend::default();
 
-        assert!(!chunks.is_empty());
-        assert!(chunks.iter().all(|chunk| chunk.len() <= UNICODE_CHUNK_SIZE));
+        let result = insert_text_with_backend(
+            &backend,
+            "Zoe\u{308} Bronte\u{308}",
+            InsertionMode::Auto,
+            InsertionOptions {
+                speed,
+                ..Default::default()
+            },
+        );
 
-        let flattened: Vec<u16> = chunks.into_iter().flatten().collect();
-        let reconstructed = String::from_utf16(&flattened).expect("valid UTF-16 chunks");
-        assert_eq!(reconstructed, text);
+        assert!(result.is_ok());
+        assert_eq!(
+            *backend.typed.borrow(),
+            vec!["Zoe\u{308} ", "Bron", "te\u{308}"]
+        );
     }
 
     #[test]
-    fn utf16_chunking_never_splits_surrogate_pairs() {
-        let text = format!("{}{}", "a".repeat(UNICODE_CHUNK_SIZE - 1), "😀😀😀");
-        let chunks = utf16_chunks_preserving_char_boundaries(&text, UNICODE_CHUNK_SIZE);
-
-        assert!(chunks.iter().all(|chunk| {
-            chunk
-                .last()
-                .is_none_or(|unit| !(0xD800..=0xDBFF).contains(unit))
-        }));
+    fn empty_text_is_noop() {
+        let backend = MockBackend::default();
+
+        let result = insert_text_with_backend(
+            &backend,
+            "",
+            InsertionMode::Auto,
+            InsertionOptions::default(),
+        );
+
+        assert!(result.is_ok());
+        assert!(backend.call_order().is_empty());
     }
 }
@@ -0,0 +1,185 @@
+use serde::Serialize;
+use tauri::State;
+use tracing::debug;
+
+use crate::history_store::HistoryStore;
+
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptDiff {
+    pub raw_text: String,
+    pub cleaned_text: String,
+    pub segments: Vec<DiffSegment>,
+}
+
+pub fn diff_words(raw: &str, cleaned: &str) -> Vec<DiffSegment> {
+    let raw_words = raw.split_whitespace().collect::<Vec<_>>();
+    let cleaned_words = cleaned.split_whitespace().collect::<Vec<_>>();
+
+    let mut segments = Vec::new();
+    for (op, word) in word_ops(&raw_words, &cleaned_words) {
+        match segments.last_mut() {
+            Some(DiffSegment { op: last_op, text }) if *last_op == op => {
+                text.push(' ');
+                text.push_str(word);
+            }
+            _ => segments.push(DiffSegment {
+                op,
+                text: word.to_string(),
+            }),
+        }
+    }
+
+    segments
+}
+
+fn word_ops<'a>(raw: &[&'a str], cleaned: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    let prefix = raw
+        .iter()
+        .zip(cleaned)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = raw[prefix..]
+        .iter()
+        .rev()
+        .zip(cleaned[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let raw_middle = &raw[prefix..raw.len() - suffix];
+    let cleaned_middle = &cleaned[prefix..cleaned.len() - suffix];
+
+    let mut ops = raw[..prefix]
+        .iter()
+        .map(|word| (DiffOp::Equal, *word))
+        .collect::<Vec<_>>();
+    if raw_middle.len().saturating_mul(cleaned_middle.len()) > MAX_DIFF_CELLS {
+        debug!(
+            raw_words = raw_middle.len(),
+            cleaned_words = cleaned_middle.len(),
+            "transcript diff too large; reporting a full replacement"
+        );
+        ops.extend(raw_middle.iter().map(|word| (DiffOp::Delete, *word)));
+        ops.extend(cleaned_middle.iter().map(|word| (DiffOp::Insert, *word)));
+    } else {
+        ops.extend(lcs_ops(raw_middle, cleaned_middle));
+    }
+    ops.extend(
+        raw[raw.len() - suffix..]
+            .iter()
+            .map(|word| (DiffOp::Equal, *word)),
+    );
+
+    ops
+}
+
+fn lcs_ops<'a>(raw: &[&'a str], cleaned: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    let width = cleaned.len() + 1;
+    // lengths[i * width + j] is the LCS length of raw[i..] and cleaned[j..].
+    let mut lengths = vec![0_u32; (raw.len() + 1) * width];
+    for i in (0..raw.len()).rev() {
+        for j in (0..cleaned.len()).rev() {
+            lengths[i * width + j] = if raw[i] == cleaned[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(raw.len() + cleaned.len());
+    let (mut i, mut j) = (0, 0);
+    while i < raw.len() && j < cleaned.len() {
+        if raw[i] == cleaned[j] {
+            ops.push((DiffOp::Equal, raw[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            ops.push((DiffOp::Delete, raw[i]));
+            i += 1;
+        } else {
+            ops.push((DiffOp::Insert, cleaned[j]));
+            j += 1;
+        }
+    }
+    ops.extend(raw[i..].iter().map(|word| (DiffOp::Delete, *word)));
+    ops.extend(cleaned[j..].iter().map(|word| (DiffOp::Insert, *word)));
+
+    ops
+}
+
+#[tauri::command]
+pub fn get_transcript_diff(
+    history_store: State<'_, HistoryStore>,
+    id: String,
+) -> Result<Option<TranscriptDiff>, String> {
+    debug!(id = %id, "transcript diff requested");
+    let entry = history_store
+        .get_entry(&id)?
+        .ok_or_else(|| format!("History entry `{id}` was not found"))?;
+
+    Ok(entry.raw_text.map(|raw_text| TranscriptDiff {
+        segments: diff_words(&raw_text, &entry.text),
+        raw_text,
+        cleaned_text: entry.text,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_words, DiffOp, DiffSegment};
+
+    fn segment(op: DiffOp, text: &str) -> DiffSegment {
+        DiffSegment {
+            op,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_words_groups_removed_filler_and_rewrites() {
+        assert_eq!(
+            diff_words(
+                "um so I think we should uh ship it friday",
+                "I think we should ship it on Friday."
+            ),
+            vec![
+                segment(DiffOp::Delete, "um so"),
+                segment(DiffOp::Equal, "I think we should"),
+                segment(DiffOp::Delete, "uh"),
+                segment(DiffOp::Equal, "ship it"),
+                segment(DiffOp::Delete, "friday"),
+                segment(DiffOp::Insert, "on Friday."),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_words_handles_identical_and_empty_text() {
+        assert_eq!(
+            diff_words("same  words", "same words"),
+            vec![segment(DiffOp::Equal, "same words")]
+        );
+        assert_eq!(
+            diff_words("", "added"),
+            vec![segment(DiffOp::Insert, "added")]
+        );
+        assert!(diff_words("", " ").is_empty());
+    }
+}
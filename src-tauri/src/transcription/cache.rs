@@ -0,0 +1,203 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use futures_util::future::{BoxFuture, Shared};
+use sha2::{Digest, Sha256};
+
+use super::{TranscriptionError, TranscriptionOptions, TranscriptionResult};
+
+pub(super) type TranscriptionKey = [u8; 32];
+pub(super) type InFlightTranscription =
+    Shared<BoxFuture<'static, Result<TranscriptionResult, TranscriptionError>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptCacheConfig {
+    pub capacity: usize,
+    pub ttl: Duration,
+}
+
+impl TranscriptCacheConfig {
+    pub const DISABLED: Self = Self {
+        capacity: 0,
+        ttl: Duration::ZERO,
+    };
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0 && !self.ttl.is_zero()
+    }
+}
+
+struct CachedTranscript {
+    key: TranscriptionKey,
+    result: TranscriptionResult,
+    stored_at: Instant,
+}
+
+pub(super) struct TranscriptCache {
+    config: TranscriptCacheConfig,
+    entries: VecDeque<CachedTranscript>,
+    in_flight: HashMap<TranscriptionKey, InFlightTranscription>,
+}
+
+impl TranscriptCache {
+    pub(super) fn new(config: TranscriptCacheConfig) -> Self {
+        Self {
+            config,
+            entries: VecDeque::new(),
+            in_flight: HashMap::new(),
+        }
+    }
+
+    pub(super) fn config(&self) -> TranscriptCacheConfig {
+        self.config
+    }
+
+    pub(super) fn configure(&mut self, config: TranscriptCacheConfig) {
+        self.config = config;
+        if !config.is_enabled() {
+            self.entries.clear();
+            self.in_flight.clear();
+            return;
+        }
+        self.evict(Instant::now());
+    }
+
+    pub(super) fn get(
+        &mut self,
+        key: &TranscriptionKey,
+        now: Instant,
+    ) -> Option<TranscriptionResult> {
+        self.evict(now);
+        self.entries
+            .iter()
+            .find(|entry| entry.key == *key)
+            .map(|entry| entry.result.clone())
+    }
+
+    pub(super) fn insert(
+        &mut self,
+        key: TranscriptionKey,
+        result: TranscriptionResult,
+        now: Instant,
+    ) {
+        if !self.config.is_enabled() {
+            return;
+        }
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.push_back(CachedTranscript {
+            key,
+            result,
+            stored_at: now,
+        });
+        self.evict(now);
+    }
+
+    pub(super) fn in_flight(&self, key: &TranscriptionKey) -> Option<InFlightTranscription> {
+        self.in_flight.get(key).cloned()
+    }
+
+    pub(super) fn begin(&mut self, key: TranscriptionKey, request: InFlightTranscription) {
+        self.in_flight.insert(key, request);
+    }
+
+    pub(super) fn finish(&mut self, key: &TranscriptionKey) {
+        self.in_flight.remove(key);
+    }
+
+    fn evict(&mut self, now: Instant) {
+        let ttl = self.config.ttl;
+        self.entries
+            .retain(|entry| now.saturating_duration_since(entry.stored_at) < ttl);
+        while self.entries.len() > self.config.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+pub(super) fn transcription_key(
+    provider: &str,
+    audio_data: &[u8],
+    options: &TranscriptionOptions,
+) -> TranscriptionKey {
+    let mut hasher = Sha256::new();
+    for field in [
+        Some(provider),
+        options.language.as_deref(),
+        options.prompt.as_deref(),
+        options.context_hint.as_deref(),
+    ] {
+        match field {
+            Some(value) => {
+                hasher.update([1]);
+                hasher.update((value.len() as u64).to_le_bytes());
+                hasher.update(value.as_bytes());
+            }
+            None => hasher.update([0]),
+        }
+    }
+    hasher.update([u8::from(options.diarize), u8::from(options.timestamps)]);
+    hasher.update(audio_data);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{transcription_key, TranscriptCache, TranscriptCacheConfig};
+    use crate::transcription::{TranscriptionOptions, TranscriptionResult};
+
+    fn result(text: &str) -> TranscriptionResult {
+        TranscriptionResult {
+            text: text.to_string(),
+            ..TranscriptionResult::default()
+        }
+    }
+
+    #[test]
+    fn key_changes_with_audio_and_options() {
+        let options = TranscriptionOptions::default();
+        let base = transcription_key("openai", &[1, 2, 3], &options);
+
+        assert_eq!(base, transcription_key("openai", &[1, 2, 3], &options));
+        assert_ne!(base, transcription_key("openai", &[1, 2, 4], &options));
+        assert_ne!(base, transcription_key("mock", &[1, 2, 3], &options));
+        assert_ne!(
+            base,
+            transcription_key(
+                "openai",
+                &[1, 2, 3],
+                &TranscriptionOptions {
+                    language: Some("en".to_string()),
+                    ..TranscriptionOptions::default()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn entries_expire_after_ttl_and_respect_capacity() {
+        let mut cache = TranscriptCache::new(TranscriptCacheConfig {
+            capacity: 2,
+            ttl: Duration::from_secs(60),
+        });
+        let now = Instant::now();
+
+        cache.insert([1; 32], result("one"), now);
+        cache.insert([2; 32], result("two"), now);
+        cache.insert([3; 32], result("three"), now);
+        assert!(cache.get(&[1; 32], now).is_none());
+        assert_eq!(
+            cache.get(&[3; 32], now).map(|result| result.text),
+            Some("three".to_string())
+        );
+
+        assert!(cache.get(&[3; 32], now + Duration::from_secs(61)).is_none());
+
+        cache.configure(TranscriptCacheConfig::DISABLED);
+        cache.insert([4; 32], result("four"), now);
+        assert!(cache.get(&[4; 32], now).is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
 use bytes::Bytes;
-use reqwest::{multipart, Client, StatusCode};
+use reqwest::{multipart, StatusCode};
 use serde::Deserialize;
 use serde_json::Value;
 use std::time::Duration;
@@ -13,8 +13,9 @@ use crate::{
 };
 
 use super::{
-    normalize_transcript_text, TranscriptionError, TranscriptionOptions, TranscriptionProvider,
-    TranscriptionResult,
+    network::{AccountHeaders, ConfigurableClient},
+    normalize_transcript_text, NetworkConfig, TranscriptionError, TranscriptionOptions,
+    TranscriptionProvider, TranscriptionResult,
 };
 
 const DEFAULT_CHATGPT_ENDPOINT: &str = "https://chatgpt.com/backend-api/transcribe";
@@ -62,7 +63,7 @@ impl ChatGptTranscriptionConfig {
 
 #[derive(Debug, Clone)]
 pub struct ChatGptTranscriptionProvider {
-    client: Client,
+    client: ConfigurableClient,
     config: ChatGptTranscriptionConfig,
     auth_store: AuthStore,
 }
@@ -144,8 +145,8 @@ impl ChatGptTranscriptionProvider {
         })
     }
 
-    fn build_form(&self, audio_data: Vec<u8>) -> Result<multipart::Form, TranscriptionError> {
-        let encoded_audio = BASE64_STANDARD.encode(Bytes::from(audio_data));
+    fn build_form(&self, audio_data: Bytes) -> Result<multipart::Form, TranscriptionError> {
+        let encoded_audio = BASE64_STANDARD.encode(&audio_data);
         let audio_len = u64::try_from(encoded_audio.len())
             .map_err(|_| TranscriptionError::Provider("Audio upload is too large".to_string()))?;
 
@@ -166,9 +167,25 @@ impl TranscriptionProvider for ChatGptTranscriptionProvider {
         "chatgpt-oauth"
     }
 
+    async fn warm_up(&self) -> Result<(), TranscriptionError> {
+        let response = self
+            .client
+            .get()
+            .head(&self.config.endpoint)
+            .send()
+            .await
+            .map_err(|error| TranscriptionError::Network(error.to_string()))?;
+        debug!(status = %response.status(), "ChatGPT transcription connection warmed");
+        Ok(())
+    }
+
+    fn configure_network(&self, network: &NetworkConfig) -> Result<(), String> {
+        self.client.configure(network)
+    }
+
     async fn transcribe(
         &self,
-        audio_data: Vec<u8>,
+        audio_data: Bytes,
         options: TranscriptionOptions,
     ) -> Result<TranscriptionResult, TranscriptionError> {
         let TranscriptionOptions {
@@ -176,6 +193,8 @@ impl TranscriptionProvider for ChatGptTranscriptionProvider {
             language: _,
             prompt: _,
             context_hint: _,
+            diarize: _,
+            timestamps: _,
         } = options;
 
         let auth = self.auth_context().await?;
@@ -184,6 +203,7 @@ impl TranscriptionProvider for ChatGptTranscriptionProvider {
         info!(endpoint = %self.config.endpoint, "starting ChatGPT transcription request");
         let response = self
             .client
+            .get()
             .post(&self.config.endpoint)
             .bearer_auth(auth.access_token)
             .header(CHATGPT_ACCOUNT_HEADER, auth.account_id)
@@ -216,6 +236,8 @@ impl TranscriptionProvider for ChatGptTranscriptionProvider {
             language: None,
             duration_secs: None,
             confidence: None,
+            segments: Vec::new(),
+            model: None,
         })
     }
 }
@@ -316,11 +338,11 @@ fn read_u64_env(name: &str) -> Option<u64> {
         .and_then(|value| value.parse::<u64>().ok())
 }
 
-fn build_client(config: &ChatGptTranscriptionConfig) -> Client {
-    Client::builder()
-        .timeout(Duration::from_secs(config.request_timeout_secs.max(1)))
-        .build()
-        .expect("ChatGPT client construction should succeed")
+fn build_client(config: &ChatGptTranscriptionConfig) -> ConfigurableClient {
+    ConfigurableClient::new(
+        Duration::from_secs(config.request_timeout_secs.max(1)),
+        AccountHeaders::None,
+    )
 }
 
 #[cfg(test)]
@@ -378,7 +400,10 @@ mod tests {
 
         let provider = provider_for_test(&server, auth_store);
         let result = provider
-            .transcribe(vec![1, 2, 3], TranscriptionOptions::default())
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
             .await
             .expect("transcription should succeed");
 
@@ -410,7 +435,10 @@ mod tests {
 
         let provider = provider_for_test(&server, auth_store);
         let error = provider
-            .transcribe(vec![1, 2, 3], TranscriptionOptions::default())
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
             .await
             .expect_err("request should fail");
 
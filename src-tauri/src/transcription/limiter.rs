@@ -0,0 +1,317 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tokio::sync::Notify;
+use tracing::{debug, info};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimitConfig {
+    pub max_concurrent: usize,
+    pub requests_per_minute: usize,
+}
+
+impl RequestLimitConfig {
+    pub const UNLIMITED: Self = Self {
+        max_concurrent: 0,
+        requests_per_minute: 0,
+    };
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuePosition {
+    pub request_id: u64,
+    pub position: usize,
+}
+
+pub type QueuePositionCallback = Arc<dyn Fn(QueuePosition) + Send + Sync>;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Admission {
+    Now,
+    Wait(Option<Duration>),
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    config: RequestLimitConfig,
+    next_id: u64,
+    active: usize,
+    waiting: VecDeque<u64>,
+    recent_starts: VecDeque<Instant>,
+}
+
+impl LimiterState {
+    fn new(config: RequestLimitConfig) -> Self {
+        Self {
+            config,
+            next_id: 1,
+            active: 0,
+            waiting: VecDeque::new(),
+            recent_starts: VecDeque::new(),
+        }
+    }
+
+    fn enqueue(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.waiting.push_back(id);
+        id
+    }
+
+    fn position(&self, id: u64) -> usize {
+        self.waiting
+            .iter()
+            .position(|waiting| *waiting == id)
+            .map_or(0, |index| index + 1)
+    }
+
+    fn try_admit(&mut self, id: u64, now: Instant) -> Admission {
+        while self
+            .recent_starts
+            .front()
+            .is_some_and(|started| now.duration_since(*started) >= RATE_WINDOW)
+        {
+            self.recent_starts.pop_front();
+        }
+
+        if self.waiting.front() != Some(&id) {
+            return Admission::Wait(None);
+        }
+        if self.config.max_concurrent > 0 && self.active >= self.config.max_concurrent {
+            return Admission::Wait(None);
+        }
+        if self.config.requests_per_minute > 0
+            && self.recent_starts.len() >= self.config.requests_per_minute
+        {
+            let oldest = self.recent_starts[0];
+            return Admission::Wait(Some(RATE_WINDOW.saturating_sub(now - oldest)));
+        }
+
+        self.waiting.pop_front();
+        self.active += 1;
+        self.recent_starts.push_back(now);
+        Admission::Now
+    }
+}
+
+pub(super) struct RequestLimiter {
+    state: Mutex<LimiterState>,
+    notify: Notify,
+    observer: Mutex<Option<QueuePositionCallback>>,
+}
+
+impl fmt::Debug for RequestLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestLimiter")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl RequestLimiter {
+    pub(super) fn new(config: RequestLimitConfig) -> Self {
+        Self {
+            state: Mutex::new(LimiterState::new(config)),
+            notify: Notify::new(),
+            observer: Mutex::new(None),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(super) fn configure(&self, config: RequestLimitConfig) {
+        let mut state = self.state();
+        if state.config == config {
+            return;
+        }
+        info!(
+            max_concurrent = config.max_concurrent,
+            requests_per_minute = config.requests_per_minute,
+            "transcription request limits configured"
+        );
+        state.config = config;
+        drop(state);
+        self.notify.notify_waiters();
+    }
+
+    pub(super) fn set_observer(&self, observer: QueuePositionCallback) {
+        *self.observer.lock().unwrap_or_else(PoisonError::into_inner) = Some(observer);
+    }
+
+    fn report(&self, position: QueuePosition) {
+        let observer = self
+            .observer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(observer) = observer {
+            observer(position);
+        }
+    }
+
+    pub(super) async fn acquire(self: &Arc<Self>) -> RequestPermit {
+        let request_id = self.state().enqueue();
+        let mut waiting = WaitingRequest {
+            limiter: self,
+            request_id,
+            admitted: false,
+        };
+        let mut reported_position = None;
+
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let (admission, position) = {
+                let mut state = self.state();
+                let admission = state.try_admit(request_id, Instant::now());
+                (admission, state.position(request_id))
+            };
+            let delay = match admission {
+                Admission::Now => {
+                    waiting.admitted = true;
+                    // The next request in line may be able to start too.
+                    self.notify.notify_waiters();
+                    if reported_position.is_some() {
+                        self.report(QueuePosition {
+                            request_id,
+                            position: 0,
+                        });
+                    }
+                    return RequestPermit {
+                        limiter: Arc::clone(self),
+                    };
+                }
+                Admission::Wait(delay) => delay,
+            };
+
+            if reported_position != Some(position) {
+                debug!(request_id, position, ?delay, "transcription request queued");
+                self.report(QueuePosition {
+                    request_id,
+                    position,
+                });
+                reported_position = Some(position);
+            }
+            match delay {
+                Some(delay) => {
+                    tokio::select! {
+                        _ = &mut notified => {}
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+                }
+                None => notified.await,
+            }
+        }
+    }
+}
+
+struct WaitingRequest<'a> {
+    limiter: &'a RequestLimiter,
+    request_id: u64,
+    admitted: bool,
+}
+
+impl Drop for WaitingRequest<'_> {
+    fn drop(&mut self) {
+        if self.admitted {
+            return;
+        }
+        self.limiter
+            .state()
+            .waiting
+            .retain(|waiting| *waiting != self.request_id);
+        self.limiter.notify.notify_waiters();
+    }
+}
+
+pub(super) struct RequestPermit {
+    limiter: Arc<RequestLimiter>,
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        {
+            let mut state = self.limiter.state();
+            state.active = state.active.saturating_sub(1);
+        }
+        self.limiter.notify.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use super::{
+        Admission, LimiterState, QueuePosition, RequestLimitConfig, RequestLimiter, RATE_WINDOW,
+    };
+
+    #[test]
+    fn rate_window_delays_requests_past_the_per_minute_cap() {
+        let mut state = LimiterState::new(RequestLimitConfig {
+            max_concurrent: 0,
+            requests_per_minute: 2,
+        });
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            let id = state.enqueue();
+            assert_eq!(state.try_admit(id, start), Admission::Now);
+        }
+        let third = state.enqueue();
+        assert_eq!(
+            state.try_admit(third, start + Duration::from_secs(20)),
+            Admission::Wait(Some(Duration::from_secs(40)))
+        );
+        assert_eq!(state.try_admit(third, start + RATE_WINDOW), Admission::Now);
+    }
+
+    #[tokio::test]
+    async fn queued_requests_wait_for_a_free_slot_and_report_their_position() {
+        let limiter = Arc::new(RequestLimiter::new(RequestLimitConfig {
+            max_concurrent: 1,
+            requests_per_minute: 0,
+        }));
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&reports);
+        limiter.set_observer(Arc::new(move |position: QueuePosition| {
+            recorded
+                .lock()
+                .expect("reports lock")
+                .push(position.position);
+        }));
+
+        let first = limiter.acquire().await;
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            tokio::spawn(async move {
+                let _permit = limiter.acquire().await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+        assert_eq!(*reports.lock().expect("reports lock"), vec![1]);
+
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("queued request should start once the slot frees")
+            .expect("waiter task should not panic");
+        assert_eq!(*reports.lock().expect("reports lock"), vec![1, 0]);
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use tracing::{debug, info};
+
+use super::{TranscriptionError, TranscriptionOptions, TranscriptionProvider, TranscriptionResult};
+
+const DEFAULT_MOCK_TEXT: &str = "This is a mock transcription.";
+const DEFAULT_MOCK_LATENCY_MS: u64 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockFailure {
+    Authentication,
+    RateLimited,
+    Network,
+    InvalidResponse,
+    Provider,
+}
+
+impl MockFailure {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auth" | "authentication" => Some(Self::Authentication),
+            "rate_limited" | "rate-limited" => Some(Self::RateLimited),
+            "network" => Some(Self::Network),
+            "invalid_response" | "invalid-response" => Some(Self::InvalidResponse),
+            "provider" => Some(Self::Provider),
+            _ => None,
+        }
+    }
+
+    fn to_error(self) -> TranscriptionError {
+        let message = "injected by the mock transcription provider".to_string();
+        match self {
+            Self::Authentication => TranscriptionError::Authentication(message),
+            Self::RateLimited => TranscriptionError::RateLimited(message),
+            Self::Network => TranscriptionError::Network(message),
+            Self::InvalidResponse => TranscriptionError::InvalidResponse(message),
+            Self::Provider => TranscriptionError::Provider(message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockTranscriptionConfig {
+    pub text: String,
+    pub latency_ms: u64,
+    pub failure: Option<MockFailure>,
+    pub fail_every: u64,
+}
+
+impl Default for MockTranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            text: DEFAULT_MOCK_TEXT.to_string(),
+            latency_ms: DEFAULT_MOCK_LATENCY_MS,
+            failure: None,
+            fail_every: 1,
+        }
+    }
+}
+
+impl MockTranscriptionConfig {
+    pub fn from_env() -> Option<Self> {
+        let enabled = read_non_empty_env("BUZZ_MOCK_TRANSCRIPTION")
+            .is_some_and(|value| value == "1" || value.eq_ignore_ascii_case("true"));
+        if !enabled {
+            return None;
+        }
+
+        let mut config = Self::default();
+        if let Some(text) = read_non_empty_env("BUZZ_MOCK_TRANSCRIPTION_TEXT") {
+            config.text = text;
+        }
+        if let Some(latency_ms) = read_u64_env("BUZZ_MOCK_TRANSCRIPTION_LATENCY_MS") {
+            config.latency_ms = latency_ms;
+        }
+        config.failure = read_non_empty_env("BUZZ_MOCK_TRANSCRIPTION_FAILURE")
+            .and_then(|value| MockFailure::parse(&value));
+        if let Some(fail_every) = read_u64_env("BUZZ_MOCK_TRANSCRIPTION_FAIL_EVERY") {
+            config.fail_every = fail_every.max(1);
+        }
+
+        info!(
+            latency_ms = config.latency_ms,
+            failure = ?config.failure,
+            fail_every = config.fail_every,
+            "mock transcription provider enabled"
+        );
+        Some(config)
+    }
+}
+
+#[derive(Debug)]
+pub struct MockTranscriptionProvider {
+    config: MockTranscriptionConfig,
+    request_count: AtomicU64,
+}
+
+impl MockTranscriptionProvider {
+    pub fn new(config: MockTranscriptionConfig) -> Self {
+        Self {
+            config,
+            request_count: AtomicU64::new(0),
+        }
+    }
+
+    fn should_fail(&self, request_number: u64) -> Option<MockFailure> {
+        self.config
+            .failure
+            .filter(|_| request_number.is_multiple_of(self.config.fail_every.max(1)))
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for MockTranscriptionProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn transcribe(
+        &self,
+        audio_data: Bytes,
+        options: TranscriptionOptions,
+    ) -> Result<TranscriptionResult, TranscriptionError> {
+        let request_number = self.request_count.fetch_add(1, Ordering::SeqCst) + 1;
+        debug!(
+            request_number,
+            audio_bytes = audio_data.len(),
+            latency_ms = self.config.latency_ms,
+            "mock transcription requested"
+        );
+        tokio::time::sleep(Duration::from_millis(self.config.latency_ms)).await;
+
+        if let Some(failure) = self.should_fail(request_number) {
+            return Err(failure.to_error());
+        }
+
+        if let Some(on_delta) = options.on_delta.as_ref() {
+            on_delta(self.config.text.clone());
+        }
+        Ok(TranscriptionResult {
+            text: self.config.text.clone(),
+            language: options.language.or_else(|| Some("en".to_string())),
+            duration_secs: None,
+            confidence: Some(1.0),
+            segments: Vec::new(),
+            model: None,
+        })
+    }
+}
+
+fn read_non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().and_then(|value| {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    })
+}
+
+fn read_u64_env(name: &str) -> Option<u64> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .and_then(|value| value.parse::<u64>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_canned_text_and_injects_failures_on_schedule() {
+        let provider = MockTranscriptionProvider::new(MockTranscriptionConfig {
+            text: "canned".to_string(),
+            latency_ms: 0,
+            failure: Some(MockFailure::Network),
+            fail_every: 2,
+        });
+
+        let first = provider
+            .transcribe(Bytes::from_static(&[1]), TranscriptionOptions::default())
+            .await
+            .expect("first request should succeed");
+        let second = provider
+            .transcribe(Bytes::from_static(&[1]), TranscriptionOptions::default())
+            .await
+            .expect_err("second request should fail");
+
+        assert_eq!(first.text, "canned");
+        assert_eq!(first.language.as_deref(), Some("en"));
+        assert!(matches!(second, TranscriptionError::Network(_)));
+        assert_eq!(
+            MockFailure::parse("Rate-Limited"),
+            Some(MockFailure::RateLimited)
+        );
+        assert_eq!(MockFailure::parse("nope"), None);
+    }
+}
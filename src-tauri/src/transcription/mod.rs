@@ -1,10 +1,25 @@
+mod cache;
 pub mod chatgpt;
+mod limiter;
+pub mod mock;
+mod network;
 pub mod openai;
 pub mod realtime;
 
-use std::{fmt, sync::Arc};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use async_trait::async_trait;
+use bytes::Bytes;
+pub use cache::TranscriptCacheConfig;
+use cache::{transcription_key, TranscriptCache};
+use futures_util::FutureExt;
+use limiter::RequestLimiter;
+pub use limiter::{QueuePosition, QueuePositionCallback, RequestLimitConfig};
+pub use network::{build_probe_client, NetworkConfig, ProxyConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
@@ -19,6 +34,10 @@ pub struct TranscriptionOptions {
     pub prompt: Option<String>,
     #[serde(default)]
     pub context_hint: Option<String>,
+    #[serde(default)]
+    pub diarize: bool,
+    #[serde(default)]
+    pub timestamps: bool,
     #[serde(skip, default)]
     pub on_delta: Option<TranscriptionDeltaCallback>,
 }
@@ -29,6 +48,8 @@ impl fmt::Debug for TranscriptionOptions {
             .field("language", &self.language)
             .field("prompt", &self.prompt)
             .field("context_hint", &self.context_hint)
+            .field("diarize", &self.diarize)
+            .field("timestamps", &self.timestamps)
             .field("on_delta", &self.on_delta.is_some())
             .finish()
     }
@@ -44,6 +65,20 @@ pub struct TranscriptionResult {
     pub duration_secs: Option<f64>,
     #[serde(default)]
     pub confidence: Option<f32>,
+    #[serde(default)]
+    pub segments: Vec<TranscriptionSegment>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSegment {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub text: String,
+    #[serde(default)]
+    pub speaker: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,14 +112,26 @@ pub trait TranscriptionProvider: Send + Sync {
 
     async fn transcribe(
         &self,
-        audio_data: Vec<u8>,
+        audio_data: Bytes,
         options: TranscriptionOptions,
     ) -> Result<TranscriptionResult, TranscriptionError>;
+
+    async fn warm_up(&self) -> Result<(), TranscriptionError> {
+        Ok(())
+    }
+
+    fn configure_network(&self, _network: &NetworkConfig) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn configure_model(&self, _model: Option<&str>) {}
 }
 
 #[derive(Clone)]
 pub struct TranscriptionOrchestrator {
     active_provider: Arc<dyn TranscriptionProvider>,
+    cache: Arc<Mutex<TranscriptCache>>,
+    limiter: Arc<RequestLimiter>,
 }
 
 impl fmt::Debug for TranscriptionOrchestrator {
@@ -101,12 +148,57 @@ impl TranscriptionOrchestrator {
             provider = active_provider.name(),
             "transcription orchestrator initialized"
         );
-        Self { active_provider }
+        Self {
+            active_provider,
+            cache: Arc::new(Mutex::new(TranscriptCache::new(
+                TranscriptCacheConfig::DISABLED,
+            ))),
+            limiter: Arc::new(RequestLimiter::new(RequestLimitConfig::UNLIMITED)),
+        }
+    }
+
+    pub fn provider_name(&self) -> &'static str {
+        self.active_provider.name()
+    }
+
+    pub async fn warm_up(&self) -> Result<(), TranscriptionError> {
+        self.active_provider.warm_up().await
+    }
+
+    pub fn configure_limits(&self, config: RequestLimitConfig) {
+        self.limiter.configure(config);
+    }
+
+    pub fn set_queue_observer(&self, observer: QueuePositionCallback) {
+        self.limiter.set_observer(observer);
+    }
+
+    pub fn configure_network(&self, network: &NetworkConfig) -> Result<(), String> {
+        self.active_provider.configure_network(network)
+    }
+
+    pub fn configure_model(&self, model: Option<&str>) {
+        self.active_provider.configure_model(model);
+    }
+
+    pub fn configure_cache(&self, config: TranscriptCacheConfig) {
+        let Ok(mut cache) = self.cache.lock() else {
+            warn!("transcript cache lock is poisoned; leaving cache settings unchanged");
+            return;
+        };
+        if cache.config() != config {
+            info!(
+                capacity = config.capacity,
+                ttl_secs = config.ttl.as_secs(),
+                "transcript cache configured"
+            );
+            cache.configure(config);
+        }
     }
 
     pub async fn transcribe(
         &self,
-        audio_data: Vec<u8>,
+        audio_data: Bytes,
         options: TranscriptionOptions,
     ) -> Result<TranscriptionResult, TranscriptionError> {
         if audio_data.is_empty() {
@@ -116,34 +208,92 @@ impl TranscriptionOrchestrator {
             ));
         }
 
-        debug!(
-            provider = self.active_provider.name(),
-            audio_bytes = audio_data.len(),
-            "dispatching transcription request"
-        );
-        let mut result = self
-            .active_provider
-            .transcribe(audio_data, options)
-            .await
-            .map_err(|error| {
-                error!(
-                    provider = self.active_provider.name(),
-                    error = %error,
-                    "transcription provider call failed"
+        let provider = Arc::clone(&self.active_provider);
+        let cache_enabled = self
+            .cache
+            .lock()
+            .is_ok_and(|cache| cache.config().is_enabled());
+        let limiter = Arc::clone(&self.limiter);
+        if !cache_enabled {
+            return request_transcription(provider, limiter, audio_data, options).await;
+        }
+
+        let key = transcription_key(provider.name(), &audio_data, &options);
+
+        let request = {
+            let Ok(mut cache) = self.cache.lock() else {
+                return request_transcription(provider, limiter, audio_data, options).await;
+            };
+            if let Some(result) = cache.get(&key, Instant::now()) {
+                info!(
+                    provider = provider.name(),
+                    transcript_chars = result.text.chars().count(),
+                    "transcription served from cache"
                 );
-                error
-            })?;
-        result.text = normalize_transcript_text(&result.text);
-        info!(
-            provider = self.active_provider.name(),
-            transcript_chars = result.text.chars().count(),
-            language = ?result.language,
-            "transcription request completed"
-        );
-        Ok(result)
+                return Ok(result);
+            }
+            match cache.in_flight(&key) {
+                Some(request) => {
+                    info!(
+                        provider = provider.name(),
+                        "joining identical in-flight transcription request"
+                    );
+                    request
+                }
+                None => {
+                    let request = request_transcription(provider, limiter, audio_data, options)
+                        .boxed()
+                        .shared();
+                    cache.begin(key, request.clone());
+                    request
+                }
+            }
+        };
+
+        let result = request.await;
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.finish(&key);
+            if let Ok(transcription) = &result {
+                cache.insert(key, transcription.clone(), Instant::now());
+            }
+        }
+        result
     }
 }
 
+async fn request_transcription(
+    provider: Arc<dyn TranscriptionProvider>,
+    limiter: Arc<RequestLimiter>,
+    audio_data: Bytes,
+    options: TranscriptionOptions,
+) -> Result<TranscriptionResult, TranscriptionError> {
+    let _permit = limiter.acquire().await;
+    debug!(
+        provider = provider.name(),
+        audio_bytes = audio_data.len(),
+        "dispatching transcription request"
+    );
+    let mut result = provider
+        .transcribe(audio_data, options)
+        .await
+        .map_err(|error| {
+            error!(
+                provider = provider.name(),
+                error = %error,
+                "transcription provider call failed"
+            );
+            error
+        })?;
+    result.text = normalize_transcript_text(&result.text);
+    info!(
+        provider = provider.name(),
+        transcript_chars = result.text.chars().count(),
+        language = ?result.language,
+        "transcription request completed"
+    );
+    Ok(result)
+}
+
 pub(crate) fn normalize_transcript_text(raw_text: &str) -> String {
     raw_text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
@@ -156,6 +306,7 @@ mod tests {
     #[derive(Debug)]
     struct StubProvider {
         captured_audio_len: Mutex<Option<usize>>,
+        call_count: Mutex<usize>,
         response_text: String,
     }
 
@@ -167,7 +318,7 @@ mod tests {
 
         async fn transcribe(
             &self,
-            audio_data: Vec<u8>,
+            audio_data: Bytes,
             _options: TranscriptionOptions,
         ) -> Result<TranscriptionResult, TranscriptionError> {
             let mut guard = self
@@ -175,12 +326,18 @@ mod tests {
                 .lock()
                 .expect("stub provider lock should not be poisoned");
             *guard = Some(audio_data.len());
+            *self
+                .call_count
+                .lock()
+                .expect("stub provider lock should not be poisoned") += 1;
 
             Ok(TranscriptionResult {
                 text: self.response_text.clone(),
                 language: Some("en".to_string()),
                 duration_secs: Some(1.5),
                 confidence: Some(0.8),
+                segments: Vec::new(),
+                model: None,
             })
         }
     }
@@ -189,13 +346,14 @@ mod tests {
     async fn orchestrator_normalizes_whitespace_and_forwards_audio() {
         let provider = Arc::new(StubProvider {
             captured_audio_len: Mutex::new(None),
+            call_count: Mutex::new(0),
             response_text: "  hello    world\n\nfrom   provider ".to_string(),
         });
         let orchestrator = TranscriptionOrchestrator::new(provider.clone());
 
         let result = orchestrator
             .transcribe(
-                vec![1, 2, 3, 4],
+                Bytes::from_static(&[1, 2, 3, 4]),
                 TranscriptionOptions {
                     language: Some("en".to_string()),
                     prompt: Some("dictation".to_string()),
@@ -222,12 +380,13 @@ mod tests {
     async fn orchestrator_rejects_empty_audio_payload() {
         let provider = Arc::new(StubProvider {
             captured_audio_len: Mutex::new(None),
+            call_count: Mutex::new(0),
             response_text: "unused".to_string(),
         });
         let orchestrator = TranscriptionOrchestrator::new(provider);
 
         let error = orchestrator
-            .transcribe(Vec::new(), TranscriptionOptions::default())
+            .transcribe(Bytes::new(), TranscriptionOptions::default())
             .await
             .expect_err("empty audio should fail");
 
@@ -236,4 +395,68 @@ mod tests {
             TranscriptionError::Provider("Audio payload is empty".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn orchestrator_reuses_cached_transcript_for_identical_audio() {
+        let provider = Arc::new(StubProvider {
+            captured_audio_len: Mutex::new(None),
+            call_count: Mutex::new(0),
+            response_text: "cached  text".to_string(),
+        });
+        let orchestrator = TranscriptionOrchestrator::new(provider.clone());
+        orchestrator.configure_cache(TranscriptCacheConfig {
+            capacity: 4,
+            ttl: std::time::Duration::from_secs(60),
+        });
+        let call_count = || {
+            *provider
+                .call_count
+                .lock()
+                .expect("stub provider lock should not be poisoned")
+        };
+
+        let (first, second) = tokio::join!(
+            orchestrator.transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default()
+            ),
+            orchestrator.transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default()
+            ),
+        );
+        assert_eq!(first.expect("first transcription").text, "cached text");
+        assert_eq!(
+            second.expect("deduplicated transcription").text,
+            "cached text"
+        );
+        assert_eq!(call_count(), 1);
+
+        let retried = orchestrator
+            .clone()
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
+            .await
+            .expect("cached transcription");
+        assert_eq!(retried.text, "cached text");
+        assert_eq!(call_count(), 1);
+
+        orchestrator
+            .transcribe(Bytes::from_static(&[9, 9]), TranscriptionOptions::default())
+            .await
+            .expect("new audio should transcribe");
+        assert_eq!(call_count(), 2);
+
+        orchestrator.configure_cache(TranscriptCacheConfig::DISABLED);
+        orchestrator
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
+            .await
+            .expect("uncached transcription");
+        assert_eq!(call_count(), 3);
+    }
 }
@@ -0,0 +1,528 @@
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Certificate, Client, Proxy, Url,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{lookup_host, TcpStream},
+};
+use tracing::{debug, info, warn};
+
+const OPENAI_ORGANIZATION_HEADER: &str = "OpenAI-Organization";
+const OPENAI_PROJECT_HEADER: &str = "OpenAI-Project";
+const SUPPORTED_PROXY_SCHEMES: [&str; 4] = ["http://", "https://", "socks5://", "socks5h://"];
+const MAX_PROXY_RESPONSE_BYTES: usize = 8 * 1024;
+const DEFAULT_HTTP_PROXY_PORT: u16 = 80;
+const DEFAULT_SOCKS_PROXY_PORT: u16 = 1080;
+const SOCKS_VERSION: u8 = 5;
+const SOCKS_NO_AUTH: u8 = 0;
+const SOCKS_USERNAME_PASSWORD: u8 = 2;
+const SOCKS_AUTH_VERSION: u8 = 1;
+const SOCKS_CONNECT: u8 = 1;
+const SOCKS_ADDRESS_IPV4: u8 = 1;
+const SOCKS_ADDRESS_DOMAIN: u8 = 3;
+const SOCKS_ADDRESS_IPV6: u8 = 4;
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ProxyConfig {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("has_password", &self.password.is_some())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkConfig {
+    pub proxy: Option<ProxyConfig>,
+    pub openai_organization: Option<String>,
+    pub openai_project: Option<String>,
+    pub ca_bundle_path: Option<PathBuf>,
+    pub bypass_proxy: bool,
+}
+
+impl NetworkConfig {
+    fn openai_headers(&self) -> Result<HeaderMap, String> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            (OPENAI_ORGANIZATION_HEADER, &self.openai_organization),
+            (OPENAI_PROJECT_HEADER, &self.openai_project),
+        ] {
+            if let Some(value) = value {
+                let value = HeaderValue::from_str(value)
+                    .map_err(|error| format!("Invalid {name} header value: {error}"))?;
+                headers.insert(name, value);
+            }
+        }
+        Ok(headers)
+    }
+}
+
+fn parse_proxy(url: &str) -> Result<Proxy, String> {
+    if !SUPPORTED_PROXY_SCHEMES
+        .iter()
+        .any(|scheme| url.to_ascii_lowercase().starts_with(scheme))
+    {
+        return Err(format!(
+            "Unsupported proxy URL `{url}`. Expected an http://, https://, socks5:// or socks5h:// URL"
+        ));
+    }
+    Proxy::all(url).map_err(|error| format!("Invalid proxy URL `{url}`: {error}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AccountHeaders {
+    OpenAi,
+    None,
+}
+
+#[derive(Debug)]
+struct ClientState {
+    network: NetworkConfig,
+    client: Client,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ConfigurableClient {
+    timeout: Duration,
+    account_headers: AccountHeaders,
+    state: Arc<RwLock<ClientState>>,
+}
+
+impl ConfigurableClient {
+    pub(super) fn new(timeout: Duration, account_headers: AccountHeaders) -> Self {
+        let network = NetworkConfig::default();
+        let client = build_client(timeout, account_headers, &network)
+            .expect("HTTP client construction should succeed");
+        Self {
+            timeout,
+            account_headers,
+            state: Arc::new(RwLock::new(ClientState { network, client })),
+        }
+    }
+
+    pub(super) fn get(&self) -> Client {
+        match self.state.read() {
+            Ok(state) => state.client.clone(),
+            Err(poisoned) => poisoned.into_inner().client.clone(),
+        }
+    }
+
+    pub(super) fn configure(&self, network: &NetworkConfig) -> Result<(), String> {
+        let mut state = self
+            .state
+            .write()
+            .map_err(|_| "HTTP client lock poisoned".to_string())?;
+        if state.network == *network {
+            return Ok(());
+        }
+
+        let client = build_client(self.timeout, self.account_headers, network).inspect_err(
+            |error| warn!(%error, "failed to rebuild HTTP client with new network settings"),
+        )?;
+        info!(
+            proxy = network.proxy.is_some(),
+            bypass_proxy = network.bypass_proxy,
+            custom_ca = network.ca_bundle_path.is_some(),
+            organization = network.openai_organization.is_some(),
+            project = network.openai_project.is_some(),
+            "HTTP client rebuilt with new network settings"
+        );
+        *state = ClientState {
+            network: network.clone(),
+            client,
+        };
+        Ok(())
+    }
+}
+
+pub fn build_probe_client(timeout: Duration, network: &NetworkConfig) -> Result<Client, String> {
+    build_client(timeout, AccountHeaders::None, network)
+}
+
+// The realtime websocket doesn't go through reqwest, so it tunnels through the proxy by hand.
+pub(super) async fn connect_tcp(
+    network: &NetworkConfig,
+    host: &str,
+    port: u16,
+) -> Result<TcpStream, String> {
+    let Some(proxy) = network.proxy.as_ref().filter(|_| !network.bypass_proxy) else {
+        return TcpStream::connect((host, port))
+            .await
+            .map_err(|error| format!("Failed to connect to {host}:{port}: {error}"));
+    };
+
+    let url = Url::parse(&proxy.url)
+        .map_err(|error| format!("Invalid proxy URL `{}`: {error}", proxy.url))?;
+    let proxy_host = url
+        .host_str()
+        .ok_or_else(|| format!("Proxy URL `{}` has no host", proxy.url))?;
+    let username = proxy
+        .username
+        .clone()
+        .or_else(|| Some(url.username().to_string()).filter(|name| !name.is_empty()));
+    let credentials = username.map(|username| {
+        let password = proxy
+            .password
+            .clone()
+            .or_else(|| url.password().map(str::to_string));
+        (username, password.unwrap_or_default())
+    });
+
+    let scheme = url.scheme();
+    let default_port = match scheme {
+        "http" => DEFAULT_HTTP_PROXY_PORT,
+        "socks5" | "socks5h" => DEFAULT_SOCKS_PROXY_PORT,
+        _ => {
+            return Err(format!(
+                "{scheme}:// proxies are not supported for realtime transcription"
+            ))
+        }
+    };
+    let proxy_port = url.port().unwrap_or(default_port);
+    let mut stream = TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .map_err(|error| {
+            format!("Failed to connect to proxy {proxy_host}:{proxy_port}: {error}")
+        })?;
+    debug!(
+        proxy_host,
+        proxy_port, scheme, "tunneling websocket through proxy"
+    );
+
+    if scheme == "http" {
+        http_connect(&mut stream, host, port, credentials.as_ref()).await?;
+    } else {
+        socks5_connect(
+            &mut stream,
+            host,
+            port,
+            credentials.as_ref(),
+            scheme == "socks5h",
+        )
+        .await?;
+    }
+    Ok(stream)
+}
+
+async fn http_connect(
+    stream: &mut TcpStream,
+    host: &str,
+    port: u16,
+    credentials: Option<&(String, String)>,
+) -> Result<(), String> {
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some((username, password)) = credentials {
+        let token = BASE64_STANDARD.encode(format!("{username}:{password}"));
+        request.push_str(&format!("Proxy-Authorization: Basic {token}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(proxy_io_error)?;
+
+    // Read one byte at a time so nothing after the headers is taken from the tunnel.
+    let mut response = Vec::new();
+    let mut byte = [0_u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_PROXY_RESPONSE_BYTES {
+            return Err("Proxy CONNECT response is too large".to_string());
+        }
+        if stream.read(&mut byte).await.map_err(proxy_io_error)? == 0 {
+            return Err("Proxy closed the connection during CONNECT".to_string());
+        }
+        response.push(byte[0]);
+    }
+
+    match connect_response_status(&response) {
+        Some(200) => Ok(()),
+        Some(status) => Err(format!("Proxy refused CONNECT with HTTP status {status}")),
+        None => Err("Proxy sent an invalid CONNECT response".to_string()),
+    }
+}
+
+fn connect_response_status(response: &[u8]) -> Option<u16> {
+    let status_line = response.split(|byte| *byte == b'\n').next()?;
+    std::str::from_utf8(status_line)
+        .ok()?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+async fn socks5_connect(
+    stream: &mut TcpStream,
+    host: &str,
+    port: u16,
+    credentials: Option<&(String, String)>,
+    resolve_remotely: bool,
+) -> Result<(), String> {
+    let greeting: &[u8] = if credentials.is_some() {
+        &[SOCKS_VERSION, 2, SOCKS_NO_AUTH, SOCKS_USERNAME_PASSWORD]
+    } else {
+        &[SOCKS_VERSION, 1, SOCKS_NO_AUTH]
+    };
+    stream.write_all(greeting).await.map_err(proxy_io_error)?;
+    let mut method = [0_u8; 2];
+    stream
+        .read_exact(&mut method)
+        .await
+        .map_err(proxy_io_error)?;
+    match (method, credentials) {
+        ([SOCKS_VERSION, SOCKS_NO_AUTH], _) => {}
+        ([SOCKS_VERSION, SOCKS_USERNAME_PASSWORD], Some((username, password))) => {
+            let request = socks5_auth_request(username, password)?;
+            stream.write_all(&request).await.map_err(proxy_io_error)?;
+            let mut status = [0_u8; 2];
+            stream
+                .read_exact(&mut status)
+                .await
+                .map_err(proxy_io_error)?;
+            if status[1] != 0 {
+                return Err("SOCKS proxy rejected the credentials".to_string());
+            }
+        }
+        _ => return Err("SOCKS proxy offered no supported authentication method".to_string()),
+    }
+
+    let request = if resolve_remotely {
+        socks5_domain_connect_request(host, port)?
+    } else {
+        let address = lookup_host((host, port))
+            .await
+            .map_err(|error| format!("Failed to resolve {host}: {error}"))?
+            .next()
+            .ok_or_else(|| format!("Failed to resolve {host}"))?;
+        let mut request = vec![SOCKS_VERSION, SOCKS_CONNECT, 0];
+        match address.ip() {
+            std::net::IpAddr::V4(ip) => {
+                request.push(SOCKS_ADDRESS_IPV4);
+                request.extend_from_slice(&ip.octets());
+            }
+            std::net::IpAddr::V6(ip) => {
+                request.push(SOCKS_ADDRESS_IPV6);
+                request.extend_from_slice(&ip.octets());
+            }
+        }
+        request.extend_from_slice(&port.to_be_bytes());
+        request
+    };
+    stream.write_all(&request).await.map_err(proxy_io_error)?;
+
+    let mut reply = [0_u8; 4];
+    stream
+        .read_exact(&mut reply)
+        .await
+        .map_err(proxy_io_error)?;
+    if reply[1] != 0 {
+        return Err(format!(
+            "SOCKS proxy refused the connection (reply code {})",
+            reply[1]
+        ));
+    }
+    let bound_address_len = match reply[3] {
+        SOCKS_ADDRESS_IPV4 => 4,
+        SOCKS_ADDRESS_IPV6 => 16,
+        SOCKS_ADDRESS_DOMAIN => {
+            let mut len = [0_u8; 1];
+            stream.read_exact(&mut len).await.map_err(proxy_io_error)?;
+            usize::from(len[0])
+        }
+        address_type => {
+            return Err(format!(
+                "SOCKS proxy sent an unknown address type {address_type}"
+            ))
+        }
+    };
+    let mut bound_address = vec![0_u8; bound_address_len + 2];
+    stream
+        .read_exact(&mut bound_address)
+        .await
+        .map_err(proxy_io_error)?;
+    Ok(())
+}
+
+fn socks5_auth_request(username: &str, password: &str) -> Result<Vec<u8>, String> {
+    let username_len = u8::try_from(username.len())
+        .map_err(|_| "SOCKS proxy username is longer than 255 bytes".to_string())?;
+    let password_len = u8::try_from(password.len())
+        .map_err(|_| "SOCKS proxy password is longer than 255 bytes".to_string())?;
+    let mut request = vec![SOCKS_AUTH_VERSION, username_len];
+    request.extend_from_slice(username.as_bytes());
+    request.push(password_len);
+    request.extend_from_slice(password.as_bytes());
+    Ok(request)
+}
+
+fn socks5_domain_connect_request(host: &str, port: u16) -> Result<Vec<u8>, String> {
+    let host_len =
+        u8::try_from(host.len()).map_err(|_| format!("Host name `{host}` is too long"))?;
+    let mut request = vec![
+        SOCKS_VERSION,
+        SOCKS_CONNECT,
+        0,
+        SOCKS_ADDRESS_DOMAIN,
+        host_len,
+    ];
+    request.extend_from_slice(host.as_bytes());
+    request.extend_from_slice(&port.to_be_bytes());
+    Ok(request)
+}
+
+fn proxy_io_error(error: std::io::Error) -> String {
+    format!("Proxy connection failed: {error}")
+}
+
+fn read_ca_bundle(path: &PathBuf) -> Result<Vec<Certificate>, String> {
+    let pem = fs::read(path)
+        .map_err(|error| format!("Failed to read CA bundle at {}: {error}", path.display()))?;
+    let certificates = Certificate::from_pem_bundle(&pem)
+        .map_err(|error| format!("Failed to parse CA bundle at {}: {error}", path.display()))?;
+    if certificates.is_empty() {
+        return Err(format!(
+            "CA bundle at {} does not contain any certificates",
+            path.display()
+        ));
+    }
+    Ok(certificates)
+}
+
+fn build_client(
+    timeout: Duration,
+    account_headers: AccountHeaders,
+    network: &NetworkConfig,
+) -> Result<Client, String> {
+    let mut builder = Client::builder().timeout(timeout);
+
+    if let Some(path) = &network.ca_bundle_path {
+        for certificate in read_ca_bundle(path)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if network.bypass_proxy {
+        builder = builder.no_proxy();
+    } else if let Some(proxy_config) = &network.proxy {
+        let mut proxy = parse_proxy(&proxy_config.url)?;
+        if let Some(username) = &proxy_config.username {
+            proxy = proxy.basic_auth(username, proxy_config.password.as_deref().unwrap_or(""));
+        }
+        builder = builder.proxy(proxy);
+    }
+
+    if account_headers == AccountHeaders::OpenAi {
+        builder = builder.default_headers(network.openai_headers()?);
+    }
+
+    builder
+        .build()
+        .map_err(|error| format!("Failed to build HTTP client: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        build_probe_client, connect_response_status, parse_proxy, socks5_auth_request,
+        socks5_domain_connect_request, NetworkConfig,
+    };
+
+    #[test]
+    fn accepts_http_and_socks_proxies_only() {
+        assert!(parse_proxy("http://proxy.corp.example:3128").is_ok());
+        assert!(parse_proxy("socks5h://127.0.0.1:1080").is_ok());
+        assert!(parse_proxy("ftp://proxy.corp.example").is_err());
+        assert!(parse_proxy("proxy.corp.example:3128").is_err());
+    }
+
+    #[test]
+    fn reads_the_status_of_connect_responses() {
+        assert_eq!(
+            connect_response_status(b"HTTP/1.1 200 Connection established\r\n\r\n"),
+            Some(200)
+        );
+        assert_eq!(
+            connect_response_status(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n"),
+            Some(407)
+        );
+        assert_eq!(connect_response_status(b"garbage\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn builds_socks5_requests() {
+        assert_eq!(
+            socks5_domain_connect_request("api.openai.com", 443).expect("short host"),
+            [
+                &[5, 1, 0, 3, 14][..],
+                b"api.openai.com",
+                &443_u16.to_be_bytes()
+            ]
+            .concat()
+        );
+        assert_eq!(
+            socks5_auth_request("me", "pw").expect("short credentials"),
+            [1, 2, b'm', b'e', 2, b'p', b'w']
+        );
+        assert!(socks5_auth_request(&"x".repeat(256), "pw").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_or_empty_ca_bundles() {
+        let missing = NetworkConfig {
+            ca_bundle_path: Some(std::env::temp_dir().join("buzz-missing-ca-bundle.pem")),
+            ..NetworkConfig::default()
+        };
+        let error = build_probe_client(Duration::from_secs(1), &missing)
+            .expect_err("missing bundle should fail");
+        assert!(error.contains("Failed to read CA bundle"));
+
+        let empty_path =
+            std::env::temp_dir().join(format!("buzz-empty-ca-{}.pem", uuid::Uuid::new_v4()));
+        std::fs::write(&empty_path, "not a certificate").expect("writing test bundle");
+        let empty = NetworkConfig {
+            ca_bundle_path: Some(empty_path.clone()),
+            ..NetworkConfig::default()
+        };
+        let error = build_probe_client(Duration::from_secs(1), &empty)
+            .expect_err("bundle without certificates should fail");
+        assert!(error.contains("does not contain any certificates"));
+        let _ = std::fs::remove_file(empty_path);
+    }
+
+    #[test]
+    fn builds_organization_and_project_headers() {
+        let headers = NetworkConfig {
+            openai_organization: Some("org-123".to_string()),
+            openai_project: Some("proj_456".to_string()),
+            ..NetworkConfig::default()
+        }
+        .openai_headers()
+        .expect("headers should be valid");
+
+        assert_eq!(headers["OpenAI-Organization"], "org-123");
+        assert_eq!(headers["OpenAI-Project"], "proj_456");
+        assert!(NetworkConfig::default()
+            .openai_headers()
+            .expect("empty headers should be valid")
+            .is_empty());
+    }
+}
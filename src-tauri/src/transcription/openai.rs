@@ -2,11 +2,14 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use reqwest::{
     header::{HeaderMap, RETRY_AFTER},
-    multipart, Client, StatusCode,
+    multipart, StatusCode,
 };
 use serde::Deserialize;
 use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
     path::PathBuf,
+    sync::{Arc, Mutex, PoisonError, RwLock},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tracing::{debug, error, info, warn};
@@ -15,12 +18,17 @@ use tracing::{debug, error, info, warn};
 use crate::api_key_store::ApiKeyStore;
 
 use super::{
-    normalize_transcript_text, TranscriptionDeltaCallback, TranscriptionError,
-    TranscriptionOptions, TranscriptionProvider, TranscriptionResult,
+    network::{AccountHeaders, ConfigurableClient},
+    normalize_transcript_text, NetworkConfig, TranscriptionDeltaCallback, TranscriptionError,
+    TranscriptionOptions, TranscriptionProvider, TranscriptionResult, TranscriptionSegment,
 };
 
 const DEFAULT_OPENAI_ENDPOINT: &str = "https://api.openai.com/v1/audio/transcriptions";
 const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini-transcribe";
+const DEFAULT_OPENAI_DIARIZATION_MODEL: &str = "gpt-4o-transcribe-diarize";
+const DEFAULT_OPENAI_TIMESTAMP_MODEL: &str = "whisper-1";
+// Every account can use this model, so it stands in when the selected one is refused.
+const FALLBACK_OPENAI_MODEL: &str = "whisper-1";
 const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 180;
 const DEFAULT_MAX_RETRIES: u32 = 3;
 const DEFAULT_INITIAL_BACKOFF_MS: u64 = 500;
@@ -34,6 +42,8 @@ pub struct OpenAiTranscriptionConfig {
     pub api_key_store_app_data_dir: Option<PathBuf>,
     pub endpoint: String,
     pub model: String,
+    pub diarization_model: String,
+    pub timestamp_model: String,
     pub request_timeout_secs: u64,
     pub max_retries: u32,
     pub retry_initial_backoff_ms: u64,
@@ -47,6 +57,8 @@ impl Default for OpenAiTranscriptionConfig {
             api_key_store_app_data_dir: None,
             endpoint: DEFAULT_OPENAI_ENDPOINT.to_string(),
             model: DEFAULT_OPENAI_MODEL.to_string(),
+            diarization_model: DEFAULT_OPENAI_DIARIZATION_MODEL.to_string(),
+            timestamp_model: DEFAULT_OPENAI_TIMESTAMP_MODEL.to_string(),
             request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
             max_retries: DEFAULT_MAX_RETRIES,
             retry_initial_backoff_ms: DEFAULT_INITIAL_BACKOFF_MS,
@@ -63,6 +75,14 @@ impl OpenAiTranscriptionConfig {
             config.model = model;
         }
 
+        if let Some(model) = read_non_empty_env("OPENAI_DIARIZATION_MODEL") {
+            config.diarization_model = model;
+        }
+
+        if let Some(model) = read_non_empty_env("OPENAI_TIMESTAMP_MODEL") {
+            config.timestamp_model = model;
+        }
+
         if let Some(endpoint) = read_non_empty_env("OPENAI_TRANSCRIPTION_ENDPOINT") {
             config.endpoint = endpoint;
         }
@@ -104,8 +124,10 @@ impl OpenAiTranscriptionConfig {
 
 #[derive(Debug, Clone)]
 pub struct OpenAiTranscriptionProvider {
-    client: Client,
+    client: ConfigurableClient,
     config: OpenAiTranscriptionConfig,
+    selected_model: Arc<RwLock<Option<String>>>,
+    unavailable_models: Arc<Mutex<HashSet<(u64, String)>>>,
     jitter_seed: u64,
 }
 
@@ -125,6 +147,8 @@ impl OpenAiTranscriptionProvider {
         Self {
             client: build_client(&config),
             config,
+            selected_model: Arc::new(RwLock::new(None)),
+            unavailable_models: Arc::new(Mutex::new(HashSet::new())),
             jitter_seed,
         }
     }
@@ -205,34 +229,81 @@ impl OpenAiTranscriptionProvider {
         state.wrapping_mul(0x2545_F491_4F6C_DD1D)
     }
 
-    fn model_supports_streaming(&self) -> bool {
-        self.config
-            .model
-            .to_ascii_lowercase()
-            .contains("transcribe")
+    fn transcription_model(&self, api_key: &str) -> String {
+        let model = self
+            .selected_model
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .unwrap_or_else(|| self.config.model.clone());
+        if self
+            .unavailable_models
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&(key_fingerprint(api_key), model.clone()))
+        {
+            debug!(
+                model = %model,
+                fallback_model = FALLBACK_OPENAI_MODEL,
+                "using fallback model for API key without access to the selected model"
+            );
+            return FALLBACK_OPENAI_MODEL.to_string();
+        }
+        model
+    }
+
+    fn remember_unavailable_model(&self, api_key: &str, model: &str) {
+        self.unavailable_models
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((key_fingerprint(api_key), model.to_string()));
+    }
+
+    fn request_model<'a>(&'a self, diarize: bool, timestamps: bool, model: &'a str) -> &'a str {
+        if diarize {
+            &self.config.diarization_model
+        } else if timestamps {
+            &self.config.timestamp_model
+        } else {
+            model
+        }
     }
 
     fn build_form(
-        &self,
+        model: &str,
         audio_data: Bytes,
         language: Option<&str>,
         prompt: Option<&str>,
         stream: bool,
+        diarize: bool,
+        timestamps: bool,
     ) -> Result<multipart::Form, TranscriptionError> {
-        let response_format = if stream { "text" } else { "verbose_json" };
+        let response_format = if diarize {
+            "diarized_json"
+        } else if stream && !timestamps {
+            "text"
+        } else {
+            "verbose_json"
+        };
         let mut form = multipart::Form::new()
-            .text("model", self.config.model.clone())
+            .text("model", model.to_string())
             .text("response_format", response_format.to_string());
 
         if stream {
             form = form.text("stream", "true".to_string());
         }
 
+        if diarize {
+            form = form.text("chunking_strategy", "auto".to_string());
+        } else if timestamps {
+            form = form.text("timestamp_granularities[]", "segment".to_string());
+        }
+
         if let Some(language) = language {
             form = form.text("language", language.to_string());
         }
 
-        if let Some(prompt) = prompt {
+        if let Some(prompt) = prompt.filter(|_| !diarize) {
             form = form.text("prompt", prompt.to_string());
         }
 
@@ -252,6 +323,7 @@ impl OpenAiTranscriptionProvider {
     async fn parse_streaming_response(
         &self,
         mut response: reqwest::Response,
+        model: &str,
         request_language: Option<String>,
         on_delta: Option<&TranscriptionDeltaCallback>,
     ) -> Result<TranscriptionResult, TranscriptionError> {
@@ -298,6 +370,8 @@ impl OpenAiTranscriptionProvider {
             language: request_language,
             duration_secs: None,
             confidence: None,
+            segments: Vec::new(),
+            model: Some(model.to_string()),
         })
     }
 }
@@ -308,28 +382,58 @@ impl TranscriptionProvider for OpenAiTranscriptionProvider {
         "openai"
     }
 
+    async fn warm_up(&self) -> Result<(), TranscriptionError> {
+        // Any response means DNS, TCP, and TLS are done and the connection sits in the pool.
+        let response = self
+            .client
+            .get()
+            .head(&self.config.endpoint)
+            .send()
+            .await
+            .map_err(|error| TranscriptionError::Network(error.to_string()))?;
+        debug!(status = %response.status(), "OpenAI transcription connection warmed");
+        Ok(())
+    }
+
+    fn configure_network(&self, network: &NetworkConfig) -> Result<(), String> {
+        self.client.configure(network)
+    }
+
+    fn configure_model(&self, model: Option<&str>) {
+        *self
+            .selected_model
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = model.map(ToString::to_string);
+    }
+
     async fn transcribe(
         &self,
-        audio_data: Vec<u8>,
+        audio_data: Bytes,
         options: TranscriptionOptions,
     ) -> Result<TranscriptionResult, TranscriptionError> {
         let TranscriptionOptions {
             language,
             prompt,
             context_hint,
+            diarize,
+            timestamps,
             on_delta,
         } = options;
         let api_key = self.api_key()?;
         let request_language = normalize_optional_string(language);
         let request_prompt = build_prompt(prompt, context_hint);
         let request_language_for_payload = request_language.clone();
-        let stream_response = self.model_supports_streaming();
-        let audio_data = Bytes::from(audio_data);
+        let diarize = diarize && !self.config.diarization_model.trim().is_empty();
+        let timestamps = timestamps && !self.config.timestamp_model.trim().is_empty();
+        let mut model = self.transcription_model(&api_key);
+        let mut stream_response = !diarize && !timestamps && model_supports_streaming(&model);
         let mut attempt_index = 0;
         info!(
             endpoint = %self.config.endpoint,
-            model = %self.config.model,
+            model = %model,
             stream = stream_response,
+            diarize,
+            timestamps,
             audio_bytes = audio_data.len(),
             language = ?request_language,
             has_prompt = request_prompt.is_some(),
@@ -341,15 +445,19 @@ impl TranscriptionProvider for OpenAiTranscriptionProvider {
                 attempt = attempt_index + 1,
                 "sending OpenAI transcription request"
             );
-            let form = self.build_form(
+            let form = Self::build_form(
+                self.request_model(diarize, timestamps, &model),
                 audio_data.clone(),
                 request_language.as_deref(),
                 request_prompt.as_deref(),
                 stream_response,
+                diarize,
+                timestamps,
             )?;
 
             let response = self
                 .client
+                .get()
                 .post(&self.config.endpoint)
                 .bearer_auth(&api_key)
                 .multipart(form)
@@ -390,6 +498,7 @@ impl TranscriptionProvider for OpenAiTranscriptionProvider {
                     return self
                         .parse_streaming_response(
                             response,
+                            &model,
                             request_language_for_payload.clone(),
                             on_delta.as_ref(),
                         )
@@ -410,10 +519,28 @@ impl TranscriptionProvider for OpenAiTranscriptionProvider {
                     confidence: response_payload
                         .confidence
                         .or_else(|| derive_confidence_from_segments(&response_payload.segments)),
+                    segments: transcription_segments(&response_payload.segments),
+                    model: Some(self.request_model(diarize, timestamps, &model).to_string()),
                 });
             }
 
             let http_error = map_http_error(response).await;
+            if http_error.model_unavailable
+                && !diarize
+                && !timestamps
+                && model != FALLBACK_OPENAI_MODEL
+            {
+                warn!(
+                    model = %model,
+                    fallback_model = FALLBACK_OPENAI_MODEL,
+                    error = %http_error.error,
+                    "OpenAI account cannot use transcription model; falling back"
+                );
+                self.remember_unavailable_model(&api_key, &model);
+                model = FALLBACK_OPENAI_MODEL.to_string();
+                stream_response = model_supports_streaming(&model);
+                continue;
+            }
             if http_error.retryable && attempt_index < self.config.max_retries {
                 let delay = self.retry_delay(attempt_index, http_error.retry_after);
                 warn!(
@@ -567,6 +694,14 @@ struct OpenAiTranscriptionResponse {
 struct OpenAiSegment {
     #[serde(default)]
     avg_logprob: Option<f32>,
+    #[serde(default)]
+    start: Option<f64>,
+    #[serde(default)]
+    end: Option<f64>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    speaker: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -580,6 +715,27 @@ struct OpenAiErrorBody {
     message: Option<String>,
     #[serde(default, rename = "type")]
     kind: Option<String>,
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+}
+
+fn transcription_segments(segments: &[OpenAiSegment]) -> Vec<TranscriptionSegment> {
+    segments
+        .iter()
+        .filter_map(|segment| {
+            let text = normalize_transcript_text(segment.text.as_deref()?);
+            if text.is_empty() {
+                return None;
+            }
+
+            Some(TranscriptionSegment {
+                start_secs: segment.start.unwrap_or_default(),
+                end_secs: segment.end.or(segment.start).unwrap_or_default(),
+                text,
+                speaker: normalize_optional_string(segment.speaker.clone()),
+            })
+        })
+        .collect()
 }
 
 fn derive_confidence_from_segments(segments: &[OpenAiSegment]) -> Option<f32> {
@@ -602,6 +758,7 @@ struct RetryableError {
     error: TranscriptionError,
     retryable: bool,
     retry_after: Option<Duration>,
+    model_unavailable: bool,
 }
 
 fn map_transport_error(error: reqwest::Error) -> RetryableError {
@@ -616,6 +773,7 @@ fn map_transport_error(error: reqwest::Error) -> RetryableError {
         error: mapped,
         retryable,
         retry_after: None,
+        model_unavailable: false,
     }
 }
 
@@ -629,6 +787,7 @@ async fn map_http_error(response: reqwest::Response) -> RetryableError {
     let response_body = response.text().await.unwrap_or_default();
     let fallback_message = format!("OpenAI request failed with status {}", status.as_u16());
     let error_message = parse_openai_error_message(&response_body).unwrap_or(fallback_message);
+    let model_unavailable = is_model_unavailable_error(status, &response_body);
     debug!(
         status = status.as_u16(),
         retry_after_ms = retry_after.map(|d| d.as_millis() as u64),
@@ -651,7 +810,36 @@ async fn map_http_error(response: reqwest::Response) -> RetryableError {
             || status.is_server_error(),
         error: mapped,
         retry_after,
+        model_unavailable,
+    }
+}
+
+fn is_model_unavailable_error(status: StatusCode, raw_body: &str) -> bool {
+    if !matches!(
+        status,
+        StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND
+    ) {
+        return false;
     }
+    let Ok(parsed) = serde_json::from_str::<OpenAiErrorEnvelope>(raw_body) else {
+        return false;
+    };
+    if parsed
+        .error
+        .code
+        .as_ref()
+        .and_then(serde_json::Value::as_str)
+        == Some("model_not_found")
+    {
+        return true;
+    }
+    let message = parsed
+        .error
+        .message
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    message.contains("does not have access to model")
+        || (message.contains("model") && message.contains("does not exist"))
 }
 
 fn parse_openai_error_message(raw_body: &str) -> Option<String> {
@@ -733,16 +921,23 @@ fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
     )
 }
 
-fn build_client(config: &OpenAiTranscriptionConfig) -> Client {
+fn model_supports_streaming(model: &str) -> bool {
+    model.to_ascii_lowercase().contains("transcribe")
+}
+
+fn key_fingerprint(api_key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    api_key.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn build_client(config: &OpenAiTranscriptionConfig) -> ConfigurableClient {
     let timeout = Duration::from_secs(config.request_timeout_secs.max(1));
     debug!(
         timeout_secs = timeout.as_secs(),
         "building OpenAI HTTP client"
     );
-    Client::builder()
-        .timeout(timeout)
-        .build()
-        .expect("OpenAI client construction should succeed")
+    ConfigurableClient::new(timeout, AccountHeaders::OpenAi)
 }
 
 fn seed_from_clock() -> u64 {
@@ -768,6 +963,8 @@ mod tests {
             api_key_store_app_data_dir: None,
             endpoint: format!("{}/v1/audio/transcriptions", server.url()),
             model: "whisper-1".to_string(),
+            diarization_model: "gpt-4o-transcribe-diarize".to_string(),
+            timestamp_model: "whisper-1".to_string(),
             request_timeout_secs: 5,
             max_retries: 3,
             retry_initial_backoff_ms: 10,
@@ -783,6 +980,21 @@ mod tests {
         provider_with_config(config_for_test(server, api_key))
     }
 
+    #[tokio::test]
+    async fn warm_up_opens_connection_without_requiring_success_status() {
+        let mut server = Server::new_async().await;
+        let warm_mock = server
+            .mock("HEAD", "/v1/audio/transcriptions")
+            .with_status(405)
+            .create_async()
+            .await;
+
+        let provider = provider_for_test(&server, None);
+        provider.warm_up().await.expect("warm up should succeed");
+
+        warm_mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn returns_transcription_payload_for_success_response() {
         let mut server = Server::new_async().await;
@@ -809,7 +1021,7 @@ mod tests {
         let provider = provider_for_test(&server, Some("test-key"));
         let result = provider
             .transcribe(
-                vec![1, 2, 3, 4],
+                Bytes::from_static(&[1, 2, 3, 4]),
                 TranscriptionOptions {
                     language: None,
                     prompt: Some("voice memo".to_string()),
@@ -866,7 +1078,7 @@ mod tests {
         };
 
         let result = provider
-            .transcribe(vec![1, 2, 3], options)
+            .transcribe(Bytes::from_static(&[1, 2, 3]), options)
             .await
             .expect("streaming transcription should succeed");
 
@@ -884,6 +1096,112 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn requests_diarized_json_and_returns_speaker_segments() {
+        let mut server = Server::new_async().await;
+        let request_mock = server
+            .mock("POST", "/v1/audio/transcriptions")
+            .match_header("authorization", "Bearer test-key")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#"name="model"\r\n\r\ngpt-4o-transcribe-diarize"#.to_string()),
+                Matcher::Regex(r#"name="response_format"\r\n\r\ndiarized_json"#.to_string()),
+                Matcher::Regex(r#"name="chunking_strategy"\r\n\r\nauto"#.to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "text": "Hi there. Hello.",
+                    "duration": 3.0,
+                    "segments": [
+                        { "start": 0.0, "end": 1.2, "text": " Hi there. ", "speaker": "A" },
+                        { "start": 1.4, "end": 3.0, "text": "Hello.", "speaker": "B" },
+                        { "start": 3.0, "end": 3.0, "text": "  ", "speaker": "B" }
+                    ]
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = provider_for_test(&server, Some("test-key"));
+        let result = provider
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions {
+                    diarize: true,
+                    ..TranscriptionOptions::default()
+                },
+            )
+            .await
+            .expect("diarized request should succeed");
+
+        request_mock.assert_async().await;
+        assert_eq!(result.text, "Hi there. Hello.");
+        assert_eq!(
+            result.segments,
+            vec![
+                TranscriptionSegment {
+                    start_secs: 0.0,
+                    end_secs: 1.2,
+                    text: "Hi there.".to_string(),
+                    speaker: Some("A".to_string()),
+                },
+                TranscriptionSegment {
+                    start_secs: 1.4,
+                    end_secs: 3.0,
+                    text: "Hello.".to_string(),
+                    speaker: Some("B".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_segment_timestamps_from_timestamp_model() {
+        let mut server = Server::new_async().await;
+        let request_mock = server
+            .mock("POST", "/v1/audio/transcriptions")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#"name="model"\r\n\r\nwhisper-1"#.to_string()),
+                Matcher::Regex(r#"name="response_format"\r\n\r\nverbose_json"#.to_string()),
+                Matcher::Regex(r#"name="timestamp_granularities\[\]"\r\n\r\nsegment"#.to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "text": "Hello there.",
+                    "segments": [{ "start": 0.5, "end": 1.75, "text": " Hello there." }]
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let mut config = config_for_test(&server, Some("test-key"));
+        config.model = "gpt-4o-mini-transcribe".to_string();
+        let result = provider_with_config(config)
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions {
+                    timestamps: true,
+                    ..TranscriptionOptions::default()
+                },
+            )
+            .await
+            .expect("timestamped request should succeed");
+
+        request_mock.assert_async().await;
+        assert_eq!(
+            result.segments,
+            vec![TranscriptionSegment {
+                start_secs: 0.5,
+                end_secs: 1.75,
+                text: "Hello there.".to_string(),
+                speaker: None,
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn returns_authentication_error_for_unauthorized_response() {
         let mut server = Server::new_async().await;
@@ -897,7 +1215,10 @@ mod tests {
 
         let provider = provider_for_test(&server, Some("bad-key"));
         let error = provider
-            .transcribe(vec![1, 2, 3], TranscriptionOptions::default())
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
             .await
             .expect_err("request should fail");
 
@@ -908,6 +1229,64 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn falls_back_to_whisper_once_per_key_without_access_to_the_selected_model() {
+        let mut server = Server::new_async().await;
+        let refused_mock = server
+            .mock("POST", "/v1/audio/transcriptions")
+            .match_body(Matcher::Regex(
+                r#"name="model"\r\n\r\ngpt-4o-transcribe\r\n"#.to_string(),
+            ))
+            .expect(2)
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"error":{"message":"Project does not have access to model gpt-4o-transcribe","code":"model_not_found"}}"#,
+            )
+            .create_async()
+            .await;
+        let fallback_mock = server
+            .mock("POST", "/v1/audio/transcriptions")
+            .match_body(Matcher::Regex(
+                r#"name="model"\r\n\r\nwhisper-1\r\n"#.to_string(),
+            ))
+            .expect(3)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"text":"hello fallback"}"#)
+            .create_async()
+            .await;
+
+        let provider = provider_for_test(&server, Some("test-key"));
+        provider.configure_model(Some("gpt-4o-transcribe"));
+        for _ in 0..2 {
+            let result = provider
+                .transcribe(
+                    Bytes::from_static(&[1, 2, 3]),
+                    TranscriptionOptions::default(),
+                )
+                .await
+                .expect("request should fall back to whisper-1");
+            assert_eq!(result.text, "hello fallback");
+            assert_eq!(result.model.as_deref(), Some("whisper-1"));
+        }
+
+        let other_key_provider = OpenAiTranscriptionProvider {
+            config: config_for_test(&server, Some("other-key")),
+            ..provider.clone()
+        };
+        other_key_provider
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
+            .await
+            .expect("a new key should try the selected model again");
+
+        refused_mock.assert_async().await;
+        fallback_mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn retries_server_errors_then_returns_success() {
         let mut server = Server::new_async().await;
@@ -936,7 +1315,10 @@ mod tests {
 
         let started_at = Instant::now();
         let result = provider
-            .transcribe(vec![1, 2, 3], TranscriptionOptions::default())
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
             .await
             .expect("request should succeed");
         let elapsed = started_at.elapsed();
@@ -970,7 +1352,10 @@ mod tests {
 
         let started_at = Instant::now();
         let error = provider
-            .transcribe(vec![1, 2, 3], TranscriptionOptions::default())
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
             .await
             .expect_err("request should fail");
         let elapsed = started_at.elapsed();
@@ -1015,7 +1400,10 @@ mod tests {
 
         let started_at = Instant::now();
         let result = provider
-            .transcribe(vec![1, 2, 3], TranscriptionOptions::default())
+            .transcribe(
+                Bytes::from_static(&[1, 2, 3]),
+                TranscriptionOptions::default(),
+            )
             .await
             .expect("request should succeed after retry");
         let elapsed = started_at.elapsed();
@@ -1,13 +1,27 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
 use futures_util::{SinkExt, StreamExt};
 use reqwest::Url;
 use serde_json::{json, Value};
-use tokio::sync::{mpsc, oneshot};
+use tokio::{
+    net::TcpStream,
+    sync::{mpsc, oneshot},
+};
 use tokio_tungstenite::{
-    connect_async,
-    tungstenite::{self, client::IntoClientRequest, http::HeaderValue, Message},
+    client_async_tls,
+    tungstenite::{
+        self,
+        client::IntoClientRequest,
+        handshake::client::{Request, Response},
+        http::HeaderValue,
+        Message,
+    },
+    MaybeTlsStream, WebSocketStream,
 };
 use tracing::{debug, info, warn};
 
@@ -15,6 +29,7 @@ use tracing::{debug, info, warn};
 use crate::api_key_store::ApiKeyStore;
 
 use super::{
+    network::{self, NetworkConfig},
     normalize_transcript_text, TranscriptionError, TranscriptionOptions, TranscriptionResult,
 };
 
@@ -24,6 +39,8 @@ const DEFAULT_OPENAI_TRANSCRIPTION_MODEL: &str = "gpt-4o-mini-transcribe";
 const OPENAI_REALTIME_BETA_HEADER_VALUE: &str = "realtime=v1";
 const DEFAULT_COMMIT_TIMEOUT_SECS: u64 = 20;
 const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const WS_DEFAULT_PORT: u16 = 80;
+const WSS_DEFAULT_PORT: u16 = 443;
 const REALTIME_OUTPUT_SAMPLE_RATE_HZ: u32 = 24_000;
 // Approximate 5 seconds of queued audio assuming ~20ms callback chunks (~50 chunks/sec).
 const REALTIME_COMMAND_BUFFER_DURATION_SECS: usize = 5;
@@ -43,6 +60,8 @@ const EVENT_SESSION_CREATED_LEGACY: &str = "transcription_session.created";
 const EVENT_SESSION_UPDATED_LEGACY: &str = "transcription_session.updated";
 const EVENT_ERROR: &str = "error";
 
+type RealtimeWebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 #[derive(Debug, Clone)]
 pub struct OpenAiRealtimeTranscriptionConfig {
     pub api_key: Option<String>,
@@ -105,6 +124,7 @@ impl OpenAiRealtimeTranscriptionConfig {
 #[derive(Debug, Clone)]
 pub struct OpenAiRealtimeTranscriptionClient {
     config: OpenAiRealtimeTranscriptionConfig,
+    network: Arc<RwLock<NetworkConfig>>,
 }
 
 impl OpenAiRealtimeTranscriptionClient {
@@ -116,7 +136,17 @@ impl OpenAiRealtimeTranscriptionClient {
             commit_timeout_secs = config.commit_timeout_secs,
             "OpenAI realtime transcription client initialized"
         );
-        Self { config }
+        Self {
+            config,
+            network: Arc::default(),
+        }
+    }
+
+    pub fn configure_network(&self, network: &NetworkConfig) {
+        match self.network.write() {
+            Ok(mut current) => *current = network.clone(),
+            Err(poisoned) => *poisoned.into_inner() = network.clone(),
+        }
     }
 
     pub fn model_supports_realtime(&self) -> bool {
@@ -146,8 +176,13 @@ impl OpenAiRealtimeTranscriptionClient {
             oneshot::channel::<Result<TranscriptionResult, TranscriptionError>>();
 
         let runtime_config = self.config.clone();
+        let network = match self.network.read() {
+            Ok(network) => network.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
         tauri::async_runtime::spawn(async move {
-            let result = run_realtime_session(runtime_config, api_key, options, command_rx).await;
+            let result =
+                run_realtime_session(runtime_config, network, api_key, options, command_rx).await;
             match &result {
                 Ok(transcription) => info!(
                     transcript_chars = transcription.text.chars().count(),
@@ -343,6 +378,7 @@ enum ParsedServerEvent {
 
 async fn run_realtime_session(
     config: OpenAiRealtimeTranscriptionConfig,
+    network: NetworkConfig,
     api_key: String,
     options: TranscriptionOptions,
     mut command_rx: mpsc::Receiver<RealtimeCommand>,
@@ -374,7 +410,8 @@ async fn run_realtime_session(
         "connecting realtime transcription websocket"
     );
     let connect_timeout = Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS);
-    let (ws_stream, response) = tokio::time::timeout(connect_timeout, connect_async(request))
+    let connect = connect_websocket(&network, request);
+    let (ws_stream, response) = tokio::time::timeout(connect_timeout, connect)
         .await
         .map_err(|_| {
             let mapped = TranscriptionError::Network(format!(
@@ -390,8 +427,7 @@ async fn run_realtime_session(
             );
             mapped
         })?
-        .map_err(|error| {
-            let mapped = map_websocket_error(error);
+        .map_err(|mapped| {
             warn!(
                 endpoint = %endpoint,
                 realtime_model = %config.realtime_model,
@@ -587,9 +623,31 @@ async fn run_realtime_session(
         language: request_language,
         duration_secs: None,
         confidence: None,
+        segments: Vec::new(),
+        model: Some(config.transcription_model.clone()),
     })
 }
 
+async fn connect_websocket(
+    network: &NetworkConfig,
+    request: Request,
+) -> Result<(RealtimeWebSocket, Response), TranscriptionError> {
+    let uri = request.uri();
+    let host = uri.host().unwrap_or_default().to_string();
+    let default_port = if uri.scheme_str() == Some("ws") {
+        WS_DEFAULT_PORT
+    } else {
+        WSS_DEFAULT_PORT
+    };
+    let port = uri.port_u16().unwrap_or(default_port);
+    let stream = network::connect_tcp(network, &host, port)
+        .await
+        .map_err(TranscriptionError::Network)?;
+    client_async_tls(request, stream)
+        .await
+        .map_err(map_websocket_error)
+}
+
 fn resolve_realtime_endpoint(endpoint: &str) -> Result<String, TranscriptionError> {
     let mut url = Url::parse(endpoint).map_err(|error| {
         TranscriptionError::Provider(format!(
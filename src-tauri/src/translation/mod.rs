@@ -0,0 +1,113 @@
+use tracing::debug;
+
+use crate::llm::{ChatCompletionClient, ChatCompletionConfig};
+
+const TRANSLATION_MODEL_ENV: &str = "OPENAI_TRANSLATION_MODEL";
+
+#[derive(Debug, Clone)]
+pub struct Translator {
+    client: ChatCompletionClient,
+}
+
+impl Translator {
+    pub fn new(config: ChatCompletionConfig) -> Self {
+        Self {
+            client: ChatCompletionClient::new(config),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(ChatCompletionConfig::from_env(TRANSLATION_MODEL_ENV))
+    }
+
+    pub async fn translate(
+        &self,
+        api_key: &str,
+        text: &str,
+        source_language: Option<&str>,
+        target_language: &str,
+    ) -> Result<String, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(String::new());
+        }
+
+        debug!(
+            model = %self.client.model(),
+            source_language = ?source_language,
+            target_language,
+            transcript_chars = text.chars().count(),
+            "requesting transcript translation"
+        );
+        let translated = self
+            .client
+            .complete(
+                api_key,
+                &translation_prompt(source_language, target_language),
+                text,
+                false,
+            )
+            .await?;
+
+        Ok(translated.trim().to_string())
+    }
+}
+
+fn translation_prompt(source_language: Option<&str>, target_language: &str) -> String {
+    let source = match source_language {
+        Some(source_language) => format!("from language code `{source_language}` "),
+        None => String::new(),
+    };
+
+    format!(
+        "Translate the user's dictated text {source}into language code `{target_language}`. \
+         Reply with only the translation, preserving meaning, tone, formatting, and proper \
+         nouns. If the text is already in the target language, return it unchanged."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server};
+
+    use super::{translation_prompt, Translator};
+    use crate::llm::ChatCompletionConfig;
+
+    #[test]
+    fn prompt_mentions_source_only_when_known() {
+        let prompt = translation_prompt(Some("de"), "en");
+        assert!(prompt.contains("from language code `de` into language code `en`"));
+
+        let prompt = translation_prompt(None, "fr");
+        assert!(prompt.contains("text into language code `fr`"));
+    }
+
+    #[tokio::test]
+    async fn translate_returns_trimmed_completion() {
+        let mut server = Server::new_async().await;
+        let request_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::Regex("language code `es`".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices": [{"message": {"content": "  Hola mundo \n"}}]}"#)
+            .create_async()
+            .await;
+        let translator = Translator::new(ChatCompletionConfig {
+            endpoint: format!("{}/v1/chat/completions", server.url()),
+            ..ChatCompletionConfig::default()
+        });
+
+        let translated = translator
+            .translate("test-key", "Hello world", Some("en"), "es")
+            .await
+            .expect("translation should succeed");
+
+        request_mock.assert_async().await;
+        assert_eq!(translated, "Hola mundo");
+        assert_eq!(
+            translator.translate("test-key", "  ", None, "es").await,
+            Ok(String::new())
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use tauri::{
+    AppHandle, LogicalPosition, Manager, PhysicalPosition, PhysicalSize, Rect, WebviewUrl,
+    WebviewWindow, WebviewWindowBuilder,
+};
+use tracing::{debug, info, warn};
+
+use crate::window_manager::{self, WindowParams, WindowTarget};
+
+pub const POPOVER_WINDOW_LABEL: &str = "tray-popover";
+
+const POPOVER_WINDOW_WIDTH: f64 = 320.0;
+const POPOVER_WINDOW_HEIGHT: f64 = 320.0;
+const POPOVER_ANCHOR_GAP: f64 = 6.0;
+const POPOVER_EDGE_MARGIN: f64 = 8.0;
+
+pub fn toggle_popover(app: &AppHandle, anchor: Rect) {
+    if let Some(window) = app.get_webview_window(POPOVER_WINDOW_LABEL) {
+        if window.is_visible().unwrap_or(false) {
+            hide_popover(app);
+            return;
+        }
+        show_popover(app, &window, anchor);
+        return;
+    }
+
+    match create_popover_window(app) {
+        Ok(window) => {
+            info!("tray popover window initialized");
+            show_popover(app, &window, anchor);
+        }
+        Err(error) => warn!(%error, "tray popover window initialization failed"),
+    }
+}
+
+pub fn hide_popover(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(POPOVER_WINDOW_LABEL) else {
+        return;
+    };
+    debug!("hiding tray popover");
+    if let Err(error) = window.hide() {
+        warn!(%error, "failed to hide tray popover");
+    }
+}
+
+#[tauri::command]
+pub fn open_main_window_from_popover(app: AppHandle) {
+    info!("main window requested from tray popover");
+    hide_popover(&app);
+    window_manager::open(&app, WindowTarget::Main, WindowParams::default());
+}
+
+fn create_popover_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+    WebviewWindowBuilder::new(
+        app,
+        POPOVER_WINDOW_LABEL,
+        WebviewUrl::App("popover.html".into()),
+    )
+    .title("Buzz")
+    .inner_size(POPOVER_WINDOW_WIDTH, POPOVER_WINDOW_HEIGHT)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .visible_on_all_workspaces(true)
+    .visible(false)
+    .transparent(true)
+    .build()
+    .map_err(|error| format!("failed to create tray popover window: {error}"))
+}
+
+fn show_popover(app: &AppHandle, window: &WebviewWindow, anchor: Rect) {
+    let scale_factor = window.scale_factor().unwrap_or(1.0);
+    let anchor_position = anchor.position.to_physical::<f64>(scale_factor);
+    let anchor_size = anchor.size.to_physical::<f64>(scale_factor);
+
+    match app.monitor_from_point(anchor_position.x, anchor_position.y) {
+        Ok(Some(monitor)) => {
+            let position = popover_position(
+                anchor_position,
+                anchor_size,
+                monitor.work_area().position,
+                monitor.work_area().size,
+                monitor.scale_factor(),
+            );
+            if let Err(error) = window.set_position(position) {
+                warn!(%error, "failed to position tray popover");
+            }
+        }
+        Ok(None) => debug!("no monitor found for tray icon; keeping popover position"),
+        Err(error) => warn!(%error, "failed to resolve monitor for tray popover"),
+    }
+
+    if let Err(error) = window.show() {
+        warn!(%error, "failed to show tray popover");
+    }
+    if let Err(error) = window.set_focus() {
+        warn!(%error, "failed to focus tray popover");
+    }
+}
+
+fn popover_position(
+    anchor_position: PhysicalPosition<f64>,
+    anchor_size: PhysicalSize<f64>,
+    work_area_position: PhysicalPosition<i32>,
+    work_area_size: PhysicalSize<u32>,
+    scale_factor: f64,
+) -> LogicalPosition<f64> {
+    let anchor_center_x = (anchor_position.x + anchor_size.width / 2.0) / scale_factor;
+    let anchor_bottom = (anchor_position.y + anchor_size.height) / scale_factor;
+    let work_area_x = f64::from(work_area_position.x) / scale_factor;
+    let work_area_y = f64::from(work_area_position.y) / scale_factor;
+    let work_area_width = f64::from(work_area_size.width) / scale_factor;
+
+    let min_x = work_area_x + POPOVER_EDGE_MARGIN;
+    let max_x =
+        (work_area_x + work_area_width - POPOVER_WINDOW_WIDTH - POPOVER_EDGE_MARGIN).max(min_x);
+
+    LogicalPosition::new(
+        (anchor_center_x - POPOVER_WINDOW_WIDTH / 2.0).clamp(min_x, max_x),
+        anchor_bottom.max(work_area_y) + POPOVER_ANCHOR_GAP,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::{PhysicalPosition, PhysicalSize};
+
+    use super::{popover_position, POPOVER_ANCHOR_GAP, POPOVER_EDGE_MARGIN, POPOVER_WINDOW_WIDTH};
+
+    #[test]
+    fn popover_centers_under_tray_icon() {
+        let position = popover_position(
+            PhysicalPosition::new(1600.0, 0.0),
+            PhysicalSize::new(44.0, 48.0),
+            PhysicalPosition::new(0, 48),
+            PhysicalSize::new(3024, 1840),
+            2.0,
+        );
+
+        assert_eq!(position.x, 811.0 - POPOVER_WINDOW_WIDTH / 2.0);
+        assert_eq!(position.y, 24.0 + POPOVER_ANCHOR_GAP);
+    }
+
+    #[test]
+    fn popover_stays_inside_work_area_near_screen_edge() {
+        let position = popover_position(
+            PhysicalPosition::new(1490.0, 0.0),
+            PhysicalSize::new(22.0, 24.0),
+            PhysicalPosition::new(0, 24),
+            PhysicalSize::new(1512, 958),
+            1.0,
+        );
+
+        assert_eq!(
+            position.x,
+            1512.0 - POPOVER_WINDOW_WIDTH - POPOVER_EDGE_MARGIN
+        );
+    }
+}
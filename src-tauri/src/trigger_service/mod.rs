@@ -0,0 +1,530 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use hidapi::{HidApi, HidDevice};
+use midir::{MidiInput, MidiInputConnection};
+use reqwest::Url;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_global_shortcut::ShortcutState;
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    hotkey_service::{HotkeyService, RecordingTransition},
+    settings_store::{parse_hid_trigger_device, VoiceSettings},
+    window_manager,
+};
+
+pub const URL_SCHEME: &str = "buzz";
+
+const MIDI_CLIENT_NAME: &str = "Buzz Trigger";
+const MIDI_NOTE_OFF: u8 = 0x80;
+const MIDI_NOTE_ON: u8 = 0x90;
+const WORKER_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const HID_READ_TIMEOUT_MS: i32 = 250;
+const HID_REPORT_BUFFER_BYTES: usize = 64;
+const HID_MAX_DESCRIPTOR_BYTES: usize = 4096;
+const HID_LONG_ITEM_PREFIX: u8 = 0xFE;
+const HID_ITEM_SIZE_MASK: u8 = 0x03;
+const HID_REPORT_ID_ITEM: u8 = 0x84;
+const MIDI_RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+const REOPEN_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+const REOPEN_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerSource {
+    UrlScheme,
+    Midi,
+    HidPedal,
+    WakeWord,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerAction {
+    Start,
+    Stop,
+    Toggle,
+    Press,
+    Release,
+}
+
+#[derive(Debug)]
+struct TriggerWorker {
+    binding: String,
+    stop: Arc<AtomicBool>,
+}
+
+impl TriggerWorker {
+    fn spawn(binding: String, run: impl FnOnce(Arc<AtomicBool>) + Send + 'static) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = Arc::clone(&stop);
+        thread::spawn(move || run(worker_stop));
+        Self { binding, stop }
+    }
+
+    fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TriggerService {
+    midi_worker: Mutex<Option<TriggerWorker>>,
+    hid_worker: Mutex<Option<TriggerWorker>>,
+}
+
+impl TriggerService {
+    pub fn new() -> Self {
+        debug!("trigger service initialized");
+        Self::default()
+    }
+
+    pub fn sync_with_settings(&self, app: &AppHandle, settings: &VoiceSettings) {
+        let midi_binding = settings.midi_trigger_note.map(|note| note.to_string());
+        sync_worker(&self.midi_worker, midi_binding, |binding| {
+            let note = binding.parse::<u8>().ok()?;
+            let app = app.clone();
+            Some(TriggerWorker::spawn(binding, move |stop| {
+                run_midi_worker(app, note, stop)
+            }))
+        });
+
+        sync_worker(
+            &self.hid_worker,
+            settings.hid_trigger_device.clone(),
+            |binding| {
+                let (vendor_id, product_id) = parse_hid_trigger_device(&binding).ok()?;
+                let app = app.clone();
+                Some(TriggerWorker::spawn(binding, move |stop| {
+                    run_hid_worker(app, vendor_id, product_id, stop)
+                }))
+            },
+        );
+    }
+}
+
+pub fn register_url_scheme_handler(app: &AppHandle) {
+    let handler_app = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_url(&handler_app, url.as_str());
+        }
+    });
+
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => {
+            for url in urls {
+                handle_url(app, url.as_str());
+            }
+        }
+        Ok(None) => {}
+        Err(error) => warn!(%error, "failed to read launch url"),
+    }
+}
+
+pub fn dispatch_trigger(app: &AppHandle, source: TriggerSource, action: TriggerAction) -> bool {
+    let hotkey_service = app.state::<HotkeyService>();
+    info!(?source, ?action, "external trigger received");
+
+    match action {
+        TriggerAction::Start => {
+            hotkey_service.request_recording_transition(app, RecordingTransition::Started)
+        }
+        TriggerAction::Stop => {
+            hotkey_service.request_recording_transition(app, RecordingTransition::Stopped)
+        }
+        TriggerAction::Toggle => hotkey_service.request_recording_toggle(app),
+        TriggerAction::Press => {
+            hotkey_service.handle_shortcut_event(app, ShortcutState::Pressed);
+            true
+        }
+        TriggerAction::Release => {
+            hotkey_service.handle_shortcut_event(app, ShortcutState::Released);
+            true
+        }
+    }
+}
+
+fn handle_url(app: &AppHandle, url: &str) {
+    if let Some((target, params)) = window_manager::parse_window_url(url) {
+        info!(target = target.as_str(), "window deep link received");
+        window_manager::open(app, target, params);
+        return;
+    }
+
+    match parse_url_action(url) {
+        Ok(action) => {
+            dispatch_trigger(app, TriggerSource::UrlScheme, action);
+        }
+        Err(error) => warn!(%error, "ignoring unsupported trigger url"),
+    }
+}
+
+fn sync_worker(
+    slot: &Mutex<Option<TriggerWorker>>,
+    binding: Option<String>,
+    spawn: impl FnOnce(String) -> Option<TriggerWorker>,
+) {
+    let mut worker = match slot.lock() {
+        Ok(worker) => worker,
+        Err(_) => {
+            error!("trigger worker lock poisoned while applying settings");
+            return;
+        }
+    };
+
+    if worker.as_ref().map(|worker| &worker.binding) == binding.as_ref() {
+        return;
+    }
+
+    if let Some(previous) = worker.take() {
+        previous.stop();
+        info!(binding = %previous.binding, "trigger worker stopped");
+    }
+
+    *worker = binding.and_then(spawn);
+}
+
+// Ports come and go as devices are plugged in, so the worker reconnects whenever the list of
+// ports changes, including when none were available at first.
+fn run_midi_worker(app: AppHandle, note: u8, stop: Arc<AtomicBool>) {
+    let mut connected_ports = None;
+    let mut connections: Vec<MidiInputConnection<()>> = Vec::new();
+    while !stop.load(Ordering::SeqCst) {
+        let ports = midi_port_names();
+        if connected_ports.as_ref() != Some(&ports) {
+            for connection in connections.drain(..) {
+                connection.close();
+            }
+            connections = connect_midi_ports(&app, note);
+            if connections.is_empty() {
+                warn!(
+                    note,
+                    "no midi inputs available for trigger; waiting for a device"
+                );
+            }
+            connected_ports = Some(ports);
+        }
+        if !sleep_unless_stopped(&stop, MIDI_RESCAN_INTERVAL) {
+            break;
+        }
+    }
+
+    for connection in connections {
+        connection.close();
+    }
+}
+
+fn midi_port_names() -> Vec<String> {
+    match MidiInput::new(MIDI_CLIENT_NAME) {
+        Ok(input) => input
+            .ports()
+            .iter()
+            .map(|port| input.port_name(port).unwrap_or_default())
+            .collect(),
+        Err(error) => {
+            debug!(%error, "failed to initialize midi input");
+            Vec::new()
+        }
+    }
+}
+
+fn connect_midi_ports(app: &AppHandle, note: u8) -> Vec<MidiInputConnection<()>> {
+    let port_count = match MidiInput::new(MIDI_CLIENT_NAME) {
+        Ok(input) => input.ports().len(),
+        Err(error) => {
+            warn!(%error, "failed to initialize midi input");
+            return Vec::new();
+        }
+    };
+
+    let mut connections = Vec::with_capacity(port_count);
+    for index in 0..port_count {
+        let input = match MidiInput::new(MIDI_CLIENT_NAME) {
+            Ok(input) => input,
+            Err(error) => {
+                warn!(%error, "failed to initialize midi input");
+                continue;
+            }
+        };
+        let Some(port) = input.ports().into_iter().nth(index) else {
+            continue;
+        };
+        let port_name = input.port_name(&port).unwrap_or_default();
+        let callback_app = app.clone();
+        match input.connect(
+            &port,
+            MIDI_CLIENT_NAME,
+            move |_timestamp, message, _| {
+                if let Some(action) = midi_message_action(message, note) {
+                    dispatch_trigger(&callback_app, TriggerSource::Midi, action);
+                }
+            },
+            (),
+        ) {
+            Ok(connection) => {
+                info!(port = %port_name, note, "listening for midi trigger");
+                connections.push(connection);
+            }
+            Err(error) => warn!(port = %port_name, %error, "failed to connect midi input"),
+        }
+    }
+    connections
+}
+
+fn run_hid_worker(app: AppHandle, vendor_id: u16, product_id: u16, stop: Arc<AtomicBool>) {
+    let api = match HidApi::new() {
+        Ok(api) => api,
+        Err(error) => {
+            warn!(%error, "failed to initialize hid api");
+            return;
+        }
+    };
+
+    let mut backoff = REOPEN_BACKOFF_INITIAL;
+    while !stop.load(Ordering::SeqCst) {
+        let device = match api.open(vendor_id, product_id) {
+            Ok(device) => device,
+            Err(error) => {
+                if backoff == REOPEN_BACKOFF_INITIAL {
+                    warn!(vendor_id, product_id, %error, "failed to open hid trigger device; retrying");
+                }
+                if !sleep_unless_stopped(&stop, backoff) {
+                    return;
+                }
+                backoff = next_backoff(backoff);
+                continue;
+            }
+        };
+        backoff = REOPEN_BACKOFF_INITIAL;
+        info!(vendor_id, product_id, "listening for hid trigger");
+        read_hid_reports(&app, &device, &stop);
+    }
+}
+
+fn read_hid_reports(app: &AppHandle, device: &HidDevice, stop: &AtomicBool) {
+    let has_report_id = hid_reports_have_id(device);
+    let mut buffer = [0_u8; HID_REPORT_BUFFER_BYTES];
+    let mut pressed = false;
+    while !stop.load(Ordering::SeqCst) {
+        let bytes_read = match device.read_timeout(&mut buffer, HID_READ_TIMEOUT_MS) {
+            Ok(bytes_read) => bytes_read,
+            Err(error) => {
+                warn!(%error, "hid trigger device read failed; reopening");
+                if pressed {
+                    dispatch_trigger(app, TriggerSource::HidPedal, TriggerAction::Release);
+                }
+                return;
+            }
+        };
+
+        if let Some(action) = hid_report_action(&buffer[..bytes_read], has_report_id, pressed) {
+            pressed = action == TriggerAction::Press;
+            dispatch_trigger(app, TriggerSource::HidPedal, action);
+        }
+    }
+}
+
+fn hid_reports_have_id(device: &HidDevice) -> bool {
+    let mut descriptor = [0_u8; HID_MAX_DESCRIPTOR_BYTES];
+    match device.get_report_descriptor(&mut descriptor) {
+        Ok(length) => descriptor_declares_report_ids(&descriptor[..length]),
+        Err(error) => {
+            warn!(%error, "failed to read hid report descriptor; assuming reports carry no id");
+            false
+        }
+    }
+}
+
+// Devices that declare a Report ID item prefix every report with that id, which is never zero.
+fn descriptor_declares_report_ids(descriptor: &[u8]) -> bool {
+    let mut offset = 0;
+    while let Some(&prefix) = descriptor.get(offset) {
+        if prefix == HID_LONG_ITEM_PREFIX {
+            let data_len = descriptor.get(offset + 1).copied().unwrap_or_default();
+            offset += 3 + usize::from(data_len);
+            continue;
+        }
+        if prefix & !HID_ITEM_SIZE_MASK == HID_REPORT_ID_ITEM {
+            return true;
+        }
+        offset += 1 + match prefix & HID_ITEM_SIZE_MASK {
+            3 => 4,
+            size => usize::from(size),
+        };
+    }
+    false
+}
+
+fn next_backoff(current: Duration) -> Duration {
+    (current * 2).min(REOPEN_BACKOFF_MAX)
+}
+
+fn sleep_unless_stopped(stop: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        thread::sleep(WORKER_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+fn parse_url_action(url: &str) -> Result<TriggerAction, String> {
+    let parsed =
+        Url::parse(url).map_err(|error| format!("Failed to parse URL `{url}`: {error}"))?;
+    if parsed.scheme() != URL_SCHEME {
+        return Err(format!("Unsupported URL scheme `{}`", parsed.scheme()));
+    }
+
+    let action = parsed
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| parsed.path().trim_matches('/'));
+    match action.to_ascii_lowercase().as_str() {
+        "start" => Ok(TriggerAction::Start),
+        "stop" => Ok(TriggerAction::Stop),
+        "toggle" => Ok(TriggerAction::Toggle),
+        other => Err(format!("Unsupported trigger action `{other}`")),
+    }
+}
+
+fn midi_message_action(message: &[u8], note: u8) -> Option<TriggerAction> {
+    let [status, message_note, velocity, ..] = *message else {
+        return None;
+    };
+    if message_note != note {
+        return None;
+    }
+
+    match status & 0xF0 {
+        MIDI_NOTE_ON if velocity > 0 => Some(TriggerAction::Press),
+        MIDI_NOTE_ON | MIDI_NOTE_OFF => Some(TriggerAction::Release),
+        _ => None,
+    }
+}
+
+fn hid_report_action(
+    report: &[u8],
+    has_report_id: bool,
+    was_pressed: bool,
+) -> Option<TriggerAction> {
+    let payload = if has_report_id {
+        report.get(1..).unwrap_or_default()
+    } else {
+        report
+    };
+    if payload.is_empty() {
+        return None;
+    }
+
+    let is_pressed = payload.iter().any(|byte| *byte != 0);
+    match (was_pressed, is_pressed) {
+        (false, true) => Some(TriggerAction::Press),
+        (true, false) => Some(TriggerAction::Release),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        descriptor_declares_report_ids, hid_report_action, midi_message_action, next_backoff,
+        parse_url_action, TriggerAction, REOPEN_BACKOFF_INITIAL, REOPEN_BACKOFF_MAX,
+    };
+
+    #[test]
+    fn url_actions_map_to_pipeline_actions() {
+        assert_eq!(parse_url_action("buzz://start"), Ok(TriggerAction::Start));
+        assert_eq!(parse_url_action("buzz://STOP"), Ok(TriggerAction::Stop));
+        assert_eq!(parse_url_action("buzz:toggle"), Ok(TriggerAction::Toggle));
+        assert!(parse_url_action("buzz://launch").is_err());
+        assert!(parse_url_action("https://start").is_err());
+    }
+
+    #[test]
+    fn midi_note_on_and_off_map_to_press_and_release() {
+        assert_eq!(
+            midi_message_action(&[0x90, 60, 100], 60),
+            Some(TriggerAction::Press)
+        );
+        assert_eq!(
+            midi_message_action(&[0x91, 60, 0], 60),
+            Some(TriggerAction::Release)
+        );
+        assert_eq!(
+            midi_message_action(&[0x80, 60, 64], 60),
+            Some(TriggerAction::Release)
+        );
+        assert_eq!(midi_message_action(&[0x90, 61, 100], 60), None);
+        assert_eq!(midi_message_action(&[0xB0, 60, 127], 60), None);
+        assert_eq!(midi_message_action(&[0xF8], 60), None);
+    }
+
+    #[test]
+    fn hid_reports_only_emit_on_pedal_edges() {
+        assert_eq!(
+            hid_report_action(&[0, 4, 0], false, false),
+            Some(TriggerAction::Press)
+        );
+        assert_eq!(hid_report_action(&[0, 4, 0], false, true), None);
+        assert_eq!(
+            hid_report_action(&[0, 0, 0], false, true),
+            Some(TriggerAction::Release)
+        );
+        assert_eq!(hid_report_action(&[0, 0, 0], false, false), None);
+        assert_eq!(hid_report_action(&[], false, true), None);
+    }
+
+    #[test]
+    fn hid_report_ids_are_not_mistaken_for_presses() {
+        assert_eq!(hid_report_action(&[1, 0, 0], true, false), None);
+        assert_eq!(
+            hid_report_action(&[1, 0, 2], true, false),
+            Some(TriggerAction::Press)
+        );
+        assert_eq!(
+            hid_report_action(&[1, 0, 0], true, true),
+            Some(TriggerAction::Release)
+        );
+        assert_eq!(hid_report_action(&[1], true, true), None);
+    }
+
+    #[test]
+    fn report_ids_are_found_in_the_descriptor() {
+        // Usage Page (Generic Desktop), Usage (Keyboard), Collection, Report ID 1, ...
+        let with_id = [
+            0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0x85, 0x01, 0x75, 0x08, 0xC0,
+        ];
+        let without_id = [0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0x75, 0x08, 0xC0];
+        // A long item whose data happens to contain the Report ID prefix.
+        let long_item = [0xFE, 0x02, 0x10, 0x85, 0x01, 0xC0];
+
+        assert!(descriptor_declares_report_ids(&with_id));
+        assert!(!descriptor_declares_report_ids(&without_id));
+        assert!(!descriptor_declares_report_ids(&long_item));
+        assert!(!descriptor_declares_report_ids(&[]));
+    }
+
+    #[test]
+    fn reopen_backoff_doubles_up_to_the_cap() {
+        assert_eq!(next_backoff(REOPEN_BACKOFF_INITIAL), Duration::from_secs(2));
+        assert_eq!(next_backoff(Duration::from_secs(20)), REOPEN_BACKOFF_MAX);
+        assert_eq!(next_backoff(REOPEN_BACKOFF_MAX), REOPEN_BACKOFF_MAX);
+    }
+}
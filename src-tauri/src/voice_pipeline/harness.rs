@@ -0,0 +1,410 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use super::{
+    NothingHeardReason, PipelineError, PipelineErrorStage, PipelineTranscript, StageError,
+    VoicePipeline, VoicePipelineDelegate,
+};
+use crate::{
+    audio_capture_service::RecordedAudio,
+    history_store::TranscriptionDetails,
+    status_notifier::{AppStatus, StatusSource},
+};
+
+pub trait RecordingBackend: Send + Sync {
+    fn start(&self) -> Result<(), String>;
+    fn stop(&self) -> Result<RecordedAudio, String>;
+    fn cancel(&self) {}
+}
+
+pub trait InsertionBackend: Send + Sync {
+    fn insert(&self, text: &str) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HarnessEvent {
+    RecordingStarted(bool),
+    RecordingStopped(bool),
+    Status(AppStatus),
+    Transcript(String),
+    HistorySaved(String),
+    NothingHeard(NothingHeardReason),
+    Error(PipelineErrorStage),
+}
+
+#[derive(Debug, Default)]
+pub struct FakeRecorder {
+    recordings: Mutex<VecDeque<RecordedAudio>>,
+    start_failure: Mutex<Option<String>>,
+    stop_failure: Mutex<Option<String>>,
+    recording: AtomicBool,
+}
+
+impl FakeRecorder {
+    pub fn queue_recording(&self, audio: RecordedAudio) {
+        self.recordings.lock().unwrap().push_back(audio);
+    }
+
+    pub fn fail_next_start(&self, message: &str) {
+        *self.start_failure.lock().unwrap() = Some(message.to_string());
+    }
+
+    pub fn fail_next_stop(&self, message: &str) {
+        *self.stop_failure.lock().unwrap() = Some(message.to_string());
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::SeqCst)
+    }
+}
+
+impl RecordingBackend for FakeRecorder {
+    fn start(&self) -> Result<(), String> {
+        if let Some(message) = self.start_failure.lock().unwrap().take() {
+            return Err(message);
+        }
+        self.recording.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&self) -> Result<RecordedAudio, String> {
+        self.recording.store(false, Ordering::SeqCst);
+        if let Some(message) = self.stop_failure.lock().unwrap().take() {
+            return Err(message);
+        }
+        self.recordings
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| "No recording queued in harness".to_string())
+    }
+
+    fn cancel(&self) {
+        self.recording.store(false, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FakeInserter {
+    inserted: Mutex<Vec<String>>,
+    failure: Mutex<Option<String>>,
+}
+
+impl FakeInserter {
+    pub fn fail_next_insert(&self, message: &str) {
+        *self.failure.lock().unwrap() = Some(message.to_string());
+    }
+
+    pub fn inserted(&self) -> Vec<String> {
+        self.inserted.lock().unwrap().clone()
+    }
+}
+
+impl InsertionBackend for FakeInserter {
+    fn insert(&self, text: &str) -> Result<(), String> {
+        if let Some(message) = self.failure.lock().unwrap().take() {
+            return Err(message);
+        }
+        self.inserted.lock().unwrap().push(text.to_string());
+        Ok(())
+    }
+}
+
+pub struct HarnessDelegate<R: RecordingBackend, I: InsertionBackend> {
+    pub recorder: R,
+    pub inserter: I,
+    transcriptions: Mutex<VecDeque<Result<PipelineTranscript, String>>>,
+    transcribe_gate: Option<Arc<Notify>>,
+    cancelled: AtomicBool,
+    events: Mutex<Vec<HarnessEvent>>,
+}
+
+impl<R: RecordingBackend, I: InsertionBackend> HarnessDelegate<R, I> {
+    pub fn new(recorder: R, inserter: I) -> Self {
+        Self {
+            recorder,
+            inserter,
+            transcriptions: Mutex::new(VecDeque::new()),
+            transcribe_gate: None,
+            cancelled: AtomicBool::new(false),
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn queue_transcription(&self, result: Result<&str, &str>) {
+        let result = result
+            .map(|text| PipelineTranscript {
+                text: text.to_string(),
+                duration_secs: None,
+                language: Some("en".to_string()),
+                provider: "harness".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
+            })
+            .map_err(str::to_string);
+        self.transcriptions.lock().unwrap().push_back(result);
+    }
+
+    pub fn with_transcribe_gate(mut self, gate: Arc<Notify>) -> Self {
+        self.transcribe_gate = Some(gate);
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.recorder.cancel();
+    }
+
+    pub fn events(&self) -> Vec<HarnessEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    pub fn statuses(&self) -> Vec<AppStatus> {
+        self.events()
+            .into_iter()
+            .filter_map(|event| match event {
+                HarnessEvent::Status(status) => Some(status),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_active(&self) -> bool {
+        !self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn push(&self, event: HarnessEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[async_trait]
+impl<R: RecordingBackend, I: InsertionBackend> VoicePipelineDelegate for HarnessDelegate<R, I> {
+    fn set_status(&self, status: AppStatus, _source: StatusSource) {
+        if self.is_active() {
+            self.push(HarnessEvent::Status(status));
+        }
+    }
+
+    fn emit_transcript(&self, transcript: &str) {
+        if self.is_active() {
+            self.push(HarnessEvent::Transcript(transcript.to_string()));
+        }
+    }
+
+    fn emit_error(&self, error: &PipelineError) {
+        if self.is_active() {
+            self.push(HarnessEvent::Error(error.stage.clone()));
+        }
+    }
+
+    fn emit_nothing_heard(&self, reason: NothingHeardReason, _duration_ms: u64) {
+        if self.is_active() {
+            self.push(HarnessEvent::NothingHeard(reason));
+        }
+    }
+
+    fn on_recording_started(&self, success: bool) {
+        self.push(HarnessEvent::RecordingStarted(success));
+    }
+
+    fn on_recording_stopped(&self, success: bool) {
+        self.push(HarnessEvent::RecordingStopped(success));
+    }
+
+    fn start_recording(&self) -> Result<(), String> {
+        self.recorder.start()
+    }
+
+    fn stop_recording(&self) -> Result<RecordedAudio, String> {
+        self.recorder.stop()
+    }
+
+    async fn transcribe(
+        &self,
+        _recorded_audio: RecordedAudio,
+    ) -> Result<PipelineTranscript, StageError> {
+        if let Some(gate) = &self.transcribe_gate {
+            gate.notified().await;
+        }
+        self.transcriptions
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Err("No transcription queued in harness".to_string()))
+            .map_err(StageError::from)
+    }
+
+    async fn insert_text(&self, transcript: &str) -> Result<(), String> {
+        if !self.is_active() {
+            return Ok(());
+        }
+        self.inserter.insert(transcript)
+    }
+
+    fn save_history_entry(&self, transcript: &PipelineTranscript) -> Result<(), String> {
+        if self.is_active() {
+            self.push(HarnessEvent::HistorySaved(transcript.text.clone()));
+        }
+        Ok(())
+    }
+}
+
+pub async fn stop_and_process<D: VoicePipelineDelegate>(pipeline: &VoicePipeline, delegate: &D) {
+    if let Some(recorded_audio) = pipeline.finish_recording(delegate).await {
+        pipeline.process_recording(delegate, recorded_audio).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use tokio::sync::Notify;
+
+    use super::{stop_and_process, FakeInserter, FakeRecorder, HarnessDelegate, HarnessEvent};
+    use crate::{
+        audio_capture_service::RecordedAudio,
+        status_notifier::AppStatus,
+        voice_pipeline::{PipelineErrorStage, PipelineTimeouts, VoicePipeline},
+    };
+
+    fn harness() -> HarnessDelegate<FakeRecorder, FakeInserter> {
+        let recorder = FakeRecorder::default();
+        recorder.queue_recording(RecordedAudio::from_wav_bytes(
+            vec![1, 2, 3],
+            16_000,
+            1,
+            1_000,
+            "harness-device".to_string(),
+            "Harness Device".to_string(),
+        ));
+        HarnessDelegate::new(recorder, FakeInserter::default())
+    }
+
+    #[tokio::test]
+    async fn started_to_inserted_flow_runs_every_stage_in_order() {
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+        let delegate = harness();
+        delegate.queue_transcription(Ok("hello harness"));
+
+        pipeline.handle_hotkey_started(&delegate).await;
+        assert!(delegate.recorder.is_recording());
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert!(!delegate.recorder.is_recording());
+        assert_eq!(delegate.inserter.inserted(), vec!["hello harness"]);
+        assert_eq!(
+            delegate.events(),
+            vec![
+                HarnessEvent::RecordingStarted(true),
+                HarnessEvent::Status(AppStatus::Listening),
+                HarnessEvent::Status(AppStatus::Transcribing),
+                HarnessEvent::RecordingStopped(true),
+                HarnessEvent::Transcript("hello harness".to_string()),
+                HarnessEvent::HistorySaved("hello harness".to_string()),
+                HarnessEvent::Status(AppStatus::Idle),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelling_mid_transcription_drops_output_and_insertion() {
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+        let gate = Arc::new(Notify::new());
+        let delegate = harness().with_transcribe_gate(Arc::clone(&gate));
+        delegate.queue_transcription(Ok("too late"));
+
+        pipeline.handle_hotkey_started(&delegate).await;
+        tokio::join!(stop_and_process(&pipeline, &delegate), async {
+            delegate.cancel();
+            gate.notify_one();
+        });
+
+        assert!(delegate.inserter.inserted().is_empty());
+        assert!(!delegate
+            .events()
+            .iter()
+            .any(|event| matches!(event, HarnessEvent::Transcript(_))));
+        assert_eq!(
+            delegate.statuses(),
+            vec![AppStatus::Listening, AppStatus::Transcribing]
+        );
+    }
+
+    #[tokio::test]
+    async fn transcription_past_its_timeout_fails_the_stage() {
+        let pipeline = VoicePipeline::new(Duration::ZERO).with_timeouts(PipelineTimeouts {
+            transcription: Some(Duration::from_millis(20)),
+            ..PipelineTimeouts::default()
+        });
+        let delegate = harness().with_transcribe_gate(Arc::new(Notify::new()));
+        delegate.queue_transcription(Ok("never delivered"));
+
+        pipeline.handle_hotkey_started(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert!(delegate.inserter.inserted().is_empty());
+        assert!(delegate
+            .events()
+            .contains(&HarnessEvent::Error(PipelineErrorStage::Transcription)));
+        assert_eq!(delegate.statuses().last(), Some(&AppStatus::Idle));
+    }
+
+    #[tokio::test]
+    async fn stage_failures_surface_as_errors_and_reset_to_idle() {
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+
+        let start_failure = harness();
+        start_failure.recorder.fail_next_start("microphone busy");
+        pipeline.handle_hotkey_started(&start_failure).await;
+        assert_eq!(
+            start_failure.events(),
+            vec![
+                HarnessEvent::RecordingStarted(false),
+                HarnessEvent::Error(PipelineErrorStage::RecordingStart),
+                HarnessEvent::Status(AppStatus::Error),
+                HarnessEvent::Status(AppStatus::Idle),
+            ]
+        );
+
+        let stop_failure = harness();
+        stop_failure.recorder.fail_next_stop("device unplugged");
+        pipeline.handle_hotkey_started(&stop_failure).await;
+        stop_and_process(&pipeline, &stop_failure).await;
+        assert!(!stop_failure.recorder.is_recording());
+        assert!(stop_failure
+            .events()
+            .contains(&HarnessEvent::Error(PipelineErrorStage::RecordingStop)));
+        assert!(stop_failure.inserter.inserted().is_empty());
+
+        let transcription_failure = harness();
+        transcription_failure.queue_transcription(Err("Network error: offline"));
+        pipeline.handle_hotkey_started(&transcription_failure).await;
+        stop_and_process(&pipeline, &transcription_failure).await;
+        assert!(transcription_failure
+            .events()
+            .contains(&HarnessEvent::Error(PipelineErrorStage::Transcription)));
+        assert!(transcription_failure.inserter.inserted().is_empty());
+
+        let insertion_failure = harness();
+        insertion_failure.queue_transcription(Ok("kept in history"));
+        insertion_failure
+            .inserter
+            .fail_next_insert("Accessibility permission denied");
+        pipeline.handle_hotkey_started(&insertion_failure).await;
+        stop_and_process(&pipeline, &insertion_failure).await;
+        let events = insertion_failure.events();
+        assert!(events.contains(&HarnessEvent::HistorySaved("kept in history".to_string())));
+        assert!(events.contains(&HarnessEvent::Error(PipelineErrorStage::TextInsertion)));
+        assert_eq!(insertion_failure.statuses().last(), Some(&AppStatus::Idle));
+    }
+}
@@ -1,12 +1,57 @@
-use std::time::Duration;
+#[cfg(test)]
+pub mod harness;
+pub mod stages;
+
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info};
 
 use crate::audio_capture_service::RecordedAudio;
-use crate::status_notifier::AppStatus;
+use crate::history_store::TranscriptionDetails;
+use crate::status_notifier::{AppStatus, StatusSource};
+use crate::transcription::TranscriptionError;
+use stages::{
+    CleanUpStage, FormatStage, OutputStage, PipelineStage, RedactStage, StageOutcome, StagePayload,
+    StageTiming, TranscribeStage, TranslateStage, OUTPUT_STAGE, PREVIEW_STAGES, TRANSCRIBE_STAGE,
+};
 
 const DEFAULT_ERROR_RESET_DELAY_MS: u64 = 1_500;
+const TIMEOUT_MARKER: &str = "did not finish in time";
+const SILENCE_RMS_THRESHOLD: f32 = 0.005;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NothingHeardReason {
+    TooShort,
+    Silent,
+}
+
+impl NothingHeardReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TooShort => "too_short",
+            Self::Silent => "silent",
+        }
+    }
+}
+
+pub fn detect_nothing_heard(
+    recorded_audio: &RecordedAudio,
+    min_duration_ms: u64,
+) -> Option<NothingHeardReason> {
+    if recorded_audio.duration_ms < min_duration_ms {
+        return Some(NothingHeardReason::TooShort);
+    }
+
+    recorded_audio
+        .rms_level()
+        .filter(|level| *level < SILENCE_RMS_THRESHOLD)
+        .map(|_| NothingHeardReason::Silent)
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PipelineErrorStage {
@@ -14,6 +59,8 @@ pub enum PipelineErrorStage {
     RecordingStop,
     RecordingRuntime,
     Transcription,
+    Translation,
+    PostProcessing,
     TextInsertion,
 }
 
@@ -24,15 +71,214 @@ impl PipelineErrorStage {
             Self::RecordingStop => "recording_stop",
             Self::RecordingRuntime => "recording_runtime",
             Self::Transcription => "transcription",
+            Self::Translation => "translation",
+            Self::PostProcessing => "post_processing",
             Self::TextInsertion => "text_insertion",
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineErrorCode {
+    MicPermissionDenied,
+    AuthMissing,
+    TokenExpired,
+    AudioEmpty,
+    ProviderRateLimited,
+    NetworkUnavailable,
+    InsertionBlocked,
+    InsertionBlockedSecureInput,
+    RecordingTimeLimit,
+    TranscriptionTimeout,
+    InsertionTimeout,
+    Unknown,
+}
+
+impl PipelineErrorCode {
+    pub fn classify(stage: &PipelineErrorStage, message: &str) -> Self {
+        let message = message.to_ascii_lowercase();
+        match stage {
+            PipelineErrorStage::RecordingStart if message.contains("microphone access") => {
+                Self::MicPermissionDenied
+            }
+            PipelineErrorStage::TextInsertion if message.contains("accessibility access") => {
+                Self::InsertionBlocked
+            }
+            PipelineErrorStage::TextInsertion if message.contains("secure input is enabled") => {
+                Self::InsertionBlockedSecureInput
+            }
+            PipelineErrorStage::RecordingRuntime if message.contains("recording limit") => {
+                Self::RecordingTimeLimit
+            }
+            PipelineErrorStage::Transcription if message.contains(TIMEOUT_MARKER) => {
+                Self::TranscriptionTimeout
+            }
+            PipelineErrorStage::TextInsertion if message.contains(TIMEOUT_MARKER) => {
+                Self::InsertionTimeout
+            }
+            _ if message.contains("no authentication configured")
+                || message.contains("missing transcription provider api key") =>
+            {
+                Self::AuthMissing
+            }
+            _ if message.contains("authentication failed") => Self::TokenExpired,
+            _ if message.contains("audio payload is empty") => Self::AudioEmpty,
+            _ if message.contains("rate limited") => Self::ProviderRateLimited,
+            _ if message.contains("network error") => Self::NetworkUnavailable,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MicPermissionDenied => "MIC_PERMISSION_DENIED",
+            Self::AuthMissing => "AUTH_MISSING",
+            Self::TokenExpired => "TOKEN_EXPIRED",
+            Self::AudioEmpty => "AUDIO_EMPTY",
+            Self::ProviderRateLimited => "PROVIDER_RATE_LIMITED",
+            Self::NetworkUnavailable => "NETWORK_UNAVAILABLE",
+            Self::InsertionBlocked => "INSERTION_BLOCKED",
+            Self::InsertionBlockedSecureInput => "INSERTION_BLOCKED_SECURE_INPUT",
+            Self::RecordingTimeLimit => "RECORDING_TIME_LIMIT",
+            Self::TranscriptionTimeout => "TRANSCRIPTION_TIMEOUT",
+            Self::InsertionTimeout => "INSERTION_TIMEOUT",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    pub fn summary(&self) -> Option<&'static str> {
+        match self {
+            Self::MicPermissionDenied => Some("Buzz can't access your microphone."),
+            Self::AuthMissing => Some("No transcription account is set up."),
+            Self::TokenExpired => Some("Your transcription sign-in has expired or was rejected."),
+            Self::AudioEmpty => Some("No audio was captured. Try speaking a little longer."),
+            Self::ProviderRateLimited => {
+                Some("The transcription service is busy. Wait a moment and try again.")
+            }
+            Self::NetworkUnavailable => Some("Couldn't reach the transcription service."),
+            Self::InsertionBlocked => Some("Buzz isn't allowed to type into other apps."),
+            Self::InsertionBlockedSecureInput => Some(
+                "Another app has Secure Input turned on, so Buzz copied the transcript to your clipboard instead.",
+            ),
+            Self::RecordingTimeLimit => Some(
+                "Your recording reached its maximum length, so Buzz stopped it and transcribed what it heard.",
+            ),
+            Self::TranscriptionTimeout => Some("Transcription took too long and was stopped."),
+            Self::InsertionTimeout => Some("Typing the transcript into the focused app took too long."),
+            Self::Unknown => None,
+        }
+    }
+
+    pub fn suggested_action(&self) -> Option<PipelineErrorAction> {
+        match self {
+            Self::MicPermissionDenied => Some(PipelineErrorAction::RequestMicrophoneAccess),
+            Self::AuthMissing | Self::TokenExpired => {
+                Some(PipelineErrorAction::OpenAccountSettings)
+            }
+            Self::NetworkUnavailable => Some(PipelineErrorAction::RetryPendingRecordings),
+            Self::InsertionBlocked => Some(PipelineErrorAction::OpenAccessibilitySettings),
+            Self::AudioEmpty
+            | Self::ProviderRateLimited
+            | Self::InsertionBlockedSecureInput
+            | Self::RecordingTimeLimit
+            | Self::TranscriptionTimeout
+            | Self::InsertionTimeout
+            | Self::Unknown => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineErrorAction {
+    RequestMicrophoneAccess,
+    OpenAccountSettings,
+    RetryPendingRecordings,
+    OpenAccessibilitySettings,
+}
+
+impl PipelineErrorAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequestMicrophoneAccess => "request_microphone_access",
+            Self::OpenAccountSettings => "open_account_settings",
+            Self::RetryPendingRecordings => "retry_pending_recordings",
+            Self::OpenAccessibilitySettings => "open_accessibility_settings",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::RequestMicrophoneAccess => "Allow Microphone",
+            Self::OpenAccountSettings => "Open Account Settings",
+            Self::RetryPendingRecordings => "Retry Now",
+            Self::OpenAccessibilitySettings => "Open Accessibility Settings",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageError {
+    pub code: Option<PipelineErrorCode>,
+    pub message: String,
+}
+
+impl StageError {
+    pub fn new(code: PipelineErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code: Some(code),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for StageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for StageError {
+    fn from(message: String) -> Self {
+        Self {
+            code: None,
+            message,
+        }
+    }
+}
+
+impl From<TranscriptionError> for StageError {
+    fn from(error: TranscriptionError) -> Self {
+        let code = match &error {
+            TranscriptionError::MissingApiKey => Some(PipelineErrorCode::AuthMissing),
+            TranscriptionError::Authentication(_) => Some(PipelineErrorCode::TokenExpired),
+            TranscriptionError::RateLimited(_) => Some(PipelineErrorCode::ProviderRateLimited),
+            TranscriptionError::Network(_) => Some(PipelineErrorCode::NetworkUnavailable),
+            TranscriptionError::InvalidResponse(_) | TranscriptionError::Provider(_) => None,
+        };
+        Self {
+            code,
+            message: error.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PipelineError {
     pub stage: PipelineErrorStage,
     pub message: String,
+    pub code: PipelineErrorCode,
+}
+
+impl PipelineError {
+    pub fn new(stage: PipelineErrorStage, error: impl Into<StageError>) -> Self {
+        let StageError { code, message } = error.into();
+        let code = code.unwrap_or_else(|| PipelineErrorCode::classify(&stage, &message));
+        Self {
+            stage,
+            message,
+            code,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -41,43 +287,174 @@ pub struct PipelineTranscript {
     pub duration_secs: Option<f64>,
     pub language: Option<String>,
     pub provider: String,
+    pub raw_text: Option<String>,
+    pub details: TranscriptionDetails,
 }
 
 #[async_trait]
 pub trait VoicePipelineDelegate: Send + Sync {
-    fn set_status(&self, status: AppStatus);
+    fn set_status(&self, status: AppStatus, source: StatusSource);
     fn emit_transcript(&self, transcript: &str);
     fn emit_error(&self, error: &PipelineError);
+    fn emit_nothing_heard(&self, _reason: NothingHeardReason, _duration_ms: u64) {}
+    fn min_recording_duration_ms(&self) -> u64 {
+        0
+    }
     fn on_recording_started(&self, _success: bool) {}
     fn on_recording_stopped(&self, _success: bool) {}
     fn start_recording(&self) -> Result<(), String>;
     fn stop_recording(&self) -> Result<RecordedAudio, String>;
-    async fn transcribe(&self, recorded_audio: RecordedAudio)
-        -> Result<PipelineTranscript, String>;
-    fn insert_text(&self, transcript: &str) -> Result<(), String>;
+    async fn transcribe(
+        &self,
+        recorded_audio: RecordedAudio,
+    ) -> Result<PipelineTranscript, StageError>;
+    fn handle_voice_command(&self, _transcript: &PipelineTranscript) -> bool {
+        false
+    }
+    async fn translate(
+        &self,
+        transcript: PipelineTranscript,
+    ) -> Result<PipelineTranscript, String> {
+        Ok(transcript)
+    }
+    async fn clean_up(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+        transcript
+    }
+    fn format_transcript(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+        transcript
+    }
+    fn redact(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+        transcript
+    }
+    async fn insert_text(&self, transcript: &str) -> Result<(), String>;
     fn save_history_entry(&self, _transcript: &PipelineTranscript) -> Result<(), String> {
         Ok(())
     }
+    fn record_stage_timings(&self, _timings: &[StageTiming]) {}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineTimeouts {
+    pub max_recording: Option<Duration>,
+    pub transcription: Option<Duration>,
+    pub insertion: Option<Duration>,
+}
+
+impl PipelineTimeouts {
+    fn for_stage(&self, stage: &str) -> Option<Duration> {
+        match stage {
+            TRANSCRIBE_STAGE => self.transcription,
+            OUTPUT_STAGE => self.insertion,
+            _ => None,
+        }
+    }
+}
+
+pub fn recording_limit_message(limit: Duration) -> String {
+    format!(
+        "Recording stopped at the {}-second recording limit",
+        limit.as_secs()
+    )
+}
+
+fn stage_timeout_message(stage: &str, limit: Duration) -> String {
+    let stage = match stage {
+        TRANSCRIBE_STAGE => "Transcription",
+        OUTPUT_STAGE => "Text insertion",
+        other => other,
+    };
+    format!("{stage} {TIMEOUT_MARKER} ({}s limit)", limit.as_secs())
+}
+
+fn stage_timeout_error(stage: &str, limit: Duration) -> StageError {
+    let message = stage_timeout_message(stage, limit);
+    match stage {
+        TRANSCRIBE_STAGE => StageError::new(PipelineErrorCode::TranscriptionTimeout, message),
+        OUTPUT_STAGE => StageError::new(PipelineErrorCode::InsertionTimeout, message),
+        _ => message.into(),
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct VoicePipeline {
     error_reset_delay: Duration,
+    timeouts: PipelineTimeouts,
+    stages: Vec<Arc<dyn PipelineStage>>,
+}
+
+impl fmt::Debug for VoicePipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoicePipeline")
+            .field("error_reset_delay", &self.error_reset_delay)
+            .field("timeouts", &self.timeouts)
+            .field("stages", &self.stage_names())
+            .finish()
+    }
 }
 
 impl Default for VoicePipeline {
     fn default() -> Self {
         Self {
             error_reset_delay: Duration::from_millis(DEFAULT_ERROR_RESET_DELAY_MS),
+            timeouts: PipelineTimeouts::default(),
+            stages: default_stages(),
         }
     }
 }
 
+fn default_stages() -> Vec<Arc<dyn PipelineStage>> {
+    vec![
+        Arc::new(TranscribeStage),
+        Arc::new(TranslateStage),
+        Arc::new(CleanUpStage),
+        Arc::new(FormatStage),
+        Arc::new(RedactStage),
+        Arc::new(OutputStage),
+    ]
+}
+
 impl VoicePipeline {
     #[cfg(test)]
     pub fn new(error_reset_delay: Duration) -> Self {
         debug!(?error_reset_delay, "voice pipeline initialized");
-        Self { error_reset_delay }
+        Self {
+            error_reset_delay,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_error_reset_delay(mut self, error_reset_delay: Duration) -> Self {
+        self.error_reset_delay = error_reset_delay;
+        self
+    }
+
+    pub fn with_timeouts(mut self, timeouts: PipelineTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    pub fn stage_names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    pub fn with_stage_before(mut self, before: &str, stage: Arc<dyn PipelineStage>) -> Self {
+        let index = self
+            .stages
+            .iter()
+            .position(|existing| existing.name() == before)
+            .or_else(|| {
+                self.stages
+                    .iter()
+                    .position(|existing| existing.name() == OUTPUT_STAGE)
+            })
+            .unwrap_or(self.stages.len());
+        self.stages.insert(index, stage);
+        self
+    }
+
+    pub fn without_stage(mut self, name: &str) -> Self {
+        self.stages.retain(|stage| stage.name() != name);
+        self
     }
 
     pub async fn handle_hotkey_started<D: VoicePipelineDelegate>(&self, delegate: &D) {
@@ -86,7 +463,7 @@ impl VoicePipeline {
             Ok(()) => {
                 info!("recording started successfully from hotkey");
                 delegate.on_recording_started(true);
-                delegate.set_status(AppStatus::Listening);
+                delegate.set_status(AppStatus::Listening, StatusSource::Hotkey);
             }
             Err(message) => {
                 error!(message = %message, "recording start failed from hotkey");
@@ -97,9 +474,12 @@ impl VoicePipeline {
         }
     }
 
-    pub async fn handle_hotkey_stopped<D: VoicePipelineDelegate>(&self, delegate: &D) {
+    pub async fn finish_recording<D: VoicePipelineDelegate>(
+        &self,
+        delegate: &D,
+    ) -> Option<RecordedAudio> {
         info!("pipeline handling hotkey stop");
-        delegate.set_status(AppStatus::Transcribing);
+        delegate.set_status(AppStatus::Transcribing, StatusSource::Hotkey);
 
         let recorded_audio = match delegate.stop_recording() {
             Ok(recorded_audio) => {
@@ -118,82 +498,155 @@ impl VoicePipeline {
                 delegate.on_recording_stopped(false);
                 self.handle_error(delegate, PipelineErrorStage::RecordingStop, message)
                     .await;
-                return;
+                return None;
             }
         };
 
+        let stop_source = StatusSource::Pipeline(PipelineErrorStage::RecordingStop.as_str());
         if !recorded_audio.has_audio() {
             info!("recording produced no audio; returning to idle");
-            delegate.set_status(AppStatus::Idle);
-            return;
+            delegate.set_status(AppStatus::Idle, stop_source);
+            return None;
         }
 
-        let transcript = match delegate.transcribe(recorded_audio).await {
-            Ok(transcript) => {
-                info!(
-                    transcript_chars = transcript.text.chars().count(),
-                    provider = %transcript.provider,
-                    "transcription completed in pipeline"
-                );
-                transcript
-            }
-            Err(message) => {
-                error!(message = %message, "pipeline transcription failed");
-                self.handle_error(delegate, PipelineErrorStage::Transcription, message)
-                    .await;
-                return;
-            }
-        };
-
-        delegate.emit_transcript(&transcript.text);
-
-        if let Err(message) = delegate.save_history_entry(&transcript) {
-            warn!(message = %message, "failed to persist transcript history entry");
+        if let Some(reason) =
+            detect_nothing_heard(&recorded_audio, delegate.min_recording_duration_ms())
+        {
+            info!(
+                reason = reason.as_str(),
+                duration_ms = recorded_audio.duration_ms,
+                "recording held no speech; skipping transcription"
+            );
+            delegate.emit_nothing_heard(reason, recorded_audio.duration_ms);
+            delegate.set_status(AppStatus::NothingHeard, stop_source);
+            tokio::time::sleep(self.error_reset_delay).await;
+            delegate.set_status(AppStatus::Idle, stop_source);
+            return None;
         }
 
-        if let Err(message) = delegate.insert_text(&transcript.text) {
-            error!(message = %message, "pipeline text insertion failed");
-            self.handle_error(delegate, PipelineErrorStage::TextInsertion, message)
-                .await;
-            return;
+        Some(recorded_audio)
+    }
+
+    pub async fn process_recording<D: VoicePipelineDelegate>(
+        &self,
+        delegate: &D,
+        recorded_audio: RecordedAudio,
+    ) {
+        let mut payload = StagePayload::Audio(recorded_audio);
+        let mut timings = Vec::with_capacity(self.stages.len());
+
+        for stage in &self.stages {
+            let started_at = Instant::now();
+            let result = match self.timeouts.for_stage(stage.name()) {
+                Some(limit) => tokio::time::timeout(limit, stage.run(delegate, payload))
+                    .await
+                    .unwrap_or_else(|_| Err(stage_timeout_error(stage.name(), limit))),
+                None => stage.run(delegate, payload).await,
+            };
+            let elapsed = started_at.elapsed();
+            match result {
+                Ok(next) => {
+                    debug!(
+                        stage = stage.name(),
+                        duration_ms = elapsed.as_millis(),
+                        "pipeline stage completed"
+                    );
+                    timings.push(StageTiming::new(
+                        stage.name(),
+                        elapsed,
+                        StageOutcome::Completed,
+                    ));
+                    if matches!(next, StagePayload::Handled) {
+                        break;
+                    }
+                    payload = next;
+                }
+                Err(stage_error) => {
+                    error!(
+                        stage = stage.name(),
+                        duration_ms = elapsed.as_millis(),
+                        message = %stage_error,
+                        "pipeline stage failed"
+                    );
+                    timings.push(StageTiming::new(
+                        stage.name(),
+                        elapsed,
+                        StageOutcome::Failed,
+                    ));
+                    delegate.record_stage_timings(&timings);
+                    self.handle_error(delegate, stage.error_stage(), stage_error)
+                        .await;
+                    return;
+                }
+            }
         }
-        info!("pipeline text insertion succeeded");
 
+        delegate.record_stage_timings(&timings);
         debug!("pipeline returning to idle status");
-        delegate.set_status(AppStatus::Idle);
+        delegate.set_status(AppStatus::Idle, StatusSource::Pipeline(OUTPUT_STAGE));
+    }
+
+    pub async fn preview_text<D: VoicePipelineDelegate>(
+        &self,
+        delegate: &D,
+        text: String,
+    ) -> Result<String, String> {
+        let mut payload = StagePayload::Transcript(PipelineTranscript {
+            text,
+            duration_secs: None,
+            language: None,
+            provider: String::new(),
+            raw_text: None,
+            details: TranscriptionDetails::default(),
+        });
+        for stage in self
+            .stages
+            .iter()
+            .filter(|stage| PREVIEW_STAGES.contains(&stage.name()))
+        {
+            payload = stage
+                .run(delegate, payload)
+                .await
+                .map_err(|error| error.message)?;
+        }
+        payload
+            .into_transcript(OUTPUT_STAGE)
+            .map(|transcript| transcript.text)
     }
 
     pub async fn handle_stage_error<D: VoicePipelineDelegate>(
         &self,
         delegate: &D,
         stage: PipelineErrorStage,
-        message: String,
+        error: impl Into<StageError>,
     ) {
         debug!(stage = stage.as_str(), "handling pipeline stage error");
-        self.handle_error(delegate, stage, message).await;
+        self.handle_error(delegate, stage, error).await;
     }
 
     async fn handle_error<D: VoicePipelineDelegate>(
         &self,
         delegate: &D,
         stage: PipelineErrorStage,
-        message: String,
+        error: impl Into<StageError>,
     ) {
-        let error = PipelineError { stage, message };
+        let source = StatusSource::Pipeline(stage.as_str());
+        let error = PipelineError::new(stage, error);
         error!(
             stage = error.stage.as_str(),
+            code = error.code.as_str(),
             message = %error.message,
             "pipeline entering error state"
         );
         delegate.emit_error(&error);
-        delegate.set_status(AppStatus::Error);
+        delegate.set_status(AppStatus::Error, source);
         debug!(
             delay_ms = self.error_reset_delay.as_millis(),
             "waiting before idle reset"
         );
         tokio::time::sleep(self.error_reset_delay).await;
         info!("pipeline resetting status to idle after error");
-        delegate.set_status(AppStatus::Idle);
+        delegate.set_status(AppStatus::Idle, source);
     }
 }
 
@@ -201,7 +654,7 @@ impl VoicePipeline {
 mod tests {
     use std::sync::Mutex;
 
-    use super::*;
+    use super::{harness::stop_and_process, *};
 
     fn recorded_audio(bytes: Vec<u8>) -> RecordedAudio {
         RecordedAudio::from_wav_bytes(
@@ -219,8 +672,13 @@ mod tests {
         start_result: Result<(), String>,
         stop_result: Result<Vec<u8>, String>,
         transcribe_result: Result<PipelineTranscript, String>,
+        translate_result: Option<Result<String, String>>,
+        voice_command: Option<&'static str>,
+        redacted_term: Option<&'static str>,
         insert_result: Result<(), String>,
         save_history_result: Result<(), String>,
+        min_recording_duration_ms: u64,
+        nothing_heard: Mutex<Vec<NothingHeardReason>>,
         start_acknowledgements: Mutex<Vec<bool>>,
         stop_acknowledgements: Mutex<Vec<bool>>,
         statuses: Mutex<Vec<AppStatus>>,
@@ -228,6 +686,7 @@ mod tests {
         saved_history: Mutex<Vec<PipelineTranscript>>,
         errors: Mutex<Vec<PipelineError>>,
         call_order: Mutex<Vec<&'static str>>,
+        stage_timings: Mutex<Vec<StageTiming>>,
     }
 
     impl Default for MockDelegate {
@@ -240,9 +699,16 @@ mod tests {
                     duration_secs: Some(2.4),
                     language: Some("en".to_string()),
                     provider: "openai".to_string(),
+                    raw_text: None,
+                    details: TranscriptionDetails::default(),
                 }),
+                translate_result: None,
+                voice_command: None,
+                redacted_term: None,
                 insert_result: Ok(()),
                 save_history_result: Ok(()),
+                min_recording_duration_ms: 0,
+                nothing_heard: Mutex::new(Vec::new()),
                 start_acknowledgements: Mutex::new(Vec::new()),
                 stop_acknowledgements: Mutex::new(Vec::new()),
                 statuses: Mutex::new(Vec::new()),
@@ -250,6 +716,7 @@ mod tests {
                 saved_history: Mutex::new(Vec::new()),
                 errors: Mutex::new(Vec::new()),
                 call_order: Mutex::new(Vec::new()),
+                stage_timings: Mutex::new(Vec::new()),
             }
         }
     }
@@ -303,17 +770,35 @@ mod tests {
                 .expect("stop-ack lock should not be poisoned")
                 .clone()
         }
+
+        fn nothing_heard(&self) -> Vec<NothingHeardReason> {
+            self.nothing_heard
+                .lock()
+                .expect("nothing-heard lock should not be poisoned")
+                .clone()
+        }
     }
 
     #[async_trait]
     impl VoicePipelineDelegate for MockDelegate {
-        fn set_status(&self, status: AppStatus) {
+        fn set_status(&self, status: AppStatus, _source: StatusSource) {
             self.statuses
                 .lock()
                 .expect("status lock should not be poisoned")
                 .push(status);
         }
 
+        fn emit_nothing_heard(&self, reason: NothingHeardReason, _duration_ms: u64) {
+            self.nothing_heard
+                .lock()
+                .expect("nothing-heard lock should not be poisoned")
+                .push(reason);
+        }
+
+        fn min_recording_duration_ms(&self) -> u64 {
+            self.min_recording_duration_ms
+        }
+
         fn emit_transcript(&self, transcript: &str) {
             self.transcripts
                 .lock()
@@ -364,15 +849,43 @@ mod tests {
         async fn transcribe(
             &self,
             _recorded_audio: RecordedAudio,
-        ) -> Result<PipelineTranscript, String> {
+        ) -> Result<PipelineTranscript, StageError> {
             self.call_order
                 .lock()
                 .expect("call-order lock should not be poisoned")
                 .push("transcribe");
-            self.transcribe_result.clone()
+            self.transcribe_result.clone().map_err(StageError::from)
+        }
+
+        fn handle_voice_command(&self, transcript: &PipelineTranscript) -> bool {
+            self.voice_command == Some(transcript.text.as_str())
+        }
+
+        fn redact(&self, transcript: PipelineTranscript) -> PipelineTranscript {
+            match self.redacted_term {
+                Some(term) => PipelineTranscript {
+                    text: transcript.text.replace(term, "[REDACTED]"),
+                    ..transcript
+                },
+                None => transcript,
+            }
+        }
+
+        async fn translate(
+            &self,
+            transcript: PipelineTranscript,
+        ) -> Result<PipelineTranscript, String> {
+            self.call_order
+                .lock()
+                .expect("call-order lock should not be poisoned")
+                .push("translate");
+            match self.translate_result.clone() {
+                Some(result) => result.map(|text| PipelineTranscript { text, ..transcript }),
+                None => Ok(transcript),
+            }
         }
 
-        fn insert_text(&self, _transcript: &str) -> Result<(), String> {
+        async fn insert_text(&self, _transcript: &str) -> Result<(), String> {
             self.call_order
                 .lock()
                 .expect("call-order lock should not be poisoned")
@@ -391,6 +904,145 @@ mod tests {
                 .push(transcript.clone());
             self.save_history_result.clone()
         }
+
+        fn record_stage_timings(&self, timings: &[StageTiming]) {
+            *self
+                .stage_timings
+                .lock()
+                .expect("stage-timing lock should not be poisoned") = timings.to_vec();
+        }
+    }
+
+    struct ShoutStage {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl PipelineStage for ShoutStage {
+        fn name(&self) -> &'static str {
+            "shout"
+        }
+
+        async fn run(
+            &self,
+            _delegate: &dyn VoicePipelineDelegate,
+            payload: StagePayload,
+        ) -> Result<StagePayload, StageError> {
+            if self.fail {
+                return Err("shouting is not allowed".to_string().into());
+            }
+            let transcript = payload.into_transcript(self.name())?;
+            Ok(StagePayload::Transcript(PipelineTranscript {
+                text: transcript.text.to_uppercase(),
+                ..transcript
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn registered_stage_runs_in_order_and_reports_timings() {
+        let pipeline = VoicePipeline::new(Duration::ZERO)
+            .without_stage(stages::TRANSLATE_STAGE)
+            .with_stage_before(stages::FORMAT_STAGE, Arc::new(ShoutStage { fail: false }));
+        assert_eq!(
+            pipeline.stage_names(),
+            vec![
+                "transcribe",
+                "clean_up",
+                "shout",
+                "format",
+                "redact",
+                "output"
+            ]
+        );
+        let delegate = MockDelegate::default();
+
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert_eq!(delegate.transcripts(), vec!["HELLO WORLD".to_string()]);
+        assert!(!delegate.call_order().contains(&"translate"));
+        let timings = delegate.stage_timings.lock().unwrap().clone();
+        assert_eq!(
+            timings
+                .iter()
+                .map(|timing| timing.stage)
+                .collect::<Vec<_>>(),
+            pipeline.stage_names()
+        );
+        assert!(timings
+            .iter()
+            .all(|timing| timing.outcome == StageOutcome::Completed));
+    }
+
+    #[tokio::test]
+    async fn preview_runs_only_the_text_stages_and_inserts_nothing() {
+        let pipeline = VoicePipeline::new(Duration::ZERO)
+            .with_stage_before(stages::FORMAT_STAGE, Arc::new(ShoutStage { fail: false }));
+        let delegate = MockDelegate {
+            redacted_term: Some("secret"),
+            ..MockDelegate::default()
+        };
+
+        let preview = pipeline
+            .preview_text(&delegate, "the secret plan".to_string())
+            .await;
+
+        assert_eq!(preview, Ok("the [REDACTED] plan".to_string()));
+        assert!(delegate.call_order().is_empty());
+        assert!(delegate.transcripts().is_empty());
+        assert!(delegate.saved_history().is_empty());
+    }
+
+    #[tokio::test]
+    async fn handled_voice_command_skips_the_remaining_stages() {
+        let pipeline = VoicePipeline::new(Duration::ZERO)
+            .with_stage_before(stages::TRANSLATE_STAGE, Arc::new(stages::VoiceCommandStage));
+        let delegate = MockDelegate {
+            voice_command: Some("hello world"),
+            ..MockDelegate::default()
+        };
+
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert_eq!(delegate.call_order(), vec!["stop_recording", "transcribe"]);
+        assert!(delegate.transcripts().is_empty());
+        assert!(delegate.errors().is_empty());
+        assert_eq!(
+            delegate.statuses(),
+            vec![AppStatus::Transcribing, AppStatus::Idle]
+        );
+        let timings = delegate.stage_timings.lock().unwrap().clone();
+        assert_eq!(
+            timings
+                .iter()
+                .map(|timing| timing.stage)
+                .collect::<Vec<_>>(),
+            vec!["transcribe", "voice_command"]
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_registered_stage_is_attributed_and_stops_the_run() {
+        let pipeline = VoicePipeline::new(Duration::ZERO)
+            .with_stage_before("missing", Arc::new(ShoutStage { fail: true }));
+        let delegate = MockDelegate::default();
+
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert!(delegate.transcripts().is_empty());
+        assert!(!delegate.call_order().contains(&"insert_text"));
+        assert_eq!(
+            delegate.errors(),
+            vec![PipelineError::new(
+                PipelineErrorStage::PostProcessing,
+                "shouting is not allowed".to_string()
+            )]
+        );
+        let timings = delegate.stage_timings.lock().unwrap().clone();
+        assert_eq!(
+            timings.last().map(|timing| (timing.stage, timing.outcome)),
+            Some(("shout", StageOutcome::Failed))
+        );
     }
 
     #[tokio::test]
@@ -423,10 +1075,10 @@ mod tests {
         assert_eq!(delegate.statuses(), vec![AppStatus::Error, AppStatus::Idle]);
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::RecordingStart,
-                message: "microphone unavailable".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::RecordingStart,
+                "microphone unavailable".to_string()
+            )]
         );
     }
 
@@ -435,13 +1087,14 @@ mod tests {
         let pipeline = VoicePipeline::new(Duration::ZERO);
         let delegate = MockDelegate::default();
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(
             delegate.call_order(),
             vec![
                 "stop_recording",
                 "transcribe",
+                "translate",
                 "save_history_entry",
                 "insert_text"
             ]
@@ -460,6 +1113,8 @@ mod tests {
                 duration_secs: Some(2.4),
                 language: Some("en".to_string()),
                 provider: "openai".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
             }]
         );
         assert!(delegate.errors().is_empty());
@@ -473,7 +1128,7 @@ mod tests {
             ..MockDelegate::default()
         };
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(delegate.call_order(), vec!["stop_recording"]);
         assert!(delegate.start_acknowledgements().is_empty());
@@ -487,6 +1142,50 @@ mod tests {
         assert!(delegate.errors().is_empty());
     }
 
+    #[tokio::test]
+    async fn hotkey_stop_with_too_short_recording_skips_transcription() {
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+        let delegate = MockDelegate {
+            min_recording_duration_ms: 1_500,
+            ..MockDelegate::default()
+        };
+
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert_eq!(delegate.call_order(), vec!["stop_recording"]);
+        assert_eq!(delegate.nothing_heard(), vec![NothingHeardReason::TooShort]);
+        assert_eq!(
+            delegate.statuses(),
+            vec![
+                AppStatus::Transcribing,
+                AppStatus::NothingHeard,
+                AppStatus::Idle
+            ]
+        );
+        assert!(delegate.transcripts().is_empty());
+        assert!(delegate.errors().is_empty());
+    }
+
+    #[test]
+    fn silent_recordings_are_detected_by_rms_level() {
+        let silent = RecordedAudio::from_pcm16_mono_samples(vec![3, -2, 4, -1], 16_000, 1_000);
+        let speech = RecordedAudio::from_pcm16_mono_samples(
+            vec![8_000, -6_000, 7_500, -9_000],
+            16_000,
+            1_000,
+        );
+
+        assert_eq!(
+            detect_nothing_heard(&silent, 300),
+            Some(NothingHeardReason::Silent)
+        );
+        assert_eq!(detect_nothing_heard(&speech, 300), None);
+        assert_eq!(
+            detect_nothing_heard(&speech, 2_000),
+            Some(NothingHeardReason::TooShort)
+        );
+    }
+
     #[tokio::test]
     async fn hotkey_stop_recording_failure_sets_error_then_idle() {
         let pipeline = VoicePipeline::new(Duration::ZERO);
@@ -495,7 +1194,7 @@ mod tests {
             ..MockDelegate::default()
         };
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(delegate.call_order(), vec!["stop_recording"]);
         assert!(delegate.start_acknowledgements().is_empty());
@@ -506,10 +1205,10 @@ mod tests {
         );
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::RecordingStop,
-                message: "recording not active".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::RecordingStop,
+                "recording not active".to_string()
+            )]
         );
         assert!(delegate.transcripts().is_empty());
         assert!(delegate.saved_history().is_empty());
@@ -523,7 +1222,7 @@ mod tests {
             ..MockDelegate::default()
         };
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(delegate.call_order(), vec!["stop_recording", "transcribe"]);
         assert!(delegate.start_acknowledgements().is_empty());
@@ -534,10 +1233,10 @@ mod tests {
         );
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::Transcription,
-                message: "provider unavailable".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::Transcription,
+                "provider unavailable".to_string()
+            )]
         );
         assert!(delegate.transcripts().is_empty());
         assert!(delegate.saved_history().is_empty());
@@ -551,13 +1250,14 @@ mod tests {
             ..MockDelegate::default()
         };
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(
             delegate.call_order(),
             vec![
                 "stop_recording",
                 "transcribe",
+                "translate",
                 "save_history_entry",
                 "insert_text"
             ]
@@ -577,13 +1277,14 @@ mod tests {
             ..MockDelegate::default()
         };
 
-        pipeline.handle_hotkey_stopped(&delegate).await;
+        stop_and_process(&pipeline, &delegate).await;
 
         assert_eq!(
             delegate.call_order(),
             vec![
                 "stop_recording",
                 "transcribe",
+                "translate",
                 "save_history_entry",
                 "insert_text"
             ]
@@ -596,6 +1297,8 @@ mod tests {
                 duration_secs: Some(2.4),
                 language: Some("en".to_string()),
                 provider: "openai".to_string(),
+                raw_text: None,
+                details: TranscriptionDetails::default(),
             }]
         );
         assert_eq!(
@@ -604,11 +1307,61 @@ mod tests {
         );
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::TextInsertion,
-                message: "accessibility denied".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::TextInsertion,
+                "accessibility denied".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn hotkey_stop_translation_replaces_transcript_before_insertion() {
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+        let delegate = MockDelegate {
+            translate_result: Some(Ok("hola mundo".to_string())),
+            ..MockDelegate::default()
+        };
+
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert_eq!(delegate.transcripts(), vec!["hola mundo".to_string()]);
+        assert_eq!(
+            delegate
+                .saved_history()
+                .into_iter()
+                .map(|transcript| transcript.text)
+                .collect::<Vec<_>>(),
+            vec!["hola mundo".to_string()]
+        );
+        assert!(delegate.errors().is_empty());
+    }
+
+    #[tokio::test]
+    async fn hotkey_stop_translation_failure_sets_error_then_idle() {
+        let pipeline = VoicePipeline::new(Duration::ZERO);
+        let delegate = MockDelegate {
+            translate_result: Some(Err("translation unavailable".to_string())),
+            ..MockDelegate::default()
+        };
+
+        stop_and_process(&pipeline, &delegate).await;
+
+        assert_eq!(
+            delegate.call_order(),
+            vec!["stop_recording", "transcribe", "translate"]
+        );
+        assert_eq!(
+            delegate.statuses(),
+            vec![AppStatus::Transcribing, AppStatus::Error, AppStatus::Idle]
         );
+        assert_eq!(
+            delegate.errors(),
+            vec![PipelineError::new(
+                PipelineErrorStage::Translation,
+                "translation unavailable".to_string()
+            )]
+        );
+        assert!(delegate.transcripts().is_empty());
     }
 
     #[tokio::test]
@@ -627,10 +1380,146 @@ mod tests {
         assert_eq!(delegate.statuses(), vec![AppStatus::Error, AppStatus::Idle]);
         assert_eq!(
             delegate.errors(),
-            vec![PipelineError {
-                stage: PipelineErrorStage::Transcription,
-                message: "provider unavailable".to_string(),
-            }]
+            vec![PipelineError::new(
+                PipelineErrorStage::Transcription,
+                "provider unavailable".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn classifies_known_failures_into_actionable_codes() {
+        let transcription_timeout =
+            stage_timeout_message(TRANSCRIBE_STAGE, Duration::from_secs(90));
+        let insertion_timeout = stage_timeout_message(OUTPUT_STAGE, Duration::from_secs(15));
+        let recording_limit = recording_limit_message(Duration::from_secs(600));
+        let cases = [
+            (
+                PipelineErrorStage::RecordingStart,
+                "Microphone access is required. Open System Settings.",
+                PipelineErrorCode::MicPermissionDenied,
+            ),
+            (
+                PipelineErrorStage::Transcription,
+                "Authentication failed: invalid token",
+                PipelineErrorCode::TokenExpired,
+            ),
+            (
+                PipelineErrorStage::Transcription,
+                "Audio payload is empty",
+                PipelineErrorCode::AudioEmpty,
+            ),
+            (
+                PipelineErrorStage::Transcription,
+                "Rate limited: slow down",
+                PipelineErrorCode::ProviderRateLimited,
+            ),
+            (
+                PipelineErrorStage::TextInsertion,
+                "Accessibility access is required to insert text",
+                PipelineErrorCode::InsertionBlocked,
+            ),
+            (
+                PipelineErrorStage::TextInsertion,
+                "Secure input is enabled by Terminal; the transcript was copied to the clipboard instead",
+                PipelineErrorCode::InsertionBlockedSecureInput,
+            ),
+            (
+                PipelineErrorStage::Transcription,
+                transcription_timeout.as_str(),
+                PipelineErrorCode::TranscriptionTimeout,
+            ),
+            (
+                PipelineErrorStage::TextInsertion,
+                insertion_timeout.as_str(),
+                PipelineErrorCode::InsertionTimeout,
+            ),
+            (
+                PipelineErrorStage::RecordingRuntime,
+                recording_limit.as_str(),
+                PipelineErrorCode::RecordingTimeLimit,
+            ),
+            (
+                PipelineErrorStage::Transcription,
+                "provider unavailable",
+                PipelineErrorCode::Unknown,
+            ),
+        ];
+
+        for (stage, message, expected) in cases {
+            assert_eq!(
+                PipelineErrorCode::classify(&stage, message),
+                expected,
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn typed_stage_errors_keep_their_code_without_message_matching() {
+        let typed = PipelineError::new(
+            PipelineErrorStage::Transcription,
+            StageError::new(PipelineErrorCode::AuthMissing, "Sign in to transcribe"),
+        );
+        assert_eq!(typed.code, PipelineErrorCode::AuthMissing);
+        assert_eq!(typed.message, "Sign in to transcribe");
+
+        let cases = [
+            (
+                TranscriptionError::MissingApiKey,
+                PipelineErrorCode::AuthMissing,
+            ),
+            (
+                TranscriptionError::Authentication("401".to_string()),
+                PipelineErrorCode::TokenExpired,
+            ),
+            (
+                TranscriptionError::RateLimited("slow down".to_string()),
+                PipelineErrorCode::ProviderRateLimited,
+            ),
+            (
+                TranscriptionError::Network("connection reset".to_string()),
+                PipelineErrorCode::NetworkUnavailable,
+            ),
+            (
+                TranscriptionError::InvalidResponse("not json".to_string()),
+                PipelineErrorCode::Unknown,
+            ),
+        ];
+        for (error, expected) in cases {
+            let message = error.to_string();
+            let pipeline_error = PipelineError::new(PipelineErrorStage::Transcription, error);
+            assert_eq!(pipeline_error.code, expected, "{message}");
+            assert_eq!(pipeline_error.message, message);
+        }
+    }
+
+    #[test]
+    fn untyped_stage_errors_fall_back_to_message_matching() {
+        let error = PipelineError::new(
+            PipelineErrorStage::Transcription,
+            "Rate limited: try again later".to_string(),
+        );
+        assert_eq!(error.code, PipelineErrorCode::ProviderRateLimited);
+
+        let error = PipelineError::new(
+            PipelineErrorStage::RecordingStart,
+            "Microphone access was denied".to_string(),
+        );
+        assert_eq!(error.code, PipelineErrorCode::MicPermissionDenied);
+    }
+
+    #[test]
+    fn actionable_codes_suggest_a_recovery_action() {
+        assert_eq!(
+            PipelineErrorCode::MicPermissionDenied.suggested_action(),
+            Some(PipelineErrorAction::RequestMicrophoneAccess)
+        );
+        assert_eq!(
+            PipelineErrorCode::TokenExpired.suggested_action(),
+            Some(PipelineErrorAction::OpenAccountSettings)
         );
+        assert_eq!(PipelineErrorCode::Unknown.suggested_action(), None);
+        assert_eq!(PipelineErrorCode::Unknown.summary(), None);
     }
 }
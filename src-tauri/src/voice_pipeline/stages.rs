@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use super::{PipelineErrorStage, PipelineTranscript, StageError, VoicePipelineDelegate};
+use crate::audio_capture_service::RecordedAudio;
+
+pub const TRANSCRIBE_STAGE: &str = "transcribe";
+pub const VOICE_COMMAND_STAGE: &str = "voice_command";
+pub const TRANSLATE_STAGE: &str = "translate";
+pub const CLEAN_UP_STAGE: &str = "clean_up";
+pub const FORMAT_STAGE: &str = "format";
+pub const REDACT_STAGE: &str = "redact";
+pub const OUTPUT_STAGE: &str = "output";
+
+pub const PREVIEW_STAGES: [&str; 2] = [FORMAT_STAGE, REDACT_STAGE];
+
+pub enum StagePayload {
+    Audio(RecordedAudio),
+    Transcript(PipelineTranscript),
+    Handled,
+}
+
+impl StagePayload {
+    pub fn into_transcript(self, stage: &str) -> Result<PipelineTranscript, String> {
+        match self {
+            Self::Transcript(transcript) => Ok(transcript),
+            Self::Audio(_) => Err(format!(
+                "Pipeline stage `{stage}` expected a transcript but received audio"
+            )),
+            Self::Handled => Err(format!(
+                "Pipeline stage `{stage}` expected a transcript but the dictation was already handled"
+            )),
+        }
+    }
+}
+
+#[async_trait]
+pub trait PipelineStage: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn error_stage(&self) -> PipelineErrorStage {
+        PipelineErrorStage::PostProcessing
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageOutcome {
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageTiming {
+    pub stage: &'static str,
+    pub duration_ms: f64,
+    pub outcome: StageOutcome,
+}
+
+impl StageTiming {
+    pub fn new(stage: &'static str, duration: Duration, outcome: StageOutcome) -> Self {
+        Self {
+            stage,
+            duration_ms: (duration.as_secs_f64() * 10_000.0).round() / 10.0,
+            outcome,
+        }
+    }
+}
+
+pub struct TranscribeStage;
+
+#[async_trait]
+impl PipelineStage for TranscribeStage {
+    fn name(&self) -> &'static str {
+        TRANSCRIBE_STAGE
+    }
+
+    fn error_stage(&self) -> PipelineErrorStage {
+        PipelineErrorStage::Transcription
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let StagePayload::Audio(recorded_audio) = payload else {
+            return Err("Transcription stage expected recorded audio"
+                .to_string()
+                .into());
+        };
+        let transcript = delegate.transcribe(recorded_audio).await?;
+        info!(
+            transcript_chars = transcript.text.chars().count(),
+            provider = %transcript.provider,
+            "transcription completed in pipeline"
+        );
+        Ok(StagePayload::Transcript(transcript))
+    }
+}
+
+pub struct VoiceCommandStage;
+
+#[async_trait]
+impl PipelineStage for VoiceCommandStage {
+    fn name(&self) -> &'static str {
+        VOICE_COMMAND_STAGE
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let transcript = payload.into_transcript(self.name())?;
+        if delegate.handle_voice_command(&transcript) {
+            info!("dictation handled as a voice command");
+            return Ok(StagePayload::Handled);
+        }
+        Ok(StagePayload::Transcript(transcript))
+    }
+}
+
+pub struct TranslateStage;
+
+#[async_trait]
+impl PipelineStage for TranslateStage {
+    fn name(&self) -> &'static str {
+        TRANSLATE_STAGE
+    }
+
+    fn error_stage(&self) -> PipelineErrorStage {
+        PipelineErrorStage::Translation
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let transcript = payload.into_transcript(self.name())?;
+        delegate
+            .translate(transcript)
+            .await
+            .map(StagePayload::Transcript)
+            .map_err(StageError::from)
+    }
+}
+
+pub struct CleanUpStage;
+
+#[async_trait]
+impl PipelineStage for CleanUpStage {
+    fn name(&self) -> &'static str {
+        CLEAN_UP_STAGE
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let transcript = payload.into_transcript(self.name())?;
+        Ok(StagePayload::Transcript(
+            delegate.clean_up(transcript).await,
+        ))
+    }
+}
+
+pub struct FormatStage;
+
+#[async_trait]
+impl PipelineStage for FormatStage {
+    fn name(&self) -> &'static str {
+        FORMAT_STAGE
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let transcript = payload.into_transcript(self.name())?;
+        Ok(StagePayload::Transcript(
+            delegate.format_transcript(transcript),
+        ))
+    }
+}
+
+pub struct RedactStage;
+
+#[async_trait]
+impl PipelineStage for RedactStage {
+    fn name(&self) -> &'static str {
+        REDACT_STAGE
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let transcript = payload.into_transcript(self.name())?;
+        Ok(StagePayload::Transcript(delegate.redact(transcript)))
+    }
+}
+
+pub struct OutputStage;
+
+#[async_trait]
+impl PipelineStage for OutputStage {
+    fn name(&self) -> &'static str {
+        OUTPUT_STAGE
+    }
+
+    fn error_stage(&self) -> PipelineErrorStage {
+        PipelineErrorStage::TextInsertion
+    }
+
+    async fn run(
+        &self,
+        delegate: &dyn VoicePipelineDelegate,
+        payload: StagePayload,
+    ) -> Result<StagePayload, StageError> {
+        let transcript = payload.into_transcript(self.name())?;
+        delegate.emit_transcript(&transcript.text);
+
+        if let Err(message) = delegate.save_history_entry(&transcript) {
+            warn!(message = %message, "failed to persist transcript history entry");
+        }
+
+        delegate.insert_text(&transcript.text).await?;
+        info!("pipeline text insertion succeeded");
+        Ok(StagePayload::Transcript(transcript))
+    }
+}
@@ -0,0 +1,292 @@
+use std::f32::consts::PI;
+
+pub const FEATURE_SAMPLE_RATE_HZ: u32 = 16_000;
+pub const CEPSTRAL_COEFFICIENTS: usize = 12;
+
+const FRAME_SAMPLES: usize = 400;
+const HOP_SAMPLES: usize = 160;
+const FFT_SIZE: usize = 512;
+const MEL_BANDS: usize = 26;
+const MIN_MEL_HZ: f32 = 20.0;
+const PRE_EMPHASIS: f32 = 0.97;
+const LOG_ENERGY_FLOOR: f32 = 1e-10;
+
+pub type FeatureFrame = [f32; CEPSTRAL_COEFFICIENTS];
+
+#[derive(Debug)]
+pub struct FeatureExtractor {
+    window: Vec<f32>,
+    mel_filters: Vec<Vec<(usize, f32)>>,
+}
+
+impl Default for FeatureExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureExtractor {
+    pub fn new() -> Self {
+        let window = (0..FRAME_SAMPLES)
+            .map(|index| 0.54 - 0.46 * (2.0 * PI * index as f32 / (FRAME_SAMPLES - 1) as f32).cos())
+            .collect();
+        Self {
+            window,
+            mel_filters: mel_filterbank(),
+        }
+    }
+
+    pub fn extract(&self, samples: &[f32]) -> Vec<FeatureFrame> {
+        if samples.len() < FRAME_SAMPLES {
+            return Vec::new();
+        }
+
+        let mut emphasized = Vec::with_capacity(samples.len());
+        emphasized.push(samples[0]);
+        emphasized.extend(
+            samples
+                .windows(2)
+                .map(|pair| pair[1] - PRE_EMPHASIS * pair[0]),
+        );
+
+        let mut real = vec![0.0_f32; FFT_SIZE];
+        let mut imag = vec![0.0_f32; FFT_SIZE];
+        let mut frames = Vec::with_capacity((samples.len() - FRAME_SAMPLES) / HOP_SAMPLES + 1);
+        for start in (0..=emphasized.len() - FRAME_SAMPLES).step_by(HOP_SAMPLES) {
+            real.fill(0.0);
+            imag.fill(0.0);
+            for (index, sample) in emphasized[start..start + FRAME_SAMPLES].iter().enumerate() {
+                real[index] = sample * self.window[index];
+            }
+            fft_in_place(&mut real, &mut imag);
+
+            let log_energies = self
+                .mel_filters
+                .iter()
+                .map(|filter| {
+                    let energy = filter
+                        .iter()
+                        .map(|&(bin, weight)| {
+                            weight * (real[bin] * real[bin] + imag[bin] * imag[bin])
+                        })
+                        .sum::<f32>();
+                    energy.max(LOG_ENERGY_FLOOR).ln()
+                })
+                .collect::<Vec<_>>();
+            frames.push(cepstrum(&log_energies));
+        }
+
+        subtract_mean(&mut frames);
+        frames
+    }
+}
+
+pub fn to_feature_rate(samples: &[i16], sample_rate_hz: u32) -> Vec<f32> {
+    let normalize = |sample: i16| f32::from(sample) / f32::from(i16::MAX);
+    if sample_rate_hz == 0 || samples.is_empty() {
+        return Vec::new();
+    }
+    if sample_rate_hz == FEATURE_SAMPLE_RATE_HZ {
+        return samples.iter().copied().map(normalize).collect();
+    }
+
+    let ratio = f64::from(sample_rate_hz) / f64::from(FEATURE_SAMPLE_RATE_HZ);
+    let output_len = (samples.len() as f64 / ratio) as usize;
+    (0..output_len)
+        .map(|index| {
+            let start = ((index as f64 * ratio) as usize).min(samples.len() - 1);
+            let end = (((index + 1) as f64 * ratio) as usize).clamp(start + 1, samples.len());
+            let covered = &samples[start..end];
+            covered.iter().copied().map(normalize).sum::<f32>() / covered.len() as f32
+        })
+        .collect()
+}
+
+pub fn dtw_distance(left: &[FeatureFrame], right: &[FeatureFrame]) -> f32 {
+    if left.is_empty() || right.is_empty() {
+        return f32::INFINITY;
+    }
+
+    let mut previous = vec![f32::INFINITY; right.len() + 1];
+    let mut current = vec![f32::INFINITY; right.len() + 1];
+    previous[0] = 0.0;
+    for left_frame in left {
+        current[0] = f32::INFINITY;
+        for (index, right_frame) in right.iter().enumerate() {
+            let best_step = previous[index].min(previous[index + 1]).min(current[index]);
+            current[index + 1] = frame_distance(left_frame, right_frame) + best_step;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[right.len()] / (left.len() + right.len()) as f32
+}
+
+fn frame_distance(left: &FeatureFrame, right: &FeatureFrame) -> f32 {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| (left - right) * (left - right))
+        .sum::<f32>()
+        .sqrt()
+}
+
+fn cepstrum(log_energies: &[f32]) -> FeatureFrame {
+    let bands = log_energies.len() as f32;
+    let mut frame = [0.0; CEPSTRAL_COEFFICIENTS];
+    for (coefficient, value) in frame.iter_mut().enumerate() {
+        let order = (coefficient + 1) as f32;
+        *value = log_energies
+            .iter()
+            .enumerate()
+            .map(|(band, energy)| energy * (PI * order * (band as f32 + 0.5) / bands).cos())
+            .sum();
+    }
+    frame
+}
+
+fn subtract_mean(frames: &mut [FeatureFrame]) {
+    if frames.is_empty() {
+        return;
+    }
+
+    let mut mean = [0.0_f32; CEPSTRAL_COEFFICIENTS];
+    for frame in frames.iter() {
+        for (sum, value) in mean.iter_mut().zip(frame) {
+            *sum += value;
+        }
+    }
+    let count = frames.len() as f32;
+    for frame in frames.iter_mut() {
+        for (value, sum) in frame.iter_mut().zip(&mean) {
+            *value -= sum / count;
+        }
+    }
+}
+
+fn mel_filterbank() -> Vec<Vec<(usize, f32)>> {
+    let hz_to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let mel_to_hz = |mel: f32| 700.0 * (10.0_f32.powf(mel / 2595.0) - 1.0);
+    let max_hz = FEATURE_SAMPLE_RATE_HZ as f32 / 2.0;
+    let (min_mel, max_mel) = (hz_to_mel(MIN_MEL_HZ), hz_to_mel(max_hz));
+    let bins = (0..MEL_BANDS + 2)
+        .map(|point| {
+            let mel = min_mel + (max_mel - min_mel) * point as f32 / (MEL_BANDS + 1) as f32;
+            let hz = mel_to_hz(mel);
+            ((FFT_SIZE as f32 * hz / FEATURE_SAMPLE_RATE_HZ as f32).floor() as usize)
+                .min(FFT_SIZE / 2)
+        })
+        .collect::<Vec<_>>();
+
+    bins.windows(3)
+        .map(|edges| {
+            let (left, center, right) = (edges[0], edges[1], edges[2]);
+            let mut filter = Vec::new();
+            for bin in left..=right {
+                let weight = if bin <= center {
+                    (bin - left) as f32 / (center - left).max(1) as f32
+                } else {
+                    (right - bin) as f32 / (right - center).max(1) as f32
+                };
+                if weight > 0.0 {
+                    filter.push((bin, weight));
+                }
+            }
+            filter
+        })
+        .collect()
+}
+
+fn fft_in_place(real: &mut [f32], imag: &mut [f32]) {
+    let size = real.len();
+    let mut reversed = 0;
+    for index in 1..size {
+        let mut bit = size >> 1;
+        while reversed & bit != 0 {
+            reversed ^= bit;
+            bit >>= 1;
+        }
+        reversed |= bit;
+        if index < reversed {
+            real.swap(index, reversed);
+            imag.swap(index, reversed);
+        }
+    }
+
+    let mut span = 2;
+    while span <= size {
+        let angle = -2.0 * PI / span as f32;
+        for start in (0..size).step_by(span) {
+            for offset in 0..span / 2 {
+                let (sin, cos) = (angle * offset as f32).sin_cos();
+                let (even, odd) = (start + offset, start + offset + span / 2);
+                let odd_real = real[odd] * cos - imag[odd] * sin;
+                let odd_imag = real[odd] * sin + imag[odd] * cos;
+                real[odd] = real[even] - odd_real;
+                imag[odd] = imag[even] - odd_imag;
+                real[even] += odd_real;
+                imag[even] += odd_imag;
+            }
+        }
+        span <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::{
+        dtw_distance, fft_in_place, to_feature_rate, FeatureExtractor, FeatureFrame,
+        CEPSTRAL_COEFFICIENTS, FEATURE_SAMPLE_RATE_HZ,
+    };
+
+    #[test]
+    fn fft_puts_a_pure_tone_in_its_bin() {
+        let mut real = (0..64)
+            .map(|index| (2.0 * PI * 5.0 * index as f32 / 64.0).cos())
+            .collect::<Vec<_>>();
+        let mut imag = vec![0.0; 64];
+        fft_in_place(&mut real, &mut imag);
+
+        let magnitudes = real
+            .iter()
+            .zip(&imag)
+            .map(|(real, imag)| (real * real + imag * imag).sqrt())
+            .collect::<Vec<_>>();
+        assert!((magnitudes[5] - 32.0).abs() < 1e-3);
+        assert!((magnitudes[59] - 32.0).abs() < 1e-3);
+        assert!(magnitudes[..5].iter().all(|magnitude| *magnitude < 1e-3));
+    }
+
+    #[test]
+    fn dtw_tolerates_tempo_changes_but_not_different_content() {
+        let frame = |value: f32| -> FeatureFrame { [value; CEPSTRAL_COEFFICIENTS] };
+        let phrase = [0.0, 1.0, 2.0, 1.0].map(frame);
+        let slower = [0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 1.0].map(frame);
+        let other = [3.0, 3.0, 3.0, 3.0].map(frame);
+
+        assert_eq!(dtw_distance(&phrase, &phrase), 0.0);
+        assert_eq!(dtw_distance(&phrase, &slower), 0.0);
+        assert!(dtw_distance(&phrase, &other) > 1.0);
+        assert_eq!(dtw_distance(&phrase, &[]), f32::INFINITY);
+    }
+
+    #[test]
+    fn extracts_one_frame_per_hop_at_the_feature_rate() {
+        let samples = (0..48_000)
+            .map(|index| ((index % 96) as i16 - 48) * 200)
+            .collect::<Vec<_>>();
+        let downsampled = to_feature_rate(&samples, 48_000);
+        assert_eq!(downsampled.len(), FEATURE_SAMPLE_RATE_HZ as usize);
+
+        let frames = FeatureExtractor::new().extract(&downsampled);
+        assert_eq!(frames.len(), 98);
+        assert!(frames
+            .iter()
+            .flatten()
+            .all(|coefficient| coefficient.is_finite()));
+        assert!(FeatureExtractor::new()
+            .extract(&downsampled[..100])
+            .is_empty());
+    }
+}
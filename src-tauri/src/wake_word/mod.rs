@@ -0,0 +1,529 @@
+mod features;
+mod segmenter;
+
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{debug, info, warn};
+
+use crate::{
+    audio_capture_service::{AudioInputChunk, AudioInputChunkCallback},
+    permission_service::PermissionState,
+    persistence::JsonDocumentStore,
+    settings_store::{VoiceSettings, DEFAULT_WAKE_WORD_SENSITIVITY},
+    trigger_service::{dispatch_trigger, TriggerAction, TriggerSource},
+    AppState,
+};
+use features::{
+    dtw_distance, to_feature_rate, FeatureExtractor, FeatureFrame, FEATURE_SAMPLE_RATE_HZ,
+};
+use segmenter::UtteranceSegmenter;
+
+pub const EVENT_WAKE_WORD_CHANGED: &str = "voice://wake-word-changed";
+pub const EVENT_WAKE_WORD_DETECTED: &str = "voice://wake-word-detected";
+pub const REQUIRED_WAKE_WORD_SAMPLES: usize = 3;
+
+const WAKE_WORD_FILE_NAME: &str = "wake_word.json";
+const MAX_WAKE_WORD_SAMPLES: usize = 5;
+const CHUNK_CHANNEL_CAPACITY: usize = 64;
+const DETECTION_COOLDOWN: Duration = Duration::from_secs(2);
+const ENROLLMENT_TIMEOUT: Duration = Duration::from_secs(10);
+const MIN_LENGTH_RATIO: f32 = 0.6;
+const MAX_LENGTH_RATIO: f32 = 1.6;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct WakeWordSample {
+    recorded_at: String,
+    frames: Vec<FeatureFrame>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct WakeWordProfile {
+    #[serde(default)]
+    samples: Vec<WakeWordSample>,
+}
+
+impl WakeWordProfile {
+    fn is_ready(&self) -> bool {
+        self.samples.len() >= REQUIRED_WAKE_WORD_SAMPLES
+    }
+
+    fn add_sample(&mut self, frames: Vec<FeatureFrame>) {
+        self.samples.push(WakeWordSample {
+            recorded_at: Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+            frames,
+        });
+        let excess = self.samples.len().saturating_sub(MAX_WAKE_WORD_SAMPLES);
+        self.samples.drain(..excess);
+    }
+
+    fn spread(&self) -> Option<f32> {
+        let mut total = 0.0;
+        let mut pairs = 0;
+        for (index, sample) in self.samples.iter().enumerate() {
+            for other in &self.samples[index + 1..] {
+                total += dtw_distance(&sample.frames, &other.frames);
+                pairs += 1;
+            }
+        }
+        (pairs > 0 && total.is_finite()).then(|| total / pairs as f32)
+    }
+
+    fn match_distance(&self, frames: &[FeatureFrame], sensitivity: u8) -> Option<f32> {
+        if !self.is_ready() || frames.is_empty() {
+            return None;
+        }
+        let mean_len = self
+            .samples
+            .iter()
+            .map(|sample| sample.frames.len())
+            .sum::<usize>() as f32
+            / self.samples.len() as f32;
+        let length_ratio = frames.len() as f32 / mean_len;
+        if !(MIN_LENGTH_RATIO..=MAX_LENGTH_RATIO).contains(&length_ratio) {
+            return None;
+        }
+
+        let threshold = self.spread()? * (1.0 + f32::from(sensitivity) / 100.0);
+        let distance = self
+            .samples
+            .iter()
+            .map(|sample| dtw_distance(frames, &sample.frames))
+            .fold(f32::INFINITY, f32::min);
+        (distance <= threshold).then_some(distance)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WakeWordStatus {
+    pub enabled: bool,
+    pub listening: bool,
+    pub enrolling: bool,
+    pub samples: usize,
+    pub required_samples: usize,
+}
+
+#[derive(Debug)]
+struct WakeWordShared {
+    document: JsonDocumentStore<WakeWordProfile>,
+    profile: Mutex<WakeWordProfile>,
+    sensitivity: AtomicU8,
+    listening: AtomicBool,
+    enrollment_deadline: Mutex<Option<Instant>>,
+    command_deadline: Mutex<Option<Instant>>,
+}
+
+impl WakeWordShared {
+    fn sample_count(&self) -> usize {
+        self.profile
+            .lock()
+            .map(|profile| profile.samples.len())
+            .unwrap_or_default()
+    }
+
+    fn is_enrolling(&self) -> bool {
+        self.enrollment_deadline
+            .lock()
+            .is_ok_and(|deadline| deadline.is_some_and(|deadline| Instant::now() < deadline))
+    }
+
+    fn expire_enrollment(&self) -> bool {
+        let Ok(mut deadline) = self.enrollment_deadline.lock() else {
+            return false;
+        };
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            *deadline = None;
+            return true;
+        }
+        false
+    }
+
+    fn take_enrollment(&self) -> bool {
+        self.enrollment_deadline
+            .lock()
+            .is_ok_and(|mut deadline| deadline.take().is_some())
+    }
+
+    fn is_awaiting_command(&self) -> bool {
+        self.command_deadline
+            .lock()
+            .is_ok_and(|deadline| deadline.is_some_and(|deadline| Instant::now() < deadline))
+    }
+
+    fn expire_command(&self) -> bool {
+        let Ok(mut deadline) = self.command_deadline.lock() else {
+            return false;
+        };
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            *deadline = None;
+            return true;
+        }
+        false
+    }
+
+    fn take_command(&self) -> bool {
+        self.command_deadline
+            .lock()
+            .is_ok_and(|mut deadline| deadline.take().is_some())
+    }
+
+    fn record_sample(&self, frames: Vec<FeatureFrame>) -> Result<usize, String> {
+        let mut profile = self
+            .profile
+            .lock()
+            .map_err(|_| "Wake word profile lock is poisoned".to_string())?;
+        profile.add_sample(frames);
+        self.document.write(&*profile)?;
+        Ok(profile.samples.len())
+    }
+
+    fn match_distance(&self, frames: &[FeatureFrame]) -> Option<f32> {
+        if !self.listening.load(Ordering::Relaxed) {
+            return None;
+        }
+        let sensitivity = self.sensitivity.load(Ordering::Relaxed);
+        self.profile
+            .lock()
+            .ok()?
+            .match_distance(frames, sensitivity)
+    }
+}
+
+pub struct WakeWordService {
+    shared: Arc<WakeWordShared>,
+    listener: Mutex<Option<AudioInputChunkCallback>>,
+}
+
+impl std::fmt::Debug for WakeWordService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WakeWordService")
+            .field("shared", &self.shared)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WakeWordService {
+    pub fn new(app: &AppHandle) -> Result<Self, String> {
+        let app_data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|error| format!("Failed to resolve app data directory: {error}"))?;
+
+        Self::new_with_file_path(app_data_dir.join(WAKE_WORD_FILE_NAME))
+    }
+
+    pub fn new_with_file_path(file_path: PathBuf) -> Result<Self, String> {
+        let document = JsonDocumentStore::<WakeWordProfile>::new(file_path, "wake word samples");
+        let profile = document.read()?;
+        debug!(
+            path = %document.file_path().display(),
+            samples = profile.samples.len(),
+            "wake word service initialized"
+        );
+        Ok(Self {
+            shared: Arc::new(WakeWordShared {
+                document,
+                profile: Mutex::new(profile),
+                sensitivity: AtomicU8::new(DEFAULT_WAKE_WORD_SENSITIVITY),
+                listening: AtomicBool::new(false),
+                enrollment_deadline: Mutex::new(None),
+                command_deadline: Mutex::new(None),
+            }),
+            listener: Mutex::new(None),
+        })
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.shared
+            .profile
+            .lock()
+            .is_ok_and(|profile| profile.is_ready())
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.shared.listening.load(Ordering::Relaxed)
+    }
+
+    pub fn status(&self, settings: &VoiceSettings) -> WakeWordStatus {
+        WakeWordStatus {
+            enabled: settings.wake_word_enabled,
+            listening: self.is_listening(),
+            enrolling: self.shared.is_enrolling(),
+            samples: self.shared.sample_count(),
+            required_samples: REQUIRED_WAKE_WORD_SAMPLES,
+        }
+    }
+
+    pub fn sync_with_settings(
+        &self,
+        app: &AppHandle,
+        settings: &VoiceSettings,
+        microphone_granted: bool,
+    ) -> Option<AudioInputChunkCallback> {
+        self.shared
+            .sensitivity
+            .store(settings.wake_word_sensitivity, Ordering::Relaxed);
+        let listening = microphone_granted && settings.wake_word_enabled && self.is_ready();
+        let was_listening = self.shared.listening.swap(listening, Ordering::Relaxed);
+        if was_listening != listening {
+            info!(listening, "wake word listening changed");
+            crate::refresh_tray_menu(app);
+            emit_status(app, self.status(settings));
+        }
+
+        let needs_audio = microphone_granted
+            && (listening || self.shared.is_enrolling() || self.shared.is_awaiting_command());
+        let Ok(mut listener) = self.listener.lock() else {
+            warn!("wake word listener lock is poisoned");
+            return None;
+        };
+        if !needs_audio {
+            *listener = None;
+            return None;
+        }
+        Some(Arc::clone(listener.get_or_insert_with(|| {
+            spawn_listener(app, Arc::clone(&self.shared))
+        })))
+    }
+
+    pub fn start_enrollment(&self) -> Result<(), String> {
+        let mut deadline = self
+            .shared
+            .enrollment_deadline
+            .lock()
+            .map_err(|_| "Wake word enrollment lock is poisoned".to_string())?;
+        *deadline = Some(Instant::now() + ENROLLMENT_TIMEOUT);
+        info!("wake word enrollment started");
+        Ok(())
+    }
+
+    pub fn cancel_enrollment(&self) {
+        if self.shared.take_enrollment() {
+            info!("wake word enrollment cancelled");
+        }
+    }
+
+    pub fn listen_for_command(&self, timeout: Duration) -> Result<(), String> {
+        let mut deadline = self
+            .shared
+            .command_deadline
+            .lock()
+            .map_err(|_| "Wake word command lock is poisoned".to_string())?;
+        *deadline = Some(Instant::now() + timeout);
+        info!(
+            timeout_ms = timeout.as_millis(),
+            "listening for a spoken command"
+        );
+        Ok(())
+    }
+
+    pub fn cancel_command(&self) -> bool {
+        self.shared.take_command()
+    }
+
+    pub fn clear_samples(&self) -> Result<(), String> {
+        let mut profile = self
+            .shared
+            .profile
+            .lock()
+            .map_err(|_| "Wake word profile lock is poisoned".to_string())?;
+        *profile = WakeWordProfile::default();
+        self.shared.document.write(&*profile)?;
+        info!("wake word samples cleared");
+        Ok(())
+    }
+}
+
+// Runs on the audio thread, so chunks are dropped when the worker falls behind.
+fn spawn_listener(app: &AppHandle, shared: Arc<WakeWordShared>) -> AudioInputChunkCallback {
+    let (chunk_tx, chunk_rx) = mpsc::sync_channel::<AudioInputChunk>(CHUNK_CHANNEL_CAPACITY);
+    let worker_app = app.clone();
+    thread::spawn(move || run_listener(worker_app, shared, chunk_rx));
+    Arc::new(move |chunk: AudioInputChunk| {
+        let _ = chunk_tx.try_send(chunk);
+    })
+}
+
+fn run_listener(app: AppHandle, shared: Arc<WakeWordShared>, chunk_rx: Receiver<AudioInputChunk>) {
+    debug!("wake word listener started");
+    let extractor = FeatureExtractor::new();
+    let mut segmenter = UtteranceSegmenter::default();
+    let mut cooldown_until: Option<Instant> = None;
+
+    for chunk in chunk_rx {
+        if shared.expire_enrollment() {
+            info!("wake word enrollment timed out");
+            sync_with_current_settings(&app);
+        }
+        if shared.expire_command() {
+            info!("spoken command window closed without a command");
+            crate::error_recovery::command_window_expired(&app);
+            sync_with_current_settings(&app);
+        }
+
+        let samples = to_feature_rate(&chunk.pcm16_mono_samples, chunk.sample_rate_hz);
+        for utterance in segmenter.push(&samples) {
+            let frames = extractor.extract(&utterance);
+            if shared.take_enrollment() {
+                match shared.record_sample(frames) {
+                    Ok(samples) => info!(samples, "wake word sample enrolled"),
+                    Err(error) => warn!(%error, "failed to save wake word sample"),
+                }
+                sync_with_current_settings(&app);
+                continue;
+            }
+            if shared.take_command() {
+                crate::error_recovery::handle_command_utterance(
+                    &app,
+                    &utterance,
+                    FEATURE_SAMPLE_RATE_HZ,
+                );
+                sync_with_current_settings(&app);
+                continue;
+            }
+            if cooldown_until.is_some_and(|until| Instant::now() < until) {
+                continue;
+            }
+            let Some(distance) = shared.match_distance(&frames) else {
+                continue;
+            };
+
+            info!(distance, "wake word detected");
+            cooldown_until = Some(Instant::now() + DETECTION_COOLDOWN);
+            if let Err(error) = app.emit(EVENT_WAKE_WORD_DETECTED, ()) {
+                warn!(%error, "failed to emit wake word detected event");
+            }
+            // The phrase is still in any pre-roll buffer and should not open the transcript.
+            app.state::<AppState>()
+                .services
+                .audio_capture_service
+                .discard_standby_audio();
+            dispatch_trigger(&app, TriggerSource::WakeWord, TriggerAction::Start);
+        }
+    }
+    debug!("wake word listener stopped");
+}
+
+fn sync_with_current_settings(app: &AppHandle) {
+    let settings = app.state::<AppState>().services.settings_store.current();
+    crate::sync_microphone_standby_with_settings(app, &settings);
+    emit_status(app, app.state::<WakeWordService>().status(&settings));
+}
+
+fn emit_status(app: &AppHandle, status: WakeWordStatus) {
+    if let Err(error) = app.emit(EVENT_WAKE_WORD_CHANGED, status) {
+        warn!(%error, "failed to emit wake word status");
+    }
+}
+
+#[tauri::command]
+pub fn get_wake_word_status(
+    state: State<'_, AppState>,
+    service: State<'_, WakeWordService>,
+) -> WakeWordStatus {
+    service.status(&state.services.settings_store.current())
+}
+
+#[tauri::command]
+pub fn start_wake_word_enrollment(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service: State<'_, WakeWordService>,
+) -> Result<WakeWordStatus, String> {
+    if state.services.permission_service.microphone_permission() != PermissionState::Granted {
+        return Err("Microphone access is required to record a wake word sample".to_string());
+    }
+    service.start_enrollment()?;
+    sync_with_current_settings(&app);
+    Ok(service.status(&state.services.settings_store.current()))
+}
+
+#[tauri::command]
+pub fn cancel_wake_word_enrollment(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service: State<'_, WakeWordService>,
+) -> WakeWordStatus {
+    service.cancel_enrollment();
+    sync_with_current_settings(&app);
+    service.status(&state.services.settings_store.current())
+}
+
+#[tauri::command]
+pub fn clear_wake_word_samples(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    service: State<'_, WakeWordService>,
+) -> Result<WakeWordStatus, String> {
+    service.clear_samples()?;
+    sync_with_current_settings(&app);
+    Ok(service.status(&state.services.settings_store.current()))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{
+        features::{FeatureFrame, CEPSTRAL_COEFFICIENTS},
+        WakeWordProfile, MAX_WAKE_WORD_SAMPLES,
+    };
+    use crate::persistence::JsonDocumentStore;
+
+    fn phrase(offset: f32, repeat: usize) -> Vec<FeatureFrame> {
+        [0.0, 2.0, 4.0, 2.0, 0.0, -2.0]
+            .iter()
+            .flat_map(|value| std::iter::repeat_n([value + offset; CEPSTRAL_COEFFICIENTS], repeat))
+            .collect()
+    }
+
+    #[test]
+    fn matches_the_enrolled_phrase_at_a_different_tempo_only() {
+        let mut profile = WakeWordProfile::default();
+        profile.add_sample(phrase(0.0, 4));
+        profile.add_sample(phrase(0.1, 4));
+        assert_eq!(profile.match_distance(&phrase(0.0, 4), 50), None);
+        profile.add_sample(phrase(-0.1, 4));
+
+        assert!(profile.match_distance(&phrase(0.0, 5), 50).is_some());
+        assert_eq!(profile.match_distance(&phrase(3.0, 4), 50), None);
+        assert_eq!(profile.match_distance(&phrase(0.0, 12), 100), None);
+    }
+
+    #[test]
+    fn keeps_only_the_latest_samples_and_round_trips_through_disk() {
+        let path = std::env::temp_dir()
+            .join(format!("buzz-wake-word-{}", Uuid::new_v4()))
+            .join("wake_word.json");
+        let document = JsonDocumentStore::<WakeWordProfile>::new(path.clone(), "wake word samples");
+
+        let mut profile = WakeWordProfile::default();
+        for offset in 0..MAX_WAKE_WORD_SAMPLES + 2 {
+            profile.add_sample(phrase(offset as f32, 1));
+        }
+        assert_eq!(profile.samples.len(), MAX_WAKE_WORD_SAMPLES);
+        assert_eq!(profile.samples[0].frames, phrase(2.0, 1));
+
+        assert_eq!(
+            document.read().expect("missing file should load"),
+            WakeWordProfile::default()
+        );
+        document.write(&profile).expect("profile should be written");
+        assert_eq!(document.read().expect("profile should load"), profile);
+        let _ = std::fs::remove_dir_all(path.parent().expect("path should have a parent"));
+    }
+}
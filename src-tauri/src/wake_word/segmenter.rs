@@ -0,0 +1,150 @@
+const BLOCK_SAMPLES: usize = 160;
+const MIN_SPEECH_RMS: f32 = 0.01;
+const NOISE_FLOOR_MARGIN: f32 = 4.0;
+const INITIAL_NOISE_FLOOR: f32 = 0.001;
+const NOISE_FLOOR_ADAPTATION: f32 = 0.05;
+const OVERFLOW_NOISE_FLOOR_ADAPTATION: f32 = 0.01;
+const LEAD_IN_BLOCKS: usize = 5;
+const TRAILING_SILENCE_BLOCKS: usize = 5;
+const END_SILENCE_BLOCKS: usize = 30;
+const MIN_SPEECH_BLOCKS: usize = 20;
+const MAX_UTTERANCE_BLOCKS: usize = 200;
+
+#[derive(Debug)]
+pub struct UtteranceSegmenter {
+    pending: Vec<f32>,
+    lead_in: Vec<f32>,
+    utterance: Vec<f32>,
+    noise_floor: f32,
+    in_utterance: bool,
+    overflowed: bool,
+    speech_blocks: usize,
+    silent_blocks: usize,
+}
+
+impl Default for UtteranceSegmenter {
+    fn default() -> Self {
+        Self {
+            pending: Vec::with_capacity(BLOCK_SAMPLES),
+            lead_in: Vec::with_capacity(LEAD_IN_BLOCKS * BLOCK_SAMPLES),
+            utterance: Vec::new(),
+            noise_floor: INITIAL_NOISE_FLOOR,
+            in_utterance: false,
+            overflowed: false,
+            speech_blocks: 0,
+            silent_blocks: 0,
+        }
+    }
+}
+
+impl UtteranceSegmenter {
+    pub fn push(&mut self, samples: &[f32]) -> Vec<Vec<f32>> {
+        let mut utterances = Vec::new();
+        for &sample in samples {
+            self.pending.push(sample);
+            if self.pending.len() < BLOCK_SAMPLES {
+                continue;
+            }
+            let block = std::mem::take(&mut self.pending);
+            if let Some(utterance) = self.push_block(&block) {
+                utterances.push(utterance);
+            }
+            self.pending = block;
+            self.pending.clear();
+        }
+        utterances
+    }
+
+    fn push_block(&mut self, block: &[f32]) -> Option<Vec<f32>> {
+        let rms =
+            (block.iter().map(|sample| sample * sample).sum::<f32>() / block.len() as f32).sqrt();
+        let is_speech = rms >= (self.noise_floor * NOISE_FLOOR_MARGIN).max(MIN_SPEECH_RMS);
+
+        if !self.in_utterance {
+            if !is_speech {
+                self.adapt_noise_floor(rms, NOISE_FLOOR_ADAPTATION);
+                self.lead_in.extend_from_slice(block);
+                let excess = self
+                    .lead_in
+                    .len()
+                    .saturating_sub(LEAD_IN_BLOCKS * BLOCK_SAMPLES);
+                self.lead_in.drain(..excess);
+                return None;
+            }
+            self.in_utterance = true;
+            self.overflowed = false;
+            self.speech_blocks = 0;
+            self.silent_blocks = 0;
+            self.utterance = std::mem::take(&mut self.lead_in);
+        }
+
+        if self.overflowed {
+            self.adapt_noise_floor(rms, OVERFLOW_NOISE_FLOOR_ADAPTATION);
+        } else {
+            self.utterance.extend_from_slice(block);
+            if self.utterance.len() > MAX_UTTERANCE_BLOCKS * BLOCK_SAMPLES {
+                self.overflowed = true;
+                self.utterance = Vec::new();
+            }
+        }
+        if is_speech {
+            self.speech_blocks += 1;
+            self.silent_blocks = 0;
+        } else {
+            self.silent_blocks += 1;
+        }
+        if self.silent_blocks < END_SILENCE_BLOCKS {
+            return None;
+        }
+
+        self.in_utterance = false;
+        let mut utterance = std::mem::take(&mut self.utterance);
+        if self.overflowed || self.speech_blocks < MIN_SPEECH_BLOCKS {
+            return None;
+        }
+        let trailing_silence = (self.silent_blocks - TRAILING_SILENCE_BLOCKS) * BLOCK_SAMPLES;
+        utterance.truncate(utterance.len().saturating_sub(trailing_silence));
+        Some(utterance)
+    }
+
+    fn adapt_noise_floor(&mut self, rms: f32, rate: f32) {
+        self.noise_floor = self.noise_floor * (1.0 - rate) + rms * rate;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UtteranceSegmenter;
+
+    fn tone(samples: usize) -> Vec<f32> {
+        (0..samples)
+            .map(|index| if index % 20 < 10 { 0.3 } else { -0.3 })
+            .collect()
+    }
+
+    #[test]
+    fn cuts_speech_between_silences_and_drops_short_blips() {
+        let mut segmenter = UtteranceSegmenter::default();
+        let silence = vec![0.0; 8_000];
+
+        let mut stream = silence.clone();
+        stream.extend(tone(1_600));
+        stream.extend(&silence);
+        assert!(segmenter.push(&stream).is_empty());
+
+        let mut stream = tone(8_000);
+        stream.extend(&silence);
+        let utterances = segmenter.push(&stream);
+        assert_eq!(utterances.len(), 1);
+        // Five blocks of lead-in, the phrase itself and five blocks of trailing silence.
+        assert_eq!(utterances[0].len(), 800 + 8_000 + 800);
+    }
+
+    #[test]
+    fn drops_sounds_longer_than_a_phrase() {
+        let mut segmenter = UtteranceSegmenter::default();
+        let mut stream = tone(48_000);
+        stream.extend(vec![0.0; 8_000]);
+        assert!(segmenter.push(&stream).is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, EventTarget, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    Window, WindowEvent,
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    settings_store::{VoiceSettingsUpdate, WindowBounds},
+    tray_popover,
+    trigger_service::URL_SCHEME,
+    AppState,
+};
+
+pub const MAIN_WINDOW_LABEL: &str = "main";
+pub const HISTORY_WINDOW_LABEL: &str = "history";
+pub const OVERLAY_WINDOW_LABEL: &str = "recording-overlay";
+pub const EVENT_NAVIGATE: &str = "voice://navigate";
+
+const HISTORY_WINDOW_WIDTH: f64 = 720.0;
+const HISTORY_WINDOW_HEIGHT: f64 = 640.0;
+const HISTORY_WINDOW_MIN_WIDTH: f64 = 360.0;
+const HISTORY_WINDOW_MIN_HEIGHT: f64 = 320.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowTarget {
+    Main,
+    Settings,
+    History,
+    Overlay,
+    Onboarding,
+}
+
+impl WindowTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::Settings => "settings",
+            Self::History => "history",
+            Self::Overlay => "overlay",
+            Self::Onboarding => "onboarding",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Main | Self::Settings | Self::Onboarding => MAIN_WINDOW_LABEL,
+            Self::History => HISTORY_WINDOW_LABEL,
+            Self::Overlay => OVERLAY_WINDOW_LABEL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WindowParams {
+    pub entry_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct NavigateEvent {
+    target: WindowTarget,
+    params: WindowParams,
+}
+
+pub fn open(app: &AppHandle, target: WindowTarget, params: WindowParams) {
+    info!(target = target.as_str(), entry_id = ?params.entry_id, "opening window");
+    let window = match target {
+        // The overlay never takes focus, so it keeps its own show path.
+        WindowTarget::Overlay => return crate::show_recording_overlay(app),
+        WindowTarget::History if app.get_webview_window(HISTORY_WINDOW_LABEL).is_none() => {
+            match create_history_window(app, params.entry_id.as_deref()) {
+                Ok(window) => window,
+                Err(error) => return warn!(%error, "history window initialization failed"),
+            }
+        }
+        _ => {
+            let Some(window) = app.get_webview_window(target.label()) else {
+                return warn!(
+                    target = target.as_str(),
+                    "window was not found while opening"
+                );
+            };
+            navigate(app, target, params);
+            window
+        }
+    };
+
+    if let Err(error) = window.show() {
+        warn!(%error, target = target.as_str(), "failed to show window");
+    }
+    if let Err(error) = window.set_focus() {
+        warn!(%error, target = target.as_str(), "failed to focus window");
+    }
+}
+
+pub fn hide(app: &AppHandle, target: WindowTarget) {
+    if target == WindowTarget::Overlay {
+        return crate::hide_recording_overlay(app);
+    }
+    let Some(window) = app.get_webview_window(target.label()) else {
+        return warn!(
+            target = target.as_str(),
+            "window was not found while hiding"
+        );
+    };
+    info!(target = target.as_str(), "hiding window");
+    if let Err(error) = window.hide() {
+        warn!(%error, target = target.as_str(), "failed to hide window");
+    }
+}
+
+fn navigate(app: &AppHandle, target: WindowTarget, params: WindowParams) {
+    if let Err(error) = app.emit_to(
+        EventTarget::webview_window(target.label()),
+        EVENT_NAVIGATE,
+        NavigateEvent { target, params },
+    ) {
+        warn!(%error, target = target.as_str(), "failed to emit navigate event");
+    }
+}
+
+fn create_history_window(app: &AppHandle, entry_id: Option<&str>) -> Result<WebviewWindow, String> {
+    let bounds = app
+        .state::<AppState>()
+        .services
+        .settings_store
+        .current()
+        .history_window_bounds;
+    let builder = WebviewWindowBuilder::new(
+        app,
+        HISTORY_WINDOW_LABEL,
+        WebviewUrl::App(history_window_path(entry_id).into()),
+    )
+    .title("Buzz History")
+    .min_inner_size(HISTORY_WINDOW_MIN_WIDTH, HISTORY_WINDOW_MIN_HEIGHT)
+    .visible(false);
+    let builder = match bounds {
+        Some(bounds) => builder
+            .inner_size(f64::from(bounds.width), f64::from(bounds.height))
+            .position(f64::from(bounds.x), f64::from(bounds.y)),
+        None => builder
+            .inner_size(HISTORY_WINDOW_WIDTH, HISTORY_WINDOW_HEIGHT)
+            .center(),
+    };
+    let window = builder
+        .build()
+        .map_err(|error| format!("failed to create history window: {error}"))?;
+    info!(
+        restored_bounds = bounds.is_some(),
+        "history window initialized"
+    );
+    Ok(window)
+}
+
+fn history_window_path(entry_id: Option<&str>) -> String {
+    let query = entry_id
+        .and_then(|id| Url::parse_with_params("buzz://history", [("entry", id)]).ok())
+        .and_then(|url| url.query().map(str::to_string));
+    match query {
+        Some(query) => format!("history.html?{query}"),
+        None => "history.html".to_string(),
+    }
+}
+
+fn remember_history_window_bounds(window: &Window) {
+    let scale_factor = window.scale_factor().unwrap_or(1.0);
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    let position = position.to_logical::<f64>(scale_factor);
+    let size = size.to_logical::<f64>(scale_factor);
+    let bounds = WindowBounds {
+        x: position.x.round() as i32,
+        y: position.y.round() as i32,
+        width: size.width.round() as u32,
+        height: size.height.round() as u32,
+    };
+
+    let state = window.app_handle().state::<AppState>();
+    if state
+        .services
+        .settings_store
+        .current()
+        .history_window_bounds
+        == Some(bounds)
+    {
+        return;
+    }
+    match state.services.settings_store.update(
+        window.app_handle(),
+        VoiceSettingsUpdate {
+            history_window_bounds: Some(Some(bounds)),
+            ..VoiceSettingsUpdate::default()
+        },
+    ) {
+        Ok(_) => debug!(?bounds, "history window bounds saved"),
+        Err(error) => warn!(%error, "failed to save history window bounds"),
+    }
+}
+
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if window.label() == HISTORY_WINDOW_LABEL
+        && matches!(
+            event,
+            WindowEvent::CloseRequested { .. } | WindowEvent::Focused(false)
+        )
+    {
+        remember_history_window_bounds(window);
+    }
+
+    if window.label() == tray_popover::POPOVER_WINDOW_LABEL {
+        if let WindowEvent::Focused(false) = event {
+            tray_popover::hide_popover(window.app_handle());
+        }
+    }
+
+    if let WindowEvent::CloseRequested { api, .. } = event {
+        if window.label() == OVERLAY_WINDOW_LABEL {
+            info!("allowing overlay window close request to proceed");
+            return;
+        }
+        api.prevent_close();
+        info!(window = %window.label(), "window close requested; hiding instead");
+        if let Err(error) = window.hide() {
+            warn!(%error, window = %window.label(), "failed to hide window on close request");
+        }
+    }
+}
+
+pub fn parse_window_url(url: &str) -> Option<(WindowTarget, WindowParams)> {
+    let parsed = Url::parse(url).ok()?;
+    if parsed.scheme() != URL_SCHEME {
+        return None;
+    }
+    let target = match parsed.host_str()?.to_ascii_lowercase().as_str() {
+        "main" => WindowTarget::Main,
+        "settings" => WindowTarget::Settings,
+        "history" => WindowTarget::History,
+        "onboarding" => WindowTarget::Onboarding,
+        _ => return None,
+    };
+    let entry_id = parsed.path().trim_matches('/');
+    let params = WindowParams {
+        entry_id: (target == WindowTarget::History && !entry_id.is_empty())
+            .then(|| entry_id.to_string()),
+    };
+    Some((target, params))
+}
+
+#[tauri::command]
+pub fn open_window(app: AppHandle, target: WindowTarget, params: Option<WindowParams>) {
+    open(&app, target, params.unwrap_or_default());
+}
+
+#[tauri::command]
+pub fn open_history_window(app: AppHandle, entry_id: Option<String>) {
+    open(&app, WindowTarget::History, WindowParams { entry_id });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{history_window_path, parse_window_url, WindowParams, WindowTarget};
+
+    #[test]
+    fn history_window_path_carries_the_entry_to_open() {
+        assert_eq!(history_window_path(None), "history.html");
+        assert_eq!(
+            history_window_path(Some("2f1c a&b")),
+            "history.html?entry=2f1c+a%26b"
+        );
+    }
+
+    #[test]
+    fn window_urls_route_to_targets() {
+        assert_eq!(
+            parse_window_url("buzz://history"),
+            Some((WindowTarget::History, WindowParams::default()))
+        );
+        assert_eq!(
+            parse_window_url("buzz://history/2f1c9a4e"),
+            Some((
+                WindowTarget::History,
+                WindowParams {
+                    entry_id: Some("2f1c9a4e".to_string())
+                }
+            ))
+        );
+        assert_eq!(
+            parse_window_url("buzz://Settings/ignored"),
+            Some((WindowTarget::Settings, WindowParams::default()))
+        );
+        assert_eq!(parse_window_url("buzz://start"), None);
+        assert_eq!(parse_window_url("https://history/2f1c9a4e"), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteBehindConfig {
+    pub debounce: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for WriteBehindConfig {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WriteBehindState<T> {
+    value: Option<T>,
+    // What the file held when last read or written, so a flush can tell local edits apart.
+    on_disk: Option<T>,
+    first_dirty_at: Option<Instant>,
+    last_dirty_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct WriteBehind<T> {
+    config: WriteBehindConfig,
+    state: Mutex<WriteBehindState<T>>,
+}
+
+impl<T: Clone> WriteBehind<T> {
+    pub fn new(config: WriteBehindConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(WriteBehindState {
+                value: None,
+                on_disk: None,
+                first_dirty_at: None,
+                last_dirty_at: None,
+            }),
+        }
+    }
+
+    pub fn cached(&self) -> Option<T> {
+        self.state.lock().ok().and_then(|state| state.value.clone())
+    }
+
+    pub fn prime(&self, value: T) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.on_disk = Some(value.clone());
+                state.value = Some(value);
+            }
+            Err(_) => warn!("write-behind cache lock is poisoned; skipping cache fill"),
+        }
+    }
+
+    pub fn store(&self, value: T, now: Instant) -> Result<(), String> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| "Write-behind cache lock is poisoned".to_string())?;
+        state.value = Some(value);
+        state.first_dirty_at.get_or_insert(now);
+        state.last_dirty_at = Some(now);
+        Ok(())
+    }
+
+    fn take_due(&self, now: Instant, force: bool) -> Option<(T, Option<T>)> {
+        let mut state = self.state.lock().ok()?;
+        let (first_dirty_at, last_dirty_at) = (state.first_dirty_at?, state.last_dirty_at?);
+        let due = force
+            || now.saturating_duration_since(last_dirty_at) >= self.config.debounce
+            || now.saturating_duration_since(first_dirty_at) >= self.config.max_delay;
+        if !due {
+            return None;
+        }
+
+        state.first_dirty_at = None;
+        state.last_dirty_at = None;
+        Some((state.value.clone()?, state.on_disk.clone()))
+    }
+
+    pub fn flush_with(
+        &self,
+        now: Instant,
+        force: bool,
+        write: impl FnOnce(&T) -> Result<(), String>,
+    ) -> Result<bool, String> {
+        self.flush_merging(now, force, |value, _| write(value).map(|()| value.clone()))
+    }
+
+    pub fn flush_merging(
+        &self,
+        now: Instant,
+        force: bool,
+        write: impl FnOnce(&T, Option<&T>) -> Result<T, String>,
+    ) -> Result<bool, String> {
+        let Some((value, on_disk)) = self.take_due(now, force) else {
+            return Ok(false);
+        };
+        let written = match write(&value, on_disk.as_ref()) {
+            Ok(written) => written,
+            Err(error) => {
+                self.mark_dirty(now);
+                return Err(error);
+            }
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.on_disk = Some(written.clone());
+            if state.first_dirty_at.is_none() {
+                state.value = Some(written);
+            }
+        }
+        Ok(true)
+    }
+
+    fn mark_dirty(&self, now: Instant) {
+        if let Ok(mut state) = self.state.lock() {
+            state.first_dirty_at.get_or_insert(now);
+            state.last_dirty_at = Some(now);
+        }
+    }
+
+    #[cfg(test)]
+    fn is_dirty(&self) -> bool {
+        self.state
+            .lock()
+            .is_ok_and(|state| state.first_dirty_at.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{WriteBehind, WriteBehindConfig};
+
+    fn due<T: Clone>(cache: &WriteBehind<T>, now: Instant, force: bool) -> Option<T> {
+        cache.take_due(now, force).map(|(value, _)| value)
+    }
+
+    #[test]
+    fn writes_flush_after_quiet_period_or_max_delay() {
+        let cache = WriteBehind::new(WriteBehindConfig {
+            debounce: Duration::from_millis(100),
+            max_delay: Duration::from_millis(250),
+        });
+        let start = Instant::now();
+        assert_eq!(due(&cache, start, true), None);
+
+        cache.store(1, start).expect("store");
+        assert_eq!(due(&cache, start + Duration::from_millis(50), false), None);
+        assert_eq!(
+            due(&cache, start + Duration::from_millis(100), false),
+            Some(1)
+        );
+        assert!(!cache.is_dirty());
+        assert_eq!(cache.cached(), Some(1));
+
+        for step in 0..4 {
+            cache
+                .store(2 + step, start + Duration::from_millis(80 * step as u64))
+                .expect("store");
+        }
+        assert_eq!(
+            due(&cache, start + Duration::from_millis(250), false),
+            Some(5)
+        );
+
+        cache.store(9, start).expect("store");
+        assert_eq!(due(&cache, start, true), Some(9));
+    }
+
+    #[test]
+    fn failed_flush_keeps_the_value_pending() {
+        let cache = WriteBehind::new(WriteBehindConfig::default());
+        let start = Instant::now();
+        cache.store(vec![1, 2], start).expect("store");
+
+        let error = cache
+            .flush_with(start, true, |_| Err("disk full".to_string()))
+            .expect_err("write should fail");
+        assert_eq!(error, "disk full");
+        assert!(cache.is_dirty());
+
+        let mut written = None;
+        let flushed = cache
+            .flush_with(start, true, |value| {
+                written = Some(value.clone());
+                Ok(())
+            })
+            .expect("write should succeed");
+        assert!(flushed);
+        assert_eq!(written, Some(vec![1, 2]));
+        assert!(!cache
+            .flush_with(start, true, |_| Ok(()))
+            .expect("nothing to write"));
+    }
+}
@@ -33,13 +33,28 @@ import HistoryPanel from "./HistoryPanel";
 import Settings from "./Settings";
 import Onboarding from "./Onboarding";
 
-type AppStatus = "idle" | "listening" | "transcribing" | "error";
+type AppStatus = "idle" | "listening" | "transcribing" | "error" | "nothing_heard";
 type AppView = "dashboard" | "history" | "settings";
+type WindowTarget = "main" | "settings" | "history" | "overlay" | "onboarding";
 type OnboardingState = "loading" | "required" | "completed";
 type PermissionState = "not_determined" | "granted" | "denied";
 type PermissionType = "microphone" | "accessibility";
 type TranscriptReadyEvent = { text: string };
-type PipelineErrorEvent = { stage: string; message: string };
+type AudioLevelEvent = { peak: number; rms: number };
+type NothingHeardEvent = { reason: "too_short" | "silent"; durationMs: number };
+type PipelineErrorActionKind =
+  | "request_microphone_access"
+  | "open_account_settings"
+  | "retry_pending_recordings"
+  | "open_accessibility_settings";
+type PipelineErrorAction = { kind: PipelineErrorActionKind; label: string };
+type PipelineErrorEvent = {
+  stage: string;
+  code: string;
+  message: string;
+  detail: string;
+  suggestedAction: PipelineErrorAction | null;
+};
 type PermissionSnapshot = {
   microphone: PermissionState;
   accessibility: PermissionState;
@@ -63,14 +78,25 @@ type UsageStatsReport = {
   streakDays: number;
   today: DailyUsageStats;
   dailyWordHistory: DailyWordCount[];
+  usageHeatmap: number[][];
+  records: PersonalRecords;
   lastUpdated: string;
 };
+type PersonalRecords = {
+  longestStreakDays: number;
+  mostWordsInDay: number;
+  longestDictationSeconds: number;
+  fastestWordsPerMinute: number;
+};
+
+const WEEKDAY_LABELS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
 
 const STATUS_LABEL: Record<AppStatus, string> = {
   idle: "Idle",
   listening: "Listening",
   transcribing: "Transcribing",
   error: "Error",
+  nothing_heard: "Nothing heard",
 };
 
 const STATUS_DESC: Record<AppStatus, string> = {
@@ -78,6 +104,12 @@ const STATUS_DESC: Record<AppStatus, string> = {
   listening: "Capturing microphone input.",
   transcribing: "Converting audio to text.",
   error: "A recoverable issue occurred.",
+  nothing_heard: "No speech was detected, so nothing was sent.",
+};
+
+const NOTHING_HEARD_DESC: Record<NothingHeardEvent["reason"], string> = {
+  too_short: "The recording was too short to transcribe.",
+  silent: "The recording was silent, so nothing was sent.",
 };
 
 const PERMISSION_LABEL: Record<PermissionType, string> = {
@@ -302,11 +334,13 @@ function PermissionOnboardingCard({
 /* ─── Dashboard View ────────────────────────────────── */
 type DashboardViewProps = {
   audioLevel: number;
+  errorAction: PipelineErrorAction | null;
   isRefreshingPermissions: boolean;
   isRefreshingUsageStats: boolean;
   isResettingUsageStats: boolean;
   lastTranscript: string;
   onDismissPermissions: () => void;
+  onErrorAction: (action: PipelineErrorAction) => void;
   onRefreshPermissions: () => void;
   onRefreshUsageStats: () => void;
   onResetUsageStats: () => void;
@@ -323,11 +357,13 @@ type DashboardViewProps = {
 
 function DashboardView({
   audioLevel,
+  errorAction,
   isRefreshingPermissions,
   isRefreshingUsageStats,
   isResettingUsageStats,
   lastTranscript,
   onDismissPermissions,
+  onErrorAction,
   onRefreshPermissions,
   onRefreshUsageStats,
   onResetUsageStats,
@@ -346,6 +382,7 @@ function DashboardView({
     listening: "bg-emerald-500 animate-pulse-dot",
     transcribing: "bg-amber-500 animate-pulse-dot-fast",
     error: "bg-destructive",
+    nothing_heard: "bg-muted-foreground",
   };
 
   const statusRingColors: Record<AppStatus, string> = {
@@ -353,6 +390,7 @@ function DashboardView({
     listening: "ring-2 ring-emerald-500/20",
     transcribing: "ring-2 ring-amber-500/20",
     error: "ring-2 ring-destructive/20",
+    nothing_heard: "",
   };
 
   const dailyWordHistory = usageStats?.dailyWordHistory?.length
@@ -366,6 +404,8 @@ function DashboardView({
     (highest, point) => Math.max(highest, point.words),
     0
   );
+  const usageHeatmap = usageStats?.usageHeatmap ?? [];
+  const maxHeatmapWords = Math.max(0, ...usageHeatmap.flat());
 
   return (
     <div className="space-y-3">
@@ -389,6 +429,16 @@ function DashboardView({
           <div className="min-w-0 flex-1">
             <p className="text-sm font-semibold">{STATUS_LABEL[status]}</p>
             <p className="text-xs text-muted-foreground">{statusDescription}</p>
+            {status === "error" && errorAction && (
+              <Button
+                size="sm"
+                variant="outline"
+                className="mt-2"
+                onClick={() => onErrorAction(errorAction)}
+              >
+                {errorAction.label}
+              </Button>
+            )}
             {status === "transcribing" && (
               <div className="mt-2 h-1 w-full overflow-hidden rounded-full bg-muted">
                 <div className="h-full w-2/5 rounded-full bg-amber-500 animate-shimmer" />
@@ -524,9 +574,23 @@ function DashboardView({
               <p className="mt-1 text-sm font-semibold tabular-nums">
                 {formatInteger(usageStats?.streakDays ?? 0)} day streak {"🔥"}
               </p>
+              <p className="text-xs text-muted-foreground tabular-nums">
+                Best {formatInteger(usageStats?.records.longestStreakDays ?? 0)} days
+              </p>
             </div>
           </div>
 
+          <div className="space-y-1">
+            <p className="text-[11px] font-semibold uppercase tracking-wider text-muted-foreground">
+              Personal Records
+            </p>
+            <p className="text-xs text-muted-foreground tabular-nums">
+              {formatInteger(usageStats?.records.mostWordsInDay ?? 0)} words in a day ·{" "}
+              {formatMetric(usageStats?.records.longestDictationSeconds ?? 0)}s longest dictation ·{" "}
+              {formatMetric(usageStats?.records.fastestWordsPerMinute ?? 0)} WPM fastest
+            </p>
+          </div>
+
           <div className="space-y-2">
             <p className="text-[11px] font-semibold uppercase tracking-wider text-muted-foreground">
               Last 14 Days (Words)
@@ -557,6 +621,31 @@ function DashboardView({
               </div>
             </div>
           </div>
+
+          {maxHeatmapWords > 0 && (
+            <div className="space-y-2">
+              <p className="text-[11px] font-semibold uppercase tracking-wider text-muted-foreground">
+                When You Dictate
+              </p>
+              <div className="space-y-0.5 rounded-lg border bg-background/60 px-2.5 py-3">
+                {usageHeatmap.map((hours, weekday) => (
+                  <div key={WEEKDAY_LABELS[weekday]} className="flex items-center gap-0.5">
+                    <span className="w-8 text-[10px] text-muted-foreground">
+                      {WEEKDAY_LABELS[weekday]}
+                    </span>
+                    {hours.map((words, hour) => (
+                      <div
+                        key={hour}
+                        className="h-2.5 flex-1 rounded-[2px] bg-primary"
+                        style={{ opacity: words > 0 ? 0.15 + (0.85 * words) / maxHeatmapWords : 0.06 }}
+                        title={`${formatInteger(words)} words, ${WEEKDAY_LABELS[weekday]} ${hour}:00`}
+                      />
+                    ))}
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
         </CardContent>
       </Card>
     </div>
@@ -578,9 +667,12 @@ function App() {
   const [status, setStatus] = useState<AppStatus>("idle");
   const [activeView, setActiveView] = useState<AppView>("dashboard");
   const [errorMessage, setErrorMessage] = useState("");
+  const [errorAction, setErrorAction] = useState<PipelineErrorAction | null>(null);
+  const [nothingHeardReason, setNothingHeardReason] = useState<NothingHeardEvent["reason"] | null>(
+    null
+  );
   const [audioLevel, setAudioLevel] = useState(0);
   const [lastTranscript, setLastTranscript] = useState("");
-  const [historyRefreshSignal, setHistoryRefreshSignal] = useState(0);
   const [backendSynced, setBackendSynced] = useState<boolean>(true);
   const [availableUpdate, setAvailableUpdate] = useState<Update | null>(null);
   const [isInstallingUpdate, setIsInstallingUpdate] = useState(false);
@@ -700,6 +792,30 @@ function App() {
     }
   }, []);
 
+  const runErrorAction = useCallback(
+    (action: PipelineErrorAction) => {
+      switch (action.kind) {
+        case "request_microphone_access":
+          void requestPermission("microphone");
+          break;
+        case "open_accessibility_settings":
+          void invoke("open_accessibility_settings").catch((error) =>
+            setErrorMessage(toErrorMessage(error, "Unable to open Accessibility settings."))
+          );
+          break;
+        case "retry_pending_recordings":
+          void invoke("flush_pending_recordings").catch((error) =>
+            setErrorMessage(toErrorMessage(error, "Unable to retry pending recordings."))
+          );
+          break;
+        case "open_account_settings":
+          setActiveView("settings");
+          break;
+      }
+    },
+    [requestPermission]
+  );
+
   const refreshUsageStats = useCallback(async () => {
     setIsRefreshingUsageStats(true);
     try {
@@ -811,9 +927,8 @@ function App() {
 
     async function bindBackend() {
       try {
-        const [initialStatus, initialAudioLevel, initialPermissions] = await Promise.all([
+        const [initialStatus, initialPermissions] = await Promise.all([
           invoke<AppStatus>("get_status"),
-          invoke<number>("get_audio_level"),
           invoke<PermissionSnapshot>("check_permissions"),
         ]);
 
@@ -821,7 +936,6 @@ function App() {
 
         setStatus(initialStatus);
         statusRef.current = initialStatus;
-        setAudioLevel(Number.isFinite(initialAudioLevel) ? initialAudioLevel : 0);
         setPermissions(initialPermissions);
         setPermissionErrorMessage("");
       } catch {
@@ -850,10 +964,13 @@ function App() {
           listen<AppStatus>("voice://status-changed", ({ payload }) => {
             statusRef.current = payload;
             setStatus(payload);
-            if (payload !== "error") setErrorMessage("");
+            if (payload !== "error") {
+              setErrorMessage("");
+              setErrorAction(null);
+            }
           }),
-          listen<number>("audio-level", ({ payload }) => {
-            const normalized = Math.max(0, Math.min(1, Number(payload) || 0));
+          listen<AudioLevelEvent>("voice://audio-level", ({ payload }) => {
+            const normalized = Math.max(0, Math.min(1, Number(payload.peak) || 0));
             if (statusRef.current !== "listening" && normalized > 0) return;
             const quantized = Math.round(normalized * 100) / 100;
             setAudioLevel((previous) =>
@@ -862,18 +979,33 @@ function App() {
           }),
           listen<TranscriptReadyEvent>("voice://transcript-ready", ({ payload }) => {
             setLastTranscript(payload.text ?? "");
-            if (activeViewRef.current === "history") {
-              setHistoryRefreshSignal((current) => current + 1);
-            }
-            if (activeViewRef.current === "dashboard") {
-              void refreshUsageStats();
-            }
+          }),
+          listen<UsageStatsReport>("voice://stats-updated", ({ payload }) => {
+            setUsageStats(payload);
+            setUsageStatsErrorMessage("");
+          }),
+          listen("voice://update-available", () => {
+            void check()
+              .then((update) => {
+                if (isMounted) setAvailableUpdate(update);
+              })
+              .catch(() => {
+                // The launch check banner stays as-is if the refresh fails.
+              });
+          }),
+          listen<NothingHeardEvent>("voice://nothing-heard", ({ payload }) => {
+            setNothingHeardReason(payload.reason);
           }),
           listen<PipelineErrorEvent>("voice://pipeline-error", ({ payload }) => {
             setErrorMessage(payload.message || "An unexpected pipeline error occurred.");
+            setErrorAction(payload.suggestedAction ?? null);
             statusRef.current = "error";
             setStatus("error");
           }),
+          listen<{ target: WindowTarget }>("voice://navigate", ({ payload }) => {
+            if (payload.target === "settings") setActiveView("settings");
+            if (payload.target === "onboarding") setOnboardingState("required");
+          }),
         ]);
 
         if (!isMounted) {
@@ -927,8 +1059,11 @@ function App() {
 
   const statusDescription = useMemo(() => {
     if (status === "error") return errorMessage || STATUS_DESC.error;
+    if (status === "nothing_heard" && nothingHeardReason) {
+      return NOTHING_HEARD_DESC[nothingHeardReason];
+    }
     return STATUS_DESC[status] ?? "Unknown state.";
-  }, [errorMessage, status]);
+  }, [errorMessage, nothingHeardReason, status]);
 
   /* Status dot color for the sidebar indicator */
   const statusDotColor = useMemo(() => {
@@ -1064,11 +1199,13 @@ function App() {
               {activeView === "dashboard" && (
                 <DashboardView
                   audioLevel={audioLevel}
+                  errorAction={errorAction}
                   isRefreshingPermissions={isRefreshingPermissions}
                   isRefreshingUsageStats={isRefreshingUsageStats}
                   isResettingUsageStats={isResettingUsageStats}
                   lastTranscript={lastTranscript}
                   onDismissPermissions={dismissPermissionCard}
+                  onErrorAction={runErrorAction}
                   onRefreshPermissions={() => void refreshPermissions()}
                   onRefreshUsageStats={() => void refreshUsageStats()}
                   onResetUsageStats={resetUsageStats}
@@ -1084,7 +1221,7 @@ function App() {
                 />
               )}
               {activeView === "history" && (
-                <HistoryPanel refreshSignal={historyRefreshSignal} />
+                <HistoryPanel />
               )}
               {activeView === "settings" && (
                 <Settings />
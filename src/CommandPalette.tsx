@@ -0,0 +1,135 @@
+import { type KeyboardEvent, useEffect, useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+
+type CommandAction = { kind: string; [field: string]: unknown };
+type PaletteCommand = {
+  title: string;
+  subtitle: string | null;
+  keywords: string[];
+  needsAudioFile: boolean;
+  action: CommandAction;
+};
+
+function CommandPalette() {
+  const [query, setQuery] = useState("");
+  const [commands, setCommands] = useState<PaletteCommand[]>([]);
+  const [selected, setSelected] = useState(0);
+  const [shownAt, setShownAt] = useState(0);
+  const [error, setError] = useState("");
+  const inputRef = useRef<HTMLInputElement>(null);
+  const fileRef = useRef<HTMLInputElement>(null);
+  const pendingRef = useRef<PaletteCommand | null>(null);
+
+  useEffect(() => {
+    invoke<PaletteCommand[]>("search_palette_commands", { query })
+      .then((results) => {
+        setCommands(results);
+        setSelected(0);
+      })
+      .catch((searchError) => setError(String(searchError)));
+  }, [query, shownAt]);
+
+  useEffect(() => {
+    // The window is hidden rather than destroyed, so start fresh whenever it is shown again.
+    const reset = () => {
+      setQuery("");
+      setError("");
+      setShownAt(Date.now());
+      inputRef.current?.focus();
+    };
+    window.addEventListener("focus", reset);
+    return () => window.removeEventListener("focus", reset);
+  }, []);
+
+  const run = async (command: PaletteCommand, audioBytes?: number[]) => {
+    setError("");
+    try {
+      await invoke("run_palette_command", { action: command.action, audioBytes });
+    } catch (runError) {
+      setError(String(runError));
+    }
+  };
+
+  const choose = (command: PaletteCommand | undefined) => {
+    if (!command) return;
+    if (command.needsAudioFile) {
+      pendingRef.current = command;
+      fileRef.current?.click();
+      return;
+    }
+    void run(command);
+  };
+
+  const handleFile = async (file: File | undefined) => {
+    const command = pendingRef.current;
+    pendingRef.current = null;
+    if (!file || !command) return;
+    const bytes = Array.from(new Uint8Array(await file.arrayBuffer()));
+    await run(command, bytes);
+  };
+
+  const handleKeyDown = (event: KeyboardEvent) => {
+    if (event.key === "Escape") {
+      void invoke("close_command_palette");
+    } else if (event.key === "ArrowDown") {
+      event.preventDefault();
+      setSelected((index) => Math.min(index + 1, commands.length - 1));
+    } else if (event.key === "ArrowUp") {
+      event.preventDefault();
+      setSelected((index) => Math.max(index - 1, 0));
+    } else if (event.key === "Enter") {
+      choose(commands[selected]);
+    }
+  };
+
+  return (
+    <main
+      className="bg-background flex h-screen flex-col gap-3 rounded-lg border p-4"
+      onKeyDown={handleKeyDown}
+    >
+      <header className="flex items-center gap-2">
+        <Input
+          ref={inputRef}
+          autoFocus
+          value={query}
+          placeholder="Type a command"
+          onChange={(event) => setQuery(event.target.value)}
+        />
+        <Badge variant="secondary">Esc to close</Badge>
+      </header>
+      <section className="min-h-0 flex-1 space-y-1 overflow-y-auto">
+        {commands.map((command, index) => (
+          <Button
+            key={`${command.title}-${index}`}
+            variant={index === selected ? "default" : "ghost"}
+            className="h-auto w-full flex-col items-start gap-0.5 py-2 text-left"
+            onMouseEnter={() => setSelected(index)}
+            onClick={() => choose(command)}
+          >
+            <span className="text-sm font-medium">{command.title}</span>
+            {command.subtitle && (
+              <span className="text-xs font-normal opacity-80">{command.subtitle}</span>
+            )}
+          </Button>
+        ))}
+        {commands.length === 0 && <p className="text-muted-foreground text-xs">No matching commands.</p>}
+      </section>
+      <input
+        ref={fileRef}
+        type="file"
+        accept="audio/*"
+        className="hidden"
+        onChange={(event) => {
+          void handleFile(event.target.files?.[0]);
+          event.target.value = "";
+        }}
+      />
+      {error && <p className="text-destructive text-xs">{error}</p>}
+    </main>
+  );
+}
+
+export default CommandPalette;
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Copy, CornerDownLeft, Trash2, RefreshCw, FileText } from "lucide-react";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { Copy, CornerDownLeft, Trash2, RefreshCw, FileText, UserPen, Sparkles } from "lucide-react";
 import { Card, CardContent } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { Badge } from "@/components/ui/badge";
@@ -11,12 +12,15 @@ import {
   formatHistoryTimestamp,
   formatLanguageCode,
   formatProvider,
+  listSpeakers,
+  type HistoryChange,
   type HistoryEntry,
+  type HistoryPage,
 } from "./historyUtils";
 
 const HISTORY_PAGE_SIZE = 25;
 
-type EntryAction = "copy" | "insert" | "delete";
+type EntryAction = "copy" | "insert" | "delete" | "rename" | "summarize";
 type ActiveEntryAction = { id: string; type: EntryAction } | null;
 
 function toErrorMessage(error: unknown, fallbackMessage: string): string {
@@ -25,13 +29,18 @@ function toErrorMessage(error: unknown, fallbackMessage: string): string {
   return fallbackMessage;
 }
 
+function sortNewestFirst(entries: HistoryEntry[]): HistoryEntry[] {
+  return [...entries].sort((left, right) => right.timestamp.localeCompare(left.timestamp));
+}
+
 type HistoryPanelProps = {
-  refreshSignal?: number;
+  /** Entry to scroll to and highlight, e.g. when opened from a deep link. */
+  focusEntryId?: string | null;
 };
 
-function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
+function HistoryPanel({ focusEntryId = null }: HistoryPanelProps) {
   const [entries, setEntries] = useState<HistoryEntry[]>([]);
-  const [offset, setOffset] = useState(0);
+  const [cursor, setCursor] = useState<string | null>(null);
   const [hasMore, setHasMore] = useState(true);
   const [isLoading, setIsLoading] = useState(false);
   const [isClearingAll, setIsClearingAll] = useState(false);
@@ -39,26 +48,27 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
   const [loadError, setLoadError] = useState("");
   const [actionError, setActionError] = useState("");
   const [actionNotice, setActionNotice] = useState("");
-  const previousRefreshSignal = useRef(refreshSignal);
+  const lastRevision = useRef(0);
+  const scrolledToEntry = useRef<string | null>(null);
 
-  const loadEntries = useCallback(async (nextOffset: number, replace: boolean) => {
+  const loadEntries = useCallback(async (pageCursor: string | null, replace: boolean) => {
     setIsLoading(true);
     setLoadError("");
 
     try {
-      const page = await invoke<HistoryEntry[]>("list_history", {
+      const page = await invoke<HistoryPage>("list_history_page", {
         limit: HISTORY_PAGE_SIZE,
-        offset: nextOffset,
+        cursor: pageCursor,
       });
 
       setEntries((existingEntries) => {
-        if (replace) return page;
+        if (replace) return page.entries;
         const existingIds = new Set(existingEntries.map((entry) => entry.id));
-        const uniquePageEntries = page.filter((entry) => !existingIds.has(entry.id));
+        const uniquePageEntries = page.entries.filter((entry) => !existingIds.has(entry.id));
         return [...existingEntries, ...uniquePageEntries];
       });
-      setOffset(nextOffset + page.length);
-      setHasMore(page.length === HISTORY_PAGE_SIZE);
+      setCursor(page.nextCursor);
+      setHasMore(page.nextCursor !== null);
     } catch (error) {
       setLoadError(toErrorMessage(error, "Failed to load transcript history."));
     } finally {
@@ -67,18 +77,76 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
   }, []);
 
   const refreshHistory = useCallback(async () => {
-    await loadEntries(0, true);
+    await loadEntries(null, true);
   }, [loadEntries]);
 
   useEffect(() => {
     void refreshHistory();
   }, [refreshHistory]);
 
+  const applyHistoryChange = useCallback(async (change: HistoryChange) => {
+    if (change.revision <= lastRevision.current) return;
+    lastRevision.current = change.revision;
+
+    const deletedIds = new Set(change.deleted);
+    const changedEntries = await Promise.all(
+      [...change.added, ...change.updated].map((id) =>
+        invoke<HistoryEntry | null>("get_history_entry", { id }).catch(() => null)
+      )
+    );
+
+    setEntries((existingEntries) => {
+      const byId = new Map(
+        existingEntries
+          .filter((entry) => !deletedIds.has(entry.id))
+          .map((entry) => [entry.id, entry])
+      );
+      for (const entry of changedEntries) {
+        if (entry) byId.set(entry.id, entry);
+      }
+      return sortNewestFirst([...byId.values()]);
+    });
+  }, []);
+
   useEffect(() => {
-    if (previousRefreshSignal.current === refreshSignal) return;
-    previousRefreshSignal.current = refreshSignal;
-    void refreshHistory();
-  }, [refreshHistory, refreshSignal]);
+    let unlisten: UnlistenFn | undefined;
+    let disposed = false;
+
+    void listen<HistoryChange>("voice://history-changed", ({ payload }) => {
+      void applyHistoryChange(payload);
+    }).then((unlistenFn) => {
+      if (disposed) unlistenFn();
+      else unlisten = unlistenFn;
+    });
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [applyHistoryChange]);
+
+  useEffect(() => {
+    if (!focusEntryId) return;
+    scrolledToEntry.current = null;
+    // The entry may be older than the loaded pages, so fetch it directly.
+    invoke<HistoryEntry | null>("get_history_entry", { id: focusEntryId })
+      .then((entry) => {
+        if (!entry) return;
+        setEntries((existingEntries) =>
+          existingEntries.some((existing) => existing.id === entry.id)
+            ? existingEntries
+            : sortNewestFirst([...existingEntries, entry])
+        );
+      })
+      .catch(() => undefined);
+  }, [focusEntryId]);
+
+  useEffect(() => {
+    if (!focusEntryId || scrolledToEntry.current === focusEntryId) return;
+    if (!entries.some((entry) => entry.id === focusEntryId)) return;
+    scrolledToEntry.current = focusEntryId;
+    document.getElementById(`history-entry-${focusEntryId}`)?.scrollIntoView({ block: "center" });
+  }, [entries, focusEntryId]);
 
   const runEntryAction = useCallback(
     async (
@@ -120,8 +188,8 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
       void runEntryAction(
         entry.id,
         "insert",
-        () => invoke("insert_text", { text: entry.text }),
-        "Transcript re-inserted into the focused app."
+        () => invoke("reinsert_entry", { id: entry.id }),
+        "Transcript re-inserted into the previous app."
       );
     },
     [runEntryAction]
@@ -145,10 +213,51 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
     [refreshHistory, runEntryAction]
   );
 
+  const onRenameSpeaker = useCallback(
+    (entry: HistoryEntry, speaker: string) => {
+      const nextName = window.prompt(`Rename ${speaker} to:`, speaker)?.trim();
+      if (!nextName || nextName === speaker) return;
+
+      void runEntryAction(
+        entry.id,
+        "rename",
+        async () => {
+          const updated = await invoke<HistoryEntry>("rename_history_speaker", {
+            id: entry.id,
+            from: speaker,
+            to: nextName,
+          });
+          setEntries((existingEntries) =>
+            existingEntries.map((existing) => (existing.id === updated.id ? updated : existing))
+          );
+        },
+        `Renamed ${speaker} to ${nextName}.`
+      );
+    },
+    [runEntryAction]
+  );
+
+  const onSummarize = useCallback(
+    (entry: HistoryEntry) => {
+      void runEntryAction(
+        entry.id,
+        "summarize",
+        async () => {
+          const updated = await invoke<HistoryEntry>("summarize_entry", { id: entry.id });
+          setEntries((existingEntries) =>
+            existingEntries.map((existing) => (existing.id === updated.id ? updated : existing))
+          );
+        },
+        "Summary generated."
+      );
+    },
+    [runEntryAction]
+  );
+
   const onLoadMore = useCallback(() => {
     if (isLoading || !hasMore) return;
-    void loadEntries(offset, false);
-  }, [hasMore, isLoading, loadEntries, offset]);
+    void loadEntries(cursor, false);
+  }, [cursor, hasMore, isLoading, loadEntries]);
 
   const onClearAll = useCallback(() => {
     if (!entries.length || isClearingAll) return;
@@ -162,7 +271,7 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
       try {
         await invoke("clear_history");
         setEntries([]);
-        setOffset(0);
+        setCursor(null);
         setHasMore(false);
         setActionNotice("History cleared.");
       } catch (error) {
@@ -240,13 +349,39 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
             const entryActionsDisabled = entryActionActive || isClearingAll;
 
             return (
-              <Card key={entry.id} className="group transition-shadow hover:shadow-md">
+              <Card
+                key={entry.id}
+                id={`history-entry-${entry.id}`}
+                className={`group transition-shadow hover:shadow-md ${
+                  entry.id === focusEntryId ? "ring-primary ring-2" : ""
+                }`}
+              >
                 <CardContent className="space-y-2 py-3">
                   {/* Transcript text */}
                   <p className="line-clamp-3 text-sm leading-relaxed break-words">
                     {entry.text}
                   </p>
 
+                  {entry.summary && (
+                    <div className="space-y-1 rounded-md bg-muted/50 px-2.5 py-2 text-xs">
+                      <ul className="list-disc space-y-0.5 pl-4">
+                        {entry.summary.bullets.map((bullet, index) => (
+                          <li key={`bullet-${index}`}>{bullet}</li>
+                        ))}
+                      </ul>
+                      {entry.summary.actionItems.length > 0 && (
+                        <>
+                          <p className="pt-1 font-medium">Action items</p>
+                          <ul className="list-disc space-y-0.5 pl-4">
+                            {entry.summary.actionItems.map((item, index) => (
+                              <li key={`action-${index}`}>{item}</li>
+                            ))}
+                          </ul>
+                        </>
+                      )}
+                    </div>
+                  )}
+
                   {/* Metadata badges */}
                   <div className="flex flex-wrap gap-1.5">
                     <Badge variant="secondary" className="text-[10px] px-1.5 py-0 font-normal">
@@ -261,6 +396,15 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
                     <Badge variant="outline" className="text-[10px] px-1.5 py-0 font-normal tracking-wide">
                       {formatProvider(entry.provider)}
                     </Badge>
+                    {entry.source?.appName && (
+                      <Badge
+                        variant="outline"
+                        className="max-w-48 truncate text-[10px] px-1.5 py-0 font-normal"
+                        title={entry.source.url ?? entry.source.windowTitle ?? undefined}
+                      >
+                        {entry.source.appName}
+                      </Badge>
+                    )}
                   </div>
 
                   {/* Action buttons — show on hover */}
@@ -297,6 +441,31 @@ function HistoryPanel({ refreshSignal = 0 }: HistoryPanelProps) {
                         ? "Deleting..."
                         : "Delete"}
                     </Button>
+                    <Button
+                      variant="outline"
+                      size="xs"
+                      onClick={() => onSummarize(entry)}
+                      disabled={entryActionsDisabled}
+                    >
+                      <Sparkles className="size-3" />
+                      {entryActionActive && activeAction?.type === "summarize"
+                        ? "Summarizing..."
+                        : entry.summary
+                          ? "Re-summarize"
+                          : "Summarize"}
+                    </Button>
+                    {listSpeakers(entry).map((speaker) => (
+                      <Button
+                        key={speaker}
+                        variant="outline"
+                        size="xs"
+                        onClick={() => onRenameSpeaker(entry, speaker)}
+                        disabled={entryActionsDisabled}
+                      >
+                        <UserPen className="size-3" />
+                        {speaker}
+                      </Button>
+                    ))}
                   </div>
                 </CardContent>
               </Card>
@@ -0,0 +1,120 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { Mic, Square } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Badge } from "@/components/ui/badge";
+import { ScrollArea } from "@/components/ui/scroll-area";
+
+type MeetingAudioSource = "microphone" | "system";
+
+type MeetingSegment = {
+  source: MeetingAudioSource;
+  startSecs: number;
+  endSecs: number;
+  speaker: string | null;
+  text: string;
+};
+
+type MeetingSnapshot = {
+  isActive: boolean;
+  startedAt: string | null;
+  segments: MeetingSegment[];
+};
+
+const EVENT_MEETING_SEGMENT = "voice://meeting-segment";
+const EVENT_MEETING_STATE_CHANGED = "voice://meeting-state-changed";
+
+function formatOffset(secs: number): string {
+  const totalSecs = Math.max(0, Math.floor(secs));
+  const hours = Math.floor(totalSecs / 3600);
+  const minutes = Math.floor(totalSecs / 60) % 60;
+  const seconds = totalSecs % 60;
+  return [hours, minutes, seconds].map((part) => String(part).padStart(2, "0")).join(":");
+}
+
+function Meeting() {
+  const [snapshot, setSnapshot] = useState<MeetingSnapshot>({
+    isActive: false,
+    startedAt: null,
+    segments: [],
+  });
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    let isMounted = true;
+    let unlistenFns: UnlistenFn[] = [];
+
+    async function bindMeetingEvents() {
+      const initialSnapshot = await invoke<MeetingSnapshot>("get_meeting_snapshot");
+      if (isMounted) setSnapshot(initialSnapshot);
+
+      unlistenFns = await Promise.all([
+        listen<MeetingSnapshot>(EVENT_MEETING_STATE_CHANGED, (event) => {
+          setSnapshot(event.payload);
+        }),
+        listen<MeetingSegment>(EVENT_MEETING_SEGMENT, (event) => {
+          setSnapshot((current) => ({
+            ...current,
+            segments: [...current.segments, event.payload],
+          }));
+        }),
+      ]);
+    }
+
+    void bindMeetingEvents().catch((bindError) => setError(String(bindError)));
+
+    return () => {
+      isMounted = false;
+      unlistenFns.forEach((unlisten) => unlisten());
+    };
+  }, []);
+
+  const toggleMeeting = async () => {
+    setError("");
+    try {
+      if (snapshot.isActive) {
+        await invoke<boolean>("stop_meeting");
+      } else {
+        setSnapshot(await invoke<MeetingSnapshot>("start_meeting"));
+      }
+    } catch (toggleError) {
+      setError(String(toggleError));
+    }
+  };
+
+  return (
+    <main className="flex h-screen flex-col gap-3 p-4">
+      <header className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          <h1 className="text-sm font-semibold">Meeting transcript</h1>
+          {snapshot.isActive && <Badge variant="secondary">Recording</Badge>}
+        </div>
+        <Button size="sm" variant={snapshot.isActive ? "destructive" : "default"} onClick={toggleMeeting}>
+          {snapshot.isActive ? <Square className="size-4" /> : <Mic className="size-4" />}
+          {snapshot.isActive ? "Stop" : "Start"}
+        </Button>
+      </header>
+      {error && <p className="text-destructive text-xs">{error}</p>}
+      <ScrollArea className="min-h-0 flex-1 rounded-md border">
+        <ol className="space-y-2 p-3 text-sm">
+          {snapshot.segments.length === 0 && (
+            <li className="text-muted-foreground">
+              {snapshot.isActive ? "Listening… transcript appears every 30 seconds." : "No meeting in progress."}
+            </li>
+          )}
+          {snapshot.segments.map((segment, index) => (
+            <li key={`${segment.source}-${segment.startSecs}-${index}`} className="flex gap-2">
+              <span className="text-muted-foreground font-mono text-xs">{formatOffset(segment.startSecs)}</span>
+              {segment.source === "system" && <Badge variant="outline">System</Badge>}
+              {segment.speaker && <Badge variant="secondary">{segment.speaker}</Badge>}
+              <span>{segment.text}</span>
+            </li>
+          ))}
+        </ol>
+      </ScrollArea>
+    </main>
+  );
+}
+
+export default Meeting;
@@ -293,6 +293,9 @@ export default function Onboarding({ onComplete }: OnboardingProps) {
     if (practiceStatus === "error") {
       return practiceErrorMessage || "A recording error occurred while testing.";
     }
+    if (practiceStatus === "nothing_heard") {
+      return "We didn't hear anything. Hold the shortcut a little longer and speak up.";
+    }
     if (practiceTranscript.length > 0) {
       return "Looks good! Review your transcript, then continue.";
     }
@@ -1038,7 +1041,7 @@ export default function Onboarding({ onComplete }: OnboardingProps) {
                   practiceStatus === "listening" && "border-emerald-300 bg-emerald-50 text-emerald-600 dark:border-emerald-700 dark:bg-emerald-950/30 dark:text-emerald-400",
                   practiceStatus === "transcribing" && "border-primary/40 bg-primary/10 text-foreground",
                   practiceStatus === "error" && "border-red-300 bg-red-50 text-red-600 dark:border-red-700 dark:bg-red-950/30 dark:text-red-400",
-                  practiceStatus === "idle" && "border-border bg-muted/30 text-muted-foreground",
+                  (practiceStatus === "idle" || practiceStatus === "nothing_heard") && "border-border bg-muted/30 text-muted-foreground",
                 )}
               >
                 {practiceStatusLabel(practiceStatus)}
@@ -6,7 +6,7 @@ import { reportRendererMemory } from "./debugMemory";
 import { formatElapsedLabel } from "./overlayUtils";
 import "./Overlay.css";
 
-type AppStatus = "idle" | "listening" | "transcribing" | "error";
+type AppStatus = "idle" | "listening" | "transcribing" | "error" | "nothing_heard";
 
 const EVENT_STATUS_CHANGED = "voice://status-changed";
 const COMMAND_COMPLETE_RECORDING = "complete_recording";
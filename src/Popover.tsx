@@ -0,0 +1,152 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { AppWindow } from "lucide-react";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import { Progress } from "@/components/ui/progress";
+import { Switch } from "@/components/ui/switch";
+import type { HistoryEntry } from "./historyUtils";
+
+type AppStatus = "idle" | "listening" | "transcribing" | "error" | "nothing_heard";
+type TranscriptReadyEvent = { text: string };
+type AudioLevelEvent = { peak: number; rms: number };
+type QuickSettings = {
+  auto_insert: boolean;
+  queued_dictation_enabled: boolean;
+};
+type QuickSettingKey = keyof QuickSettings;
+type PrivacyModeStatus = { active: boolean; remainingSecs: number | null };
+type SettingsValidationError = { message: string; fields: { field: string; message: string }[] };
+
+const STATUS_LABEL: Record<AppStatus, string> = {
+  idle: "Idle",
+  listening: "Listening",
+  transcribing: "Transcribing",
+  error: "Error",
+  nothing_heard: "Nothing heard",
+};
+
+function Popover() {
+  const [status, setStatus] = useState<AppStatus>("idle");
+  const [audioLevel, setAudioLevel] = useState(0);
+  const [lastTranscript, setLastTranscript] = useState("");
+  const [settings, setSettings] = useState<QuickSettings | null>(null);
+  const [privacyMode, setPrivacyMode] = useState<PrivacyModeStatus | null>(null);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    let isMounted = true;
+    let unlistenFns: UnlistenFn[] = [];
+
+    async function bindPopoverEvents() {
+      const [initialStatus, initialSettings, latestEntries, initialPrivacyMode] = await Promise.all([
+        invoke<AppStatus>("get_status"),
+        invoke<QuickSettings>("get_settings"),
+        invoke<HistoryEntry[]>("list_history", { limit: 1, offset: 0 }),
+        invoke<PrivacyModeStatus>("get_privacy_mode"),
+      ]);
+      if (isMounted) {
+        setStatus(initialStatus);
+        setSettings(initialSettings);
+        setPrivacyMode(initialPrivacyMode);
+        setLastTranscript(latestEntries[0]?.text ?? "");
+      }
+
+      unlistenFns = await Promise.all([
+        listen<AppStatus>("voice://status-changed", ({ payload }) => {
+          setStatus(payload);
+          if (payload !== "listening") setAudioLevel(0);
+        }),
+        listen<AudioLevelEvent>("voice://audio-level", ({ payload }) => {
+          setAudioLevel(Math.max(0, Math.min(1, Number(payload.peak) || 0)));
+        }),
+        listen<TranscriptReadyEvent>("voice://transcript-ready", ({ payload }) => {
+          setLastTranscript(payload.text ?? "");
+        }),
+        listen<PrivacyModeStatus>("voice://privacy-mode-changed", ({ payload }) => {
+          setPrivacyMode(payload);
+        }),
+      ]);
+      if (!isMounted) unlistenFns.forEach((unlisten) => unlisten());
+    }
+
+    void bindPopoverEvents().catch((bindError) => setError(String(bindError)));
+
+    return () => {
+      isMounted = false;
+      unlistenFns.forEach((unlisten) => unlisten());
+    };
+  }, []);
+
+  const toggleSetting = async (key: QuickSettingKey, enabled: boolean) => {
+    setError("");
+    try {
+      const updated = await invoke<QuickSettings>("update_settings", {
+        update: { [key]: enabled },
+      });
+      setSettings(updated);
+    } catch (toggleError) {
+      const validationError = toggleError as SettingsValidationError;
+      setError(validationError.fields?.[0]?.message ?? validationError.message ?? String(toggleError));
+    }
+  };
+
+  return (
+    <main className="bg-background flex h-screen flex-col gap-3 rounded-lg border p-4">
+      <header className="flex items-center justify-between">
+        <h1 className="text-sm font-semibold">Buzz</h1>
+        <Badge variant={status === "error" ? "destructive" : "secondary"}>{STATUS_LABEL[status]}</Badge>
+      </header>
+      <Progress value={status === "listening" ? Math.round(audioLevel * 100) : 0} />
+      <section className="min-h-0 flex-1 space-y-1">
+        <p className="text-muted-foreground text-xs">Last transcript</p>
+        <p className="line-clamp-4 text-sm">{lastTranscript || "Nothing transcribed yet."}</p>
+      </section>
+      <section className="space-y-2">
+        <div className="flex items-center justify-between">
+          <Label htmlFor="popover-auto-insert">Insert into active app</Label>
+          <Switch
+            id="popover-auto-insert"
+            size="sm"
+            disabled={!settings}
+            checked={settings?.auto_insert ?? false}
+            onCheckedChange={(checked) => void toggleSetting("auto_insert", checked)}
+          />
+        </div>
+        <div className="flex items-center justify-between">
+          <Label htmlFor="popover-queued-dictation">Queue dictations</Label>
+          <Switch
+            id="popover-queued-dictation"
+            size="sm"
+            disabled={!settings}
+            checked={settings?.queued_dictation_enabled ?? false}
+            onCheckedChange={(checked) => void toggleSetting("queued_dictation_enabled", checked)}
+          />
+        </div>
+        <div className="flex items-center justify-between">
+          <Label htmlFor="popover-privacy-mode">Privacy mode</Label>
+          <Switch
+            id="popover-privacy-mode"
+            size="sm"
+            disabled={!privacyMode}
+            checked={privacyMode?.active ?? false}
+            onCheckedChange={(checked) =>
+              void invoke<PrivacyModeStatus>("set_privacy_mode", { active: checked })
+                .then(setPrivacyMode)
+                .catch((privacyError) => setError(String(privacyError)))
+            }
+          />
+        </div>
+      </section>
+      {error && <p className="text-destructive text-xs">{error}</p>}
+      <Button size="sm" variant="outline" onClick={() => void invoke("open_main_window_from_popover")}>
+        <AppWindow className="size-4" />
+        Open Buzz
+      </Button>
+    </main>
+  );
+}
+
+export default Popover;
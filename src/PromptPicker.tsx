@@ -0,0 +1,92 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+
+type PromptTemplate = {
+  name: string;
+  prompt: string;
+  cleanup_instructions: string;
+};
+type PickerSettings = {
+  prompt_templates: PromptTemplate[];
+  active_prompt_template: string | null;
+};
+
+function PromptPicker() {
+  const [settings, setSettings] = useState<PickerSettings | null>(null);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    const loadSettings = () => {
+      invoke<PickerSettings>("get_settings")
+        .then(setSettings)
+        .catch((loadError) => setError(String(loadError)));
+    };
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.key === "Escape") void invoke("close_prompt_picker");
+    };
+
+    loadSettings();
+    // The window is hidden rather than destroyed, so refresh whenever it is shown again.
+    window.addEventListener("focus", loadSettings);
+    window.addEventListener("keydown", handleKeyDown);
+    return () => {
+      window.removeEventListener("focus", loadSettings);
+      window.removeEventListener("keydown", handleKeyDown);
+    };
+  }, []);
+
+  const choose = async (name: string | null) => {
+    setError("");
+    try {
+      const updated = await invoke<PickerSettings>("choose_prompt_template", {
+        name,
+        startDictation: true,
+      });
+      setSettings(updated);
+    } catch (chooseError) {
+      setError(String(chooseError));
+    }
+  };
+
+  const options: { name: string | null; detail: string }[] = [
+    { name: null, detail: "Use the transcription style from Settings." },
+    ...(settings?.prompt_templates ?? []).map((template) => ({
+      name: template.name,
+      detail: template.cleanup_instructions || template.prompt || "No instructions.",
+    })),
+  ];
+
+  return (
+    <main className="bg-background flex h-screen flex-col gap-3 rounded-lg border p-4">
+      <header className="flex items-center justify-between">
+        <h1 className="text-sm font-semibold">Choose a prompt template</h1>
+        <Badge variant="secondary">Esc to close</Badge>
+      </header>
+      <section className="min-h-0 flex-1 space-y-2 overflow-y-auto">
+        {options.map((option) => {
+          const isActive = (settings?.active_prompt_template ?? null) === option.name;
+          return (
+            <Button
+              key={option.name ?? "__default"}
+              variant={isActive ? "default" : "outline"}
+              className="h-auto w-full flex-col items-start gap-0.5 py-2 text-left"
+              disabled={!settings}
+              onClick={() => void choose(option.name)}
+            >
+              <span className="text-sm font-medium">{option.name ?? "Default style"}</span>
+              <span className="line-clamp-2 text-xs font-normal opacity-80">{option.detail}</span>
+            </Button>
+          );
+        })}
+        {settings && settings.prompt_templates.length === 0 && (
+          <p className="text-muted-foreground text-xs">Add templates in Settings to pick them here.</p>
+        )}
+      </section>
+      {error && <p className="text-destructive text-xs">{error}</p>}
+    </main>
+  );
+}
+
+export default PromptPicker;
@@ -44,6 +44,13 @@ type HotkeyConfig = {
   mode: RecordingMode;
 };
 
+type HotkeyCaptureEvent = {
+  status: "captured" | "rejected" | "cancelled";
+  shortcut: string | null;
+  error: string | null;
+  registered: boolean;
+};
+
 type MicrophoneInfo = {
   id: string;
   name: string;
@@ -334,11 +341,17 @@ export default function Settings() {
       return;
     }
 
+    let submitted = false;
+    void invoke("begin_hotkey_capture").catch((error) => {
+      setIsRecordingShortcut(false);
+      setFeedback({ kind: "error", message: toErrorMessage(error, "Unable to capture a shortcut.") });
+    });
+
     const handleShortcutKeydown = (event: KeyboardEvent) => {
       event.preventDefault();
       event.stopPropagation();
 
-      if (event.repeat) {
+      if (event.repeat || submitted) {
         return;
       }
 
@@ -347,14 +360,31 @@ export default function Settings() {
         return;
       }
 
+      submitted = true;
       setIsRecordingShortcut(false);
-      setHotkeyShortcut(capturedShortcut);
-      setFeedback({ kind: "success", message: `Shortcut set to ${capturedShortcut}.` });
+      void invoke<HotkeyCaptureEvent>("submit_hotkey_capture", { shortcut: capturedShortcut })
+        .then((result) => {
+          if (result.status === "captured" && result.shortcut) {
+            setHotkeyShortcut(result.shortcut);
+            setFeedback({ kind: "success", message: `Shortcut set to ${result.shortcut}.` });
+          } else {
+            setFeedback({
+              kind: "error",
+              message: result.error ?? `Shortcut ${capturedShortcut} is not available.`,
+            });
+          }
+        })
+        .catch((error) => {
+          setFeedback({ kind: "error", message: toErrorMessage(error, "Unable to capture a shortcut.") });
+        });
     };
 
     window.addEventListener("keydown", handleShortcutKeydown, true);
     return () => {
       window.removeEventListener("keydown", handleShortcutKeydown, true);
+      if (!submitted) {
+        void invoke("cancel_hotkey_capture").catch(() => undefined);
+      }
     };
   }, [isRecordingShortcut]);
 
@@ -0,0 +1,35 @@
+import React, { useEffect, useState } from "react";
+import ReactDOM from "react-dom/client";
+import { listen } from "@tauri-apps/api/event";
+import HistoryPanel from "./HistoryPanel";
+import "./index.css";
+
+function HistoryWindow() {
+  const [focusEntryId, setFocusEntryId] = useState(() =>
+    new URLSearchParams(window.location.search).get("entry")
+  );
+
+  useEffect(() => {
+    const unlisten = listen<{ params: { entryId: string | null } }>(
+      "voice://navigate",
+      ({ payload }) => {
+        if (payload.params.entryId) setFocusEntryId(payload.params.entryId);
+      },
+    );
+    return () => {
+      void unlisten.then((unlistenFn) => unlistenFn());
+    };
+  }, []);
+
+  return (
+    <main className="bg-background h-screen overflow-y-auto p-4">
+      <HistoryPanel focusEntryId={focusEntryId} />
+    </main>
+  );
+}
+
+ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+  <React.StrictMode>
+    <HistoryWindow />
+  </React.StrictMode>,
+);
@@ -4,6 +4,7 @@ import {
   formatHistoryTimestamp,
   formatLanguageCode,
   formatProvider,
+  listSpeakers,
 } from "./historyUtils";
 
 describe("formatHistoryTimestamp", () => {
@@ -59,3 +60,27 @@ describe("formatProvider", () => {
     expect(formatProvider("openai")).toBe("OPENAI");
   });
 });
+
+describe("listSpeakers", () => {
+  it("returns distinct speakers in order of appearance", () => {
+    const entry = {
+      id: "1",
+      text: "",
+      timestamp: "2026-01-01T00:00:00Z",
+      provider: "meeting",
+      segments: [
+        { startSecs: 0, endSecs: 1, speaker: "Speaker 2", text: "hi" },
+        { startSecs: 1, endSecs: 2, speaker: null, text: "noise" },
+        { startSecs: 2, endSecs: 3, speaker: "Speaker 1", text: "hello" },
+        { startSecs: 3, endSecs: 4, speaker: "Speaker 2", text: "bye" },
+      ],
+    };
+    expect(listSpeakers(entry)).toEqual(["Speaker 2", "Speaker 1"]);
+  });
+
+  it("handles entries without segments", () => {
+    expect(
+      listSpeakers({ id: "1", text: "hi", timestamp: "", provider: "openai" })
+    ).toEqual([]);
+  });
+});
@@ -5,6 +5,66 @@ export type HistoryEntry = {
   durationSecs?: number | null;
   language?: string | null;
   provider: string;
+  segments?: HistorySegment[];
+  summary?: TranscriptSummary | null;
+  rawText?: string | null;
+  /** Recording peaks scaled to 0-255, oldest first. */
+  waveform?: number[];
+  /** Where the transcript was dictated, when source recording is on. */
+  source?: HistorySource | null;
+};
+
+export type HistorySource = {
+  appName?: string | null;
+  bundleId?: string | null;
+  windowTitle?: string | null;
+  url?: string | null;
+};
+
+/** A history row from `list_entry_previews`, with the transcript cut short. */
+export type HistoryEntryPreview = {
+  id: string;
+  timestamp: string;
+  provider: string;
+  preview: string;
+  truncated: boolean;
+};
+
+export type HistoryChange = {
+  revision: number;
+  added: string[];
+  updated: string[];
+  deleted: string[];
+};
+
+export type HistoryPage = {
+  entries: HistoryEntry[];
+  nextCursor: string | null;
+};
+
+export type TranscriptDiffSegment = {
+  op: "equal" | "insert" | "delete";
+  text: string;
+};
+
+export type TranscriptDiff = {
+  rawText: string;
+  cleanedText: string;
+  segments: TranscriptDiffSegment[];
+};
+
+export type TranscriptSummary = {
+  bullets: string[];
+  actionItems: string[];
+  model: string;
+  createdAt: string;
+};
+
+export type HistorySegment = {
+  startSecs: number;
+  endSecs: number;
+  speaker?: string | null;
+  text: string;
 };
 
 const MINUTE_SECONDS = 60;
@@ -119,3 +179,12 @@ export function formatProvider(provider: string): string {
   const normalized = provider.trim();
   return normalized ? normalized.toUpperCase() : "UNKNOWN";
 }
+
+export function listSpeakers(entry: HistoryEntry): string[] {
+  const speakers = new Set<string>();
+  for (const segment of entry.segments ?? []) {
+    const speaker = segment.speaker?.trim();
+    if (speaker) speakers.add(speaker);
+  }
+  return [...speakers];
+}
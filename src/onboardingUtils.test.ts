@@ -27,6 +27,7 @@ describe("onboardingUtils", () => {
     expect(practiceStatusLabel("listening")).toBe("Recording");
     expect(practiceStatusLabel("transcribing")).toBe("Transcribing");
     expect(practiceStatusLabel("error")).toBe("Error");
+    expect(practiceStatusLabel("nothing_heard")).toBe("Nothing heard");
   });
 
   it("always shows the API key input when API key auth is selected", () => {
@@ -1,4 +1,9 @@
-export type OnboardingPracticeStatus = "idle" | "listening" | "transcribing" | "error";
+export type OnboardingPracticeStatus =
+  | "idle"
+  | "listening"
+  | "transcribing"
+  | "error"
+  | "nothing_heard";
 export type OnboardingAuthMethod = "oauth" | "api_key";
 export type OnboardingAuthStatus = { accountId: string } | null;
 
@@ -36,6 +41,8 @@ export function practiceStatusLabel(status: OnboardingPracticeStatus): string {
       return "Transcribing";
     case "error":
       return "Error";
+    case "nothing_heard":
+      return "Nothing heard";
     default:
       return "Ready";
   }
@@ -19,6 +19,11 @@ export default defineConfig(async () => ({
       input: {
         main: path.resolve(__dirname, "index.html"),
         overlay: path.resolve(__dirname, "overlay.html"),
+        meeting: path.resolve(__dirname, "meeting.html"),
+        popover: path.resolve(__dirname, "popover.html"),
+        picker: path.resolve(__dirname, "picker.html"),
+        palette: path.resolve(__dirname, "palette.html"),
+        history: path.resolve(__dirname, "history.html"),
       },
     },
   },